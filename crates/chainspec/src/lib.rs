@@ -18,6 +18,8 @@ pub use constants::*;
 mod api;
 /// The chain info module.
 mod info;
+/// Chain ID and hardfork overrides.
+mod overrides;
 /// The chain spec module.
 mod spec;
 
@@ -28,6 +30,7 @@ pub use reth_ethereum_forks::*;
 pub use alloy_evm::EvmLimitParams;
 pub use api::EthChainSpec;
 pub use info::ChainInfo;
+pub use overrides::{ChainSpecOverrideError, ChainSpecOverrides};
 #[cfg(any(test, feature = "test-utils"))]
 pub use spec::test_fork_ids;
 pub use spec::{
@@ -0,0 +1,174 @@
+use crate::{make_genesis_header, ChainSpec};
+use alloc::vec::Vec;
+use alloy_chains::Chain;
+use reth_ethereum_forks::{EthereumHardfork, ForkCondition};
+
+/// Overrides for the chain ID and hardfork schedule of an existing [`ChainSpec`].
+///
+/// Used to test upcoming hardforks against a built-in chain, e.g. as a shadow fork, without
+/// writing a custom genesis file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainSpecOverrides {
+    /// Replaces the chain ID of the chain spec.
+    pub chain_id: Option<u64>,
+    /// Replaces the activation condition of the given hardforks.
+    pub hardforks: Vec<(EthereumHardfork, ForkCondition)>,
+}
+
+impl ChainSpecOverrides {
+    /// Returns `true` if no overrides are configured.
+    pub const fn is_empty(&self) -> bool {
+        self.chain_id.is_none() && self.hardforks.is_empty()
+    }
+}
+
+/// Errors that can occur when applying [`ChainSpecOverrides`] to a [`ChainSpec`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ChainSpecOverrideError {
+    /// Pre-merge hardforks activate by block number, post-merge hardforks by timestamp.
+    #[error("hardfork {fork} can't be activated by {condition:?}")]
+    InvalidCondition {
+        /// The overridden hardfork.
+        fork: EthereumHardfork,
+        /// The rejected activation condition.
+        condition: ForkCondition,
+    },
+    /// The override would change the genesis header and therefore the genesis hash.
+    #[error("overriding hardfork {0} changes the genesis block")]
+    GenesisChanged(EthereumHardfork),
+}
+
+impl ChainSpec {
+    /// Applies the given [`ChainSpecOverrides`] to this chain spec.
+    ///
+    /// Fork IDs, fork filters and the hardfork checks used by the engine API are all derived from
+    /// the hardfork schedule, so they reflect the overrides without further changes. Rescheduled
+    /// BPO hardforks keep their blob parameters.
+    pub fn with_overrides(
+        mut self,
+        overrides: &ChainSpecOverrides,
+    ) -> Result<Self, ChainSpecOverrideError> {
+        if let Some(chain_id) = overrides.chain_id {
+            self.chain = Chain::from_id(chain_id);
+            self.genesis.config.chain_id = chain_id;
+        }
+
+        for &(fork, condition) in &overrides.hardforks {
+            let valid = match condition {
+                ForkCondition::Block(_) => fork < EthereumHardfork::Paris,
+                ForkCondition::Timestamp(_) => fork > EthereumHardfork::Paris,
+                ForkCondition::Never => true,
+                ForkCondition::TTD { .. } => false,
+            };
+            if !valid {
+                return Err(ChainSpecOverrideError::InvalidCondition { fork, condition })
+            }
+
+            if EthereumHardfork::bpo_variants().contains(&fork) &&
+                let ForkCondition::Timestamp(previous) = self.hardforks.fork(fork)
+            {
+                let new_timestamp = match condition {
+                    ForkCondition::Timestamp(timestamp) => Some(timestamp),
+                    _ => None,
+                };
+                self.blob_params.scheduled.retain_mut(|(timestamp, _)| {
+                    if *timestamp != previous {
+                        return true
+                    }
+                    new_timestamp.inspect(|new| *timestamp = *new).is_some()
+                });
+                self.blob_params.scheduled.sort_by_key(|(timestamp, _)| *timestamp);
+            }
+
+            self.hardforks.insert(fork, condition);
+
+            if make_genesis_header(&self.genesis, &self.hardforks) != *self.genesis_header.header()
+            {
+                return Err(ChainSpecOverrideError::GenesisChanged(fork))
+            }
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EthChainSpec, EthereumHardforks, MAINNET};
+    use alloy_eips::eip7840::BlobParams;
+    use reth_ethereum_forks::Head;
+
+    #[test]
+    fn override_mainnet_fork_schedule() {
+        let amsterdam = 2_000_000_000;
+        let overrides = ChainSpecOverrides {
+            chain_id: Some(1337),
+            hardforks: vec![(EthereumHardfork::Amsterdam, ForkCondition::Timestamp(amsterdam))],
+        };
+        let spec = MAINNET.as_ref().clone().with_overrides(&overrides).unwrap();
+
+        assert_eq!(spec.chain_id(), 1337);
+        assert_eq!(spec.genesis_hash(), MAINNET.genesis_hash());
+        assert!(!spec.is_amsterdam_active_at_timestamp(amsterdam - 1));
+        assert!(spec.is_amsterdam_active_at_timestamp(amsterdam));
+
+        let head = Head { timestamp: amsterdam, ..Default::default() };
+        assert_ne!(spec.latest_fork_id(), MAINNET.latest_fork_id());
+        assert_eq!(spec.fork_id(&head), spec.latest_fork_id());
+    }
+
+    #[test]
+    fn override_block_hardfork() {
+        let gray_glacier = 15_100_000;
+        let overrides = ChainSpecOverrides {
+            hardforks: vec![(EthereumHardfork::GrayGlacier, ForkCondition::Block(gray_glacier))],
+            ..Default::default()
+        };
+        let spec = MAINNET.as_ref().clone().with_overrides(&overrides).unwrap();
+
+        assert_eq!(
+            spec.hardforks.fork(EthereumHardfork::GrayGlacier),
+            ForkCondition::Block(gray_glacier)
+        );
+        let head = Head { number: gray_glacier - 1, ..Default::default() };
+        assert_ne!(spec.fork_id(&head), MAINNET.fork_id(&head));
+    }
+
+    #[test]
+    fn reschedule_bpo_keeps_blob_params() {
+        let bpo1 = MAINNET.hardforks.fork(EthereumHardfork::Bpo1).as_timestamp().unwrap();
+        let overrides = ChainSpecOverrides {
+            hardforks: vec![(EthereumHardfork::Bpo1, ForkCondition::Timestamp(bpo1 + 100))],
+            ..Default::default()
+        };
+        let spec = MAINNET.as_ref().clone().with_overrides(&overrides).unwrap();
+
+        assert_eq!(spec.blob_params_at_timestamp(bpo1), Some(BlobParams::osaka()));
+        assert_eq!(spec.blob_params_at_timestamp(bpo1 + 100), Some(BlobParams::bpo1()));
+    }
+
+    #[test]
+    fn reject_invalid_overrides() {
+        let overrides = ChainSpecOverrides {
+            hardforks: vec![(EthereumHardfork::Cancun, ForkCondition::Block(1))],
+            ..Default::default()
+        };
+        assert_eq!(
+            MAINNET.as_ref().clone().with_overrides(&overrides),
+            Err(ChainSpecOverrideError::InvalidCondition {
+                fork: EthereumHardfork::Cancun,
+                condition: ForkCondition::Block(1)
+            })
+        );
+
+        let overrides = ChainSpecOverrides {
+            hardforks: vec![(EthereumHardfork::Shanghai, ForkCondition::Timestamp(0))],
+            ..Default::default()
+        };
+        assert_eq!(
+            MAINNET.as_ref().clone().with_overrides(&overrides),
+            Err(ChainSpecOverrideError::GenesisChanged(EthereumHardfork::Shanghai))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::{fs, path::PathBuf, sync::Arc};
 
 use clap::builder::TypedValueParser;
+use reth_chainspec::ChainSpecOverrides;
 
 #[derive(Debug, Clone)]
 struct Parser<C>(std::marker::PhantomData<C>);
@@ -56,6 +57,20 @@ pub trait ChainSpecParser: Clone + Send + Sync + 'static {
     /// chain spec.
     fn parse(s: &str) -> eyre::Result<Arc<Self::ChainSpec>>;
 
+    /// Applies the given chain ID and hardfork overrides to a parsed chain spec.
+    ///
+    /// By default, only an empty set of overrides is accepted.
+    fn apply_overrides(
+        spec: Arc<Self::ChainSpec>,
+        overrides: &ChainSpecOverrides,
+    ) -> eyre::Result<Arc<Self::ChainSpec>> {
+        eyre::ensure!(
+            overrides.is_empty(),
+            "chain spec overrides are not supported for this chain"
+        );
+        Ok(spec)
+    }
+
     /// Produces a [`TypedValueParser`] for this chain spec parser.
     fn parser() -> impl TypedValueParser<Value = Arc<Self::ChainSpec>> {
         Parser(std::marker::PhantomData::<Self>)
@@ -16,10 +16,11 @@ use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_network::NetworkEventListenerProvider;
 use reth_node_api::FullNodeTypesAdapter;
 use reth_node_builder::{
-    Node, NodeComponents, NodeComponentsBuilder, NodeTypes, NodeTypesWithDBAdapter,
+    setup::build_cold_storage_config, Node, NodeComponents, NodeComponentsBuilder, NodeTypes,
+    NodeTypesWithDBAdapter,
 };
 use reth_node_core::{
-    args::{DatabaseArgs, DatadirArgs, StaticFilesArgs, StorageArgs},
+    args::{ChainOverrideArgs, DatabaseArgs, DatadirArgs, StaticFilesArgs, StorageArgs},
     dirs::{ChainPath, DataDirPath},
 };
 use reth_provider::{
@@ -68,6 +69,10 @@ pub struct EnvironmentArgs<C: ChainSpecParser> {
     #[command(flatten)]
     pub static_files: StaticFilesArgs,
 
+    /// All chain spec override arguments with --override prefix
+    #[command(flatten)]
+    pub overrides: ChainOverrideArgs,
+
     /// Storage mode configuration (v2 vs v1/legacy)
     #[command(flatten)]
     pub storage: StorageArgs,
@@ -87,6 +92,14 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         }
     }
 
+    /// Returns the chain spec with the chain ID and hardfork overrides applied.
+    ///
+    /// The datadir is still resolved from the chain ID of [`Self::chain`], so that commands keep
+    /// operating on the datadir of the overridden chain.
+    pub fn chain_spec(&self) -> eyre::Result<Arc<C::ChainSpec>> {
+        apply_chain_overrides::<C>(self.chain.clone(), &self.overrides)
+    }
+
     /// Initializes environment according to [`AccessRights`] and returns an instance of
     /// [`Environment`].
     ///
@@ -128,22 +141,31 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
 
         info!(target: "reth::cli", ?db_path, ?sf_path, "Opening storage");
         let genesis_block_number = self.chain.genesis().number.unwrap_or_default();
+        let cold_storage = config
+            .static_files
+            .cold
+            .as_ref()
+            .map(|cold| build_cold_storage_config(cold, &sf_path))
+            .transpose()?;
         let (db, sfp) = match access {
             AccessRights::RW => (
                 init_db(db_path, self.db.database_args())?,
                 StaticFileProviderBuilder::read_write(sf_path)
                     .with_metrics()
-                    .with_genesis_block_number(genesis_block_number)
-                    .build()?,
+                    .with_genesis_block_number(genesis_block_number),
             ),
             AccessRights::RO | AccessRights::RoInconsistent => (
                 open_db_read_only(&db_path, self.db.database_args())?,
                 StaticFileProviderBuilder::read_only(sf_path)
                     .with_metrics()
-                    .with_genesis_block_number(genesis_block_number)
-                    .build()?,
+                    .with_genesis_block_number(genesis_block_number),
             ),
         };
+        let sfp = match cold_storage {
+            Some(cold_storage) => sfp.with_cold_storage(cold_storage),
+            None => sfp,
+        }
+        .build()?;
         let rocksdb_provider = if !access.is_read_write() && !RocksDBProvider::exists(&rocksdb_path)
         {
             // RocksDB database doesn't exist yet (e.g. datadir restored from a snapshot
@@ -169,8 +191,15 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
             builder.build()?
         };
 
-        let provider_factory =
-            self.create_provider_factory(&config, db, sfp, rocksdb_provider, access, runtime)?;
+        let provider_factory = self.create_provider_factory(
+            &config,
+            self.chain_spec()?,
+            db,
+            sfp,
+            rocksdb_provider,
+            access,
+            runtime,
+        )?;
         if access.is_read_write() {
             debug!(target: "reth::cli", chain=%self.chain.chain(), genesis=?self.chain.genesis_hash(), "Initializing genesis");
             init_genesis_with_settings(&provider_factory, self.storage_settings())?;
@@ -187,6 +216,7 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
     fn create_provider_factory<N: CliNodeTypes>(
         &self,
         config: &Config,
+        chain: Arc<N::ChainSpec>,
         db: DatabaseEnv,
         static_file_provider: StaticFileProvider<N::Primitives>,
         rocksdb_provider: RocksDBProvider,
@@ -203,7 +233,7 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         ));
         let factory = ProviderFactory::<NodeTypesWithDBAdapter<N, DatabaseEnv>>::new(
             db,
-            self.chain.clone(),
+            chain,
             static_file_provider,
             rocksdb_provider,
             runtime,
@@ -261,6 +291,22 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
     }
 }
 
+/// Applies the chain ID and hardfork overrides to a chain spec parsed by `C`.
+///
+/// Shared by [`EnvironmentArgs`] and the `node` command, so that every command sees the same
+/// chain for a datadir.
+pub fn apply_chain_overrides<C: ChainSpecParser>(
+    chain: Arc<C::ChainSpec>,
+    overrides: &ChainOverrideArgs,
+) -> eyre::Result<Arc<C::ChainSpec>> {
+    let overrides = overrides.overrides();
+    if overrides.is_empty() {
+        return Ok(chain)
+    }
+    info!(target: "reth::cli", ?overrides, "Applying chain spec overrides");
+    C::apply_overrides(chain, &overrides)
+}
+
 /// Environment built from [`EnvironmentArgs`].
 #[derive(Debug)]
 pub struct Environment<N: NodeTypes> {
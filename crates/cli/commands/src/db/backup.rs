@@ -0,0 +1,237 @@
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use clap::Parser;
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::{version::get_db_version, DatabaseEnv};
+use reth_db_common::{
+    backup::{create_backup, BackupManifest, BackupRequest},
+    DbTool,
+};
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_provider::{DBProvider, StageCheckpointReader};
+use reth_stages_types::StageId;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Creates a consistent backup of the MDBX database, `RocksDB` and static files.
+///
+/// If a node is running on the datadir, the backup is taken by the node itself, which pauses its
+/// persistence service until the MDBX database, `RocksDB` and static files are snapshotted.
+/// Otherwise the datadir is opened with write access, which fails if another process holds it,
+/// and the backup is taken directly.
+#[derive(Parser, Debug)]
+pub struct BackupCommand {
+    /// Destination directory for the backup. Must not exist yet.
+    dest: PathBuf,
+
+    /// Compact the MDBX database while copying (reclaims free space).
+    #[arg(short, long)]
+    compact: bool,
+
+    /// Copy sealed static files instead of hardlinking them.
+    ///
+    /// Hardlinks are only used for static files that are no longer appended to. They are
+    /// required to be on the same filesystem as the datadir and fall back to a copy otherwise.
+    #[arg(long)]
+    no_hardlinks: bool,
+}
+
+impl BackupCommand {
+    /// Returns the request for this backup. The destination is made absolute, since it may be
+    /// resolved by a node running in another working directory.
+    fn request(&self) -> eyre::Result<BackupRequest> {
+        Ok(BackupRequest {
+            dest: std::path::absolute(&self.dest)?,
+            compact: self.compact,
+            hardlink: !self.no_hardlinks,
+        })
+    }
+
+    /// Asks the node running on the datadir to take the backup.
+    ///
+    /// Returns `false` if no node is running.
+    pub fn execute_on_node(&self, data_dir: &ChainPath<DataDirPath>) -> eyre::Result<bool> {
+        #[cfg(unix)]
+        {
+            let request = self.request()?;
+            let Some(manifest) =
+                reth_db_common::backup::request_backup(&data_dir.backup_socket(), &request)?
+            else {
+                return Ok(false)
+            };
+            info!(target: "reth::cli", block_number = manifest.block_number, dest = ?request.dest, "Backup written by running node");
+            Ok(true)
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = data_dir;
+            Ok(false)
+        }
+    }
+
+    /// Execute `db backup` command against a datadir opened with write access.
+    pub fn execute<N: CliNodeTypes>(
+        self,
+        tool: &DbTool<NodeTypesWithDBAdapter<N, DatabaseEnv>>,
+    ) -> eyre::Result<()> {
+        let request = self.request()?;
+        let manifest = create_backup(&tool.provider_factory, &request)?;
+        info!(target: "reth::cli", block_number = manifest.block_number, dest = ?request.dest, "Backup written");
+        Ok(())
+    }
+}
+
+/// Restores a backup created by `reth db backup` into the datadir.
+///
+/// After copying, the storage consistency checks are run against the restored datadir. Static file
+/// and `RocksDB` data written past the MDBX snapshot is pruned, and the restore fails if any
+/// storage is found to lag behind it.
+#[derive(Parser, Debug)]
+pub struct RestoreCommand {
+    /// Directory containing the backup.
+    src: PathBuf,
+}
+
+impl RestoreCommand {
+    /// Execute `db restore` command
+    pub fn execute<N: CliNodeTypes, C: ChainSpecParser<ChainSpec = N::ChainSpec>>(
+        self,
+        env: &EnvironmentArgs<C>,
+        data_dir: &ChainPath<DataDirPath>,
+        runtime: reth_tasks::Runtime,
+    ) -> eyre::Result<()> {
+        let manifest = BackupManifest::load(&self.src)?;
+
+        eyre::ensure!(
+            manifest.genesis_hash == env.chain.genesis_hash(),
+            "Backup belongs to a chain with genesis {}, expected {}",
+            manifest.genesis_hash,
+            env.chain.genesis_hash()
+        );
+        eyre::ensure!(
+            get_db_version(self.src.join("db"))? == manifest.db_version,
+            "Backup database version does not match its manifest"
+        );
+
+        let db_path = data_dir.db();
+        for path in [&db_path, &data_dir.static_files(), &data_dir.rocksdb()] {
+            eyre::ensure!(
+                is_empty_dir(path)?,
+                "{path:?} is not empty, refusing to restore into an existing datadir"
+            );
+        }
+
+        info!(target: "reth::cli", src = ?self.src, block_number = manifest.block_number, "Restoring backup");
+        copy_dir_all(&self.src.join("db"), &db_path)?;
+        copy_dir_all(&self.src.join("static_files"), &data_dir.static_files())?;
+        copy_dir_all(&self.src.join("rocksdb"), &data_dir.rocksdb())?;
+
+        let Environment { provider_factory, .. } = env.init::<N>(AccessRights::RW, runtime)?;
+
+        info!(target: "reth::cli", "Verifying restored storage");
+        let (rocksdb_unwind, static_file_unwind) = provider_factory.check_consistency()?;
+        eyre::ensure!(
+            rocksdb_unwind.is_none() && static_file_unwind.is_none(),
+            "Restored storage is inconsistent: RocksDB unwind target {rocksdb_unwind:?}, static files unwind target {static_file_unwind:?}"
+        );
+
+        let block_number = provider_factory
+            .provider()?
+            .get_stage_checkpoint(StageId::Finish)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+        eyre::ensure!(
+            block_number == manifest.block_number,
+            "Restored database is at block {block_number}, expected {}",
+            manifest.block_number
+        );
+
+        info!(target: "reth::cli", block_number, datadir = ?data_dir.data_dir(), "Backup restored");
+        Ok(())
+    }
+}
+
+/// Returns `true` if `path` doesn't exist or is an empty directory.
+fn is_empty_dir(path: &Path) -> eyre::Result<bool> {
+    if !path.exists() {
+        return Ok(true)
+    }
+    Ok(reth_fs_util::read_dir(path)?.next().is_none())
+}
+
+/// Recursively copies the contents of `src` into `dest`.
+fn copy_dir_all(src: &Path, dest: &Path) -> eyre::Result<()> {
+    reth_fs_util::create_dir_all(dest)?;
+    for entry in reth_fs_util::read_dir(src)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Command;
+    use reth_cli_runner::CliContext;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_node_ethereum::EthereumNode;
+    use reth_provider::BlockHashReader;
+
+    fn db_command(datadir: &Path, args: &[&Path]) -> Command<EthereumChainSpecParser> {
+        let args = ["reth", "--datadir", datadir.to_str().unwrap(), "--chain", "dev"]
+            .into_iter()
+            .chain(args.iter().map(|arg| arg.to_str().unwrap()));
+        Command::try_parse_from(args).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn backup_restore_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source");
+        let backup = dir.path().join("backup");
+        let restored = dir.path().join("restored");
+        let runtime = reth_tasks::Runtime::test();
+        let ctx = || CliContext { task_executor: runtime.clone() };
+
+        // initialize the source datadir with the genesis block
+        let Environment { provider_factory, .. } = db_command(&source, &[Path::new("stats")])
+            .env
+            .init::<EthereumNode>(AccessRights::RW, runtime.clone())
+            .unwrap();
+        let genesis_hash = provider_factory.block_hash(0).unwrap();
+        assert!(genesis_hash.is_some());
+        drop(provider_factory);
+
+        db_command(&source, &[Path::new("backup"), &backup])
+            .execute::<EthereumNode>(ctx())
+            .await
+            .unwrap();
+        let manifest = BackupManifest::load(&backup).unwrap();
+        assert_eq!(manifest.block_number, 0);
+        assert!(!manifest.static_files.is_empty());
+
+        // the source datadir is not empty
+        assert!(db_command(&source, &[Path::new("restore"), &backup])
+            .execute::<EthereumNode>(ctx())
+            .await
+            .is_err());
+
+        db_command(&restored, &[Path::new("restore"), &backup])
+            .execute::<EthereumNode>(ctx())
+            .await
+            .unwrap();
+
+        let Environment { provider_factory, .. } = db_command(&restored, &[Path::new("stats")])
+            .env
+            .init::<EthereumNode>(AccessRights::RO, runtime)
+            .unwrap();
+        assert_eq!(provider_factory.block_hash(0).unwrap(), genesis_hash);
+    }
+}
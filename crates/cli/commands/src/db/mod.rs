@@ -10,6 +10,7 @@ use std::{
     sync::Arc,
 };
 mod account_storage;
+mod backup;
 mod checksum;
 mod clear;
 mod copy;
@@ -48,6 +49,10 @@ pub enum Subcommands {
     Checksum(checksum::Command),
     /// Copies the MDBX database to a new location (bundled mdbx_copy)
     Copy(copy::Command),
+    /// Creates a consistent backup of the MDBX database, RocksDB and static files
+    Backup(backup::BackupCommand),
+    /// Restores a backup created by `reth db backup` into an empty datadir
+    Restore(backup::RestoreCommand),
     /// Create a diff between two database tables or two entire databases.
     Diff(diff::Command),
     /// Gets the content of a table for the given key
@@ -106,6 +111,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let static_files_path = data_dir.static_files();
         let exex_wal_path = data_dir.exex_wal();
 
+        // restoring populates an empty datadir, so it can't rely on the checks below
+        if let Subcommands::Restore(command) = self.command {
+            return command.execute::<N, C>(&self.env, &data_dir, ctx.task_executor)
+        }
+
         // ensure the provided datadir exists
         eyre::ensure!(
             data_dir.data_dir().is_dir(),
@@ -143,6 +153,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(tool.provider_factory.db_ref())?;
                 });
             }
+            Subcommands::Backup(command) => {
+                // a running node takes the backup itself while its persistence is paused
+                if !command.execute_on_node(&data_dir)? {
+                    db_exec!(self.env, tool, N, AccessRights::RW, {
+                        command.execute(&tool)?;
+                    });
+                }
+            }
+            Subcommands::Restore(_) => unreachable!("handled above"),
             Subcommands::Diff(command) => {
                 db_exec!(self.env, tool, N, AccessRights::RO, {
                     command.execute(&tool)?;
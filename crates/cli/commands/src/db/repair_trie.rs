@@ -71,7 +71,7 @@ impl Command {
 
             let handle = task_executor.spawn_critical_task("metrics server", async move {
                 let config = MetricServerConfig::new(
-                    listen_addr,
+                    Some(listen_addr),
                     VersionInfo {
                         version: version_metadata().cargo_pkg_version.as_ref(),
                         build_timestamp: version_metadata().vergen_build_timestamp.as_ref(),
@@ -179,6 +179,7 @@ pub(crate) fn config_for_selections(
             account_change_sets: blocks_per_file(SnapshotComponentType::AccountChangesets),
             storage_change_sets: blocks_per_file(SnapshotComponentType::StorageChangesets),
         },
+        ..Default::default()
     };
 
     if is_archive || matches!(preset, Some(SelectionPreset::Archive)) {
@@ -51,7 +51,9 @@ pub struct ImportArgs {
     url: Option<Url>,
 }
 
-trait TryFromChain {
+/// Resolves the default remote host of ERA files for a chain.
+pub(crate) trait TryFromChain {
+    /// Returns the URL of the known ERA host of the chain.
     fn try_to_url(&self) -> eyre::Result<Url>;
 }
 
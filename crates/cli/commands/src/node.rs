@@ -1,6 +1,6 @@
 //! Main node command for launching a node
 
-use crate::launcher::Launcher;
+use crate::{common::apply_chain_overrides, launcher::Launcher};
 use clap::{value_parser, Args, Parser};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
@@ -9,9 +9,9 @@ use reth_db::init_db;
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, JitArgs, MetricArgs,
-        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, StaticFilesArgs, StorageArgs,
-        TxPoolArgs,
+        ChainOverrideArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs,
+        JitArgs, MetricArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs, ReplicaArgs,
+        RpcServerArgs, ShadowForkArgs, StaticFilesArgs, StorageArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
@@ -123,6 +123,18 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "JIT")]
     pub jit: JitArgs,
 
+    /// All replica notification related arguments with --replica prefix
+    #[command(flatten, next_help_heading = "Replica")]
+    pub replica: ReplicaArgs,
+
+    /// All chain spec override arguments with --override prefix
+    #[command(flatten, next_help_heading = "Chain overrides")]
+    pub overrides: ChainOverrideArgs,
+
+    /// All shadow fork related arguments with --shadow-fork prefix
+    #[command(flatten, next_help_heading = "Chain overrides")]
+    pub shadow_fork: ShadowForkArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -180,11 +192,23 @@ where
             static_files,
             storage,
             jit,
+            replica,
+            overrides,
+            shadow_fork,
             ext,
         } = self;
 
         engine.validate()?;
 
+        let mut datadir = datadir;
+        if overrides.chain_id.is_some() {
+            // Keep using the datadir of the original chain, the default one is derived from the
+            // chain ID.
+            datadir.datadir =
+                datadir.clone().resolve_datadir(chain.chain()).data_dir().to_path_buf().into();
+        }
+        let chain = apply_chain_overrides::<C>(chain, &overrides)?;
+
         // set up node config
         let mut node_config = NodeConfig {
             datadir,
@@ -205,6 +229,8 @@ where
             static_files,
             storage,
             jit,
+            replica,
+            shadow_fork,
         };
 
         let data_dir = node_config.datadir();
@@ -1,7 +1,7 @@
 //! Command that runs pruning.
-use crate::common::{AccessRights, CliNodeTypes, EnvironmentArgs};
-use clap::Parser;
-use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
+use crate::common::{AccessRights, CliNodeComponents, CliNodeTypes, EnvironmentArgs};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use reth_cli_util::cancellation::CancellationToken;
@@ -18,9 +18,15 @@ use reth_static_file::StaticFileProducer;
 use std::sync::Arc;
 use tracing::info;
 
+pub mod restore;
+
 /// Prunes according to the configuration
 #[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct PruneCommand<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Option<Subcommands<C>>,
+
     #[command(flatten)]
     env: EnvironmentArgs<C>,
 
@@ -29,12 +35,29 @@ pub struct PruneCommand<C: ChainSpecParser> {
     metrics: MetricArgs,
 }
 
-impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneCommand<C> {
+/// `reth prune` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Restore previously pruned data of a segment from ERA files, peers or by re-executing
+    /// blocks
+    Restore(restore::RestoreCommand<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> PruneCommand<C> {
     /// Execute the `prune` command
-    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(
+    pub async fn execute<N, Comp>(
         self,
         ctx: CliContext,
-    ) -> eyre::Result<()> {
+        components: impl FnOnce(Arc<C::ChainSpec>) -> Comp,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+    {
+        if let Some(Subcommands::Restore(command)) = self.command {
+            return command.execute::<N, _>(ctx, components).await
+        }
+
         let env = self.env.init::<N>(AccessRights::RW, ctx.task_executor.clone())?;
         let provider_factory = env.provider_factory;
         let config = env.config.prune;
@@ -42,7 +65,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneComma
 
         if let Some(listen_addr) = self.metrics.prometheus {
             let config = MetricServerConfig::new(
-                listen_addr,
+                Some(listen_addr),
                 VersionInfo {
                     version: version_metadata().cargo_pkg_version.as_ref(),
                     build_timestamp: version_metadata().vergen_build_timestamp.as_ref(),
@@ -134,6 +157,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> PruneComma
 impl<C: ChainSpecParser> PruneCommand<C> {
     /// Returns the underlying chain being used to run this command
     pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
-        Some(&self.env.chain)
+        match self.command {
+            Some(Subcommands::Restore(ref command)) => command.chain_spec(),
+            None => Some(&self.env.chain),
+        }
     }
 }
@@ -0,0 +1,772 @@
+//! Command that restores previously pruned data.
+use crate::{
+    common::{AccessRights, CliNodeComponents, CliNodeTypes, Environment, EnvironmentArgs},
+    import_era::TryFromChain,
+};
+use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, TxReceipt};
+use alloy_primitives::{BlockNumber, TxNumber, B256};
+use clap::{Args, Parser, ValueEnum};
+use eyre::{bail, eyre, WrapErr};
+use futures::{Future, FutureExt, Stream, StreamExt};
+use reqwest::{Client, Url};
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use reth_cli_util::get_secret_key;
+use reth_consensus::Consensus;
+use reth_era::common::file_ops::EraFileType;
+use reth_era_downloader::{read_dir, EraClient, EraMeta, EraStream, EraStreamConfig};
+use reth_era_utils::{self as era, EraBlockReader, EraReceiptReader};
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_fs_util as fs;
+use reth_network::BlockDownloaderProvider;
+use reth_network_p2p::{
+    download::DownloadClient, error::PeerRequestResult, BodiesClient, ReceiptsClient,
+};
+use reth_node_api::{BlockTy, BodyTy, HeaderTy, ReceiptTy, TxTy};
+use reth_node_core::{
+    args::NetworkArgs,
+    dirs::{ChainPath, DataDirPath},
+};
+use reth_primitives_traits::{
+    Block, BlockBody, NodePrimitives, Receipt, RecoveredBlock, SealedHeader, SignerRecoverable,
+};
+use reth_provider::{
+    providers::StaticFileProviderRW, BlockBodyIndicesProvider, BlockReader, ChainSpecProvider,
+    DBProvider, DatabaseProviderFactory, EitherWriter, EitherWriterDestination, HeaderProvider,
+    ProviderError, ProviderResult, PruneCheckpointReader, PruneCheckpointWriter,
+    StateProviderFactory, StaticFileProviderFactory, TransactionsProvider,
+};
+use reth_prune_types::{PruneCheckpoint, PruneSegment};
+use reth_revm::database::StateProviderDatabase;
+use reth_static_file_types::{SegmentRangeInclusive, StaticFileSegment};
+use std::{path::PathBuf, pin::Pin, sync::Arc, time::Duration};
+use tracing::{info, warn};
+
+/// Number of blocks requested from a peer at once.
+const PEER_BATCH_SIZE: u64 = 64;
+
+/// Number of consecutive failed peer requests after which restoring is aborted.
+const MAX_PEER_RETRIES: usize = 30;
+
+/// Delay between failed peer requests.
+const PEER_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Size hint of the executor after which it's recreated to bound memory usage.
+const EXECUTOR_SIZE_HINT_LIMIT: usize = 5_000_000;
+
+/// ERA files of execution blocks, either read from a local directory or downloaded.
+type EraFiles = Pin<Box<dyn Stream<Item = eyre::Result<Box<dyn EraMeta + Send>>> + Send>>;
+
+/// `reth prune restore` command
+///
+/// Restores previously pruned data of a segment by refilling its static files down to `--from`,
+/// then lowers the prune checkpoint of the segment accordingly.
+#[derive(Debug, Parser)]
+pub struct RestoreCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The pruned segment to restore.
+    #[arg(long, value_enum)]
+    segment: RestoreSegment,
+
+    /// The lowest block to restore.
+    ///
+    /// Static files are restored whole, so this is rounded down to the first block of the static
+    /// file containing it.
+    #[arg(long, value_name = "BLOCK", verbatim_doc_comment)]
+    from: BlockNumber,
+
+    /// Where to restore the data from.
+    ///
+    /// Required for bodies and receipts. Senders are always recovered from the stored
+    /// transactions.
+    #[arg(
+        long,
+        value_enum,
+        required_if_eq_any([("segment", "bodies"), ("segment", "receipts")]),
+        verbatim_doc_comment
+    )]
+    source: Option<RestoreSource>,
+
+    #[command(flatten)]
+    era: RestoreEraArgs,
+
+    #[command(flatten)]
+    network: NetworkArgs,
+}
+
+/// Segments of pruned data that can be restored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum RestoreSegment {
+    /// Block bodies, i.e. transactions.
+    Bodies,
+    /// Transaction receipts.
+    Receipts,
+    /// Transaction senders.
+    SenderRecovery,
+}
+
+impl RestoreSegment {
+    /// Returns the static file segment the data is restored into.
+    const fn static_file_segment(self) -> StaticFileSegment {
+        match self {
+            Self::Bodies => StaticFileSegment::Transactions,
+            Self::Receipts => StaticFileSegment::Receipts,
+            Self::SenderRecovery => StaticFileSegment::TransactionSenders,
+        }
+    }
+
+    /// Returns the prune segment whose checkpoint is lowered after restoring.
+    const fn prune_segment(self) -> PruneSegment {
+        match self {
+            Self::Bodies => PruneSegment::Bodies,
+            Self::Receipts => PruneSegment::Receipts,
+            Self::SenderRecovery => PruneSegment::SenderRecovery,
+        }
+    }
+}
+
+/// Sources to restore pruned data from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum RestoreSource {
+    /// Local or remote ERA1 and ERE files, see `--era.path` and `--era.url`.
+    Era,
+    /// Peers of the network. Receipts are only served by peers speaking eth/69 or later.
+    Peers,
+    /// Re-execute the blocks, which requires their state history. Receipts only.
+    Reexecute,
+}
+
+/// ERA files to restore from.
+#[derive(Debug, Args)]
+#[group(required = false, multiple = false)]
+pub struct RestoreEraArgs {
+    /// The path to a local directory of ERA1 or ERE files.
+    #[arg(long = "era.path", value_name = "ERA_PATH")]
+    path: Option<PathBuf>,
+
+    /// The URL to a remote host of ERA1 or ERE files.
+    ///
+    /// Defaults to the known host of the chain, if any.
+    #[arg(long = "era.url", value_name = "ERA_URL", verbatim_doc_comment)]
+    url: Option<Url>,
+}
+
+impl RestoreEraArgs {
+    /// Streams the ERA files of execution blocks, starting with the file containing `start`.
+    ///
+    /// Returns the type of the files along with the stream.
+    fn files(
+        &self,
+        chain: &impl EthChainSpec,
+        data_dir: &ChainPath<DataDirPath>,
+        start: BlockNumber,
+    ) -> eyre::Result<(EraFileType, EraFiles)> {
+        let (era_type, files): (_, EraFiles) = if let Some(path) = &self.path {
+            let era_type = EraFileType::from_dir(path)?.ok_or_else(|| {
+                eyre!("No ERA1 (.era1) or ERE (.ere, .erae) files found in {}", path.display())
+            })?;
+            let files = read_dir(path.clone(), start)?;
+            (era_type, Box::pin(files.map(|meta| meta.map(|meta| Box::new(meta) as _))))
+        } else {
+            let url = match &self.url {
+                Some(url) => url.clone(),
+                None => chain.chain().kind().try_to_url()?,
+            };
+            let era_type = EraFileType::from_url(url.as_str());
+
+            let folder = data_dir.data_dir().join("era");
+            fs::create_dir_all(&folder)?;
+
+            let client = EraClient::new(Client::new(), url, folder).with_era_type(era_type);
+            let files = EraStream::new(client, EraStreamConfig::default().start_from(start));
+            (era_type, Box::pin(files.map(|meta| meta.map(|meta| Box::new(meta) as _))))
+        };
+
+        // Consensus `.era` files are slot-indexed and can't be streamed from a block.
+        if era_type == EraFileType::Era {
+            bail!(
+                "Restoring from consensus ERA (.era) files is not supported, use ERA1 or ERE files"
+            )
+        }
+
+        Ok((era_type, files))
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>>
+    RestoreCommand<C>
+{
+    /// Execute the `prune restore` command
+    pub async fn execute<N, Comp>(
+        self,
+        ctx: CliContext,
+        components: impl FnOnce(Arc<C::ChainSpec>) -> Comp,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        Comp: CliNodeComponents<N>,
+    {
+        match (self.segment, self.source) {
+            (RestoreSegment::SenderRecovery, Some(_)) => {
+                bail!("Senders are recovered from the stored transactions, `--source` is not supported")
+            }
+            (RestoreSegment::Bodies, Some(RestoreSource::Reexecute)) => {
+                bail!("Bodies can't be restored by re-executing blocks")
+            }
+            _ => {}
+        }
+
+        let runtime = ctx.task_executor.clone();
+        let Environment { provider_factory, config, data_dir } =
+            self.env.init::<N>(AccessRights::RW, ctx.task_executor)?;
+        let components = components(provider_factory.chain_spec());
+        let static_file_provider = provider_factory.static_file_provider();
+        let segment = self.segment.static_file_segment();
+
+        // Only static files are restored, data written to the database has to be resynced.
+        {
+            let provider = provider_factory.provider()?;
+            let destination = match self.segment {
+                RestoreSegment::Bodies => EitherWriterDestination::StaticFile,
+                RestoreSegment::Receipts => EitherWriter::receipts_destination(&provider),
+                RestoreSegment::SenderRecovery => EitherWriterDestination::senders(&provider),
+            };
+            if !destination.is_static_file() {
+                bail!(
+                    "{segment} are not stored in static files with the current storage settings, \
+                     restoring them is not supported"
+                )
+            }
+        }
+
+        let target = static_file_provider.find_fixed_range(segment, self.from).start();
+
+        let fetch_client = if self.source == Some(RestoreSource::Peers) {
+            let network_secret_path =
+                self.network.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret());
+            let p2p_secret_key = get_secret_key(&network_secret_path)?;
+
+            let network = self
+                .network
+                .network_config::<N::NetworkPrimitives>(
+                    &config,
+                    provider_factory.chain_spec(),
+                    p2p_secret_key,
+                    data_dir.known_peers(),
+                    runtime,
+                )
+                .build(provider_factory.clone())
+                .start_network()
+                .await?;
+            Some(network.fetch_client().await?)
+        } else {
+            None
+        };
+
+        loop {
+            let Some(lowest) = static_file_provider.get_lowest_range_start(segment) else {
+                bail!("No {segment} static files found")
+            };
+            if lowest <= target {
+                break
+            }
+
+            let mut writer = static_file_provider.restore_writer(segment)?;
+            let range = writer.user_header().expected_block_range();
+            info!(target: "reth::cli", %segment, %range, source = ?self.source, "Restoring static file");
+
+            // Senders and re-executed receipts are derived from the stored bodies.
+            if (self.segment == RestoreSegment::SenderRecovery ||
+                self.source == Some(RestoreSource::Reexecute)) &&
+                static_file_provider
+                    .get_lowest_range_start(StaticFileSegment::Transactions)
+                    .is_none_or(|lowest| lowest > range.start())
+            {
+                bail!("Bodies of blocks {range} are pruned, restore them first")
+            }
+
+            let provider =
+                provider_factory.database_provider_ro()?.disable_long_read_transaction_safety();
+
+            match (self.segment, self.source) {
+                (RestoreSegment::Bodies | RestoreSegment::Receipts, Some(RestoreSource::Era)) => {
+                    let files = self.era.files(
+                        &*provider_factory.chain_spec(),
+                        &data_dir,
+                        range.start(),
+                    )?;
+                    restore_from_era::<N>(
+                        &mut writer,
+                        &provider,
+                        components.consensus(),
+                        self.segment,
+                        files,
+                        range,
+                    )
+                    .await?
+                }
+                (RestoreSegment::Bodies, Some(RestoreSource::Peers)) => {
+                    let client = fetch_client.as_ref().expect("network is started for peers");
+                    let consensus = components.consensus();
+                    restore_from_peers(
+                        &mut writer,
+                        &provider,
+                        client,
+                        range,
+                        |hashes| client.get_block_bodies(hashes),
+                        |header, body| validate_body::<BlockTy<N>>(consensus, header, body),
+                        |writer, header, body| {
+                            append_block(
+                                writer,
+                                &provider,
+                                header.number(),
+                                body.transactions(),
+                                StaticFileProviderRW::append_transaction,
+                            )
+                        },
+                    )
+                    .await?
+                }
+                (RestoreSegment::Receipts, Some(RestoreSource::Peers)) => {
+                    let client = fetch_client.as_ref().expect("network is started for peers");
+                    restore_from_peers(
+                        &mut writer,
+                        &provider,
+                        client,
+                        range,
+                        |hashes| {
+                            client.get_receipts(hashes).map(|response| {
+                                response.map(|response| response.map(|response| response.receipts))
+                            })
+                        },
+                        |header, receipts| validate_receipts(header.header(), receipts),
+                        |writer, header, receipts| {
+                            append_block(
+                                writer,
+                                &provider,
+                                header.number(),
+                                &receipts,
+                                StaticFileProviderRW::append_receipt,
+                            )
+                        },
+                    )
+                    .await?
+                }
+                (RestoreSegment::Receipts, Some(RestoreSource::Reexecute)) => {
+                    restore_receipts_by_execution::<N>(
+                        &mut writer,
+                        &provider,
+                        &provider_factory,
+                        components.evm_config(),
+                        provider_factory.chain_spec().genesis_header().number(),
+                        range,
+                    )?
+                }
+                (RestoreSegment::SenderRecovery, None) => {
+                    recover_senders::<N>(&mut writer, &provider, range)?
+                }
+                // Rejected above or when parsing the arguments.
+                (RestoreSegment::Bodies, Some(RestoreSource::Reexecute)) |
+                (RestoreSegment::SenderRecovery, Some(_)) |
+                (RestoreSegment::Bodies | RestoreSegment::Receipts, None) => unreachable!(),
+            }
+
+            writer.commit()?;
+            drop(provider);
+
+            lower_prune_checkpoint(&provider_factory, self.segment.prune_segment(), range.start())?;
+            info!(target: "reth::cli", %segment, %range, "Restored static file");
+        }
+
+        let mode = match self.segment {
+            RestoreSegment::Bodies => config.prune.segments.bodies_history,
+            RestoreSegment::Receipts => config.prune.segments.receipts,
+            RestoreSegment::SenderRecovery => config.prune.segments.sender_recovery,
+        };
+        if let Some(mode) = mode {
+            warn!(
+                target: "reth::cli",
+                %segment,
+                ?mode,
+                "Restored data is pruned again on the next run unless the configured prune mode is widened"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> RestoreCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Restores the bodies or receipts of `range` from ERA files.
+async fn restore_from_era<N: CliNodeTypes>(
+    writer: &mut StaticFileProviderRW<N::Primitives>,
+    provider: &(impl BlockBodyIndicesProvider + HeaderProvider<Header = HeaderTy<N>>),
+    consensus: &impl Consensus<BlockTy<N>>,
+    segment: RestoreSegment,
+    (era_type, mut files): (EraFileType, EraFiles),
+    range: SegmentRangeInclusive,
+) -> eyre::Result<()> {
+    let mut next = range.start();
+    while next <= range.end() {
+        let Some(meta) = files.next().await.transpose()? else {
+            bail!("ERA files end before block #{next}")
+        };
+
+        let append_body = |writer: &mut StaticFileProviderRW<N::Primitives>,
+                           header: &SealedHeader<HeaderTy<N>>,
+                           body: BodyTy<N>| {
+            validate_body::<BlockTy<N>>(consensus, header, &body)?;
+            append_block(
+                writer,
+                provider,
+                header.number(),
+                body.transactions(),
+                StaticFileProviderRW::append_transaction,
+            )
+        };
+        let append_receipts = |writer: &mut StaticFileProviderRW<N::Primitives>,
+                               header: &SealedHeader<HeaderTy<N>>,
+                               receipts: Vec<ReceiptTy<N>>| {
+            validate_receipts(header.header(), &receipts)?;
+            append_block(
+                writer,
+                provider,
+                header.number(),
+                &receipts,
+                StaticFileProviderRW::append_receipt,
+            )
+        };
+
+        // Consensus `.era` files are rejected by `RestoreEraArgs::files`.
+        match (segment, era_type) {
+            (RestoreSegment::Bodies, EraFileType::Era1) => append_era_file(
+                writer,
+                provider,
+                <era::Era1 as EraBlockReader<_, _>>::blocks(&*meta)?,
+                range,
+                &mut next,
+                append_body,
+            )?,
+            (RestoreSegment::Bodies, _) => append_era_file(
+                writer,
+                provider,
+                <era::Ere as EraBlockReader<_, _>>::blocks(&*meta)?,
+                range,
+                &mut next,
+                append_body,
+            )?,
+            (_, EraFileType::Era1) => append_era_file(
+                writer,
+                provider,
+                <era::Era1 as EraReceiptReader<_, _>>::receipts(&*meta)?,
+                range,
+                &mut next,
+                append_receipts,
+            )?,
+            (_, _) => append_era_file(
+                writer,
+                provider,
+                <era::Ere as EraReceiptReader<_, _>>::receipts(&*meta)?,
+                range,
+                &mut next,
+                append_receipts,
+            )?,
+        }
+
+        meta.mark_as_processed()?;
+    }
+
+    Ok(())
+}
+
+/// Appends the blocks of one ERA file that fall into `range`, advancing `next` past them.
+///
+/// Every block has to match the canonical header stored for its number.
+fn append_era_file<P: NodePrimitives, T>(
+    writer: &mut StaticFileProviderRW<P>,
+    provider: &impl HeaderProvider<Header = P::BlockHeader>,
+    blocks: impl Iterator<Item = eyre::Result<(P::BlockHeader, T)>>,
+    range: SegmentRangeInclusive,
+    next: &mut BlockNumber,
+    mut append: impl FnMut(
+        &mut StaticFileProviderRW<P>,
+        &SealedHeader<P::BlockHeader>,
+        T,
+    ) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    for block in blocks {
+        let (header, data) = block?;
+        let number = header.number();
+
+        if number < *next {
+            continue
+        }
+        if number > range.end() {
+            break
+        }
+        if number != *next {
+            bail!("Non-contiguous ERA files: expected block #{next}, got #{number}")
+        }
+
+        let sealed_header =
+            provider.sealed_header(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        if header.hash_slow() != sealed_header.hash() {
+            bail!("ERA block #{number} doesn't match the canonical block")
+        }
+
+        append(writer, &sealed_header, data)?;
+        *next += 1;
+    }
+
+    Ok(())
+}
+
+/// Downloads the data of `range` from peers in batches, validating and appending it block by
+/// block.
+///
+/// Invalid responses are reported, and restoring is aborted after [`MAX_PEER_RETRIES`]
+/// consecutive requests without progress.
+async fn restore_from_peers<P, T, Fut>(
+    writer: &mut StaticFileProviderRW<P>,
+    provider: &impl HeaderProvider<Header = P::BlockHeader>,
+    client: &impl DownloadClient,
+    range: SegmentRangeInclusive,
+    request: impl Fn(Vec<B256>) -> Fut,
+    validate: impl Fn(&SealedHeader<P::BlockHeader>, &T) -> eyre::Result<()>,
+    append: impl Fn(&mut StaticFileProviderRW<P>, &SealedHeader<P::BlockHeader>, T) -> eyre::Result<()>,
+) -> eyre::Result<()>
+where
+    P: NodePrimitives,
+    Fut: Future<Output = PeerRequestResult<Vec<T>>>,
+{
+    let mut next = range.start();
+    let mut failures = 0;
+    while next <= range.end() {
+        let headers = (next..=range.end().min(next + PEER_BATCH_SIZE - 1))
+            .map(|number| {
+                provider.sealed_header(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))
+            })
+            .collect::<ProviderResult<Vec<_>>>()?;
+
+        let start = next;
+        match request(headers.iter().map(|header| header.hash()).collect()).await {
+            Ok(response) => {
+                // Peers may respond with only a part of the requested blocks.
+                let (peer_id, data) = response.split();
+                for (header, data) in headers.iter().zip(data) {
+                    if let Err(err) = validate(header, &data) {
+                        warn!(target: "reth::cli", %peer_id, %err, "Received invalid data from peer");
+                        client.report_bad_message(peer_id);
+                        break
+                    }
+
+                    append(writer, header, data)?;
+                    next += 1;
+                }
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", block = next, %err, "Failed to request data from peers")
+            }
+        }
+
+        if next > start {
+            failures = 0;
+        } else {
+            failures += 1;
+            if failures >= MAX_PEER_RETRIES {
+                bail!("Failed to download block #{next} from peers after {failures} attempts")
+            }
+            tokio::time::sleep(PEER_RETRY_DELAY).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Restores the receipts of `range` by re-executing its blocks on top of their state history.
+fn restore_receipts_by_execution<N: CliNodeTypes>(
+    writer: &mut StaticFileProviderRW<N::Primitives>,
+    provider: &(impl BlockReader<Block = BlockTy<N>> + BlockBodyIndicesProvider),
+    state_provider_factory: &impl StateProviderFactory,
+    evm_config: &impl ConfigureEvm<Primitives = N::Primitives>,
+    genesis: BlockNumber,
+    range: SegmentRangeInclusive,
+) -> eyre::Result<()> {
+    let state_at = |block: BlockNumber| -> eyre::Result<_> {
+        let state = state_provider_factory.history_by_block_number(block).wrap_err_with(|| {
+            format!("Re-execution requires the state history at block #{block}")
+        })?;
+        Ok(StateProviderDatabase(state))
+    };
+
+    let mut executor = evm_config.batch_executor(state_at(range.start().max(genesis + 1) - 1)?);
+    for number in range.start()..=range.end() {
+        // The genesis block has no receipts.
+        if number <= genesis {
+            let receipts: Vec<ReceiptTy<N>> = Vec::new();
+            append_block(
+                writer,
+                provider,
+                number,
+                &receipts,
+                StaticFileProviderRW::append_receipt,
+            )?;
+            continue
+        }
+
+        let block =
+            provider.block(number.into())?.ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        // stored blocks are known to be valid, see `recover_senders`
+        let senders = block
+            .body()
+            .recover_signers_unchecked()
+            .wrap_err_with(|| format!("Failed to recover senders of block #{number}"))?;
+        let block = RecoveredBlock::new_unhashed(block, senders);
+        let result = executor
+            .execute_one(&block)
+            .wrap_err_with(|| format!("Failed to re-execute block #{number}"))?;
+
+        validate_receipts(block.header(), &result.receipts)?;
+        append_block(
+            writer,
+            provider,
+            number,
+            &result.receipts,
+            StaticFileProviderRW::append_receipt,
+        )?;
+
+        // Recreate the executor once in a while to bound memory usage.
+        if executor.size_hint() > EXECUTOR_SIZE_HINT_LIMIT {
+            executor = evm_config.batch_executor(state_at(number)?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Restores the senders of `range` by recovering them from the stored transactions.
+fn recover_senders<N: CliNodeTypes>(
+    writer: &mut StaticFileProviderRW<N::Primitives>,
+    provider: &(impl TransactionsProvider<Transaction = TxTy<N>> + BlockBodyIndicesProvider),
+    range: SegmentRangeInclusive,
+) -> eyre::Result<()> {
+    for number in range.start()..=range.end() {
+        let transactions = provider
+            .transactions_by_block(number.into())?
+            .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        // Stored transactions are known to be valid, so the `s` value of their signatures isn't
+        // checked against `secp256k1n / 2`, like in the sender recovery stage. Pre-homestead
+        // transactions can have large `s` values, which EIP-2 later disallowed.
+        let senders = transactions
+            .iter()
+            .map(SignerRecoverable::recover_signer_unchecked)
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err_with(|| format!("Failed to recover senders of block #{number}"))?;
+
+        append_block(
+            writer,
+            provider,
+            number,
+            &senders,
+            StaticFileProviderRW::append_transaction_sender,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Increments the writer to `block` and appends its rows, numbered by the stored body indices of
+/// the block.
+fn append_block<P: NodePrimitives, T>(
+    writer: &mut StaticFileProviderRW<P>,
+    provider: &impl BlockBodyIndicesProvider,
+    block: BlockNumber,
+    rows: &[T],
+    append: impl Fn(&mut StaticFileProviderRW<P>, TxNumber, &T) -> ProviderResult<()>,
+) -> eyre::Result<()> {
+    let indices = provider
+        .block_body_indices(block)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(block))?;
+    if indices.tx_count() != rows.len() as u64 {
+        bail!("Block #{block} has {} transactions, got {} rows", indices.tx_count(), rows.len())
+    }
+
+    writer.increment_block(block)?;
+    for (tx_num, row) in indices.tx_num_range().zip(rows) {
+        append(writer, tx_num, row)?;
+    }
+
+    Ok(())
+}
+
+/// Validates `body` against the header of its block.
+fn validate_body<B: Block>(
+    consensus: &impl Consensus<B>,
+    header: &SealedHeader<B::Header>,
+    body: &B::Body,
+) -> eyre::Result<()> {
+    consensus
+        .validate_body_against_header(body, header)
+        .wrap_err_with(|| format!("Invalid body of block #{}", header.number()))
+}
+
+/// Validates `receipts` against the receipts root of the header of their block.
+fn validate_receipts<R: Receipt>(header: &impl BlockHeader, receipts: &[R]) -> eyre::Result<()> {
+    let receipts_with_bloom = receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
+    let receipts_root = calculate_receipt_root(&receipts_with_bloom);
+    if receipts_root != header.receipts_root() {
+        bail!(
+            "Receipts root mismatch of block #{}: got {receipts_root}, expected {}",
+            header.number(),
+            header.receipts_root()
+        )
+    }
+
+    Ok(())
+}
+
+/// Lowers the prune checkpoint of `segment` below the restored static file starting at
+/// `range_start`, if it's not already lower.
+fn lower_prune_checkpoint<PF>(
+    provider_factory: &PF,
+    segment: PruneSegment,
+    range_start: BlockNumber,
+) -> eyre::Result<()>
+where
+    PF: DatabaseProviderFactory<
+        ProviderRW: PruneCheckpointReader + PruneCheckpointWriter + BlockBodyIndicesProvider,
+    >,
+{
+    let provider_rw = provider_factory.database_provider_rw()?;
+    let Some(checkpoint) = provider_rw.get_prune_checkpoint(segment)? else { return Ok(()) };
+    if checkpoint.block_number.is_none_or(|block_number| block_number < range_start) {
+        return Ok(())
+    }
+
+    let first_tx_num = provider_rw
+        .block_body_indices(range_start)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(range_start))?
+        .first_tx_num();
+    let checkpoint = PruneCheckpoint {
+        block_number: range_start.checked_sub(1),
+        tx_number: first_tx_num.checked_sub(1),
+        prune_mode: checkpoint.prune_mode,
+    };
+    provider_rw.save_prune_checkpoint(segment, checkpoint)?;
+    provider_rw.commit()?;
+
+    info!(target: "reth::cli", %segment, ?checkpoint, "Lowered prune checkpoint");
+
+    Ok(())
+}
@@ -129,7 +129,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
 
         if let Some(listen_addr) = self.metrics {
             let config = MetricServerConfig::new(
-                listen_addr,
+                Some(listen_addr),
                 VersionInfo {
                     version: version_metadata().cargo_pkg_version.as_ref(),
                     build_timestamp: version_metadata().vergen_build_timestamp.as_ref(),
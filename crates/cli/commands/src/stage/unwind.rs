@@ -219,4 +219,23 @@ mod tests {
         assert_eq!(cmd.command, Subcommands::ToBlock { target: BlockHashOrNumber::Number(100) });
         assert_eq!(cmd.env.chain.chain_id(), SEPOLIA.chain_id());
     }
+
+    #[test]
+    fn parse_unwind_chain_overrides() {
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--chain",
+            "sepolia",
+            "--override.chain-id",
+            "1337",
+            "to-block",
+            "100",
+        ]);
+        assert_eq!(cmd.env.chain_spec().unwrap().chain_id(), 1337);
+        // the datadir of the overridden chain is used
+        assert_eq!(
+            cmd.env.datadir.clone().resolve_datadir(cmd.env.chain.chain()).as_ref(),
+            cmd.env.datadir.clone().resolve_datadir(SEPOLIA.chain()).as_ref()
+        );
+    }
 }
@@ -419,12 +419,15 @@ impl EtlConfig {
 }
 
 /// Static files configuration.
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct StaticFilesConfig {
     /// Number of blocks per file for each segment.
     pub blocks_per_file: BlocksPerFileConfig,
+    /// Cold storage tier for sealed static files. Disabled if not set.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub cold: Option<ColdStaticFilesConfig>,
 }
 
 /// Configuration for the number of blocks per file for each segment.
@@ -480,6 +483,9 @@ impl StaticFilesConfig {
             storage_change_sets != Some(0),
             "Storage changesets segment blocks per file must be greater than 0"
         );
+        if let Some(cold) = &self.cold {
+            cold.validate()?;
+        }
         Ok(())
     }
 
@@ -515,6 +521,139 @@ impl StaticFilesConfig {
     }
 }
 
+/// Cold storage tier configuration for sealed static files.
+///
+/// Sealed files of the configured segments that are older than `keep_recent_blocks` are moved to
+/// the configured backend and fetched into a local read cache on access. Headers are always kept
+/// local.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColdStaticFilesConfig {
+    /// Segments whose sealed files are moved to cold storage.
+    #[cfg_attr(feature = "serde", serde(default = "ColdStaticFilesConfig::default_segments"))]
+    pub segments: Vec<StaticFileSegment>,
+    /// Number of most recent blocks whose static files are always kept local.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "ColdStaticFilesConfig::default_keep_recent_blocks")
+    )]
+    pub keep_recent_blocks: u64,
+    /// Directory of the local read cache. Defaults to `static_files_cache` next to the static
+    /// files directory.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub cache_dir: Option<PathBuf>,
+    /// Maximum size of the local read cache in bytes.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "ColdStaticFilesConfig::default_cache_max_size")
+    )]
+    pub cache_max_size: u64,
+    /// How often sealed files are checked and moved to cold storage.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default = "ColdStaticFilesConfig::default_offload_interval",
+            with = "humantime_serde"
+        )
+    )]
+    pub offload_interval: Duration,
+    /// Backend that stores the cold files.
+    pub backend: ColdStorageBackendConfig,
+}
+
+impl ColdStaticFilesConfig {
+    /// Default number of most recent blocks kept local.
+    pub const DEFAULT_KEEP_RECENT_BLOCKS: u64 = 1_000_000;
+
+    /// Default maximum size of the local read cache, 16 GiB.
+    pub const DEFAULT_CACHE_MAX_SIZE: u64 = 16 * 1024 * 1024 * 1024;
+
+    /// Default interval between offload runs.
+    pub const DEFAULT_OFFLOAD_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+    /// Creates a new configuration for the given backend with default values.
+    pub fn new(backend: ColdStorageBackendConfig) -> Self {
+        Self {
+            segments: Self::default_segments(),
+            keep_recent_blocks: Self::DEFAULT_KEEP_RECENT_BLOCKS,
+            cache_dir: None,
+            cache_max_size: Self::DEFAULT_CACHE_MAX_SIZE,
+            offload_interval: Self::DEFAULT_OFFLOAD_INTERVAL,
+            backend,
+        }
+    }
+
+    /// Returns the segments moved to cold storage by default: transactions and receipts.
+    pub fn default_segments() -> Vec<StaticFileSegment> {
+        vec![StaticFileSegment::Transactions, StaticFileSegment::Receipts]
+    }
+
+    #[cfg(feature = "serde")]
+    const fn default_keep_recent_blocks() -> u64 {
+        Self::DEFAULT_KEEP_RECENT_BLOCKS
+    }
+
+    #[cfg(feature = "serde")]
+    const fn default_cache_max_size() -> u64 {
+        Self::DEFAULT_CACHE_MAX_SIZE
+    }
+
+    #[cfg(feature = "serde")]
+    const fn default_offload_interval() -> Duration {
+        Self::DEFAULT_OFFLOAD_INTERVAL
+    }
+
+    /// Validates the cold storage configuration.
+    ///
+    /// Returns an error if the headers segment is configured, since headers are always kept
+    /// local.
+    pub fn validate(&self) -> eyre::Result<()> {
+        eyre::ensure!(
+            !self.segments.contains(&StaticFileSegment::Headers),
+            "Headers segment cannot be moved to cold storage"
+        );
+        eyre::ensure!(
+            !self.offload_interval.is_zero(),
+            "Cold storage offload interval must be greater than 0"
+        );
+        Ok(())
+    }
+}
+
+/// Backend of the static files cold storage tier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "snake_case"))]
+pub enum ColdStorageBackendConfig {
+    /// Files are moved to a directory, usually on a slower or larger disk.
+    Local {
+        /// Directory that stores the cold files.
+        path: PathBuf,
+    },
+    /// Files are stored in an S3-compatible object store.
+    ///
+    /// Credentials are read from the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`
+    /// environment variables.
+    S3 {
+        /// Endpoint of the object store, e.g. `https://s3.us-east-1.amazonaws.com`.
+        endpoint: Url,
+        /// Bucket that stores the cold files.
+        bucket: String,
+        /// Region used for request signing.
+        #[cfg_attr(feature = "serde", serde(default = "default_s3_region"))]
+        region: String,
+        /// Prefix prepended to every object key.
+        #[cfg_attr(feature = "serde", serde(default))]
+        prefix: String,
+    },
+}
+
+/// Returns the default S3 region.
+#[cfg(feature = "serde")]
+fn default_s3_region() -> String {
+    "us-east-1".to_string()
+}
+
 /// History stage configuration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -577,6 +716,8 @@ impl PruneConfig {
     /// - `block_interval`: set from `other` only if `self.block_interval ==
     ///   DEFAULT_BLOCK_INTERVAL`.
     /// - `receipts_log_filter`: set from `other` only if `self` is empty and `other` is non-empty.
+    /// - `history_retained_addresses`: set from `other` only if `self` is empty and `other` is
+    ///   non-empty.
     pub fn merge(&mut self, other: Self) {
         let Self {
             block_interval,
@@ -589,6 +730,7 @@ impl PruneConfig {
                     storage_history,
                     bodies_history,
                     receipts_log_filter,
+                    history_retained_addresses,
                 },
             minimum_pruning_distance,
         } = other;
@@ -614,6 +756,12 @@ impl PruneConfig {
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
         }
+
+        if self.segments.history_retained_addresses.is_empty() &&
+            !history_retained_addresses.is_empty()
+        {
+            self.segments.history_retained_addresses = history_retained_addresses;
+        }
     }
 }
 
@@ -638,14 +786,20 @@ where
 
 #[cfg(all(test, feature = "serde"))]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{ColdStaticFilesConfig, ColdStorageBackendConfig, Config, EXTENSION};
     use crate::PruneConfig;
     use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
     use reth_prune_types::{
         PruneMode, PruneModes, ReceiptsLogPruneConfig, MINIMUM_UNWIND_SAFE_DISTANCE,
     };
-    use std::{collections::BTreeMap, path::Path, str::FromStr, time::Duration};
+    use reth_static_file_types::StaticFileSegment;
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        path::Path,
+        str::FromStr,
+        time::Duration,
+    };
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -1126,6 +1280,7 @@ receipts = { distance = 16384 }
                     Address::random(),
                     PruneMode::Full,
                 )])),
+                history_retained_addresses: Default::default(),
             },
         };
 
@@ -1143,10 +1298,12 @@ receipts = { distance = 16384 }
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
                 ])),
+                history_retained_addresses: BTreeSet::from([Address::random()]),
             },
         };
 
         let original_filter = config1.segments.receipts_log_filter.clone();
+        let retained_addresses = config2.segments.history_retained_addresses.clone();
         config1.merge(config2);
 
         // Check that the configuration has been merged. Any configuration present in config1
@@ -1158,6 +1315,7 @@ receipts = { distance = 16384 }
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
+        assert_eq!(config1.segments.history_retained_addresses, retained_addresses);
     }
 
     #[test]
@@ -1200,4 +1358,39 @@ connect_trusted_nodes_only = true
             assert!(conf.peers.trusted_nodes.contains(&node));
         }
     }
+
+    #[test]
+    fn test_cold_static_files_config() {
+        let reth_toml = r#"
+    [static_files.cold]
+    keep_recent_blocks = 500000
+    offload_interval = "30m"
+
+    [static_files.cold.backend]
+    type = "s3"
+    endpoint = "http://localhost:9000"
+    bucket = "reth"
+    "#;
+
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let cold = conf.static_files.cold.unwrap();
+        assert_eq!(cold.segments, ColdStaticFilesConfig::default_segments());
+        assert_eq!(cold.keep_recent_blocks, 500_000);
+        assert_eq!(cold.cache_max_size, ColdStaticFilesConfig::DEFAULT_CACHE_MAX_SIZE);
+        assert_eq!(cold.offload_interval, Duration::from_secs(30 * 60));
+        assert_eq!(
+            cold.backend,
+            ColdStorageBackendConfig::S3 {
+                endpoint: "http://localhost:9000".parse().unwrap(),
+                bucket: "reth".to_string(),
+                region: "us-east-1".to_string(),
+                prefix: String::new(),
+            }
+        );
+        assert!(cold.validate().is_ok());
+
+        let mut headers = cold;
+        headers.segments.push(StaticFileSegment::Headers);
+        assert!(headers.validate().is_err());
+    }
 }
@@ -3,6 +3,10 @@
 //! This is a worker that sends FCUs and new payloads by fetching recent payloads from an external
 //! provider like Etherscan or an RPC endpoint. This allows to quickly test the execution client
 //! without running a consensus node.
+//!
+//! [`BeaconLightClientProvider`] follows the beacon chain through the light client endpoints of a
+//! Beacon API instead, and only hands payloads to the engine that are authenticated by sync
+//! committee signatures.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -16,4 +20,9 @@ mod client;
 mod providers;
 
 pub use client::{DebugConsensusClient, PayloadProvider};
-pub use providers::{EtherscanBlockProvider, RpcBlockProvider};
+pub use providers::{
+    BeaconBlockHeader, BeaconFork, BeaconLightClientProvider, EtherscanBlockProvider,
+    ExecutionPayloadHeader, ForkActivation, LightClientBootstrap, LightClientConfig,
+    LightClientError, LightClientHeader, LightClientUpdate, RpcBlockProvider, SyncAggregate,
+    SyncCommittee,
+};
@@ -0,0 +1,74 @@
+//! Minimal client of the Beacon API light client endpoints.
+
+use super::types::{LightClientBootstrap, LightClientUpdate, SignedBeaconBlock, VersionedResponse};
+use alloy_primitives::B256;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+
+/// Maximum number of updates the updates endpoint serves per request.
+pub(crate) const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+/// Client of a Beacon API endpoint.
+#[derive(Debug, Clone)]
+pub(crate) struct BeaconApiClient {
+    http_client: Client,
+    base_url: String,
+}
+
+impl BeaconApiClient {
+    /// Creates a client of the Beacon API at the given URL.
+    pub(crate) fn new(base_url: impl Into<String>) -> Self {
+        Self { http_client: Client::new(), base_url: base_url.into().trim_end_matches('/').into() }
+    }
+
+    /// Fetches the light client bootstrap of the given block root.
+    pub(crate) async fn bootstrap(&self, block_root: B256) -> eyre::Result<LightClientBootstrap> {
+        let path = format!("/eth/v1/beacon/light_client/bootstrap/{block_root}");
+        Ok(self.get::<VersionedResponse<_>>(&path, &[]).await?.data)
+    }
+
+    /// Fetches the best updates of `count` sync committee periods, starting at `start_period`.
+    pub(crate) async fn updates(
+        &self,
+        start_period: u64,
+        count: u64,
+    ) -> eyre::Result<Vec<LightClientUpdate>> {
+        let updates = self
+            .get::<Vec<VersionedResponse<_>>>(
+                "/eth/v1/beacon/light_client/updates",
+                &[("start_period", start_period), ("count", count)],
+            )
+            .await?;
+        Ok(updates.into_iter().map(|update| update.data).collect())
+    }
+
+    /// Fetches the latest finality update.
+    pub(crate) async fn finality_update(&self) -> eyre::Result<LightClientUpdate> {
+        let path = "/eth/v1/beacon/light_client/finality_update";
+        Ok(self.get::<VersionedResponse<_>>(path, &[]).await?.data)
+    }
+
+    /// Fetches the latest optimistic update.
+    pub(crate) async fn optimistic_update(&self) -> eyre::Result<LightClientUpdate> {
+        let path = "/eth/v1/beacon/light_client/optimistic_update";
+        Ok(self.get::<VersionedResponse<_>>(path, &[]).await?.data)
+    }
+
+    /// Fetches the block with the given root.
+    pub(crate) async fn block(&self, block_root: B256) -> eyre::Result<SignedBeaconBlock> {
+        let path = format!("/eth/v2/beacon/blocks/{block_root}");
+        Ok(self.get::<VersionedResponse<_>>(&path, &[]).await?.data)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, u64)]) -> eyre::Result<T> {
+        let response = self
+            .http_client
+            .get(format!("{}{path}", self.base_url))
+            .query(query)
+            .header("accept", "application/json")
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(serde_json::from_str(&response.text().await?)?)
+    }
+}
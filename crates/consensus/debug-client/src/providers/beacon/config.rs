@@ -0,0 +1,148 @@
+//! Beacon chain parameters the light client depends on.
+
+use super::ssz::hash_pair;
+use alloy_primitives::{b256, B256};
+
+/// Number of slots of an epoch.
+pub(crate) const SLOTS_PER_EPOCH: u64 = 32;
+
+/// Number of epochs of a sync committee period.
+pub(crate) const EPOCHS_PER_SYNC_COMMITTEE_PERIOD: u64 = 256;
+
+/// Number of members of a sync committee.
+pub(crate) const SYNC_COMMITTEE_SIZE: usize = 512;
+
+/// Domain type of sync committee signatures.
+const DOMAIN_SYNC_COMMITTEE: [u8; 4] = [0x07, 0x00, 0x00, 0x00];
+
+/// Consensus layer forks relevant to the light client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BeaconFork {
+    /// The genesis fork.
+    Phase0,
+    /// Introduced sync committees.
+    Altair,
+    /// The merge.
+    Bellatrix,
+    /// Introduced withdrawals.
+    Capella,
+    /// Introduced blobs, first fork whose execution payload headers the light client supports.
+    Deneb,
+    /// Introduced execution requests and changed the beacon state layout.
+    Electra,
+    /// Introduced `PeerDAS`.
+    Fulu,
+}
+
+/// Activation of a [`BeaconFork`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ForkActivation {
+    /// The activated fork.
+    pub fork: BeaconFork,
+    /// First epoch of the fork.
+    pub epoch: u64,
+    /// Fork version that is mixed into signing domains.
+    pub version: [u8; 4],
+}
+
+/// Beacon chain parameters the light client verifies updates with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LightClientConfig {
+    /// Genesis time of the beacon chain.
+    pub genesis_time: u64,
+    /// Root of the genesis validators, mixed into signing domains.
+    pub genesis_validators_root: B256,
+    /// Duration of a slot in seconds.
+    pub seconds_per_slot: u64,
+    /// Fork activations, ordered by epoch.
+    pub forks: Vec<ForkActivation>,
+}
+
+impl LightClientConfig {
+    /// Returns the parameters of Ethereum mainnet.
+    pub fn mainnet() -> Self {
+        Self {
+            genesis_time: 1606824023,
+            genesis_validators_root: b256!(
+                "0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"
+            ),
+            seconds_per_slot: 12,
+            forks: vec![
+                ForkActivation { fork: BeaconFork::Phase0, epoch: 0, version: [0, 0, 0, 0] },
+                ForkActivation { fork: BeaconFork::Altair, epoch: 74240, version: [1, 0, 0, 0] },
+                ForkActivation {
+                    fork: BeaconFork::Bellatrix,
+                    epoch: 144896,
+                    version: [2, 0, 0, 0],
+                },
+                ForkActivation { fork: BeaconFork::Capella, epoch: 194048, version: [3, 0, 0, 0] },
+                ForkActivation { fork: BeaconFork::Deneb, epoch: 269568, version: [4, 0, 0, 0] },
+                ForkActivation { fork: BeaconFork::Electra, epoch: 364032, version: [5, 0, 0, 0] },
+                ForkActivation { fork: BeaconFork::Fulu, epoch: 411392, version: [6, 0, 0, 0] },
+            ],
+        }
+    }
+
+    /// Returns the fork active at the given slot.
+    pub fn fork_at_slot(&self, slot: u64) -> ForkActivation {
+        let epoch = slot / SLOTS_PER_EPOCH;
+        self.forks
+            .iter()
+            .rev()
+            .find(|activation| activation.epoch <= epoch)
+            .copied()
+            .unwrap_or(ForkActivation { fork: BeaconFork::Phase0, epoch: 0, version: [0; 4] })
+    }
+
+    /// Returns the slot at the given unix timestamp.
+    pub const fn slot_at(&self, timestamp: u64) -> u64 {
+        timestamp.saturating_sub(self.genesis_time) / self.seconds_per_slot
+    }
+
+    /// Computes the domain sync committee members sign blocks for, using the fork version at the
+    /// given signature slot.
+    pub(crate) fn sync_committee_domain(&self, signature_slot: u64) -> B256 {
+        // the signature covers the block of the previous slot
+        let version = self.fork_at_slot(signature_slot.max(1) - 1).version;
+
+        // hash tree root of `ForkData { current_version, genesis_validators_root }`
+        let mut current_version = B256::ZERO;
+        current_version[..4].copy_from_slice(&version);
+        let fork_data_root = hash_pair(current_version, self.genesis_validators_root);
+
+        let mut domain = B256::ZERO;
+        domain[..4].copy_from_slice(&DOMAIN_SYNC_COMMITTEE);
+        domain[4..].copy_from_slice(&fork_data_root[..28]);
+        domain
+    }
+}
+
+/// Returns the sync committee period of the given slot.
+pub(crate) const fn sync_committee_period(slot: u64) -> u64 {
+    slot / SLOTS_PER_EPOCH / EPOCHS_PER_SYNC_COMMITTEE_PERIOD
+}
+
+/// Generalized indices of the proofs against a beacon state, which moved with Electra.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StateProofIndices {
+    /// Index of the finalized checkpoint root.
+    pub(crate) finalized_root: u64,
+    /// Index of the current sync committee.
+    pub(crate) current_sync_committee: u64,
+    /// Index of the next sync committee.
+    pub(crate) next_sync_committee: u64,
+}
+
+impl StateProofIndices {
+    /// Returns the generalized indices of the state of the given fork.
+    pub(crate) const fn for_fork(fork: BeaconFork) -> Self {
+        if matches!(fork, BeaconFork::Electra | BeaconFork::Fulu) {
+            Self { finalized_root: 169, current_sync_committee: 86, next_sync_committee: 87 }
+        } else {
+            Self { finalized_root: 105, current_sync_committee: 54, next_sync_committee: 55 }
+        }
+    }
+}
+
+/// Generalized index of the execution payload in a beacon block body.
+pub(crate) const EXECUTION_PAYLOAD_INDEX: u64 = 25;
@@ -0,0 +1,364 @@
+//! Payload provider that follows the beacon chain through Beacon API light client updates.
+
+mod api;
+mod config;
+mod ssz;
+mod store;
+mod types;
+
+pub use config::{BeaconFork, ForkActivation, LightClientConfig};
+pub use types::{
+    BeaconBlockHeader, ExecutionPayloadHeader, LightClientBootstrap, LightClientHeader,
+    LightClientUpdate, SyncAggregate, SyncCommittee,
+};
+
+use crate::PayloadProvider;
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionData, ExecutionPayload, ExecutionPayloadSidecar,
+    ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3, PraguePayloadFields,
+};
+use api::{BeaconApiClient, MAX_REQUEST_LIGHT_CLIENT_UPDATES};
+use config::sync_committee_period;
+use parking_lot::Mutex;
+use reth_tracing::tracing::{debug, warn};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use store::LightClientStore;
+use tokio::{sync::mpsc, time::interval};
+use types::BeaconBlock;
+
+/// Number of verified payloads kept to answer [`PayloadProvider::get_payload`] without walking
+/// back the chain again.
+const PAYLOAD_CACHE_SIZE: usize = 128;
+
+/// Errors when verifying light client data.
+#[derive(Debug, thiserror::Error)]
+pub enum LightClientError {
+    /// The header is of a fork before Deneb.
+    #[error("unsupported fork at slot {0}")]
+    UnsupportedFork(u64),
+    /// The bootstrap header doesn't match the trusted checkpoint.
+    #[error("bootstrap header {got} does not match checkpoint {expected}")]
+    CheckpointMismatch {
+        /// The trusted checkpoint.
+        expected: B256,
+        /// Root of the bootstrap header.
+        got: B256,
+    },
+    /// The execution payload header is not part of the beacon block.
+    #[error("invalid execution payload branch")]
+    InvalidExecutionBranch,
+    /// The sync committee is not part of the beacon state.
+    #[error("invalid sync committee branch")]
+    InvalidSyncCommitteeBranch,
+    /// The sync committee has the wrong size or invalid public keys.
+    #[error("invalid sync committee")]
+    InvalidSyncCommittee,
+    /// The update's next sync committee differs from the known one.
+    #[error("next sync committee mismatch")]
+    SyncCommitteeMismatch,
+    /// The finalized header is not part of the attested state.
+    #[error("invalid finality branch")]
+    InvalidFinalityBranch,
+    /// Less than two thirds of the sync committee signed the update.
+    #[error("insufficient sync committee participation: {0}")]
+    InsufficientParticipation(usize),
+    /// The slots of the update are not ordered.
+    #[error("invalid update slots")]
+    InvalidSlots,
+    /// The update is signed by a sync committee that isn't known.
+    #[error("unexpected signature period {0}")]
+    UnexpectedSignaturePeriod(u64),
+    /// The sync committee signature is invalid.
+    #[error("invalid sync committee signature")]
+    InvalidSignature,
+    /// The execution payload doesn't match the verified execution payload header.
+    #[error("execution payload does not match verified header of block {0}")]
+    PayloadMismatch(B256),
+}
+
+/// Block provider that follows the beacon chain through the light client endpoints of a Beacon
+/// API.
+///
+/// The provider bootstraps from a trusted checkpoint block root and only accepts headers signed
+/// by a supermajority of the sync committee. Execution payloads are fetched from the Beacon API and
+/// checked against the verified execution payload headers before they are handed to the engine.
+#[derive(derive_more::Debug, Clone)]
+pub struct BeaconLightClientProvider<Data> {
+    api: BeaconApiClient,
+    config: Arc<LightClientConfig>,
+    checkpoint: B256,
+    interval: Duration,
+    #[debug(skip)]
+    store: Arc<tokio::sync::Mutex<Option<LightClientStore>>>,
+    #[debug(skip)]
+    payloads: Arc<Mutex<BTreeMap<u64, ExecutionData>>>,
+    #[debug(skip)]
+    convert: Arc<dyn Fn(ExecutionData) -> Data + Send + Sync>,
+}
+
+impl<Data> BeaconLightClientProvider<Data> {
+    /// Creates a provider that follows the chain of the given config through the Beacon API at
+    /// `base_url`, starting from the trusted `checkpoint` block root.
+    pub fn new(
+        base_url: impl Into<String>,
+        checkpoint: B256,
+        config: LightClientConfig,
+        convert: impl Fn(ExecutionData) -> Data + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            api: BeaconApiClient::new(base_url),
+            config: Arc::new(config),
+            checkpoint,
+            interval: Duration::from_secs(4),
+            store: Default::default(),
+            payloads: Default::default(),
+            convert: Arc::new(convert),
+        }
+    }
+
+    /// Sets the interval at which the provider polls for new updates.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Bootstraps the light client if needed, applies the latest updates and returns the latest
+    /// verified header.
+    pub async fn sync(&self) -> eyre::Result<LightClientHeader> {
+        let mut store = self.store.lock().await;
+        if store.is_none() {
+            let bootstrap = self.api.bootstrap(self.checkpoint).await?;
+            *store = Some(LightClientStore::bootstrap(&self.config, self.checkpoint, &bootstrap)?);
+            debug!(
+                target: "consensus::debug-client",
+                slot = bootstrap.header.beacon.slot,
+                "Bootstrapped beacon light client",
+            );
+        }
+        let store = store.as_mut().expect("light client is bootstrapped");
+
+        let current_slot = self.current_slot();
+        let current_period = sync_committee_period(current_slot);
+
+        // Catch up with the sync committee of the current period.
+        while store.period() < current_period || !store.is_next_sync_committee_known() {
+            let start_period = store.period();
+            let progress = (start_period, store.is_next_sync_committee_known());
+            let count = (current_period.saturating_sub(start_period) + 1)
+                .min(MAX_REQUEST_LIGHT_CLIENT_UPDATES);
+            for update in self.api.updates(start_period, count).await? {
+                if let Err(err) = store.process_update(&self.config, &update, current_slot) {
+                    warn!(
+                        target: "consensus::debug-client",
+                        %err,
+                        "Failed to apply light client update",
+                    );
+                    break
+                }
+            }
+            if (store.period(), store.is_next_sync_committee_known()) == progress {
+                break
+            }
+        }
+
+        let finality_update = self.api.finality_update().await?;
+        store.process_update(&self.config, &finality_update, current_slot)?;
+        let optimistic_update = self.api.optimistic_update().await?;
+        store.process_update(&self.config, &optimistic_update, current_slot)?;
+
+        Ok(store.optimistic_header().clone())
+    }
+
+    /// Returns the latest finalized header, if the light client is bootstrapped.
+    pub async fn finalized_header(&self) -> Option<LightClientHeader> {
+        self.store.lock().await.as_ref().map(|store| store.finalized_header().clone())
+    }
+
+    /// Fetches the execution payload of the verified header.
+    async fn verified_payload(&self, header: &LightClientHeader) -> eyre::Result<ExecutionData> {
+        let block_hash = header.execution.block_hash;
+        if let Some(payload) = self.cached_payload(header.execution.block_number, block_hash) {
+            return Ok(payload)
+        }
+
+        let block = self.api.block(header.beacon.hash_tree_root()).await?.message;
+        if block.body.execution_payload.header() != header.execution {
+            return Err(LightClientError::PayloadMismatch(block_hash).into())
+        }
+
+        // The parent root of the verified header is used, the block's is not authenticated.
+        Ok(self.cache_payload(self.execution_data(block, header.beacon.parent_root)))
+    }
+
+    /// Fetches the execution payload of the block with `block_root`, which must have the given
+    /// block hash.
+    async fn payload_by_hash(
+        &self,
+        block_root: B256,
+        block_hash: B256,
+    ) -> eyre::Result<ExecutionData> {
+        let block = self.api.block(block_root).await?.message;
+        let payload = &block.body.execution_payload;
+        if payload.block_hash != block_hash {
+            return Err(LightClientError::PayloadMismatch(block_hash).into())
+        }
+
+        let parent_root = block.parent_root;
+        Ok(self.cache_payload(self.execution_data(block, parent_root)))
+    }
+
+    fn cached_payload(&self, block_number: u64, block_hash: B256) -> Option<ExecutionData> {
+        self.payloads
+            .lock()
+            .get(&block_number)
+            .filter(|payload| payload.block_hash() == block_hash)
+            .cloned()
+    }
+
+    fn cache_payload(&self, payload: ExecutionData) -> ExecutionData {
+        let mut payloads = self.payloads.lock();
+        payloads.insert(payload.block_number(), payload.clone());
+        while payloads.len() > PAYLOAD_CACHE_SIZE {
+            payloads.pop_first();
+        }
+        payload
+    }
+
+    /// Assembles the engine API payload of the block.
+    fn execution_data(&self, block: BeaconBlock, parent_beacon_block_root: B256) -> ExecutionData {
+        let fork = self.config.fork_at_slot(block.slot).fork;
+        let body = block.body;
+        let payload = body.execution_payload;
+
+        let versioned_hashes = body
+            .blob_kzg_commitments
+            .iter()
+            .map(|commitment| {
+                let mut hash = B256::from_slice(&Sha256::digest(commitment));
+                hash[0] = 0x01;
+                hash
+            })
+            .collect();
+        let cancun = CancunPayloadFields::new(parent_beacon_block_root, versioned_hashes);
+        let sidecar = if fork >= BeaconFork::Electra {
+            let requests = body.execution_requests.unwrap_or_default().encode();
+            ExecutionPayloadSidecar::v4(cancun, PraguePayloadFields::new(requests))
+        } else {
+            ExecutionPayloadSidecar::v3(cancun)
+        };
+
+        let payload = ExecutionPayloadV3 {
+            payload_inner: ExecutionPayloadV2 {
+                payload_inner: ExecutionPayloadV1 {
+                    parent_hash: payload.parent_hash,
+                    fee_recipient: payload.fee_recipient,
+                    state_root: payload.state_root,
+                    receipts_root: payload.receipts_root,
+                    logs_bloom: payload.logs_bloom,
+                    prev_randao: payload.prev_randao,
+                    block_number: payload.block_number,
+                    gas_limit: payload.gas_limit,
+                    gas_used: payload.gas_used,
+                    timestamp: payload.timestamp,
+                    extra_data: payload.extra_data,
+                    base_fee_per_gas: payload.base_fee_per_gas,
+                    block_hash: payload.block_hash,
+                    transactions: payload.transactions,
+                },
+                withdrawals: payload.withdrawals.into_iter().map(Into::into).collect(),
+            },
+            blob_gas_used: payload.blob_gas_used,
+            excess_blob_gas: payload.excess_blob_gas,
+        };
+
+        ExecutionData::new(ExecutionPayload::V3(payload), sidecar)
+    }
+
+    fn current_slot(&self) -> u64 {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.config.slot_at(now)
+    }
+}
+
+impl<Data> PayloadProvider for BeaconLightClientProvider<Data>
+where
+    Data: reth_node_api::ExecutionPayload,
+{
+    type ExecutionData = Data;
+
+    async fn subscribe_payloads(&self, tx: mpsc::Sender<Self::ExecutionData>) {
+        let mut last_block_hash = None;
+        let mut interval = interval(self.interval);
+        loop {
+            interval.tick().await;
+            let header = match self.sync().await {
+                Ok(header) => header,
+                Err(err) => {
+                    warn!(
+                        target: "consensus::debug-client",
+                        %err,
+                        "Failed to sync the beacon light client",
+                    );
+                    continue
+                }
+            };
+            if Some(header.execution.block_hash) == last_block_hash {
+                continue
+            }
+
+            let payload = match self.verified_payload(&header).await {
+                Ok(payload) => payload,
+                Err(err) => {
+                    warn!(
+                        target: "consensus::debug-client",
+                        %err,
+                        "Failed to fetch a verified execution payload",
+                    );
+                    continue
+                }
+            };
+
+            if tx.send((self.convert)(payload)).await.is_err() {
+                // Channel closed.
+                break
+            }
+
+            last_block_hash = Some(header.execution.block_hash);
+        }
+    }
+
+    async fn get_payload(&self, block_number: u64) -> eyre::Result<Self::ExecutionData> {
+        let head = self
+            .store
+            .lock()
+            .await
+            .as_ref()
+            .map(|store| store.optimistic_header().clone())
+            .ok_or_else(|| eyre::eyre!("beacon light client is not bootstrapped"))?;
+        if block_number > head.execution.block_number {
+            eyre::bail!("block {block_number} is ahead of the light client head")
+        }
+
+        // Walk back from the verified head, each payload is authenticated by the parent hash of
+        // its child.
+        let mut payload = self.verified_payload(&head).await?;
+        while payload.block_number() > block_number {
+            let parent_hash = payload.parent_hash();
+            let parent_root = payload
+                .parent_beacon_block_root()
+                .ok_or_else(|| eyre::eyre!("payload without parent beacon block root"))?;
+            payload = match self.cached_payload(payload.block_number() - 1, parent_hash) {
+                Some(parent) => parent,
+                None => self.payload_by_hash(parent_root, parent_hash).await?,
+            };
+        }
+
+        Ok((self.convert)(payload))
+    }
+}
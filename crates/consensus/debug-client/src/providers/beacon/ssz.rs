@@ -0,0 +1,137 @@
+//! SSZ merkleization helpers for the light client containers.
+
+use alloy_primitives::{Address, B256};
+use sha2::{Digest, Sha256};
+use std::sync::LazyLock;
+
+/// Maximum depth of the merkle trees the light client computes.
+const MAX_DEPTH: usize = 32;
+
+/// Roots of all-zero subtrees, indexed by depth.
+static ZERO_HASHES: LazyLock<[B256; MAX_DEPTH + 1]> = LazyLock::new(|| {
+    let mut hashes = [B256::ZERO; MAX_DEPTH + 1];
+    for depth in 1..=MAX_DEPTH {
+        hashes[depth] = hash_pair(hashes[depth - 1], hashes[depth - 1]);
+    }
+    hashes
+});
+
+/// Hashes two chunks into their parent.
+pub(crate) fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// Merkleizes the given leaves into a tree with room for `limit` leaves, padding them with zero
+/// leaves.
+pub(crate) fn merkleize(leaves: &[B256], limit: usize) -> B256 {
+    debug_assert!(leaves.len() <= limit.max(1));
+    let depth = limit.max(1).next_power_of_two().trailing_zeros() as usize;
+
+    let mut layer = leaves.to_vec();
+    for zero_hash in &ZERO_HASHES[..depth] {
+        if layer.len() % 2 == 1 {
+            layer.push(*zero_hash);
+        }
+        layer = layer.chunks(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+    }
+    layer.first().copied().unwrap_or(ZERO_HASHES[depth])
+}
+
+/// Mixes the length of a list into its root.
+pub(crate) fn mix_in_length(root: B256, length: usize) -> B256 {
+    hash_pair(root, uint64_leaf(length as u64))
+}
+
+/// Packs the given bytes into zero padded chunks.
+pub(crate) fn pack_bytes(bytes: &[u8]) -> Vec<B256> {
+    bytes
+        .chunks(32)
+        .map(|chunk| {
+            let mut leaf = B256::ZERO;
+            leaf[..chunk.len()].copy_from_slice(chunk);
+            leaf
+        })
+        .collect()
+}
+
+/// Returns the hash tree root of a `ByteList` with room for `limit` bytes.
+pub(crate) fn byte_list_root(bytes: &[u8], limit: usize) -> B256 {
+    mix_in_length(merkleize(&pack_bytes(bytes), limit.div_ceil(32)), bytes.len())
+}
+
+/// Returns the hash tree root of a fixed size byte vector of more than 32 bytes, like a BLS
+/// public key.
+pub(crate) fn byte_vector_root(bytes: &[u8]) -> B256 {
+    let chunks = pack_bytes(bytes);
+    merkleize(&chunks, chunks.len())
+}
+
+/// Returns the leaf of a `uint64`.
+pub(crate) fn uint64_leaf(value: u64) -> B256 {
+    let mut leaf = B256::ZERO;
+    leaf[..8].copy_from_slice(&value.to_le_bytes());
+    leaf
+}
+
+/// Returns the leaf of an execution address.
+pub(crate) fn address_leaf(address: Address) -> B256 {
+    let mut leaf = B256::ZERO;
+    leaf[..20].copy_from_slice(address.as_slice());
+    leaf
+}
+
+/// Returns `true` if `branch` proves `leaf` at the given generalized index of the tree with the
+/// given `root`.
+pub(crate) fn is_valid_merkle_branch(
+    leaf: B256,
+    branch: &[B256],
+    generalized_index: u64,
+    root: B256,
+) -> bool {
+    let depth = generalized_index.ilog2() as usize;
+    if branch.len() != depth {
+        return false
+    }
+
+    let mut node = leaf;
+    for (level, sibling) in branch.iter().enumerate() {
+        node = if (generalized_index >> level) & 1 == 1 {
+            hash_pair(*sibling, node)
+        } else {
+            hash_pair(node, *sibling)
+        };
+    }
+    node == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn zero_hashes() {
+        assert_eq!(
+            ZERO_HASHES[1],
+            b256!("0xf5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b")
+        );
+        assert_eq!(merkleize(&[], 4), ZERO_HASHES[2]);
+        assert_eq!(merkleize(&[B256::ZERO; 3], 4), ZERO_HASHES[2]);
+    }
+
+    #[test]
+    fn merkle_branch() {
+        let leaves = [B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3)];
+        let root = merkleize(&leaves, 4);
+
+        // generalized index of the third leaf of a tree of depth 2
+        let branch = [ZERO_HASHES[0], hash_pair(leaves[0], leaves[1])];
+        assert!(is_valid_merkle_branch(leaves[2], &branch, 6, root));
+        assert!(!is_valid_merkle_branch(leaves[2], &branch, 7, root));
+        assert!(!is_valid_merkle_branch(leaves[1], &branch, 6, root));
+        assert!(!is_valid_merkle_branch(leaves[2], &branch[..1], 6, root));
+    }
+}
@@ -0,0 +1,296 @@
+//! Light client store that verifies and applies light client updates.
+//!
+//! Follows the light client sync protocol of the consensus specs, see
+//! <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md>.
+
+use super::{
+    config::{
+        sync_committee_period, BeaconFork, LightClientConfig, StateProofIndices,
+        EXECUTION_PAYLOAD_INDEX, SYNC_COMMITTEE_SIZE,
+    },
+    ssz::{hash_pair, is_valid_merkle_branch},
+    types::{LightClientBootstrap, LightClientHeader, LightClientUpdate, SyncCommittee},
+    LightClientError,
+};
+use alloy_primitives::B256;
+use blst::{
+    min_pk::{PublicKey, Signature},
+    BLST_ERROR,
+};
+
+/// Domain separation tag of the BLS signature scheme used by the consensus layer.
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// A sync committee whose public keys have been validated.
+#[derive(Debug, Clone)]
+struct VerifiedSyncCommittee {
+    /// Hash tree root of the committee.
+    root: B256,
+    /// Public keys of the committee members.
+    pubkeys: Vec<PublicKey>,
+}
+
+impl TryFrom<&SyncCommittee> for VerifiedSyncCommittee {
+    type Error = LightClientError;
+
+    fn try_from(committee: &SyncCommittee) -> Result<Self, Self::Error> {
+        if committee.pubkeys.len() != SYNC_COMMITTEE_SIZE {
+            return Err(LightClientError::InvalidSyncCommittee)
+        }
+        let pubkeys = committee
+            .pubkeys
+            .iter()
+            .map(|pubkey| PublicKey::key_validate(pubkey.as_slice()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| LightClientError::InvalidSyncCommittee)?;
+        Ok(Self { root: committee.hash_tree_root(), pubkeys })
+    }
+}
+
+/// Tracks the finalized and optimistic beacon headers, authenticated by sync committee
+/// signatures.
+///
+/// Unlike the spec, updates are only applied with a supermajority of the sync committee, so there
+/// are no safety thresholds or forced updates.
+#[derive(Debug, Clone)]
+pub(crate) struct LightClientStore {
+    /// Latest finalized header.
+    finalized_header: LightClientHeader,
+    /// Latest header signed by the sync committee.
+    optimistic_header: LightClientHeader,
+    /// Sync committee of the finalized header's period.
+    current_sync_committee: VerifiedSyncCommittee,
+    /// Sync committee of the period after the finalized header's, if known.
+    next_sync_committee: Option<VerifiedSyncCommittee>,
+}
+
+impl LightClientStore {
+    /// Initializes the store from the bootstrap of the trusted checkpoint block root.
+    pub(crate) fn bootstrap(
+        config: &LightClientConfig,
+        checkpoint: B256,
+        bootstrap: &LightClientBootstrap,
+    ) -> Result<Self, LightClientError> {
+        let header = &bootstrap.header;
+        validate_header(config, header)?;
+
+        let root = header.beacon.hash_tree_root();
+        if root != checkpoint {
+            return Err(LightClientError::CheckpointMismatch { expected: checkpoint, got: root })
+        }
+
+        let indices = StateProofIndices::for_fork(config.fork_at_slot(header.beacon.slot).fork);
+        if !is_valid_merkle_branch(
+            bootstrap.current_sync_committee.hash_tree_root(),
+            &bootstrap.current_sync_committee_branch,
+            indices.current_sync_committee,
+            header.beacon.state_root,
+        ) {
+            return Err(LightClientError::InvalidSyncCommitteeBranch)
+        }
+
+        Ok(Self {
+            finalized_header: header.clone(),
+            optimistic_header: header.clone(),
+            current_sync_committee: (&bootstrap.current_sync_committee).try_into()?,
+            next_sync_committee: None,
+        })
+    }
+
+    /// Returns the latest finalized header.
+    pub(crate) const fn finalized_header(&self) -> &LightClientHeader {
+        &self.finalized_header
+    }
+
+    /// Returns the latest header signed by the sync committee.
+    pub(crate) const fn optimistic_header(&self) -> &LightClientHeader {
+        &self.optimistic_header
+    }
+
+    /// Returns the sync committee period of the finalized header.
+    pub(crate) const fn period(&self) -> u64 {
+        sync_committee_period(self.finalized_header.beacon.slot)
+    }
+
+    /// Returns `true` if the sync committee of the next period is known.
+    pub(crate) const fn is_next_sync_committee_known(&self) -> bool {
+        self.next_sync_committee.is_some()
+    }
+
+    /// Verifies the update and applies it to the store.
+    ///
+    /// Returns `false` if the update is valid but doesn't advance the store.
+    pub(crate) fn process_update(
+        &mut self,
+        config: &LightClientConfig,
+        update: &LightClientUpdate,
+        current_slot: u64,
+    ) -> Result<bool, LightClientError> {
+        let participants = update.sync_aggregate.participants();
+        if participants * 3 < SYNC_COMMITTEE_SIZE * 2 {
+            return Err(LightClientError::InsufficientParticipation(participants))
+        }
+
+        let attested = &update.attested_header;
+        validate_header(config, attested)?;
+
+        let finalized_slot = update.finalized_header.as_ref().map(|header| header.beacon.slot);
+        if current_slot < update.signature_slot ||
+            update.signature_slot <= attested.beacon.slot ||
+            finalized_slot.is_some_and(|slot| slot > attested.beacon.slot)
+        {
+            return Err(LightClientError::InvalidSlots)
+        }
+
+        let store_period = self.period();
+        let signature_period = sync_committee_period(update.signature_slot);
+        let signing_committee = if signature_period == store_period {
+            &self.current_sync_committee
+        } else if signature_period == store_period + 1 &&
+            let Some(next) = &self.next_sync_committee
+        {
+            next
+        } else {
+            return Err(LightClientError::UnexpectedSignaturePeriod(signature_period))
+        };
+
+        let attested_period = sync_committee_period(attested.beacon.slot);
+        let has_next_sync_committee = update.next_sync_committee.is_some();
+        let is_relevant = attested.beacon.slot > self.optimistic_header.beacon.slot ||
+            finalized_slot.is_some_and(|slot| slot > self.finalized_header.beacon.slot) ||
+            (attested_period == store_period &&
+                has_next_sync_committee &&
+                !self.is_next_sync_committee_known());
+        if !is_relevant {
+            return Ok(false)
+        }
+
+        let indices = StateProofIndices::for_fork(config.fork_at_slot(attested.beacon.slot).fork);
+
+        if let Some(finalized) = &update.finalized_header {
+            validate_header(config, finalized)?;
+            let branch = update.finality_branch.as_deref().unwrap_or_default();
+            if !is_valid_merkle_branch(
+                finalized.beacon.hash_tree_root(),
+                branch,
+                indices.finalized_root,
+                attested.beacon.state_root,
+            ) {
+                return Err(LightClientError::InvalidFinalityBranch)
+            }
+        }
+
+        let next_sync_committee = match &update.next_sync_committee {
+            Some(committee) => {
+                let root = committee.hash_tree_root();
+                if attested_period == store_period &&
+                    let Some(next) = &self.next_sync_committee &&
+                    next.root != root
+                {
+                    return Err(LightClientError::SyncCommitteeMismatch)
+                }
+                let branch = update.next_sync_committee_branch.as_deref().unwrap_or_default();
+                if !is_valid_merkle_branch(
+                    root,
+                    branch,
+                    indices.next_sync_committee,
+                    attested.beacon.state_root,
+                ) {
+                    return Err(LightClientError::InvalidSyncCommitteeBranch)
+                }
+                Some(committee)
+            }
+            None => None,
+        };
+
+        verify_sync_aggregate(config, signing_committee, update)?;
+
+        // Only verified data is applied from here on.
+        if let Some(finalized) = &update.finalized_header {
+            let finalized_period = sync_committee_period(finalized.beacon.slot);
+            if !self.is_next_sync_committee_known() {
+                if finalized_period == store_period && attested_period == store_period {
+                    self.next_sync_committee =
+                        next_sync_committee.map(TryInto::try_into).transpose()?;
+                }
+            } else if finalized_period == store_period + 1 {
+                self.current_sync_committee =
+                    self.next_sync_committee.take().expect("next sync committee is known");
+                self.next_sync_committee = next_sync_committee
+                    .filter(|_| attested_period == finalized_period)
+                    .map(TryInto::try_into)
+                    .transpose()?;
+            }
+
+            if finalized.beacon.slot > self.finalized_header.beacon.slot {
+                self.finalized_header = finalized.clone();
+                if finalized.beacon.slot > self.optimistic_header.beacon.slot {
+                    self.optimistic_header = finalized.clone();
+                }
+            }
+        }
+
+        if attested.beacon.slot > self.optimistic_header.beacon.slot {
+            self.optimistic_header = attested.clone();
+        }
+
+        Ok(true)
+    }
+}
+
+/// Validates that the header is of a supported fork and that its execution payload header is
+/// part of the beacon block.
+pub(crate) fn validate_header(
+    config: &LightClientConfig,
+    header: &LightClientHeader,
+) -> Result<(), LightClientError> {
+    if config.fork_at_slot(header.beacon.slot).fork < BeaconFork::Deneb {
+        return Err(LightClientError::UnsupportedFork(header.beacon.slot))
+    }
+
+    if !is_valid_merkle_branch(
+        header.execution.hash_tree_root(),
+        &header.execution_branch,
+        EXECUTION_PAYLOAD_INDEX,
+        header.beacon.body_root,
+    ) {
+        return Err(LightClientError::InvalidExecutionBranch)
+    }
+
+    Ok(())
+}
+
+/// Verifies the signature of the participating sync committee members over the attested header.
+fn verify_sync_aggregate(
+    config: &LightClientConfig,
+    committee: &VerifiedSyncCommittee,
+    update: &LightClientUpdate,
+) -> Result<(), LightClientError> {
+    let aggregate = &update.sync_aggregate;
+    if aggregate.sync_committee_bits.len() != SYNC_COMMITTEE_SIZE / 8 {
+        return Err(LightClientError::InvalidSignature)
+    }
+
+    let pubkeys = committee
+        .pubkeys
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| aggregate.participated(*index))
+        .map(|(_, pubkey)| pubkey)
+        .collect::<Vec<_>>();
+
+    let signing_root = hash_pair(
+        update.attested_header.beacon.hash_tree_root(),
+        config.sync_committee_domain(update.signature_slot),
+    );
+    let signature = Signature::from_bytes(aggregate.sync_committee_signature.as_slice())
+        .map_err(|_| LightClientError::InvalidSignature)?;
+
+    if signature.fast_aggregate_verify(true, signing_root.as_slice(), BLS_DST, &pubkeys) !=
+        BLST_ERROR::BLST_SUCCESS
+    {
+        return Err(LightClientError::InvalidSignature)
+    }
+
+    Ok(())
+}
@@ -0,0 +1,456 @@
+//! Beacon API light client types and their hash tree roots.
+
+use super::ssz::{
+    address_leaf, byte_list_root, byte_vector_root, hash_pair, merkleize, mix_in_length,
+    uint64_leaf,
+};
+use alloy_eips::eip4895::Withdrawal;
+use alloy_primitives::{Address, Bloom, Bytes, FixedBytes, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of transactions of an execution payload.
+const MAX_TRANSACTIONS_PER_PAYLOAD: usize = 1 << 20;
+
+/// Maximum size of a transaction of an execution payload.
+const MAX_BYTES_PER_TRANSACTION: usize = 1 << 30;
+
+/// Maximum number of withdrawals of an execution payload.
+const MAX_WITHDRAWALS_PER_PAYLOAD: usize = 16;
+
+/// Maximum size of the extra data of an execution payload.
+const MAX_EXTRA_DATA_BYTES: usize = 32;
+
+/// A BLS public key.
+pub(crate) type BlsPublicKey = FixedBytes<48>;
+
+/// A BLS signature.
+pub(crate) type BlsSignature = FixedBytes<96>;
+
+/// Envelope of versioned Beacon API responses.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct VersionedResponse<T> {
+    /// The response data.
+    pub(crate) data: T,
+}
+
+/// A beacon block header.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BeaconBlockHeader {
+    /// Slot of the block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub slot: u64,
+    /// Index of the block proposer.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub proposer_index: u64,
+    /// Root of the parent block.
+    pub parent_root: B256,
+    /// Root of the post state.
+    pub state_root: B256,
+    /// Root of the block body.
+    pub body_root: B256,
+}
+
+impl BeaconBlockHeader {
+    /// Computes the hash tree root, which is the root of the beacon block.
+    pub fn hash_tree_root(&self) -> B256 {
+        merkleize(
+            &[
+                uint64_leaf(self.slot),
+                uint64_leaf(self.proposer_index),
+                self.parent_root,
+                self.state_root,
+                self.body_root,
+            ],
+            5,
+        )
+    }
+}
+
+/// Header of an execution payload, as of Deneb.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ExecutionPayloadHeader {
+    /// Hash of the parent block.
+    pub parent_hash: B256,
+    /// Fee recipient of the block.
+    pub fee_recipient: Address,
+    /// State root of the block.
+    pub state_root: B256,
+    /// Receipts root of the block.
+    pub receipts_root: B256,
+    /// Logs bloom of the block.
+    pub logs_bloom: Bloom,
+    /// Randao value of the block.
+    pub prev_randao: B256,
+    /// Number of the block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub block_number: u64,
+    /// Gas limit of the block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub gas_limit: u64,
+    /// Gas used by the block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub gas_used: u64,
+    /// Timestamp of the block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub timestamp: u64,
+    /// Extra data of the block.
+    pub extra_data: Bytes,
+    /// Base fee of the block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub base_fee_per_gas: U256,
+    /// Hash of the block.
+    pub block_hash: B256,
+    /// Hash tree root of the transactions of the block.
+    pub transactions_root: B256,
+    /// Hash tree root of the withdrawals of the block.
+    pub withdrawals_root: B256,
+    /// Blob gas used by the block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub blob_gas_used: u64,
+    /// Excess blob gas of the block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub excess_blob_gas: u64,
+}
+
+impl ExecutionPayloadHeader {
+    /// Computes the hash tree root, which is the leaf the execution branch of a
+    /// [`LightClientHeader`] proves.
+    pub fn hash_tree_root(&self) -> B256 {
+        merkleize(
+            &[
+                self.parent_hash,
+                address_leaf(self.fee_recipient),
+                self.state_root,
+                self.receipts_root,
+                byte_vector_root(self.logs_bloom.as_slice()),
+                self.prev_randao,
+                uint64_leaf(self.block_number),
+                uint64_leaf(self.gas_limit),
+                uint64_leaf(self.gas_used),
+                uint64_leaf(self.timestamp),
+                byte_list_root(&self.extra_data, MAX_EXTRA_DATA_BYTES),
+                B256::from(self.base_fee_per_gas.to_le_bytes::<{ U256::BYTES }>()),
+                self.block_hash,
+                self.transactions_root,
+                self.withdrawals_root,
+                uint64_leaf(self.blob_gas_used),
+                uint64_leaf(self.excess_blob_gas),
+            ],
+            17,
+        )
+    }
+}
+
+/// A beacon block header together with the proven header of its execution payload.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LightClientHeader {
+    /// The beacon block header.
+    pub beacon: BeaconBlockHeader,
+    /// The header of the execution payload of the block.
+    pub execution: ExecutionPayloadHeader,
+    /// Proof of the execution payload header against the beacon block body root.
+    pub execution_branch: Vec<B256>,
+}
+
+/// The committee of validators signing blocks during a sync committee period.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SyncCommittee {
+    /// Public keys of the committee members.
+    pub pubkeys: Vec<BlsPublicKey>,
+    /// Aggregate of all public keys of the committee.
+    pub aggregate_pubkey: BlsPublicKey,
+}
+
+impl SyncCommittee {
+    /// Computes the hash tree root, which is the leaf the sync committee branches prove.
+    pub fn hash_tree_root(&self) -> B256 {
+        let pubkeys = self
+            .pubkeys
+            .iter()
+            .map(|pubkey| byte_vector_root(pubkey.as_slice()))
+            .collect::<Vec<_>>();
+        hash_pair(
+            merkleize(&pubkeys, pubkeys.len()),
+            byte_vector_root(self.aggregate_pubkey.as_slice()),
+        )
+    }
+}
+
+/// Aggregated signature of the sync committee members over a block root.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SyncAggregate {
+    /// Bitfield of the committee members that participated in the signature.
+    pub sync_committee_bits: Bytes,
+    /// The aggregated signature.
+    pub sync_committee_signature: BlsSignature,
+}
+
+impl SyncAggregate {
+    /// Returns `true` if the committee member at `index` participated in the signature.
+    pub fn participated(&self, index: usize) -> bool {
+        self.sync_committee_bits.get(index / 8).is_some_and(|byte| byte & (1 << (index % 8)) != 0)
+    }
+
+    /// Returns the number of committee members that participated in the signature.
+    pub fn participants(&self) -> usize {
+        self.sync_committee_bits.iter().map(|byte| byte.count_ones() as usize).sum()
+    }
+}
+
+/// Response of the light client bootstrap endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LightClientBootstrap {
+    /// Header of the trusted checkpoint block.
+    pub header: LightClientHeader,
+    /// Sync committee of the checkpoint block's period.
+    pub current_sync_committee: SyncCommittee,
+    /// Proof of the sync committee against the checkpoint block's state root.
+    pub current_sync_committee_branch: Vec<B256>,
+}
+
+/// A light client update.
+///
+/// Covers the responses of the updates, finality update and optimistic update endpoints. Fields
+/// that are not part of an update are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LightClientUpdate {
+    /// Header the sync committee signed.
+    pub attested_header: LightClientHeader,
+    /// Sync committee of the next period, proven against the attested state.
+    #[serde(default)]
+    pub next_sync_committee: Option<SyncCommittee>,
+    /// Proof of the next sync committee.
+    #[serde(default)]
+    pub next_sync_committee_branch: Option<Vec<B256>>,
+    /// Finalized header, proven against the attested state.
+    #[serde(default)]
+    pub finalized_header: Option<LightClientHeader>,
+    /// Proof of the finalized header.
+    #[serde(default)]
+    pub finality_branch: Option<Vec<B256>>,
+    /// Signature of the sync committee over the attested header.
+    pub sync_aggregate: SyncAggregate,
+    /// Slot at which the signature was included.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub signature_slot: u64,
+}
+
+/// Response of the block endpoint. Only the fields needed to assemble the execution payload are
+/// decoded.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct SignedBeaconBlock {
+    /// The block.
+    pub(crate) message: BeaconBlock,
+}
+
+/// A beacon block.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct BeaconBlock {
+    /// Slot of the block.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) slot: u64,
+    /// Root of the parent block.
+    pub(crate) parent_root: B256,
+    /// The block body.
+    pub(crate) body: BeaconBlockBody,
+}
+
+/// Body of a beacon block.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct BeaconBlockBody {
+    /// The execution payload.
+    pub(crate) execution_payload: ExecutionPayload,
+    /// KZG commitments of the blobs of the payload.
+    #[serde(default)]
+    pub(crate) blob_kzg_commitments: Vec<FixedBytes<48>>,
+    /// Execution layer requests of the payload, as of Electra.
+    #[serde(default)]
+    pub(crate) execution_requests: Option<ExecutionRequests>,
+}
+
+/// An execution payload, as of Deneb.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct ExecutionPayload {
+    pub(crate) parent_hash: B256,
+    pub(crate) fee_recipient: Address,
+    pub(crate) state_root: B256,
+    pub(crate) receipts_root: B256,
+    pub(crate) logs_bloom: Bloom,
+    pub(crate) prev_randao: B256,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) block_number: u64,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) gas_limit: u64,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) gas_used: u64,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) timestamp: u64,
+    pub(crate) extra_data: Bytes,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) base_fee_per_gas: U256,
+    pub(crate) block_hash: B256,
+    pub(crate) transactions: Vec<Bytes>,
+    pub(crate) withdrawals: Vec<BeaconWithdrawal>,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) blob_gas_used: u64,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) excess_blob_gas: u64,
+}
+
+impl ExecutionPayload {
+    /// Returns the header of the payload.
+    pub(crate) fn header(&self) -> ExecutionPayloadHeader {
+        let transactions = self
+            .transactions
+            .iter()
+            .map(|tx| byte_list_root(tx, MAX_BYTES_PER_TRANSACTION))
+            .collect::<Vec<_>>();
+        let withdrawals =
+            self.withdrawals.iter().map(BeaconWithdrawal::hash_tree_root).collect::<Vec<_>>();
+
+        ExecutionPayloadHeader {
+            parent_hash: self.parent_hash,
+            fee_recipient: self.fee_recipient,
+            state_root: self.state_root,
+            receipts_root: self.receipts_root,
+            logs_bloom: self.logs_bloom,
+            prev_randao: self.prev_randao,
+            block_number: self.block_number,
+            gas_limit: self.gas_limit,
+            gas_used: self.gas_used,
+            timestamp: self.timestamp,
+            extra_data: self.extra_data.clone(),
+            base_fee_per_gas: self.base_fee_per_gas,
+            block_hash: self.block_hash,
+            transactions_root: mix_in_length(
+                merkleize(&transactions, MAX_TRANSACTIONS_PER_PAYLOAD),
+                transactions.len(),
+            ),
+            withdrawals_root: mix_in_length(
+                merkleize(&withdrawals, MAX_WITHDRAWALS_PER_PAYLOAD),
+                withdrawals.len(),
+            ),
+            blob_gas_used: self.blob_gas_used,
+            excess_blob_gas: self.excess_blob_gas,
+        }
+    }
+}
+
+/// A withdrawal of an execution payload, as encoded by the Beacon API.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub(crate) struct BeaconWithdrawal {
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) index: u64,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) validator_index: u64,
+    pub(crate) address: Address,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) amount: u64,
+}
+
+impl BeaconWithdrawal {
+    fn hash_tree_root(&self) -> B256 {
+        merkleize(
+            &[
+                uint64_leaf(self.index),
+                uint64_leaf(self.validator_index),
+                address_leaf(self.address),
+                uint64_leaf(self.amount),
+            ],
+            4,
+        )
+    }
+}
+
+impl From<BeaconWithdrawal> for Withdrawal {
+    fn from(withdrawal: BeaconWithdrawal) -> Self {
+        Self {
+            index: withdrawal.index,
+            validator_index: withdrawal.validator_index,
+            address: withdrawal.address,
+            amount: withdrawal.amount,
+        }
+    }
+}
+
+/// Execution layer requests of a beacon block.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub(crate) struct ExecutionRequests {
+    #[serde(default)]
+    pub(crate) deposits: Vec<DepositRequest>,
+    #[serde(default)]
+    pub(crate) withdrawals: Vec<WithdrawalRequest>,
+    #[serde(default)]
+    pub(crate) consolidations: Vec<ConsolidationRequest>,
+}
+
+impl ExecutionRequests {
+    /// Returns the EIP-7685 encoding of the requests: the request type followed by the SSZ
+    /// encoding of the requests of that type. Request types without requests are omitted.
+    pub(crate) fn encode(&self) -> Vec<Bytes> {
+        let mut requests = Vec::new();
+
+        if !self.deposits.is_empty() {
+            let mut encoded = vec![0x00];
+            for deposit in &self.deposits {
+                encoded.extend_from_slice(deposit.pubkey.as_slice());
+                encoded.extend_from_slice(deposit.withdrawal_credentials.as_slice());
+                encoded.extend_from_slice(&deposit.amount.to_le_bytes());
+                encoded.extend_from_slice(deposit.signature.as_slice());
+                encoded.extend_from_slice(&deposit.index.to_le_bytes());
+            }
+            requests.push(encoded.into());
+        }
+
+        if !self.withdrawals.is_empty() {
+            let mut encoded = vec![0x01];
+            for withdrawal in &self.withdrawals {
+                encoded.extend_from_slice(withdrawal.source_address.as_slice());
+                encoded.extend_from_slice(withdrawal.validator_pubkey.as_slice());
+                encoded.extend_from_slice(&withdrawal.amount.to_le_bytes());
+            }
+            requests.push(encoded.into());
+        }
+
+        if !self.consolidations.is_empty() {
+            let mut encoded = vec![0x02];
+            for consolidation in &self.consolidations {
+                encoded.extend_from_slice(consolidation.source_address.as_slice());
+                encoded.extend_from_slice(consolidation.source_pubkey.as_slice());
+                encoded.extend_from_slice(consolidation.target_pubkey.as_slice());
+            }
+            requests.push(encoded.into());
+        }
+
+        requests
+    }
+}
+
+/// An EIP-6110 deposit request.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct DepositRequest {
+    pub(crate) pubkey: BlsPublicKey,
+    pub(crate) withdrawal_credentials: B256,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) amount: u64,
+    pub(crate) signature: BlsSignature,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) index: u64,
+}
+
+/// An EIP-7002 withdrawal request.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct WithdrawalRequest {
+    pub(crate) source_address: Address,
+    pub(crate) validator_pubkey: BlsPublicKey,
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub(crate) amount: u64,
+}
+
+/// An EIP-7251 consolidation request.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct ConsolidationRequest {
+    pub(crate) source_address: Address,
+    pub(crate) source_pubkey: BlsPublicKey,
+    pub(crate) target_pubkey: BlsPublicKey,
+}
@@ -1,5 +1,11 @@
+mod beacon;
 mod etherscan;
 mod rpc;
 
+pub use beacon::{
+    BeaconBlockHeader, BeaconFork, BeaconLightClientProvider, ExecutionPayloadHeader,
+    ForkActivation, LightClientBootstrap, LightClientConfig, LightClientError, LightClientHeader,
+    LightClientUpdate, SyncAggregate, SyncCommittee,
+};
 pub use etherscan::EtherscanBlockProvider;
 pub use rpc::RpcBlockProvider;
@@ -0,0 +1,574 @@
+//! Tests the beacon light client provider against light client fixtures served by a mock Beacon
+//! API.
+
+use alloy_primitives::{b256, Address, Bloom, Bytes, FixedBytes, B256, U256};
+use blst::min_pk::{AggregateSignature, SecretKey};
+use reth_consensus_debug_client::{
+    BeaconBlockHeader, BeaconFork, BeaconLightClientProvider, ExecutionPayloadHeader,
+    ForkActivation, LightClientBootstrap, LightClientConfig, LightClientError, LightClientHeader,
+    LightClientUpdate, PayloadProvider, SyncAggregate, SyncCommittee,
+};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+};
+
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Returns the generalized index of the field at `field_index` of a container whose fields are
+/// merkleized into a tree of the given depth.
+const fn field_index(depth: u32, field_index: u64) -> u64 {
+    (1 << depth) + field_index
+}
+
+/// Generalized index of the execution payload, the 10th of the 12 (Deneb) or 13 (Electra) fields
+/// of the beacon block body.
+const EXECUTION_PAYLOAD_INDEX: u64 = field_index(4, 9);
+
+/// Generalized indices of the light client proofs against a beacon state.
+#[derive(Clone, Copy)]
+struct StateIndices {
+    /// The root of the finalized checkpoint, the second field of the `finalized_checkpoint`.
+    finalized_root: u64,
+    current_sync_committee: u64,
+    next_sync_committee: u64,
+}
+
+impl StateIndices {
+    /// Returns the indices of the beacon state of the given fork, which grew from 28 fields in
+    /// Deneb to 37 fields in Electra.
+    fn for_fork(fork: BeaconFork) -> Self {
+        let depth = if fork >= BeaconFork::Electra { 6 } else { 5 };
+        Self {
+            finalized_root: field_index(depth, 20) * 2 + 1,
+            current_sync_committee: field_index(depth, 22),
+            next_sync_committee: field_index(depth, 23),
+        }
+    }
+}
+
+/// Sync committee signing domains of mainnet, `DOMAIN_SYNC_COMMITTEE` followed by the first 28
+/// bytes of the fork data root, whose first four bytes are the mainnet fork digests.
+const MAINNET_DENEB_SYNC_COMMITTEE_DOMAIN: B256 =
+    b256!("0x070000006a95a1a967855d676d48be69883b712607f952d5198d0f5677564636");
+const MAINNET_ELECTRA_SYNC_COMMITTEE_DOMAIN: B256 =
+    b256!("0x07000000ad532ceb9ec5d246daad29da8aa157bfdab35e5f069f9db81f1da754");
+
+/// Slots of the fixture blocks, which are all in sync committee period 1.
+const SLOTS: [u64; 5] = [8256, 8300, 8350, 8400, 8401];
+
+/// First execution block number of the fixture chain.
+const FIRST_BLOCK: u64 = 100;
+
+fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// A merkle tree with the given leaves, all other nodes down to depth 8 are zero.
+struct Tree(BTreeMap<u64, B256>);
+
+impl Tree {
+    fn node(&self, index: u64) -> B256 {
+        if let Some(leaf) = self.0.get(&index) {
+            return *leaf
+        }
+        if index >= 1 << 8 {
+            return B256::ZERO
+        }
+        hash_pair(self.node(2 * index), self.node(2 * index + 1))
+    }
+
+    fn root(&self) -> B256 {
+        self.node(1)
+    }
+
+    fn branch(&self, mut index: u64) -> Vec<B256> {
+        let mut branch = Vec::new();
+        while index > 1 {
+            branch.push(self.node(index ^ 1));
+            index /= 2;
+        }
+        branch
+    }
+}
+
+/// Light client fixtures of a chain of five blocks signed by a single key.
+struct Fixture {
+    config: LightClientConfig,
+    fork: BeaconFork,
+    indices: StateIndices,
+    secret_key: SecretKey,
+    committee: SyncCommittee,
+    headers: Vec<LightClientHeader>,
+    state_trees: Vec<Tree>,
+    blocks: HashMap<B256, Value>,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        Self::with_fork(BeaconFork::Electra)
+    }
+
+    /// Creates the fixture of a chain that uses the mainnet genesis validators root and fork
+    /// version of the given fork.
+    fn with_fork(fork: BeaconFork) -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let mainnet = LightClientConfig::mainnet();
+        let version =
+            mainnet.forks.iter().find(|activation| activation.fork == fork).unwrap().version;
+        let config = LightClientConfig {
+            genesis_time: now - (SLOTS[4] + 1) * 12,
+            genesis_validators_root: mainnet.genesis_validators_root,
+            seconds_per_slot: 12,
+            forks: vec![ForkActivation { fork, epoch: 0, version }],
+        };
+
+        let secret_key = SecretKey::key_gen(&[1; 32], &[]).unwrap();
+        let pubkey = FixedBytes::from(secret_key.sk_to_pk().to_bytes());
+        let committee = SyncCommittee { pubkeys: vec![pubkey; 512], aggregate_pubkey: pubkey };
+
+        let mut fixture = Self {
+            config,
+            fork,
+            indices: StateIndices::for_fork(fork),
+            secret_key,
+            committee,
+            headers: Vec::new(),
+            state_trees: Vec::new(),
+            blocks: HashMap::new(),
+        };
+        for (index, slot) in SLOTS.into_iter().enumerate() {
+            fixture.push_block(slot, finalized_by(index));
+        }
+        fixture
+    }
+
+    fn push_block(&mut self, slot: u64, finalized: Option<usize>) {
+        let number = FIRST_BLOCK + self.headers.len() as u64;
+        let parent = self.headers.last();
+        let execution = ExecutionPayloadHeader {
+            parent_hash: parent.map(|header| header.execution.block_hash).unwrap_or_default(),
+            fee_recipient: Address::repeat_byte(1),
+            state_root: B256::repeat_byte(2),
+            receipts_root: B256::repeat_byte(3),
+            logs_bloom: Bloom::default(),
+            prev_randao: B256::repeat_byte(4),
+            block_number: number,
+            gas_limit: 36_000_000,
+            gas_used: 21_000,
+            timestamp: self.config.genesis_time + slot * 12,
+            extra_data: Bytes::from_static(b"reth"),
+            base_fee_per_gas: U256::from(7),
+            block_hash: B256::with_last_byte(number as u8),
+            transactions_root: empty_list_root(20),
+            withdrawals_root: empty_list_root(4),
+            blob_gas_used: 0,
+            excess_blob_gas: 0,
+        };
+
+        let body = Tree([(EXECUTION_PAYLOAD_INDEX, execution.hash_tree_root())].into());
+        let committee_root = self.committee.hash_tree_root();
+        let mut state = BTreeMap::from([
+            (self.indices.current_sync_committee, committee_root),
+            (self.indices.next_sync_committee, committee_root),
+        ]);
+        if let Some(finalized) = finalized {
+            state.insert(
+                self.indices.finalized_root,
+                self.headers[finalized].beacon.hash_tree_root(),
+            );
+        }
+        let state = Tree(state);
+
+        let header = LightClientHeader {
+            beacon: BeaconBlockHeader {
+                slot,
+                proposer_index: 0,
+                parent_root: parent
+                    .map(|header| header.beacon.hash_tree_root())
+                    .unwrap_or_default(),
+                state_root: state.root(),
+                body_root: body.root(),
+            },
+            execution_branch: body.branch(EXECUTION_PAYLOAD_INDEX),
+            execution: execution.clone(),
+        };
+
+        let mut payload = serde_json::to_value(&execution).unwrap();
+        let payload_fields = payload.as_object_mut().unwrap();
+        payload_fields.remove("transactions_root");
+        payload_fields.remove("withdrawals_root");
+        payload_fields.insert("transactions".into(), json!([]));
+        payload_fields.insert("withdrawals".into(), json!([]));
+        let mut body = json!({ "execution_payload": payload, "blob_kzg_commitments": [] });
+        if self.fork == BeaconFork::Electra {
+            body["execution_requests"] =
+                json!({ "deposits": [], "withdrawals": [], "consolidations": [] });
+        }
+        let block = json!({
+            "version": fork_name(self.fork),
+            "execution_optimistic": false,
+            "finalized": false,
+            "data": {
+                "message": {
+                    "slot": slot.to_string(),
+                    "proposer_index": "0",
+                    "parent_root": header.beacon.parent_root,
+                    "state_root": header.beacon.state_root,
+                    "body": body
+                },
+                "signature": FixedBytes::<96>::ZERO
+            }
+        });
+
+        self.blocks.insert(header.beacon.hash_tree_root(), block);
+        self.headers.push(header);
+        self.state_trees.push(state);
+    }
+
+    fn checkpoint(&self) -> B256 {
+        self.headers[0].beacon.hash_tree_root()
+    }
+
+    fn bootstrap(&self) -> LightClientBootstrap {
+        LightClientBootstrap {
+            header: self.headers[0].clone(),
+            current_sync_committee: self.committee.clone(),
+            current_sync_committee_branch: self.state_trees[0]
+                .branch(self.indices.current_sync_committee),
+        }
+    }
+
+    /// Returns an update of the attested block, signed by the sync committee in the next slot.
+    fn update(&self, attested: usize, with_next_sync_committee: bool) -> LightClientUpdate {
+        let attested_header = self.headers[attested].clone();
+        let signature_slot = attested_header.beacon.slot + 1;
+        let finalized = finalized_by(attested);
+
+        LightClientUpdate {
+            sync_aggregate: self.sign(attested_header.beacon.hash_tree_root()),
+            attested_header,
+            next_sync_committee: with_next_sync_committee.then(|| self.committee.clone()),
+            next_sync_committee_branch: with_next_sync_committee
+                .then(|| self.state_trees[attested].branch(self.indices.next_sync_committee)),
+            finalized_header: finalized.map(|index| self.headers[index].clone()),
+            finality_branch: finalized
+                .map(|_| self.state_trees[attested].branch(self.indices.finalized_root)),
+            signature_slot,
+        }
+    }
+
+    fn sign(&self, block_root: B256) -> SyncAggregate {
+        let domain = match self.fork {
+            BeaconFork::Deneb => MAINNET_DENEB_SYNC_COMMITTEE_DOMAIN,
+            BeaconFork::Electra => MAINNET_ELECTRA_SYNC_COMMITTEE_DOMAIN,
+            fork => unreachable!("no fixture for {fork:?}"),
+        };
+        let signature =
+            self.secret_key.sign(hash_pair(block_root, domain).as_slice(), BLS_DST, &[]);
+        let aggregate = AggregateSignature::aggregate(&[&signature; 512], false).unwrap();
+        SyncAggregate {
+            sync_committee_bits: Bytes::from(vec![0xff; 64]),
+            sync_committee_signature: FixedBytes::from(aggregate.to_signature().to_bytes()),
+        }
+    }
+}
+
+/// Returns the name of the fork in Beacon API responses.
+const fn fork_name(fork: BeaconFork) -> &'static str {
+    match fork {
+        BeaconFork::Deneb => "deneb",
+        _ => "electra",
+    }
+}
+
+/// Returns the index of the block finalized by the state of the block at `index`: the second
+/// block finalizes the first and the fourth block finalizes the third.
+const fn finalized_by(index: usize) -> Option<usize> {
+    match index {
+        1 => Some(0),
+        3 => Some(2),
+        _ => None,
+    }
+}
+
+/// Returns the root of an empty SSZ list whose limit is `2^depth` chunks.
+fn empty_list_root(depth: usize) -> B256 {
+    let mut root = B256::ZERO;
+    for _ in 0..depth {
+        root = hash_pair(root, root);
+    }
+    hash_pair(root, B256::ZERO)
+}
+
+/// Responses of the mock Beacon API.
+#[derive(Clone)]
+struct BeaconApi {
+    bootstrap: LightClientBootstrap,
+    updates: Vec<LightClientUpdate>,
+    finality_update: LightClientUpdate,
+    optimistic_update: LightClientUpdate,
+    blocks: HashMap<B256, Value>,
+}
+
+impl BeaconApi {
+    fn new(fixture: &Fixture) -> Self {
+        Self {
+            bootstrap: fixture.bootstrap(),
+            updates: vec![fixture.update(1, true)],
+            finality_update: fixture.update(3, false),
+            optimistic_update: fixture.update(4, false),
+            blocks: fixture.blocks.clone(),
+        }
+    }
+}
+
+fn versioned(data: impl serde::Serialize) -> Value {
+    json!({ "version": "electra", "data": data })
+}
+
+/// Serves the light client endpoints of the given mock Beacon API.
+async fn spawn_beacon_api(api: BeaconApi) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (mut stream, head) = read_request(stream).await;
+            let path = head.split_whitespace().nth(1).unwrap_or_default().to_string();
+            let body = if path.starts_with("/eth/v1/beacon/light_client/bootstrap/") {
+                Some(versioned(&api.bootstrap))
+            } else if path.starts_with("/eth/v1/beacon/light_client/updates") {
+                Some(Value::Array(api.updates.iter().map(versioned).collect()))
+            } else if path == "/eth/v1/beacon/light_client/finality_update" {
+                Some(versioned(&api.finality_update))
+            } else if path == "/eth/v1/beacon/light_client/optimistic_update" {
+                Some(versioned(&api.optimistic_update))
+            } else if let Some(root) = path.strip_prefix("/eth/v2/beacon/blocks/") {
+                api.blocks.get(&root.parse::<B256>().unwrap()).cloned()
+            } else {
+                panic!("unexpected request: {head}");
+            };
+
+            let response = match body {
+                Some(body) => {
+                    let body = body.to_string();
+                    format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                }
+                None => "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                    .to_string(),
+            };
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+
+    url
+}
+
+/// Reads the head of a single HTTP request.
+async fn read_request(mut stream: TcpStream) -> (TcpStream, String) {
+    let mut buf = Vec::new();
+    loop {
+        let mut chunk = [0; 4096];
+        let read = stream.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..read]);
+        if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            return (stream, String::from_utf8_lossy(&buf[..pos]).to_string())
+        }
+    }
+}
+
+async fn provider(
+    fixture: &Fixture,
+    api: BeaconApi,
+) -> BeaconLightClientProvider<alloy_rpc_types_engine::ExecutionData> {
+    let url = spawn_beacon_api(api).await;
+    BeaconLightClientProvider::new(url, fixture.checkpoint(), fixture.config.clone(), |payload| {
+        payload
+    })
+    .with_interval(Duration::from_millis(10))
+}
+
+fn light_client_error(err: &eyre::Report) -> &LightClientError {
+    err.downcast_ref::<LightClientError>().unwrap_or_else(|| panic!("unexpected error: {err}"))
+}
+
+#[test]
+fn mainnet_genesis_block_root() {
+    let header = BeaconBlockHeader {
+        slot: 0,
+        proposer_index: 0,
+        parent_root: B256::ZERO,
+        state_root: b256!("0x7e76880eb67bbdc86250aa578958e9d0675e64e714337855204fb5abaaf82c2b"),
+        body_root: b256!("0xccb62460692be0ec813b56be97f68a82cf57abc102e27bf49ebf4190ff22eedd"),
+    };
+    assert_eq!(
+        header.hash_tree_root(),
+        b256!("0x4d611d5b93fdab69013a7f0a2f961caca0c853f87cfe9595fe50038163079360")
+    );
+}
+
+#[test]
+fn execution_payload_header_root() {
+    let header = ExecutionPayloadHeader {
+        parent_hash: B256::repeat_byte(1),
+        fee_recipient: Address::repeat_byte(2),
+        state_root: B256::repeat_byte(3),
+        receipts_root: B256::repeat_byte(4),
+        logs_bloom: Bloom::from_slice(&(0..=255).collect::<Vec<u8>>()),
+        prev_randao: B256::repeat_byte(5),
+        block_number: 6,
+        gas_limit: 7,
+        gas_used: 8,
+        timestamp: 9,
+        extra_data: Bytes::from_static(b"reth"),
+        base_fee_per_gas: U256::from(1_000_000_007),
+        block_hash: B256::repeat_byte(11),
+        transactions_root: B256::repeat_byte(12),
+        withdrawals_root: B256::repeat_byte(13),
+        blob_gas_used: 14,
+        excess_blob_gas: 15,
+    };
+    assert_eq!(
+        header.hash_tree_root(),
+        b256!("0x171e212808ab512d681faa79755e523354c419a8b34f4635dab2176428294728")
+    );
+}
+
+#[test]
+fn sync_committee_root() {
+    let committee = SyncCommittee {
+        pubkeys: (0..512u16)
+            .map(|index| {
+                let mut pubkey = FixedBytes::<48>::ZERO;
+                pubkey[..2].copy_from_slice(&index.to_be_bytes());
+                pubkey
+            })
+            .collect(),
+        aggregate_pubkey: FixedBytes::repeat_byte(0xff),
+    };
+    assert_eq!(
+        committee.hash_tree_root(),
+        b256!("0x44453209265f0885866be90fe8004889479c14273fbd20d839b25bd3d27ab2b9")
+    );
+}
+
+/// The derived indices match the `FINALIZED_ROOT_GINDEX`, `CURRENT_SYNC_COMMITTEE_GINDEX` and
+/// `NEXT_SYNC_COMMITTEE_GINDEX` constants of the light client specs.
+#[test]
+fn state_proof_indices() {
+    let deneb = StateIndices::for_fork(BeaconFork::Deneb);
+    assert_eq!(
+        (deneb.finalized_root, deneb.current_sync_committee, deneb.next_sync_committee),
+        (105, 54, 55)
+    );
+    let electra = StateIndices::for_fork(BeaconFork::Electra);
+    assert_eq!(
+        (electra.finalized_root, electra.current_sync_committee, electra.next_sync_committee),
+        (169, 86, 87)
+    );
+    assert_eq!(EXECUTION_PAYLOAD_INDEX, 25);
+}
+
+#[tokio::test]
+async fn follows_verified_chain() {
+    follows_verified_chain_of(BeaconFork::Electra).await;
+}
+
+#[tokio::test]
+async fn follows_verified_deneb_chain() {
+    follows_verified_chain_of(BeaconFork::Deneb).await;
+}
+
+async fn follows_verified_chain_of(fork: BeaconFork) {
+    let fixture = Fixture::with_fork(fork);
+    let provider = provider(&fixture, BeaconApi::new(&fixture)).await;
+
+    let head = provider.sync().await.unwrap();
+    assert_eq!(head, fixture.headers[4]);
+    assert_eq!(provider.finalized_header().await.unwrap(), fixture.headers[2]);
+
+    // Ancestors are fetched by walking back from the verified head.
+    let payload = provider.get_payload(FIRST_BLOCK + 1).await.unwrap();
+    assert_eq!(payload.block_hash(), fixture.headers[1].execution.block_hash);
+    assert_eq!(payload.parent_beacon_block_root(), Some(fixture.headers[1].beacon.parent_root));
+
+    let (tx, mut rx) = mpsc::channel(1);
+    tokio::spawn(async move { provider.subscribe_payloads(tx).await });
+    let payload = rx.recv().await.unwrap();
+    assert_eq!(payload.block_hash(), fixture.headers[4].execution.block_hash);
+    assert_eq!(payload.parent_beacon_block_root(), Some(fixture.headers[3].beacon.parent_root));
+}
+
+#[tokio::test]
+async fn rejects_wrong_checkpoint() {
+    let fixture = Fixture::new();
+    let mut api = BeaconApi::new(&fixture);
+    api.bootstrap.header = fixture.headers[1].clone();
+    let provider = provider(&fixture, api).await;
+
+    let err = provider.sync().await.unwrap_err();
+    assert!(matches!(light_client_error(&err), LightClientError::CheckpointMismatch { .. }));
+}
+
+#[tokio::test]
+async fn rejects_invalid_signature() {
+    let fixture = Fixture::new();
+    let mut api = BeaconApi::new(&fixture);
+    // signed over another block root
+    api.optimistic_update.sync_aggregate = fixture.sign(B256::repeat_byte(0xaa));
+    let provider = provider(&fixture, api).await;
+
+    let err = provider.sync().await.unwrap_err();
+    assert!(matches!(light_client_error(&err), LightClientError::InvalidSignature));
+}
+
+#[tokio::test]
+async fn rejects_insufficient_participation() {
+    let fixture = Fixture::new();
+    let mut api = BeaconApi::new(&fixture);
+    api.optimistic_update.sync_aggregate.sync_committee_bits = Bytes::from(vec![0x0f; 64]);
+    let provider = provider(&fixture, api).await;
+
+    let err = provider.sync().await.unwrap_err();
+    assert!(matches!(light_client_error(&err), LightClientError::InsufficientParticipation(256)));
+}
+
+#[tokio::test]
+async fn rejects_invalid_finality_branch() {
+    let fixture = Fixture::new();
+    let mut api = BeaconApi::new(&fixture);
+    api.finality_update.finality_branch.as_mut().unwrap()[0] = B256::repeat_byte(0xaa);
+    let provider = provider(&fixture, api).await;
+
+    let err = provider.sync().await.unwrap_err();
+    assert!(matches!(light_client_error(&err), LightClientError::InvalidFinalityBranch));
+}
+
+#[tokio::test]
+async fn rejects_unverified_payload() {
+    let fixture = Fixture::new();
+    let mut api = BeaconApi::new(&fixture);
+    let head_root = fixture.headers[4].beacon.hash_tree_root();
+    api.blocks.get_mut(&head_root).unwrap()["data"]["message"]["body"]["execution_payload"]
+        ["gas_used"] = json!("42");
+    let provider = provider(&fixture, api).await;
+
+    provider.sync().await.unwrap();
+    let err = provider.get_payload(FIRST_BLOCK + 4).await.unwrap_err();
+    assert!(matches!(light_client_error(&err), LightClientError::PayloadMismatch(_)));
+}
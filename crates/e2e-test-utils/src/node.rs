@@ -8,7 +8,7 @@ use eyre::Ok;
 use futures_util::Future;
 use jsonrpsee::{core::client::ClientT, http_client::HttpClient};
 use reth_chainspec::EthereumHardforks;
-use reth_network_api::test_utils::PeersHandleProvider;
+use reth_network_api::test_utils::{NetworkFaultsProvider, PeersHandleProvider};
 use reth_node_api::{Block, BlockBody, BlockTy, FullNodeComponents, PayloadTypes, PrimitivesTy};
 use reth_node_builder::{rpc::RethRpcAddOns, FullNode, NodeTypes};
 
@@ -334,8 +334,12 @@ where
     ///
     /// This helper method extracts the necessary handles and creates a client
     /// that can interact with both the regular RPC and Engine API endpoints.
-    /// It automatically includes the beacon engine handle for direct consensus engine interaction.
-    pub fn to_node_client(&self) -> eyre::Result<crate::testsuite::NodeClient<Payload>> {
+    /// It automatically includes the beacon engine handle for direct consensus engine interaction,
+    /// and the network's fault injection handle for degrading the links to other nodes.
+    pub fn to_node_client(&self) -> eyre::Result<crate::testsuite::NodeClient<Payload>>
+    where
+        Node::Network: NetworkFaultsProvider,
+    {
         let rpc = self
             .rpc_client()
             .ok_or_else(|| eyre::eyre!("Failed to create HTTP RPC client for node"))?;
@@ -343,7 +347,10 @@ where
         let url = self.rpc_url();
         let beacon_handle = self.inner.add_ons_handle.beacon_engine_handle.clone();
 
-        Ok(crate::testsuite::NodeClient::new_with_beacon_engine(rpc, auth, url, beacon_handle))
+        let network_faults = self.inner.network.network_faults().clone();
+
+        Ok(crate::testsuite::NodeClient::new_with_beacon_engine(rpc, auth, url, beacon_handle)
+            .with_network(self.network.record().id, network_faults))
     }
 
     /// Calls the `testing_buildBlockV1` RPC on this node.
@@ -12,6 +12,7 @@ use tracing::debug;
 pub mod custom_fcu;
 pub mod engine_api;
 pub mod fork;
+pub mod network_faults;
 pub mod node_ops;
 pub mod produce_blocks;
 pub mod reorg;
@@ -19,6 +20,7 @@ pub mod reorg;
 pub use custom_fcu::{BlockReference, FinalizeBlock, SendForkchoiceUpdate};
 pub use engine_api::{ExpectedPayloadStatus, SendNewPayload, SendNewPayloads};
 pub use fork::{CreateFork, ForkBase, SetForkBase, SetForkBaseFromBlockInfo, ValidateFork};
+pub use network_faults::{ClearNetworkFaults, HealPartition, PartitionNodes, SetLinkConditions};
 pub use node_ops::{
     AssertChainTip, CaptureBlockOnNode, CompareNodeChainTips, SelectActiveNode, ValidateBlockTag,
     WaitForSync,
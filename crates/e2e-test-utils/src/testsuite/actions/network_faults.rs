@@ -0,0 +1,182 @@
+//! Actions for degrading the network links between nodes.
+//!
+//! Link conditions are applied by the receiving node, so the conditions of the link from node `a`
+//! to node `b` are configured on node `b`.
+
+use crate::testsuite::{Action, Environment};
+use eyre::Result;
+use futures_util::future::BoxFuture;
+use reth_network_api::test_utils::{LinkConditions, NetworkFaults};
+use reth_network_peers::PeerId;
+use reth_node_api::EngineTypes;
+use tracing::debug;
+
+/// Returns the p2p identity and the fault injection handle of the node at the given index.
+fn node_network<Engine: EngineTypes>(
+    env: &Environment<Engine>,
+    node_idx: usize,
+) -> Result<(PeerId, &NetworkFaults)> {
+    let client = env
+        .node_clients
+        .get(node_idx)
+        .ok_or_else(|| eyre::eyre!("Node index {} out of bounds", node_idx))?;
+    match (client.peer_id, &client.network_faults) {
+        (Some(peer_id), Some(faults)) => Ok((peer_id, faults)),
+        _ => Err(eyre::eyre!("Network faults are not available for node {}", node_idx)),
+    }
+}
+
+/// Action that sets the conditions of the link from one node to another.
+#[derive(Debug)]
+pub struct SetLinkConditions {
+    /// Index of the node sending over the link
+    pub from: usize,
+    /// Index of the node receiving over the link
+    pub to: usize,
+    /// Whether the conditions also apply to the link in the opposite direction
+    pub bidirectional: bool,
+    /// The conditions of the link
+    pub conditions: LinkConditions,
+}
+
+impl SetLinkConditions {
+    /// Create a new `SetLinkConditions` action for the link from `from` to `to`
+    pub const fn new(from: usize, to: usize, conditions: LinkConditions) -> Self {
+        Self { from, to, bidirectional: false, conditions }
+    }
+
+    /// Create a new `SetLinkConditions` action for the links in both directions between two nodes
+    pub const fn both_ways(node_a: usize, node_b: usize, conditions: LinkConditions) -> Self {
+        Self { from: node_a, to: node_b, bidirectional: true, conditions }
+    }
+}
+
+impl<Engine> Action<Engine> for SetLinkConditions
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let (from_id, from_faults) = node_network(env, self.from)?;
+            let (to_id, to_faults) = node_network(env, self.to)?;
+
+            to_faults.set_link(from_id, self.conditions);
+            if self.bidirectional {
+                from_faults.set_link(to_id, self.conditions);
+            }
+
+            debug!(
+                from = self.from,
+                to = self.to,
+                bidirectional = self.bidirectional,
+                conditions = ?self.conditions,
+                "Set link conditions"
+            );
+            Ok(())
+        })
+    }
+}
+
+/// Action that partitions two groups of nodes from each other, dropping all messages between them
+/// until the partition is healed.
+#[derive(Debug)]
+pub struct PartitionNodes {
+    /// Nodes on one side of the partition
+    pub side_a: Vec<usize>,
+    /// Nodes on the other side of the partition
+    pub side_b: Vec<usize>,
+}
+
+impl PartitionNodes {
+    /// Create a new `PartitionNodes` action
+    pub const fn new(side_a: Vec<usize>, side_b: Vec<usize>) -> Self {
+        Self { side_a, side_b }
+    }
+
+    /// Create a new `PartitionNodes` action that isolates a single node from the given others
+    pub fn isolate(node_idx: usize, others: impl IntoIterator<Item = usize>) -> Self {
+        Self::new(vec![node_idx], others.into_iter().collect())
+    }
+}
+
+impl<Engine> Action<Engine> for PartitionNodes
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            for_each_link(env, &self.side_a, &self.side_b, |faults, peer_id| {
+                faults.partition(peer_id)
+            })?;
+            debug!(side_a = ?self.side_a, side_b = ?self.side_b, "Partitioned nodes");
+            Ok(())
+        })
+    }
+}
+
+/// Action that heals a partition between two groups of nodes, keeping the other conditions of the
+/// links.
+#[derive(Debug)]
+pub struct HealPartition {
+    /// Nodes on one side of the partition
+    pub side_a: Vec<usize>,
+    /// Nodes on the other side of the partition
+    pub side_b: Vec<usize>,
+}
+
+impl HealPartition {
+    /// Create a new `HealPartition` action
+    pub const fn new(side_a: Vec<usize>, side_b: Vec<usize>) -> Self {
+        Self { side_a, side_b }
+    }
+}
+
+impl<Engine> Action<Engine> for HealPartition
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            for_each_link(env, &self.side_a, &self.side_b, |faults, peer_id| faults.heal(peer_id))?;
+            debug!(side_a = ?self.side_a, side_b = ?self.side_b, "Healed partition");
+            Ok(())
+        })
+    }
+}
+
+/// Action that restores perfect links between all nodes.
+#[derive(Debug, Default)]
+pub struct ClearNetworkFaults;
+
+impl<Engine> Action<Engine> for ClearNetworkFaults
+where
+    Engine: EngineTypes,
+{
+    fn execute<'a>(&'a mut self, env: &'a mut Environment<Engine>) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            for node_idx in 0..env.node_count() {
+                node_network(env, node_idx)?.1.clear();
+            }
+            debug!("Cleared network faults of all nodes");
+            Ok(())
+        })
+    }
+}
+
+/// Applies `f` to both ends of every link between the two groups of nodes.
+fn for_each_link<Engine: EngineTypes>(
+    env: &Environment<Engine>,
+    side_a: &[usize],
+    side_b: &[usize],
+    f: impl Fn(&NetworkFaults, PeerId),
+) -> Result<()> {
+    for &a in side_a {
+        for &b in side_b {
+            let (a_id, a_faults) = node_network(env, a)?;
+            let (b_id, b_faults) = node_network(env, b)?;
+            f(a_faults, b_id);
+            f(b_faults, a_id);
+        }
+    }
+    Ok(())
+}
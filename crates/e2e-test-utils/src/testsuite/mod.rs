@@ -16,6 +16,8 @@ use crate::testsuite::setup::Setup;
 use alloy_provider::{Provider, ProviderBuilder};
 use alloy_rpc_types_engine::{ForkchoiceState, PayloadAttributes};
 use reth_engine_primitives::ConsensusEngineHandle;
+use reth_network_api::test_utils::NetworkFaults;
+use reth_network_peers::PeerId;
 use reth_rpc_builder::auth::AuthServerHandle;
 use std::sync::Arc;
 use url::Url;
@@ -32,6 +34,10 @@ where
     pub engine: AuthServerHandle,
     /// Beacon consensus engine handle for direct interaction with the consensus engine
     pub beacon_engine_handle: Option<ConsensusEngineHandle<Payload>>,
+    /// Identifier of the node on the p2p network, if known
+    pub peer_id: Option<PeerId>,
+    /// Handle to inject faults into the node's network links, if the node runs in process
+    pub network_faults: Option<NetworkFaults>,
     /// Alloy provider for interacting with the node
     provider: Arc<dyn Provider + Send + Sync>,
 }
@@ -44,7 +50,14 @@ where
     pub fn new(rpc: HttpClient, engine: AuthServerHandle, url: Url) -> Self {
         let provider =
             Arc::new(ProviderBuilder::new().connect_http(url)) as Arc<dyn Provider + Send + Sync>;
-        Self { rpc, engine, beacon_engine_handle: None, provider }
+        Self {
+            rpc,
+            engine,
+            beacon_engine_handle: None,
+            peer_id: None,
+            network_faults: None,
+            provider,
+        }
     }
 
     /// Instantiates a new [`NodeClient`] with the given handles, RPC URL, and beacon engine handle
@@ -56,7 +69,21 @@ where
     ) -> Self {
         let provider =
             Arc::new(ProviderBuilder::new().connect_http(url)) as Arc<dyn Provider + Send + Sync>;
-        Self { rpc, engine, beacon_engine_handle: Some(beacon_engine_handle), provider }
+        Self {
+            rpc,
+            engine,
+            beacon_engine_handle: Some(beacon_engine_handle),
+            peer_id: None,
+            network_faults: None,
+            provider,
+        }
+    }
+
+    /// Sets the p2p identity and the [`NetworkFaults`] handle of the node's network.
+    pub fn with_network(mut self, peer_id: PeerId, network_faults: NetworkFaults) -> Self {
+        self.peer_id = Some(peer_id);
+        self.network_faults = Some(network_faults);
+        self
     }
 
     /// Get a block by number using the alloy provider
@@ -85,6 +112,8 @@ where
             .field("rpc", &self.rpc)
             .field("engine", &self.engine)
             .field("beacon_engine_handle", &self.beacon_engine_handle.is_some())
+            .field("peer_id", &self.peer_id)
+            .field("network_faults", &self.network_faults)
             .field("provider", &"<Provider>")
             .finish()
     }
@@ -7,6 +7,7 @@ use alloy_rpc_types_engine::{ForkchoiceState, PayloadAttributes};
 use eyre::{eyre, Result};
 use reth_chainspec::ChainSpec;
 use reth_ethereum_primitives::Block;
+use reth_network_api::test_utils::NetworkFaultsProvider;
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState};
 use reth_node_api::{EngineTypes, NodeTypes, PayloadTypes, TreeConfig};
 use reth_node_core::primitives::RecoveredBlock;
@@ -166,7 +167,11 @@ where
             let auth = node.auth_server_handle();
             let url = node.rpc_url();
             // TODO: Pass beacon_engine_handle once import system supports generic types
-            node_clients.push(crate::testsuite::NodeClient::new(rpc, auth, url));
+            let network_faults = node.inner.network.network_faults().clone();
+            node_clients.push(
+                crate::testsuite::NodeClient::new(rpc, auth, url)
+                    .with_network(node.network.record().id, network_faults),
+            );
         }
 
         // Store the import result to keep nodes alive
@@ -9,17 +9,19 @@ use reth_e2e_test_utils::{
     testsuite::{
         actions::{
             Action, AssertChainTip, AssertMineBlock, CaptureBlock, CaptureBlockOnNode,
-            CompareNodeChainTips, CreateFork, MakeCanonical, ProduceBlocks, ReorgTo,
-            SelectActiveNode, UpdateBlockInfo,
+            ClearNetworkFaults, CompareNodeChainTips, CreateFork, HealPartition, MakeCanonical,
+            PartitionNodes, ProduceBlocks, ReorgTo, SelectActiveNode, SetLinkConditions,
+            UpdateBlockInfo,
         },
         setup::{NetworkSetup, Setup},
         Environment, TestBuilder,
     },
     E2ETestSetupBuilder,
 };
+use reth_network_api::test_utils::LinkConditions;
 use reth_node_api::TreeConfig;
 use reth_node_ethereum::{EthEngineTypes, EthereumNode};
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tempfile::TempDir;
 use tracing::debug;
 
@@ -354,6 +356,56 @@ async fn test_testsuite_multinode_block_production() -> Result<()> {
     Ok(())
 }
 
+/// Tests that block production keeps working while the links between the nodes are degraded,
+/// partitioned and healed again.
+#[tokio::test]
+async fn test_testsuite_network_faults() -> Result<()> {
+    reth_tracing::init_test_tracing();
+
+    let setup = Setup::default()
+        .with_chain_spec(Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(
+                    serde_json::from_str(include_str!(
+                        "../../../../crates/e2e-test-utils/src/testsuite/assets/genesis.json"
+                    ))
+                    .unwrap(),
+                )
+                .cancun_activated()
+                .build(),
+        ))
+        .with_network(NetworkSetup::multi_node(2))
+        .with_tree_config(TreeConfig::default().with_state_root_fallback(true));
+
+    let lossy = LinkConditions::perfect()
+        .with_latency(Duration::from_millis(50))
+        .with_jitter(Duration::from_millis(20))
+        .with_drop_probability(0.1);
+
+    let test = TestBuilder::new()
+        .with_setup(setup)
+        .with_action(SetLinkConditions::both_ways(0, 1, lossy))
+        .with_action(SelectActiveNode::new(0))
+        .with_action(ProduceBlocks::<EthEngineTypes>::new(2))
+        .with_action(MakeCanonical::new())
+        .with_action(CompareNodeChainTips::expect_same(0, 1))
+        // blocks are delivered over the engine API, so they reach both sides of a partition
+        .with_action(PartitionNodes::isolate(1, [0]))
+        .with_action(ProduceBlocks::<EthEngineTypes>::new(2))
+        .with_action(MakeCanonical::new())
+        .with_action(CompareNodeChainTips::expect_same(0, 1))
+        .with_action(HealPartition::new(vec![1], vec![0]))
+        .with_action(ClearNetworkFaults)
+        .with_action(ProduceBlocks::<EthEngineTypes>::new(1))
+        .with_action(MakeCanonical::new())
+        .with_action(CompareNodeChainTips::expect_same(0, 1));
+
+    test.run::<EthereumNode>().await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_setup_builder_with_custom_tree_config() -> Result<()> {
     reth_tracing::init_test_tracing();
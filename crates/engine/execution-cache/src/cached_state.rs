@@ -1,6 +1,7 @@
 //! Execution cache implementation for block processing.
+use crate::{ExecutionCacheSnapshot, HotSet};
 use alloy_primitives::{
-    map::{DefaultHashBuilder, FbBuildHasher},
+    map::{B256Set, DefaultHashBuilder, FbBuildHasher},
     Address, StorageKey, StorageValue, B256,
 };
 use fixed_cache::{AnyRef, CacheConfig, Stats, StatsHandler};
@@ -19,7 +20,7 @@ use reth_trie::{
     MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
 };
 use std::{
-    cell::Cell,
+    cell::{Cell, RefCell},
     fmt,
     sync::{
         atomic::{AtomicU64, AtomicUsize, Ordering},
@@ -108,6 +109,9 @@ pub struct CachedStateProvider<S> {
     /// Provider-local hit/miss counters flushed when the provider is dropped.
     metric_counts: CacheMetricCounts,
 
+    /// Provider-local hot set accesses flushed when the provider is dropped.
+    hot_set_accesses: HotSetAccesses,
+
     /// Whether cache misses should populate the shared execution cache.
     fill_mode: CacheFillMode,
 
@@ -148,6 +152,7 @@ impl<S> CachedStateProvider<S> {
             caches,
             metrics,
             metric_counts: CacheMetricCounts::new(),
+            hot_set_accesses: HotSetAccesses::new(),
             fill_mode,
             cache_stats,
         }
@@ -213,6 +218,29 @@ impl<S> CachedStateProvider<S> {
         }
     }
 
+    #[inline]
+    fn record_account_access(&self, address: Address) {
+        if self.caches.0.hot_set.is_some() {
+            self.hot_set_accesses.accounts.borrow_mut().push(address);
+        }
+    }
+
+    #[inline]
+    fn record_storage_access(&self, address: Address, storage_key: StorageKey) {
+        if self.caches.0.hot_set.is_some() {
+            self.hot_set_accesses.storage.borrow_mut().push((address, storage_key));
+        }
+    }
+
+    fn flush_hot_set_accesses(&self) {
+        if let Some(hot_set) = &self.caches.0.hot_set {
+            hot_set.record(
+                &self.hot_set_accesses.accounts.take(),
+                &self.hot_set_accesses.storage.take(),
+            );
+        }
+    }
+
     const fn should_fill_on_miss(&self) -> bool {
         matches!(self.fill_mode, CacheFillMode::FillOnMiss)
     }
@@ -221,6 +249,7 @@ impl<S> CachedStateProvider<S> {
 impl<S> Drop for CachedStateProvider<S> {
     fn drop(&mut self) {
         self.flush_buffered_metrics();
+        self.flush_hot_set_accesses();
     }
 }
 
@@ -311,6 +340,20 @@ impl CacheMetricCounts {
     }
 }
 
+/// Accounts and storage slots accessed through a [`CachedStateProvider`], recorded in the
+/// [`HotSet`] in one batch to avoid locking it on every access.
+#[derive(Debug, Default)]
+struct HotSetAccesses {
+    accounts: RefCell<Vec<Address>>,
+    storage: RefCell<Vec<(Address, StorageKey)>>,
+}
+
+impl HotSetAccesses {
+    const fn new() -> Self {
+        Self { accounts: RefCell::new(Vec::new()), storage: RefCell::new(Vec::new()) }
+    }
+}
+
 /// Represents the status of a key in the cache.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CachedStatus<T> {
@@ -637,6 +680,7 @@ impl<K: PartialEq, V> StatsHandler<K, V> for CacheStatsHandler {
 
 impl<S: AccountReader> AccountReader for CachedStateProvider<S> {
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        self.record_account_access(*address);
         if self.should_fill_on_miss() {
             match self.caches.get_or_try_insert_account_with(*address, || {
                 self.state_provider.basic_account(address)
@@ -675,6 +719,7 @@ impl<S: StateProvider> StateProvider for CachedStateProvider<S> {
         account: Address,
         storage_key: StorageKey,
     ) -> ProviderResult<Option<StorageValue>> {
+        self.record_storage_access(account, storage_key);
         if self.should_fill_on_miss() {
             match self.caches.get_or_try_insert_storage_with(account, storage_key, || {
                 self.state_provider.storage(account, storage_key).map(Option::unwrap_or_default)
@@ -859,6 +904,9 @@ struct ExecutionCacheInner {
 
     /// One-time notification when SELFDESTRUCT is encountered
     selfdestruct_encountered: Once,
+
+    /// Hot working set of the cache, tracked only if the cache is snapshotted.
+    hot_set: Option<HotSet>,
 }
 
 impl ExecutionCache {
@@ -884,6 +932,12 @@ impl ExecutionCache {
 
     /// Build an [`ExecutionCache`] struct, so that execution caches can be easily cloned.
     pub fn new(total_cache_size: usize) -> Self {
+        Self::new_with_hot_set(total_cache_size, None)
+    }
+
+    /// Build an [`ExecutionCache`] that records accounts and storage slots read through
+    /// [`CachedStateProvider`]s in the given [`HotSet`].
+    pub fn new_with_hot_set(total_cache_size: usize, hot_set: Option<HotSet>) -> Self {
         let code_cache_size = (total_cache_size * 556) / 10000; // 5.56% of total
         let storage_cache_size = (total_cache_size * 8888) / 10000; // 88.88% of total
         let account_cache_size = (total_cache_size * 556) / 10000; // 5.56% of total
@@ -907,6 +961,7 @@ impl ExecutionCache {
             storage_stats,
             account_stats,
             selfdestruct_encountered: Once::new(),
+            hot_set,
         }))
     }
 
@@ -1009,6 +1064,11 @@ impl ExecutionCache {
     #[instrument(level = "debug", target = "engine::caching", skip_all)]
     #[expect(clippy::result_unit_err)]
     pub fn insert_state(&self, state_updates: &BundleState) -> Result<(), ()> {
+        // the accesses of the block were recorded when its state providers were dropped
+        if let Some(hot_set) = &self.0.hot_set {
+            hot_set.decay_and_evict();
+        }
+
         let _enter =
             debug_span!(target: "engine::tree", "contracts", len = state_updates.contracts.len())
                 .entered();
@@ -1093,6 +1153,69 @@ impl ExecutionCache {
         self.0.account_stats.reset_size();
     }
 
+    /// Returns the [`HotSet`] tracked by this cache, if any.
+    pub fn hot_set(&self) -> Option<&HotSet> {
+        self.0.hot_set.as_ref()
+    }
+
+    /// Takes a snapshot of the hot working set, which is valid on top of the state of the block
+    /// with the given hash.
+    ///
+    /// The snapshot holds the cached values of all keys retained by the [`HotSet`], and the
+    /// bytecode of the snapshotted accounts. Returns `None` if the cache doesn't track a hot set.
+    pub fn snapshot(&self, block_hash: B256) -> Option<ExecutionCacheSnapshot> {
+        let (accounts, storage) = self.0.hot_set.as_ref()?.keys();
+
+        let mut snapshot = ExecutionCacheSnapshot::new(block_hash);
+        let mut code_hashes = B256Set::default();
+        for address in accounts {
+            let Some(account) = self.0.account_cache.get(&address) else { continue };
+            if let Some(code_hash) = account.and_then(|account| account.bytecode_hash) &&
+                code_hashes.insert(code_hash) &&
+                let Some(Some(code)) = self.0.code_cache.get(&code_hash)
+            {
+                snapshot.bytecodes.push((code_hash, code.original_bytes()));
+            }
+            snapshot.accounts.push((address, account.map(Into::into)));
+        }
+        for (address, key) in storage {
+            if let Some(value) = self.0.storage_cache.get(&(address, key)) {
+                snapshot.storage.push((address, key, value));
+            }
+        }
+
+        Some(snapshot)
+    }
+
+    /// Inserts the entries of the snapshot into the cache.
+    ///
+    /// The snapshotted accounts and storage slots are also recorded in the [`HotSet`], so they are
+    /// retained by the next snapshot unless colder than newly accessed keys.
+    ///
+    /// The caller must ensure the snapshot was taken at the block whose state the cache holds.
+    pub fn restore(&self, snapshot: &ExecutionCacheSnapshot) {
+        for (code_hash, code) in &snapshot.bytecodes {
+            self.insert_code(*code_hash, Some(Bytecode::new_raw(code.clone())));
+        }
+        for (address, key, value) in &snapshot.storage {
+            self.insert_storage(*address, *key, Some(*value));
+        }
+        for (address, account) in &snapshot.accounts {
+            self.insert_account(*address, account.map(Into::into));
+        }
+
+        if let Some(hot_set) = &self.0.hot_set {
+            let accounts =
+                snapshot.accounts.iter().map(|(address, _)| *address).collect::<Vec<_>>();
+            let storage = snapshot
+                .storage
+                .iter()
+                .map(|(address, key, _)| (*address, *key))
+                .collect::<Vec<_>>();
+            hot_set.record(&accounts, &storage);
+        }
+    }
+
     /// Updates the provided metrics with the current stats from the cache's stats handlers,
     /// and resets the hit/miss/collision counters.
     pub fn update_metrics(&self, metrics: &CachedStateCacheMetrics) {
@@ -5,6 +5,8 @@
 //! - [`SavedCache`]: An execution cache snapshot associated with a specific block hash
 //! - [`PayloadExecutionCache`]: Thread-safe wrapper for sharing cached state across payload
 //!   processing tasks
+//! - [`ExecutionCacheSnapshot`]: The hot working set of an execution cache, persisted across
+//!   restarts
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -17,6 +19,9 @@
 mod cached_state;
 pub use cached_state::*;
 
+mod snapshot;
+pub use snapshot::*;
+
 use alloy_primitives::B256;
 use metrics::{Counter, Histogram};
 use parking_lot::Mutex;
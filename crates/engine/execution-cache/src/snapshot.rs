@@ -0,0 +1,306 @@
+//! Persistence of the execution cache's hot working set across restarts.
+use alloy_primitives::{Address, Bytes, StorageKey, StorageValue, B256, U256};
+use parking_lot::Mutex;
+use reth_fs_util::FsPathError;
+use reth_primitives_traits::Account;
+use reth_trie_sparse::BucketedLfu;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{BufWriter, Write},
+    path::Path,
+    sync::Arc,
+};
+
+/// Default number of accounts retained in the [`HotSet`].
+pub const DEFAULT_HOT_SET_MAX_ACCOUNTS: usize = 100_000;
+
+/// Default number of storage slots retained in the [`HotSet`].
+pub const DEFAULT_HOT_SET_MAX_SLOTS: usize = 500_000;
+
+/// Encoding version of [`ExecutionCacheSnapshot`]. Snapshots with another version are discarded.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// The most frequently accessed accounts and storage slots of the execution cache.
+///
+/// Keys are scored by a [`BucketedLfu`], the same policy the sparse trie uses to retain hot trie
+/// paths across prunes, and the coldest keys are evicted after every block. The hot set is shared
+/// by all execution caches created with it, so it survives cache clears and re-creations.
+#[derive(Debug, Clone)]
+pub struct HotSet(Arc<Mutex<HotSetInner>>);
+
+#[derive(Debug)]
+struct HotSetInner {
+    max_accounts: usize,
+    max_slots: usize,
+    accounts: BucketedLfu<Address>,
+    storage: BucketedLfu<(Address, StorageKey)>,
+}
+
+impl HotSet {
+    /// Creates a hot set that retains at most `max_accounts` accounts and `max_slots` storage
+    /// slots.
+    pub fn new(max_accounts: usize, max_slots: usize) -> Self {
+        Self(Arc::new(Mutex::new(HotSetInner {
+            max_accounts,
+            max_slots,
+            accounts: BucketedLfu::new(max_accounts),
+            storage: BucketedLfu::new(max_slots),
+        })))
+    }
+
+    /// Evicts the least frequently accessed keys exceeding the capacity of the hot set.
+    ///
+    /// Called once per block, after its accesses were recorded.
+    pub(crate) fn decay_and_evict(&self) {
+        let mut inner = self.0.lock();
+        let HotSetInner { max_accounts, max_slots, accounts, storage } = &mut *inner;
+        accounts.decay_and_evict(*max_accounts);
+        storage.decay_and_evict(*max_slots);
+    }
+
+    /// Records one access of each of the given accounts and storage slots.
+    pub(crate) fn record(&self, accounts: &[Address], storage: &[(Address, StorageKey)]) {
+        if accounts.is_empty() && storage.is_empty() {
+            return;
+        }
+
+        let mut inner = self.0.lock();
+        for address in accounts {
+            inner.accounts.touch(*address);
+        }
+        for slot in storage {
+            inner.storage.touch(*slot);
+        }
+    }
+
+    /// Returns the retained accounts and storage slots.
+    pub(crate) fn keys(&self) -> (Vec<Address>, Vec<(Address, StorageKey)>) {
+        let inner = self.0.lock();
+        (inner.accounts.keys().copied().collect(), inner.storage.keys().copied().collect())
+    }
+}
+
+impl Default for HotSet {
+    fn default() -> Self {
+        Self::new(DEFAULT_HOT_SET_MAX_ACCOUNTS, DEFAULT_HOT_SET_MAX_SLOTS)
+    }
+}
+
+/// The hot working set of an execution cache, taken after executing the block with
+/// [`Self::block_hash`].
+///
+/// The snapshot is only valid on top of that block's state, callers must check it against the
+/// persisted head before restoring it.
+///
+/// See [`ExecutionCache::snapshot`](crate::ExecutionCache::snapshot) and
+/// [`ExecutionCache::restore`](crate::ExecutionCache::restore).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionCacheSnapshot {
+    /// Encoding version.
+    pub(crate) version: u32,
+    /// Hash of the block the snapshot was taken at.
+    pub(crate) block_hash: B256,
+    /// Cached accounts, `None` for accounts that don't exist.
+    pub(crate) accounts: Vec<(Address, Option<SnapshotAccount>)>,
+    /// Cached storage values.
+    pub(crate) storage: Vec<(Address, StorageKey, StorageValue)>,
+    /// Original bytecode of the cached accounts, keyed by code hash.
+    pub(crate) bytecodes: Vec<(B256, Bytes)>,
+}
+
+impl ExecutionCacheSnapshot {
+    /// Creates an empty snapshot for the given block hash.
+    pub(crate) const fn new(block_hash: B256) -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            block_hash,
+            accounts: Vec::new(),
+            storage: Vec::new(),
+            bytecodes: Vec::new(),
+        }
+    }
+
+    /// Returns the hash of the block the snapshot was taken at.
+    pub const fn block_hash(&self) -> B256 {
+        self.block_hash
+    }
+
+    /// Returns the number of accounts, storage slots and bytecodes in the snapshot.
+    pub const fn len(&self) -> usize {
+        self.accounts.len() + self.storage.len() + self.bytecodes.len()
+    }
+
+    /// Returns `true` if the snapshot holds no entries.
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Atomically writes the snapshot to `path`.
+    pub fn save(&self, path: &Path) -> Result<(), ExecutionCacheSnapshotError> {
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+
+        reth_fs_util::atomic_write_file(path, |file| {
+            let mut writer = BufWriter::new(file);
+            bincode::serialize_into(&mut writer, self)?;
+            writer.flush().map_err(bincode::Error::from)
+        })?;
+        Ok(())
+    }
+
+    /// Reads the snapshot from `path`.
+    ///
+    /// Returns `None` if the file does not exist.
+    pub fn load(path: &Path) -> Result<Option<Self>, ExecutionCacheSnapshotError> {
+        if !path.exists() {
+            return Ok(None)
+        }
+
+        let snapshot: Self = bincode::deserialize(&reth_fs_util::read(path)?)?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(ExecutionCacheSnapshotError::UnsupportedVersion(snapshot.version))
+        }
+        Ok(Some(snapshot))
+    }
+}
+
+/// Account of an [`ExecutionCacheSnapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SnapshotAccount {
+    nonce: u64,
+    balance: U256,
+    bytecode_hash: Option<B256>,
+}
+
+impl From<Account> for SnapshotAccount {
+    fn from(account: Account) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            bytecode_hash: account.bytecode_hash,
+        }
+    }
+}
+
+impl From<SnapshotAccount> for Account {
+    fn from(account: SnapshotAccount) -> Self {
+        Self {
+            nonce: account.nonce,
+            balance: account.balance,
+            bytecode_hash: account.bytecode_hash,
+        }
+    }
+}
+
+/// Errors when reading or writing an [`ExecutionCacheSnapshot`].
+#[derive(Debug, thiserror::Error)]
+pub enum ExecutionCacheSnapshotError {
+    /// Failed to access the snapshot file.
+    #[error(transparent)]
+    Fs(#[from] FsPathError),
+    /// Failed to decode the snapshot.
+    #[error("failed to decode execution cache snapshot: {0}")]
+    Decode(#[from] bincode::Error),
+    /// The snapshot was written with an unsupported encoding version.
+    #[error("unsupported execution cache snapshot version {0}")]
+    UnsupportedVersion(u32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CachedStateProvider, ExecutionCache};
+    use reth_primitives_traits::Bytecode;
+    use reth_provider::{test_utils::MockEthProvider, AccountReader, StateProvider};
+
+    #[test]
+    fn snapshot_includes_accessed_entries() {
+        let address = Address::random();
+        let slot = StorageKey::random();
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let code_hash = code.hash_slow();
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: Some(code_hash) };
+
+        let cache = ExecutionCache::new_with_hot_set(1_000, Some(HotSet::default()));
+        cache.insert_code(code_hash, Some(code));
+        cache.insert_account(address, Some(account));
+        cache.insert_storage(address, slot, Some(U256::from(7)));
+        // Cached, but never accessed.
+        cache.insert_account(Address::random(), None);
+
+        let provider = CachedStateProvider::new(MockEthProvider::default(), cache.clone(), None);
+        provider.basic_account(&address).unwrap();
+        provider.storage(address, slot).unwrap();
+        drop(provider);
+
+        let snapshot = cache.snapshot(B256::with_last_byte(1)).unwrap();
+        assert_eq!(snapshot.block_hash(), B256::with_last_byte(1));
+        assert_eq!(snapshot.accounts, vec![(address, Some(account.into()))]);
+        assert_eq!(snapshot.storage, vec![(address, slot, U256::from(7))]);
+        assert_eq!(snapshot.bytecodes, vec![(code_hash, Bytes::from_static(&[0x60, 0x00]))]);
+    }
+
+    #[test]
+    fn hot_set_retains_hottest_keys() {
+        let hot_set = HotSet::new(2, 1);
+        let [a, b, c] = [1, 2, 3].map(Address::with_last_byte);
+        let [x, y] = [1, 2].map(|byte| (a, StorageKey::with_last_byte(byte)));
+
+        hot_set.record(&[a, b], &[x]);
+        hot_set.record(&[a, c], &[x, y]);
+        hot_set.decay_and_evict();
+
+        let (accounts, storage) = hot_set.keys();
+        assert_eq!(accounts.len(), 2);
+        assert!(accounts.contains(&a));
+        assert_eq!(storage, vec![x]);
+    }
+
+    #[test]
+    fn snapshot_requires_hot_set() {
+        assert!(ExecutionCache::new(1_000).snapshot(B256::ZERO).is_none());
+    }
+
+    #[test]
+    fn save_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("execution-cache.snapshot");
+        assert!(ExecutionCacheSnapshot::load(&path).unwrap().is_none());
+
+        let address = Address::random();
+        let slot = StorageKey::random();
+        let mut snapshot = ExecutionCacheSnapshot::new(B256::random());
+        snapshot.accounts.push((address, Some(Account::default().into())));
+        snapshot.storage.push((address, slot, U256::from(42)));
+        snapshot.save(&path).unwrap();
+
+        let loaded = ExecutionCacheSnapshot::load(&path).unwrap().unwrap();
+        assert_eq!(loaded, snapshot);
+
+        let cache = ExecutionCache::new_with_hot_set(1_000, Some(HotSet::default()));
+        cache.restore(&loaded);
+        let provider = CachedStateProvider::new(MockEthProvider::default(), cache.clone(), None);
+        assert_eq!(provider.basic_account(&address).unwrap(), Some(Account::default()));
+        assert_eq!(provider.storage(address, slot).unwrap(), Some(U256::from(42)));
+        drop(provider);
+
+        // Restored entries are part of the hot set of the next snapshot.
+        assert_eq!(cache.snapshot(loaded.block_hash()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn load_rejects_unsupported_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("execution-cache.snapshot");
+
+        let snapshot =
+            ExecutionCacheSnapshot { version: SNAPSHOT_VERSION + 1, ..Default::default() };
+        snapshot.save(&path).unwrap();
+
+        assert!(matches!(
+            ExecutionCacheSnapshot::load(&path),
+            Err(ExecutionCacheSnapshotError::UnsupportedVersion(_))
+        ));
+    }
+}
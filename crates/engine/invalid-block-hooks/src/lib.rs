@@ -1,5 +1,11 @@
 //! Invalid block hook implementations.
 
+mod opcode;
+mod output;
+mod prestate;
+mod trace;
 mod witness;
 
+pub use opcode::InvalidBlockOpcodeHook;
+pub use prestate::InvalidBlockPreStateHook;
 pub use witness::InvalidBlockWitnessHook;
@@ -0,0 +1,293 @@
+use crate::{
+    output::save_diff,
+    trace::{healthy_node_traces, save_failed_transaction, trace_block, tx_prefix, BlockTraces},
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_trace::geth::{
+    DefaultFrame, GethDebugTracingOptions, GethDefaultTracingOptions, GethTrace, StructLog,
+    TraceResult,
+};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::{BlockExecutionOutput, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use revm_bytecode::OpCode;
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// Hook for writing opcode traces of invalid blocks.
+///
+/// Every transaction of the block is re-executed with the struct logger, and its steps are
+/// written as an [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace, one JSON object per
+/// line followed by a summary line.
+#[derive(Debug)]
+pub struct InvalidBlockOpcodeHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: E,
+    /// The directory to write the traces to. Additionally, diff files will be written to this
+    /// directory in case of mismatches against the healthy node.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the traces against.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+}
+
+impl<P, E> InvalidBlockOpcodeHook<P, E> {
+    /// Creates a new opcode hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { provider, evm_config, output_directory, healthy_node_client }
+    }
+}
+
+impl<P, E, N> InvalidBlockOpcodeHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    /// Returns the tracing options of the struct logger.
+    ///
+    /// Memory is enabled so that the memory size of every step can be derived from the traces of
+    /// the healthy node, which may not report it otherwise.
+    fn tracing_options() -> GethDebugTracingOptions {
+        GethDebugTracingOptions {
+            config: GethDefaultTracingOptions {
+                enable_memory: Some(true),
+                enable_return_data: Some(true),
+                disable_storage: Some(true),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let BlockTraces { traces, failed } = trace_block(
+            &self.provider,
+            &self.evm_config,
+            parent_header,
+            block,
+            Self::tracing_options(),
+        )?;
+
+        let block_prefix = format!("{}_{}", block.number(), block.hash());
+        let mut re_executed = Vec::with_capacity(traces.len());
+        for (index, (tx_hash, trace)) in traces.iter().enumerate() {
+            let lines = eip3155_lines(trace)?;
+            let filename =
+                format!("{}.opcode.re_executed.jsonl", tx_prefix(&block_prefix, index, *tx_hash));
+            let path = save_lines(&self.output_directory, filename, &lines)?;
+            re_executed.push((*tx_hash, lines, path));
+        }
+        if let Some(failed) = &failed {
+            save_failed_transaction(&self.output_directory, &block_prefix, "opcode", failed)?;
+        }
+
+        let Some(healthy_node_client) = &self.healthy_node_client else { return Ok(()) };
+        let healthy_traces =
+            healthy_node_traces(healthy_node_client, block.number(), Self::tracing_options())?;
+        if failed.is_none() && healthy_traces.len() != re_executed.len() {
+            warn!(
+                target: "engine::invalid_block_hooks::opcode",
+                re_executed = re_executed.len(),
+                healthy = healthy_traces.len(),
+                "Transaction count mismatch against healthy node"
+            );
+        }
+
+        for (index, ((tx_hash, lines, re_executed_path), healthy)) in
+            re_executed.iter().zip(healthy_traces).enumerate()
+        {
+            let tx_prefix = tx_prefix(&block_prefix, index, *tx_hash);
+            let healthy_trace = match healthy {
+                TraceResult::Success { result, .. } => result,
+                TraceResult::Error { error, tx_hash: healthy_tx_hash } => {
+                    warn!(
+                        target: "engine::invalid_block_hooks::opcode",
+                        %tx_hash,
+                        ?healthy_tx_hash,
+                        %error,
+                        "Healthy node failed to trace transaction"
+                    );
+                    continue
+                }
+            };
+
+            let healthy_lines = eip3155_lines(&healthy_trace)?;
+            let filename = format!("{tx_prefix}.opcode.healthy.jsonl");
+            let healthy_path = save_lines(&self.output_directory, filename, &healthy_lines)?;
+
+            if lines != &healthy_lines {
+                let filename = format!("{tx_prefix}.opcode.diff");
+                let diff_path = save_diff(&self.output_directory, filename, lines, &healthy_lines)?;
+                warn!(
+                    target: "engine::invalid_block_hooks::opcode",
+                    %tx_hash,
+                    diff_path = %diff_path.display(),
+                    re_executed_path = %re_executed_path.display(),
+                    healthy_path = %healthy_path.display(),
+                    "Opcode trace mismatch against healthy node"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockOpcodeHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::opcode", %err, "Failed to invoke hook");
+        }
+    }
+}
+
+/// A single step of an EIP-3155 trace.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Step<'a> {
+    pc: u64,
+    op: u8,
+    gas: String,
+    gas_cost: String,
+    mem_size: u64,
+    stack: Vec<String>,
+    depth: u64,
+    return_data: &'a Bytes,
+    refund: String,
+    op_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+impl<'a> From<&'a StructLog> for Eip3155Step<'a> {
+    fn from(log: &'a StructLog) -> Self {
+        static EMPTY: Bytes = Bytes::new();
+
+        Self {
+            pc: log.pc,
+            op: OpCode::parse(&log.op).unwrap_or(OpCode::INVALID).get(),
+            gas: format!("{:#x}", log.gas),
+            gas_cost: format!("{:#x}", log.gas_cost),
+            mem_size: log.memory_size.unwrap_or_else(|| {
+                log.memory.as_ref().map_or(0, |memory| memory.len() as u64 * 32)
+            }),
+            stack: log.stack.iter().flatten().map(|value| format!("{value:#x}")).collect(),
+            depth: log.depth,
+            return_data: log.return_data.as_ref().unwrap_or(&EMPTY),
+            refund: format!("{:#x}", log.refund_counter.unwrap_or_default()),
+            op_name: &log.op,
+            error: log.error.as_deref(),
+        }
+    }
+}
+
+/// The summary line of an EIP-3155 trace.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Summary<'a> {
+    output: &'a Bytes,
+    gas_used: String,
+    pass: bool,
+}
+
+/// Converts the struct logs of a transaction into the lines of an EIP-3155 trace.
+fn eip3155_lines(trace: &GethTrace) -> eyre::Result<Vec<String>> {
+    let GethTrace::Default(DefaultFrame { failed, gas, return_value, struct_logs }) = trace else {
+        eyre::bail!("expected a struct log trace, got {trace:?}")
+    };
+
+    let mut lines = struct_logs
+        .iter()
+        .map(|log| serde_json::to_string(&Eip3155Step::from(log)))
+        .collect::<Result<Vec<_>, _>>()?;
+    lines.push(serde_json::to_string(&Eip3155Summary {
+        output: return_value,
+        gas_used: format!("{gas:#x}"),
+        pass: !failed,
+    })?);
+
+    Ok(lines)
+}
+
+/// Saves the lines of a trace to a file in the output directory.
+fn save_lines(
+    output_directory: &Path,
+    filename: String,
+    lines: &[String],
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    let mut file = BufWriter::new(File::create(&path)?);
+    for line in lines {
+        writeln!(file, "{line}")?;
+    }
+    file.flush()?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+
+    #[test]
+    fn test_eip3155_lines() {
+        let trace = GethTrace::Default(DefaultFrame {
+            failed: false,
+            gas: 21_010,
+            return_value: Bytes::from_static(&[0xff]),
+            struct_logs: vec![StructLog {
+                pc: 2,
+                op: "PUSH1".into(),
+                gas: 978,
+                gas_cost: 3,
+                depth: 1,
+                stack: Some(vec![U256::from(1)]),
+                memory: Some(vec![B256::ZERO.to_string()]),
+                ..Default::default()
+            }],
+        });
+
+        assert_eq!(
+            eip3155_lines(&trace).unwrap(),
+            vec![
+                r#"{"pc":2,"op":96,"gas":"0x3d2","gasCost":"0x3","memSize":32,"stack":["0x1"],"depth":1,"returnData":"0x","refund":"0x0","opName":"PUSH1"}"#,
+                r#"{"output":"0xff","gasUsed":"0x5212","pass":true}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eip3155_lines_rejects_other_tracers() {
+        assert!(eip3155_lines(&GethTrace::NoopTracer(Default::default())).is_err());
+    }
+}
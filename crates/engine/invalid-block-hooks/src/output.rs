@@ -0,0 +1,36 @@
+//! Helpers for writing the output files of the hooks.
+
+use pretty_assertions::Comparison;
+use serde::Serialize;
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Serializes and saves a value to a JSON file in the output directory.
+pub(crate) fn save_file<T: Serialize>(
+    output_directory: &Path,
+    filename: String,
+    value: &T,
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Compares two values and saves their diff to a file in the output directory.
+pub(crate) fn save_diff<T: PartialEq + Debug>(
+    output_directory: &Path,
+    filename: String,
+    original: &T,
+    new: &T,
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    let diff = Comparison::new(original, new);
+    File::create(&path)?.write_all(diff.to_string().as_bytes())?;
+
+    Ok(path)
+}
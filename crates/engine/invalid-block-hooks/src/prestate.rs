@@ -0,0 +1,210 @@
+use crate::{
+    output::{save_diff, save_file},
+    trace::{healthy_node_traces, save_failed_transaction, trace_block, tx_prefix, BlockTraces},
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::{GethDebugTracingOptions, PreStateConfig, TraceResult};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::{BlockExecutionOutput, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use std::path::PathBuf;
+
+/// Hook for writing prestate traces of invalid blocks.
+///
+/// Every transaction of the block is re-executed with the `prestateTracer` in diff mode, which
+/// captures the state each transaction touched before and after its execution.
+#[derive(Debug)]
+pub struct InvalidBlockPreStateHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: E,
+    /// The directory to write the traces to. Additionally, diff files will be written to this
+    /// directory in case of mismatches against the healthy node.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the traces against.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+}
+
+impl<P, E> InvalidBlockPreStateHook<P, E> {
+    /// Creates a new prestate hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { provider, evm_config, output_directory, healthy_node_client }
+    }
+}
+
+impl<P, E, N> InvalidBlockPreStateHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    /// Returns the tracing options of the `prestateTracer` in diff mode.
+    fn tracing_options() -> GethDebugTracingOptions {
+        GethDebugTracingOptions::prestate_tracer(PreStateConfig {
+            diff_mode: Some(true),
+            ..Default::default()
+        })
+    }
+
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let BlockTraces { traces, failed } = trace_block(
+            &self.provider,
+            &self.evm_config,
+            parent_header,
+            block,
+            Self::tracing_options(),
+        )?;
+
+        let block_prefix = format!("{}_{}", block.number(), block.hash());
+        let mut re_executed_paths = Vec::with_capacity(traces.len());
+        for (index, (tx_hash, trace)) in traces.iter().enumerate() {
+            let filename =
+                format!("{}.prestate.re_executed.json", tx_prefix(&block_prefix, index, *tx_hash));
+            re_executed_paths.push(save_file(&self.output_directory, filename, trace)?);
+        }
+        if let Some(failed) = &failed {
+            save_failed_transaction(&self.output_directory, &block_prefix, "prestate", failed)?;
+        }
+
+        let Some(healthy_node_client) = &self.healthy_node_client else { return Ok(()) };
+        let healthy_traces =
+            healthy_node_traces(healthy_node_client, block.number(), Self::tracing_options())?;
+        if failed.is_none() && healthy_traces.len() != traces.len() {
+            warn!(
+                target: "engine::invalid_block_hooks::prestate",
+                re_executed = traces.len(),
+                healthy = healthy_traces.len(),
+                "Transaction count mismatch against healthy node"
+            );
+        }
+
+        for (index, ((tx_hash, trace), healthy)) in traces.iter().zip(healthy_traces).enumerate() {
+            let tx_prefix = tx_prefix(&block_prefix, index, *tx_hash);
+            let healthy_trace = match healthy {
+                TraceResult::Success { result, .. } => result,
+                TraceResult::Error { error, tx_hash: healthy_tx_hash } => {
+                    warn!(
+                        target: "engine::invalid_block_hooks::prestate",
+                        %tx_hash,
+                        ?healthy_tx_hash,
+                        %error,
+                        "Healthy node failed to trace transaction"
+                    );
+                    continue
+                }
+            };
+
+            let filename = format!("{tx_prefix}.prestate.healthy.json");
+            let healthy_path = save_file(&self.output_directory, filename, &healthy_trace)?;
+
+            if trace != &healthy_trace {
+                let filename = format!("{tx_prefix}.prestate.diff");
+                let diff_path = save_diff(&self.output_directory, filename, trace, &healthy_trace)?;
+                warn!(
+                    target: "engine::invalid_block_hooks::prestate",
+                    %tx_hash,
+                    diff_path = %diff_path.display(),
+                    re_executed_path = %re_executed_paths[index].display(),
+                    healthy_path = %healthy_path.display(),
+                    "Prestate trace mismatch against healthy node"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockPreStateHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::prestate", %err, "Failed to invoke hook");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::tests::block_with_failing_tx;
+    use alloy_consensus::transaction::TxHashRef;
+    use alloy_rpc_types_trace::geth::{GethDebugBuiltInTracerType, GethDebugTracerType};
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::BlockBody;
+    use reth_provider::test_utils::MockEthProvider;
+    use tempfile::TempDir;
+
+    type Hook = InvalidBlockPreStateHook<MockEthProvider, EthEvmConfig>;
+
+    #[test]
+    fn test_tracing_options() {
+        let opts = <Hook>::tracing_options();
+        assert_eq!(
+            opts.tracer,
+            Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::PreStateTracer))
+        );
+        let config = opts.tracer_config.into_pre_state_config().unwrap();
+        assert_eq!(config.diff_mode, Some(true));
+    }
+
+    #[test]
+    fn test_on_invalid_block_writes_traces_and_failed_tx() {
+        let temp_dir = TempDir::new().unwrap();
+        let (provider, parent, block) = block_with_failing_tx();
+        let hook = InvalidBlockPreStateHook::new(
+            provider,
+            EthEvmConfig::mainnet(),
+            temp_dir.path().to_path_buf(),
+            None,
+        );
+
+        hook.on_invalid_block(&parent, &block).unwrap();
+
+        let block_prefix = format!("{}_{}", block.number(), block.hash());
+        let transactions = block.body().transactions();
+
+        let trace_path = temp_dir.path().join(format!(
+            "{}.prestate.re_executed.json",
+            tx_prefix(&block_prefix, 0, *transactions[0].tx_hash())
+        ));
+        let trace: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(trace_path).unwrap()).unwrap();
+        assert!(trace.get("pre").is_some() && trace.get("post").is_some());
+
+        let error_path = temp_dir.path().join(format!(
+            "{}.prestate.error.json",
+            tx_prefix(&block_prefix, 1, *transactions[1].tx_hash())
+        ));
+        let error: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(error_path).unwrap()).unwrap();
+        assert_eq!(error["index"], 1);
+        assert!(error["error"].as_str().is_some_and(|error| !error.is_empty()));
+
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 2);
+    }
+}
@@ -0,0 +1,249 @@
+//! Re-execution of invalid blocks with a geth debug tracer, shared by the tracing hooks.
+
+use crate::output::save_file;
+use alloy_consensus::transaction::TxHashRef;
+use alloy_primitives::TxHash;
+use alloy_rpc_types_trace::geth::{GethDebugTracingOptions, GethTrace, TraceResult};
+use reth_evm::{block::BlockExecutor, ConfigureEvm, Evm};
+use reth_primitives_traits::{BlockBody, NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::StateProviderFactory;
+use reth_revm::{database::StateProviderDatabase, db::State};
+use reth_rpc_api::DebugApiClient;
+use reth_tracing::tracing::warn;
+use revm::DatabaseCommit;
+use revm_inspectors::tracing::{DebugInspector, TransactionContext};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// The traces of the transactions of a re-executed block.
+#[derive(Debug)]
+pub(crate) struct BlockTraces {
+    /// The traces of the transactions that were executed, in block order.
+    pub(crate) traces: Vec<(TxHash, GethTrace)>,
+    /// The transaction that failed to execute, if any.
+    ///
+    /// Transactions after it are not executed, because their state depends on it.
+    pub(crate) failed: Option<FailedTransaction>,
+}
+
+/// A transaction of a re-executed block that failed to execute.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct FailedTransaction {
+    /// Index of the transaction in the block.
+    pub(crate) index: usize,
+    /// Hash of the transaction.
+    pub(crate) tx_hash: TxHash,
+    /// The execution error.
+    pub(crate) error: String,
+}
+
+/// Re-executes the block on top of its parent state and traces every transaction with the
+/// tracer configured by `opts`, the same way `debug_traceBlock` does.
+///
+/// Execution stops at the first transaction that fails, which is expected for an invalid block,
+/// and the traces of the transactions before it are returned.
+pub(crate) fn trace_block<P, E, N>(
+    provider: &P,
+    evm_config: &E,
+    parent_header: &SealedHeader<N::BlockHeader>,
+    block: &RecoveredBlock<N::Block>,
+    opts: GethDebugTracingOptions,
+) -> eyre::Result<BlockTraces>
+where
+    P: StateProviderFactory,
+    E: ConfigureEvm<Primitives = N>,
+    N: NodePrimitives,
+{
+    let mut db = State::builder()
+        .with_database(StateProviderDatabase::new(
+            provider.state_by_block_hash(parent_header.hash())?,
+        ))
+        .build();
+
+    evm_config.executor_for_block(&mut db, block.sealed_block())?.apply_pre_execution_changes()?;
+
+    let evm_env = evm_config.evm_env(block.header())?;
+    let mut inspector = DebugInspector::new(opts)?;
+    let mut traces = Vec::with_capacity(block.body().transaction_count());
+    let mut transactions = block.transactions_recovered().enumerate().peekable();
+    while let Some((index, tx)) = transactions.next() {
+        let tx_hash = *tx.tx_hash();
+        let tx_env = evm_config.tx_env(tx);
+
+        let res = match evm_config
+            .evm_with_env_and_inspector(&mut db, evm_env.clone(), &mut inspector)
+            .transact(tx_env.clone())
+        {
+            Ok(res) => res,
+            Err(err) => {
+                let failed = FailedTransaction { index, tx_hash, error: err.to_string() };
+                return Ok(BlockTraces { traces, failed: Some(failed) })
+            }
+        };
+        let trace = inspector.get_result(
+            Some(TransactionContext {
+                block_hash: Some(block.hash()),
+                tx_hash: Some(tx_hash),
+                tx_index: Some(index),
+            }),
+            &tx_env,
+            &evm_env.block_env,
+            &res,
+            &mut db,
+        )?;
+
+        traces.push((tx_hash, trace));
+        if transactions.peek().is_some() {
+            inspector.fuse()?;
+            // The next transaction executes on top of the state changes of this one.
+            db.commit(res.state);
+        }
+    }
+
+    Ok(BlockTraces { traces, failed: None })
+}
+
+/// Saves the failed transaction of a re-executed block to a file in the output directory and
+/// logs it.
+pub(crate) fn save_failed_transaction(
+    output_directory: &Path,
+    block_prefix: &str,
+    tracer: &str,
+    failed: &FailedTransaction,
+) -> eyre::Result<PathBuf> {
+    let filename =
+        format!("{}.{tracer}.error.json", tx_prefix(block_prefix, failed.index, failed.tx_hash));
+    let path = save_file(output_directory, filename, failed)?;
+    warn!(
+        target: "engine::invalid_block_hooks",
+        tracer,
+        index = failed.index,
+        tx_hash = %failed.tx_hash,
+        error = %failed.error,
+        path = %path.display(),
+        "Transaction of invalid block failed to execute"
+    );
+
+    Ok(path)
+}
+
+/// Fetches the traces of the block with the same number from the healthy node.
+pub(crate) fn healthy_node_traces(
+    client: &jsonrpsee::http_client::HttpClient,
+    block_number: u64,
+    opts: GethDebugTracingOptions,
+) -> eyre::Result<Vec<TraceResult>> {
+    Ok(futures::executor::block_on(DebugApiClient::<()>::debug_trace_block_by_number(
+        client,
+        block_number.into(),
+        Some(opts),
+    ))?)
+}
+
+/// Returns the prefix of the output files of the transaction at `index` of the block.
+pub(crate) fn tx_prefix(block_prefix: &str, index: usize, tx_hash: TxHash) -> String {
+    format!("{block_prefix}.{index}_{tx_hash}")
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_primitives::{Address, TxKind, U256};
+    use alloy_rpc_types_trace::geth::{GethDefaultTracingOptions, PreStateConfig};
+    use reth_chainspec::ChainSpec;
+    use reth_ethereum_primitives::{Block, EthPrimitives, Transaction};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_testing_utils::generators::{self, random_block, sign_tx_with_key_pair, BlockParams};
+
+    /// Returns a provider and a block of two transfers of the same sender, where the sender can
+    /// only pay for the first one.
+    pub(crate) fn block_with_failing_tx(
+    ) -> (MockEthProvider<EthPrimitives, ChainSpec>, SealedHeader<Header>, RecoveredBlock<Block>)
+    {
+        let mut rng = generators::rng();
+        let key_pair = generators::generate_key(&mut rng);
+        let parent = generators::random_header(&mut rng, 0, None);
+
+        let transfer = |nonce| {
+            sign_tx_with_key_pair(
+                key_pair,
+                Transaction::Legacy(TxLegacy {
+                    nonce,
+                    gas_price: 1,
+                    gas_limit: 21_000,
+                    to: TxKind::Call(Address::random()),
+                    value: U256::ZERO,
+                    ..Default::default()
+                }),
+            )
+        };
+
+        let mut block = random_block(
+            &mut rng,
+            1,
+            BlockParams { parent: Some(parent.hash()), tx_count: Some(0), ..Default::default() },
+        )
+        .unseal();
+        block.header.gas_limit = 1_000_000;
+        block.header.timestamp = 0;
+        block.header.base_fee_per_gas = None;
+        block.body.transactions = vec![transfer(0), transfer(1)];
+        let block = block.seal_slow().try_recover().unwrap();
+
+        let provider = MockEthProvider::<EthPrimitives, ChainSpec>::default();
+        provider.add_account(block.senders()[0], ExtendedAccount::new(0, U256::from(21_000)));
+
+        (provider, parent, block)
+    }
+
+    #[test]
+    fn test_trace_block_stops_at_failing_tx() {
+        let (provider, parent, block) = block_with_failing_tx();
+
+        let BlockTraces { traces, failed } = trace_block(
+            &provider,
+            &EthEvmConfig::mainnet(),
+            &parent,
+            &block,
+            GethDebugTracingOptions::prestate_tracer(PreStateConfig::default()),
+        )
+        .unwrap();
+
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].0, *block.body().transactions[0].tx_hash());
+        assert!(matches!(traces[0].1, GethTrace::PreStateTracer(_)));
+
+        let failed = failed.unwrap();
+        assert_eq!(failed.index, 1);
+        assert_eq!(failed.tx_hash, *block.body().transactions[1].tx_hash());
+        assert!(!failed.error.is_empty());
+    }
+
+    #[test]
+    fn test_trace_block_all_txs() {
+        let (provider, parent, block) = block_with_failing_tx();
+        provider.add_account(block.senders()[0], ExtendedAccount::new(0, U256::from(42_000)));
+
+        let BlockTraces { traces, failed } = trace_block(
+            &provider,
+            &EthEvmConfig::mainnet(),
+            &parent,
+            &block,
+            GethDebugTracingOptions {
+                config: GethDefaultTracingOptions::default(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert!(failed.is_none());
+        assert_eq!(
+            traces.iter().map(|(tx_hash, _)| *tx_hash).collect::<Vec<_>>(),
+            block.body().transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>()
+        );
+        assert!(traces.iter().all(|(_, trace)| matches!(trace, GethTrace::Default(_))));
+    }
+}
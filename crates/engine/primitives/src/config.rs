@@ -194,11 +194,22 @@ pub struct TreeConfig {
     /// When set, BAL storage slots are not read into the execution cache. BAL hashed-state
     /// streaming for parallel state-root computation is controlled separately.
     disable_bal_batch_io: bool,
+    /// Whether to execute blocks without a BAL through the speculative parallel executor.
+    ///
+    /// Transactions are executed optimistically in parallel against the parent state and
+    /// re-executed in order when they conflict with an earlier transaction of the block.
+    speculative_parallel_execution: bool,
     /// Whether to skip trie state-root computation during engine validation.
     ///
     /// This trusts the block header's state root. It is intended for experiments that measure
     /// execution without trie state-root work.
     skip_state_root: bool,
+    /// Path of the execution cache snapshot.
+    ///
+    /// When set, the hot working set of the cross-block execution cache is written to this file
+    /// on shutdown and restored from it on startup.
+    #[cfg(feature = "std")]
+    execution_cache_snapshot_path: Option<std::path::PathBuf>,
     /// Maximum random jitter applied before each proof computation (trie-debug only).
     /// When set, each proof worker sleeps for a random duration up to this value
     /// before starting a proof calculation.
@@ -246,7 +257,10 @@ impl Default for TreeConfig {
             disable_bal_parallel_execution: false,
             disable_bal_parallel_state_root: false,
             disable_bal_batch_io: false,
+            speculative_parallel_execution: false,
             skip_state_root: false,
+            #[cfg(feature = "std")]
+            execution_cache_snapshot_path: None,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
         }
@@ -324,7 +338,10 @@ impl TreeConfig {
             disable_bal_parallel_execution: false,
             disable_bal_parallel_state_root: false,
             disable_bal_batch_io: false,
+            speculative_parallel_execution: false,
             skip_state_root: false,
+            #[cfg(feature = "std")]
+            execution_cache_snapshot_path: None,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
         }
@@ -755,6 +772,20 @@ impl TreeConfig {
         self
     }
 
+    /// Returns whether blocks without a BAL are executed by the speculative parallel executor.
+    pub const fn speculative_parallel_execution(&self) -> bool {
+        self.speculative_parallel_execution
+    }
+
+    /// Setter for whether to execute blocks without a BAL by the speculative parallel executor.
+    pub const fn with_speculative_parallel_execution(
+        mut self,
+        speculative_parallel_execution: bool,
+    ) -> Self {
+        self.speculative_parallel_execution = speculative_parallel_execution;
+        self
+    }
+
     /// Returns whether trie state-root computation is skipped during engine validation.
     pub const fn skip_state_root(&self) -> bool {
         self.skip_state_root
@@ -766,6 +797,22 @@ impl TreeConfig {
         self
     }
 
+    /// Returns the path of the execution cache snapshot, if configured.
+    #[cfg(feature = "std")]
+    pub fn execution_cache_snapshot_path(&self) -> Option<&std::path::Path> {
+        self.execution_cache_snapshot_path.as_deref()
+    }
+
+    /// Setter for the path of the execution cache snapshot.
+    #[cfg(feature = "std")]
+    pub fn with_execution_cache_snapshot_path(
+        mut self,
+        execution_cache_snapshot_path: Option<std::path::PathBuf>,
+    ) -> Self {
+        self.execution_cache_snapshot_path = execution_cache_snapshot_path;
+        self
+    }
+
     /// Returns the proof jitter duration, if configured (trie-debug only).
     #[cfg(feature = "trie-debug")]
     pub const fn proof_jitter(&self) -> Option<Duration> {
@@ -0,0 +1,86 @@
+//! Types of fork-choice enforced inclusion lists, see [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::PayloadStatus;
+use serde::{Deserialize, Serialize};
+
+/// Maximum total size of the transactions of an inclusion list, in bytes.
+pub const MAX_BYTES_PER_INCLUSION_LIST: usize = 8192;
+
+/// Status of a payload that was validated against an inclusion list.
+///
+/// This extends [`PayloadStatus`] with the `INCLUSION_LIST_UNSATISFIED` status, which is reported
+/// for payloads that are valid but omit a transaction of the inclusion list that could have been
+/// appended to the block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum InclusionListPayloadStatus {
+    /// The payload is valid, but doesn't satisfy the inclusion list.
+    Unsatisfied(UnsatisfiedInclusionList),
+    /// The status of the payload, if it satisfies the inclusion list or isn't valid.
+    Payload(PayloadStatus),
+}
+
+impl InclusionListPayloadStatus {
+    /// Creates the status of a valid payload that doesn't satisfy the inclusion list.
+    pub const fn unsatisfied(latest_valid_hash: B256) -> Self {
+        Self::Unsatisfied(UnsatisfiedInclusionList {
+            status: InclusionListUnsatisfied::InclusionListUnsatisfied,
+            latest_valid_hash,
+        })
+    }
+
+    /// Returns `true` if the payload doesn't satisfy the inclusion list.
+    pub const fn is_unsatisfied(&self) -> bool {
+        matches!(self, Self::Unsatisfied(_))
+    }
+}
+
+impl From<PayloadStatus> for InclusionListPayloadStatus {
+    fn from(status: PayloadStatus) -> Self {
+        Self::Payload(status)
+    }
+}
+
+/// Response for a valid payload that doesn't satisfy the inclusion list.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsatisfiedInclusionList {
+    /// Always `INCLUSION_LIST_UNSATISFIED`.
+    pub status: InclusionListUnsatisfied,
+    /// Hash of the payload, which is valid.
+    pub latest_valid_hash: B256,
+}
+
+/// The `INCLUSION_LIST_UNSATISFIED` payload status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InclusionListUnsatisfied {
+    /// The payload doesn't satisfy the inclusion list.
+    #[serde(rename = "INCLUSION_LIST_UNSATISFIED")]
+    InclusionListUnsatisfied,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::PayloadStatusEnum;
+
+    #[test]
+    fn serde_inclusion_list_status() {
+        let hash = B256::repeat_byte(1);
+        let unsatisfied = InclusionListPayloadStatus::unsatisfied(hash);
+        let json = serde_json::to_string(&unsatisfied).unwrap();
+        assert_eq!(
+            json,
+            r#"{"status":"INCLUSION_LIST_UNSATISFIED","latestValidHash":"0x0101010101010101010101010101010101010101010101010101010101010101"}"#
+        );
+        assert_eq!(serde_json::from_str::<InclusionListPayloadStatus>(&json).unwrap(), unsatisfied);
+
+        let valid = InclusionListPayloadStatus::from(PayloadStatus::new(
+            PayloadStatusEnum::Valid,
+            Some(hash),
+        ));
+        let json = serde_json::to_string(&valid).unwrap();
+        assert_eq!(serde_json::from_str::<InclusionListPayloadStatus>(&json).unwrap(), valid);
+    }
+}
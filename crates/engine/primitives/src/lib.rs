@@ -40,6 +40,11 @@ pub use message::*;
 mod event;
 pub use event::*;
 
+#[cfg(feature = "focil")]
+mod inclusion_list;
+#[cfg(feature = "focil")]
+pub use inclusion_list::*;
+
 mod invalid_block_hook;
 pub use invalid_block_hook::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
 
@@ -1,3 +1,5 @@
+#[cfg(feature = "focil")]
+use crate::InclusionListPayloadStatus;
 use crate::{
     error::BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, ExecutionPayload, ForkchoiceStatus,
 };
@@ -262,6 +264,17 @@ pub enum BeaconEngineMessage<Payload: PayloadTypes> {
         /// When this message was enqueued, used to measure backpressure wait time.
         enqueued_at: Instant,
     },
+    /// Message with new payload that must satisfy an inclusion list, see
+    /// [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+    #[cfg(feature = "focil")]
+    NewPayloadWithInclusionList {
+        /// The execution payload received by Engine API.
+        payload: Payload::ExecutionData,
+        /// The EIP-2718 encoded transactions of the inclusion list.
+        inclusion_list: Vec<Bytes>,
+        /// The sender for returning payload status result.
+        tx: oneshot::Sender<Result<InclusionListPayloadStatus, BeaconOnNewPayloadError>>,
+    },
     /// Message with updated forkchoice state.
     ForkchoiceUpdated {
         /// The updated forkchoice state.
@@ -294,6 +307,17 @@ impl<Payload: PayloadTypes> Display for BeaconEngineMessage<Payload> {
                     payload.block_hash()
                 )
             }
+            #[cfg(feature = "focil")]
+            Self::NewPayloadWithInclusionList { payload, inclusion_list, .. } => {
+                write!(
+                    f,
+                    "NewPayloadWithInclusionList(parent: {}, number: {}, hash: {}, inclusion_list: {})",
+                    payload.parent_hash(),
+                    payload.block_number(),
+                    payload.block_hash(),
+                    inclusion_list.len()
+                )
+            }
             Self::ForkchoiceUpdated { state, payload_attrs, .. } => {
                 // we don't want to print the entire payload attributes, because for OP this
                 // includes all txs
@@ -362,6 +386,26 @@ where
         rx.await.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)?
     }
 
+    /// Sends a new payload message with an inclusion list to the beacon consensus engine and waits
+    /// for a response.
+    ///
+    /// If the payload is valid but omits a transaction of the inclusion list that could have been
+    /// included, [`InclusionListPayloadStatus::Unsatisfied`] is returned.
+    #[cfg(feature = "focil")]
+    pub async fn new_payload_with_inclusion_list(
+        &self,
+        payload: Payload::ExecutionData,
+        inclusion_list: Vec<Bytes>,
+    ) -> Result<InclusionListPayloadStatus, BeaconOnNewPayloadError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::NewPayloadWithInclusionList {
+            payload,
+            inclusion_list,
+            tx,
+        });
+        rx.await.map_err(|_| BeaconOnNewPayloadError::EngineUnavailable)?
+    }
+
     /// Sends a forkchoice update message to the beacon consensus engine and waits for a response.
     ///
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/shanghai.md#engine_forkchoiceupdatedv2>
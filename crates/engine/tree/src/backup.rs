@@ -0,0 +1,185 @@
+//! Backup server run by the node, see [`BackupServer`].
+
+use crate::persistence::PersistenceHandle;
+use reth_db_common::backup::{create_backup, handle_backup_request};
+use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
+use reth_tasks::TaskExecutor;
+use std::{io, path::PathBuf};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{UnixListener, UnixStream},
+};
+use tracing::{debug, info, warn};
+
+/// Creates backups of the storage of a running node on request of `reth db backup`.
+///
+/// Requests are received on a Unix socket and served one at a time. The persistence service is
+/// paused while a backup is taken, so that the MDBX database, `RocksDB` and static files are
+/// snapshotted at the same block and `RocksDB` is checkpointed from the primary instance.
+#[derive(Debug)]
+pub struct BackupServer<N: ProviderNodeTypes> {
+    /// Path of the Unix socket.
+    path: PathBuf,
+    /// The provider factory of the node.
+    provider_factory: ProviderFactory<N>,
+    /// Handle to the persistence service of the node.
+    persistence: PersistenceHandle<N::Primitives>,
+    /// Executor the backups are taken on.
+    executor: TaskExecutor,
+}
+
+impl<N: ProviderNodeTypes> BackupServer<N> {
+    /// Creates a new server that will listen on the given socket path.
+    pub const fn new(
+        path: PathBuf,
+        provider_factory: ProviderFactory<N>,
+        persistence: PersistenceHandle<N::Primitives>,
+        executor: TaskExecutor,
+    ) -> Self {
+        Self { path, provider_factory, persistence, executor }
+    }
+
+    /// Binds the socket and serves backup requests until the returned future is dropped.
+    ///
+    /// A stale socket file left behind by a previous run is removed before binding.
+    pub async fn run(self) -> io::Result<()> {
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        let listener = UnixListener::bind(&self.path)?;
+        debug!(target: "engine::backup", path = %self.path.display(), "Serving backup requests");
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            if let Err(err) = self.serve(stream).await {
+                debug!(target: "engine::backup", %err, "Failed to serve backup request");
+            }
+        }
+    }
+
+    /// Reads a single request from `stream`, takes the backup and writes the response.
+    async fn serve(&self, stream: UnixStream) -> io::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut request = String::new();
+        BufReader::new(reader).read_line(&mut request).await?;
+
+        let provider_factory = self.provider_factory.clone();
+        let persistence = self.persistence.clone();
+        let response = self
+            .executor
+            .spawn_blocking(move || {
+                handle_backup_request(&request, |request| {
+                    info!(target: "engine::backup", dest = ?request.dest, "Pausing persistence to create backup");
+                    let _paused = persistence
+                        .pause()
+                        .map_err(|_| io::Error::other("persistence service stopped"))?;
+                    let manifest = create_backup(&provider_factory, request)
+                        .inspect_err(|err| warn!(target: "engine::backup", %err, "Failed to create backup"))?;
+                    info!(target: "engine::backup", block_number = manifest.block_number, dest = ?request.dest, "Backup created");
+                    Ok(manifest)
+                })
+            })
+            .await
+            .map_err(io::Error::other)?;
+
+        writer.write_all(response.as_bytes()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chain_state::test_utils::TestBlockBuilder;
+    use reth_chainspec::MAINNET;
+    use reth_db::test_utils::{create_test_rw_db_with_path, tempdir_path};
+    use reth_db_common::backup::{request_backup, BackupRequest};
+    use reth_ethereum_primitives::EthPrimitives;
+    use reth_exex_types::FinishedExExHeight;
+    use reth_provider::{
+        providers::{RocksDBBuilder, StaticFileProvider},
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        BlockNumReader, StaticFileSegment,
+    };
+    use reth_prune::Pruner;
+    use reth_tasks::Runtime;
+    use std::time::Duration;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    const BLOCKS: u64 = 200;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn backup_while_persisting_blocks() {
+        reth_tracing::init_test_tracing();
+
+        let provider_factory = create_test_provider_factory();
+        let (_finished_exex_height_tx, finished_exex_height_rx) =
+            tokio::sync::watch::channel(FinishedExExHeight::NoExExs);
+        let pruner = Pruner::new_with_factory(
+            provider_factory.clone(),
+            vec![],
+            5,
+            0,
+            None,
+            finished_exex_height_rx,
+        );
+        let (sync_metrics_tx, _sync_metrics_rx) = unbounded_channel();
+        let persistence = PersistenceHandle::<EthPrimitives>::spawn_service(
+            provider_factory.clone(),
+            pruner,
+            sync_metrics_tx,
+        );
+
+        let dir = tempdir_path();
+        let socket = dir.join("backup.ipc");
+        let server = BackupServer::new(
+            socket.clone(),
+            provider_factory.clone(),
+            persistence.clone(),
+            Runtime::test(),
+        );
+        tokio::spawn(server.run());
+
+        // persist blocks one by one, so that the backup is requested while blocks are written
+        let writer = std::thread::spawn(move || {
+            let mut test_block_builder = TestBlockBuilder::eth();
+            for number in 0..BLOCKS {
+                let blocks = test_block_builder.get_executed_blocks(number..number + 1).collect();
+                let (tx, rx) = crossbeam_channel::bounded(1);
+                persistence.save_blocks(blocks, tx).unwrap();
+                rx.recv().unwrap();
+            }
+        });
+
+        while !socket.exists() || provider_factory.best_block_number().unwrap() < 10 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let dest = dir.join("backup");
+        let request = BackupRequest { dest: dest.clone(), compact: false, hardlink: true };
+        let manifest = tokio::task::spawn_blocking(move || request_backup(&socket, &request))
+            .await
+            .unwrap()
+            .unwrap()
+            .expect("node is listening");
+
+        // persistence resumes after the backup
+        writer.join().unwrap();
+        assert_eq!(provider_factory.best_block_number().unwrap(), BLOCKS - 1);
+        assert!(manifest.block_number >= 10);
+
+        // no block was written while the storages were snapshotted
+        assert_eq!(manifest.static_files[&StaticFileSegment::Headers], manifest.block_number);
+
+        let backup_factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            create_test_rw_db_with_path(dest.join("db")),
+            MAINNET.clone(),
+            StaticFileProvider::read_write(dest.join("static_files")).unwrap(),
+            RocksDBBuilder::new(dest.join("rocksdb")).with_default_tables().build().unwrap(),
+            Runtime::test(),
+        )
+        .unwrap();
+        assert_eq!(backup_factory.check_consistency().unwrap(), (None, None));
+        assert_eq!(backup_factory.best_block_number().unwrap(), manifest.block_number);
+        assert_eq!(backup_factory.last_block_number().unwrap(), manifest.block_number);
+    }
+}
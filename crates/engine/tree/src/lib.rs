@@ -94,6 +94,11 @@
 
 /// Support for backfill sync mode.
 pub mod backfill;
+/// Backups of the storage of a running node.
+#[cfg(unix)]
+pub mod backup;
+#[cfg(not(unix))]
+use reth_db_common as _;
 /// The type that drives the chain forward.
 pub mod chain;
 /// Support for downloading blocks on demand for live sync.
@@ -1,6 +1,6 @@
 use crate::metrics::PersistenceMetrics;
 use alloy_eips::BlockNumHash;
-use crossbeam_channel::Sender as CrossbeamSender;
+use crossbeam_channel::{Receiver as CrossbeamReceiver, Sender as CrossbeamSender};
 use reth_chain_state::ExecutedBlock;
 use reth_errors::ProviderError;
 use reth_ethereum_primitives::EthPrimitives;
@@ -122,6 +122,13 @@ where
                 PersistenceAction::SaveSafeBlock(safe_block) => {
                     self.pending_safe_block = Some(safe_block);
                 }
+                PersistenceAction::Pause(paused, resume) => {
+                    debug!(target: "engine::persistence", "Pausing persistence");
+                    let _ = paused.send(());
+                    // blocks until the pause guard is dropped
+                    let _ = resume.recv();
+                    debug!(target: "engine::persistence", "Resuming persistence");
+                }
             }
         }
         Ok(())
@@ -250,6 +257,12 @@ pub enum PersistenceAction<N: NodePrimitives = EthPrimitives> {
 
     /// Update the persisted safe block on disk
     SaveSafeBlock(u64),
+
+    /// Holds the service until the second channel is disconnected.
+    ///
+    /// The first channel is notified once all previously queued actions, including the pruner
+    /// runs they triggered, have been written to disk.
+    Pause(CrossbeamSender<()>, CrossbeamReceiver<()>),
 }
 
 /// A handle to the persistence service
@@ -361,6 +374,28 @@ impl<T: NodePrimitives> PersistenceHandle<T> {
     ) -> Result<(), SendError<PersistenceAction<T>>> {
         self.send_action(PersistenceAction::RemoveBlocksAbove(block_num, tx))
     }
+
+    /// Pauses the persistence service, blocking until all previously queued actions have been
+    /// written to disk.
+    ///
+    /// No blocks are written, removed or pruned until the returned guard is dropped. Actions sent
+    /// in the meantime are queued and processed once the service resumes.
+    pub fn pause(&self) -> Result<PersistencePauseGuard, SendError<PersistenceAction<T>>> {
+        let (paused_tx, paused_rx) = crossbeam_channel::bounded(1);
+        let (resume_tx, resume_rx) = crossbeam_channel::bounded(0);
+        self.send_action(PersistenceAction::Pause(paused_tx, resume_rx))?;
+        // the sender is only dropped without a notification if the service stopped, in which case
+        // nothing is written anymore either
+        let _ = paused_rx.recv();
+        Ok(PersistencePauseGuard { _resume: resume_tx })
+    }
+}
+
+/// Keeps the persistence service paused until dropped, see [`PersistenceHandle::pause`].
+#[derive(Debug)]
+pub struct PersistencePauseGuard {
+    /// Disconnecting this channel resumes the service.
+    _resume: CrossbeamSender<()>,
 }
 
 /// Guard that joins the persistence service thread when dropped.
@@ -1,6 +1,8 @@
 //! Internal errors for the tree module.
 
-use crate::tree::payload_processor::bal::BalExecutionError;
+use crate::tree::payload_processor::{
+    bal::BalExecutionError, speculative::SpeculativeExecutionError,
+};
 use alloy_consensus::BlockHeader;
 use reth_consensus::ConsensusError;
 use reth_errors::{BlockExecutionError, BlockValidationError, ProviderError};
@@ -133,6 +135,16 @@ impl From<BalExecutionError> for InsertBlockErrorKind {
     }
 }
 
+impl From<SpeculativeExecutionError> for InsertBlockErrorKind {
+    fn from(e: SpeculativeExecutionError) -> Self {
+        match e {
+            SpeculativeExecutionError::Execution(inner) => Self::Execution(inner),
+            SpeculativeExecutionError::Provider(inner) => Self::Provider(inner),
+            SpeculativeExecutionError::Other(inner) => Self::Other(inner),
+        }
+    }
+}
+
 impl InsertBlockErrorKind {
     /// Returns an [`InsertBlockValidationError`] if the error is caused by an invalid block.
     ///
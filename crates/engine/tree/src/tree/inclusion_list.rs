@@ -0,0 +1,123 @@
+//! Validation of payloads against fork-choice enforced inclusion lists, see
+//! [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+
+use alloy_consensus::{BlockHeader, Transaction, Typed2718};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{map::HashSet, Bytes, TxHash, U256};
+use reth_errors::ProviderResult;
+use reth_primitives_traits::{Block, BlockBody, RecoveredBlock, SignedTransaction};
+use reth_provider::AccountReader;
+
+/// Returns the hash of the first inclusion list transaction that is missing from the block although
+/// it could have been appended to it.
+///
+/// A transaction could have been appended if it fits into the remaining gas of the block, pays at
+/// least the base fee, and its sender has the matching nonce and enough balance in the state after
+/// the block. Transactions that can't be decoded or recovered, and blob transactions, are ignored.
+///
+/// The given state must be the state after executing the block.
+pub(crate) fn unsatisfied_inclusion_list_transaction<B: Block>(
+    block: &RecoveredBlock<B>,
+    state: &impl AccountReader,
+    inclusion_list: &[Bytes],
+) -> ProviderResult<Option<TxHash>> {
+    let included =
+        block.body().transactions().iter().map(|tx| *tx.tx_hash()).collect::<HashSet<_>>();
+    let gas_left = block.header().gas_limit().saturating_sub(block.header().gas_used());
+    let base_fee = block.header().base_fee_per_gas().unwrap_or_default();
+
+    for encoded in inclusion_list {
+        let Ok(tx) =
+            <<B::Body as BlockBody>::Transaction as Decodable2718>::decode_2718_exact(encoded)
+        else {
+            continue
+        };
+        if included.contains(tx.tx_hash()) ||
+            tx.is_eip4844() ||
+            tx.gas_limit() > gas_left ||
+            tx.max_fee_per_gas() < base_fee as u128
+        {
+            continue
+        }
+        let Ok(sender) = tx.try_recover() else { continue };
+
+        let account = state.basic_account(&sender)?.unwrap_or_default();
+        let max_cost = U256::from(tx.gas_limit()) * U256::from(tx.max_fee_per_gas()) + tx.value();
+        if account.nonce == tx.nonce() && account.balance >= max_cost {
+            return Ok(Some(*tx.tx_hash()))
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxEip1559};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{Address, TxKind};
+    use reth_ethereum_primitives::{BlockBody as EthBlockBody, Transaction as EthTransaction};
+    use reth_primitives_traits::SignerRecoverable;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_testing_utils::generators::{self, generate_key, sign_tx_with_key_pair};
+
+    fn block(
+        transactions: Vec<reth_ethereum_primitives::TransactionSigned>,
+    ) -> RecoveredBlock<reth_ethereum_primitives::Block> {
+        let senders = transactions.iter().map(|tx| tx.recover_signer().unwrap()).collect();
+        RecoveredBlock::new_unhashed(
+            reth_ethereum_primitives::Block {
+                header: Header {
+                    gas_limit: 30_000_000,
+                    base_fee_per_gas: Some(7),
+                    ..Default::default()
+                },
+                body: EthBlockBody { transactions, ..Default::default() },
+            },
+            senders,
+        )
+    }
+
+    #[test]
+    fn detects_omitted_transaction() {
+        let mut rng = generators::rng();
+        let tx = sign_tx_with_key_pair(
+            generate_key(&mut rng),
+            EthTransaction::Eip1559(TxEip1559 {
+                chain_id: 1,
+                gas_limit: 21_000,
+                max_fee_per_gas: 10,
+                to: TxKind::Call(Address::repeat_byte(1)),
+                ..Default::default()
+            }),
+        );
+        let sender = tx.recover_signer().unwrap();
+        let inclusion_list = vec![tx.encoded_2718().into(), Bytes::from_static(&[0xff])];
+
+        let state = MockEthProvider::default();
+        state.add_account(sender, ExtendedAccount::new(0, U256::from(1_000_000)));
+
+        // the transaction could have been included
+        assert_eq!(
+            unsatisfied_inclusion_list_transaction(&block(vec![]), &state, &inclusion_list)
+                .unwrap(),
+            Some(*tx.tx_hash())
+        );
+
+        // the transaction is included
+        assert_eq!(
+            unsatisfied_inclusion_list_transaction(&block(vec![tx]), &state, &inclusion_list)
+                .unwrap(),
+            None
+        );
+
+        // the sender's nonce changed, so the transaction is no longer valid
+        state.add_account(sender, ExtendedAccount::new(1, U256::from(1_000_000)));
+        assert_eq!(
+            unsatisfied_inclusion_list_transaction(&block(vec![]), &state, &inclusion_list)
+                .unwrap(),
+            None
+        );
+    }
+}
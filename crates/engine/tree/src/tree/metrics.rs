@@ -1,4 +1,7 @@
-use crate::tree::{error::InsertBlockFatalError, TreeOutcome};
+use crate::tree::{
+    error::InsertBlockFatalError, payload_processor::speculative::SpeculativeExecutionStats,
+    TreeOutcome,
+};
 use alloy_rpc_types_engine::{PayloadStatus, PayloadStatusEnum};
 use reth_engine_primitives::{ForkchoiceStatus, OnForkChoiceUpdated};
 use reth_errors::ProviderError;
@@ -34,6 +37,8 @@ pub struct EngineApiMetrics {
     /// Metrics for EIP-7928 Block-Level Access Lists (BAL).
     #[allow(dead_code)]
     pub(crate) bal: BalMetrics,
+    /// Metrics for speculative parallel execution.
+    pub(crate) speculative: SpeculativeExecutionMetrics,
     /// Gas-bucketed execution sub-phase metrics.
     pub(crate) execution_gas_buckets: ExecutionGasBucketMetrics,
     /// Gas-bucketed block validation sub-phase metrics.
@@ -111,6 +116,21 @@ impl EngineApiMetrics {
     pub fn record_transaction_execution(&self, elapsed: Duration) {
         self.executor.transaction_execution_histogram.record(elapsed);
     }
+
+    /// Records the outcome of a block executed on the speculative path.
+    pub(crate) fn record_speculative_execution(
+        &self,
+        stats: &SpeculativeExecutionStats,
+        elapsed: Duration,
+    ) {
+        self.speculative.committed_transactions_total.increment(stats.committed as u64);
+        self.speculative
+            .beneficiary_rebased_transactions_total
+            .increment(stats.beneficiary_rebased as u64);
+        self.speculative.reexecuted_transactions_total.increment(stats.reexecuted as u64);
+        self.speculative.conflict_ratio.record(stats.conflict_ratio());
+        self.speculative.speedup.record(stats.speedup(elapsed));
+    }
 }
 
 /// Metrics for the entire blockchain tree
@@ -505,6 +525,22 @@ pub(crate) struct BalMetrics {
     pub(crate) code_changes: Gauge,
 }
 
+/// Metrics for speculative parallel execution of blocks without a BAL.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "sync.execution.speculative")]
+pub(crate) struct SpeculativeExecutionMetrics {
+    /// Total number of transactions whose speculative result was committed as-is.
+    pub(crate) committed_transactions_total: Counter,
+    /// Total number of transactions committed after rebasing the beneficiary fee credit.
+    pub(crate) beneficiary_rebased_transactions_total: Counter,
+    /// Total number of transactions re-executed after a conflict or a speculative failure.
+    pub(crate) reexecuted_transactions_total: Counter,
+    /// Share of re-executed transactions per block.
+    pub(crate) conflict_ratio: Histogram,
+    /// Estimated speedup over sequential execution per block.
+    pub(crate) speedup: Histogram,
+}
+
 /// Metrics for non-execution related block validation.
 #[derive(Metrics, Clone)]
 #[metrics(scope = "sync.block_validation")]
@@ -7,16 +7,22 @@ use crate::{
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::{eip1898::BlockWithParent, merge::EPOCH_SLOTS, BlockNumHash, NumHash};
+#[cfg(feature = "focil")]
+use alloy_primitives::Bytes;
 use alloy_primitives::{map::B256Map, B256};
 use alloy_rpc_types_engine::{
     ForkchoiceState, PayloadStatus, PayloadStatusEnum, PayloadValidationError,
 };
 use error::{InsertBlockError, InsertBlockFatalError, InsertBlockValidationError};
+#[cfg(feature = "focil")]
+use inclusion_list::unsatisfied_inclusion_list_transaction;
 use reth_chain_state::{
     CanonicalInMemoryState, ComputedTrieData, ExecutedBlock, ExecutionTimingStats,
     MemoryOverlayStateProvider, NewCanonicalChain, StateTrieOverlayManager,
 };
 use reth_consensus::{Consensus, FullConsensus};
+#[cfg(feature = "focil")]
+use reth_engine_primitives::InclusionListPayloadStatus;
 use reth_engine_primitives::{
     BeaconEngineMessage, BeaconOnNewPayloadError, ConsensusEngineEvent, ExecutionPayload,
     ForkchoiceStateTracker, NewPayloadTimings, OnForkChoiceUpdated, SlowBlockInfo,
@@ -52,6 +58,8 @@ use tracing::*;
 
 mod block_buffer;
 pub mod error;
+#[cfg(feature = "focil")]
+mod inclusion_list;
 pub mod instrumented_state;
 mod invalid_headers;
 mod metrics;
@@ -448,6 +456,7 @@ where
             last_persisted_block: BlockNumHash::new(best_block_number, header.hash()),
             rx: None,
         };
+        let persisted_head = persistence_state.last_persisted_block;
 
         let (tx, outgoing) = unbounded_channel();
         let state = EngineApiTreeState::new(
@@ -475,6 +484,7 @@ where
             changeset_cache,
             runtime,
         );
+        task.payload_validator.on_startup(persisted_head);
         let incoming = task.incoming_tx.clone();
         spawn_os_thread("engine", || {
             increase_thread_priority();
@@ -789,6 +799,80 @@ where
         Ok(outcome)
     }
 
+    /// Handles a new payload message from the engine API.
+    ///
+    /// Processes the payload, records the response metrics and sends the response produced by
+    /// `response` from the payload status to the sender of the message, then handles the tree
+    /// event of the outcome, if any.
+    fn on_new_payload_message<R>(
+        &mut self,
+        payload: T::ExecutionData,
+        tx: oneshot::Sender<Result<R, BeaconOnNewPayloadError>>,
+        response: impl FnOnce(&Self, PayloadStatus) -> Result<R, BeaconOnNewPayloadError>,
+    ) -> Result<(), InsertBlockFatalError> {
+        let start = Instant::now();
+        let gas_used = payload.gas_used();
+        let num_hash = payload.num_hash();
+        let mut output = self.on_new_payload(payload);
+        self.metrics.engine.new_payload.update_response_metrics(
+            start,
+            &mut self.metrics.engine.forkchoice_updated.latest_finish_at,
+            &output,
+            gas_used,
+        );
+
+        let maybe_event = output.as_mut().ok().and_then(|out| out.event.take());
+
+        let result = match output {
+            Ok(output) => response(self, output.outcome),
+            Err(e) => Err(BeaconOnNewPayloadError::Internal(Box::new(e))),
+        };
+
+        // emit response
+        if let Err(err) = tx.send(result) {
+            warn!(target: "engine::tree", payload=?num_hash, elapsed=?start.elapsed(), "Failed to deliver newPayload response, receiver dropped (request cancelled): {err:?}");
+            self.metrics.engine.failed_new_payload_response_deliveries.increment(1);
+        }
+
+        // handle the event if any
+        self.on_maybe_tree_event(maybe_event)?;
+        Ok(())
+    }
+
+    /// Checks a processed payload against an inclusion list, see
+    /// [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+    ///
+    /// Only valid payloads are checked: if the payload omits a transaction of the inclusion list
+    /// that could have been appended to it, the payload is reported as unsatisfying the inclusion
+    /// list. Otherwise the given status is returned as is.
+    #[cfg(feature = "focil")]
+    fn inclusion_list_status(
+        &self,
+        status: PayloadStatus,
+        block_hash: B256,
+        inclusion_list: &[Bytes],
+    ) -> ProviderResult<InclusionListPayloadStatus> {
+        if !status.is_valid() {
+            return Ok(status.into())
+        }
+        // blocks that are already persisted were validated before
+        let Some(block) = self.state.tree_state.executed_block_by_hash(block_hash) else {
+            return Ok(status.into())
+        };
+        let Some(state) = self.state_provider_builder(block_hash)? else {
+            return Ok(status.into())
+        };
+
+        let state = state.build()?;
+        if let Some(tx_hash) =
+            unsatisfied_inclusion_list_transaction(block.recovered_block(), &state, inclusion_list)?
+        {
+            debug!(target: "engine::tree", ?block_hash, ?tx_hash, "Payload doesn't satisfy inclusion list");
+            return Ok(InclusionListPayloadStatus::unsatisfied(block_hash))
+        }
+        Ok(status.into())
+    }
+
     /// Processes a payload during normal sync operation.
     #[instrument(level = "debug", target = "engine::tree", skip_all)]
     fn try_insert_payload(
@@ -1424,6 +1508,9 @@ where
     ) -> Result<(), AdvancePersistenceError> {
         trace!(target: "engine::tree", "finishing termination, persisting remaining blocks");
         let result = self.persist_until_complete();
+        if result.is_ok() {
+            self.payload_validator.on_shutdown(self.persistence_state.last_persisted_block);
+        }
         let _ = pending_termination.send(());
         result
     }
@@ -1636,35 +1723,19 @@ where
                                 }
                             }
                             BeaconEngineMessage::NewPayload { payload, tx } => {
-                                let start = Instant::now();
-                                let gas_used = payload.gas_used();
-                                let num_hash = payload.num_hash();
-                                let mut output = self.on_new_payload(payload);
-                                self.metrics.engine.new_payload.update_response_metrics(
-                                    start,
-                                    &mut self.metrics.engine.forkchoice_updated.latest_finish_at,
-                                    &output,
-                                    gas_used,
-                                );
-
-                                let maybe_event =
-                                    output.as_mut().ok().and_then(|out| out.event.take());
-
-                                // emit response
-                                if let Err(err) =
-                                    tx.send(output.map(|o| o.outcome).map_err(|e| {
-                                        BeaconOnNewPayloadError::Internal(Box::new(e))
-                                    }))
-                                {
-                                    warn!(target: "engine::tree", payload=?num_hash, elapsed=?start.elapsed(), "Failed to deliver newPayload response, receiver dropped (request cancelled): {err:?}");
-                                    self.metrics
-                                        .engine
-                                        .failed_new_payload_response_deliveries
-                                        .increment(1);
-                                }
-
-                                // handle the event if any
-                                self.on_maybe_tree_event(maybe_event)?;
+                                self.on_new_payload_message(payload, tx, |_, status| Ok(status))?;
+                            }
+                            #[cfg(feature = "focil")]
+                            BeaconEngineMessage::NewPayloadWithInclusionList {
+                                payload,
+                                inclusion_list,
+                                tx,
+                            } => {
+                                let block_hash = payload.block_hash();
+                                self.on_new_payload_message(payload, tx, |this, status| {
+                                    this.inclusion_list_status(status, block_hash, &inclusion_list)
+                                        .map_err(|e| BeaconOnNewPayloadError::Internal(Box::new(e)))
+                                })?;
                             }
                             BeaconEngineMessage::RethNewPayload {
                                 payload,
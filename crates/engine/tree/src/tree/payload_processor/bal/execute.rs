@@ -228,20 +228,21 @@ where
 }
 
 /// Closes the abort channel on drop, waking scoped workers before the scope exits.
-struct AbortGuard {
+pub(in crate::tree::payload_processor) struct AbortGuard {
     _tx: Sender<()>,
 }
 
 impl AbortGuard {
-    fn new() -> (Self, Receiver<()>) {
+    pub(in crate::tree::payload_processor) fn new() -> (Self, Receiver<()>) {
         let (tx, rx) = crossbeam_channel::bounded(0);
         (Self { _tx: tx }, rx)
     }
 }
 
-/// Mirrors `EthBlockExecutor`'s cumulative gas admission check in the ordered BAL commit loop.
+/// Mirrors `EthBlockExecutor`'s cumulative gas admission check in the ordered BAL and speculative
+/// commit loops.
 #[derive(Debug)]
-struct BlockGasTracker {
+pub(in crate::tree::payload_processor) struct BlockGasTracker {
     block_gas_limit: u64,
     enable_amsterdam_eip8037: bool,
     tx_gas_limit_cap: Option<u64>,
@@ -250,7 +251,7 @@ struct BlockGasTracker {
 }
 
 impl BlockGasTracker {
-    const fn new(
+    pub(in crate::tree::payload_processor) const fn new(
         block_gas_limit: u64,
         enable_amsterdam_eip8037: bool,
         tx_gas_limit_cap: Option<u64>,
@@ -264,7 +265,10 @@ impl BlockGasTracker {
         }
     }
 
-    fn validate_tx_limit(&self, tx_gas_limit: u64) -> Result<(), BlockExecutionError> {
+    pub(in crate::tree::payload_processor) fn validate_tx_limit(
+        &self,
+        tx_gas_limit: u64,
+    ) -> Result<(), BlockExecutionError> {
         let block_gas_used = if self.enable_amsterdam_eip8037 {
             self.block_regular_gas_used
         } else {
@@ -285,7 +289,10 @@ impl BlockGasTracker {
         Ok(())
     }
 
-    const fn record_result<H>(&mut self, result: &ResultAndState<H>) {
+    pub(in crate::tree::payload_processor) const fn record_result<H>(
+        &mut self,
+        result: &ResultAndState<H>,
+    ) {
         let gas = result.result.gas();
         self.cumulative_tx_gas_used = self.cumulative_tx_gas_used.saturating_add(gas.tx_gas_used());
         self.block_regular_gas_used =
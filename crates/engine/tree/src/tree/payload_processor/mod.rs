@@ -9,7 +9,7 @@ use crate::tree::{
     WaitForCaches,
 };
 use alloy_eip7928::bal::DecodedBal;
-use alloy_eips::{eip1898::BlockWithParent, eip4895::Withdrawal};
+use alloy_eips::{eip1898::BlockWithParent, eip4895::Withdrawal, BlockNumHash};
 use alloy_primitives::B256;
 use crossbeam_channel::{Receiver as CrossbeamReceiver, Sender as CrossbeamSender};
 use multiproof::*;
@@ -21,6 +21,7 @@ use reth_evm::{
     ConfigureEvm, ConvertTx, EvmEnvFor, ExecutableTxIterator, ExecutableTxTuple, OnStateHook,
     SpecFor, TxEnvFor,
 };
+use reth_execution_cache::{ExecutionCacheSnapshot, HotSet};
 use reth_primitives_traits::{FastInstant as Instant, NodePrimitives};
 use reth_provider::{
     BlockExecutionOutput, BlockReader, DatabaseProviderROFactory, StateProviderFactory, StateReader,
@@ -39,13 +40,14 @@ use reth_trie_sparse::{
 };
 use std::{
     ops::Not,
+    path::PathBuf,
     sync::{
         atomic::{AtomicBool, AtomicUsize},
         mpsc::{self, channel},
         Arc, OnceLock,
     },
 };
-use tracing::{debug, debug_span, instrument, trace, warn, Span};
+use tracing::{debug, debug_span, info, instrument, trace, warn, Span};
 
 pub mod bal;
 pub(crate) mod bal_prewarm_pool;
@@ -54,6 +56,7 @@ mod preserved_sparse_trie;
 pub mod prewarm;
 pub mod receipt_root_task;
 pub mod sparse_trie;
+pub mod speculative;
 
 use preserved_sparse_trie::{PreservedSparseTrie, SharedPreservedSparseTrie};
 
@@ -106,6 +109,10 @@ where
     trie_metrics: MultiProofTaskMetrics,
     /// Cross-block cache size in bytes.
     cross_block_cache_size: usize,
+    /// Hot working set of the execution cache, tracked if the cache is snapshotted.
+    execution_cache_hot_set: Option<HotSet>,
+    /// Path of the execution cache snapshot.
+    execution_cache_snapshot_path: Option<PathBuf>,
     /// Whether transactions should not be executed on prewarming task.
     disable_transaction_prewarming: bool,
     /// Whether state cache should be disable
@@ -143,6 +150,8 @@ pub struct PayloadProcessorSpawnOptions {
     pub parallel_bal_execution: bool,
     /// Pending sparse trie prune request to run after successful state root computation.
     pub pending_sparse_trie_prune: Option<SparseTrieRetainedPaths>,
+    /// Whether to execute the block through the speculative parallel path.
+    pub speculative_execution: bool,
 }
 
 impl PayloadProcessorSpawnOptions {
@@ -151,7 +160,13 @@ impl PayloadProcessorSpawnOptions {
         parallel_bal_execution: bool,
         pending_sparse_trie_prune: Option<SparseTrieRetainedPaths>,
     ) -> Self {
-        Self { parallel_bal_execution, pending_sparse_trie_prune }
+        Self { parallel_bal_execution, pending_sparse_trie_prune, speculative_execution: false }
+    }
+
+    /// Sets whether the block is executed through the speculative parallel path.
+    pub const fn with_speculative_execution(mut self, speculative_execution: bool) -> Self {
+        self.speculative_execution = speculative_execution;
+        self
     }
 }
 
@@ -183,6 +198,11 @@ where
             execution_cache: Default::default(),
             trie_metrics: Default::default(),
             cross_block_cache_size: config.cross_block_cache_size(),
+            execution_cache_hot_set: config
+                .execution_cache_snapshot_path()
+                .is_some()
+                .then(HotSet::default),
+            execution_cache_snapshot_path: config.execution_cache_snapshot_path().map(Into::into),
             disable_transaction_prewarming: config.disable_prewarming(),
             evm_config,
             disable_state_cache: config.disable_state_cache(),
@@ -303,11 +323,17 @@ where
             + Sync
             + 'static,
     {
-        let PayloadProcessorSpawnOptions { parallel_bal_execution, pending_sparse_trie_prune } =
-            options;
+        let PayloadProcessorSpawnOptions {
+            parallel_bal_execution,
+            pending_sparse_trie_prune,
+            speculative_execution,
+        } = options;
         // start preparing transactions immediately
-        let (prewarm_rx, execution_rx) =
-            self.spawn_tx_iterator(transactions, env.transaction_count, parallel_bal_execution);
+        let (prewarm_rx, execution_rx) = self.spawn_tx_iterator(
+            transactions,
+            env.transaction_count,
+            parallel_bal_execution || speculative_execution,
+        );
 
         let span = Span::current();
 
@@ -334,6 +360,7 @@ where
             provider_builder,
             Some(state_root_handle.updates_tx().clone()),
             parallel_bal_execution,
+            speculative_execution,
         );
 
         PayloadHandle {
@@ -355,18 +382,23 @@ where
         transactions: I,
         provider_builder: StateProviderBuilder<N, P>,
         parallel_bal_execution: bool,
+        speculative_execution: bool,
     ) -> IteratorPayloadHandle<Evm, I, N>
     where
         P: BlockReader + StateProviderFactory + StateReader + Clone + 'static,
     {
-        let (prewarm_rx, execution_rx) =
-            self.spawn_tx_iterator(transactions, env.transaction_count, parallel_bal_execution);
+        let (prewarm_rx, execution_rx) = self.spawn_tx_iterator(
+            transactions,
+            env.transaction_count,
+            parallel_bal_execution || speculative_execution,
+        );
         let prewarm_handle = self.spawn_caching_with(
             env,
             prewarm_rx,
             provider_builder,
             None,
             parallel_bal_execution,
+            speculative_execution,
         );
         PayloadHandle {
             state_root_handle: None,
@@ -461,14 +493,15 @@ where
     /// sequential iteration to avoid rayon overhead. For larger blocks, uses rayon parallel
     /// iteration to convert transactions in parallel while streaming results to execution.
     ///
-    /// When `parallel_bal_execution` is disabled, uses [`ForEachOrdered`] to preserve the
-    /// original transaction order. Otherwise, streams results as they become available.
+    /// When `parallel_execution` is disabled, uses [`ForEachOrdered`] to preserve the original
+    /// transaction order. Otherwise, streams results as they become available, since the BAL and
+    /// speculative executors reorder transactions themselves.
     #[instrument(level = "debug", target = "engine::tree::payload_processor", skip_all)]
     fn spawn_tx_iterator<I: ExecutableTxIterator<Evm>>(
         &self,
         transactions: I,
         transaction_count: usize,
-        parallel_bal_execution: bool,
+        parallel_execution: bool,
     ) -> (IteratorPrewarmTxReceiver<Evm, I>, IteratorExecuteTxReceiver<Evm, I>) {
         let (prewarm_tx, prewarm_rx) = mpsc::sync_channel(transaction_count);
         let (execute_tx, execute_rx) = crossbeam_channel::bounded(transaction_count);
@@ -493,9 +526,9 @@ where
             let executor = self.executor.clone();
             self.executor.spawn_blocking_named("tx-iterator", move || {
                 let (transactions, convert) = transactions.into_parts();
-                if parallel_bal_execution {
-                    // With BALs, we don't care about the order of transactions in execution and
-                    // prewarming, so we don't have to use `for_each_ordered_in`.
+                if parallel_execution {
+                    // With parallel execution, we don't care about the order of transactions in
+                    // execution and prewarming, so we don't have to use `for_each_ordered_in`.
                     executor.cpu_pool().install(|| {
                         transactions
                             .into_par_iter()
@@ -556,6 +589,9 @@ where
     /// `parallel_bal_execution` is true when the BAL execute path will execute this block. In
     /// that case prewarm runs in BAL mode: it streams BAL-derived sparse-trie updates and,
     /// unless `disable_bal_batch_io` is set, prefetches BAL-declared state into the shared cache.
+    ///
+    /// `speculative_execution` is true when the speculative executor will execute this block. Its
+    /// workers already fill the shared cache, so transaction prewarming is skipped.
     #[instrument(level = "debug", target = "engine::tree::payload_processor", skip_all)]
     fn spawn_caching_with<P>(
         &self,
@@ -564,6 +600,7 @@ where
         provider_builder: StateProviderBuilder<N, P>,
         to_sparse_trie_task: Option<CrossbeamSender<StateRootMessage>>,
         parallel_bal_execution: bool,
+        speculative_execution: bool,
     ) -> CacheTaskHandle<N::Receipt>
     where
         P: BlockReader + StateProviderFactory + StateReader + Clone + 'static,
@@ -573,6 +610,7 @@ where
                 env.decoded_bal.clone().expect("BAL dispatch implies decoded BAL"),
             )
         } else if self.disable_transaction_prewarming ||
+            speculative_execution ||
             env.transaction_count < SMALL_BLOCK_TX_THRESHOLD
         {
             PrewarmMode::Skipped
@@ -633,7 +671,7 @@ where
         } else {
             debug!("creating new execution cache on cache miss");
             let start = Instant::now();
-            let cache = ExecutionCache::new(self.cross_block_cache_size);
+            let cache = self.new_execution_cache();
             if let Some(metrics) = &self.cache_metrics {
                 metrics.record_cache_creation(start.elapsed());
             }
@@ -641,6 +679,14 @@ where
         }
     }
 
+    /// Creates an empty execution cache that shares the processor's hot set.
+    fn new_execution_cache(&self) -> ExecutionCache {
+        ExecutionCache::new_with_hot_set(
+            self.cross_block_cache_size,
+            self.execution_cache_hot_set.clone(),
+        )
+    }
+
     /// Spawns the [`SparseTrieCacheTask`] for this payload processor.
     ///
     /// The trie is preserved when the new payload is a child of the previous one.
@@ -800,7 +846,7 @@ where
             // Take existing cache (if any) or create fresh caches
             let caches = match cached.take() {
                 Some(existing) => existing.cache().clone(),
-                None => ExecutionCache::new(self.cross_block_cache_size),
+                None => self.new_execution_cache(),
             };
 
             // Insert the block's bundle state into cache
@@ -817,6 +863,101 @@ where
             debug!(target: "engine::caching", ?block_with_parent, "Updated execution cache for inserted block");
         });
     }
+
+    /// Writes the hot working set of the execution cache to the configured snapshot path.
+    ///
+    /// The snapshot is only written if the cache holds the state of the given persisted `head`,
+    /// because it can only be restored on top of that state.
+    pub fn save_execution_cache_snapshot(&self, head: BlockNumHash) {
+        let Some(path) = &self.execution_cache_snapshot_path else { return };
+
+        let start = Instant::now();
+        let mut snapshot = None;
+        self.execution_cache.update_with_guard(|cached| match cached.as_ref() {
+            Some(cache) if cache.executed_block_hash() == head.hash => {
+                snapshot = cache.cache().snapshot(head.hash);
+            }
+            cached => {
+                debug!(
+                    target: "engine::caching",
+                    ?head,
+                    cached_hash = ?cached.map(|cache| cache.executed_block_hash()),
+                    "Execution cache does not match persisted head, skipping snapshot"
+                );
+            }
+        });
+        let Some(snapshot) = snapshot else { return };
+
+        match snapshot.save(path) {
+            Ok(()) => info!(
+                target: "engine::caching",
+                ?head,
+                entries = snapshot.len(),
+                elapsed = ?start.elapsed(),
+                ?path,
+                "Saved execution cache snapshot"
+            ),
+            Err(err) => {
+                warn!(target: "engine::caching", %err, ?path, "Failed to save execution cache snapshot")
+            }
+        }
+    }
+
+    /// Restores the execution cache from the configured snapshot path in the background.
+    ///
+    /// The snapshot is discarded if it was not taken at the given persisted `head`, or if a
+    /// block has already populated the execution cache by the time it is loaded. The snapshot
+    /// file is removed once read, so a stale snapshot is never restored after an unclean
+    /// shutdown.
+    pub fn restore_execution_cache_snapshot(&self, head: BlockNumHash) {
+        let Some(path) = self.execution_cache_snapshot_path.clone() else { return };
+        let execution_cache = self.execution_cache.clone();
+        let cross_block_cache_size = self.cross_block_cache_size;
+        let hot_set = self.execution_cache_hot_set.clone();
+
+        self.executor.spawn_blocking_named("exec-cache-load", move || {
+            let start = Instant::now();
+            let snapshot = match ExecutionCacheSnapshot::load(&path) {
+                Ok(Some(snapshot)) => snapshot,
+                Ok(None) => return,
+                Err(err) => {
+                    warn!(target: "engine::caching", %err, ?path, "Failed to load execution cache snapshot");
+                    let _ = reth_fs_util::remove_file(&path);
+                    return
+                }
+            };
+            if let Err(err) = reth_fs_util::remove_file(&path) {
+                warn!(target: "engine::caching", %err, ?path, "Failed to remove execution cache snapshot");
+            }
+
+            if snapshot.block_hash() != head.hash {
+                debug!(
+                    target: "engine::caching",
+                    ?head,
+                    snapshot_hash = %snapshot.block_hash(),
+                    "Execution cache snapshot does not match persisted head, discarding"
+                );
+                return
+            }
+
+            let cache = ExecutionCache::new_with_hot_set(cross_block_cache_size, hot_set);
+            cache.restore(&snapshot);
+            execution_cache.update_with_guard(|cached| {
+                if cached.is_some() {
+                    debug!(target: "engine::caching", "Execution cache already populated, discarding snapshot");
+                    return
+                }
+                *cached = Some(SavedCache::new(head.hash, cache));
+                info!(
+                    target: "engine::caching",
+                    ?head,
+                    entries = snapshot.len(),
+                    elapsed = ?start.elapsed(),
+                    "Restored execution cache snapshot"
+                );
+            });
+        });
+    }
 }
 
 /// Converts transactions sequentially and sends them to the prewarm and execute channels.
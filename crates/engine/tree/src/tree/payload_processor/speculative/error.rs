@@ -0,0 +1,18 @@
+//! Errors for the speculative execution path.
+
+use alloy_evm::block::BlockExecutionError;
+use reth_provider::ProviderError;
+
+/// Errors surfaced by `execute_block`.
+#[derive(Debug, thiserror::Error)]
+pub enum SpeculativeExecutionError {
+    /// Canonical EVM failure.
+    #[error("evm execution failed: {0}")]
+    Execution(#[from] BlockExecutionError),
+    /// Provider setup failed before EVM execution could start.
+    #[error("provider setup failed: {0}")]
+    Provider(#[from] ProviderError),
+    /// Speculative execution failed before it reached EVM execution.
+    #[error(transparent)]
+    Other(#[from] Box<dyn core::error::Error + Send + Sync + 'static>),
+}
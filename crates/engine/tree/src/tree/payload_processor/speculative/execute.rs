@@ -0,0 +1,441 @@
+//! Speculative executor.
+//!
+//! Worker states execute transactions speculatively against the parent state. Each worker gets
+//! one fresh cache-filling database from `make_db(true)`, never commits, and returns uncommitted
+//! transaction results together with their read sets.
+//!
+//! The canonical state owns block effects. It runs the normal pre/post block hooks, validates
+//! worker results in transaction order, commits the valid ones, re-executes the rest, and tracks
+//! block gas admission.
+
+use super::{
+    read_set::Validation,
+    worker::{self, SpeculativeOutput, SpeculativeWorkerError},
+    SpeculativeExecutionError,
+};
+use crate::tree::payload_processor::{
+    bal::execute::{AbortGuard, BlockGasTracker},
+    receipt_root_task::IndexedReceipt,
+};
+use alloy_consensus::Transaction;
+use alloy_evm::{
+    block::{BlockExecutor, TxResult},
+    Evm,
+};
+use alloy_primitives::Address;
+use crossbeam_channel::{Receiver, Sender};
+use reth_evm::{
+    execute::ExecutableTxFor, ConfigureEvm, Database, EvmEnvFor, ExecutionCtxFor, OnStateHook,
+};
+use reth_primitives_traits::ReceiptTy;
+use reth_provider::BlockExecutionOutput;
+use reth_tasks::Runtime;
+use revm::{
+    context::Block,
+    database::{states::bundle_state::BundleRetention, State},
+    DatabaseCommit,
+};
+use std::time::{Duration, Instant};
+
+/// Outcome counters of one speculatively executed block.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SpeculativeExecutionStats {
+    /// Transactions whose speculative result was committed as-is.
+    pub committed: usize,
+    /// Transactions whose speculative result was committed after rebasing the beneficiary fee
+    /// credit.
+    pub beneficiary_rebased: usize,
+    /// Transactions that conflicted with an earlier transaction, or failed speculatively, and
+    /// were re-executed on the canonical state.
+    pub reexecuted: usize,
+    /// Sum of the execution time of every committed result, i.e. an estimate of the time a
+    /// sequential execution of the block would have taken.
+    pub serial_duration: Duration,
+}
+
+impl SpeculativeExecutionStats {
+    /// Returns the share of transactions that had to be re-executed.
+    pub fn conflict_ratio(&self) -> f64 {
+        let total = self.committed + self.beneficiary_rebased + self.reexecuted;
+        if total == 0 {
+            return 0.0;
+        }
+        self.reexecuted as f64 / total as f64
+    }
+
+    /// Returns the estimated speedup over sequential execution, given the wall-clock duration of
+    /// the speculative execution.
+    pub fn speedup(&self, elapsed: Duration) -> f64 {
+        if elapsed.is_zero() {
+            return 1.0;
+        }
+        self.serial_duration.as_secs_f64() / elapsed.as_secs_f64()
+    }
+}
+
+/// Executes one block on the speculative path using the runtime's prewarming pool.
+///
+/// The state hook, if any, is installed on the canonical state and observes every committed
+/// transaction, like on the sequential path.
+#[expect(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn execute_block<'a, Evm, Tx, Err, DB, MakeDb>(
+    runtime: &Runtime,
+    evm_config: &'a Evm,
+    make_db: &'a MakeDb,
+    evm_env: EvmEnvFor<Evm>,
+    ctx: ExecutionCtxFor<'a, Evm>,
+    transaction_count: usize,
+    txs: Receiver<(usize, Result<Tx, Err>)>,
+    receipt_tx: Sender<IndexedReceipt<ReceiptTy<Evm::Primitives>>>,
+    state_hook: Option<Box<dyn OnStateHook>>,
+) -> Result<
+    (BlockExecutionOutput<ReceiptTy<Evm::Primitives>>, Vec<Address>, SpeculativeExecutionStats),
+    SpeculativeExecutionError,
+>
+where
+    Evm: ConfigureEvm + 'static,
+    Tx: ExecutableTxFor<Evm> + Clone + Send + 'a,
+    Err: core::error::Error + Send + Sync + 'static,
+    DB: Database + Send + 'a,
+    MakeDb: Fn(bool) -> Result<DB, SpeculativeExecutionError> + Sync + 'a,
+    ReceiptTy<Evm::Primitives>: Clone,
+{
+    let worker_pool = runtime.prewarming_pool();
+    let worker_count = worker_pool.current_num_threads().max(1).min(transaction_count);
+
+    worker_pool.in_place_scope(|scope| {
+        execute_block_inner(
+            scope,
+            evm_config,
+            make_db,
+            evm_env,
+            ctx,
+            transaction_count,
+            txs,
+            receipt_tx,
+            state_hook,
+            worker_count,
+        )
+    })
+}
+
+#[expect(clippy::too_many_arguments, clippy::type_complexity)]
+fn execute_block_inner<'scope, Evm, Tx, Err, DB, MakeDb>(
+    scope: &rayon::Scope<'scope>,
+    evm_config: &'scope Evm,
+    make_db: &'scope MakeDb,
+    evm_env: EvmEnvFor<Evm>,
+    ctx: ExecutionCtxFor<'scope, Evm>,
+    transaction_count: usize,
+    txs: Receiver<(usize, Result<Tx, Err>)>,
+    receipt_tx: Sender<IndexedReceipt<ReceiptTy<Evm::Primitives>>>,
+    state_hook: Option<Box<dyn OnStateHook>>,
+    worker_count: usize,
+) -> Result<
+    (BlockExecutionOutput<ReceiptTy<Evm::Primitives>>, Vec<Address>, SpeculativeExecutionStats),
+    SpeculativeExecutionError,
+>
+where
+    Evm: ConfigureEvm + 'scope,
+    Tx: ExecutableTxFor<Evm> + Clone + Send + 'scope,
+    Err: core::error::Error + Send + Sync + 'static,
+    DB: Database + Send + 'scope,
+    MakeDb: Fn(bool) -> Result<DB, SpeculativeExecutionError> + Sync + 'scope,
+    ReceiptTy<Evm::Primitives>: Clone,
+{
+    let beneficiary = evm_env.block_env.beneficiary();
+    let block_gas_limit = evm_env.block_env.gas_limit();
+    let enable_amsterdam_eip8037 = evm_env.cfg_env.enable_amsterdam_eip8037;
+    let tx_gas_limit_cap = evm_env.cfg_env.tx_gas_limit_cap;
+    let mut canonical_state =
+        State::builder().with_database(make_db(false)?).with_bundle_update().build();
+    canonical_state.set_state_hook(state_hook);
+
+    let (block_result, senders, stats) = {
+        let (result_tx, result_rx) = crossbeam_channel::unbounded();
+        let (abort_guard, abort_rx) = AbortGuard::new();
+
+        for _ in 0..worker_count {
+            worker::spawn_worker(
+                scope,
+                txs.clone(),
+                abort_rx.clone(),
+                result_tx.clone(),
+                evm_config,
+                make_db,
+                evm_env.clone(),
+                ctx.clone(),
+            );
+        }
+        drop(result_tx);
+
+        let mut gas_tracker =
+            BlockGasTracker::new(block_gas_limit, enable_amsterdam_eip8037, tx_gas_limit_cap);
+        let evm = evm_config.evm_with_env(&mut canonical_state, evm_env);
+        let mut canonical_executor = evm_config.create_executor_with_state(evm, ctx.clone());
+
+        canonical_executor.apply_pre_execution_changes()?;
+        let mut senders = Vec::with_capacity(transaction_count);
+        let mut stats = SpeculativeExecutionStats::default();
+        let mut last_sent_len = 0usize;
+        for output in ordered_outputs(&result_rx, transaction_count) {
+            let SpeculativeOutput { tx, speculative, .. } = output?;
+            let signer = *tx.signer();
+            gas_tracker.validate_tx_limit(tx.tx().gas_limit())?;
+
+            // Validate the speculative result against the state committed so far.
+            let mut committed = None;
+            if let Some(speculative) = speculative {
+                let rebase_beneficiary = (!speculative.beneficiary_accessed).then_some(beneficiary);
+                let validation = speculative
+                    .reads
+                    .validate(
+                        canonical_executor.evm_mut().db_mut(),
+                        &speculative.result.result().state,
+                        rebase_beneficiary,
+                    )
+                    .map_err(|e| SpeculativeExecutionError::Other(Box::new(e)))?;
+                match validation {
+                    Validation::Valid => {
+                        stats.committed += 1;
+                        committed = Some((speculative.result, None, speculative.elapsed));
+                    }
+                    Validation::Rebase(rebase) => {
+                        stats.beneficiary_rebased += 1;
+                        committed = Some((speculative.result, Some(rebase), speculative.elapsed));
+                    }
+                    Validation::Conflict => {}
+                }
+            }
+
+            let (result, rebase, elapsed) = match committed {
+                Some(committed) => committed,
+                None => {
+                    stats.reexecuted += 1;
+                    let start = Instant::now();
+                    let result = canonical_executor.execute_transaction_without_commit(tx)?;
+                    (result, None, start.elapsed())
+                }
+            };
+            stats.serial_duration += elapsed;
+
+            gas_tracker.record_result(result.result());
+            let _ = canonical_executor.commit_transaction(result);
+            if let Some(rebase) = rebase {
+                canonical_executor.evm_mut().db_mut().commit(rebase.into_state());
+            }
+            senders.push(signer);
+
+            let current_len = canonical_executor.receipts().len();
+            if current_len > last_sent_len {
+                last_sent_len = current_len;
+                if let Some(receipt) = canonical_executor.receipts().last() {
+                    let tx_index = current_len - 1;
+                    let _ = receipt_tx.send(IndexedReceipt::new(tx_index, receipt.clone()));
+                }
+            }
+        }
+        drop(abort_guard);
+
+        let block_result = canonical_executor.apply_post_execution_changes()?;
+        (block_result, senders, stats)
+    };
+
+    canonical_state.merge_transitions(BundleRetention::Reverts);
+    Ok((
+        BlockExecutionOutput { state: canonical_state.take_bundle(), result: block_result },
+        senders,
+        stats,
+    ))
+}
+
+type WorkerResultReceiver<Tx, R> =
+    Receiver<Result<SpeculativeOutput<Tx, R>, SpeculativeWorkerError>>;
+
+/// Returns a blocking iterator over worker outputs in transaction order.
+///
+/// Workers may finish transactions out of order, so outputs with a future index are buffered
+/// until every earlier output has been yielded. After the first error, the iterator is exhausted.
+fn ordered_outputs<Tx, R>(
+    result_rx: &WorkerResultReceiver<Tx, R>,
+    total: usize,
+) -> impl Iterator<Item = Result<SpeculativeOutput<Tx, R>, SpeculativeExecutionError>> + '_ {
+    let mut pending: Vec<Option<SpeculativeOutput<Tx, R>>> = (0..total).map(|_| None).collect();
+    let mut next = 0;
+    let mut failed = false;
+    std::iter::from_fn(move || {
+        if failed || next >= total {
+            return None;
+        }
+
+        loop {
+            if let Some(output) = pending[next].take() {
+                next += 1;
+                return Some(Ok(output));
+            }
+
+            match result_rx.recv() {
+                Ok(Ok(output)) => {
+                    let index = output.index;
+                    assert!(
+                        pending[index].replace(output).is_none(),
+                        "duplicate speculative output for transaction {index}"
+                    );
+                }
+                Ok(Err(err)) => {
+                    failed = true;
+                    return Some(Err(err.into()));
+                }
+                Err(_) => {
+                    failed = true;
+                    return Some(Err(SpeculativeExecutionError::Other(
+                        "speculative worker result channel closed while waiting for ordered outputs"
+                            .into(),
+                    )));
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_primitives::{TxKind, U256};
+    use reth_chainspec::MAINNET;
+    use reth_ethereum_primitives::{Block, BlockBody, Receipt, Transaction, TransactionSigned};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{
+        crypto::secp256k1::public_key_to_address, Block as _, Recovered, SealedBlock,
+    };
+    use reth_testing_utils::generators::{generate_key, rng, sign_tx_with_key_pair};
+    use revm::{
+        database::{CacheDB, EmptyDB},
+        state::AccountInfo,
+    };
+    use std::convert::Infallible;
+
+    const BENEFICIARY: Address = Address::repeat_byte(0xBE);
+
+    fn block() -> SealedBlock<Block> {
+        let header = Header {
+            number: 1,
+            gas_limit: 30_000_000,
+            beneficiary: BENEFICIARY,
+            ..Header::default()
+        };
+        Block { header, body: BlockBody::default() }.seal_slow()
+    }
+
+    fn transfer(nonce: u64, to: Address, value: u64) -> Transaction {
+        Transaction::Legacy(TxLegacy {
+            chain_id: Some(MAINNET.chain.id()),
+            nonce,
+            gas_price: 2,
+            gas_limit: 21_000,
+            to: TxKind::Call(to),
+            value: U256::from(value),
+            input: Default::default(),
+        })
+    }
+
+    /// Executes the block sequentially and returns the bundle state and receipts.
+    fn execute_sequential(
+        evm_config: &EthEvmConfig,
+        db: CacheDB<EmptyDB>,
+        block: &SealedBlock<Block>,
+        txs: &[Recovered<TransactionSigned>],
+    ) -> BlockExecutionOutput<Receipt> {
+        let mut state = State::builder().with_database(db).with_bundle_update().build();
+        let result = {
+            let mut executor = evm_config.executor_for_block(&mut state, block).unwrap();
+            executor.apply_pre_execution_changes().unwrap();
+            for tx in txs {
+                executor.execute_transaction(tx.clone()).unwrap();
+            }
+            executor.apply_post_execution_changes().unwrap()
+        };
+        state.merge_transitions(BundleRetention::Reverts);
+        BlockExecutionOutput { state: state.take_bundle(), result }
+    }
+
+    fn execute_speculative(
+        evm_config: &EthEvmConfig,
+        db: CacheDB<EmptyDB>,
+        block: &SealedBlock<Block>,
+        txs: Vec<Recovered<TransactionSigned>>,
+    ) -> (BlockExecutionOutput<Receipt>, SpeculativeExecutionStats) {
+        let transaction_count = txs.len();
+        let (tx_sender, tx_receiver) = crossbeam_channel::unbounded();
+        for (index, tx) in txs.into_iter().enumerate() {
+            tx_sender.send((index, Ok::<_, Infallible>(tx))).unwrap();
+        }
+        drop(tx_sender);
+
+        let (receipt_tx, _receipt_rx) = crossbeam_channel::unbounded();
+        let make_db = |_: bool| Ok(db.clone());
+        let (output, _, stats) = execute_block(
+            &Runtime::test(),
+            evm_config,
+            &make_db,
+            evm_config.evm_env(block.header()).unwrap(),
+            evm_config.context_for_block(block).unwrap(),
+            transaction_count,
+            tx_receiver,
+            receipt_tx,
+            None,
+        )
+        .unwrap();
+        (output, stats)
+    }
+
+    #[test]
+    fn matches_sequential_execution() {
+        let evm_config = EthEvmConfig::mainnet();
+        let recipient = Address::repeat_byte(0xCA);
+
+        let alice_key = generate_key(&mut rng());
+        let alice = public_key_to_address(alice_key.public_key());
+        let bob_key = generate_key(&mut rng());
+        let bob = public_key_to_address(bob_key.public_key());
+        let mut db = CacheDB::new(EmptyDB::default());
+        for sender in [alice, bob] {
+            db.insert_account_info(
+                sender,
+                AccountInfo { balance: U256::from(1_000_000_000u64), ..Default::default() },
+            );
+        }
+        db.insert_account_info(
+            BENEFICIARY,
+            AccountInfo { balance: U256::from(1), ..Default::default() },
+        );
+
+        // Alice sends two transactions, the second one depends on the first. Bob's transaction
+        // is independent, except for the fee credited to the beneficiary.
+        let txs = vec![
+            Recovered::new_unchecked(
+                sign_tx_with_key_pair(alice_key, transfer(0, recipient, 100)),
+                alice,
+            ),
+            Recovered::new_unchecked(
+                sign_tx_with_key_pair(alice_key, transfer(1, recipient, 200)),
+                alice,
+            ),
+            Recovered::new_unchecked(
+                sign_tx_with_key_pair(bob_key, transfer(0, Address::repeat_byte(0xCB), 300)),
+                bob,
+            ),
+        ];
+
+        let block = block();
+        let expected = execute_sequential(&evm_config, db.clone(), &block, &txs);
+        let (output, stats) = execute_speculative(&evm_config, db, &block, txs);
+
+        assert_eq!(output.state, expected.state);
+        assert_eq!(output.result.receipts, expected.result.receipts);
+        assert_eq!(output.result.gas_used, expected.result.gas_used);
+        assert_eq!(stats.committed + stats.beneficiary_rebased + stats.reexecuted, 3);
+        assert!(stats.reexecuted >= 1, "alice's second transaction must be re-executed");
+    }
+}
@@ -0,0 +1,96 @@
+//! Detection of explicit accesses to the block beneficiary.
+
+use alloy_primitives::{Address, B256, U256};
+use revm::{
+    bytecode::opcode,
+    context_interface::ContextTr,
+    inspector::Inspector,
+    interpreter::{
+        interpreter::EthInterpreter,
+        interpreter_types::{InputsTr, Jumps},
+        CallInputs, CallOutcome, Interpreter,
+    },
+};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Records whether a transaction accessed the block beneficiary.
+///
+/// The fee credit at the end of a transaction does not count as an access. The beneficiary is
+/// accessed when it sends or receives a call, when its code runs, when it self-destructs or
+/// receives a self-destruct balance, or when `BALANCE` and the `EXTCODE*` opcodes read it.
+///
+/// The flag is shared with the worker, so it can be read while the executor owns the inspector.
+#[derive(Debug, Clone)]
+pub(super) struct BeneficiaryInspector {
+    beneficiary: Address,
+    accessed: Arc<AtomicBool>,
+}
+
+impl BeneficiaryInspector {
+    /// Creates a new inspector for the given beneficiary.
+    pub(super) fn new(beneficiary: Address) -> Self {
+        Self { beneficiary, accessed: Arc::default() }
+    }
+
+    /// Returns whether the beneficiary was accessed since the last call and resets the flag.
+    pub(super) fn take_accessed(&self) -> bool {
+        self.accessed.swap(false, Ordering::Relaxed)
+    }
+
+    fn record(&self, address: Address) {
+        if address == self.beneficiary {
+            self.accessed.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<CTX> Inspector<CTX, EthInterpreter> for BeneficiaryInspector
+where
+    CTX: ContextTr,
+{
+    fn step(&mut self, interp: &mut Interpreter<EthInterpreter>, _context: &mut CTX) {
+        // Code running at the beneficiary can observe its balance through `SELFBALANCE`.
+        self.record(interp.input.target_address());
+
+        if matches!(
+            interp.bytecode.opcode(),
+            opcode::BALANCE | opcode::EXTCODESIZE | opcode::EXTCODECOPY | opcode::EXTCODEHASH
+        ) && let Some(word) = interp.stack.data().last()
+        {
+            self.record(Address::from_word(B256::from(word.to_be_bytes())));
+        }
+    }
+
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.record(inputs.caller);
+        self.record(inputs.target_address);
+        self.record(inputs.bytecode_address);
+        None
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        self.record(contract);
+        self.record(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_beneficiary_only() {
+        let beneficiary = Address::with_last_byte(1);
+        let inspector = BeneficiaryInspector::new(beneficiary);
+
+        inspector.record(Address::with_last_byte(2));
+        assert!(!inspector.take_accessed());
+
+        inspector.clone().record(beneficiary);
+        assert!(inspector.take_accessed());
+        assert!(!inspector.take_accessed(), "flag is reset once taken");
+    }
+}
@@ -0,0 +1,24 @@
+//! Speculative parallel block execution.
+//!
+//! The engine uses this path for blocks without a Block-Level Access List (BAL) when
+//! `--engine.speculative-parallel-execution` is set. It follows the Block-STM approach:
+//! transactions are executed optimistically in parallel and validated in order.
+//!
+//! Workers execute every transaction against the parent state and record the accounts and storage
+//! slots it read, together with the values it observed. The main thread validates each result in
+//! transaction order against the state committed so far. Results that only read unchanged state are
+//! committed as-is, all other transactions are re-executed on the canonical state.
+//!
+//! Every transaction credits its fee to the block beneficiary. Unless a transaction accesses the
+//! beneficiary explicitly, the credit is rebased onto the beneficiary balance left by the earlier
+//! transactions instead of being treated as a conflict.
+
+mod inspector;
+mod read_set;
+mod worker;
+
+pub mod error;
+pub mod execute;
+
+pub use error::SpeculativeExecutionError;
+pub use execute::{execute_block, SpeculativeExecutionStats};
@@ -0,0 +1,232 @@
+//! Read sets of speculatively executed transactions.
+
+use alloy_primitives::{Address, U256};
+use revm::{
+    state::{Account, AccountInfo, AccountStatus, EvmState},
+    Database,
+};
+
+/// The accounts and storage slots a speculatively executed transaction read, together with the
+/// values it observed in the parent state.
+///
+/// Every account and storage slot a transaction loads ends up in its result state, so the read
+/// set is a superset of the write set.
+#[derive(Debug, Default)]
+pub(super) struct ReadSet {
+    accounts: Vec<(Address, Option<AccountInfo>)>,
+    storage: Vec<(Address, U256, U256)>,
+}
+
+impl ReadSet {
+    /// Builds the read set of the given result state.
+    ///
+    /// `db` must be the database the transaction was executed against. It is only used to look
+    /// up the observed account infos, which are already cached from the execution.
+    pub(super) fn new<DB: Database>(state: &EvmState, db: &mut DB) -> Result<Self, DB::Error> {
+        let mut accounts = Vec::with_capacity(state.len());
+        let mut storage = Vec::new();
+        for (address, account) in state {
+            accounts.push((*address, db.basic(*address)?));
+            storage.extend(
+                account.storage.iter().map(|(slot, value)| (*address, *slot, value.original_value)),
+            );
+        }
+        Ok(Self { accounts, storage })
+    }
+
+    /// Validates the observed values against the current values of `db`.
+    ///
+    /// If `beneficiary` is set, a balance difference of that account is not a conflict. Instead,
+    /// the fee credited to it in `state` is rebased onto its current balance.
+    pub(super) fn validate<DB: Database>(
+        &self,
+        db: &mut DB,
+        state: &EvmState,
+        beneficiary: Option<Address>,
+    ) -> Result<Validation, DB::Error> {
+        let mut rebase = None;
+        for (address, observed) in &self.accounts {
+            let current = db.basic(*address)?;
+            if same_account(observed.as_ref(), current.as_ref()) {
+                continue;
+            }
+
+            if beneficiary == Some(*address) &&
+                let Some(rebased) = BeneficiaryRebase::new(
+                    *address,
+                    observed.as_ref(),
+                    current.as_ref(),
+                    state.get(address).map(|account| &account.info),
+                )
+            {
+                rebase = Some(rebased);
+                continue;
+            }
+
+            return Ok(Validation::Conflict);
+        }
+
+        for (address, slot, observed) in &self.storage {
+            if db.storage(*address, *slot)? != *observed {
+                return Ok(Validation::Conflict);
+            }
+        }
+
+        Ok(rebase.map_or(Validation::Valid, Validation::Rebase))
+    }
+}
+
+/// Outcome of validating a speculative result.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Validation {
+    /// The transaction only read unchanged state, its result can be committed.
+    Valid,
+    /// The result can be committed after rebasing the beneficiary fee credit.
+    Rebase(BeneficiaryRebase),
+    /// The transaction read state changed by an earlier transaction and must be re-executed.
+    Conflict,
+}
+
+/// The corrected beneficiary account of a speculative result.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct BeneficiaryRebase {
+    address: Address,
+    info: AccountInfo,
+}
+
+impl BeneficiaryRebase {
+    /// Rebases the balance credited from `observed` to `credited` onto `current`.
+    ///
+    /// Returns `None` if the beneficiary did not exist in either state, if its nonce or code
+    /// changed, or if its balance decreased.
+    fn new(
+        address: Address,
+        observed: Option<&AccountInfo>,
+        current: Option<&AccountInfo>,
+        credited: Option<&AccountInfo>,
+    ) -> Option<Self> {
+        let (observed, current, credited) = (observed?, current?, credited?);
+        if observed.nonce != current.nonce ||
+            observed.code_hash != current.code_hash ||
+            credited.nonce != observed.nonce ||
+            credited.code_hash != observed.code_hash
+        {
+            return None;
+        }
+
+        let credit = credited.balance.checked_sub(observed.balance)?;
+        let balance = current.balance.checked_add(credit)?;
+        Some(Self { address, info: AccountInfo { balance, ..credited.clone() } })
+    }
+
+    /// Returns the state change that overwrites the credited beneficiary balance.
+    pub(super) fn into_state(self) -> EvmState {
+        let mut account = Account::default();
+        account.info = self.info;
+        account.status = AccountStatus::Touched;
+        EvmState::from_iter([(self.address, account)])
+    }
+}
+
+/// Returns whether the two account infos are observably the same.
+fn same_account(a: Option<&AccountInfo>, b: Option<&AccountInfo>) -> bool {
+    a.map(|info| (info.balance, info.nonce, info.code_hash)) ==
+        b.map(|info| (info.balance, info.nonce, info.code_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm::{
+        database::{CacheDB, EmptyDB},
+        state::EvmStorageSlot,
+    };
+
+    const SENDER: Address = Address::with_last_byte(1);
+    const BENEFICIARY: Address = Address::with_last_byte(2);
+
+    fn info(balance: u64) -> AccountInfo {
+        AccountInfo { balance: U256::from(balance), ..Default::default() }
+    }
+
+    fn touched(info: AccountInfo) -> Account {
+        let mut account = Account::default();
+        account.info = info;
+        account.status = AccountStatus::Touched;
+        account
+    }
+
+    /// Parent state with a funded sender and beneficiary, and a speculative transfer result.
+    fn setup() -> (CacheDB<EmptyDB>, EvmState) {
+        let mut db = CacheDB::new(EmptyDB::default());
+        db.insert_account_info(SENDER, info(1_000));
+        db.insert_account_info(BENEFICIARY, info(50));
+        db.insert_account_storage(SENDER, U256::from(1), U256::from(7)).unwrap();
+
+        let mut sender = touched(AccountInfo { nonce: 1, ..info(900) });
+        sender.storage.insert(U256::from(1), EvmStorageSlot::new(U256::from(7), 0));
+        let state = EvmState::from_iter([(SENDER, sender), (BENEFICIARY, touched(info(60)))]);
+        (db, state)
+    }
+
+    #[test]
+    fn unchanged_state_is_valid() {
+        let (mut db, state) = setup();
+        let reads = ReadSet::new(&state, &mut db).unwrap();
+        assert_eq!(reads.validate(&mut db, &state, Some(BENEFICIARY)).unwrap(), Validation::Valid);
+    }
+
+    #[test]
+    fn changed_account_conflicts() {
+        let (mut db, state) = setup();
+        let reads = ReadSet::new(&state, &mut db).unwrap();
+
+        db.insert_account_info(SENDER, info(500));
+        assert_eq!(
+            reads.validate(&mut db, &state, Some(BENEFICIARY)).unwrap(),
+            Validation::Conflict
+        );
+    }
+
+    #[test]
+    fn changed_storage_conflicts() {
+        let (mut db, state) = setup();
+        let reads = ReadSet::new(&state, &mut db).unwrap();
+
+        db.insert_account_storage(SENDER, U256::from(1), U256::from(8)).unwrap();
+        assert_eq!(
+            reads.validate(&mut db, &state, Some(BENEFICIARY)).unwrap(),
+            Validation::Conflict
+        );
+    }
+
+    #[test]
+    fn beneficiary_credit_is_rebased() {
+        let (mut db, state) = setup();
+        let reads = ReadSet::new(&state, &mut db).unwrap();
+
+        // An earlier transaction credited 100 wei to the beneficiary.
+        db.insert_account_info(BENEFICIARY, info(150));
+        let Validation::Rebase(rebase) =
+            reads.validate(&mut db, &state, Some(BENEFICIARY)).unwrap()
+        else {
+            panic!("expected beneficiary rebase")
+        };
+        assert_eq!(rebase.info.balance, U256::from(160));
+
+        // Without rebasing, e.g. after an explicit access, the beneficiary conflicts.
+        assert_eq!(reads.validate(&mut db, &state, None).unwrap(), Validation::Conflict);
+    }
+
+    #[test]
+    fn beneficiary_nonce_change_conflicts() {
+        let (mut db, state) = setup();
+        let reads = ReadSet::new(&state, &mut db).unwrap();
+
+        db.insert_account_info(BENEFICIARY, AccountInfo { nonce: 1, ..info(150) });
+        assert_eq!(
+            reads.validate(&mut db, &state, Some(BENEFICIARY)).unwrap(),
+            Validation::Conflict
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use super::{inspector::BeneficiaryInspector, read_set::ReadSet, SpeculativeExecutionError};
+use alloy_evm::{
+    block::{BlockExecutor, BlockExecutorFactory, TxResult},
+    Evm,
+};
+use crossbeam_channel::{Receiver, Sender};
+use reth_evm::{execute::ExecutableTxFor, ConfigureEvm, Database, EvmEnvFor, ExecutionCtxFor};
+use revm::{context::Block, database::State};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, thiserror::Error)]
+pub(super) enum SpeculativeWorkerError {
+    /// Worker state or provider setup failed.
+    #[error("speculative worker setup failed: {0}")]
+    Setup(#[source] SpeculativeExecutionError),
+    /// Transaction recovery or conversion failed before EVM execution.
+    #[error("speculative worker transaction conversion failed: {0}")]
+    Transaction(Box<dyn core::error::Error + Send + Sync + 'static>),
+    /// Loading the observed values of the read set failed.
+    #[error("speculative worker read set failed: {0}")]
+    ReadSet(Box<dyn core::error::Error + Send + Sync + 'static>),
+}
+
+impl From<SpeculativeWorkerError> for SpeculativeExecutionError {
+    fn from(err: SpeculativeWorkerError) -> Self {
+        match err {
+            SpeculativeWorkerError::Setup(err) => err,
+            SpeculativeWorkerError::Transaction(err) | SpeculativeWorkerError::ReadSet(err) => {
+                Self::Other(err)
+            }
+        }
+    }
+}
+
+/// A transaction together with the outcome of its speculative execution.
+pub(super) struct SpeculativeOutput<Tx, R> {
+    pub(super) index: usize,
+    pub(super) tx: Tx,
+    /// `None` if speculative execution failed, e.g. because the transaction depends on an
+    /// earlier transaction of the same sender. The transaction is then re-executed on the
+    /// canonical state, which surfaces the error if it persists.
+    pub(super) speculative: Option<SpeculativeResult<R>>,
+}
+
+pub(super) struct SpeculativeResult<R> {
+    pub(super) result: R,
+    pub(super) reads: ReadSet,
+    /// Whether the transaction accessed the block beneficiary explicitly.
+    pub(super) beneficiary_accessed: bool,
+    pub(super) elapsed: Duration,
+}
+
+type WorkerExecutorResult<Cfg> =
+    <<Cfg as ConfigureEvm>::BlockExecutorFactory as BlockExecutorFactory>::TxExecutionResult;
+
+type WorkerResultSender<Cfg, Tx> =
+    Sender<Result<SpeculativeOutput<Tx, WorkerExecutorResult<Cfg>>, SpeculativeWorkerError>>;
+
+#[expect(clippy::too_many_arguments)]
+pub(super) fn spawn_worker<'scope, Evm, Tx, Err, DB, MakeDb>(
+    scope: &rayon::Scope<'scope>,
+    tx_rx: Receiver<(usize, Result<Tx, Err>)>,
+    abort_rx: Receiver<()>,
+    result_tx: WorkerResultSender<Evm, Tx>,
+    evm_config: &'scope Evm,
+    make_db: &'scope MakeDb,
+    evm_env: EvmEnvFor<Evm>,
+    ctx: ExecutionCtxFor<'scope, Evm>,
+) where
+    Evm: ConfigureEvm + 'scope,
+    Tx: ExecutableTxFor<Evm> + Clone + Send + 'scope,
+    Err: core::error::Error + Send + Sync + 'static,
+    DB: Database + Send + 'scope,
+    MakeDb: Fn(bool) -> Result<DB, SpeculativeExecutionError> + Sync + 'scope,
+{
+    scope.spawn(move |_| {
+        let worker_result = (|| -> Result<(), SpeculativeWorkerError> {
+            // Create a database with fill_on_miss=true ensuring misses are inserted for the
+            // other workers and the canonical executor.
+            let database = make_db(true).map_err(SpeculativeWorkerError::Setup)?;
+            // Results are never committed to the worker state, so every transaction executes
+            // against the parent state.
+            let mut worker_state = State::builder().with_database(database).build();
+            let inspector = BeneficiaryInspector::new(evm_env.block_env.beneficiary());
+            let evm = evm_config.evm_with_env_and_inspector(
+                &mut worker_state,
+                evm_env,
+                inspector.clone(),
+            );
+            let mut executor = evm_config.create_executor_with_state(evm, ctx.clone());
+
+            loop {
+                let (index, tx) = crossbeam_channel::select_biased! {
+                    recv(abort_rx) -> _ => break,
+                    recv(tx_rx) -> msg => match msg {
+                        Ok(ix_tx) => ix_tx,
+                        Err(_) => break,
+                    },
+                };
+                let tx = tx.map_err(|e| SpeculativeWorkerError::Transaction(Box::new(e)))?;
+
+                let start = Instant::now();
+                let speculative = executor.execute_transaction_without_commit(tx.clone()).ok();
+                let elapsed = start.elapsed();
+                let beneficiary_accessed = inspector.take_accessed();
+
+                let speculative = match speculative {
+                    Some(result) => {
+                        let reads =
+                            ReadSet::new(&result.result().state, executor.evm_mut().db_mut())
+                                .map_err(|e| SpeculativeWorkerError::ReadSet(Box::new(e)))?;
+                        Some(SpeculativeResult { result, reads, beneficiary_accessed, elapsed })
+                    }
+                    None => None,
+                };
+
+                if result_tx.send(Ok(SpeculativeOutput { index, tx, speculative })).is_err() {
+                    break;
+                }
+            }
+
+            Ok(())
+        })();
+
+        if let Err(err) = worker_result {
+            let _ = result_tx.send(Err(err));
+        }
+    });
+}
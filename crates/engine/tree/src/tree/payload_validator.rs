@@ -18,7 +18,8 @@
 //!    `StateRootTask` strategy also starts proof workers and the sparse trie task.
 //! 5. Execute the block. BAL payloads use the parallel BAL execute path only when state caching and
 //!    BAL parallel execution are enabled. Otherwise the regular executor still builds and validates
-//!    the BAL before post-execution consensus uses the decoded BAL hash.
+//!    the BAL before post-execution consensus uses the decoded BAL hash. Larger blocks without a
+//!    BAL use the speculative parallel execute path when it is enabled.
 //! 6. Stop prewarming, terminate execution caching, spawn `hash-post-state`, await
 //!    `payload-convert` and `receipt-root`, then run post-execution consensus validation.
 //! 7. Resolve the state root from the selected strategy and fall back to serial computation when a
@@ -102,7 +103,7 @@ use crate::tree::{
     error::{InsertBlockError, InsertBlockErrorKind, InsertPayloadError},
     instrumented_state::{InstrumentedStateProvider, StateProviderMetrics, StateProviderStats},
     multiproof::{StateRootComputeOutcome, StateRootHandle},
-    payload_processor::{PayloadProcessor, PayloadProcessorSpawnOptions},
+    payload_processor::{PayloadProcessor, PayloadProcessorSpawnOptions, SMALL_BLOCK_TX_THRESHOLD},
     precompile_cache::{CachedPrecompile, CachedPrecompileMetrics, PrecompileCacheMap},
     types::{InsertPayloadResult, ValidationOutput},
     CacheWaitDurations, CachedStateProvider, EngineApiMetrics, EngineApiTreeState, ExecutionEnv,
@@ -591,6 +592,7 @@ where
             OverlayStateProviderFactory::new(provider_factory.clone(), overlay_builder.clone());
 
         let parallel_bal_execution = ensure_ok!(self.bal_path_eligible(env.decoded_bal.as_deref()));
+        let speculative_execution = !parallel_bal_execution && self.speculative_path_eligible(&env);
 
         // Spawn the appropriate processor based on strategy
         let pending_sparse_trie_prune = if matches!(strategy, StateRootStrategy::StateRootTask) {
@@ -599,7 +601,8 @@ where
             None
         };
         let processor_options =
-            PayloadProcessorSpawnOptions::new(parallel_bal_execution, pending_sparse_trie_prune);
+            PayloadProcessorSpawnOptions::new(parallel_bal_execution, pending_sparse_trie_prune)
+                .with_speculative_execution(speculative_execution);
         let mut handle = ensure_ok!(self.spawn_payload_processor(
             env.clone(),
             txs,
@@ -680,6 +683,8 @@ where
         let execute_block_start = Instant::now();
         let execution_result = if parallel_bal_execution {
             self.execute_block_bal(env, &input, &handle, &make_state_provider)
+        } else if speculative_execution {
+            self.execute_block_speculative(env, &input, &handle, &make_state_provider)
         } else {
             let state_provider = make_state_provider(false);
             match state_provider {
@@ -1282,6 +1287,92 @@ where
         Ok((output, senders, result_rx, Some(built_bal)))
     }
 
+    /// Returns true when the speculative execute path should be used for a block without a BAL.
+    ///
+    /// Below [`SMALL_BLOCK_TX_THRESHOLD`] transactions, worker scheduling overhead exceeds the
+    /// gain of executing in parallel.
+    fn speculative_path_eligible(&self, env: &ExecutionEnv<Evm>) -> bool {
+        self.config.speculative_parallel_execution() &&
+            env.decoded_bal.is_none() &&
+            env.transaction_count >= SMALL_BLOCK_TX_THRESHOLD
+    }
+
+    /// Executes the block on the speculative path. Mirrors the return shape of
+    /// [`Self::execute_block`] so the dispatch site stays uniform.
+    ///
+    /// Inside, this:
+    /// 1. Creates a shared parent-state cache handle for provider-backed workers.
+    /// 2. Spawns the receipt-root task.
+    /// 3. Calls [`crate::tree::payload_processor::speculative::execute_block`], which streams
+    ///    committed state into the state hook.
+    /// 4. Records the conflict ratio and estimated speedup.
+    #[instrument(level = "debug", target = "engine::tree::payload_validator", skip_all)]
+    #[expect(clippy::type_complexity)]
+    fn execute_block_speculative<Tx, Err, MakeStateProvider, T>(
+        &self,
+        env: ExecutionEnv<Evm>,
+        input: &BlockOrPayload<T>,
+        handle: &PayloadHandle<Tx, Err, N::Receipt>,
+        make_state_provider: &MakeStateProvider,
+    ) -> Result<
+        (
+            BlockExecutionOutput<N::Receipt>,
+            Vec<Address>,
+            ReceiptRootReceiver,
+            Option<BlockAccessList>,
+        ),
+        InsertBlockErrorKind,
+    >
+    where
+        Tx: ExecutableTxFor<Evm> + Clone + Send,
+        Err: core::error::Error + Send + Sync + 'static,
+        MakeStateProvider: Fn(bool) -> ProviderResult<StateProviderBox> + Sync,
+        Evm: ConfigureEngineEvm<T::ExecutionData, Primitives = N>,
+        T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = N>>,
+        V: PayloadValidator<T, Block = N::Block>,
+    {
+        debug!(target: "engine::tree::payload_validator", "Executing block via speculative path");
+
+        let (receipt_tx, result_rx) = self.spawn_receipt_root_task(env.transaction_count);
+        let make_db = |fill_on_miss| {
+            let provider = make_state_provider(fill_on_miss).map_err(
+                crate::tree::payload_processor::speculative::SpeculativeExecutionError::Provider,
+            )?;
+            Ok(StateProviderDatabase::new(provider))
+        };
+        let state_hook =
+            handle.state_hook().map(|hook| Box::new(hook) as Box<dyn OnStateHook + 'static>);
+        let execution_start = Instant::now();
+        let ctx =
+            self.execution_ctx_for(input).map_err(|e| InsertBlockErrorKind::Other(Box::new(e)))?;
+        let (output, senders, stats) = crate::tree::payload_processor::speculative::execute_block(
+            &self.runtime,
+            &self.evm_config,
+            &make_db,
+            env.evm_env,
+            ctx,
+            env.transaction_count,
+            handle.clone_transaction_receiver(),
+            receipt_tx,
+            state_hook,
+        )?;
+        let execution_duration = execution_start.elapsed();
+
+        self.metrics.record_block_execution(&output, execution_duration);
+        self.metrics.record_block_execution_gas_bucket(output.result.gas_used, execution_duration);
+        self.metrics.record_speculative_execution(&stats, execution_duration);
+        debug!(
+            target: "engine::tree::payload_validator",
+            elapsed = ?execution_duration,
+            committed = stats.committed,
+            beneficiary_rebased = stats.beneficiary_rebased,
+            reexecuted = stats.reexecuted,
+            "Executed block via speculative path",
+        );
+
+        Ok((output, senders, result_rx, None))
+    }
+
     fn spawn_receipt_root_task(
         &self,
         receipts_len: usize,
@@ -1746,14 +1837,17 @@ where
         options: PayloadProcessorSpawnOptions,
     ) -> Result<
         PayloadHandle<
-            impl ExecutableTxFor<Evm> + use<N, P, Evm, V, T>,
+            impl ExecutableTxFor<Evm> + Clone + use<N, P, Evm, V, T>,
             impl core::error::Error + Send + Sync + 'static + use<N, P, Evm, V, T>,
             N::Receipt,
         >,
         InsertBlockErrorKind,
     > {
-        let PayloadProcessorSpawnOptions { parallel_bal_execution, pending_sparse_trie_prune } =
-            options;
+        let PayloadProcessorSpawnOptions {
+            parallel_bal_execution,
+            pending_sparse_trie_prune,
+            speculative_execution,
+        } = options;
         match strategy {
             StateRootStrategy::StateRootTask => {
                 let spawn_start = Instant::now();
@@ -1768,7 +1862,8 @@ where
                     PayloadProcessorSpawnOptions::new(
                         parallel_bal_execution,
                         pending_sparse_trie_prune,
-                    ),
+                    )
+                    .with_speculative_execution(speculative_execution),
                 );
 
                 // record prewarming initialization duration
@@ -1789,6 +1884,7 @@ where
                     txs,
                     provider_builder,
                     parallel_bal_execution,
+                    speculative_execution,
                 );
 
                 // Record prewarming initialization duration
@@ -2155,6 +2251,15 @@ pub trait EngineValidator<
     /// Returns [`SavedCache`] for the given block hash.
     fn cache_for(&self, _block_hash: B256) -> Option<SavedCache>;
 
+    /// Hook called once when the engine tree starts, with the persisted head.
+    ///
+    /// Implementations may restore caches persisted by [`Self::on_shutdown`] here. This must not
+    /// block the engine.
+    fn on_startup(&self, _persisted_head: NumHash) {}
+
+    /// Hook called on shutdown, after all canonical blocks have been persisted.
+    fn on_shutdown(&self, _persisted_head: NumHash) {}
+
     /// Spawns a sparse trie pipeline and returns a handle for the payload builder.
     fn sparse_trie_handle_for(
         &self,
@@ -2240,6 +2345,14 @@ where
         Some(self.payload_processor.cache_for(block_hash))
     }
 
+    fn on_startup(&self, persisted_head: NumHash) {
+        self.payload_processor.restore_execution_cache_snapshot(persisted_head);
+    }
+
+    fn on_shutdown(&self, persisted_head: NumHash) {
+        self.payload_processor.save_execution_cache_snapshot(persisted_head);
+    }
+
     fn sparse_trie_handle_for(
         &self,
         parent_hash: B256,
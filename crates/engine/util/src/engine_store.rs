@@ -74,18 +74,32 @@ impl EngineMessageStore {
             }
             BeaconEngineMessage::NewPayload { payload, .. } |
             BeaconEngineMessage::RethNewPayload { payload, .. } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
-                fs::write(
-                    self.path.join(filename),
-                    serde_json::to_vec(&StoredEngineApiMessage::<T>::NewPayload {
-                        payload: payload.clone(),
-                    })?,
-                )?;
+                self.save_new_payload::<T>(payload, timestamp)?;
+            }
+            #[cfg(feature = "focil")]
+            BeaconEngineMessage::NewPayloadWithInclusionList { payload, .. } => {
+                self.save_new_payload::<T>(payload, timestamp)?;
             }
         };
         Ok(())
     }
 
+    /// Stores the payload of a new payload message received at the given timestamp.
+    fn save_new_payload<T: PayloadTypes>(
+        &self,
+        payload: &T::ExecutionData,
+        timestamp: u128,
+    ) -> eyre::Result<()> {
+        let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
+        fs::write(
+            self.path.join(filename),
+            serde_json::to_vec(&StoredEngineApiMessage::<T>::NewPayload {
+                payload: payload.clone(),
+            })?,
+        )?;
+        Ok(())
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
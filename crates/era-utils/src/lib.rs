@@ -6,8 +6,12 @@ mod history;
 
 mod export;
 
+mod receipts;
+
 pub use export::{export, EraBlockWriter, ExportBlock, ExportConfig};
 
+pub use receipts::EraReceiptReader;
+
 pub use history::{
     build_index, calculate_td_by_number, decode, import, open, process, process_iter,
     save_stage_checkpoints, Era, Era1, EraBlockReader, Ere,
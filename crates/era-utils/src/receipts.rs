@@ -0,0 +1,124 @@
+//! Reads execution receipts out of ERA files, e.g. to restore previously pruned receipts.
+
+use crate::{open, Era1, Ere};
+use alloy_consensus::{ReceiptEnvelope, ReceiptWithBloom};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use eyre::eyre;
+use reth_db_api::table::Value;
+use reth_era::{
+    common::file_ops::StreamReader,
+    era1::file::Era1Reader,
+    ere::{file::EreReader, types::execution::SlimReceipt},
+};
+use reth_era_downloader::EraMeta;
+use reth_primitives_traits::{FullBlockHeader, Receipt};
+
+/// Reads execution `(header, receipts)` pairs out of an ERA file.
+///
+/// Per-format seam for receipts. Consensus `.era` files carry no receipts and have no
+/// implementation.
+pub trait EraReceiptReader<BH, R> {
+    /// Opens the ERA file at `meta` and iterates the receipts of its execution blocks.
+    fn receipts<M: EraMeta + ?Sized>(
+        meta: &M,
+    ) -> eyre::Result<impl Iterator<Item = eyre::Result<(BH, Vec<R>)>>>;
+}
+
+impl<BH, R> EraReceiptReader<BH, R> for Era1
+where
+    BH: FullBlockHeader + Value,
+    R: Receipt,
+{
+    fn receipts<M: EraMeta + ?Sized>(
+        meta: &M,
+    ) -> eyre::Result<impl Iterator<Item = eyre::Result<(BH, Vec<R>)>>> {
+        let reader: Era1Reader<std::fs::File> = open(meta)?;
+        Ok(reader.iter().map(|block| {
+            let block = block?;
+            let header: BH = block.header.decode()?;
+            let receipts: Vec<ReceiptWithBloom<R>> = block.receipts.decode()?;
+            Ok((header, receipts.into_iter().map(|receipt| receipt.receipt).collect()))
+        }))
+    }
+}
+
+impl<BH, R> EraReceiptReader<BH, R> for Ere
+where
+    BH: FullBlockHeader + Value,
+    R: Receipt,
+{
+    fn receipts<M: EraMeta + ?Sized>(
+        meta: &M,
+    ) -> eyre::Result<impl Iterator<Item = eyre::Result<(BH, Vec<R>)>>> {
+        let reader: EreReader<std::fs::File> = open(meta)?;
+        let path = meta.path().to_path_buf();
+        let mut buf = Vec::new();
+        Ok(reader.iter().map(move |block| {
+            let block = block?;
+            let header: BH = block.header.decode()?;
+            // `noreceipts` files omit the receipts of every block.
+            let receipts = block
+                .receipts
+                .ok_or_else(|| eyre!("ERE file {} carries no receipts", path.display()))?
+                .decode_receipts()?
+                .into_iter()
+                .map(|receipt| decode_slim_receipt(receipt, &mut buf))
+                .collect::<eyre::Result<_>>()?;
+            Ok((header, receipts))
+        }))
+    }
+}
+
+/// Converts a slim `ERE` receipt into the node's receipt type.
+///
+/// The slim form omits the bloom filter, so it is recomputed from the logs and the receipt is
+/// decoded from its canonical EIP-2718 encoding, which every receipt type shares.
+fn decode_slim_receipt<R: Decodable2718>(
+    receipt: SlimReceipt,
+    buf: &mut Vec<u8>,
+) -> eyre::Result<R> {
+    let SlimReceipt { tx_type, status, cumulative_gas_used, logs } = receipt;
+    let envelope = ReceiptEnvelope::from_typed(
+        tx_type,
+        alloy_consensus::Receipt { status, cumulative_gas_used, logs }.with_bloom(),
+    );
+
+    buf.clear();
+    envelope.encode_2718(buf);
+    Ok(R::decode_2718(&mut buf.as_slice())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Eip658Value, TxType};
+    use alloy_primitives::{Address, Bytes, Log, B256};
+    use reth_ethereum_primitives::Receipt as EthReceipt;
+
+    #[test]
+    fn slim_receipt_decodes_into_node_receipt() {
+        let log = Log::new_unchecked(
+            Address::repeat_byte(1),
+            vec![B256::repeat_byte(2)],
+            Bytes::from_static(&[3]),
+        );
+        let mut buf = Vec::new();
+
+        for (tx_type, success) in [(TxType::Legacy, true), (TxType::Eip1559, false)] {
+            let slim = SlimReceipt {
+                tx_type,
+                status: Eip658Value::Eip658(success),
+                cumulative_gas_used: 21_000,
+                logs: vec![log.clone()],
+            };
+            let expected = EthReceipt {
+                tx_type,
+                success,
+                cumulative_gas_used: 21_000,
+                logs: vec![log.clone()],
+            };
+
+            assert_eq!(decode_slim_receipt::<EthReceipt>(slim, &mut buf).unwrap(), expected);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     interface::{Commands, NoSubCmd},
+    rpc_node::RpcNodeCommand,
     Cli,
 };
 use clap::Subcommand;
@@ -15,7 +16,6 @@ use reth_db::DatabaseEnv;
 use reth_node_api::NodePrimitives;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_ethereum::{consensus::EthBeaconConsensus, EthereumNode};
-use reth_node_metrics::recorder::install_prometheus_recorder;
 use reth_rpc_server_types::RpcModuleValidator;
 use reth_tasks::RayonConfig;
 use reth_tracing::{Layers, TracingGuards};
@@ -81,9 +81,11 @@ where
             (evm_config, Arc::new(EthBeaconConsensus::new(spec)))
         };
 
-        self.run_with_components::<EthereumNode>(components, async move |builder, ext| {
-            launcher.entrypoint(builder, ext).await
-        })
+        self.run_with_components_and_rpc_node::<EthereumNode>(
+            components,
+            async move |builder, ext| launcher.entrypoint(builder, ext).await,
+            Some(|command, runner| runner.run_command_until_exit(|ctx| command.execute(ctx))),
+        )
     }
 
     /// Execute the configured cli command with the provided [`CliComponentsBuilder`].
@@ -92,12 +94,30 @@ where
     /// [`NodeCommand`](reth_cli_commands::node::NodeCommand) and allows providing custom
     /// components.
     pub fn run_with_components<N>(
+        self,
+        components: impl CliComponentsBuilder<N>,
+        launcher: impl AsyncFnOnce(
+            WithLaunchContext<NodeBuilder<DatabaseEnv, C::ChainSpec>>,
+            Ext,
+        ) -> Result<()>,
+    ) -> Result<()>
+    where
+        N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: HeaderMut>, ChainSpec: Hardforks>,
+        C: ChainSpecParser<ChainSpec = N::ChainSpec>,
+    {
+        self.run_with_components_and_rpc_node(components, launcher, None)
+    }
+
+    /// Same as [`Self::run_with_components`], but also supports the `rpc-node` command if a runner
+    /// for it is provided.
+    fn run_with_components_and_rpc_node<N>(
         mut self,
         components: impl CliComponentsBuilder<N>,
         launcher: impl AsyncFnOnce(
             WithLaunchContext<NodeBuilder<DatabaseEnv, C::ChainSpec>>,
             Ext,
         ) -> Result<()>,
+        rpc_node: Option<RpcNodeRunner<C>>,
     ) -> Result<()>
     where
         N: CliNodeTypes<Primitives: NodePrimitives<BlockHeader: HeaderMut>, ChainSpec: Hardforks>,
@@ -133,15 +153,22 @@ where
             self.cli.logs.apply_node_defaults();
         }
 
+        // The `t8n` command can print its outputs to stdout, which must not contain any logs
+        if matches!(self.cli.command, Commands::T8n(_)) {
+            self.cli.logs.log_stdout_filter = "off".to_string();
+        }
+
         self.init_tracing(&runner)?;
 
         // Deprioritize background threads spawned by tracing/OTel libraries.
         reth_tasks::utils::deprioritize_background_threads();
 
-        // Install the prometheus recorder to be sure to record all metrics
-        install_prometheus_recorder();
+        // Install the metrics recorder to be sure to record all metrics
+        self.cli.init_metrics_recorder(&runner)?;
 
-        run_commands_with::<C, Ext, Rpc, N, SubCmd>(self.cli, runner, components, launcher)
+        run_commands_with::<C, Ext, Rpc, N, SubCmd>(
+            self.cli, runner, components, launcher, rpc_node,
+        )
     }
 
     /// Initializes tracing with the configured options.
@@ -156,6 +183,12 @@ where
     }
 }
 
+/// Runs the `rpc-node` command.
+///
+/// The command builds the Ethereum RPC stack directly, so it is only available when running with
+/// the default Ethereum node types.
+pub(crate) type RpcNodeRunner<C> = fn(Box<RpcNodeCommand<C>>, CliRunner) -> Result<()>;
+
 /// Run CLI commands with the provided runner, components and launcher.
 /// This is the shared implementation used by both `CliApp` and Cli methods.
 pub(crate) fn run_commands_with<C, Ext, Rpc, N, SubCmd>(
@@ -166,6 +199,7 @@ pub(crate) fn run_commands_with<C, Ext, Rpc, N, SubCmd>(
         WithLaunchContext<NodeBuilder<DatabaseEnv, C::ChainSpec>>,
         Ext,
     ) -> Result<()>,
+    rpc_node: Option<RpcNodeRunner<C>>,
 ) -> Result<()>
 where
     C: ChainSpecParser<ChainSpec = N::ChainSpec>,
@@ -208,12 +242,21 @@ where
         }
         Commands::P2P(command) => runner.run_until_ctrl_c(command.execute::<N>()),
         Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
-        Commands::Prune(command) => runner.run_command_until_exit(|ctx| command.execute::<N>(ctx)),
+        Commands::Prune(command) => {
+            runner.run_command_until_exit(|ctx| command.execute::<N, _>(ctx, components))
+        }
         #[cfg(feature = "dev")]
         Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
         Commands::ReExecute(command) => {
             runner.run_until_ctrl_c(command.execute::<N>(components, rt))
         }
+        Commands::RpcNode(command) => {
+            let rpc_node = rpc_node.ok_or_else(|| {
+                eyre!("the `rpc-node` command is only supported for the default Ethereum node")
+            })?;
+            rpc_node(command, runner)
+        }
+        Commands::T8n(command) => command.execute(),
         Commands::Ext(command) => command.execute(runner),
     }
 }
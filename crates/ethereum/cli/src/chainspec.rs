@@ -1,4 +1,4 @@
-use reth_chainspec::{ChainSpec, DEV, HOLESKY, HOODI, MAINNET, SEPOLIA};
+use reth_chainspec::{ChainSpec, ChainSpecOverrides, DEV, HOLESKY, HOODI, MAINNET, SEPOLIA};
 use reth_cli::chainspec::{parse_genesis, ChainSpecParser};
 use std::sync::Arc;
 
@@ -33,6 +33,16 @@ impl ChainSpecParser for EthereumChainSpecParser {
     fn parse(s: &str) -> eyre::Result<Arc<ChainSpec>> {
         chain_value_parser(s)
     }
+
+    fn apply_overrides(
+        spec: Arc<ChainSpec>,
+        overrides: &ChainSpecOverrides,
+    ) -> eyre::Result<Arc<ChainSpec>> {
+        if overrides.is_empty() {
+            return Ok(spec)
+        }
+        Ok(Arc::new(Arc::unwrap_or_clone(spec).with_overrides(overrides)?))
+    }
 }
 
 #[cfg(test)]
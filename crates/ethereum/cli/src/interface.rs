@@ -18,9 +18,10 @@ use reth_db::DatabaseEnv;
 use reth_node_api::NodePrimitives;
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
 use reth_node_core::{
-    args::{LogArgs, OtlpInitStatus, OtlpLogsStatus, TraceArgs},
+    args::{LogArgs, OtlpInitStatus, OtlpLogsStatus, OtlpMetricsStatus, TraceArgs},
     version::version_metadata,
 };
+use reth_node_metrics::recorder::install_prometheus_recorder;
 use reth_rpc_server_types::{DefaultRpcModuleValidator, RethRpcModule, RpcModuleValidator};
 use reth_tracing::{Layers, TracingGuards};
 use std::{ffi::OsString, fmt, future::Future, marker::PhantomData, sync::Arc};
@@ -277,6 +278,32 @@ impl<
 
         Ok(guards)
     }
+
+    /// Installs the global metrics recorder.
+    ///
+    /// Metrics are always recorded for Prometheus. If an OTLP metrics endpoint is specified, they
+    /// are also exported to the configured collector.
+    pub fn init_metrics_recorder(&mut self, _runner: &CliRunner) -> eyre::Result<()> {
+        match self.traces.otlp_metrics_config()? {
+            #[cfg(feature = "otlp-metrics")]
+            OtlpMetricsStatus::Enabled(config) => {
+                // The gRPC exporter must be built from within a Tokio runtime
+                _runner.block_on(async {
+                    reth_node_metrics::recorder::try_install_prometheus_recorder_with_otlp(&config)
+                })?;
+                info!(target: "reth::cli", "Started OTLP {:?} metrics export to {}", self.traces.protocol, config.endpoint());
+            }
+            OtlpMetricsStatus::NoFeature => {
+                warn!(target: "reth::cli", "Provided OTLP metrics arguments do not have effect, compile with the `otlp-metrics` feature");
+                install_prometheus_recorder();
+            }
+            OtlpMetricsStatus::Disabled => {
+                install_prometheus_recorder();
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Commands to be executed
@@ -334,6 +361,12 @@ pub enum Commands<
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
+    /// Serve the RPC API from the datadir of a running node without syncing.
+    #[command(name = "rpc-node")]
+    RpcNode(Box<crate::rpc_node::RpcNodeCommand<C>>),
+    /// Run a state transition with the interface of the `evm t8n` tool.
+    #[command(name = "t8n")]
+    T8n(Box<crate::t8n::T8nCommand>),
     /// Extension subcommands provided by consumers.
     #[command(flatten)]
     Ext(SubCmd),
@@ -375,6 +408,8 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, SubCmd: Subcommand + fmt:
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::RpcNode(cmd) => cmd.chain_spec(),
+            Self::T8n(_) => None,
             Self::Ext(_) => None,
         }
     }
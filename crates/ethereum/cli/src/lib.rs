@@ -13,6 +13,10 @@ pub mod app;
 /// Chain specification parser.
 pub mod chainspec;
 pub mod interface;
+/// Read-only RPC node command.
+pub mod rpc_node;
+/// State transition command.
+pub mod t8n;
 
 pub use app::{CliApp, ExtendedCommand};
 pub use interface::{Cli, Commands, NoSubCmd};
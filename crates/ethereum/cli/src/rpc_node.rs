@@ -0,0 +1,199 @@
+//! Command that serves the RPC API from the datadir of a running node.
+
+use clap::Parser;
+use humantime::parse_duration;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_network_api::noop::NoopNetwork;
+use reth_node_core::args::RpcServerArgs;
+use reth_node_ethereum::{consensus::EthBeaconConsensus, EthEvmConfig, EthereumNode};
+use reth_node_replica::{ReplicaFollower, DEFAULT_REPLICA_POLL_INTERVAL};
+use reth_provider::providers::BlockchainProvider;
+use reth_rpc::EthApiBuilder;
+use reth_rpc_builder::{config::RethRpcServerConfig, RpcModuleBuilder};
+use reth_rpc_server_types::{constants::DEFAULT_IPC_ENDPOINT, RethRpcModule, RpcModuleSelection};
+use reth_transaction_pool::noop::NoopTransactionPool;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tracing::info;
+
+/// Namespaces served by default if none are configured explicitly.
+const DEFAULT_MODULES: [RethRpcModule; 5] = [
+    RethRpcModule::Eth,
+    RethRpcModule::Net,
+    RethRpcModule::Web3,
+    RethRpcModule::Debug,
+    RethRpcModule::Trace,
+];
+
+/// Namespaces that can be served without a network, transaction pool or consensus engine.
+const READ_ONLY_MODULES: [RethRpcModule; 8] = [
+    RethRpcModule::Eth,
+    RethRpcModule::Net,
+    RethRpcModule::Web3,
+    RethRpcModule::Debug,
+    RethRpcModule::Trace,
+    RethRpcModule::Rpc,
+    RethRpcModule::Reth,
+    RethRpcModule::Ots,
+];
+
+/// Serves the RPC API from the datadir of another running node, without syncing.
+///
+/// The database, static files and `RocksDB` are opened read-only and followed as the primary
+/// writes to them. If the primary runs with `--replica.notifications`, blocks that are not
+/// persisted yet are received over its notification socket, so the replica serves the same head as
+/// the primary.
+#[derive(Debug, Parser)]
+pub struct RpcNodeCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// All rpc related arguments
+    #[command(flatten)]
+    rpc: RpcServerArgs,
+
+    /// Path of the primary's replica notification socket.
+    ///
+    /// Defaults to `<DATADIR>/replica.ipc`.
+    #[arg(long = "replica.socket", value_name = "PATH", help_heading = "Replica")]
+    socket: Option<PathBuf>,
+
+    /// Don't connect to the primary's notification socket and only serve persisted blocks.
+    #[arg(long = "replica.disable-notifications", help_heading = "Replica")]
+    disable_notifications: bool,
+
+    /// Interval at which the shared database is polled for newly persisted blocks.
+    #[arg(
+        long = "replica.poll-interval",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = humantime::format_duration(DEFAULT_REPLICA_POLL_INTERVAL).to_string(),
+        help_heading = "Replica"
+    )]
+    poll_interval: Duration,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> RpcNodeCommand<C> {
+    /// Execute the `rpc-node` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let Self { env, mut rpc, socket, disable_notifications, poll_interval } = self;
+
+        // Replicas usually run next to the primary, so don't compete for its default IPC endpoint.
+        if rpc.ipcpath == DEFAULT_IPC_ENDPOINT {
+            rpc.ipcdisable = true;
+        }
+        if !rpc.http && !rpc.ws && rpc.ipcdisable {
+            rpc.http = true;
+        }
+        rpc.http_api.get_or_insert_with(|| DEFAULT_MODULES.into());
+        rpc.ws_api.get_or_insert_with(|| DEFAULT_MODULES.into());
+
+        let module_config = rpc.transport_rpc_module_config();
+        for selection in
+            [module_config.http(), module_config.ws(), module_config.ipc()].into_iter().flatten()
+        {
+            ensure_read_only(selection)?;
+        }
+
+        let chain_spec = env.chain_spec()?;
+        let Environment { provider_factory, data_dir, .. } =
+            env.init::<EthereumNode>(AccessRights::RoInconsistent, ctx.task_executor.clone())?;
+        let factory = provider_factory.with_read_only_sync(true);
+        let provider = BlockchainProvider::new(factory.clone())?;
+
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
+        let eth_config = rpc.eth_config();
+        let eth_api = EthApiBuilder::new(
+            provider.clone(),
+            NoopTransactionPool::default(),
+            NoopNetwork::default(),
+            evm_config.clone(),
+        )
+        .task_spawner(ctx.task_executor.clone())
+        .eth_state_cache_config(eth_config.cache)
+        .gas_cap(eth_config.rpc_gas_cap.into())
+        .max_simulate_blocks(eth_config.rpc_max_simulate_blocks)
+        .compute_state_root_for_eth_simulate(eth_config.compute_state_root_for_eth_simulate)
+        .eth_proof_window(eth_config.eth_proof_window)
+        .fee_history_cache_config(eth_config.fee_history_cache)
+        .proof_permits(eth_config.proof_permits)
+        .gas_oracle_config(eth_config.gas_oracle)
+        .max_batch_size(eth_config.max_batch_size)
+        .max_blocking_io_requests(eth_config.max_blocking_io_requests)
+        .raw_tx_forwarder(eth_config.raw_tx_forwarder)
+        .evm_memory_limit(eth_config.rpc_evm_memory_limit)
+        .force_blob_sidecar_upcasting(eth_config.force_blob_sidecar_upcasting)
+        .build();
+
+        let modules = RpcModuleBuilder::default()
+            .with_provider(provider.clone())
+            .with_noop_pool()
+            .with_noop_network()
+            .with_executor(ctx.task_executor.clone())
+            .with_evm_config(evm_config)
+            .with_consensus(EthBeaconConsensus::new(chain_spec))
+            .build(module_config, eth_api, Default::default());
+
+        let _handle = rpc.rpc_server_config().start(&modules).await?;
+        info!(target: "reth::cli", datadir = %data_dir.data_dir().display(), "Read-only RPC node started");
+
+        let mut follower = ReplicaFollower::new(factory, provider.canonical_in_memory_state())
+            .with_poll_interval(poll_interval);
+        if !disable_notifications {
+            follower = follower.with_socket(socket.unwrap_or_else(|| data_dir.replica_socket()));
+        }
+        follower.run().await;
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> RpcNodeCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// Returns an error if the selection contains namespaces that need a live node.
+fn ensure_read_only(selection: &RpcModuleSelection) -> eyre::Result<()> {
+    if let Some(module) =
+        selection.iter_selection().find(|module| !READ_ONLY_MODULES.contains(module))
+    {
+        eyre::bail!("the `{module}` namespace is not available on a read-only rpc node")
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_rpc_node_command() {
+        let cmd = RpcNodeCommand::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--datadir",
+            "/tmp/reth",
+            "--http",
+            "--http.port",
+            "9545",
+            "--replica.poll-interval",
+            "500ms",
+        ]);
+        assert!(cmd.rpc.http);
+        assert_eq!(cmd.rpc.http_port, 9545);
+        assert_eq!(cmd.poll_interval, Duration::from_millis(500));
+        assert!(!cmd.disable_notifications);
+    }
+
+    #[test]
+    fn rejects_live_namespaces() {
+        assert!(ensure_read_only(&DEFAULT_MODULES.into()).is_ok());
+        assert!(ensure_read_only(&[RethRpcModule::Eth, RethRpcModule::Admin].into()).is_err());
+        assert!(ensure_read_only(&RpcModuleSelection::All).is_err());
+    }
+}
@@ -0,0 +1,566 @@
+//! Command that runs a state transition with the interface of the `evm t8n` tool.
+
+mod types;
+
+use alloy_consensus::{
+    proofs::{calculate_receipt_root, calculate_transaction_root, calculate_withdrawals_root},
+    transaction::TxHashRef,
+    SignableTransaction, Transaction as _, TxReceipt,
+};
+use alloy_eips::eip4895::Withdrawals;
+use alloy_genesis::GenesisAccount;
+use alloy_primitives::{hex, keccak256, Address, Bloom, Bytes, B256, KECCAK_EMPTY, U256};
+use alloy_rlp::Decodable;
+use clap::Parser;
+use eyre::{OptionExt, WrapErr};
+use reth_chainspec::{ChainSpec, EthChainSpec, EthereumHardforks};
+use reth_ethereum_primitives::{Block, BlockBody, Transaction, TransactionSigned};
+use reth_evm::{
+    execute::{BlockExecutionError, BlockExecutor, BlockValidationError},
+    ConfigureEvm,
+};
+use reth_node_ethereum::EthEvmConfig;
+use reth_primitives_traits::{
+    crypto::secp256k1::sign_message, Recovered, SealedBlock, SignerRecoverable,
+};
+use reth_revm::{
+    db::{states::bundle_state::BundleRetention, BundleState, CacheDB, EmptyDB},
+    state::{AccountInfo, Bytecode},
+    State,
+};
+use reth_trie::root::state_root_ref_unhashed;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::Arc};
+use tracing::debug;
+use types::{T8nEnv, T8nFork, T8nReceipt, T8nRejected, T8nResult, T8nStdin};
+
+/// Input path or output file name that reads from stdin.
+const STDIN: &str = "stdin";
+/// Output file name that prints to stdout.
+const STDOUT: &str = "stdout";
+/// Output file name that prints to stderr.
+const STDERR: &str = "stderr";
+
+/// A pre-state or post-state allocation.
+type Alloc = BTreeMap<Address, GenesisAccount>;
+
+/// Runs a state transition on top of a pre-state allocation and writes the resulting state,
+/// receipts and roots.
+///
+/// The inputs and outputs follow the interface of the `evm t8n` tool, which is what
+/// `execution-spec-tests` uses to fill fixtures. Transactions are executed with the Ethereum block
+/// executor, so system calls, withdrawals, rewards and execution layer requests of every fork are
+/// applied the same way as when the node executes a block.
+#[derive(Debug, Parser)]
+pub struct T8nCommand {
+    /// Path of the pre-state allocation, or `stdin` to read it from the JSON object on stdin.
+    #[arg(long = "input.alloc", value_name = "PATH", default_value = "alloc.json")]
+    input_alloc: String,
+
+    /// Path of the block environment, or `stdin` to read it from the JSON object on stdin.
+    #[arg(long = "input.env", value_name = "PATH", default_value = "env.json")]
+    input_env: String,
+
+    /// Path of the transactions, or `stdin` to read them from the JSON object on stdin.
+    ///
+    /// The transactions are either a JSON list, or a hex string of the RLP encoded list.
+    /// Transactions in a JSON list that have a `secretKey` are signed with it.
+    #[arg(long = "input.txs", value_name = "PATH", default_value = "txs.json")]
+    input_txs: String,
+
+    /// Directory the output files are written to.
+    #[arg(long = "output.basedir", value_name = "DIR", default_value = ".")]
+    output_basedir: PathBuf,
+
+    /// File name of the result, or `stdout`/`stderr` to print it.
+    #[arg(long = "output.result", value_name = "FILE", default_value = "result.json")]
+    output_result: String,
+
+    /// File name of the post-state allocation, or `stdout`/`stderr` to print it.
+    #[arg(long = "output.alloc", value_name = "FILE", default_value = "alloc.json")]
+    output_alloc: String,
+
+    /// File name of the RLP encoded list of included transactions, or `stdout`/`stderr` to print
+    /// it.
+    #[arg(long = "output.body", value_name = "FILE")]
+    output_body: Option<String>,
+
+    /// The fork rules to apply, e.g. `Cancun` or `ShanghaiToCancunAtTime15k`.
+    #[arg(long = "state.fork", value_name = "FORK", default_value = "GrayGlacier")]
+    fork: T8nFork,
+
+    /// The chain ID.
+    #[arg(long = "state.chainid", value_name = "CHAIN_ID", default_value_t = 1)]
+    chain_id: u64,
+
+    /// The block reward.
+    ///
+    /// Accepted for compatibility with other transition tools, block and ommer rewards always
+    /// follow the rules of the fork.
+    #[arg(
+        long = "state.reward",
+        value_name = "WEI",
+        default_value_t = -1,
+        allow_negative_numbers = true
+    )]
+    reward: i64,
+}
+
+impl T8nCommand {
+    /// Execute the `t8n` command
+    pub fn execute(self) -> eyre::Result<()> {
+        if self.reward >= 0 {
+            debug!(target: "reth::cli", reward = self.reward, "Ignoring block reward");
+        }
+
+        let (alloc, env, transactions) = self.read_input()?;
+        let chain_spec = self.fork.chain_spec(self.chain_id);
+        let (result, alloc, body) = transition(chain_spec, alloc, &env, transactions)?;
+
+        self.write_output(result, alloc, body)
+    }
+
+    /// Reads the pre-state allocation, the block environment and the transactions.
+    fn read_input(&self) -> eyre::Result<(Alloc, T8nEnv, Vec<TransactionSigned>)> {
+        let mut stdin = if [&self.input_alloc, &self.input_env, &self.input_txs]
+            .into_iter()
+            .any(|input| input == STDIN)
+        {
+            serde_json::from_reader::<_, T8nStdin>(std::io::stdin().lock())
+                .wrap_err("failed to read input from stdin")?
+        } else {
+            T8nStdin::default()
+        };
+
+        let alloc = if self.input_alloc == STDIN {
+            stdin.alloc.take().unwrap_or_default()
+        } else {
+            read_json(&self.input_alloc)?
+        };
+        let env = if self.input_env == STDIN {
+            stdin.env.take().ok_or_eyre("missing env in the input from stdin")?
+        } else {
+            read_json(&self.input_env)?
+        };
+        let transactions = if self.input_txs == STDIN {
+            stdin.txs.take().unwrap_or_default()
+        } else {
+            read_json(&self.input_txs)?
+        };
+
+        Ok((alloc, env, decode_transactions(transactions)?))
+    }
+
+    /// Writes the outputs to their files, or prints them as a single JSON object.
+    fn write_output(&self, result: T8nResult, alloc: Alloc, body: Bytes) -> eyre::Result<()> {
+        let mut outputs = vec![
+            (&self.output_result, "result", serde_json::to_value(result)?),
+            (&self.output_alloc, "alloc", serde_json::to_value(alloc)?),
+        ];
+        if let Some(output_body) = &self.output_body {
+            outputs.push((output_body, "body", serde_json::to_value(body)?));
+        }
+
+        let mut stdout = serde_json::Map::new();
+        let mut stderr = serde_json::Map::new();
+        for (name, key, value) in outputs {
+            match name.as_str() {
+                STDOUT => {
+                    stdout.insert(key.to_string(), value);
+                }
+                STDERR => {
+                    stderr.insert(key.to_string(), value);
+                }
+                _ => {
+                    fs::create_dir_all(&self.output_basedir)?;
+                    let path = self.output_basedir.join(name);
+                    fs::write(&path, serde_json::to_string_pretty(&value)?)
+                        .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+                }
+            }
+        }
+
+        if !stdout.is_empty() {
+            println!("{}", serde_json::to_string_pretty(&stdout)?);
+        }
+        if !stderr.is_empty() {
+            eprintln!("{}", serde_json::to_string_pretty(&stderr)?);
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes the transactions in a block on top of the pre-state allocation.
+///
+/// Returns the result, the post-state allocation and the RLP encoded list of included
+/// transactions. Transactions that can't be included in the block are reported as rejected.
+fn transition(
+    chain_spec: Arc<ChainSpec>,
+    alloc: Alloc,
+    env: &T8nEnv,
+    transactions: Vec<TransactionSigned>,
+) -> eyre::Result<(T8nResult, Alloc, Bytes)> {
+    let header = env.header(&chain_spec)?;
+    let (number, timestamp, base_fee) = (header.number, header.timestamp, header.base_fee_per_gas);
+    let (difficulty, excess_blob_gas) = (header.difficulty, header.excess_blob_gas);
+    let block = SealedBlock::seal_slow(Block {
+        header,
+        body: BlockBody {
+            transactions: Vec::new(),
+            ommers: env.ommer_headers(),
+            withdrawals: env.withdrawals.clone().map(Withdrawals::new),
+        },
+    });
+
+    let mut db =
+        State::builder().with_database(pre_state_db(&alloc, env)).with_bundle_update().build();
+    let evm_config = EthEvmConfig::ethereum(chain_spec.clone());
+    let mut executor = evm_config.executor_for_block(&mut db, &block)?;
+    executor.apply_pre_execution_changes()?;
+
+    let max_blob_gas = chain_spec
+        .blob_params_at_timestamp(timestamp)
+        .map(|params| params.max_blob_gas_per_block());
+    let homestead = chain_spec.is_homestead_active_at_block(number);
+    let mut blob_gas_used = 0;
+    let mut included = Vec::with_capacity(transactions.len());
+    let mut rejected = Vec::new();
+    for (index, tx) in transactions.into_iter().enumerate() {
+        // Signatures with a high `s` value are only invalid since EIP-2.
+        let signer = if homestead { tx.recover_signer() } else { tx.recover_signer_unchecked() };
+        let tx = match signer {
+            Ok(signer) => Recovered::new_unchecked(tx, signer),
+            Err(err) => {
+                rejected.push(T8nRejected { index, error: err.to_string() });
+                continue
+            }
+        };
+
+        let tx_blob_gas = tx.blob_gas_used().unwrap_or_default();
+        if let Some(max_blob_gas) = max_blob_gas &&
+            blob_gas_used + tx_blob_gas > max_blob_gas
+        {
+            rejected.push(T8nRejected {
+                index,
+                error: format!(
+                    "blob gas used {} exceeds maximum allowance {max_blob_gas}",
+                    blob_gas_used + tx_blob_gas
+                ),
+            });
+            continue
+        }
+
+        match executor.execute_transaction(tx.as_recovered_ref()) {
+            Ok(_) => {}
+            Err(BlockExecutionError::Validation(
+                err @ (BlockValidationError::InvalidTx { .. } |
+                BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas {
+                    ..
+                }),
+            )) => {
+                rejected.push(T8nRejected { index, error: err.to_string() });
+                continue
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        blob_gas_used += tx_blob_gas;
+        included.push(tx);
+    }
+
+    let (_, result) = executor.finish()?;
+    db.merge_transitions(BundleRetention::Reverts);
+    let alloc = post_state_alloc(alloc, db.take_bundle());
+
+    let mut receipts = Vec::with_capacity(included.len());
+    let mut logs = Vec::new();
+    let mut cumulative_gas_used = 0;
+    for (index, (tx, receipt)) in included.iter().zip(&result.receipts).enumerate() {
+        let gas_used = receipt.cumulative_gas_used - cumulative_gas_used;
+        cumulative_gas_used = receipt.cumulative_gas_used;
+
+        receipts.push(T8nReceipt {
+            tx_type: receipt.tx_type as u8,
+            root: Bytes::new(),
+            status: receipt.success as u64,
+            cumulative_gas_used: receipt.cumulative_gas_used,
+            logs_bloom: receipt.bloom(),
+            logs: receipt
+                .logs
+                .iter()
+                .map(|log| {
+                    let log_index = logs.len() as u64;
+                    logs.push(log.clone());
+                    alloy_rpc_types_eth::Log {
+                        inner: log.clone(),
+                        block_hash: Some(B256::ZERO),
+                        block_number: Some(number),
+                        block_timestamp: None,
+                        transaction_hash: Some(*tx.tx_hash()),
+                        transaction_index: Some(index as u64),
+                        log_index: Some(log_index),
+                        removed: false,
+                    }
+                })
+                .collect(),
+            transaction_hash: *tx.tx_hash(),
+            contract_address: if tx.is_create() {
+                tx.signer().create(tx.nonce())
+            } else {
+                Address::ZERO
+            },
+            gas_used,
+            effective_gas_price: tx.effective_gas_price(base_fee),
+            block_hash: B256::ZERO,
+            transaction_index: index as u64,
+            blob_gas_used: tx.blob_gas_used(),
+        });
+    }
+
+    let transactions = included.into_iter().map(Recovered::into_inner).collect::<Vec<_>>();
+    let receipts_with_bloom =
+        result.receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
+    let is_prague = chain_spec.is_prague_active_at_timestamp(timestamp);
+
+    let result = T8nResult {
+        state_root: state_root_ref_unhashed(&alloc),
+        tx_root: calculate_transaction_root(&transactions),
+        receipts_root: calculate_receipt_root(&receipts_with_bloom),
+        logs_hash: keccak256(alloy_rlp::encode(&logs)),
+        logs_bloom: receipts_with_bloom.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom_ref()),
+        receipts,
+        rejected,
+        current_difficulty: (!chain_spec.is_paris_active_at_block(number)).then_some(difficulty),
+        gas_used: result.gas_used,
+        current_base_fee: base_fee,
+        withdrawals_root: chain_spec
+            .is_shanghai_active_at_timestamp(timestamp)
+            .then(|| calculate_withdrawals_root(env.withdrawals.as_deref().unwrap_or_default())),
+        current_excess_blob_gas: excess_blob_gas,
+        blob_gas_used: chain_spec.is_cancun_active_at_timestamp(timestamp).then_some(blob_gas_used),
+        requests_hash: is_prague.then(|| result.requests.requests_hash()),
+        requests: is_prague.then_some(result.requests),
+    };
+
+    Ok((result, alloc, alloy_rlp::encode(&transactions).into()))
+}
+
+/// Returns an in-memory database holding the pre-state allocation and the known block hashes.
+fn pre_state_db(alloc: &Alloc, env: &T8nEnv) -> CacheDB<EmptyDB> {
+    let mut db = CacheDB::new(EmptyDB::default());
+    for (address, account) in alloc {
+        let code = account.code.clone().map(Bytecode::new_raw).unwrap_or_default();
+        db.insert_account_info(
+            *address,
+            AccountInfo::new(
+                account.balance,
+                account.nonce.unwrap_or_default(),
+                code.hash_slow(),
+                code,
+            ),
+        );
+        for (slot, value) in account.storage.iter().flatten() {
+            db.insert_account_storage(
+                *address,
+                U256::from_be_bytes(slot.0),
+                U256::from_be_bytes(value.0),
+            )
+            .expect("empty database is infallible");
+        }
+    }
+    db.cache.block_hashes.extend(env.block_hashes.iter().map(|(number, hash)| (*number, *hash)));
+    db
+}
+
+/// Applies the changes of the bundle to the pre-state allocation.
+fn post_state_alloc(mut alloc: Alloc, bundle: BundleState) -> Alloc {
+    let BundleState { state, contracts, .. } = bundle;
+    for (address, account) in state {
+        let Some(info) = &account.info else {
+            alloc.remove(&address);
+            continue
+        };
+
+        let entry = alloc.entry(address).or_default();
+        entry.balance = info.balance;
+        entry.nonce = Some(info.nonce);
+        if let Some(code) = info.code.as_ref().or_else(|| contracts.get(&info.code_hash)) {
+            entry.code = (!code.is_empty()).then(|| code.original_bytes());
+        } else if info.code_hash == KECCAK_EMPTY {
+            entry.code = None;
+        }
+
+        let mut storage = if account.was_destroyed() {
+            BTreeMap::new()
+        } else {
+            entry.storage.take().unwrap_or_default()
+        };
+        storage.extend(
+            account
+                .storage
+                .iter()
+                .map(|(slot, value)| (B256::from(*slot), B256::from(value.present_value))),
+        );
+        storage.retain(|_, value| !value.is_zero());
+        entry.storage = (!storage.is_empty()).then_some(storage);
+    }
+    alloc
+}
+
+/// Reads and deserializes a JSON file.
+fn read_json<T: DeserializeOwned>(path: &str) -> eyre::Result<T> {
+    let file = fs::read_to_string(path).wrap_err_with(|| format!("failed to read {path}"))?;
+    serde_json::from_str(&file).wrap_err_with(|| format!("failed to deserialize {path}"))
+}
+
+/// Decodes the transactions from a JSON list or a hex string of the RLP encoded list.
+fn decode_transactions(transactions: Value) -> eyre::Result<Vec<TransactionSigned>> {
+    match transactions {
+        Value::Null => Ok(Vec::new()),
+        Value::String(rlp) => {
+            let rlp = hex::decode(rlp)?;
+            Ok(Vec::<TransactionSigned>::decode(&mut rlp.as_slice())?)
+        }
+        Value::Array(transactions) => transactions
+            .into_iter()
+            .enumerate()
+            .map(|(index, tx)| {
+                decode_json_transaction(tx)
+                    .wrap_err_with(|| format!("failed to decode transaction {index}"))
+            })
+            .collect(),
+        other => eyre::bail!("expected a list of transactions or RLP, got {other}"),
+    }
+}
+
+/// Decodes a JSON transaction, signing it if it has a `secretKey`.
+fn decode_json_transaction(mut tx: Value) -> eyre::Result<TransactionSigned> {
+    let Some(fields) = tx.as_object_mut() else { eyre::bail!("expected an object, got {tx}") };
+    if let Some(data) = fields.remove("data") {
+        fields.entry("input").or_insert(data);
+    }
+
+    let Some(secret_key) = fields.remove("secretKey") else {
+        return Ok(serde_json::from_value(tx)?)
+    };
+    let secret_key: B256 = serde_json::from_value(secret_key)?;
+    let tx: Transaction = serde_json::from_value(tx)?;
+    let signature = sign_message(secret_key, tx.signature_hash())?;
+
+    Ok(tx.into_signed(signature).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{address, b256, Signature, TxKind};
+
+    #[test]
+    fn parse_t8n_command() {
+        let cmd = T8nCommand::parse_from([
+            "reth",
+            "--input.alloc=stdin",
+            "--input.env=stdin",
+            "--input.txs=stdin",
+            "--output.result=stdout",
+            "--output.alloc=stdout",
+            "--state.fork=Cancun",
+            "--state.chainid=1",
+            "--state.reward=-1",
+        ]);
+        assert_eq!(cmd.fork, T8nFork::Cancun);
+        assert_eq!(cmd.reward, -1);
+        assert_eq!(cmd.output_result, STDOUT);
+    }
+
+    #[test]
+    fn transfer() {
+        let sender = address!("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let recipient = Address::with_last_byte(0xaa);
+        let alloc = Alloc::from([(
+            sender,
+            GenesisAccount::default().with_balance(U256::from(1_000_000_000_000_000_000u64)),
+        )]);
+        let env: T8nEnv = serde_json::from_value(serde_json::json!({
+            "currentCoinbase": "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+            "currentGasLimit": "0x16345785d8a0000",
+            "currentNumber": "0x1",
+            "currentTimestamp": "0x3e8",
+            "currentRandom": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "currentBaseFee": "0x7",
+            "withdrawals": []
+        }))
+        .unwrap();
+        let transactions = decode_transactions(serde_json::json!([{
+            "type": "0x0",
+            "chainId": "0x1",
+            "nonce": "0x0",
+            "gasPrice": "0xa",
+            "gas": "0x5208",
+            "to": recipient,
+            "value": "0x1",
+            "data": "0x",
+            "secretKey": "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8"
+        }]))
+        .unwrap();
+
+        let (result, alloc, _) =
+            transition(T8nFork::Shanghai.chain_spec(1), alloc, &env, transactions).unwrap();
+
+        assert!(result.rejected.is_empty());
+        assert_eq!(result.gas_used, 21_000);
+        assert_eq!(result.receipts[0].status, 1);
+        assert_eq!(alloc[&recipient].balance, U256::from(1));
+        assert_eq!(alloc[&sender].nonce, Some(1));
+        assert_eq!(result.withdrawals_root, Some(calculate_withdrawals_root(&[])));
+        assert_eq!(result.blob_gas_used, None);
+    }
+
+    #[test]
+    fn high_s_signature_before_homestead() {
+        let secret_key =
+            b256!("0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8");
+        let sender = address!("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let alloc = Alloc::from([(
+            sender,
+            GenesisAccount::default().with_balance(U256::from(1_000_000_000_000_000_000u64)),
+        )]);
+        let env: T8nEnv = serde_json::from_value(serde_json::json!({
+            "currentCoinbase": "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+            "currentGasLimit": "0x16345785d8a0000",
+            "currentNumber": "0x1",
+            "currentTimestamp": "0x3e8",
+            "currentDifficulty": "0x20000"
+        }))
+        .unwrap();
+
+        let tx = Transaction::Legacy(TxLegacy {
+            gas_price: 10,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::with_last_byte(0xaa)),
+            value: U256::from(1),
+            ..Default::default()
+        });
+        let signature = sign_message(secret_key, tx.signature_hash()).unwrap();
+        // the same signer recovers from `(r, n - s)` with the flipped parity
+        let secp256k1_order = U256::from_be_bytes(
+            b256!("0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141").0,
+        );
+        let signature =
+            Signature::new(signature.r(), secp256k1_order - signature.s(), !signature.v());
+        let transactions = vec![TransactionSigned::from(tx.into_signed(signature))];
+
+        let (result, post_alloc, _) =
+            transition(T8nFork::Frontier.chain_spec(1), alloc.clone(), &env, transactions.clone())
+                .unwrap();
+        assert!(result.rejected.is_empty());
+        assert_eq!(post_alloc[&sender].nonce, Some(1));
+
+        let (result, _, _) =
+            transition(T8nFork::Homestead.chain_spec(1), alloc, &env, transactions).unwrap();
+        assert_eq!(result.rejected.len(), 1);
+    }
+}
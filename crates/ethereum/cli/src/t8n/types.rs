@@ -0,0 +1,456 @@
+//! JSON types of the `t8n` interface.
+
+use alloy_consensus::Header;
+use alloy_eips::{eip1559::INITIAL_BASE_FEE, eip4895::Withdrawal, eip7685::Requests};
+use alloy_genesis::GenesisAccount;
+use alloy_primitives::{Address, Bloom, Bytes, B256, U256};
+use alloy_rpc_types_eth::Log;
+use eyre::OptionExt;
+use reth_chainspec::{
+    Chain, ChainSpec, ChainSpecBuilder, EthChainSpec, EthereumHardfork, EthereumHardforks,
+    ForkCondition,
+};
+use serde::{de::IntoDeserializer, Deserialize, Serialize};
+use std::{collections::BTreeMap, str::FromStr, sync::Arc};
+
+/// The fork rules of a state transition, named as in `execution-spec-tests`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub(crate) enum T8nFork {
+    /// Frontier
+    Frontier,
+    /// Frontier to Homestead
+    FrontierToHomesteadAt5,
+    /// Homestead
+    Homestead,
+    /// Homestead to DAO
+    HomesteadToDaoAt5,
+    /// Homestead to Tangerine
+    HomesteadToEIP150At5,
+    /// Tangerine
+    #[serde(alias = "TangerineWhistle")]
+    EIP150,
+    /// Spurious Dragon
+    #[serde(alias = "SpuriousDragon")]
+    EIP158,
+    /// Spurious Dragon to Byzantium
+    EIP158ToByzantiumAt5,
+    /// Byzantium
+    Byzantium,
+    /// Byzantium to Constantinople fix
+    ByzantiumToConstantinopleFixAt5,
+    /// Constantinople
+    Constantinople,
+    /// Constantinople fix
+    #[serde(alias = "Petersburg")]
+    ConstantinopleFix,
+    /// Istanbul
+    Istanbul,
+    /// Muir Glacier
+    MuirGlacier,
+    /// Berlin
+    Berlin,
+    /// Berlin to London
+    BerlinToLondonAt5,
+    /// London
+    London,
+    /// Arrow Glacier
+    ArrowGlacier,
+    /// Gray Glacier
+    GrayGlacier,
+    /// Paris aka The Merge
+    #[serde(alias = "Merge")]
+    Paris,
+    /// Paris to Shanghai at time 15k
+    ParisToShanghaiAtTime15k,
+    /// Shanghai
+    Shanghai,
+    /// Shanghai to Cancun at time 15k
+    ShanghaiToCancunAtTime15k,
+    /// Cancun
+    Cancun,
+    /// Cancun to Prague at time 15k
+    CancunToPragueAtTime15k,
+    /// Prague
+    Prague,
+    /// Prague to Osaka at time 15k
+    PragueToOsakaAtTime15k,
+    /// Osaka
+    Osaka,
+    /// Amsterdam
+    Amsterdam,
+}
+
+impl T8nFork {
+    /// Returns the chain spec with the rules of this fork and the given chain ID.
+    pub(crate) fn chain_spec(self, chain_id: u64) -> Arc<ChainSpec> {
+        let spec_builder = ChainSpecBuilder::mainnet().reset().chain(Chain::from_id(chain_id));
+
+        let spec_builder = match self {
+            Self::Frontier => spec_builder.frontier_activated(),
+            Self::FrontierToHomesteadAt5 => spec_builder
+                .frontier_activated()
+                .with_fork(EthereumHardfork::Homestead, ForkCondition::Block(5)),
+            Self::Homestead => spec_builder.homestead_activated(),
+            Self::HomesteadToDaoAt5 => spec_builder
+                .homestead_activated()
+                .with_fork(EthereumHardfork::Dao, ForkCondition::Block(5)),
+            Self::HomesteadToEIP150At5 => spec_builder
+                .homestead_activated()
+                .with_fork(EthereumHardfork::Tangerine, ForkCondition::Block(5)),
+            Self::EIP150 => spec_builder.tangerine_whistle_activated(),
+            Self::EIP158 => spec_builder.spurious_dragon_activated(),
+            Self::EIP158ToByzantiumAt5 => spec_builder
+                .spurious_dragon_activated()
+                .with_fork(EthereumHardfork::Byzantium, ForkCondition::Block(5)),
+            Self::Byzantium => spec_builder.byzantium_activated(),
+            Self::ByzantiumToConstantinopleFixAt5 => spec_builder
+                .byzantium_activated()
+                .with_fork(EthereumHardfork::Constantinople, ForkCondition::Block(5))
+                .with_fork(EthereumHardfork::Petersburg, ForkCondition::Block(5)),
+            Self::Constantinople => spec_builder.constantinople_activated(),
+            Self::ConstantinopleFix => spec_builder.petersburg_activated(),
+            Self::Istanbul => spec_builder.istanbul_activated(),
+            Self::MuirGlacier => spec_builder.muirglacier_activated(),
+            Self::Berlin => spec_builder.berlin_activated(),
+            Self::BerlinToLondonAt5 => spec_builder
+                .berlin_activated()
+                .with_fork(EthereumHardfork::London, ForkCondition::Block(5)),
+            Self::London => spec_builder.london_activated(),
+            Self::ArrowGlacier => spec_builder.arrowglacier_activated(),
+            Self::GrayGlacier => spec_builder.grayglacier_activated(),
+            Self::Paris => spec_builder.paris_activated(),
+            Self::ParisToShanghaiAtTime15k => spec_builder
+                .paris_activated()
+                .with_fork(EthereumHardfork::Shanghai, ForkCondition::Timestamp(15_000)),
+            Self::Shanghai => spec_builder.shanghai_activated(),
+            Self::ShanghaiToCancunAtTime15k => spec_builder
+                .shanghai_activated()
+                .with_fork(EthereumHardfork::Cancun, ForkCondition::Timestamp(15_000)),
+            Self::Cancun => spec_builder.cancun_activated(),
+            Self::CancunToPragueAtTime15k => spec_builder
+                .cancun_activated()
+                .with_fork(EthereumHardfork::Prague, ForkCondition::Timestamp(15_000)),
+            Self::Prague => spec_builder.prague_activated(),
+            Self::PragueToOsakaAtTime15k => spec_builder.prague_activated().with_osaka_at(15_000),
+            Self::Osaka => spec_builder.osaka_activated(),
+            Self::Amsterdam => spec_builder.amsterdam_activated(),
+        };
+
+        Arc::new(spec_builder.build())
+    }
+}
+
+impl FromStr for T8nFork {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::deserialize(s.into_deserializer())
+    }
+}
+
+/// The inputs of a state transition read from stdin.
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct T8nStdin {
+    /// The pre-state allocation.
+    pub alloc: Option<BTreeMap<Address, GenesisAccount>>,
+    /// The block environment.
+    pub env: Option<T8nEnv>,
+    /// The transactions, either as a list or as RLP.
+    pub txs: Option<serde_json::Value>,
+}
+
+/// The block environment of a state transition.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct T8nEnv {
+    /// Fee recipient of the block.
+    pub current_coinbase: Address,
+    /// Gas limit of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub current_gas_limit: u64,
+    /// Number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub current_number: u64,
+    /// Timestamp of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub current_timestamp: u64,
+    /// Difficulty of the block, required before the merge.
+    pub current_difficulty: Option<U256>,
+    /// `prevrandao` of the block, required after the merge.
+    pub current_random: Option<B256>,
+    /// Base fee of the block, derived from the parent if absent.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub current_base_fee: Option<u64>,
+    /// Excess blob gas of the block, derived from the parent if absent.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub current_excess_blob_gas: Option<u64>,
+    /// Timestamp of the parent block.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub parent_timestamp: Option<u64>,
+    /// Base fee of the parent block.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub parent_base_fee: Option<u64>,
+    /// Gas used by the parent block.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub parent_gas_used: Option<u64>,
+    /// Gas limit of the parent block.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub parent_gas_limit: Option<u64>,
+    /// Excess blob gas of the parent block.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub parent_excess_blob_gas: Option<u64>,
+    /// Blob gas used by the parent block.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub parent_blob_gas_used: Option<u64>,
+    /// Parent beacon block root of the block.
+    pub parent_beacon_block_root: Option<B256>,
+    /// Hashes of previous blocks, available to the `BLOCKHASH` opcode.
+    #[serde(default)]
+    pub block_hashes: BTreeMap<U256, B256>,
+    /// Ommers of the block.
+    #[serde(default)]
+    pub ommers: Vec<T8nOmmer>,
+    /// Withdrawals of the block.
+    pub withdrawals: Option<Vec<Withdrawal>>,
+}
+
+impl T8nEnv {
+    /// Returns the header of the block, with the base fee and excess blob gas derived from the
+    /// parent if they are not set explicitly.
+    pub(crate) fn header(&self, chain_spec: &ChainSpec) -> eyre::Result<Header> {
+        let number = self.current_number;
+        let timestamp = self.current_timestamp;
+        let parent = self.parent_header();
+
+        let difficulty = if chain_spec.is_paris_active_at_block(number) {
+            U256::ZERO
+        } else {
+            self.current_difficulty.ok_or_eyre("currentDifficulty is required before the merge")?
+        };
+
+        let base_fee_per_gas = if !chain_spec.is_london_active_at_block(number) {
+            None
+        } else if let Some(base_fee) = self.current_base_fee {
+            Some(base_fee)
+        } else if chain_spec
+            .ethereum_fork_activation(EthereumHardfork::London)
+            .transitions_at_block(number)
+        {
+            Some(INITIAL_BASE_FEE)
+        } else {
+            Some(
+                chain_spec
+                    .next_block_base_fee(&parent, timestamp)
+                    .ok_or_eyre("currentBaseFee or the parent base fee is required")?,
+            )
+        };
+
+        let excess_blob_gas = if !chain_spec.is_cancun_active_at_timestamp(timestamp) {
+            None
+        } else if let Some(excess_blob_gas) = self.current_excess_blob_gas {
+            Some(excess_blob_gas)
+        } else {
+            // For the first post-fork block, the parent fields are evaluated as 0.
+            Some(
+                chain_spec
+                    .blob_params_at_timestamp(timestamp)
+                    .unwrap_or_default()
+                    .next_block_excess_blob_gas_osaka(
+                        parent.excess_blob_gas.unwrap_or_default(),
+                        parent.blob_gas_used.unwrap_or_default(),
+                        parent.base_fee_per_gas.unwrap_or_default(),
+                    ),
+            )
+        };
+
+        Ok(Header {
+            parent_hash: self.block_hash(number.checked_sub(1)).unwrap_or_default(),
+            beneficiary: self.current_coinbase,
+            difficulty,
+            number,
+            gas_limit: self.current_gas_limit,
+            timestamp,
+            mix_hash: self.current_random.unwrap_or_default(),
+            base_fee_per_gas,
+            excess_blob_gas,
+            parent_beacon_block_root: self.parent_beacon_block_root,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the headers of the ommers, with the fields that matter for their rewards.
+    pub(crate) fn ommer_headers(&self) -> Vec<Header> {
+        self.ommers
+            .iter()
+            .map(|ommer| Header {
+                number: self.current_number.saturating_sub(ommer.delta),
+                beneficiary: ommer.address,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Returns the parent header, with the fields that matter for the fee calculations.
+    fn parent_header(&self) -> Header {
+        Header {
+            number: self.current_number.saturating_sub(1),
+            timestamp: self.parent_timestamp.unwrap_or_default(),
+            gas_limit: self.parent_gas_limit.unwrap_or(self.current_gas_limit),
+            gas_used: self.parent_gas_used.unwrap_or_default(),
+            base_fee_per_gas: self.parent_base_fee,
+            excess_blob_gas: self.parent_excess_blob_gas,
+            blob_gas_used: self.parent_blob_gas_used,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the hash of the block with the given number, if it is known.
+    fn block_hash(&self, number: Option<u64>) -> Option<B256> {
+        self.block_hashes.get(&U256::from(number?)).copied()
+    }
+}
+
+/// An ommer of the block, relative to the block number.
+#[derive(Debug, Deserialize)]
+pub(crate) struct T8nOmmer {
+    /// Distance of the ommer from the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub delta: u64,
+    /// Fee recipient of the ommer.
+    pub address: Address,
+}
+
+/// The result of a state transition.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct T8nResult {
+    /// State root after the transition.
+    pub state_root: B256,
+    /// Root of the included transactions.
+    pub tx_root: B256,
+    /// Root of the receipts.
+    pub receipts_root: B256,
+    /// Hash of the RLP encoded list of all logs.
+    pub logs_hash: B256,
+    /// Bloom of all logs.
+    pub logs_bloom: Bloom,
+    /// Receipts of the included transactions.
+    pub receipts: Vec<T8nReceipt>,
+    /// Transactions that could not be included.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rejected: Vec<T8nRejected>,
+    /// Difficulty of the block, before the merge.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_difficulty: Option<U256>,
+    /// Gas used by the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Base fee of the block, after London.
+    #[serde(skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub current_base_fee: Option<u64>,
+    /// Root of the withdrawals, after Shanghai.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub withdrawals_root: Option<B256>,
+    /// Excess blob gas of the block, after Cancun.
+    #[serde(skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub current_excess_blob_gas: Option<u64>,
+    /// Blob gas used by the block, after Cancun.
+    #[serde(skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub blob_gas_used: Option<u64>,
+    /// Hash of the execution layer requests, after Prague.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests_hash: Option<B256>,
+    /// Execution layer requests of the block, after Prague.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests: Option<Requests>,
+}
+
+/// The receipt of an included transaction.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct T8nReceipt {
+    /// Type of the transaction.
+    #[serde(rename = "type", with = "alloy_serde::quantity")]
+    pub tx_type: u8,
+    /// Intermediate state root, always empty.
+    pub root: Bytes,
+    /// Status of the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub status: u64,
+    /// Gas used by the block up to and including the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub cumulative_gas_used: u64,
+    /// Bloom of the logs of the transaction.
+    pub logs_bloom: Bloom,
+    /// Logs of the transaction.
+    pub logs: Vec<Log>,
+    /// Hash of the transaction.
+    pub transaction_hash: B256,
+    /// Address of the created contract, or the zero address.
+    pub contract_address: Address,
+    /// Gas used by the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// Price paid per unit of gas.
+    #[serde(with = "alloy_serde::quantity")]
+    pub effective_gas_price: u128,
+    /// Hash of the block, always zero.
+    pub block_hash: B256,
+    /// Index of the transaction in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+    /// Blob gas used by the transaction.
+    #[serde(skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub blob_gas_used: Option<u64>,
+}
+
+/// A transaction that could not be included.
+#[derive(Debug, Serialize)]
+pub(crate) struct T8nRejected {
+    /// Index of the transaction in the input.
+    pub index: usize,
+    /// Reason the transaction was rejected.
+    pub error: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fork() {
+        assert_eq!("Cancun".parse::<T8nFork>().unwrap(), T8nFork::Cancun);
+        assert_eq!("Merge".parse::<T8nFork>().unwrap(), T8nFork::Paris);
+        assert_eq!(
+            "ShanghaiToCancunAtTime15k".parse::<T8nFork>().unwrap(),
+            T8nFork::ShanghaiToCancunAtTime15k
+        );
+        assert!("Unknown".parse::<T8nFork>().is_err());
+    }
+
+    #[test]
+    fn env_header() {
+        let env: T8nEnv = serde_json::from_value(serde_json::json!({
+            "currentCoinbase": "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+            "currentGasLimit": "0x16345785d8a0000",
+            "currentNumber": "0x2",
+            "currentTimestamp": "0x3e8",
+            "currentRandom": "0x0000000000000000000000000000000000000000000000000000000000020000",
+            "parentBaseFee": "0x7",
+            "parentGasUsed": "0x0",
+            "parentGasLimit": "0x16345785d8a0000",
+            "parentTimestamp": "0x0",
+            "blockHashes": { "0x1": "0x0000000000000000000000000000000000000000000000000000000000000001" },
+            "withdrawals": []
+        }))
+        .unwrap();
+
+        let header = env.header(&T8nFork::Shanghai.chain_spec(1)).unwrap();
+        assert_eq!(header.number, 2);
+        assert_eq!(header.difficulty, U256::ZERO);
+        assert_eq!(header.parent_hash, B256::with_last_byte(1));
+        // The decrease after an empty parent rounds down to zero at this base fee.
+        assert_eq!(header.base_fee_per_gas, Some(7));
+        assert_eq!(header.excess_blob_gas, None);
+    }
+}
@@ -472,6 +472,10 @@ impl<N: FullNodeComponents<Types = Self>> DebugNode<N> for EthereumNode {
     ) -> impl PayloadAttributesBuilder<<Self::Payload as PayloadTypes>::PayloadAttributes> {
         LocalPayloadAttributesBuilder::new(Arc::new(chain_spec.clone()))
     }
+
+    fn beacon_payload_converter() -> Option<fn(ExecutionData) -> ExecutionData> {
+        Some(|payload| payload)
+    }
 }
 
 /// Builds a [`RuntimeConfig`] from CLI [`JitArgs`].
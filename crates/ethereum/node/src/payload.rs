@@ -2,7 +2,7 @@
 
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_engine_primitives::{EthBuiltPayload, EthPayloadAttributes};
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
+use reth_ethereum_payload_builder::{EthereumBuilderConfig, ProposerPayment};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::ConfigureEvm;
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
@@ -14,7 +14,19 @@ use reth_transaction_pool::{PoolTransaction, TransactionPool};
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder {
+    /// Pays the proposer at the end of built blocks, if set.
+    proposer_payment: Option<ProposerPayment>,
+}
+
+impl EthereumPayloadBuilder {
+    /// Collects block fees in the builder's account and pays the proposer at the end of built
+    /// blocks, as required when building for MEV relays.
+    pub fn with_proposer_payment(mut self, proposer_payment: ProposerPayment) -> Self {
+        self.proposer_payment = Some(proposer_payment);
+        self
+    }
+}
 
 impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for EthereumPayloadBuilder
 where
@@ -44,15 +56,29 @@ where
         let gas_limit = conf.gas_limit_for(chain);
         let skip_state_root = ctx.config().tree_config().skip_state_root();
 
+        let mut builder_config = EthereumBuilderConfig::new()
+            .with_gas_limit(gas_limit)
+            .with_max_blobs_per_block(conf.max_blobs_per_block())
+            .with_extra_data(conf.extra_data())
+            .with_skip_state_root(skip_state_root);
+        // include the bundles received by `mev_sendBundle` at the top of built blocks
+        if let Some(bundle_pool) = ctx.bundle_pool() {
+            builder_config = builder_config.with_bundle_pool(bundle_pool.clone());
+        }
+        if let Some(proposer_payment) = self.proposer_payment {
+            builder_config = builder_config.with_proposer_payment(proposer_payment);
+        }
+        // append the transactions of the inclusion lists received by the engine API
+        #[cfg(feature = "focil")]
+        if let Some(inclusion_lists) = ctx.inclusion_lists() {
+            builder_config = builder_config.with_inclusion_lists(inclusion_lists.clone());
+        }
+
         Ok(reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new()
-                .with_gas_limit(gas_limit)
-                .with_max_blobs_per_block(conf.max_blobs_per_block())
-                .with_extra_data(conf.extra_data())
-                .with_skip_state_root(skip_state_root),
+            builder_config,
         ))
     }
 }
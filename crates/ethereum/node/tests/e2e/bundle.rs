@@ -0,0 +1,81 @@
+use crate::utils::eth_payload_attributes;
+use alloy_eips::Decodable2718;
+use alloy_genesis::Genesis;
+use jsonrpsee_core::client::ClientT;
+use reth_chainspec::{ChainSpecBuilder, MAINNET};
+use reth_e2e_test_utils::{
+    node::NodeTestContext, transaction::TransactionTestContext, wallet::Wallet,
+};
+use reth_ethereum_primitives::TransactionSigned;
+use reth_node_builder::{NodeBuilder, NodeHandle};
+use reth_node_core::{
+    args::{PayloadBuilderArgs, RpcServerArgs},
+    node_config::NodeConfig,
+};
+use reth_node_ethereum::EthereumNode;
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
+use reth_tasks::Runtime;
+use std::sync::Arc;
+
+#[tokio::test]
+async fn includes_bundles_at_top_of_block() -> eyre::Result<()> {
+    reth_tracing::init_test_tracing();
+    let runtime = Runtime::test();
+
+    let genesis: Genesis = serde_json::from_str(include_str!("../assets/genesis.json")).unwrap();
+    let chain_spec = Arc::new(
+        ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(genesis)
+            .cancun_activated()
+            .build(),
+    );
+    let node_config = NodeConfig::test()
+        .with_chain(chain_spec)
+        .with_unused_ports()
+        .with_rpc(
+            RpcServerArgs::default()
+                .with_unused_ports()
+                .with_http()
+                .with_http_api(RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Mev])),
+        )
+        .with_payload_builder(PayloadBuilderArgs { bundles: true, ..Default::default() });
+    let NodeHandle { node, node_exit_future: _ } = NodeBuilder::new(node_config)
+        .testing_node(runtime)
+        .node(EthereumNode::default())
+        .launch()
+        .await?;
+    let mut node = NodeTestContext::new(node, eth_payload_attributes).await?;
+    let client = node.rpc_client().expect("http rpc is enabled");
+
+    let wallets = Wallet::new(3).wallet_gen();
+
+    // a pool transaction, which must be included after the bundle
+    let pool_tx = TransactionTestContext::transfer_tx_bytes(1, wallets[0].clone()).await;
+    let pool_tx_hash = node.rpc.inject_tx(pool_tx).await?;
+
+    // a bundle that can't be executed, which must be skipped without failing the build
+    let invalid_tx =
+        TransactionTestContext::transfer_tx_bytes_with_nonce(1, wallets[1].clone(), 5).await;
+    let _: serde_json::Value = client
+        .request(
+            "eth_sendBundle",
+            (serde_json::json!({ "txs": [invalid_tx], "blockNumber": "0x1" }),),
+        )
+        .await?;
+
+    let bundle_tx = TransactionTestContext::transfer_tx_bytes(1, wallets[2].clone()).await;
+    let bundle_tx_hash = *TransactionSigned::decode_2718(&mut bundle_tx.as_ref())?.hash();
+    let _: serde_json::Value = client
+        .request(
+            "eth_sendBundle",
+            (serde_json::json!({ "txs": [bundle_tx], "blockNumber": "0x1" }),),
+        )
+        .await?;
+
+    let payload = node.advance_block().await?;
+    let tx_hashes = payload.block().body().transactions().map(|tx| *tx.hash()).collect::<Vec<_>>();
+    assert_eq!(tx_hashes, vec![bundle_tx_hash, pool_tx_hash]);
+
+    Ok(())
+}
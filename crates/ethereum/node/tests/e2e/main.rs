@@ -1,6 +1,7 @@
 #![allow(missing_docs)]
 
 mod blobs;
+mod bundle;
 mod custom_genesis;
 mod dev;
 mod eth;
@@ -0,0 +1,195 @@
+//! Top-of-block bundle simulation.
+
+use alloy_consensus::{Transaction, Typed2718};
+use alloy_primitives::{Address, B256, U256};
+use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
+use reth_evm::{ConfigureEvm, Evm, EvmEnvFor};
+use reth_payload_util::{BundleRefundConfig, PoolBundle};
+use reth_revm::db::CacheDB;
+use revm::{
+    bytecode::Bytecode, context_interface::result::ResultAndState, state::AccountInfo, Database,
+    DatabaseCommit, DatabaseRef,
+};
+use std::cell::RefCell;
+use tracing::trace;
+
+/// Gas limit of the transactions paying bundle refunds.
+///
+/// This matches the payout cost `mev_simBundle` charges for every refund recipient.
+pub(crate) const BUNDLE_REFUND_GAS: u64 = 30_000;
+
+/// The block a bundle is simulated for.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BundleBlockEnv {
+    /// The beneficiary of the block.
+    pub(crate) coinbase: Address,
+    /// The base fee of the block.
+    pub(crate) base_fee: u64,
+    /// The maximum gas limit of a transaction.
+    pub(crate) tx_gas_limit_cap: u64,
+    /// The gas that is still available to the transactions of the block.
+    pub(crate) available_gas: u64,
+    /// The state gas that is still available in the block, if Amsterdam is active.
+    pub(crate) available_state_gas: Option<u64>,
+}
+
+/// A bundle that can be included at the current position of the block.
+#[derive(Debug)]
+pub(crate) struct SimulatedBundle {
+    /// The refunds to pay after the bundle, net of the cost of the refund transactions.
+    pub(crate) refunds: Vec<(Address, U256)>,
+}
+
+/// A read-only view of the state of the block that is being built.
+///
+/// Bundles are simulated on an overlay of this view, so they see the changes of everything that
+/// was already added to the block without modifying it. [`RefCell`] provides the interior
+/// mutability the [`DatabaseRef`] trait requires.
+pub(crate) struct BlockStateRef<'a, DB> {
+    inner: RefCell<&'a mut DB>,
+}
+
+impl<'a, DB> BlockStateRef<'a, DB> {
+    /// Creates a view of the given state.
+    pub(crate) const fn new(db: &'a mut DB) -> Self {
+        Self { inner: RefCell::new(db) }
+    }
+}
+
+impl<DB: Database> DatabaseRef for BlockStateRef<'_, DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.inner.borrow_mut().basic(address)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.inner.borrow_mut().code_by_hash(code_hash)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.inner.borrow_mut().storage(address, index)
+    }
+
+    fn block_hash_ref(&self, number: u64) -> Result<B256, Self::Error> {
+        self.inner.borrow_mut().block_hash(number)
+    }
+}
+
+/// Simulates the bundle on top of `db` and returns the refunds to pay if it can be included.
+///
+/// The bundle is discarded if any of its transactions is invalid, reverts without being allowed
+/// to, or doesn't fit into the remaining block gas together with the refund transactions, or if a
+/// refund doesn't cover the cost of its transaction. Bundles with blob transactions are skipped,
+/// because their sidecars are not available to the builder.
+///
+/// Refunds follow `mev_simBundle`: every transaction with a refund percent refunds that share of
+/// what the transactions without one paid to the block beneficiary, split between its refund
+/// recipients.
+pub(crate) fn simulate_bundle<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    evm_env: &EvmEnvFor<EvmConfig>,
+    db: DB,
+    bundle: &PoolBundle<TransactionSigned>,
+    block_env: BundleBlockEnv,
+) -> Option<SimulatedBundle>
+where
+    EvmConfig: ConfigureEvm<Primitives = EthPrimitives>,
+    DB: DatabaseRef,
+{
+    if bundle.transactions.iter().any(|item| item.tx.is_eip4844()) {
+        trace!(target: "payload_builder", bundle = ?bundle.hash, "skipping bundle with blob transactions");
+        return None
+    }
+
+    let BundleBlockEnv { coinbase, base_fee, tx_gas_limit_cap, available_gas, available_state_gas } =
+        block_env;
+    let mut overlay = CacheDB::new(db);
+    let mut evm = evm_config.evm_with_env(&mut overlay, evm_env.clone());
+    let mut gas_used = 0u64;
+    // state gas is bounded by the gas limits of the transactions
+    let mut state_gas_limit = 0u64;
+    let mut refundable_value = U256::ZERO;
+
+    for item in &bundle.transactions {
+        let tx_gas_limit = item.tx.gas_limit();
+        if tx_gas_limit.min(tx_gas_limit_cap) > available_gas.saturating_sub(gas_used) ||
+            available_state_gas.is_some_and(|available_state_gas| {
+                tx_gas_limit > available_state_gas.saturating_sub(state_gas_limit)
+            })
+        {
+            trace!(target: "payload_builder", bundle = ?bundle.hash, tx = ?item.tx.tx_hash(), "skipping bundle exceeding block gas limit");
+            return None
+        }
+
+        let balance_before = coinbase_balance(evm.db_mut(), coinbase);
+        let ResultAndState { result, state } = match evm.transact(evm_config.tx_env(&item.tx)) {
+            Ok(result) => result,
+            Err(err) => {
+                trace!(target: "payload_builder", bundle = ?bundle.hash, tx = ?item.tx.tx_hash(), %err, "skipping bundle with invalid transaction");
+                return None
+            }
+        };
+        if !result.is_success() && !item.can_revert {
+            trace!(target: "payload_builder", bundle = ?bundle.hash, tx = ?item.tx.tx_hash(), "skipping bundle with reverted transaction");
+            return None
+        }
+
+        gas_used += if available_state_gas.is_some() {
+            result.gas().block_regular_gas_used()
+        } else {
+            result.tx_gas_used()
+        };
+        state_gas_limit += tx_gas_limit;
+        evm.db_mut().commit(state);
+
+        if item.refund_percent.is_none() {
+            refundable_value +=
+                coinbase_balance(evm.db_mut(), coinbase).saturating_sub(balance_before);
+        }
+    }
+    drop(evm);
+
+    let refund_fee = U256::from(base_fee) * U256::from(BUNDLE_REFUND_GAS);
+    let mut refunds = Vec::new();
+    let mut refunded_value = U256::ZERO;
+    for item in &bundle.transactions {
+        let Some(refund_percent) = item.refund_percent else { continue };
+        let payout_value = refundable_value * U256::from(refund_percent) / U256::from(100);
+
+        let signer_refund = [BundleRefundConfig { address: item.tx.signer(), percent: 100 }];
+        let refund_configs =
+            if item.refund_configs.is_empty() { &signer_refund[..] } else { &item.refund_configs };
+        for config in refund_configs {
+            let value = payout_value * U256::from(config.percent) / U256::from(100);
+            // the refund pays for its own transaction
+            let Some(value) = value.checked_sub(refund_fee) else {
+                trace!(target: "payload_builder", bundle = ?bundle.hash, recipient = %config.address, "skipping bundle with refund below its transaction cost");
+                return None
+            };
+            refunded_value += value + refund_fee;
+            refunds.push((config.address, value));
+        }
+    }
+    if refunded_value > refundable_value {
+        trace!(target: "payload_builder", bundle = ?bundle.hash, %refunded_value, %refundable_value, "skipping bundle with negative profit");
+        return None
+    }
+
+    let refund_gas = BUNDLE_REFUND_GAS * refunds.len() as u64;
+    if gas_used + refund_gas > available_gas ||
+        available_state_gas.is_some_and(|available_state_gas| {
+            state_gas_limit + refund_gas > available_state_gas
+        })
+    {
+        trace!(target: "payload_builder", bundle = ?bundle.hash, "skipping bundle with refunds exceeding block gas limit");
+        return None
+    }
+
+    Some(SimulatedBundle { refunds })
+}
+
+/// Returns the balance of the block beneficiary, treating read errors as an empty account.
+fn coinbase_balance<DB: Database>(db: &mut DB, coinbase: Address) -> U256 {
+    db.basic(coinbase).ok().flatten().map(|account| account.balance).unwrap_or_default()
+}
@@ -0,0 +1,42 @@
+//! Checks of conditional transactions against the block that is being built.
+
+use alloy_primitives::{Address, B256, U256};
+use reth_errors::ProviderError;
+use reth_revm::db::State;
+use reth_storage_api::StateProvider;
+use reth_transaction_pool::conditional::KnownAccountsReader;
+use reth_trie_common::HashedStorage;
+use revm::Database;
+
+/// The state of the block that is being built, on top of the state of its parent.
+///
+/// The known accounts of conditional transactions are checked against this state, so the changes
+/// of the transactions that were already added to the block are taken into account.
+pub(crate) struct InBlockState<'a, DB> {
+    /// The state of the block that is being built.
+    pub(crate) db: &'a mut State<DB>,
+    /// The state of the parent block.
+    pub(crate) parent: &'a dyn StateProvider,
+}
+
+impl<DB: Database<Error = ProviderError>> KnownAccountsReader for InBlockState<'_, DB> {
+    fn storage_root(&mut self, address: Address) -> Result<B256, ProviderError> {
+        // the cached storage of the account holds all slots that were changed in the block
+        let hashed_storage = self
+            .db
+            .cache
+            .accounts
+            .get(&address)
+            .and_then(|account| {
+                account.account.as_ref().map(|plain_account| {
+                    HashedStorage::from_plain_storage(account.status, plain_account.storage.iter())
+                })
+            })
+            .unwrap_or_default();
+        self.parent.storage_root(address, hashed_storage)
+    }
+
+    fn storage(&mut self, address: Address, slot: B256) -> Result<U256, ProviderError> {
+        self.db.storage(address, U256::from_be_bytes(slot.0))
+    }
+}
@@ -1,6 +1,11 @@
+use crate::ProposerPayment;
 pub use alloy_eips::eip1559::calculate_block_gas_limit;
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
 use alloy_primitives::Bytes;
+use reth_ethereum_primitives::TransactionSigned;
+use reth_payload_util::BundlePool;
+#[cfg(feature = "focil")]
+use reth_payload_util::InclusionListStore;
 
 /// Settings for the Ethereum builder.
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -18,6 +23,13 @@ pub struct EthereumBuilderConfig {
     pub extra_data: Bytes,
     /// Whether payload builds should skip state-root computation.
     pub skip_state_root: bool,
+    /// Pool of bundles to include at the top of built blocks, if any.
+    pub bundle_pool: Option<BundlePool<TransactionSigned>>,
+    /// Pays the proposer from the builder's account at the end of each block, if set.
+    pub proposer_payment: Option<ProposerPayment>,
+    /// Inclusion lists (EIP-7805) that built blocks must satisfy, if any.
+    #[cfg(feature = "focil")]
+    pub inclusion_lists: Option<InclusionListStore>,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -35,6 +47,10 @@ impl EthereumBuilderConfig {
             max_blobs_per_block: None,
             extra_data: Bytes::new(),
             skip_state_root: false,
+            bundle_pool: None,
+            proposer_payment: None,
+            #[cfg(feature = "focil")]
+            inclusion_lists: None,
         }
     }
 
@@ -68,6 +84,25 @@ impl EthereumBuilderConfig {
         self.skip_state_root = skip_state_root;
         self
     }
+
+    /// Set the pool of bundles to include at the top of built blocks.
+    pub fn with_bundle_pool(mut self, bundle_pool: BundlePool<TransactionSigned>) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
+    }
+
+    /// Pay the proposer from the builder's account at the end of each block.
+    pub fn with_proposer_payment(mut self, proposer_payment: ProposerPayment) -> Self {
+        self.proposer_payment = Some(proposer_payment);
+        self
+    }
+
+    /// Set the store of inclusion lists (EIP-7805) that built blocks must satisfy.
+    #[cfg(feature = "focil")]
+    pub fn with_inclusion_lists(mut self, inclusion_lists: InclusionListStore) -> Self {
+        self.inclusion_lists = Some(inclusion_lists);
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -9,6 +9,8 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 use alloy_consensus::{BlockHeader, Transaction};
+#[cfg(feature = "focil")]
+use alloy_eips::{eip2718::Decodable2718, Typed2718};
 use alloy_primitives::{Bytes, U256};
 use alloy_rlp::Encodable;
 use alloy_rpc_types_engine::PayloadAttributes as EthPayloadAttributes;
@@ -21,7 +23,7 @@ use reth_consensus_common::validation::MAX_RLP_BLOCK_SIZE;
 use reth_errors::{BlockExecutionError, BlockValidationError, ConsensusError};
 use reth_ethereum_primitives::{EthPrimitives, TransactionSigned};
 use reth_evm::{
-    block::TxResult,
+    block::{CommitChanges, TxResult},
     execute::{BlockBuilder, BlockBuilderOutcome},
     ConfigureEvm, Evm, NextBlockEnvAttributes,
 };
@@ -31,20 +33,35 @@ use reth_payload_builder::{BlobSidecars, EthBuiltPayload};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadAttributes;
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
+#[cfg(feature = "focil")]
+use reth_primitives_traits::SignedTransaction;
 use reth_revm::{database::StateProviderDatabase, db::State};
 use reth_storage_api::StateProviderFactory;
 use reth_transaction_pool::{
+    conditional::{validate_block_conditional, validate_known_accounts},
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
     BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
     ValidPoolTransaction,
 };
-use revm::context_interface::{Block as _, Cfg as _};
+use revm::{
+    context_interface::{Block as _, Cfg as _},
+    Database as _,
+};
 use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
+mod bundle;
+use bundle::{simulate_bundle, BlockStateRef, BundleBlockEnv, BUNDLE_REFUND_GAS};
+
+mod conditional;
+use conditional::InBlockState;
+
 mod config;
 pub use config::*;
 
+mod payment;
+pub use payment::{ProposerFeeRecipients, ProposerPayment, PROPOSER_PAYMENT_GAS};
+
 pub mod validator;
 pub use validator::EthereumExecutionPayloadValidator;
 
@@ -129,7 +146,15 @@ where
             self.evm_config.clone(),
             self.client.clone(),
             self.pool.clone(),
-            self.builder_config.clone(),
+            // the empty payload must not contain bundles, inclusion list transactions or a
+            // proposer payment either
+            EthereumBuilderConfig {
+                bundle_pool: None,
+                proposer_payment: None,
+                #[cfg(feature = "focil")]
+                inclusion_lists: None,
+                ..self.builder_config.clone()
+            },
             args,
             |_| -> BestTransactionsIter<Pool> { Box::new(std::iter::empty()) },
         )?
@@ -189,29 +214,35 @@ where
         .build();
 
     let evm_config = evm_config.with_jit_support();
+    let proposer_payment = builder_config.proposer_payment.as_ref();
+    let next_block_attributes = NextBlockEnvAttributes {
+        timestamp: attributes.timestamp(),
+        // when paying the proposer, fees are collected by the builder's account first
+        suggested_fee_recipient: proposer_payment
+            .map(|payment| payment.address())
+            .unwrap_or(attributes.suggested_fee_recipient),
+        prev_randao: attributes.prev_randao,
+        gas_limit: builder_config
+            .gas_limit_with_target(parent_header.gas_limit, attributes.target_gas_limit()),
+        parent_beacon_block_root: attributes.parent_beacon_block_root(),
+        withdrawals: attributes.withdrawals.clone().map(Into::into),
+        extra_data: builder_config.extra_data.clone(),
+        slot_number: attributes.slot_number(),
+    };
     let mut builder = evm_config
-        .builder_for_next_block(
-            &mut db,
-            &parent_header,
-            NextBlockEnvAttributes {
-                timestamp: attributes.timestamp(),
-                suggested_fee_recipient: attributes.suggested_fee_recipient,
-                prev_randao: attributes.prev_randao,
-                gas_limit: builder_config
-                    .gas_limit_with_target(parent_header.gas_limit, attributes.target_gas_limit()),
-                parent_beacon_block_root: attributes.parent_beacon_block_root(),
-                withdrawals: attributes.withdrawals.clone().map(Into::into),
-                extra_data: builder_config.extra_data.clone(),
-                slot_number: attributes.slot_number(),
-            },
-        )
+        .builder_for_next_block(&mut db, &parent_header, next_block_attributes.clone())
         .map_err(PayloadBuilderError::other)?;
 
     debug!(target: "payload_builder", id=%payload_id, parent_header = ?parent_header.hash(), parent_number = parent_header.number, "building new payload");
     let mut cumulative_tx_gas_used = 0;
     let mut block_regular_gas_used = 0;
     let mut block_state_gas_used = 0;
-    let block_gas_limit: u64 = builder.evm_mut().block().gas_limit();
+    // leave room for the proposer payment at the end of the block
+    let block_gas_limit: u64 = builder
+        .evm_mut()
+        .block()
+        .gas_limit()
+        .saturating_sub(proposer_payment.map(|_| PROPOSER_PAYMENT_GAS).unwrap_or_default());
     let tx_gas_limit_cap = builder.evm_mut().cfg_env().tx_gas_limit_cap();
     let base_fee = builder.evm_mut().block().basefee();
 
@@ -255,6 +286,166 @@ where
     let withdrawals_rlp_length =
         attributes.withdrawals.as_ref().map(|withdrawals| withdrawals.length()).unwrap_or(0);
 
+    // Include bundles atomically at the top of the block before falling back to the mempool.
+    if let Some(bundle_pool) = &builder_config.bundle_pool {
+        let bundles = bundle_pool.bundles_for_block(parent_header.number + 1);
+        if !bundles.is_empty() {
+            let evm_env = evm_config
+                .next_evm_env(&parent_header, &next_block_attributes)
+                .map_err(PayloadBuilderError::other)?;
+            let coinbase = builder.evm_mut().block().beneficiary();
+
+            'bundles: for bundle in bundles {
+                // check if the job was cancelled, if so we can exit early
+                if cancel.is_cancelled() {
+                    return Ok(BuildOutcome::Cancelled)
+                }
+
+                let bundle_rlp_len: usize =
+                    bundle.transactions.iter().map(|item| item.tx.inner().length()).sum();
+                if is_osaka &&
+                    block_transactions_rlp_length +
+                        bundle_rlp_len +
+                        withdrawals_rlp_length +
+                        1024 >
+                        MAX_RLP_BLOCK_SIZE
+                {
+                    trace!(target: "payload_builder", bundle = ?bundle.hash, "skipping bundle exceeding the max block size");
+                    continue
+                }
+
+                // simulate the bundle on the state of the block built so far, so it is executed
+                // exactly like in the simulation below
+                let block_env = BundleBlockEnv {
+                    coinbase,
+                    base_fee,
+                    tx_gas_limit_cap,
+                    available_gas: if is_amsterdam {
+                        block_gas_limit.saturating_sub(block_regular_gas_used)
+                    } else {
+                        block_gas_limit.saturating_sub(cumulative_tx_gas_used)
+                    },
+                    available_state_gas: is_amsterdam
+                        .then(|| block_gas_limit.saturating_sub(block_state_gas_used)),
+                };
+                let Some(simulated) = simulate_bundle(
+                    &evm_config,
+                    &evm_env,
+                    BlockStateRef::new(builder.evm_mut().db_mut()),
+                    &bundle,
+                    block_env,
+                ) else {
+                    continue
+                };
+
+                // refunds are paid from the builder's account
+                if !simulated.refunds.is_empty() && proposer_payment.is_none() {
+                    trace!(target: "payload_builder", bundle = ?bundle.hash, "skipping bundle with refunds without a builder account");
+                    continue
+                }
+
+                let balance_before = builder
+                    .evm_mut()
+                    .db_mut()
+                    .basic(coinbase)
+                    .map_err(PayloadBuilderError::other)?
+                    .map(|account| account.balance)
+                    .unwrap_or_default();
+
+                for (idx, item) in bundle.transactions.iter().enumerate() {
+                    let tx_rlp_len = item.tx.inner().length();
+                    let mut tx_regular_gas_used = 0;
+                    let result = builder.execute_transaction_with_commit_condition(
+                        item.tx.clone(),
+                        |result| {
+                            let result = &result.result().result;
+                            if !result.is_success() && !item.can_revert {
+                                return CommitChanges::No
+                            }
+                            tx_regular_gas_used = result.gas().block_regular_gas_used();
+                            CommitChanges::Yes
+                        },
+                    );
+                    let err = match result {
+                        Ok(Some(gas_output)) => {
+                            block_transactions_rlp_length += tx_rlp_len;
+                            cumulative_tx_gas_used += gas_output.tx_gas_used();
+                            block_regular_gas_used += tx_regular_gas_used;
+                            block_state_gas_used += gas_output.state_gas_used();
+                            continue
+                        }
+                        Ok(None) => BlockExecutionError::msg("bundle transaction reverted"),
+                        Err(err) => err,
+                    };
+
+                    // The bundle was simulated on the same state, so this is not expected. If the
+                    // first transaction failed nothing was added to the block and the bundle is
+                    // skipped, otherwise part of it is already executed, so drop it and build
+                    // again.
+                    warn!(target: "payload_builder", %err, bundle = ?bundle.hash, "failed to execute simulated bundle, removing it");
+                    bundle_pool.remove(&bundle.hash);
+                    if idx == 0 {
+                        continue 'bundles
+                    }
+                    return Err(PayloadBuilderError::evm(err))
+                }
+
+                if let Some(payment) = proposer_payment {
+                    for (recipient, value) in simulated.refunds {
+                        let nonce = builder
+                            .evm_mut()
+                            .db_mut()
+                            .basic(payment.address())
+                            .map_err(PayloadBuilderError::other)?
+                            .map(|account| account.nonce)
+                            .unwrap_or_default();
+                        let tx = payment
+                            .sign_transfer(
+                                chain_spec.chain_id(),
+                                nonce,
+                                base_fee,
+                                BUNDLE_REFUND_GAS,
+                                recipient,
+                                value,
+                            )
+                            .map_err(PayloadBuilderError::other)?;
+                        let tx_rlp_len = tx.inner().length();
+
+                        // the gas for the refunds was reserved by the simulation
+                        let mut tx_regular_gas_used = 0;
+                        let gas_output = builder
+                            .execute_transaction_with_result_closure(tx, |result| {
+                                tx_regular_gas_used =
+                                    result.result().result.gas().block_regular_gas_used();
+                            })
+                            .map_err(|err| {
+                                bundle_pool.remove(&bundle.hash);
+                                PayloadBuilderError::evm(err)
+                            })?;
+                        trace!(target: "payload_builder", bundle = ?bundle.hash, %recipient, %value, "paid bundle refund");
+
+                        block_transactions_rlp_length += tx_rlp_len;
+                        cumulative_tx_gas_used += gas_output.tx_gas_used();
+                        block_regular_gas_used += tx_regular_gas_used;
+                        block_state_gas_used += gas_output.state_gas_used();
+                    }
+                }
+
+                // the bundle is worth what it paid to the block beneficiary, net of refunds
+                let balance_after = builder
+                    .evm_mut()
+                    .db_mut()
+                    .basic(coinbase)
+                    .map_err(PayloadBuilderError::other)?
+                    .map(|account| account.balance)
+                    .unwrap_or_default();
+                let profit = balance_after.saturating_sub(balance_before);
+                total_fees += profit;
+                debug!(target: "payload_builder", id=%payload_id, bundle = ?bundle.hash, %profit, "included bundle");
+            }
+        }
+    }
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         let exceeds_gas_limit = if is_amsterdam {
@@ -294,6 +485,27 @@ where
             return Ok(BuildOutcome::Cancelled)
         }
 
+        // ensure the ERC-7796 conditions of the transaction are satisfied by this block, the known
+        // accounts are checked against the state after the transactions added so far
+        if let Some(conditional) = pool_tx.conditional.as_deref() &&
+            let Err(err) = validate_block_conditional(
+                conditional,
+                parent_header.number + 1,
+                attributes.timestamp(),
+            )
+            .and_then(|()| {
+                let state = InBlockState {
+                    db: builder.evm_mut().db_mut(),
+                    parent: state_provider.as_ref(),
+                };
+                validate_known_accounts(conditional, state)
+            })
+        {
+            trace!(target: "payload_builder", tx=?pool_tx.hash(), %err, "skipping conditional transaction");
+            best_txs.mark_invalid(&pool_tx, InvalidPoolTransactionError::other(err));
+            continue
+        }
+
         // convert tx to a signed transaction
         let tx = pool_tx.to_consensus();
 
@@ -441,6 +653,62 @@ where
         }
     }
 
+    // Append the transactions of the inclusion list the block must satisfy (EIP-7805) that the
+    // pool didn't provide. Transactions that can't be included are ignored, like already included
+    // ones which fail with a nonce too low.
+    #[cfg(feature = "focil")]
+    if let Some(inclusion_lists) = &builder_config.inclusion_lists &&
+        let Some(inclusion_list) = inclusion_lists.get(parent_header.hash())
+    {
+        for encoded in inclusion_list {
+            let Ok(tx) = TransactionSigned::decode_2718_exact(&encoded) else { continue };
+            if tx.is_eip4844() ||
+                tx.gas_limit() > block_gas_limit.saturating_sub(cumulative_tx_gas_used)
+            {
+                continue
+            }
+            let Ok(tx) = tx.try_into_recovered() else { continue };
+
+            let miner_fee = tx.effective_tip_per_gas(base_fee);
+            let tx_hash = *tx.tx_hash();
+            match builder.execute_transaction(tx) {
+                Ok(gas_output) => {
+                    let gas_used = gas_output.tx_gas_used();
+                    total_fees += U256::from(miner_fee.unwrap_or_default()) * U256::from(gas_used);
+                    cumulative_tx_gas_used += gas_used;
+                }
+                Err(err) => {
+                    trace!(target: "payload_builder", %err, ?tx_hash, "skipping inclusion list transaction");
+                }
+            }
+        }
+    }
+
+    if let Some(payment) = proposer_payment {
+        let recipient =
+            payment.fee_recipient(attributes.timestamp(), attributes.suggested_fee_recipient);
+        let nonce = builder
+            .evm_mut()
+            .db_mut()
+            .basic(payment.address())
+            .map_err(PayloadBuilderError::other)?
+            .map(|account| account.nonce)
+            .unwrap_or_default();
+        // the builder pays for the transfer out of the collected fees
+        let value =
+            total_fees.saturating_sub(U256::from(PROPOSER_PAYMENT_GAS) * U256::from(base_fee));
+        let tx = payment
+            .sign_payment(chain_spec.chain_id(), nonce, base_fee, recipient, value)
+            .map_err(PayloadBuilderError::other)?;
+
+        // the gas for the payment was reserved up front, so it always fits into the block
+        builder.execute_transaction(tx).map_err(PayloadBuilderError::evm)?;
+
+        trace!(target: "payload_builder", id=%payload_id, %recipient, %value, "paid proposer");
+        // the payload is worth what the proposer receives
+        total_fees = value;
+    }
+
     // check if we have a better block
     if !is_better_payload(best_payload.as_ref(), total_fees) {
         // Release db
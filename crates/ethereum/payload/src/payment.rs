@@ -0,0 +1,153 @@
+//! Proposer payment for blocks built on behalf of other proposers.
+
+use alloy_consensus::{SignableTransaction, TxEip1559};
+use alloy_primitives::{Address, ChainId, TxKind, U256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use parking_lot::RwLock;
+use reth_ethereum_primitives::TransactionSigned;
+use reth_primitives_traits::Recovered;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Gas reserved at the end of the block for the proposer payment transaction.
+pub const PROPOSER_PAYMENT_GAS: u64 = 21_000;
+
+/// Fee recipients of upcoming proposers, keyed by the timestamp of the block they propose.
+///
+/// This is shared between the payload builder and whatever learns about upcoming proposers, for
+/// example from the proposer duties of a relay.
+#[derive(Debug, Clone, Default)]
+pub struct ProposerFeeRecipients {
+    inner: Arc<RwLock<BTreeMap<u64, Address>>>,
+}
+
+impl ProposerFeeRecipients {
+    /// Sets the fee recipient of the proposer of the block with the given timestamp.
+    pub fn insert(&self, timestamp: u64, fee_recipient: Address) {
+        self.inner.write().insert(timestamp, fee_recipient);
+    }
+
+    /// Returns the fee recipient of the proposer of the block with the given timestamp.
+    pub fn get(&self, timestamp: u64) -> Option<Address> {
+        self.inner.read().get(&timestamp).copied()
+    }
+
+    /// Removes all entries for blocks before the given timestamp.
+    pub fn prune_before(&self, timestamp: u64) {
+        let mut inner = self.inner.write();
+        *inner = inner.split_off(&timestamp);
+    }
+}
+
+/// Configures the builder to collect block fees in its own account and pay the proposer at the end
+/// of the block.
+///
+/// With this enabled, the builder's address is used as the block beneficiary and the last
+/// transaction of every block transfers the collected fees, minus the cost of the transfer, to the
+/// proposer's fee recipient. The value of the built payload is the amount paid to the proposer.
+///
+/// The builder's account also pays the refunds of the bundles included in the block.
+#[derive(Debug, Clone)]
+pub struct ProposerPayment {
+    /// Signer of the builder's account.
+    signer: PrivateKeySigner,
+    /// Known fee recipients of upcoming proposers.
+    fee_recipients: ProposerFeeRecipients,
+}
+
+impl ProposerPayment {
+    /// Creates a new proposer payment config for the given builder account.
+    pub fn new(signer: PrivateKeySigner, fee_recipients: ProposerFeeRecipients) -> Self {
+        Self { signer, fee_recipients }
+    }
+
+    /// Returns the address of the builder's account.
+    pub fn address(&self) -> Address {
+        self.signer.address()
+    }
+
+    /// Returns the fee recipient to pay for the block with the given timestamp, falling back to the
+    /// suggested fee recipient of the payload attributes.
+    pub fn fee_recipient(&self, timestamp: u64, suggested_fee_recipient: Address) -> Address {
+        self.fee_recipients.get(timestamp).unwrap_or(suggested_fee_recipient)
+    }
+
+    /// Signs the payment transaction transferring `value` to `recipient`.
+    pub fn sign_payment(
+        &self,
+        chain_id: ChainId,
+        nonce: u64,
+        base_fee: u64,
+        recipient: Address,
+        value: U256,
+    ) -> Result<Recovered<TransactionSigned>, alloy_signer::Error> {
+        self.sign_transfer(chain_id, nonce, base_fee, PROPOSER_PAYMENT_GAS, recipient, value)
+    }
+
+    /// Signs a transaction transferring `value` from the builder's account to `recipient`, for
+    /// example to pay a bundle refund.
+    ///
+    /// The transaction pays no priority fee.
+    pub fn sign_transfer(
+        &self,
+        chain_id: ChainId,
+        nonce: u64,
+        base_fee: u64,
+        gas_limit: u64,
+        recipient: Address,
+        value: U256,
+    ) -> Result<Recovered<TransactionSigned>, alloy_signer::Error> {
+        let tx = TxEip1559 {
+            chain_id,
+            nonce,
+            gas_limit,
+            max_fee_per_gas: base_fee.into(),
+            max_priority_fee_per_gas: 0,
+            to: TxKind::Call(recipient),
+            value,
+            ..Default::default()
+        };
+        let signature = self.signer.sign_hash_sync(&tx.signature_hash())?;
+        Ok(Recovered::new_unchecked(tx.into_signed(signature).into(), self.address()))
+    }
+}
+
+impl PartialEq for ProposerPayment {
+    fn eq(&self, other: &Self) -> bool {
+        self.address() == other.address() &&
+            Arc::ptr_eq(&self.fee_recipients.inner, &other.fee_recipients.inner)
+    }
+}
+
+impl Eq for ProposerPayment {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{transaction::SignerRecoverable, Transaction};
+    use alloy_primitives::B256;
+
+    #[test]
+    fn signs_payment_to_fee_recipient() {
+        let recipients = ProposerFeeRecipients::default();
+        let payment = ProposerPayment::new(
+            PrivateKeySigner::from_bytes(&B256::repeat_byte(1)).unwrap(),
+            recipients.clone(),
+        );
+        let fallback = Address::random();
+        let proposer = Address::random();
+
+        recipients.insert(24, proposer);
+        assert_eq!(payment.fee_recipient(12, fallback), fallback);
+        assert_eq!(payment.fee_recipient(24, fallback), proposer);
+
+        let tx = payment.sign_payment(1, 7, 10, proposer, U256::from(100)).unwrap();
+        assert_eq!(tx.signer(), payment.address());
+        assert_eq!(tx.recover_signer().unwrap(), payment.address());
+        assert_eq!(tx.to(), Some(proposer));
+        assert_eq!(tx.nonce(), 7);
+
+        recipients.prune_before(25);
+        assert_eq!(recipients.get(24), None);
+    }
+}
@@ -0,0 +1,112 @@
+//! Payload builder component submitting built payloads to relays.
+
+use crate::{BuilderSigner, RelayChainConfig, RelayClient, RelaySubmitter};
+use alloy_signer_local::PrivateKeySigner;
+use reth_basic_payload_builder::BetterPayloadEmitter;
+use reth_chainspec::EthereumHardforks;
+use reth_ethereum_engine_primitives::{EthBuiltPayload, EthPayloadAttributes};
+use reth_ethereum_payload_builder::{ProposerFeeRecipients, ProposerPayment};
+use reth_ethereum_primitives::EthPrimitives;
+use reth_evm::ConfigureEvm;
+use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
+use reth_node_builder::{components::PayloadBuilderBuilder, BuilderContext, PayloadTypes};
+use reth_node_ethereum::EthereumPayloadBuilder;
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use tokio::sync::broadcast;
+use url::Url;
+
+/// Capacity of the channel between the payload builder and the relay submitter.
+const PAYLOAD_CHANNEL_CAPACITY: usize = 16;
+
+/// Configuration of the relay builder mode.
+#[derive(Debug, Clone)]
+pub struct RelayConfig {
+    /// Base URLs of the relays to submit to.
+    pub relays: Vec<Url>,
+    /// Signs the submitted bids.
+    pub builder_signer: BuilderSigner,
+    /// Signer of the account that collects the block fees and pays the proposer.
+    pub payment_signer: PrivateKeySigner,
+    /// Beacon chain parameters, derived from the chain if not set.
+    pub chain_config: Option<RelayChainConfig>,
+}
+
+/// Payload builder component that builds blocks for MEV relays.
+///
+/// Blocks are built by the [`EthereumPayloadBuilder`] with a proposer payment at the end, and every
+/// improved payload is signed and submitted to the configured relays by a [`RelaySubmitter`].
+#[derive(Debug, Clone)]
+pub struct RelayPayloadBuilder {
+    /// The relay configuration.
+    config: RelayConfig,
+    /// The wrapped Ethereum payload builder component.
+    payload_builder: EthereumPayloadBuilder,
+}
+
+impl RelayPayloadBuilder {
+    /// Creates a new relay payload builder component.
+    pub fn new(config: RelayConfig) -> Self {
+        Self { config, payload_builder: EthereumPayloadBuilder::default() }
+    }
+
+    /// Sets the wrapped Ethereum payload builder component, for example to include bundles.
+    pub fn with_payload_builder(mut self, payload_builder: EthereumPayloadBuilder) -> Self {
+        self.payload_builder = payload_builder;
+        self
+    }
+}
+
+impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for RelayPayloadBuilder
+where
+    Types: NodeTypes<ChainSpec: EthereumHardforks, Primitives = EthPrimitives>,
+    Node: FullNodeTypes<Types = Types>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
+        + Unpin
+        + 'static,
+    Evm: ConfigureEvm<
+            Primitives = PrimitivesTy<Types>,
+            NextBlockEnvCtx = reth_evm::NextBlockEnvAttributes,
+        > + 'static,
+    Types::Payload:
+        PayloadTypes<BuiltPayload = EthBuiltPayload, PayloadAttributes = EthPayloadAttributes>,
+{
+    type PayloadBuilder = BetterPayloadEmitter<
+        reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Node::Provider, Evm>,
+    >;
+
+    async fn build_payload_builder(
+        self,
+        ctx: &BuilderContext<Node>,
+        pool: Pool,
+        evm_config: Evm,
+    ) -> eyre::Result<Self::PayloadBuilder> {
+        let Self { config, payload_builder } = self;
+        let chain_config = match config.chain_config {
+            Some(chain_config) => chain_config,
+            None => RelayChainConfig::from_chain_spec(&*ctx.chain_spec()).ok_or_else(|| {
+                eyre::eyre!("relay builder mode requires the beacon chain config of custom chains")
+            })?,
+        };
+
+        let fee_recipients = ProposerFeeRecipients::default();
+        let payload_builder = payload_builder
+            .with_proposer_payment(ProposerPayment::new(
+                config.payment_signer,
+                fee_recipients.clone(),
+            ))
+            .build_payload_builder(ctx, pool, evm_config)
+            .await?;
+
+        let (payloads_tx, payloads_rx) = broadcast::channel(PAYLOAD_CHANNEL_CAPACITY);
+        let submitter = RelaySubmitter::new(
+            ctx.chain_spec(),
+            config.relays.into_iter().map(RelayClient::new).collect(),
+            config.builder_signer,
+            chain_config,
+            fee_recipients,
+        );
+        ctx.task_executor().spawn_critical_task("relay submitter", submitter.run(payloads_rx));
+
+        Ok(BetterPayloadEmitter::new(payloads_tx, payload_builder))
+    }
+}
@@ -0,0 +1,87 @@
+//! HTTP client for the relay builder API.
+
+use crate::{ProposerDuty, RelayError, SubmitBlockRequest};
+use reqwest::Response;
+use url::Url;
+
+/// Client of a single relay's builder API.
+#[derive(Debug, Clone)]
+pub struct RelayClient {
+    /// The base URL of the relay.
+    url: Url,
+    /// The HTTP client.
+    client: reqwest::Client,
+}
+
+impl RelayClient {
+    /// Creates a new client for the relay at the given base URL.
+    pub fn new(mut url: Url) -> Self {
+        // endpoints are joined to the base URL, which replaces its last segment unless it is a
+        // directory
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Self { url, client: reqwest::Client::new() }
+    }
+
+    /// Returns the base URL of the relay.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the proposers of the current and the next epoch that registered with the relay.
+    pub async fn proposer_duties(&self) -> Result<Vec<ProposerDuty>, RelayError> {
+        let response =
+            self.client.get(self.endpoint("relay/v1/builder/validators")?).send().await?;
+        Ok(check_status(response).await?.json().await?)
+    }
+
+    /// Submits a signed block to the relay.
+    pub async fn submit_block(&self, request: &SubmitBlockRequest) -> Result<(), RelayError> {
+        let response = self
+            .client
+            .post(self.endpoint("relay/v1/builder/blocks")?)
+            .json(request)
+            .send()
+            .await?;
+        check_status(response).await?;
+        Ok(())
+    }
+
+    /// Returns the URL of the given endpoint below the base URL.
+    fn endpoint(&self, path: &str) -> Result<Url, RelayError> {
+        Ok(self.url.join(path)?)
+    }
+}
+
+/// Turns non-success responses into [`RelayError::Rejected`].
+async fn check_status(response: Response) -> Result<Response, RelayError> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response)
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(RelayError::Rejected { status, body })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoint_keeps_base_path() {
+        for base in ["https://relay.example/api", "https://relay.example/api/"] {
+            let client = RelayClient::new(Url::parse(base).unwrap());
+            assert_eq!(
+                client.endpoint("relay/v1/builder/blocks").unwrap().as_str(),
+                "https://relay.example/api/relay/v1/builder/blocks"
+            );
+        }
+
+        let client = RelayClient::new(Url::parse("https://0xab@relay.example").unwrap());
+        assert_eq!(
+            client.endpoint("relay/v1/builder/validators").unwrap().as_str(),
+            "https://0xab@relay.example/relay/v1/builder/validators"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+//! Relay errors.
+
+use reth_ethereum_engine_primitives::BuiltPayloadConversionError;
+
+/// Errors that can occur when submitting payloads to relays.
+#[derive(Debug, thiserror::Error)]
+pub enum RelayError {
+    /// The builder's BLS secret key is invalid.
+    #[error("invalid BLS secret key")]
+    InvalidSecretKey,
+    /// The payload can't be converted into a submission.
+    #[error(transparent)]
+    Conversion(#[from] BuiltPayloadConversionError),
+    /// The execution requests of the payload can't be converted into a submission.
+    #[error("invalid execution requests")]
+    InvalidExecutionRequests,
+    /// The URL of a relay endpoint is invalid.
+    #[error(transparent)]
+    InvalidUrl(#[from] url::ParseError),
+    /// The HTTP request to the relay failed.
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    /// The relay rejected the request.
+    #[error("relay responded with {status}: {body}")]
+    Rejected {
+        /// The status code of the response.
+        status: reqwest::StatusCode,
+        /// The body of the response.
+        body: String,
+    },
+}
@@ -0,0 +1,34 @@
+//! Builder mode that submits payloads to MEV relays.
+//!
+//! In this mode the node builds blocks on behalf of the proposers registered with the configured
+//! relays: the block fees are collected by the builder's account and paid to the proposer in the
+//! last transaction of the block, and every improved payload is signed with the builder's BLS key
+//! and submitted to the relays' builder API.
+//!
+//! The [`RelayPayloadBuilder`] node component wires this up on top of the Ethereum payload builder.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod builder;
+pub use builder::{RelayConfig, RelayPayloadBuilder};
+
+mod client;
+pub use client::RelayClient;
+
+mod error;
+pub use error::RelayError;
+
+mod service;
+pub use service::{RelayChainConfig, RelaySubmitter};
+
+mod signer;
+pub use signer::{bid_trace_root, compute_builder_domain, BuilderSigner};
+
+mod types;
+pub use types::{ProposerDuty, SubmitBlockRequest};
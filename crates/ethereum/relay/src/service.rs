@@ -0,0 +1,251 @@
+//! Submission of built payloads to relays.
+
+use crate::{compute_builder_domain, BuilderSigner, ProposerDuty, RelayClient, SubmitBlockRequest};
+use alloy_consensus::{
+    constants::{
+        HOLESKY_GENESIS_HASH, HOODI_GENESIS_HASH, MAINNET_GENESIS_HASH, SEPOLIA_GENESIS_HASH,
+    },
+    Transaction as _,
+};
+use alloy_primitives::{b256, B256};
+use alloy_rpc_types_beacon::relay::BidTrace;
+use futures_util::future::join_all;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_ethereum_engine_primitives::EthBuiltPayload;
+use reth_ethereum_payload_builder::ProposerFeeRecipients;
+use reth_payload_primitives::BuiltPayload;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{debug, trace, warn};
+
+/// Beacon chain parameters needed to submit blocks to relays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelayChainConfig {
+    /// Timestamp of the beacon chain genesis.
+    pub genesis_time: u64,
+    /// Fork version of the beacon chain genesis, which determines the builder domain.
+    pub genesis_fork_version: [u8; 4],
+    /// Genesis validators root of the beacon chain.
+    pub genesis_validators_root: B256,
+    /// Duration of a slot in seconds.
+    pub seconds_per_slot: u64,
+}
+
+impl RelayChainConfig {
+    /// Returns the config of the beacon chain of a known execution chain.
+    ///
+    /// Chains are identified by their genesis hash, so that chains reusing the chain id of a known
+    /// network don't inherit its beacon chain parameters.
+    pub fn from_chain_spec(chain_spec: &impl EthChainSpec) -> Option<Self> {
+        let (genesis_time, genesis_fork_version, genesis_validators_root) =
+            match chain_spec.genesis_hash() {
+                MAINNET_GENESIS_HASH => (
+                    1606824023,
+                    [0x00, 0x00, 0x00, 0x00],
+                    b256!("0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"),
+                ),
+                SEPOLIA_GENESIS_HASH => (
+                    1655733600,
+                    [0x90, 0x00, 0x00, 0x69],
+                    b256!("0xd8ea171f3c94aea21ebc42a1ed61052acf3f9209c00e4efbaaddac09ed9b8078"),
+                ),
+                HOLESKY_GENESIS_HASH => (
+                    1695902400,
+                    [0x01, 0x01, 0x70, 0x00],
+                    b256!("0x9143aa7c615a7f7115e2b6aac319c03529df8242ae705fba9df39b79c59fa8b1"),
+                ),
+                HOODI_GENESIS_HASH => (
+                    1742213400,
+                    [0x10, 0x00, 0x09, 0x10],
+                    b256!("0x212f13fc4df078b6cb7db228f1c8307566dcecf900867401a92023d7ba99cb5f"),
+                ),
+                _ => return None,
+            };
+        Some(Self {
+            genesis_time,
+            genesis_fork_version,
+            genesis_validators_root,
+            seconds_per_slot: 12,
+        })
+    }
+
+    /// Returns the slot of the block with the given timestamp.
+    pub const fn slot_at(&self, timestamp: u64) -> u64 {
+        timestamp.saturating_sub(self.genesis_time) / self.seconds_per_slot
+    }
+
+    /// Returns the timestamp of the block in the given slot.
+    pub const fn timestamp_at(&self, slot: u64) -> u64 {
+        self.genesis_time + slot * self.seconds_per_slot
+    }
+}
+
+/// Signs the payloads emitted by the payload builder and submits them to the configured relays.
+///
+/// The submitter also polls the relays for upcoming proposers and publishes their fee recipients
+/// to the payload builder, so that the proposer payment of built blocks goes to the right account.
+/// Payloads for slots without a registered proposer are not submitted.
+#[derive(Debug)]
+pub struct RelaySubmitter<ChainSpec> {
+    /// The chain spec, used to pick the submission version.
+    chain_spec: Arc<ChainSpec>,
+    /// The relays to submit to.
+    relays: Vec<RelayClient>,
+    /// Signs the submitted bids.
+    signer: BuilderSigner,
+    /// Beacon chain parameters.
+    chain_config: RelayChainConfig,
+    /// The domain bids are signed for.
+    domain: B256,
+    /// Fee recipients of upcoming proposers, shared with the payload builder.
+    fee_recipients: ProposerFeeRecipients,
+    /// Upcoming proposer duties by slot.
+    duties: BTreeMap<u64, ProposerDuty>,
+}
+
+impl<ChainSpec: EthereumHardforks> RelaySubmitter<ChainSpec> {
+    /// Creates a new submitter.
+    pub fn new(
+        chain_spec: Arc<ChainSpec>,
+        relays: Vec<RelayClient>,
+        signer: BuilderSigner,
+        chain_config: RelayChainConfig,
+        fee_recipients: ProposerFeeRecipients,
+    ) -> Self {
+        Self {
+            chain_spec,
+            relays,
+            signer,
+            domain: compute_builder_domain(chain_config.genesis_fork_version),
+            chain_config,
+            fee_recipients,
+            duties: BTreeMap::new(),
+        }
+    }
+
+    /// Submits payloads received from the channel until it is closed.
+    pub async fn run(mut self, mut payloads: broadcast::Receiver<Arc<EthBuiltPayload>>) {
+        debug!(
+            target: "relay",
+            genesis_validators_root = %self.chain_config.genesis_validators_root,
+            genesis_fork_version = %alloy_primitives::hex::encode(self.chain_config.genesis_fork_version),
+            "Starting relay submitter"
+        );
+        // poll twice per slot, so duties of the next slot are known before its payload is built
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(self.chain_config.seconds_per_slot / 2));
+        loop {
+            tokio::select! {
+                _ = interval.tick() => self.update_duties().await,
+                payload = payloads.recv() => match payload {
+                    Ok(payload) => self.submit(Arc::unwrap_or_clone(payload)).await,
+                    Err(RecvError::Lagged(skipped)) => {
+                        trace!(target: "relay", skipped, "Skipped outdated payloads");
+                    }
+                    Err(RecvError::Closed) => return,
+                },
+            }
+        }
+    }
+
+    /// Fetches the proposer duties from all relays.
+    async fn update_duties(&mut self) {
+        let responses = join_all(self.relays.iter().map(|relay| relay.proposer_duties())).await;
+        for (relay, response) in self.relays.iter().zip(responses) {
+            match response {
+                Ok(duties) => {
+                    for duty in duties {
+                        let timestamp = self.chain_config.timestamp_at(duty.slot);
+                        self.fee_recipients.insert(timestamp, duty.entry.message.fee_recipient);
+                        self.duties.insert(duty.slot, duty);
+                    }
+                }
+                Err(err) => {
+                    warn!(target: "relay", relay = %relay.url(), %err, "Failed to fetch proposer duties");
+                }
+            }
+        }
+
+        // drop duties of past slots
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let current_slot = self.chain_config.slot_at(now);
+        self.duties = self.duties.split_off(&current_slot);
+        self.fee_recipients.prune_before(self.chain_config.timestamp_at(current_slot));
+    }
+
+    /// Signs the payload and submits it to all relays.
+    async fn submit(&self, payload: EthBuiltPayload) {
+        let block = payload.block();
+        let slot = self.chain_config.slot_at(block.timestamp);
+        let Some(duty) = self.duties.get(&slot) else {
+            trace!(target: "relay", slot, "No registered proposer for slot");
+            return
+        };
+
+        // the builder pays the proposer in the last transaction of the block
+        let fee_recipient = duty.entry.message.fee_recipient;
+        let paid_to = block.body().transactions.last().and_then(|tx| tx.to());
+        if paid_to != Some(fee_recipient) {
+            debug!(target: "relay", slot, %fee_recipient, ?paid_to, "Payload doesn't pay the registered proposer");
+            return
+        }
+
+        let message = BidTrace {
+            slot,
+            parent_hash: block.parent_hash,
+            block_hash: block.hash(),
+            builder_pubkey: self.signer.public_key(),
+            proposer_pubkey: duty.entry.message.pubkey,
+            proposer_fee_recipient: fee_recipient,
+            gas_limit: block.gas_limit,
+            gas_used: block.gas_used,
+            value: payload.fees(),
+        };
+        let signature = self.signer.sign_bid(&message, self.domain);
+        let request = match SubmitBlockRequest::new(&*self.chain_spec, payload, message, signature)
+        {
+            Ok(request) => request,
+            Err(err) => {
+                warn!(target: "relay", slot, %err, "Failed to create block submission");
+                return
+            }
+        };
+
+        let block_hash = request.message().block_hash;
+        let value = request.message().value;
+        let results = join_all(self.relays.iter().map(|relay| relay.submit_block(&request))).await;
+        for (relay, result) in self.relays.iter().zip(results) {
+            match result {
+                Ok(()) => {
+                    debug!(target: "relay", relay = %relay.url(), slot, ?block_hash, %value, "Submitted block");
+                }
+                Err(err) => {
+                    warn!(target: "relay", relay = %relay.url(), slot, ?block_hash, %err, "Failed to submit block");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::{ChainSpecBuilder, MAINNET, SEPOLIA};
+
+    #[test]
+    fn chain_config_from_chain_spec() {
+        let mainnet = RelayChainConfig::from_chain_spec(&*MAINNET).unwrap();
+        assert_eq!(mainnet.genesis_fork_version, [0; 4]);
+        assert_eq!(mainnet.slot_at(1606824023 + 12 * 100), 100);
+
+        let sepolia = RelayChainConfig::from_chain_spec(&*SEPOLIA).unwrap();
+        assert_eq!(sepolia.genesis_fork_version, [0x90, 0x00, 0x00, 0x69]);
+
+        // a custom genesis with the mainnet chain id is not mainnet
+        let custom = ChainSpecBuilder::mainnet().genesis(Default::default()).build();
+        assert_eq!(RelayChainConfig::from_chain_spec(&custom), None);
+    }
+}
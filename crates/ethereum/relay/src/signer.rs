@@ -0,0 +1,160 @@
+//! BLS signing of builder bids.
+
+use crate::RelayError;
+use alloy_primitives::{B256, U256};
+use alloy_rpc_types_beacon::{relay::BidTrace, BlsPublicKey, BlsSignature};
+use blst::min_pk::SecretKey;
+use sha2::{Digest, Sha256};
+
+/// Domain separation tag of the BLS signature scheme used by the consensus layer.
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Domain type of builder API messages.
+const DOMAIN_APPLICATION_BUILDER: [u8; 4] = [0x00, 0x00, 0x00, 0x01];
+
+/// Signs bids with the builder's BLS key.
+#[derive(Clone)]
+pub struct BuilderSigner {
+    /// The builder's secret key.
+    secret_key: SecretKey,
+    /// The builder's public key, as registered with the relays.
+    public_key: BlsPublicKey,
+}
+
+impl BuilderSigner {
+    /// Creates a signer from the 32 bytes of a BLS secret key.
+    pub fn from_bytes(secret_key: &[u8]) -> Result<Self, RelayError> {
+        let secret_key =
+            SecretKey::from_bytes(secret_key).map_err(|_| RelayError::InvalidSecretKey)?;
+        let public_key = BlsPublicKey::from(secret_key.sk_to_pk().to_bytes());
+        Ok(Self { secret_key, public_key })
+    }
+
+    /// Returns the builder's public key.
+    pub const fn public_key(&self) -> BlsPublicKey {
+        self.public_key
+    }
+
+    /// Signs the bid trace for the given builder domain, see [`compute_builder_domain`].
+    pub fn sign_bid(&self, bid: &BidTrace, domain: B256) -> BlsSignature {
+        let signing_root = hash_pair(bid_trace_root(bid), domain);
+        BlsSignature::from(self.secret_key.sign(signing_root.as_slice(), BLS_DST, &[]).to_bytes())
+    }
+}
+
+impl core::fmt::Debug for BuilderSigner {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BuilderSigner")
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Computes the domain builder bids are signed for.
+///
+/// Unlike consensus messages, builder messages are not bound to a fork or to the genesis validators
+/// root, so the domain only depends on the genesis fork version of the chain.
+pub fn compute_builder_domain(genesis_fork_version: [u8; 4]) -> B256 {
+    // hash tree root of `ForkData { current_version, genesis_validators_root: 0 }`
+    let mut version = B256::ZERO;
+    version[..4].copy_from_slice(&genesis_fork_version);
+    let fork_data_root = hash_pair(version, B256::ZERO);
+
+    let mut domain = B256::ZERO;
+    domain[..4].copy_from_slice(&DOMAIN_APPLICATION_BUILDER);
+    domain[4..].copy_from_slice(&fork_data_root[..28]);
+    domain
+}
+
+/// Computes the SSZ hash tree root of a [`BidTrace`].
+pub fn bid_trace_root(bid: &BidTrace) -> B256 {
+    let mut fee_recipient = B256::ZERO;
+    fee_recipient[..20].copy_from_slice(bid.proposer_fee_recipient.as_slice());
+
+    merkleize(&[
+        uint64_leaf(bid.slot),
+        bid.parent_hash,
+        bid.block_hash,
+        pubkey_root(&bid.builder_pubkey),
+        pubkey_root(&bid.proposer_pubkey),
+        fee_recipient,
+        uint64_leaf(bid.gas_limit),
+        uint64_leaf(bid.gas_used),
+        B256::from(bid.value.to_le_bytes::<{ U256::BYTES }>()),
+    ])
+}
+
+/// Returns the hash tree root of a 48 byte public key, which spans two chunks.
+fn pubkey_root(pubkey: &BlsPublicKey) -> B256 {
+    let mut second = B256::ZERO;
+    second[..16].copy_from_slice(&pubkey[32..]);
+    hash_pair(B256::from_slice(&pubkey[..32]), second)
+}
+
+/// Returns the leaf of a `uint64`.
+fn uint64_leaf(value: u64) -> B256 {
+    let mut leaf = B256::ZERO;
+    leaf[..8].copy_from_slice(&value.to_le_bytes());
+    leaf
+}
+
+/// Merkleizes the given leaves, padding them with zero leaves to the next power of two.
+fn merkleize(leaves: &[B256]) -> B256 {
+    let mut layer = leaves.to_vec();
+    layer.resize(leaves.len().next_power_of_two().max(1), B256::ZERO);
+    while layer.len() > 1 {
+        layer = layer.chunks(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+    }
+    layer[0]
+}
+
+/// Hashes two chunks into their parent.
+fn hash_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, Address};
+    use blst::{min_pk::Signature, BLST_ERROR};
+
+    #[test]
+    fn mainnet_builder_domain() {
+        assert_eq!(
+            compute_builder_domain([0; 4]),
+            b256!("0x00000001f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a9")
+        );
+    }
+
+    #[test]
+    fn signs_bid_trace() {
+        let signer = BuilderSigner::from_bytes(&[7; 32]).unwrap();
+        let bid = BidTrace {
+            slot: 1,
+            builder_pubkey: signer.public_key(),
+            proposer_fee_recipient: Address::repeat_byte(1),
+            gas_limit: 30_000_000,
+            gas_used: 21_000,
+            value: U256::from(1_000_000_000u64),
+            ..Default::default()
+        };
+        let domain = compute_builder_domain([0; 4]);
+        let signature = signer.sign_bid(&bid, domain);
+
+        let signature = Signature::from_bytes(signature.as_slice()).unwrap();
+        let public_key = signer.secret_key.sk_to_pk();
+        let signing_root = hash_pair(bid_trace_root(&bid), domain);
+        assert_eq!(
+            signature.verify(true, signing_root.as_slice(), BLS_DST, &[], &public_key, true),
+            BLST_ERROR::BLST_SUCCESS
+        );
+
+        // the signature commits to the bid
+        let other = BidTrace { slot: 2, ..bid };
+        assert_ne!(bid_trace_root(&bid), bid_trace_root(&other));
+    }
+}
@@ -0,0 +1,110 @@
+//! Types of the relay builder API.
+
+use crate::RelayError;
+use alloy_rpc_types_beacon::{
+    relay::{
+        BidTrace, SignedBidSubmissionV3, SignedBidSubmissionV4, SignedBidSubmissionV5,
+        SignedBidSubmissionV6, ValidatorRegistration,
+    },
+    BlsSignature,
+};
+use reth_chainspec::EthereumHardforks;
+use reth_ethereum_engine_primitives::EthBuiltPayload;
+use serde::{Deserialize, Serialize};
+
+/// A validator scheduled to propose a block, as returned by `GET /relay/v1/builder/validators`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposerDuty {
+    /// The slot the validator proposes in.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub slot: u64,
+    /// The index of the validator.
+    #[serde(with = "alloy_serde::displayfromstr")]
+    pub validator_index: u64,
+    /// The latest registration of the validator, with its fee recipient and gas limit.
+    pub entry: ValidatorRegistration,
+}
+
+/// A signed block submission, as accepted by `POST /relay/v1/builder/blocks`.
+///
+/// The variant depends on the fork active at the timestamp of the submitted block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SubmitBlockRequest {
+    /// Cancun submission.
+    V3(SignedBidSubmissionV3),
+    /// Prague submission.
+    V4(SignedBidSubmissionV4),
+    /// Osaka submission.
+    V5(SignedBidSubmissionV5),
+    /// Amsterdam submission.
+    V6(SignedBidSubmissionV6),
+}
+
+impl SubmitBlockRequest {
+    /// Creates the submission of the given payload for the signed bid.
+    pub fn new<ChainSpec: EthereumHardforks>(
+        chain_spec: &ChainSpec,
+        payload: EthBuiltPayload,
+        message: BidTrace,
+        signature: BlsSignature,
+    ) -> Result<Self, RelayError> {
+        let timestamp = payload.block().timestamp;
+        let request = if chain_spec.is_amsterdam_active_at_timestamp(timestamp) {
+            let envelope = payload.try_into_v6()?;
+            Self::V6(SignedBidSubmissionV6 {
+                message,
+                execution_payload: envelope.execution_payload,
+                blobs_bundle: envelope.blobs_bundle,
+                execution_requests: envelope
+                    .execution_requests
+                    .try_into()
+                    .map_err(|_| RelayError::InvalidExecutionRequests)?,
+                signature,
+            })
+        } else if chain_spec.is_osaka_active_at_timestamp(timestamp) {
+            let envelope = payload.try_into_v5()?;
+            Self::V5(SignedBidSubmissionV5 {
+                message,
+                execution_payload: envelope.execution_payload,
+                blobs_bundle: envelope.blobs_bundle,
+                execution_requests: envelope
+                    .execution_requests
+                    .try_into()
+                    .map_err(|_| RelayError::InvalidExecutionRequests)?,
+                signature,
+            })
+        } else if chain_spec.is_prague_active_at_timestamp(timestamp) {
+            let envelope = payload.try_into_v4()?;
+            Self::V4(SignedBidSubmissionV4 {
+                message,
+                execution_payload: envelope.envelope_inner.execution_payload,
+                blobs_bundle: envelope.envelope_inner.blobs_bundle,
+                execution_requests: envelope
+                    .execution_requests
+                    .try_into()
+                    .map_err(|_| RelayError::InvalidExecutionRequests)?,
+                signature,
+            })
+        } else {
+            let envelope = payload.try_into_v3()?;
+            Self::V3(SignedBidSubmissionV3 {
+                message,
+                execution_payload: envelope.execution_payload,
+                blobs_bundle: envelope.blobs_bundle,
+                signature,
+            })
+        };
+        Ok(request)
+    }
+
+    /// Returns the signed bid.
+    pub const fn message(&self) -> &BidTrace {
+        match self {
+            Self::V3(request) => &request.message,
+            Self::V4(request) => &request.message,
+            Self::V5(request) => &request.message,
+            Self::V6(request) => &request.message,
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Tests block submission against a mock relay.
+
+use alloy_consensus::Header;
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types_beacon::relay::BidTrace;
+use alloy_signer_local::PrivateKeySigner;
+use reth_chainspec::MAINNET;
+use reth_ethereum_engine_primitives::EthBuiltPayload;
+use reth_ethereum_payload_builder::{ProposerFeeRecipients, ProposerPayment};
+use reth_ethereum_primitives::{Block, BlockBody};
+use reth_ethereum_relay::{BuilderSigner, RelayChainConfig, RelayClient, RelaySubmitter};
+use reth_primitives_traits::RecoveredBlock;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{broadcast, mpsc},
+};
+use url::Url;
+
+/// Serves the given proposer duties and forwards submitted blocks to the returned channel.
+async fn spawn_mock_relay(duties: serde_json::Value) -> (Url, mpsc::UnboundedReceiver<Vec<u8>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+    let (submissions_tx, submissions_rx) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (mut stream, head, body) = read_request(stream).await;
+            let response_body = if head.starts_with("GET /relay/v1/builder/validators") {
+                duties.to_string()
+            } else if head.starts_with("POST /relay/v1/builder/blocks") {
+                submissions_tx.send(body).unwrap();
+                String::new()
+            } else {
+                panic!("unexpected request: {head}");
+            };
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{response_body}",
+                response_body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+
+    (url, submissions_rx)
+}
+
+/// Reads a single HTTP request, returning the stream with the head and body of the request.
+async fn read_request(mut stream: TcpStream) -> (TcpStream, String, Vec<u8>) {
+    let mut buf = Vec::new();
+    let head_end = loop {
+        let mut chunk = [0; 4096];
+        let read = stream.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..read]);
+        if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos + 4
+        }
+    };
+    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+    let content_length = head
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length").then(|| value.trim().parse().unwrap())
+        })
+        .unwrap_or(0);
+    while buf.len() < head_end + content_length {
+        let mut chunk = [0; 4096];
+        let read = stream.read(&mut chunk).await.unwrap();
+        buf.extend_from_slice(&chunk[..read]);
+    }
+    let body = buf[head_end..head_end + content_length].to_vec();
+    (stream, head, body)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn submits_signed_block_to_relay() {
+    let chain_config = RelayChainConfig {
+        genesis_time: 0,
+        genesis_fork_version: [0; 4],
+        genesis_validators_root: B256::ZERO,
+        seconds_per_slot: 12,
+    };
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let slot = chain_config.slot_at(now) + 1;
+    let timestamp = chain_config.timestamp_at(slot);
+
+    let proposer_pubkey = [2; 48];
+    let proposer_fee_recipient = Address::repeat_byte(3);
+    let duties = serde_json::json!([{
+        "slot": slot.to_string(),
+        "validator_index": "1",
+        "entry": {
+            "message": {
+                "fee_recipient": proposer_fee_recipient,
+                "gas_limit": "30000000",
+                "timestamp": "0",
+                "pubkey": alloy_primitives::FixedBytes::from(proposer_pubkey),
+            },
+            "signature": alloy_primitives::FixedBytes::<96>::ZERO,
+        }
+    }]);
+    let (url, mut submissions) = spawn_mock_relay(duties).await;
+
+    let fee_recipients = ProposerFeeRecipients::default();
+    let builder_signer = BuilderSigner::from_bytes(&[7; 32]).unwrap();
+    let submitter = RelaySubmitter::new(
+        MAINNET.clone(),
+        vec![RelayClient::new(url)],
+        builder_signer.clone(),
+        chain_config,
+        fee_recipients.clone(),
+    );
+    let (payloads_tx, payloads_rx) = broadcast::channel(16);
+    tokio::spawn(submitter.run(payloads_rx));
+
+    // wait for the submitter to learn about the proposer
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while fee_recipients.get(timestamp).is_none() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert_eq!(fee_recipients.get(timestamp), Some(proposer_fee_recipient));
+
+    // a payload paying the proposer in its last transaction
+    let payment = ProposerPayment::new(
+        PrivateKeySigner::from_bytes(&B256::repeat_byte(1)).unwrap(),
+        fee_recipients.clone(),
+    );
+    let value = U256::from(1_000_000_000u64);
+    let payment_tx = payment.sign_payment(1, 0, 7, proposer_fee_recipient, value).unwrap();
+    let (payment_tx, sender) = payment_tx.into_parts();
+    let block = RecoveredBlock::new_unhashed(
+        Block {
+            header: Header {
+                timestamp,
+                gas_limit: 30_000_000,
+                gas_used: 21_000,
+                ..Default::default()
+            },
+            body: BlockBody { transactions: vec![payment_tx], ..Default::default() },
+        },
+        vec![sender],
+    );
+    let block_hash = block.hash();
+    payloads_tx.send(Arc::new(EthBuiltPayload::new(Arc::new(block), value, None, None))).unwrap();
+
+    let submission =
+        tokio::time::timeout(Duration::from_secs(10), submissions.recv()).await.unwrap().unwrap();
+    let submission: serde_json::Value = serde_json::from_slice(&submission).unwrap();
+    let message: BidTrace = serde_json::from_value(submission["message"].clone()).unwrap();
+    assert_eq!(message.slot, slot);
+    assert_eq!(message.block_hash, block_hash);
+    assert_eq!(message.builder_pubkey, builder_signer.public_key());
+    assert_eq!(message.proposer_pubkey.0, proposer_pubkey);
+    assert_eq!(message.proposer_fee_recipient, proposer_fee_recipient);
+    assert_eq!(message.value, value);
+    assert!(submission["execution_payload"].is_object());
+}
@@ -33,6 +33,8 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 pub struct NoopNetwork<Net = EthNetworkPrimitives> {
     chain_id: u64,
     peers_handle: PeersHandle,
+    #[cfg(feature = "test-utils")]
+    network_faults: crate::test_utils::NetworkFaults,
     cell_custody: CellCustody,
     _marker: PhantomData<Net>,
 }
@@ -45,6 +47,8 @@ impl<Net> NoopNetwork<Net> {
         Self {
             chain_id: 1, // mainnet
             peers_handle: PeersHandle::new(tx),
+            #[cfg(feature = "test-utils")]
+            network_faults: Default::default(),
             cell_custody: CellCustody::default(),
             _marker: PhantomData,
         }
@@ -236,3 +240,13 @@ where
         &self.peers_handle
     }
 }
+
+#[cfg(feature = "test-utils")]
+impl<Net> crate::test_utils::NetworkFaultsProvider for NoopNetwork<Net>
+where
+    Net: NetworkPrimitives,
+{
+    fn network_faults(&self) -> &crate::test_utils::NetworkFaults {
+        &self.network_faults
+    }
+}
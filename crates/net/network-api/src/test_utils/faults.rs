@@ -0,0 +1,252 @@
+//! Fault injection for the links between network peers, for integration testing.
+//!
+//! The network applies the configured [`LinkConditions`] to the messages it _receives_ from a
+//! peer, so conditions set on one node shape the traffic of the peer towards that node. To degrade
+//! a link in both directions, the conditions need to be set on both nodes.
+
+use parking_lot::RwLock;
+use reth_network_peers::PeerId;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// Provides access to the [`NetworkFaults`] of the network.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait NetworkFaultsProvider {
+    /// Returns the [`NetworkFaults`] that can be cloned and shared.
+    ///
+    /// The [`NetworkFaults`] can be used to degrade the links to the network's peers at runtime.
+    fn network_faults(&self) -> &NetworkFaults;
+}
+
+/// Conditions of the link to a peer.
+///
+/// The default conditions describe a perfect link.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LinkConditions {
+    /// Fixed delay of every message.
+    pub latency: Duration,
+    /// Upper bound of the random delay added on top of the latency.
+    pub jitter: Duration,
+    /// Maximum throughput of the link in bytes per second, unlimited if `None`.
+    pub bandwidth: Option<u64>,
+    /// Probability in `[0, 1]` that a message is dropped.
+    pub drop_probability: f64,
+    /// Probability in `[0, 1]` that a message is delivered twice.
+    pub duplicate_probability: f64,
+    /// Whether all messages are dropped.
+    pub partitioned: bool,
+}
+
+impl LinkConditions {
+    /// Returns the conditions of a perfect link.
+    pub const fn perfect() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            jitter: Duration::ZERO,
+            bandwidth: None,
+            drop_probability: 0.0,
+            duplicate_probability: 0.0,
+            partitioned: false,
+        }
+    }
+
+    /// Sets the fixed delay of every message.
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Sets the upper bound of the random delay added on top of the latency.
+    pub const fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Caps the throughput of the link to the given number of bytes per second.
+    pub const fn with_bandwidth(mut self, bytes_per_second: u64) -> Self {
+        self.bandwidth = Some(bytes_per_second);
+        self
+    }
+
+    /// Sets the probability that a message is dropped.
+    ///
+    /// # Panics
+    ///
+    /// If the probability is `NaN` or not in `[0, 1]`.
+    pub fn with_drop_probability(mut self, probability: f64) -> Self {
+        assert!((0.0..=1.0).contains(&probability), "invalid drop probability: {probability}");
+        self.drop_probability = probability;
+        self
+    }
+
+    /// Sets the probability that a message is delivered twice.
+    ///
+    /// # Panics
+    ///
+    /// If the probability is `NaN` or not in `[0, 1]`.
+    pub fn with_duplicate_probability(mut self, probability: f64) -> Self {
+        assert!((0.0..=1.0).contains(&probability), "invalid duplicate probability: {probability}");
+        self.duplicate_probability = probability;
+        self
+    }
+
+    /// Sets whether all messages are dropped.
+    pub const fn with_partitioned(mut self, partitioned: bool) -> Self {
+        self.partitioned = partitioned;
+        self
+    }
+
+    /// Returns `true` if the conditions describe a perfect link.
+    pub fn is_perfect(&self) -> bool {
+        *self == Self::perfect()
+    }
+
+    /// Returns the time it takes to transfer a message of the given size over the link.
+    pub fn transfer_time(&self, size: usize) -> Duration {
+        match self.bandwidth {
+            Some(0) => Duration::MAX,
+            Some(bandwidth) => Duration::from_secs_f64(size as f64 / bandwidth as f64),
+            None => Duration::ZERO,
+        }
+    }
+}
+
+/// A shareable handle to the [`LinkConditions`] of the links to the network's peers, that can be
+/// changed at runtime.
+///
+/// Changes apply to the messages received after the change, including those of already
+/// established sessions.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkFaults {
+    inner: Arc<NetworkFaultsInner>,
+}
+
+#[derive(Debug, Default)]
+struct NetworkFaultsInner {
+    /// Whether any link is not perfect, so that sessions can skip the lookup otherwise.
+    enabled: AtomicBool,
+    links: RwLock<Links>,
+}
+
+#[derive(Debug, Default)]
+struct Links {
+    /// Conditions of the links without peer specific conditions.
+    default: LinkConditions,
+    /// Peer specific conditions.
+    peers: HashMap<PeerId, LinkConditions>,
+}
+
+impl Links {
+    fn is_perfect(&self) -> bool {
+        self.default.is_perfect() && self.peers.values().all(LinkConditions::is_perfect)
+    }
+}
+
+// === impl NetworkFaults ===
+
+impl NetworkFaults {
+    /// Creates a new handle where all links are perfect.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if any link is not perfect.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Returns the conditions of the link to the given peer.
+    pub fn link(&self, peer_id: &PeerId) -> LinkConditions {
+        let links = self.inner.links.read();
+        links.peers.get(peer_id).copied().unwrap_or(links.default)
+    }
+
+    /// Sets the conditions of the links to all peers without peer specific conditions.
+    pub fn set_default(&self, conditions: LinkConditions) {
+        self.update(|links| links.default = conditions);
+    }
+
+    /// Sets the conditions of the link to the given peer.
+    pub fn set_link(&self, peer_id: PeerId, conditions: LinkConditions) {
+        self.update(|links| {
+            links.peers.insert(peer_id, conditions);
+        });
+    }
+
+    /// Modifies the conditions of the link to the given peer.
+    pub fn update_link(&self, peer_id: PeerId, f: impl FnOnce(&mut LinkConditions)) {
+        self.update(|links| {
+            let default = links.default;
+            f(links.peers.entry(peer_id).or_insert(default));
+        });
+    }
+
+    /// Removes the peer specific conditions of the link to the given peer.
+    pub fn clear_link(&self, peer_id: &PeerId) {
+        self.update(|links| {
+            links.peers.remove(peer_id);
+        });
+    }
+
+    /// Drops all messages of the given peer until the partition is healed.
+    pub fn partition(&self, peer_id: PeerId) {
+        self.update_link(peer_id, |link| link.partitioned = true);
+    }
+
+    /// Heals the partition from the given peer, keeping the other conditions of the link.
+    pub fn heal(&self, peer_id: PeerId) {
+        self.update_link(peer_id, |link| link.partitioned = false);
+    }
+
+    /// Restores perfect links to all peers.
+    pub fn clear(&self) {
+        self.update(|links| *links = Links::default());
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Links)) {
+        let mut links = self.inner.links.write();
+        f(&mut links);
+        self.inner.enabled.store(!links.is_perfect(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_conditions() {
+        let faults = NetworkFaults::new();
+        let peer = PeerId::random();
+        assert!(!faults.is_enabled());
+
+        faults.set_default(LinkConditions::perfect().with_latency(Duration::from_millis(50)));
+        assert!(faults.is_enabled());
+        assert_eq!(faults.link(&peer).latency, Duration::from_millis(50));
+
+        faults.partition(peer);
+        let link = faults.link(&peer);
+        assert!(link.partitioned);
+        assert_eq!(link.latency, Duration::from_millis(50));
+
+        faults.set_default(LinkConditions::perfect());
+        assert!(faults.is_enabled());
+
+        faults.heal(peer);
+        assert!(!faults.is_enabled());
+        assert!(faults.link(&peer).is_perfect());
+    }
+
+    #[test]
+    fn transfer_time() {
+        let link = LinkConditions::perfect().with_bandwidth(1_000);
+        assert_eq!(link.transfer_time(500), Duration::from_millis(500));
+        assert_eq!(LinkConditions::perfect().transfer_time(500), Duration::ZERO);
+    }
+}
@@ -1,5 +1,9 @@
 //! API for integration testing network components.
 
+#[cfg(feature = "test-utils")]
+pub mod faults;
 pub mod peers_manager;
 
+#[cfg(feature = "test-utils")]
+pub use faults::{LinkConditions, NetworkFaults, NetworkFaultsProvider};
 pub use peers_manager::{PeerCommand, PeersHandle, PeersHandleProvider};
@@ -3,6 +3,7 @@
 use crate::{
     error::NetworkError,
     import::{BlockImport, ProofOfStakeBlockImport},
+    proxy::ProxyConfig,
     transactions::TransactionsManagerConfig,
     NetworkHandle, NetworkManager,
 };
@@ -102,6 +103,8 @@ pub struct NetworkConfig<C, N: NetworkPrimitives = EthNetworkPrimitives> {
     /// List of block number-hash pairs to check for required blocks.
     /// If non-empty, peers that don't have these blocks will be filtered out.
     pub required_block_hashes: Vec<BlockNumHash>,
+    /// Proxy to dial outbound `RLPx` connections through.
+    pub proxy: Option<ProxyConfig>,
 }
 
 // === impl NetworkConfig ===
@@ -228,6 +231,8 @@ pub struct NetworkConfigBuilder<N: NetworkPrimitives = EthNetworkPrimitives> {
     required_block_hashes: Vec<BlockNumHash>,
     /// Optional network id
     network_id: Option<u64>,
+    /// Proxy to dial outbound `RLPx` connections through.
+    proxy: Option<ProxyConfig>,
 }
 
 impl NetworkConfigBuilder<EthNetworkPrimitives> {
@@ -271,6 +276,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             eth_max_message_size: MAX_MESSAGE_SIZE,
             required_block_hashes: Vec::new(),
             network_id: None,
+            proxy: None,
         }
     }
 
@@ -612,6 +618,21 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
         self
     }
 
+    /// Dials outbound `RLPx` connections through the given proxy.
+    ///
+    /// Discovery and incoming connections are not proxied, so discovery should be disabled if the
+    /// node is not reachable from the outside.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Dials outbound `RLPx` connections through the given proxy, if any.
+    pub fn proxy_opt(mut self, proxy: Option<ProxyConfig>) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
     /// Consumes the type and creates the actual [`NetworkConfig`]
     /// for the given client type that can interact with the chain.
     ///
@@ -647,6 +668,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             eth_max_message_size,
             required_block_hashes,
             network_id,
+            proxy,
         } = self;
 
         let head = head.unwrap_or_else(|| Head {
@@ -727,6 +749,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
             handshake,
             eth_max_message_size,
             required_block_hashes,
+            proxy,
         }
     }
 }
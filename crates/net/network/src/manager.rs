@@ -262,6 +262,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             handshake,
             eth_max_message_size,
             required_block_hashes,
+            proxy,
         } = config;
 
         let peers_manager = PeersManager::new(peers_config);
@@ -306,6 +307,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         let discv5 = discovery.discv5();
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
+        #[cfg(feature = "test-utils")]
+        let network_faults = reth_network_api::test_utils::NetworkFaults::default();
 
         let sessions = SessionManager::new(
             secret_key,
@@ -318,6 +321,9 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             handshake,
             eth_max_message_size,
             network_mode.is_stake(),
+            #[cfg(feature = "test-utils")]
+            network_faults.clone(),
+            proxy,
         );
 
         let state = NetworkState::new(
@@ -340,6 +346,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             secret_key,
             local_peer_id,
             peers_handle,
+            #[cfg(feature = "test-utils")]
+            network_faults,
             network_mode,
             Arc::new(AtomicU64::new(chain_id)),
             tx_gossip_disabled,
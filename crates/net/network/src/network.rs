@@ -59,6 +59,7 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
         secret_key: SecretKey,
         local_peer_id: PeerId,
         peers: PeersHandle,
+        #[cfg(feature = "test-utils")] network_faults: reth_network_api::test_utils::NetworkFaults,
         network_mode: NetworkMode,
         chain_id: Arc<AtomicU64>,
         tx_gossip_disabled: bool,
@@ -74,6 +75,8 @@ impl<N: NetworkPrimitives> NetworkHandle<N> {
             secret_key,
             local_peer_id,
             peers,
+            #[cfg(feature = "test-utils")]
+            network_faults,
             network_mode,
             is_syncing: Arc::new(AtomicBool::new(false)),
             initial_sync_done: Arc::new(AtomicBool::new(false)),
@@ -428,6 +431,15 @@ impl<N: NetworkPrimitives> PeersHandleProvider for NetworkHandle<N> {
     }
 }
 
+#[cfg(feature = "test-utils")]
+impl<N: NetworkPrimitives> reth_network_api::test_utils::NetworkFaultsProvider
+    for NetworkHandle<N>
+{
+    fn network_faults(&self) -> &reth_network_api::test_utils::NetworkFaults {
+        &self.inner.network_faults
+    }
+}
+
 impl<N: NetworkPrimitives> NetworkInfo for NetworkHandle<N> {
     fn local_addr(&self) -> SocketAddr {
         *self.inner.listener_address.lock()
@@ -514,6 +526,9 @@ struct NetworkInner<N: NetworkPrimitives = EthNetworkPrimitives> {
     local_peer_id: PeerId,
     /// Access to all the nodes.
     peers: PeersHandle,
+    /// Faults injected into the links to the peers.
+    #[cfg(feature = "test-utils")]
+    network_faults: reth_network_api::test_utils::NetworkFaults,
     /// The mode of the network
     network_mode: NetworkMode,
     /// Represents if the network is currently syncing.
@@ -190,9 +190,44 @@ pub(crate) struct ActiveSession<N: NetworkPrimitives> {
     /// The last latest block number we sent in a range update
     /// Used to avoid sending unnecessary updates when block height hasn't changed significantly
     pub(crate) last_sent_latest_block: Option<u64>,
+    /// Applies the injected faults of the link to the messages received from the peer.
+    #[cfg(feature = "test-utils")]
+    pub(crate) faults: crate::session::faults::SessionFaults<N>,
 }
 
 impl<N: NetworkPrimitives> ActiveSession<N> {
+    /// Returns the next message received from the peer that was held back by injected faults.
+    #[cfg(feature = "test-utils")]
+    fn poll_held_back_message(&mut self, cx: &mut Context<'_>) -> Poll<EthMessage<N>> {
+        self.faults.poll_next(cx)
+    }
+
+    /// Returns the next message received from the peer that was held back by injected faults.
+    #[cfg(not(feature = "test-utils"))]
+    const fn poll_held_back_message(&mut self, _cx: &mut Context<'_>) -> Poll<EthMessage<N>> {
+        Poll::Pending
+    }
+
+    /// Applies the injected faults to a message received from the peer.
+    ///
+    /// Returns the message if it can be handled right away.
+    #[cfg(feature = "test-utils")]
+    fn apply_faults(&mut self, msg: EthMessage<N>) -> Option<EthMessage<N>> {
+        if self.faults.is_active() {
+            self.faults.on_message(msg);
+            return None
+        }
+        Some(msg)
+    }
+
+    /// Applies the injected faults to a message received from the peer.
+    ///
+    /// Returns the message if it can be handled right away.
+    #[cfg(not(feature = "test-utils"))]
+    const fn apply_faults(&mut self, msg: EthMessage<N>) -> Option<EthMessage<N>> {
+        Some(msg)
+    }
+
     /// Returns `true` if the session is currently in the process of disconnecting
     fn is_disconnecting(&self) -> bool {
         self.conn.inner().is_disconnecting()
@@ -794,47 +829,53 @@ impl<N: NetworkPrimitives> Future for ActiveSession<N> {
                     break 'receive
                 }
 
-                match this.conn.poll_next_unpin(cx) {
-                    Poll::Pending => break,
-                    Poll::Ready(None) => {
-                        if this.is_disconnecting() {
-                            break
-                        }
-                        debug!(target: "net::session", remote_peer_id=?this.remote_peer_id, "eth stream completed");
-                        return this.emit_disconnect(cx)
-                    }
-                    Poll::Ready(Some(res)) => {
-                        match res {
-                            Ok(msg) => {
-                                trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
-                                // decode and handle message
-                                match this.on_incoming_message(msg) {
-                                    OnIncomingMessageOutcome::Ok => {
-                                        // handled successfully
-                                        progress = true;
-                                    }
-                                    OnIncomingMessageOutcome::BadMessage { error, message } => {
-                                        debug!(target: "net::session", %error, msg=?message, remote_peer_id=?this.remote_peer_id, "received invalid protocol message");
-                                        this.on_bad_message();
-                                        return this
-                                            .try_disconnect(DisconnectReason::ProtocolBreach, cx)
-                                    }
-                                    OnIncomingMessageOutcome::NoCapacity(msg) => {
-                                        // failed to send due to lack of capacity
-                                        this.pending_message_to_session = Some(msg);
-                                    }
-                                }
+                // messages held back by injected faults are handled before new ones are read
+                let msg = if let Poll::Ready(msg) = this.poll_held_back_message(cx) {
+                    msg
+                } else {
+                    match this.conn.poll_next_unpin(cx) {
+                        Poll::Pending => break,
+                        Poll::Ready(None) => {
+                            if this.is_disconnecting() {
+                                break
                             }
-                            Err(err) => {
-                                debug!(target: "net::session", %err, remote_peer_id=?this.remote_peer_id, "failed to receive message");
-                                if err.is_protocol_breach() {
-                                    this.on_bad_message();
-                                    return this.try_disconnect(DisconnectReason::ProtocolBreach, cx)
-                                }
-                                return this.close_on_error(err, cx)
+                            debug!(target: "net::session", remote_peer_id=?this.remote_peer_id, "eth stream completed");
+                            return this.emit_disconnect(cx)
+                        }
+                        Poll::Ready(Some(Ok(msg))) => {
+                            trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                            let Some(msg) = this.apply_faults(msg) else {
+                                progress = true;
+                                continue
+                            };
+                            msg
+                        }
+                        Poll::Ready(Some(Err(err))) => {
+                            debug!(target: "net::session", %err, remote_peer_id=?this.remote_peer_id, "failed to receive message");
+                            if err.is_protocol_breach() {
+                                this.on_bad_message();
+                                return this.try_disconnect(DisconnectReason::ProtocolBreach, cx)
                             }
+                            return this.close_on_error(err, cx)
                         }
                     }
+                };
+
+                // decode and handle message
+                match this.on_incoming_message(msg) {
+                    OnIncomingMessageOutcome::Ok => {
+                        // handled successfully
+                        progress = true;
+                    }
+                    OnIncomingMessageOutcome::BadMessage { error, message } => {
+                        debug!(target: "net::session", %error, msg=?message, remote_peer_id=?this.remote_peer_id, "received invalid protocol message");
+                        this.on_bad_message();
+                        return this.try_disconnect(DisconnectReason::ProtocolBreach, cx)
+                    }
+                    OnIncomingMessageOutcome::NoCapacity(msg) => {
+                        // failed to send due to lack of capacity
+                        this.pending_message_to_session = Some(msg);
+                    }
                 }
             }
 
@@ -1289,6 +1330,11 @@ mod tests {
                         ),
                         range_update_interval: None,
                         last_sent_latest_block: None,
+                        #[cfg(feature = "test-utils")]
+                        faults: crate::session::faults::SessionFaults::new(
+                            peer_id,
+                            Default::default(),
+                        ),
                     }
                 }
                 ev => {
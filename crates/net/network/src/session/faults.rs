@@ -0,0 +1,181 @@
+//! Fault injection for the messages an active session receives from its peer.
+
+use alloy_rlp::Encodable;
+use rand::Rng;
+use reth_eth_wire::{EthMessage, NetworkPrimitives};
+use reth_network_api::test_utils::NetworkFaults;
+use reth_network_peers::PeerId;
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Sleep};
+use tracing::trace;
+
+/// Maximum number of messages held back per session.
+///
+/// Messages received while the queue is full are dropped, like on a congested link.
+const MAX_DELAYED_MESSAGES: usize = 4096;
+
+/// Applies the [`LinkConditions`](reth_network_api::test_utils::LinkConditions) of the link to the
+/// session's peer to the messages received from it.
+///
+/// Messages are released in the order they were received.
+pub(crate) struct SessionFaults<N: NetworkPrimitives> {
+    /// The peer messages are received from.
+    peer_id: PeerId,
+    /// The conditions of the network's links.
+    faults: NetworkFaults,
+    /// Messages that are held back, with the instant they're released at.
+    delayed: VecDeque<(Instant, EthMessage<N>)>,
+    /// Wakes the session once the next message is released, created on first use.
+    timer: Option<Pin<Box<Sleep>>>,
+}
+
+impl<N: NetworkPrimitives> SessionFaults<N> {
+    /// Creates a new instance for the session with the given peer.
+    pub(crate) const fn new(peer_id: PeerId, faults: NetworkFaults) -> Self {
+        Self { peer_id, faults, delayed: VecDeque::new(), timer: None }
+    }
+
+    /// Returns `true` if received messages must go through [`Self::on_message`], because the link
+    /// is degraded or messages are still held back.
+    pub(crate) fn is_active(&self) -> bool {
+        self.faults.is_enabled() || !self.delayed.is_empty()
+    }
+
+    /// Applies the link conditions to a message received from the peer.
+    pub(crate) fn on_message(&mut self, msg: EthMessage<N>) {
+        let link = self.faults.link(&self.peer_id);
+        let mut rng = rand::rng();
+
+        if link.partitioned || link.bandwidth == Some(0) || happens(&mut rng, link.drop_probability)
+        {
+            trace!(target: "net::session::faults", msg_id=?msg.message_id(), remote_peer_id=?self.peer_id, "dropping message");
+            return
+        }
+
+        let jitter = Duration::from_nanos(rng.random_range(0..=link.jitter.as_nanos() as u64));
+        let mut release = Instant::now() + link.latency + jitter;
+        // messages can't overtake each other and share the bandwidth of the link
+        if let Some((last, _)) = self.delayed.back() {
+            release = release.max(*last);
+        }
+        release += link.transfer_time(msg.length());
+
+        if happens(&mut rng, link.duplicate_probability) {
+            trace!(target: "net::session::faults", msg_id=?msg.message_id(), remote_peer_id=?self.peer_id, "duplicating message");
+            self.hold_back(release, msg.clone());
+        }
+        self.hold_back(release, msg);
+    }
+
+    /// Queues the message for release at the given instant, unless the queue is full.
+    fn hold_back(&mut self, release: Instant, msg: EthMessage<N>) {
+        if self.delayed.len() >= MAX_DELAYED_MESSAGES {
+            trace!(target: "net::session::faults", msg_id=?msg.message_id(), remote_peer_id=?self.peer_id, "dropping message, too many messages held back");
+            return
+        }
+        self.delayed.push_back((release, msg));
+    }
+
+    /// Returns the next message that is due for release.
+    pub(crate) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<EthMessage<N>> {
+        let Some((release, _)) = self.delayed.front() else { return Poll::Pending };
+
+        if *release > Instant::now() {
+            let timer =
+                self.timer.get_or_insert_with(|| Box::pin(tokio::time::sleep_until(*release)));
+            timer.as_mut().reset(*release);
+            if timer.as_mut().poll(cx).is_pending() {
+                return Poll::Pending
+            }
+        }
+
+        Poll::Ready(self.delayed.pop_front().expect("front exists").1)
+    }
+}
+
+/// Returns `true` with the given probability.
+///
+/// Unlike [`Rng::random_bool`] this doesn't panic if the probability is `NaN` or out of range,
+/// since the fields of the link conditions can be set directly.
+fn happens(rng: &mut impl Rng, probability: f64) -> bool {
+    rng.random::<f64>() < probability
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_eth_wire::EthNetworkPrimitives;
+    use reth_eth_wire_types::NewPooledTransactionHashes66;
+    use reth_network_api::test_utils::LinkConditions;
+    use std::future::poll_fn;
+
+    fn message() -> EthMessage<EthNetworkPrimitives> {
+        EthMessage::NewPooledTransactionHashes66(NewPooledTransactionHashes66::default())
+    }
+
+    #[tokio::test]
+    async fn delays_messages_in_order() {
+        let peer_id = PeerId::random();
+        let network_faults = NetworkFaults::new();
+        network_faults
+            .set_link(peer_id, LinkConditions::perfect().with_latency(Duration::from_millis(100)));
+        let mut faults = SessionFaults::new(peer_id, network_faults.clone());
+        assert!(faults.is_active());
+
+        let start = Instant::now();
+        faults.on_message(message());
+        network_faults.clear();
+        faults.on_message(message());
+        assert!(faults.is_active());
+
+        assert!(poll_fn(|cx| Poll::Ready(faults.poll_next(cx))).await.is_pending());
+        poll_fn(|cx| faults.poll_next(cx)).await;
+        assert!(start.elapsed() >= Duration::from_millis(100));
+        // the second message is not delayed but can't overtake the first
+        assert!(poll_fn(|cx| Poll::Ready(faults.poll_next(cx))).await.is_ready());
+        assert!(!faults.is_active());
+    }
+
+    #[test]
+    fn drops_and_duplicates_messages() {
+        let peer_id = PeerId::random();
+        let network_faults = NetworkFaults::new();
+        let mut faults = SessionFaults::new(peer_id, network_faults.clone());
+
+        network_faults.partition(peer_id);
+        faults.on_message(message());
+        assert!(faults.delayed.is_empty());
+
+        network_faults.set_link(peer_id, LinkConditions::perfect().with_duplicate_probability(1.0));
+        faults.on_message(message());
+        assert_eq!(faults.delayed.len(), 2);
+
+        // out of range probabilities set on the fields are tolerated
+        network_faults.set_link(
+            peer_id,
+            LinkConditions { drop_probability: f64::NAN, ..LinkConditions::perfect() },
+        );
+        faults.on_message(message());
+        assert_eq!(faults.delayed.len(), 3);
+    }
+
+    #[test]
+    fn bounds_held_back_messages() {
+        let peer_id = PeerId::random();
+        let network_faults = NetworkFaults::new();
+        network_faults
+            .set_link(peer_id, LinkConditions::perfect().with_latency(Duration::from_secs(60)));
+        let mut faults = SessionFaults::new(peer_id, network_faults);
+
+        for _ in 0..MAX_DELAYED_MESSAGES + 1 {
+            faults.on_message(message());
+        }
+        assert_eq!(faults.delayed.len(), MAX_DELAYED_MESSAGES);
+    }
+}
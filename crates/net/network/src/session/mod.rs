@@ -3,6 +3,8 @@
 mod active;
 mod conn;
 mod counter;
+#[cfg(feature = "test-utils")]
+mod faults;
 mod handle;
 mod types;
 pub use types::BlockRangeInfo;
@@ -11,6 +13,7 @@ use crate::{
     message::PeerMessage,
     metrics::SessionManagerMetrics,
     protocol::{IntoRlpxSubProtocol, OnNotSupported, RlpxSubProtocolHandlers, RlpxSubProtocols},
+    proxy::ProxyConfig,
     session::active::ActiveSession,
 };
 use active::QueuedOutgoingMessages;
@@ -27,7 +30,7 @@ use reth_ethereum_forks::{ForkFilter, ForkId, ForkTransition, Head};
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_api::{PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
-use reth_network_types::SessionsConfig;
+use reth_network_types::{PeerKind, SessionsConfig};
 use reth_tasks::Runtime;
 use rustc_hash::FxHashMap;
 use secp256k1::SecretKey;
@@ -126,6 +129,11 @@ pub struct SessionManager<N: NetworkPrimitives> {
     /// When true, block announcement messages (`NewBlock`, `NewBlockHashes`) are rejected before
     /// RLP decoding on new sessions to avoid memory amplification.
     reject_block_announcements: bool,
+    /// Faults injected into the links of active sessions.
+    #[cfg(feature = "test-utils")]
+    network_faults: reth_network_api::test_utils::NetworkFaults,
+    /// Proxy to dial outbound connections through.
+    proxy: Option<Arc<ProxyConfig>>,
 }
 
 // === impl SessionManager ===
@@ -144,6 +152,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
         handshake: Arc<dyn EthRlpxHandshake>,
         eth_max_message_size: usize,
         reject_block_announcements: bool,
+        #[cfg(feature = "test-utils")] network_faults: reth_network_api::test_utils::NetworkFaults,
+        proxy: Option<ProxyConfig>,
     ) -> Self {
         let (pending_sessions_tx, pending_sessions_rx) = mpsc::channel(config.session_event_buffer);
         let (active_session_tx, active_session_rx) = mpsc::channel(config.session_event_buffer);
@@ -181,6 +191,9 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             eth_max_message_size,
             local_range_info,
             reject_block_announcements,
+            #[cfg(feature = "test-utils")]
+            network_faults,
+            proxy: proxy.map(Arc::new),
         }
     }
 
@@ -315,7 +328,15 @@ impl<N: NetworkPrimitives> SessionManager<N> {
     }
 
     /// Starts a new pending session from the local node to the given remote node.
-    pub fn dial_outbound(&mut self, remote_addr: SocketAddr, remote_peer_id: PeerId) {
+    ///
+    /// If a proxy is configured, the connection is dialed through it unless the proxy is bypassed
+    /// for the peer.
+    pub fn dial_outbound(
+        &mut self,
+        remote_addr: SocketAddr,
+        remote_peer_id: PeerId,
+        peer_kind: PeerKind,
+    ) {
         // The error can be dropped because no dial will be made if it would exceed the limit
         if self.counter.ensure_pending_outbound().is_ok() {
             let session_id = self.next_id();
@@ -326,6 +347,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
+            let proxy =
+                self.proxy.clone().filter(|proxy| !proxy.is_bypassed(&remote_peer_id, peer_kind));
             self.spawn(pending_session_with_timeout(
                 self.pending_session_timeout,
                 session_id,
@@ -340,6 +363,7 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     session_id,
                     remote_addr,
                     remote_peer_id,
+                    proxy,
                     secret_key,
                     hello_message,
                     status,
@@ -602,6 +626,8 @@ impl<N: NetworkPrimitives> SessionManager<N> {
                     local_range_info: self.local_range_info.clone(),
                     range_update_interval,
                     last_sent_latest_block: None,
+                    #[cfg(feature = "test-utils")]
+                    faults: faults::SessionFaults::new(peer_id, self.network_faults.clone()),
                 };
 
                 self.spawn(session);
@@ -948,13 +974,21 @@ async fn start_pending_outbound_session<N: NetworkPrimitives>(
     session_id: SessionId,
     remote_addr: SocketAddr,
     remote_peer_id: PeerId,
+    proxy: Option<Arc<ProxyConfig>>,
     secret_key: SecretKey,
     hello: HelloMessageWithProtocols,
     status: UnifiedStatus,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
 ) {
-    let stream = match TcpStream::connect(remote_addr).await {
+    let stream = match proxy {
+        Some(proxy) => {
+            trace!(target: "net::session", %proxy, "dialing through proxy");
+            proxy.connect(remote_addr).await
+        }
+        None => TcpStream::connect(remote_addr).await,
+    };
+    let stream = match stream {
         Ok(stream) => {
             if let Err(err) = stream.set_nodelay(true) {
                 tracing::warn!(target: "net::session", "set nodelay failed: {:?}", err);
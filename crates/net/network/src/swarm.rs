@@ -113,7 +113,9 @@ impl<N: NetworkPrimitives> Swarm<N> {
 impl<N: NetworkPrimitives> Swarm<N> {
     /// Triggers a new outgoing connection to the given node
     pub(crate) fn dial_outbound(&mut self, remote_addr: SocketAddr, remote_id: PeerId) {
-        self.sessions.dial_outbound(remote_addr, remote_id)
+        let peer_kind =
+            self.peers().peer_by_id(remote_id).map(|(_, kind)| kind).unwrap_or_default();
+        self.sessions.dial_outbound(remote_addr, remote_id, peer_kind)
     }
 
     /// Handles a polled [`SessionEvent`]
@@ -1,6 +1,7 @@
 //! Common helpers for network testing.
 
 mod init;
+mod proxy;
 mod testnet;
 pub mod transactions;
 
@@ -8,5 +9,6 @@ pub use init::{
     enr_to_peer_id, unused_port, unused_tcp_addr, unused_tcp_and_udp_port, unused_tcp_udp,
     unused_udp_addr, unused_udp_port,
 };
+pub use proxy::TestProxy;
 pub use testnet::{NetworkEventStream, Peer, PeerConfig, PeerHandle, Testnet, TestnetHandle};
 pub use transactions::{buffer_hash_to_tx_fetcher, new_mock_session, new_tx_manager};
@@ -0,0 +1,204 @@
+//! A local proxy for testing outbound connections through a proxy.
+
+use crate::proxy::{ProxyConfig, ProxyProtocol};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use std::{
+    io,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+/// A minimal `SOCKS5` or HTTP `CONNECT` proxy, listening on a local port.
+///
+/// The proxy is shut down when dropped.
+#[derive(Debug)]
+pub struct TestProxy {
+    protocol: ProxyProtocol,
+    addr: SocketAddr,
+    connections: Arc<AtomicUsize>,
+    task: JoinHandle<()>,
+}
+
+impl TestProxy {
+    /// Spawns a proxy that doesn't require authentication.
+    pub async fn spawn(protocol: ProxyProtocol) -> Self {
+        Self::spawn_inner(protocol, None).await
+    }
+
+    /// Spawns a proxy that requires the given credentials.
+    pub async fn spawn_with_credentials(
+        protocol: ProxyProtocol,
+        username: &str,
+        password: &str,
+    ) -> Self {
+        Self::spawn_inner(protocol, Some((username.to_string(), password.to_string()))).await
+    }
+
+    async fn spawn_inner(protocol: ProxyProtocol, credentials: Option<(String, String)>) -> Self {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let credentials = Arc::new(credentials);
+
+        let counter = Arc::clone(&connections);
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let counter = Arc::clone(&counter);
+                let credentials = Arc::clone(&credentials);
+                tokio::spawn(async move {
+                    let credentials = (*credentials).as_ref();
+                    let tunnel = match protocol {
+                        ProxyProtocol::Socks5 => serve_socks5(stream, credentials, &counter).await,
+                        ProxyProtocol::HttpConnect => {
+                            serve_http_connect(stream, credentials, &counter).await
+                        }
+                    };
+                    if let Ok(Some((mut client, mut target))) = tunnel {
+                        let _ = tokio::io::copy_bidirectional(&mut client, &mut target).await;
+                    }
+                });
+            }
+        });
+
+        Self { protocol, addr, connections, task }
+    }
+
+    /// Returns the address the proxy listens on.
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns a [`ProxyConfig`] that dials through this proxy, without credentials.
+    pub fn config(&self) -> ProxyConfig {
+        ProxyConfig::new(self.protocol, self.addr.to_string())
+    }
+
+    /// Returns the number of connections that were tunneled through the proxy.
+    pub fn num_connections(&self) -> usize {
+        self.connections.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for TestProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Serves the `SOCKS5` handshake, returning the client and target streams if the tunnel was
+/// established and counting it in `connections`.
+async fn serve_socks5(
+    mut client: TcpStream,
+    credentials: Option<&(String, String)>,
+    connections: &AtomicUsize,
+) -> io::Result<Option<(TcpStream, TcpStream)>> {
+    let mut header = [0u8; 2];
+    client.read_exact(&mut header).await?;
+    let mut methods = vec![0u8; header[1] as usize];
+    client.read_exact(&mut methods).await?;
+
+    let method = if credentials.is_some() { 0x02 } else { 0x00 };
+    if !methods.contains(&method) {
+        client.write_all(&[0x05, 0xff]).await?;
+        return Ok(None)
+    }
+    client.write_all(&[0x05, method]).await?;
+
+    if let Some((username, password)) = credentials {
+        let _version = client.read_u8().await?;
+        let mut user = vec![0u8; client.read_u8().await? as usize];
+        client.read_exact(&mut user).await?;
+        let mut pass = vec![0u8; client.read_u8().await? as usize];
+        client.read_exact(&mut pass).await?;
+        if user != username.as_bytes() || pass != password.as_bytes() {
+            client.write_all(&[0x01, 0x01]).await?;
+            return Ok(None)
+        }
+        client.write_all(&[0x01, 0x00]).await?;
+    }
+
+    let mut request = [0u8; 4];
+    client.read_exact(&mut request).await?;
+    let ip = match request[3] {
+        0x01 => {
+            let mut octets = [0u8; 4];
+            client.read_exact(&mut octets).await?;
+            Ipv4Addr::from(octets).into()
+        }
+        0x04 => {
+            let mut octets = [0u8; 16];
+            client.read_exact(&mut octets).await?;
+            Ipv6Addr::from(octets).into()
+        }
+        _ => return Ok(None),
+    };
+    let port = client.read_u16().await?;
+
+    match TcpStream::connect(SocketAddr::new(ip, port)).await {
+        Ok(target) => {
+            connections.fetch_add(1, Ordering::Relaxed);
+            client.write_all(&[0x05, 0x00, 0x00, 0x01, 0, 0, 0, 0, 0, 0]).await?;
+            Ok(Some((client, target)))
+        }
+        Err(_) => {
+            client.write_all(&[0x05, 0x05, 0x00, 0x01, 0, 0, 0, 0, 0, 0]).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Serves the HTTP `CONNECT` handshake, returning the client and target streams if the tunnel was
+/// established and counting it in `connections`.
+async fn serve_http_connect(
+    mut client: TcpStream,
+    credentials: Option<&(String, String)>,
+    connections: &AtomicUsize,
+) -> io::Result<Option<(TcpStream, TcpStream)>> {
+    let mut request = Vec::new();
+    while !request.ends_with(b"\r\n\r\n") {
+        request.push(client.read_u8().await?);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let mut lines = request.lines();
+
+    let target = lines
+        .next()
+        .and_then(|line| line.strip_prefix("CONNECT "))
+        .and_then(|line| line.split_whitespace().next())
+        .and_then(|target| target.parse::<SocketAddr>().ok());
+    let Some(target) = target else {
+        client.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n").await?;
+        return Ok(None)
+    };
+
+    if let Some((username, password)) = credentials {
+        let expected = format!(
+            "Proxy-Authorization: Basic {}",
+            BASE64.encode(format!("{username}:{password}"))
+        );
+        if !lines.any(|line| line == expected) {
+            client.write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n").await?;
+            return Ok(None)
+        }
+    }
+
+    match TcpStream::connect(target).await {
+        Ok(target) => {
+            connections.fetch_add(1, Ordering::Relaxed);
+            client.write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n").await?;
+            Ok(Some((client, target)))
+        }
+        Err(_) => {
+            client.write_all(b"HTTP/1.1 502 Bad Gateway\r\n\r\n").await?;
+            Ok(None)
+        }
+    }
+}
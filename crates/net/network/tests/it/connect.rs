@@ -6,7 +6,8 @@ use reth_chainspec::{MAINNET, SEPOLIA};
 use reth_discv4::Discv4Config;
 use reth_eth_wire::{DisconnectReason, EthNetworkPrimitives, HeadersDirection};
 use reth_network::{
-    test_utils::{NetworkEventStream, PeerConfig, Testnet},
+    proxy::ProxyProtocol,
+    test_utils::{NetworkEventStream, PeerConfig, TestProxy, Testnet},
     BlockDownloaderProvider, NetworkConfigBuilder, NetworkEvent, NetworkEventListenerProvider,
     NetworkManager, PeersConfig,
 };
@@ -526,6 +527,73 @@ async fn test_exceed_outgoing_connections() {
     assert_eq!(handle.num_connected_peers(), 1);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_connect_through_proxy() {
+    for protocol in [ProxyProtocol::Socks5, ProxyProtocol::HttpConnect] {
+        let net = Testnet::create(1).await;
+        let proxy = TestProxy::spawn(protocol).await;
+        let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+
+        let config = NetworkConfigBuilder::eth(secret_key, Runtime::test())
+            .listener_port(0)
+            .disable_discovery()
+            .proxy(proxy.config())
+            .build(NoopProvider::default());
+
+        let network = NetworkManager::new(config).await.unwrap();
+        let handle = network.handle().clone();
+        tokio::task::spawn(network);
+
+        let mut event_stream = NetworkEventStream::new(handle.event_listener());
+
+        let peer = net.handles().next().unwrap();
+        let _handle = net.spawn();
+
+        handle.add_peer(*peer.peer_id(), peer.local_addr());
+
+        let peer_id = event_stream.next_session_established().await.unwrap();
+        assert_eq!(peer_id, *peer.peer_id());
+        assert_eq!(proxy.num_connections(), 1);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_proxy_bypass_trusted() {
+    let net = Testnet::create(2).await;
+    let proxy = TestProxy::spawn(ProxyProtocol::Socks5).await;
+    let secret_key = SecretKey::new(&mut rand_08::thread_rng());
+
+    let config = NetworkConfigBuilder::eth(secret_key, Runtime::test())
+        .listener_port(0)
+        .disable_discovery()
+        .proxy(proxy.config().with_bypass_trusted(true))
+        .build(NoopProvider::default());
+
+    let network = NetworkManager::new(config).await.unwrap();
+    let handle = network.handle().clone();
+    tokio::task::spawn(network);
+
+    let mut event_stream = NetworkEventStream::new(handle.event_listener());
+
+    let mut handles = net.handles();
+    let trusted = handles.next().unwrap();
+    let basic = handles.next().unwrap();
+    drop(handles);
+    let _handle = net.spawn();
+
+    // trusted peers are dialed directly
+    handle.add_trusted_peer(*trusted.peer_id(), trusted.local_addr());
+    let peer_id = event_stream.next_session_established().await.unwrap();
+    assert_eq!(peer_id, *trusted.peer_id());
+    assert_eq!(proxy.num_connections(), 0);
+
+    // other peers are dialed through the proxy
+    handle.add_peer(*basic.peer_id(), basic.local_addr());
+    let peer_id = event_stream.next_session_established().await.unwrap();
+    assert_eq!(peer_id, *basic.peer_id());
+    assert_eq!(proxy.num_connections(), 1);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_disconnect_incoming_when_exceeded_incoming_connections() {
     let net = Testnet::create(1).await;
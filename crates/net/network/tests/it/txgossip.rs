@@ -10,12 +10,12 @@ use reth_network::{
     },
     NetworkEvent, NetworkEventListenerProvider, Peers,
 };
-use reth_network_api::{events::PeerEvent, PeerKind, PeersInfo};
+use reth_network_api::{events::PeerEvent, test_utils::NetworkFaultsProvider, PeerKind, PeersInfo};
 use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
 use reth_transaction_pool::{
     test_utils::TransactionGenerator, AddedTransactionOutcome, PoolTransaction, TransactionPool,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::join;
 
 #[tokio::test(flavor = "multi_thread")]
@@ -57,6 +57,50 @@ async fn test_tx_gossip() {
     assert_eq!(received, hash);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tx_gossip_partitioned() {
+    reth_tracing::init_test_tracing();
+
+    let provider = MockEthProvider::default().with_genesis_block();
+    let net = Testnet::create_with(2, provider.clone()).await;
+
+    // install request handlers
+    let net = net.with_eth_pool();
+    let handle = net.spawn();
+    // connect all the peers
+    handle.connect_peers().await;
+
+    let peer0 = &handle.peers()[0];
+    let peer1 = &handle.peers()[1];
+
+    let peer0_pool = peer0.pool().unwrap();
+    let mut peer1_tx_listener = peer1.pool().unwrap().pending_transactions_listener();
+
+    // drop everything peer1 receives from peer0
+    peer1.network().network_faults().partition(*peer0.peer_id());
+
+    let mut tx_gen = TransactionGenerator::new(rand::rng());
+    let tx = tx_gen.gen_eip1559_pooled();
+    provider.add_account(tx.sender(), ExtendedAccount::new(0, U256::from(100_000_000)));
+    peer0_pool.add_external_transaction(tx).await.unwrap();
+
+    // ensure the tx is not gossiped across the partition
+    tokio::time::timeout(Duration::from_millis(500), peer1_tx_listener.recv())
+        .await
+        .expect_err("tx gossiped across partition");
+
+    peer1.network().network_faults().heal(*peer0.peer_id());
+
+    let tx = tx_gen.gen_eip1559_pooled();
+    provider.add_account(tx.sender(), ExtendedAccount::new(0, U256::from(100_000_000)));
+    let AddedTransactionOutcome { hash, .. } =
+        peer0_pool.add_external_transaction(tx).await.unwrap();
+
+    // ensure the tx is gossiped once the partition is healed
+    let received = peer1_tx_listener.recv().await.unwrap();
+    assert_eq!(received, hash);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_tx_propagation_policy_trusted_only() {
     reth_tracing::init_test_tracing();
@@ -11,6 +11,7 @@ use reth_network_api::FullNetwork;
 use reth_node_core::node_config::NodeConfig;
 use reth_node_types::{NodeTypes, NodeTypesWithDBAdapter, TxTy};
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_payload_util::BundlePool;
 use reth_provider::FullProvider;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
@@ -115,6 +116,11 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<ConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Pool of bundles shared between `mev_sendBundle` and the payload builder, if enabled.
+    pub bundle_pool: Option<BundlePool<TxTy<N::Types>>>,
+    /// Inclusion lists (EIP-7805) shared between the engine API and the payload builder.
+    #[cfg(feature = "focil")]
+    pub inclusion_lists: reth_payload_util::InclusionListStore,
 }
 
 /// Customizable node add-on types.
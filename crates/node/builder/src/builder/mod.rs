@@ -24,7 +24,7 @@ use reth_network::{
     NetworkPrimitives,
 };
 use reth_node_api::{
-    FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes, NodeTypesWithDBAdapter,
+    FullNodeTypes, FullNodeTypesAdapter, NodeAddOns, NodeTypes, NodeTypesWithDBAdapter, TxTy,
 };
 use reth_node_core::{
     cli::config::{PayloadBuilderConfig, RethTransactionPoolConfig},
@@ -32,6 +32,7 @@ use reth_node_core::{
     node_config::NodeConfig,
     primitives::Head,
 };
+use reth_payload_util::BundlePool;
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider, RocksDBProvider},
     ChainSpecProvider, FullProvider,
@@ -751,6 +752,11 @@ pub struct BuilderContext<Node: FullNodeTypes> {
     pub(crate) executor: TaskExecutor,
     /// Config container
     pub(crate) config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
+    /// Pool of bundles shared with `mev_sendBundle`, if provided by the launcher.
+    pub(crate) bundle_pool: Option<BundlePool<TxTy<Node::Types>>>,
+    /// Inclusion lists (EIP-7805) shared with the engine API, if provided by the launcher.
+    #[cfg(feature = "focil")]
+    pub(crate) inclusion_lists: Option<reth_payload_util::InclusionListStore>,
 }
 
 impl<Node: FullNodeTypes> BuilderContext<Node> {
@@ -761,7 +767,47 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         executor: TaskExecutor,
         config_container: WithConfigs<<Node::Types as NodeTypes>::ChainSpec>,
     ) -> Self {
-        Self { head, provider, executor, config_container }
+        Self {
+            head,
+            provider,
+            executor,
+            config_container,
+            bundle_pool: None,
+            #[cfg(feature = "focil")]
+            inclusion_lists: None,
+        }
+    }
+
+    /// Sets the pool of bundles that `mev_sendBundle` inserts the received bundles into.
+    pub fn with_bundle_pool(mut self, bundle_pool: BundlePool<TxTy<Node::Types>>) -> Self {
+        self.bundle_pool = Some(bundle_pool);
+        self
+    }
+
+    /// Returns the pool of bundles received through `mev_sendBundle`, if enabled.
+    ///
+    /// Payload builders include the bundles of this pool at the top of the blocks they build.
+    pub const fn bundle_pool(&self) -> Option<&BundlePool<TxTy<Node::Types>>> {
+        self.bundle_pool.as_ref()
+    }
+
+    /// Sets the store of inclusion lists (EIP-7805) that the engine API inserts the inclusion
+    /// lists received from the consensus layer into.
+    #[cfg(feature = "focil")]
+    pub fn with_inclusion_lists(
+        mut self,
+        inclusion_lists: reth_payload_util::InclusionListStore,
+    ) -> Self {
+        self.inclusion_lists = Some(inclusion_lists);
+        self
+    }
+
+    /// Returns the store of inclusion lists (EIP-7805) received by the engine API, if any.
+    ///
+    /// Payload builders read the inclusion lists that built blocks must satisfy from this store.
+    #[cfg(feature = "focil")]
+    pub const fn inclusion_lists(&self) -> Option<&reth_payload_util::InclusionListStore> {
+        self.inclusion_lists.as_ref()
     }
 
     /// Returns the configured provider to interact with the blockchain.
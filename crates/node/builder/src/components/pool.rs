@@ -1,10 +1,12 @@
 //! Pool component for the node builder.
 
 use crate::{BuilderContext, FullNodeTypes};
-use alloy_primitives::map::AddressSet;
+use alloy_primitives::{map::AddressSet, BlockNumber};
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::EthereumHardforks;
 use reth_node_api::{BlockTy, NodeTypes, TxTy};
+use reth_provider::ProviderError;
+use reth_prune::segments::BlobSidecarStore;
 use reth_transaction_pool::{
     blobstore::DiskFileBlobStore, BlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction,
     SubPoolLimit, TransactionOrdering, TransactionPool, TransactionValidationTaskExecutor,
@@ -220,16 +222,53 @@ pub fn create_blob_store_with_cache<Node: FullNodeTypes>(
     cache_size: Option<u32>,
 ) -> eyre::Result<DiskFileBlobStore> {
     let data_dir = ctx.config().datadir();
-    let config = if let Some(cache_size) = cache_size {
+    let mut config = if let Some(cache_size) = cache_size {
         reth_transaction_pool::blobstore::DiskFileBlobStoreConfig::default()
             .with_max_cached_entries(cache_size)
     } else {
-        Default::default()
+        reth_transaction_pool::blobstore::DiskFileBlobStoreConfig::default()
     };
 
+    if ctx.config().txpool.persist {
+        // keep the sidecars of the journaled blob transactions, so they can be reinserted
+        let retain = reth_transaction_pool::journal::TransactionPoolJournal::read(
+            &data_dir.txpool_journal(),
+        )?
+        .map(|journal| journal.blob_transactions())
+        .unwrap_or_default();
+        config = config
+            .with_open(reth_transaction_pool::blobstore::OpenDiskFileBlobStore::ReIndex)
+            .with_retain(retain);
+    }
+
+    if ctx.config().txpool.blob_sidecar_retention > 0 {
+        config = config.with_retained_sidecars(
+            reth_transaction_pool::blobstore::RetainedBlobSidecars::open(
+                data_dir.retained_blob_sidecars(),
+            )?,
+        );
+    }
+
     Ok(reth_transaction_pool::blobstore::DiskFileBlobStore::open(data_dir.blobstore(), config)?)
 }
 
+/// Exposes the blob sidecars a [`BlobStore`] retains for finalized blocks to the pruner.
+#[derive(Debug)]
+pub struct BlobStoreSidecars(Box<dyn BlobStore>);
+
+impl BlobStoreSidecars {
+    /// Creates a new instance for the given blob store.
+    pub fn new(blob_store: Box<dyn BlobStore>) -> Self {
+        Self(blob_store)
+    }
+}
+
+impl BlobSidecarStore for BlobStoreSidecars {
+    fn prune_blob_sidecars(&self, to_block: BlockNumber) -> Result<usize, ProviderError> {
+        self.0.prune_finalized_sidecars(to_block).map_err(ProviderError::other)
+    }
+}
+
 /// Spawn local transaction backup task if enabled.
 fn spawn_local_backup_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> eyre::Result<()>
 where
@@ -264,6 +303,32 @@ where
     Ok(())
 }
 
+/// Spawn the transaction pool journal task if enabled.
+fn spawn_journal_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> eyre::Result<()>
+where
+    Node: FullNodeTypes,
+    Pool: TransactionPool + Clone + 'static,
+{
+    if ctx.config().txpool.persist {
+        let journal_config = reth_transaction_pool::journal::TransactionPoolJournalConfig::new(
+            ctx.config().datadir().txpool_journal(),
+        )
+        .with_interval(ctx.config().txpool.persist_interval);
+
+        ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+            "transaction pool journal task",
+            |shutdown| {
+                reth_transaction_pool::journal::journal_transactions_task(
+                    shutdown,
+                    pool,
+                    journal_config,
+                )
+            },
+        );
+    }
+    Ok(())
+}
+
 /// Spawn the main maintenance task for transaction pool.
 fn spawn_pool_maintenance_task<Node, Pool>(
     ctx: &BuilderContext<Node>,
@@ -296,7 +361,7 @@ where
     Ok(())
 }
 
-/// Spawn all maintenance tasks for a transaction pool (backup + main maintenance).
+/// Spawn all maintenance tasks for a transaction pool (backup + journal + main maintenance).
 pub fn spawn_maintenance_tasks<Node, Pool>(
     ctx: &BuilderContext<Node>,
     pool: Pool,
@@ -308,6 +373,7 @@ where
     Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>,
 {
     spawn_local_backup_task(ctx, pool.clone())?;
+    spawn_journal_task(ctx, pool.clone())?;
     spawn_pool_maintenance_task(ctx, pool, pool_config)?;
     Ok(())
 }
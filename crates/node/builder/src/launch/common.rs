@@ -32,6 +32,7 @@
 use crate::{
     components::{NodeComponents, NodeComponentsBuilder},
     hooks::OnComponentInitializedHook,
+    setup::build_cold_storage_config,
     BuilderContext, ExExLauncher, NodeAdapter, PrimitivesTy,
 };
 use alloy_eips::eip2124::Head;
@@ -51,7 +52,7 @@ use reth_evm::{noop::NoopEvmConfig, ConfigureEvm};
 use reth_exex::ExExManagerHandle;
 use reth_fs_util as fs;
 use reth_network_p2p::headers::client::HeadersClient;
-use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
+use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter, TxTy};
 use reth_node_core::{
     args::{DefaultEraHost, PruneConfigKind},
     dirs::{ChainPath, DataDirPath},
@@ -61,17 +62,20 @@ use reth_node_core::{
 };
 use reth_node_metrics::{
     chain::ChainSpecInfo,
+    health::NodeHealth,
     hooks::Hooks,
     recorder::install_prometheus_recorder,
     server::{MetricServer, MetricServerConfig},
     storage::StorageSettingsInfo,
     version::VersionInfo,
 };
+use reth_payload_util::BundlePool;
 use reth_provider::{
     providers::{NodeTypesForProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider},
-    BalConfig, BalStoreHandle, BlockHashReader, BlockNumReader, InMemoryBalStore, ProviderError,
-    ProviderFactory, ProviderResult, RocksDBProviderFactory, StageCheckpointReader,
-    StaticFileProviderBuilder, StaticFileProviderFactory, StorageSettingsCache,
+    BalConfig, BalStoreHandle, BlockHashReader, BlockNumReader, InMemoryBalStore, MetadataProvider,
+    MetadataWriter, ProviderError, ProviderFactory, ProviderResult, RocksDBProviderFactory,
+    StageCheckpointReader, StaticFileProviderBuilder, StaticFileProviderFactory,
+    StorageSettingsCache,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
 use reth_rpc_builder::config::RethRpcServerConfig;
@@ -88,7 +92,12 @@ use reth_tracing::{
 };
 use reth_transaction_pool::TransactionPool;
 use reth_trie_db::ChangesetCache;
-use std::{num::NonZeroUsize, sync::Arc, thread::available_parallelism, time::Duration};
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, OnceLock},
+    thread::available_parallelism,
+    time::Duration,
+};
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedSender},
     oneshot, watch,
@@ -122,12 +131,16 @@ pub struct LaunchContext {
     pub task_executor: TaskExecutor,
     /// The data directory for the node.
     pub data_dir: ChainPath<DataDirPath>,
+    /// The health of the node, served by the metrics endpoint.
+    ///
+    /// Created on first access, so that the context can be constructed in const context.
+    pub node_health: OnceLock<NodeHealth>,
 }
 
 impl LaunchContext {
     /// Create a new instance of the default node launcher.
     pub const fn new(task_executor: TaskExecutor, data_dir: ChainPath<DataDirPath>) -> Self {
-        Self { task_executor, data_dir }
+        Self { task_executor, data_dir, node_health: OnceLock::new() }
     }
 
     /// Create launch context with attachment.
@@ -283,6 +296,11 @@ impl<T> LaunchContextWith<T> {
         &self.inner.task_executor
     }
 
+    /// Returns the health handle of the node.
+    pub fn node_health(&self) -> &NodeHealth {
+        self.inner.node_health.get_or_init(NodeHealth::default)
+    }
+
     /// Attaches another value to the launch context.
     pub fn attach<A>(self, attachment: A) -> LaunchContextWith<Attached<T, A>> {
         LaunchContextWith {
@@ -488,12 +506,17 @@ where
         static_files_config.validate()?;
 
         // Apply per-segment blocks_per_file configuration
-        let static_file_provider =
+        let mut static_file_provider_builder =
             StaticFileProviderBuilder::read_write(self.data_dir().static_files())
                 .with_metrics()
                 .with_blocks_per_file_for_segments(&static_files_config.as_blocks_per_file_map())
-                .with_genesis_block_number(self.chain_spec().genesis().number.unwrap_or_default())
-                .build()?;
+                .with_genesis_block_number(self.chain_spec().genesis().number.unwrap_or_default());
+        if let Some(cold) = &static_files_config.cold {
+            static_file_provider_builder = static_file_provider_builder.with_cold_storage(
+                build_cold_storage_config(cold, &self.data_dir().static_files())?,
+            );
+        }
+        let static_file_provider = static_file_provider_builder.build()?;
 
         // Use the provided RocksDB provider or create a new one
         let rocksdb_provider = if let Some(provider) = rocksdb_provider {
@@ -554,46 +577,130 @@ where
 
             info!(target: "reth::cli", %unwind_target, %inconsistency_source, "Executing unwind after consistency check.");
 
-            let (_tip_tx, tip_rx) = watch::channel(B256::ZERO);
-
-            // Builds an unwind-only pipeline
-            let pipeline = PipelineBuilder::default()
-                .add_stages(
-                    DefaultStages::new(
-                        factory.clone(),
-                        tip_rx,
-                        Arc::new(NoopConsensus::default()),
-                        NoopHeaderDownloader::default(),
-                        NoopBodiesDownloader::default(),
-                        NoopEvmConfig::<Evm>::default(),
-                        self.toml_config().stages.clone(),
-                        self.prune_modes(),
-                        None,
-                    )
-                    .builder()
-                    .disable_all(disabled_stages),
-                )
-                .build(
-                    factory.clone(),
-                    StaticFileProducer::new(factory.clone(), self.prune_modes()),
-                );
+            self.unwind::<N, Evm>(&factory, unwind_target, disabled_stages).await.inspect_err(
+                |err| {
+                    error!(target: "reth::cli", %unwind_target, %inconsistency_source, %err, "failed to run unwind")
+                },
+            )?;
+        }
 
-            // Unwinds to block
-            let (tx, rx) = oneshot::channel();
+        if let Some(shadow_fork_block) = self.node_config().shadow_fork.block {
+            self.branch_shadow_fork::<N, Evm>(&factory, shadow_fork_block, disabled_stages).await?;
+        }
 
-            // Pipeline should be run as blocking and panic if it fails.
-            self.task_executor().spawn_critical_blocking_task("pipeline task", async move {
-                let (_, result) = pipeline.run_as_fut(Some(unwind_target)).await;
-                let _ = tx.send(result);
+        if let Some(cold) = &self.toml_config().static_files.cold {
+            let static_file_provider = factory.static_file_provider();
+            let offload_interval = cold.offload_interval;
+            let task_executor = self.task_executor().clone();
+            // Moving files takes the writer lock of their segment before removing them locally, so
+            // it's coordinated with the static file writers.
+            self.task_executor().spawn_task(async move {
+                let mut interval = tokio::time::interval(offload_interval);
+                loop {
+                    interval.tick().await;
+                    let static_file_provider = static_file_provider.clone();
+                    match task_executor
+                        .spawn_blocking(move || static_file_provider.offload_cold_static_files())
+                        .await
+                    {
+                        Ok(Ok(_)) => {}
+                        Ok(Err(err)) => {
+                            warn!(target: "reth::cli", %err, "Failed to move static files to cold storage")
+                        }
+                        Err(err) => {
+                            warn!(target: "reth::cli", %err, "Static files cold storage task failed")
+                        }
+                    }
+                }
             });
-            rx.await?.inspect_err(|err| {
-                error!(target: "reth::cli", %unwind_target, %inconsistency_source, %err, "failed to run unwind")
-            })?;
         }
 
         Ok(factory)
     }
 
+    /// Unwinds the datadir to the shadow fork block on the first start of a shadow fork, and
+    /// records the block so that later starts keep the blocks of the shadow fork.
+    async fn branch_shadow_fork<N, Evm>(
+        &self,
+        factory: &ProviderFactory<N>,
+        shadow_fork_block: BlockNumber,
+        disabled_stages: &[StageId],
+    ) -> eyre::Result<()>
+    where
+        N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
+        Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
+    {
+        let provider = factory.provider()?;
+        if provider.shadow_fork_block()? == Some(shadow_fork_block) {
+            debug!(target: "reth::cli", shadow_fork_block, "Datadir already branched off for the shadow fork");
+            return Ok(())
+        }
+
+        let head = provider.last_block_number()?;
+        drop(provider);
+        eyre::ensure!(
+            head >= shadow_fork_block,
+            "Datadir is synced to block {head}, below the shadow fork block {shadow_fork_block}"
+        );
+
+        if head > shadow_fork_block {
+            let unwind_target = PipelineTarget::Unwind(shadow_fork_block);
+            info!(target: "reth::cli", %unwind_target, head, "Unwinding to the shadow fork block.");
+            self.unwind::<N, Evm>(factory, unwind_target, disabled_stages).await?;
+        }
+
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.write_shadow_fork_block(shadow_fork_block)?;
+        provider_rw.commit()?;
+
+        info!(target: "reth::cli", shadow_fork_block, chain = %self.chain_id(), "Starting shadow fork");
+        Ok(())
+    }
+
+    /// Runs an unwind-only pipeline to the given target.
+    async fn unwind<N, Evm>(
+        &self,
+        factory: &ProviderFactory<N>,
+        unwind_target: PipelineTarget,
+        disabled_stages: &[StageId],
+    ) -> eyre::Result<()>
+    where
+        N: ProviderNodeTypes<DB = DB, ChainSpec = ChainSpec>,
+        Evm: ConfigureEvm<Primitives = N::Primitives> + 'static,
+    {
+        let (_tip_tx, tip_rx) = watch::channel(B256::ZERO);
+
+        // Builds an unwind-only pipeline
+        let pipeline = PipelineBuilder::default()
+            .add_stages(
+                DefaultStages::new(
+                    factory.clone(),
+                    tip_rx,
+                    Arc::new(NoopConsensus::default()),
+                    NoopHeaderDownloader::default(),
+                    NoopBodiesDownloader::default(),
+                    NoopEvmConfig::<Evm>::default(),
+                    self.toml_config().stages.clone(),
+                    self.prune_modes(),
+                    None,
+                )
+                .builder()
+                .disable_all(disabled_stages),
+            )
+            .build(factory.clone(), StaticFileProducer::new(factory.clone(), self.prune_modes()));
+
+        // Unwinds to block
+        let (tx, rx) = oneshot::channel();
+
+        // Pipeline should be run as blocking and panic if it fails.
+        self.task_executor().spawn_critical_blocking_task("pipeline task", async move {
+            let (_, result) = pipeline.run_as_fut(Some(unwind_target)).await;
+            let _ = tx.send(result);
+        });
+        rx.await??;
+        Ok(())
+    }
+
     /// Creates a new [`ProviderFactory`] and attaches it to the launch context.
     pub async fn with_provider_factory<N, Evm>(
         self,
@@ -653,10 +760,12 @@ where
         T::ChainSpec: EthereumHardforks,
     {
         // ensure recorder runs upkeep periodically
-        install_prometheus_recorder().spawn_upkeep();
+        let recorder = install_prometheus_recorder();
+        recorder.spawn_upkeep();
 
         let listen_addr = self.node_config().metrics.prometheus;
-        if let Some(addr) = listen_addr {
+        let otlp_interval = recorder.otlp_interval();
+        if listen_addr.is_some() || otlp_interval.is_some() {
             let prune_config = self.prune_config();
             let pruning_mode =
                 PruneConfigKind::from_config(&prune_config, self.chain_spec().as_ref()).as_str();
@@ -670,7 +779,7 @@ where
                     self.node_config().storage_settings()
                 };
             let config = MetricServerConfig::new(
-                addr,
+                listen_addr,
                 VersionInfo {
                     version: version_metadata().cargo_pkg_version.as_ref(),
                     build_timestamp: version_metadata().vergen_build_timestamp.as_ref(),
@@ -693,7 +802,9 @@ where
             .with_push_gateway(
                 self.node_config().metrics.push_gateway_url.clone(),
                 self.node_config().metrics.push_gateway_interval,
-            );
+            )
+            .with_health(self.node_health().clone())
+            .with_otlp_interval(otlp_interval);
 
             MetricServer::new(config).serve().await?;
         }
@@ -850,6 +961,17 @@ where
             self.task_executor().clone(),
             self.configs().clone(),
         );
+        // bundles received by `mev_sendBundle` are shared with the payload builder
+        let bundle_pool = self.node_config().builder.bundles.then(BundlePool::default);
+        let builder_ctx = match bundle_pool.clone() {
+            Some(bundle_pool) => builder_ctx.with_bundle_pool(bundle_pool),
+            None => builder_ctx,
+        };
+        // the inclusion lists received by the engine API are shared with the payload builder
+        #[cfg(feature = "focil")]
+        let inclusion_lists = reth_payload_util::InclusionListStore::default();
+        #[cfg(feature = "focil")]
+        let builder_ctx = builder_ctx.with_inclusion_lists(inclusion_lists.clone());
 
         debug!(target: "reth::cli", "creating components");
         let components = components_builder.build_components(&builder_ctx).await?;
@@ -872,6 +994,9 @@ where
             },
             node_adapter,
             head,
+            bundle_pool,
+            #[cfg(feature = "focil")]
+            inclusion_lists,
         };
 
         let ctx = LaunchContextWith {
@@ -924,6 +1049,19 @@ where
         self.right().head
     }
 
+    /// Returns the pool of bundles shared between `mev_sendBundle` and the payload builder, if
+    /// enabled.
+    pub const fn bundle_pool(&self) -> Option<&BundlePool<TxTy<T::Types>>> {
+        self.right().bundle_pool.as_ref()
+    }
+
+    /// Returns the inclusion lists (EIP-7805) shared between the engine API and the payload
+    /// builder.
+    #[cfg(feature = "focil")]
+    pub const fn inclusion_lists(&self) -> &reth_payload_util::InclusionListStore {
+        &self.right().inclusion_lists
+    }
+
     /// Returns the configured `NodeAdapter`.
     pub const fn node_adapter(&self) -> &NodeAdapter<T, CB::Components> {
         &self.right().node_adapter
@@ -1297,6 +1435,9 @@ where
     db_provider_container: WithMeteredProvider<NodeTypesWithDBAdapter<T::Types, T::DB>>,
     node_adapter: NodeAdapter<T, CB::Components>,
     head: Head,
+    bundle_pool: Option<BundlePool<TxTy<T::Types>>>,
+    #[cfg(feature = "focil")]
+    inclusion_lists: reth_payload_util::InclusionListStore,
 }
 
 /// Returns the metrics hooks for the node.
@@ -1367,6 +1508,7 @@ mod tests {
                     bodies_distance: None,
                     receipts_log_filter: None,
                     bodies_before: None,
+                    history_retained_addresses: Vec::new(),
                     minimum_distance: None,
                 },
                 ..NodeConfig::test()
@@ -3,9 +3,10 @@ use crate::{rpc::RethRpcAddOns, EngineNodeLauncher, Node, NodeHandle};
 use alloy_consensus::transaction::Either;
 use alloy_provider::network::AnyNetwork;
 use jsonrpsee::core::{DeserializeOwned, Serialize};
-use reth_chainspec::EthChainSpec;
+use reth_chainspec::{Chain, EthChainSpec};
 use reth_consensus_debug_client::{
-    DebugConsensusClient, EtherscanBlockProvider, PayloadProvider, RpcBlockProvider,
+    BeaconLightClientProvider, DebugConsensusClient, EtherscanBlockProvider, LightClientConfig,
+    PayloadProvider, RpcBlockProvider,
 };
 use reth_engine_local::{LocalMiner, MiningMode};
 use reth_node_api::{
@@ -35,6 +36,8 @@ pub(crate) type PayloadDataTy<N> = <<N as NodeTypes>::Payload as PayloadTypes>::
 ///   blocks to the local engine.
 /// - **RPC Consensus Client**: Connect to an external RPC endpoint to fetch blocks and submit them
 ///   to the local engine to follow the chain.
+/// - **Beacon Light Client**: Follow the beacon chain through the light client updates of a Beacon
+///   API and submit the verified execution payloads to the local engine.
 ///
 /// See [`DebugNodeLauncher`] for the launcher that enables these features.
 ///
@@ -83,6 +86,16 @@ pub trait DebugNode<N: FullNodeComponents>: Node<N> {
     fn local_payload_attributes_builder(
         chain_spec: &Self::ChainSpec,
     ) -> impl PayloadAttributesBuilder<<Self::Payload as PayloadTypes>::PayloadAttributes, HeaderTy<Self>>;
+
+    /// Returns the conversion from beacon chain execution payloads to the node's execution payload
+    /// data, if the node's chain is driven by the beacon chain.
+    ///
+    /// This enables following the chain with the beacon light client. Returns `None` by default.
+    fn beacon_payload_converter() -> Option<
+        fn(alloy_rpc_types_engine::ExecutionData) -> <Self::Payload as PayloadTypes>::ExecutionData,
+    > {
+        None
+    }
 }
 
 /// Node launcher with support for launching various debugging utilities.
@@ -106,6 +119,14 @@ pub trait DebugNode<N: FullNodeComponents>: Node<N> {
 /// - Submit them to the local engine
 /// - Requires `ETHERSCAN_API_KEY` environment variable
 /// - Falls back to default Etherscan URL for the chain if URL not provided
+///
+/// ## Beacon Light Client
+///
+/// When `--debug.beacon-api-url <URL>` is provided, the launcher will:
+/// - Bootstrap a light client from the trusted `--debug.beacon-checkpoint` block root
+/// - Follow the beacon chain through sync committee signed light client updates
+/// - Submit the execution payloads of the verified beacon blocks to the local engine
+/// - Only supports Ethereum mainnet
 #[derive(Debug, Clone)]
 pub struct DebugNodeLauncher<L = EngineNodeLauncher> {
     inner: L,
@@ -298,6 +319,37 @@ where
                 .spawn_critical_task("etherscan consensus client", async move {
                     rpc_consensus_client.run().await
                 });
+        } else if let Some(url) = config.debug.beacon_api_url.clone() {
+            info!(target: "reth::cli", "Using beacon light client as consensus client: {}", url);
+
+            let chain = config.chain.chain();
+            if chain != Chain::mainnet() {
+                eyre::bail!("beacon light client is not supported for chain: {chain}")
+            }
+            let convert = N::Types::beacon_payload_converter().ok_or_else(|| {
+                eyre::eyre!("beacon light client is not supported by the node type")
+            })?;
+            let checkpoint = config
+                .debug
+                .beacon_checkpoint
+                .ok_or_else(|| eyre::eyre!("beacon light client requires a checkpoint"))?;
+
+            let block_provider = BeaconLightClientProvider::new(
+                url,
+                checkpoint,
+                LightClientConfig::mainnet(),
+                convert,
+            );
+            let rpc_consensus_client = DebugConsensusClient::new(
+                handle.node.add_ons_handle.beacon_engine_handle.clone(),
+                Arc::new(block_provider),
+            );
+            handle
+                .node
+                .task_executor
+                .spawn_critical_task("beacon light client consensus client", async move {
+                    rpc_consensus_client.run().await
+                });
         }
 
         if config.dev.dev {
@@ -2,6 +2,7 @@
 
 use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
+    components::BlobStoreSidecars,
     hooks::NodeHooks,
     rpc::{EngineShutdown, EngineValidatorAddOn, EngineValidatorBuilder, RethRpcAddOns, RpcHandle},
     setup::build_networked_pipeline,
@@ -17,12 +18,13 @@ use reth_engine_tree::{
     chain::{ChainEvent, FromOrchestrator},
     engine::{EngineApiKind, EngineApiRequest, EngineRequestHandler},
     launch::build_engine_orchestrator,
+    persistence::PersistenceHandle,
     tree::TreeConfig,
 };
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
-use reth_network_api::BlockDownloaderProvider;
+use reth_network_api::{BlockDownloaderProvider, NetworkInfo, PeersInfo};
 use reth_node_api::{
     BuiltPayload, ConsensusEngineHandle, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
 };
@@ -33,15 +35,18 @@ use reth_node_core::{
     primitives::Head,
 };
 use reth_node_events::node;
+use reth_node_metrics::health::{HealthSnapshot, HealthThresholds};
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     BlockNumReader, StorageSettingsCache,
 };
+use reth_prune::PruneMode;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
+use reth_transaction_pool::TransactionPool;
 use reth_trie_db::ChangesetCache;
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
@@ -184,6 +189,13 @@ impl EngineNodeLauncher {
             pruner_builder =
                 pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
         }
+        let blob_sidecar_retention = ctx.node_config().txpool.blob_sidecar_retention;
+        if blob_sidecar_retention > 0 {
+            pruner_builder = pruner_builder.blob_sidecars(
+                PruneMode::Distance(blob_sidecar_retention),
+                Arc::new(BlobStoreSidecars::new(ctx.components().pool().blob_store())),
+            );
+        }
         let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config(), "Pruner initialized");
@@ -201,9 +213,19 @@ impl EngineNodeLauncher {
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            bundle_pool: ctx.bundle_pool().cloned(),
+            #[cfg(feature = "focil")]
+            inclusion_lists: ctx.inclusion_lists().clone(),
         };
         let validator_builder = add_ons.engine_validator_builder();
 
+        let engine_tree_config = if ctx.node_config().engine.execution_cache_snapshot {
+            engine_tree_config
+                .with_execution_cache_snapshot_path(Some(ctx.data_dir().execution_cache_snapshot()))
+        } else {
+            engine_tree_config
+        };
+
         // Build the engine validator with all required components
         let engine_validator = validator_builder
             .clone()
@@ -241,6 +263,12 @@ impl EngineNodeLauncher {
             EngineApiKind::Ethereum
         };
 
+        let persistence_handle = PersistenceHandle::<N::Primitives>::spawn_service(
+            ctx.provider_factory().clone(),
+            pruner,
+            ctx.sync_metrics_tx(),
+        );
+
         let mut orchestrator = build_engine_orchestrator(
             engine_kind,
             consensus.clone(),
@@ -248,13 +276,11 @@ impl EngineNodeLauncher {
             Box::pin(consensus_engine_stream),
             pipeline,
             ctx.task_executor().clone(),
-            ctx.provider_factory().clone(),
             ctx.blockchain_db().clone(),
-            pruner,
+            persistence_handle.clone(),
             ctx.components().payload_builder_handle().clone(),
             engine_validator,
             engine_tree_config,
-            ctx.sync_metrics_tx(),
             ctx.components().evm_config().clone(),
             changeset_cache,
             ctx.task_executor().clone(),
@@ -262,6 +288,41 @@ impl EngineNodeLauncher {
 
         info!(target: "reth::cli", "Consensus engine initialized");
 
+        #[cfg(unix)]
+        {
+            let server = reth_engine_tree::backup::BackupServer::new(
+                ctx.data_dir().backup_socket(),
+                ctx.provider_factory().clone(),
+                persistence_handle,
+                ctx.task_executor().clone(),
+            );
+            ctx.task_executor().spawn_task(async move {
+                if let Err(err) = server.run().await {
+                    error!(target: "reth::cli", %err, "Backup server failed");
+                }
+            });
+        }
+
+        #[cfg(unix)]
+        if ctx.node_config().replica.notifications {
+            let socket = ctx
+                .node_config()
+                .replica
+                .socket
+                .clone()
+                .unwrap_or_else(|| ctx.data_dir().replica_socket());
+            let server = reth_node_replica::ReplicaNotificationServer::new(
+                socket,
+                ctx.blockchain_db().canonical_in_memory_state(),
+                ctx.task_executor().clone(),
+            );
+            ctx.task_executor().spawn_task(async move {
+                if let Err(err) = server.run().await {
+                    error!(target: "reth::cli", %err, "Replica notification server failed");
+                }
+            });
+        }
+
         #[expect(clippy::needless_continue)]
         let events = stream_select!(
             event_sender.new_listener().map(Into::into),
@@ -280,6 +341,31 @@ impl EngineNodeLauncher {
             ),
         );
 
+        let metric_args = &ctx.node_config().metrics;
+        let health_thresholds = HealthThresholds {
+            max_head_lag: Duration::from_secs(metric_args.health_max_head_lag_secs),
+            max_persistence_backlog: metric_args.health_max_persistence_backlog,
+            min_peers: metric_args.health_min_peers,
+        };
+        let network = ctx.components().network().clone();
+        let canonical_state = ctx.blockchain_db().canonical_in_memory_state();
+        ctx.node_health().init(health_thresholds, move || {
+            let head = canonical_state.get_canonical_head();
+            HealthSnapshot {
+                timestamp: HealthSnapshot::unix_timestamp_since(Duration::ZERO),
+                // the network is kept in the syncing state until live sync advances the head
+                pipeline_sync: network.is_syncing(),
+                last_cl_message: canonical_state
+                    .last_received_update_timestamp()
+                    .map(|received| HealthSnapshot::unix_timestamp_since(received.elapsed())),
+                head_number: head.number(),
+                head_timestamp: head.timestamp(),
+                // blocks are kept in memory until they are persisted
+                persistence_backlog: canonical_state.canonical_chain().count() as u64,
+                peers: network.num_connected_peers(),
+            }
+        });
+
         let RpcHandle {
             rpc_server_handles,
             rpc_registry,
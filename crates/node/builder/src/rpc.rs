@@ -36,11 +36,14 @@ use reth_rpc::{
     eth::{core::EthRpcConverterFor, DevSigner, EthApiTypes, FullEthApiServer},
     AdminApi,
 };
-use reth_rpc_api::{eth::helpers::EthTransactions, IntoEngineApiRpcModule};
+use reth_rpc_api::{
+    eth::helpers::EthTransactions, EthBundleApiServer, IntoEngineApiRpcModule, MevFullApiServer,
+};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle,
+    TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
@@ -1099,7 +1102,15 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            bundle_pool,
+            ..
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -1143,6 +1154,15 @@ where
                 beacon_engine_handle.clone(),
             );
 
+        // serve `mev_sendBundle` and `eth_sendBundle` from the bundle pool shared with the payload
+        // builder
+        if let Some(bundle_pool) = bundle_pool {
+            let mev_api = registry.mev_bundle_api(bundle_pool);
+            let mut mev_module = MevFullApiServer::into_rpc(mev_api.clone());
+            mev_module.merge(EthBundleApiServer::into_rpc(mev_api))?;
+            modules.add_or_replace_if_module_configured(RethRpcModule::Mev, mev_module)?;
+        }
+
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             let signers = DevSigner::from_mnemonic(config.dev.dev_mnemonic.as_str(), 20);
@@ -1519,7 +1539,7 @@ where
             commit: version_metadata().vergen_git_sha.to_string(),
         };
 
-        Ok(EngineApi::new(
+        let engine_api = EngineApi::new(
             ctx.node.provider().clone(),
             ctx.config.chain.clone(),
             ctx.beacon_engine_handle.clone(),
@@ -1531,7 +1551,10 @@ where
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
             ctx.node.network().clone(),
-        ))
+        );
+        #[cfg(feature = "focil")]
+        let engine_api = engine_api.with_inclusion_lists(ctx.inclusion_lists.clone());
+        Ok(engine_api)
     }
 }
 
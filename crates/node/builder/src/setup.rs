@@ -1,10 +1,13 @@
 //! Helpers for setting up parts of the node.
 
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use crate::BlockTy;
 use alloy_primitives::{BlockNumber, B256};
-use reth_config::{config::StageConfig, PruneConfig};
+use reth_config::{
+    config::{ColdStaticFilesConfig, ColdStorageBackendConfig, StageConfig},
+    PruneConfig,
+};
 use reth_consensus::FullConsensus;
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -16,7 +19,10 @@ use reth_network_p2p::{
     bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
 };
 use reth_node_api::HeaderTy;
-use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
+use reth_provider::{
+    providers::{ColdStorage, ColdStorageConfig, LocalColdStorage, ProviderNodeTypes},
+    ProviderFactory,
+};
 use reth_stages::{
     prelude::DefaultStages,
     stages::{EraImportSource, ExecutionStage},
@@ -137,3 +143,48 @@ where
 
     Ok(pipeline)
 }
+
+/// Builds the static files [`ColdStorageConfig`] from its TOML configuration.
+///
+/// The read cache defaults to a `static_files_cache` directory next to `static_files_dir`. S3
+/// credentials are read from the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY` environment
+/// variables. The S3 backend requires the `s3-cold-storage` feature.
+pub fn build_cold_storage_config(
+    config: &ColdStaticFilesConfig,
+    static_files_dir: &Path,
+) -> eyre::Result<ColdStorageConfig> {
+    let storage: Arc<dyn ColdStorage> = match &config.backend {
+        ColdStorageBackendConfig::Local { path } => Arc::new(LocalColdStorage::new(path)?),
+        #[cfg(feature = "s3-cold-storage")]
+        ColdStorageBackendConfig::S3 { endpoint, bucket, region, prefix } => Arc::new(
+            reth_provider::providers::S3ColdStorage::new(
+                endpoint.clone(),
+                bucket,
+                region,
+                std::env::var("AWS_ACCESS_KEY_ID").map_err(|_| {
+                    eyre::eyre!("AWS_ACCESS_KEY_ID is required for S3 cold storage")
+                })?,
+                std::env::var("AWS_SECRET_ACCESS_KEY").map_err(|_| {
+                    eyre::eyre!("AWS_SECRET_ACCESS_KEY is required for S3 cold storage")
+                })?,
+            )?
+            .with_prefix(prefix),
+        ),
+        #[cfg(not(feature = "s3-cold-storage"))]
+        ColdStorageBackendConfig::S3 { .. } => {
+            eyre::bail!(
+                "S3 cold storage requires reth to be built with the `s3-cold-storage` feature"
+            )
+        }
+    };
+
+    let cache_dir = config
+        .cache_dir
+        .clone()
+        .unwrap_or_else(|| static_files_dir.with_file_name("static_files_cache"));
+
+    Ok(ColdStorageConfig::new(storage, cache_dir)
+        .with_segments(config.segments.iter().copied())
+        .with_keep_recent_blocks(config.keep_recent_blocks)
+        .with_cache_max_bytes(config.cache_max_size))
+}
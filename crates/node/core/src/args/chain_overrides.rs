@@ -0,0 +1,231 @@
+//! clap [Args](clap::Args) for overriding the chain ID and hardfork schedule
+
+use clap::Args;
+use reth_chainspec::{ChainSpecOverrides, EthereumHardfork, ForkCondition};
+
+/// Parameters for overriding the chain ID and hardfork schedule of the configured chain.
+///
+/// Overrides of pre-merge hardforks take the activation block, overrides of post-merge hardforks
+/// the activation timestamp of the hardfork.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Chain overrides")]
+pub struct ChainOverrideArgs {
+    /// Overrides the chain ID of the configured chain.
+    ///
+    /// The datadir of the configured chain keeps being used, so together with hardfork overrides
+    /// this runs a shadow fork that branches off the datadir's current head. `reth node
+    /// --shadow-fork.block` branches it off an earlier block.
+    #[arg(long = "override.chain-id", value_name = "CHAIN_ID")]
+    pub chain_id: Option<u64>,
+
+    /// Overrides the activation block of the Homestead hardfork.
+    #[arg(long = "override.homestead", value_name = "BLOCK")]
+    pub homestead: Option<u64>,
+
+    /// Overrides the activation block of the DAO hardfork.
+    #[arg(long = "override.dao", value_name = "BLOCK")]
+    pub dao: Option<u64>,
+
+    /// Overrides the activation block of the Tangerine Whistle hardfork.
+    #[arg(long = "override.tangerine", value_name = "BLOCK")]
+    pub tangerine: Option<u64>,
+
+    /// Overrides the activation block of the Spurious Dragon hardfork.
+    #[arg(long = "override.spurious-dragon", value_name = "BLOCK")]
+    pub spurious_dragon: Option<u64>,
+
+    /// Overrides the activation block of the Byzantium hardfork.
+    #[arg(long = "override.byzantium", value_name = "BLOCK")]
+    pub byzantium: Option<u64>,
+
+    /// Overrides the activation block of the Constantinople hardfork.
+    #[arg(long = "override.constantinople", value_name = "BLOCK")]
+    pub constantinople: Option<u64>,
+
+    /// Overrides the activation block of the Petersburg hardfork.
+    #[arg(long = "override.petersburg", value_name = "BLOCK")]
+    pub petersburg: Option<u64>,
+
+    /// Overrides the activation block of the Istanbul hardfork.
+    #[arg(long = "override.istanbul", value_name = "BLOCK")]
+    pub istanbul: Option<u64>,
+
+    /// Overrides the activation block of the Muir Glacier hardfork.
+    #[arg(long = "override.muir-glacier", value_name = "BLOCK")]
+    pub muir_glacier: Option<u64>,
+
+    /// Overrides the activation block of the Berlin hardfork.
+    #[arg(long = "override.berlin", value_name = "BLOCK")]
+    pub berlin: Option<u64>,
+
+    /// Overrides the activation block of the London hardfork.
+    #[arg(long = "override.london", value_name = "BLOCK")]
+    pub london: Option<u64>,
+
+    /// Overrides the activation block of the Arrow Glacier hardfork.
+    #[arg(long = "override.arrow-glacier", value_name = "BLOCK")]
+    pub arrow_glacier: Option<u64>,
+
+    /// Overrides the activation block of the Gray Glacier hardfork.
+    #[arg(long = "override.gray-glacier", value_name = "BLOCK")]
+    pub gray_glacier: Option<u64>,
+
+    /// Overrides the activation timestamp of the Shanghai hardfork.
+    #[arg(long = "override.shanghai", value_name = "TIMESTAMP")]
+    pub shanghai: Option<u64>,
+
+    /// Overrides the activation timestamp of the Cancun hardfork.
+    #[arg(long = "override.cancun", value_name = "TIMESTAMP")]
+    pub cancun: Option<u64>,
+
+    /// Overrides the activation timestamp of the Prague hardfork.
+    #[arg(long = "override.prague", value_name = "TIMESTAMP")]
+    pub prague: Option<u64>,
+
+    /// Overrides the activation timestamp of the Osaka hardfork.
+    #[arg(long = "override.osaka", value_name = "TIMESTAMP")]
+    pub osaka: Option<u64>,
+
+    /// Overrides the activation timestamp of the BPO1 hardfork.
+    #[arg(long = "override.bpo1", value_name = "TIMESTAMP")]
+    pub bpo1: Option<u64>,
+
+    /// Overrides the activation timestamp of the BPO2 hardfork.
+    #[arg(long = "override.bpo2", value_name = "TIMESTAMP")]
+    pub bpo2: Option<u64>,
+
+    /// Overrides the activation timestamp of the BPO3 hardfork.
+    #[arg(long = "override.bpo3", value_name = "TIMESTAMP")]
+    pub bpo3: Option<u64>,
+
+    /// Overrides the activation timestamp of the BPO4 hardfork.
+    #[arg(long = "override.bpo4", value_name = "TIMESTAMP")]
+    pub bpo4: Option<u64>,
+
+    /// Overrides the activation timestamp of the BPO5 hardfork.
+    #[arg(long = "override.bpo5", value_name = "TIMESTAMP")]
+    pub bpo5: Option<u64>,
+
+    /// Overrides the activation timestamp of the Amsterdam hardfork.
+    #[arg(long = "override.amsterdam", value_name = "TIMESTAMP")]
+    pub amsterdam: Option<u64>,
+}
+
+/// Parameters for branching a shadow fork off an earlier block of the datadir.
+#[derive(Debug, Clone, Copy, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Chain overrides")]
+pub struct ShadowForkArgs {
+    /// Runs the node as a shadow fork branching off the datadir at the given block.
+    ///
+    /// Requires a new chain ID via `--override.chain-id`. On the first start, the datadir is
+    /// unwound to this block before the overridden chain continues on top of it. Later starts with
+    /// the same block keep the blocks of the shadow fork.
+    #[arg(long = "shadow-fork.block", value_name = "BLOCK", requires = "chain_id")]
+    pub block: Option<u64>,
+}
+
+impl ChainOverrideArgs {
+    /// Returns the [`ChainSpecOverrides`] configured by these arguments.
+    pub fn overrides(&self) -> ChainSpecOverrides {
+        let blocks = [
+            (EthereumHardfork::Homestead, self.homestead),
+            (EthereumHardfork::Dao, self.dao),
+            (EthereumHardfork::Tangerine, self.tangerine),
+            (EthereumHardfork::SpuriousDragon, self.spurious_dragon),
+            (EthereumHardfork::Byzantium, self.byzantium),
+            (EthereumHardfork::Constantinople, self.constantinople),
+            (EthereumHardfork::Petersburg, self.petersburg),
+            (EthereumHardfork::Istanbul, self.istanbul),
+            (EthereumHardfork::MuirGlacier, self.muir_glacier),
+            (EthereumHardfork::Berlin, self.berlin),
+            (EthereumHardfork::London, self.london),
+            (EthereumHardfork::ArrowGlacier, self.arrow_glacier),
+            (EthereumHardfork::GrayGlacier, self.gray_glacier),
+        ]
+        .into_iter()
+        .filter_map(|(fork, block)| block.map(|block| (fork, ForkCondition::Block(block))));
+        let timestamps = [
+            (EthereumHardfork::Shanghai, self.shanghai),
+            (EthereumHardfork::Cancun, self.cancun),
+            (EthereumHardfork::Prague, self.prague),
+            (EthereumHardfork::Osaka, self.osaka),
+            (EthereumHardfork::Bpo1, self.bpo1),
+            (EthereumHardfork::Bpo2, self.bpo2),
+            (EthereumHardfork::Bpo3, self.bpo3),
+            (EthereumHardfork::Bpo4, self.bpo4),
+            (EthereumHardfork::Bpo5, self.bpo5),
+            (EthereumHardfork::Amsterdam, self.amsterdam),
+        ]
+        .into_iter()
+        .filter_map(|(fork, timestamp)| {
+            timestamp.map(|timestamp| (fork, ForkCondition::Timestamp(timestamp)))
+        });
+
+        ChainSpecOverrides {
+            chain_id: self.chain_id,
+            hardforks: blocks.chain(timestamps).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    /// A helper type to parse the shadow fork args together with the overrides they require
+    #[derive(Parser)]
+    struct ShadowForkParser {
+        #[command(flatten)]
+        overrides: ChainOverrideArgs,
+        #[command(flatten)]
+        shadow_fork: ShadowForkArgs,
+    }
+
+    #[test]
+    fn test_parse_shadow_fork_args() {
+        let args = ShadowForkParser::parse_from([
+            "reth",
+            "--override.chain-id",
+            "1337",
+            "--shadow-fork.block",
+            "100",
+        ]);
+        assert_eq!(args.overrides.chain_id, Some(1337));
+        assert_eq!(args.shadow_fork.block, Some(100));
+
+        assert!(ShadowForkParser::try_parse_from(["reth", "--shadow-fork.block", "100"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_chain_override_args() {
+        let args = CommandParser::<ChainOverrideArgs>::parse_from([
+            "reth",
+            "--override.chain-id",
+            "1337",
+            "--override.gray-glacier",
+            "15050000",
+            "--override.amsterdam",
+            "2000000000",
+        ])
+        .args;
+
+        assert_eq!(
+            args.overrides(),
+            ChainSpecOverrides {
+                chain_id: Some(1337),
+                hardforks: vec![
+                    (EthereumHardfork::GrayGlacier, ForkCondition::Block(15_050_000)),
+                    (EthereumHardfork::Amsterdam, ForkCondition::Timestamp(2_000_000_000)),
+                ],
+            }
+        );
+    }
+}
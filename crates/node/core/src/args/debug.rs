@@ -50,6 +50,28 @@ pub struct DebugArgs {
     )]
     pub rpc_consensus_url: Option<String>,
 
+    /// Runs a fake consensus client that follows the beacon chain through the light client
+    /// updates of a Beacon API endpoint. Sync committee signatures are verified, starting from
+    /// the trusted `--debug.beacon-checkpoint`.
+    #[arg(
+        long = "debug.beacon-api-url",
+        help_heading = "Debug",
+        conflicts_with_all = ["tip", "etherscan", "rpc_consensus_url"],
+        requires = "beacon_checkpoint",
+        value_name = "BEACON_API_URL"
+    )]
+    pub beacon_api_url: Option<String>,
+
+    /// Trusted beacon block root the light client bootstraps from, e.g. a recent finalized
+    /// checkpoint.
+    #[arg(
+        long = "debug.beacon-checkpoint",
+        help_heading = "Debug",
+        requires = "beacon_api_url",
+        value_name = "BLOCK_ROOT"
+    )]
+    pub beacon_checkpoint: Option<B256>,
+
     /// If provided, the engine will skip `n` consecutive FCUs.
     #[arg(long = "debug.skip-fcu", help_heading = "Debug")]
     pub skip_fcu: Option<usize>,
@@ -134,6 +156,8 @@ impl Default for DebugArgs {
             max_block: None,
             etherscan: None,
             rpc_consensus_url: None,
+            beacon_api_url: None,
+            beacon_checkpoint: None,
             skip_fcu: None,
             skip_new_payload: None,
             skip_state_root: false,
@@ -382,6 +406,31 @@ mod tests {
         assert_eq!(args, expected_args);
     }
 
+    #[test]
+    fn test_parse_beacon_light_client_args() {
+        let expected_args = DebugArgs {
+            beacon_api_url: Some("http://localhost:5052".to_string()),
+            beacon_checkpoint: Some(B256::with_last_byte(1)),
+            ..Default::default()
+        };
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.beacon-api-url",
+            "http://localhost:5052",
+            "--debug.beacon-checkpoint",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+        ])
+        .args;
+        assert_eq!(args, expected_args);
+
+        assert!(CommandParser::<DebugArgs>::try_parse_from([
+            "reth",
+            "--debug.beacon-api-url",
+            "http://localhost:5052",
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_parse_invalid_block_args_none() {
         let expected_args = DebugArgs {
@@ -537,6 +537,21 @@ pub struct EngineArgs {
     #[arg(long = "engine.disable-bal-batch-io", default_value_t = false)]
     pub disable_bal_batch_io: bool,
 
+    /// Execute blocks without a BAL (Block Access List) with the speculative parallel executor.
+    ///
+    /// Transactions are executed optimistically in parallel and re-executed in order when they
+    /// conflict with an earlier transaction of the block.
+    #[arg(long = "engine.speculative-parallel-execution", default_value_t = false)]
+    pub speculative_parallel_execution: bool,
+
+    /// Persist the hot working set of the execution cache across restarts.
+    ///
+    /// The most frequently accessed accounts and storage slots are written to
+    /// `<DIR>/<CHAIN_ID>/execution-cache.snapshot` on shutdown and restored in the background on
+    /// startup if the snapshot matches the persisted head.
+    #[arg(long = "engine.execution-cache-snapshot", default_value_t = false)]
+    pub execution_cache_snapshot: bool,
+
     /// Add random jitter before each proof computation (trie-debug only).
     /// Each proof worker sleeps for a random duration up to this value before
     /// starting work. Useful for stress-testing timing-sensitive proof logic.
@@ -626,6 +641,8 @@ impl Default for EngineArgs {
             bal_parallel_execution_disabled,
             bal_parallel_state_root_disabled,
             disable_bal_batch_io: false,
+            speculative_parallel_execution: false,
+            execution_cache_snapshot: false,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
         }
@@ -692,7 +709,8 @@ impl EngineArgs {
             .with_suppress_persistence_during_build(self.suppress_persistence_during_build)
             .without_bal_parallel_execution(self.bal_parallel_execution_disabled)
             .without_bal_parallel_state_root(self.bal_parallel_state_root_disabled)
-            .without_bal_batch_io(self.disable_bal_batch_io);
+            .without_bal_batch_io(self.disable_bal_batch_io)
+            .with_speculative_parallel_execution(self.speculative_parallel_execution);
         #[cfg(feature = "trie-debug")]
         let config = config.with_proof_jitter(self.proof_jitter);
         config
@@ -812,6 +830,8 @@ mod tests {
             bal_parallel_execution_disabled: true,
             bal_parallel_state_root_disabled: true,
             disable_bal_batch_io: true,
+            speculative_parallel_execution: true,
+            execution_cache_snapshot: true,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
         };
@@ -859,6 +879,8 @@ mod tests {
             "--engine.disable-bal-parallel-execution",
             "--engine.disable-bal-parallel-state-root",
             "--engine.disable-bal-batch-io",
+            "--engine.speculative-parallel-execution",
+            "--engine.execution-cache-snapshot",
         ])
         .args;
 
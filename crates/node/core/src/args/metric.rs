@@ -5,6 +5,15 @@ use std::{net::SocketAddr, time::Duration};
 /// Default push gateway interval in seconds.
 const DEFAULT_PUSH_GATEWAY_INTERVAL_SECS: u64 = 5;
 
+/// Default maximum age of the canonical head in seconds.
+const DEFAULT_HEALTH_MAX_HEAD_LAG_SECS: u64 = 60;
+
+/// Default maximum number of canonical blocks that are not persisted yet.
+const DEFAULT_HEALTH_MAX_PERSISTENCE_BACKLOG: u64 = 128;
+
+/// Default minimum number of connected peers.
+const DEFAULT_HEALTH_MIN_PEERS: usize = 1;
+
 /// Metrics configuration.
 #[derive(Debug, Clone, Parser)]
 pub struct MetricArgs {
@@ -35,6 +44,35 @@ pub struct MetricArgs {
         help_heading = "Metrics"
     )]
     pub push_gateway_interval: Duration,
+
+    /// Maximum age in seconds of the canonical head before `/health/ready` reports the node as
+    /// not ready.
+    #[arg(
+        long = "metrics.health.max-head-lag",
+        default_value_t = DEFAULT_HEALTH_MAX_HEAD_LAG_SECS,
+        value_name = "SECONDS",
+        help_heading = "Metrics"
+    )]
+    pub health_max_head_lag_secs: u64,
+
+    /// Maximum number of canonical blocks not yet persisted to disk before `/health/ready`
+    /// reports the node as not ready.
+    #[arg(
+        long = "metrics.health.max-persistence-backlog",
+        default_value_t = DEFAULT_HEALTH_MAX_PERSISTENCE_BACKLOG,
+        value_name = "BLOCKS",
+        help_heading = "Metrics"
+    )]
+    pub health_max_persistence_backlog: u64,
+
+    /// Minimum number of connected peers before `/health/ready` reports the node as ready.
+    #[arg(
+        long = "metrics.health.min-peers",
+        default_value_t = DEFAULT_HEALTH_MIN_PEERS,
+        value_name = "PEERS",
+        help_heading = "Metrics"
+    )]
+    pub health_min_peers: usize,
 }
 
 impl Default for MetricArgs {
@@ -43,6 +81,9 @@ impl Default for MetricArgs {
             prometheus: None,
             push_gateway_url: None,
             push_gateway_interval: Duration::from_secs(DEFAULT_PUSH_GATEWAY_INTERVAL_SECS),
+            health_max_head_lag_secs: DEFAULT_HEALTH_MAX_HEAD_LAG_SECS,
+            health_max_persistence_backlog: DEFAULT_HEALTH_MAX_PERSISTENCE_BACKLOG,
+            health_min_peers: DEFAULT_HEALTH_MIN_PEERS,
         }
     }
 }
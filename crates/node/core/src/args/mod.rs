@@ -26,7 +26,7 @@ pub use log::{ColorMode, DefaultLogArgs, LogArgs, Verbosity};
 
 /// `TraceArgs` for tracing and spans support
 mod trace;
-pub use trace::{DefaultTraceValues, OtlpInitStatus, OtlpLogsStatus, TraceArgs};
+pub use trace::{DefaultTraceValues, OtlpInitStatus, OtlpLogsStatus, OtlpMetricsStatus, TraceArgs};
 
 /// `MetricArgs` to configure metrics.
 mod metric;
@@ -52,6 +52,10 @@ pub use txpool::{DefaultTxPoolValues, TxPoolArgs};
 mod dev;
 pub use dev::DevArgs;
 
+/// `ChainOverrideArgs` for overriding the chain ID and hardfork schedule
+mod chain_overrides;
+pub use chain_overrides::{ChainOverrideArgs, ShadowForkArgs};
+
 /// PruneArgs for configuring the pruning and full node
 mod pruning;
 pub use pruning::{DefaultPruningValues, PruneConfigKind, PruningArgs};
@@ -80,5 +84,9 @@ pub use storage::{DefaultStorageValues, StorageArgs};
 mod jit;
 pub use jit::JitArgs;
 
+/// `ReplicaArgs` for serving notifications to read-only replicas.
+mod replica;
+pub use replica::ReplicaArgs;
+
 mod error;
 pub mod types;
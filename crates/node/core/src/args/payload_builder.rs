@@ -128,6 +128,13 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of blobs to include per block.
     #[arg(long = "builder.max-blobs", value_name = "COUNT")]
     pub max_blobs_per_block: Option<u64>,
+
+    /// Accept bundles through `mev_sendBundle` and `eth_sendBundle` and include them at the top
+    /// of built blocks.
+    ///
+    /// Bundles are only received on the transports with the `mev` RPC module enabled.
+    #[arg(long = "builder.bundles")]
+    pub bundles: bool,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -140,6 +147,7 @@ impl Default for PayloadBuilderArgs {
             deadline: Duration::from_secs(defaults.deadline.parse().unwrap()),
             max_payload_tasks: defaults.max_payload_tasks,
             max_blobs_per_block: None,
+            bundles: false,
         }
     }
 }
@@ -240,6 +248,13 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_args_with_bundles() {
+        let args =
+            CommandParser::<PayloadBuilderArgs>::parse_from(["reth", "--builder.bundles"]).args;
+        assert!(args.bundles);
+    }
+
     #[test]
     fn test_default_extra_data() {
         let extra_data = default_extra_data();
@@ -76,6 +76,7 @@ impl Default for DefaultPruningValues {
                 // This field is ignored when full_bodies_history_use_pre_merge is true
                 bodies_history: None,
                 receipts_log_filter: Default::default(),
+                history_retained_addresses: Default::default(),
             },
             full_bodies_history_use_pre_merge: true,
             minimal_prune_modes: PruneModes {
@@ -86,6 +87,7 @@ impl Default for DefaultPruningValues {
                 storage_history: Some(PruneMode::Distance(MINIMUM_UNWIND_SAFE_DISTANCE)),
                 bodies_history: Some(PruneMode::Distance(MINIMUM_UNWIND_SAFE_DISTANCE)),
                 receipts_log_filter: Default::default(),
+                history_retained_addresses: Default::default(),
             },
         }
     }
@@ -232,6 +234,19 @@ pub struct PruningArgs {
     #[arg(long = "prune.storage-history.before", alias = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
     pub storage_history_before: Option<BlockNumber>,
 
+    // History retention
+    /// Comma-separated list of addresses whose full account and storage history is never pruned.
+    ///
+    /// Historical state queries for other addresses fail below the history prune checkpoint. An
+    /// address retained after pruning started keeps its history from the prune checkpoint at the
+    /// first prune run after it was added, and queries for it below that block fail.
+    #[arg(
+        long = "prune.history.retain-addresses",
+        value_name = "ADDRESSES",
+        value_delimiter = ','
+    )]
+    pub history_retained_addresses: Vec<Address>,
+
     // Bodies
     /// Prune bodies before the merge block.
     #[arg(long = "prune.bodies.pre-merge", value_name = "BLOCKS", conflicts_with_all = &["bodies_distance", "bodies_before"])]
@@ -314,6 +329,10 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if !self.history_retained_addresses.is_empty() {
+            config.segments.history_retained_addresses =
+                self.history_retained_addresses.iter().copied().collect();
+        }
         if let Some(receipt_logs) =
             self.receipts_log_filter.as_ref().filter(|c| !c.is_empty()).cloned()
         {
@@ -510,6 +529,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_history_retained_addresses() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--full",
+            "--prune.history.retain-addresses",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+        ])
+        .args;
+        let config = args.prune_config(MAINNET.as_ref()).unwrap();
+        assert_eq!(
+            config.segments.history_retained_addresses,
+            [
+                address!("0x0000000000000000000000000000000000000001"),
+                address!("0x0000000000000000000000000000000000000002"),
+            ]
+            .into()
+        );
+        assert!(config.segments.account_history.is_some());
+    }
+
     #[test]
     fn parse_receiptslogfilter() {
         let default_args = PruningArgs::default();
@@ -0,0 +1,51 @@
+//! clap [Args](clap::Args) for read-only replica notifications
+
+use clap::Args;
+use std::path::PathBuf;
+
+/// Parameters for serving notifications to read-only replicas sharing this node's datadir.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Replica")]
+pub struct ReplicaArgs {
+    /// Serve canonical in-memory blocks and forkchoice updates to read-only `reth rpc-node`
+    /// instances that share this node's datadir.
+    ///
+    /// Without this, replicas only see blocks once they are persisted.
+    #[arg(long = "replica.notifications", default_value_t = false)]
+    pub notifications: bool,
+
+    /// Path of the Unix socket used for replica notifications.
+    ///
+    /// Defaults to `<DATADIR>/replica.ipc`.
+    #[arg(long = "replica.socket", value_name = "PATH", requires = "notifications")]
+    pub socket: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_replica_args() {
+        let args = CommandParser::<ReplicaArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ReplicaArgs::default());
+
+        let args = CommandParser::<ReplicaArgs>::parse_from([
+            "reth",
+            "--replica.notifications",
+            "--replica.socket",
+            "/tmp/replica.ipc",
+        ])
+        .args;
+        assert!(args.notifications);
+        assert_eq!(args.socket, Some(PathBuf::from("/tmp/replica.ipc")));
+    }
+}
@@ -72,6 +72,7 @@ impl StaticFilesArgs {
                     .or(minimal_blocks_per_file)
                     .or(config.blocks_per_file.storage_change_sets),
             },
+            cold: config.cold,
         }
     }
 }
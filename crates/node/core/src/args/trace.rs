@@ -2,13 +2,17 @@
 
 use clap::{builder::Resettable, Parser};
 use eyre::WrapErr;
+use reth_cli_util::parse_duration_from_secs;
 use reth_tracing::{tracing_subscriber::EnvFilter, Layers};
 use reth_tracing_otlp::OtlpProtocol;
-use std::sync::OnceLock;
+use std::{sync::OnceLock, time::Duration};
 use url::Url;
 
 static TRACE_DEFAULTS: OnceLock<DefaultTraceValues> = OnceLock::new();
 
+/// Default interval between two OTLP metrics exports in seconds.
+const DEFAULT_METRICS_OTLP_INTERVAL_SECS: u64 = 10;
+
 /// Overridable defaults for OTLP trace configuration.
 ///
 /// Downstream binaries that embed reth can call
@@ -18,8 +22,10 @@ static TRACE_DEFAULTS: OnceLock<DefaultTraceValues> = OnceLock::new();
 pub struct DefaultTraceValues {
     otlp: Option<Url>,
     logs_otlp: Option<Url>,
+    metrics_otlp: Option<Url>,
     otlp_default_endpoint: String,
     logs_otlp_default_endpoint: String,
+    metrics_otlp_default_endpoint: String,
     protocol: OtlpProtocol,
     service_name: String,
     service_version: Option<String>,
@@ -33,8 +39,10 @@ impl Default for DefaultTraceValues {
         Self {
             otlp: None,
             logs_otlp: None,
+            metrics_otlp: None,
             otlp_default_endpoint: "http://localhost:4318/v1/traces".to_string(),
             logs_otlp_default_endpoint: "http://localhost:4318/v1/logs".to_string(),
+            metrics_otlp_default_endpoint: "http://localhost:4318/v1/metrics".to_string(),
             protocol: OtlpProtocol::Http,
             service_name: "reth".to_string(),
             service_version: None,
@@ -68,6 +76,12 @@ impl DefaultTraceValues {
         self
     }
 
+    /// Set the default OTLP metrics endpoint.
+    pub fn with_metrics_otlp(mut self, metrics_otlp: Option<Url>) -> Self {
+        self.metrics_otlp = metrics_otlp;
+        self
+    }
+
     /// Set the endpoint used when `--tracing-otlp` is provided without a value.
     pub fn with_otlp_default_endpoint(mut self, endpoint: impl Into<String>) -> Self {
         self.otlp_default_endpoint = endpoint.into();
@@ -80,6 +94,12 @@ impl DefaultTraceValues {
         self
     }
 
+    /// Set the endpoint used when `--metrics-otlp` is provided without a value.
+    pub fn with_metrics_otlp_default_endpoint(mut self, endpoint: impl Into<String>) -> Self {
+        self.metrics_otlp_default_endpoint = endpoint.into();
+        self
+    }
+
     /// Set the default OTLP transport protocol.
     pub const fn with_protocol(mut self, protocol: OtlpProtocol) -> Self {
         self.protocol = protocol;
@@ -170,9 +190,9 @@ pub struct TraceArgs {
     )]
     pub logs_otlp: Option<Url>,
 
-    /// OTLP transport protocol to use for exporting traces and logs.
+    /// OTLP transport protocol to use for exporting traces, logs and metrics.
     ///
-    /// - `http`: expects endpoint path to end with `/v1/traces` or `/v1/logs`
+    /// - `http`: expects endpoint path to end with `/v1/traces`, `/v1/logs` or `/v1/metrics`
     /// - `grpc`: expects endpoint without a path
     ///
     /// Defaults to HTTP if not specified.
@@ -267,6 +287,40 @@ pub struct TraceArgs {
         help_heading = "Tracing"
     )]
     pub sample_ratio: Option<f64>,
+
+    /// Enable `Opentelemetry` metrics export to an OTLP endpoint.
+    ///
+    /// Metrics are exported in addition to the Prometheus endpoint.
+    ///
+    /// If no value provided, defaults based on protocol:
+    /// - HTTP: `http://localhost:4318/v1/metrics`
+    /// - gRPC: `http://localhost:4317`
+    ///
+    /// Example: --metrics-otlp=http://collector:4318/v1/metrics
+    #[arg(
+        long = "metrics-otlp",
+        env = "OTEL_EXPORTER_OTLP_METRICS_ENDPOINT",
+        global = true,
+        value_name = "URL",
+        num_args = 0..=1,
+        default_value = Resettable::from(DefaultTraceValues::get_global().metrics_otlp.as_ref().map(|url| url.as_str().into())),
+        default_missing_value = DefaultTraceValues::get_global().metrics_otlp_default_endpoint.as_str(),
+        require_equals = true,
+        value_parser = parse_otlp_endpoint,
+        help_heading = "Metrics"
+    )]
+    pub metrics_otlp: Option<Url>,
+
+    /// Interval in seconds between two OTLP metrics exports.
+    #[arg(
+        long = "metrics-otlp.interval",
+        global = true,
+        value_name = "SECONDS",
+        default_value = "10",
+        value_parser = parse_duration_from_secs,
+        help_heading = "Metrics"
+    )]
+    pub metrics_otlp_interval: Duration,
 }
 
 impl Default for TraceArgs {
@@ -281,6 +335,8 @@ impl Default for TraceArgs {
             sample_ratio: defaults.sample_ratio,
             service_name: defaults.service_name.clone(),
             service_version: defaults.service_version.clone(),
+            metrics_otlp: defaults.metrics_otlp.clone(),
+            metrics_otlp_interval: Duration::from_secs(DEFAULT_METRICS_OTLP_INTERVAL_SECS),
         }
     }
 }
@@ -363,6 +419,37 @@ impl TraceArgs {
             Ok(OtlpLogsStatus::Disabled)
         }
     }
+
+    /// Returns the configuration for OTLP metrics export.
+    ///
+    /// Unlike traces and logs, metrics are not exported through a tracing layer, so the caller is
+    /// responsible for installing a metrics recorder that bridges to the configured endpoint.
+    pub fn otlp_metrics_config(&mut self) -> eyre::Result<OtlpMetricsStatus> {
+        if let Some(endpoint) = self.metrics_otlp.as_mut() {
+            self.protocol.validate_metrics_endpoint(endpoint)?;
+
+            #[cfg(feature = "otlp-metrics")]
+            {
+                let mut config = reth_tracing_otlp::OtlpMetricsConfig::new(
+                    self.service_name.clone(),
+                    endpoint.clone(),
+                    self.protocol,
+                    self.metrics_otlp_interval,
+                )?;
+                if let Some(version) = &self.service_version {
+                    config = config.with_service_version(version.clone());
+                }
+
+                Ok(OtlpMetricsStatus::Enabled(config))
+            }
+            #[cfg(not(feature = "otlp-metrics"))]
+            {
+                Ok(OtlpMetricsStatus::NoFeature)
+            }
+        } else {
+            Ok(OtlpMetricsStatus::Disabled)
+        }
+    }
 }
 
 /// Status of OTLP tracing initialization.
@@ -387,6 +474,18 @@ pub enum OtlpLogsStatus {
     NoFeature,
 }
 
+/// Status of OTLP metrics export configuration.
+#[derive(Debug)]
+pub enum OtlpMetricsStatus {
+    /// OTLP metrics export is enabled with the given configuration.
+    #[cfg(feature = "otlp-metrics")]
+    Enabled(reth_tracing_otlp::OtlpMetricsConfig),
+    /// OTLP metrics export is disabled (no endpoint configured).
+    Disabled,
+    /// OTLP metrics arguments provided but feature is not compiled.
+    NoFeature,
+}
+
 // Parses an OTLP endpoint url.
 fn parse_otlp_endpoint(arg: &str) -> eyre::Result<Url> {
     Url::parse(arg).wrap_err("Invalid URL for OTLP trace output")
@@ -431,6 +530,8 @@ mod tests {
             service_name: defaults.service_name.clone(),
             service_version: defaults.service_version.clone(),
             sample_ratio: defaults.sample_ratio,
+            metrics_otlp: defaults.metrics_otlp.clone(),
+            metrics_otlp_interval: std::time::Duration::from_secs(10),
         };
 
         assert!(args.otlp.is_some());
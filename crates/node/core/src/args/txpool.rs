@@ -3,17 +3,22 @@
 use crate::cli::config::RethTransactionPoolConfig;
 use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT_30M, MIN_PROTOCOL_BASE_FEE};
 use alloy_primitives::Address;
-use clap::{builder::Resettable, Args};
+use clap::{
+    builder::{RangedU64ValueParser, Resettable},
+    Args,
+};
 use reth_cli_util::{parse_duration_from_secs_or_ms, parsers::format_duration_as_secs_or_ms};
 use reth_transaction_pool::{
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    journal::DEFAULT_JOURNAL_INTERVAL,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
     LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, DEFAULT_TXPOOL_SHARDS,
+    MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
 use std::{path::PathBuf, sync::OnceLock, time::Duration};
 
@@ -44,6 +49,7 @@ pub struct DefaultTxPoolValues {
     blob_transaction_price_bump: u128,
     max_tx_input_bytes: usize,
     max_cached_entries: u32,
+    blob_sidecar_retention: u64,
     no_locals: bool,
     locals: Vec<Address>,
     no_local_transactions_propagation: bool,
@@ -52,8 +58,12 @@ pub struct DefaultTxPoolValues {
     new_tx_listener_buffer_size: usize,
     max_new_pending_txs_notifications: usize,
     max_queued_lifetime: Duration,
+    history_size: usize,
+    shards: usize,
     transactions_backup_path: Option<PathBuf>,
     disable_transactions_backup: bool,
+    persist: bool,
+    persist_interval: Duration,
     max_batch_size: usize,
 }
 
@@ -182,6 +192,13 @@ impl DefaultTxPoolValues {
         self
     }
 
+    /// Set the default number of blocks the blob sidecars of finalized transactions are retained
+    /// for
+    pub const fn with_blob_sidecar_retention(mut self, v: u64) -> Self {
+        self.blob_sidecar_retention = v;
+        self
+    }
+
     /// Set whether to disable local transaction exemptions by default
     pub const fn with_no_locals(mut self, v: bool) -> Self {
         self.no_locals = v;
@@ -230,6 +247,18 @@ impl DefaultTxPoolValues {
         self
     }
 
+    /// Set the default number of transactions the lifecycle event history is kept for
+    pub const fn with_history_size(mut self, v: usize) -> Self {
+        self.history_size = v;
+        self
+    }
+
+    /// Set the default number of shards the pool is split into
+    pub const fn with_shards(mut self, v: usize) -> Self {
+        self.shards = v;
+        self
+    }
+
     /// Set the default transactions backup path
     pub fn with_transactions_backup_path(mut self, v: Option<PathBuf>) -> Self {
         self.transactions_backup_path = v;
@@ -242,6 +271,18 @@ impl DefaultTxPoolValues {
         self
     }
 
+    /// Set whether to persist all pool transactions to the journal by default
+    pub const fn with_persist(mut self, v: bool) -> Self {
+        self.persist = v;
+        self
+    }
+
+    /// Set the default interval at which the transaction pool journal is written
+    pub const fn with_persist_interval(mut self, v: Duration) -> Self {
+        self.persist_interval = v;
+        self
+    }
+
     /// Set the default max batch size
     pub const fn with_max_batch_size(mut self, v: usize) -> Self {
         self.max_batch_size = v;
@@ -271,6 +312,7 @@ impl Default for DefaultTxPoolValues {
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
+            blob_sidecar_retention: 0,
             no_locals: false,
             locals: Vec::new(),
             no_local_transactions_propagation: false,
@@ -279,8 +321,12 @@ impl Default for DefaultTxPoolValues {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            history_size: 0,
+            shards: DEFAULT_TXPOOL_SHARDS,
             transactions_backup_path: None,
             disable_transactions_backup: false,
+            persist: false,
+            persist_interval: DEFAULT_JOURNAL_INTERVAL,
             max_batch_size: 1,
         }
     }
@@ -364,6 +410,13 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.max-cached-entries", alias = "txpool.max_cached_entries", default_value_t = DefaultTxPoolValues::get_global().max_cached_entries)]
     pub max_cached_entries: u32,
 
+    /// Number of blocks the blob sidecars of included transactions are retained for once they are
+    /// finalized, e.g. `131072` for 4096 epochs. Retained sidecars are served by
+    /// `reth_getBlobSidecars` and `engine_getBlobs*` and pruned by the pruner. `0` deletes them
+    /// once finalized.
+    #[arg(long = "txpool.blob-sidecar-retention", value_name = "BLOCKS", default_value_t = DefaultTxPoolValues::get_global().blob_sidecar_retention)]
+    pub blob_sidecar_retention: u64,
+
     /// Flag to disable local transaction exemptions.
     #[arg(long = "txpool.nolocals", default_value_t = DefaultTxPoolValues::get_global().no_locals)]
     pub no_locals: bool,
@@ -395,6 +448,18 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION", default_value = format_duration_as_secs_or_ms(DefaultTxPoolValues::get_global().max_queued_lifetime))]
     pub max_queued_lifetime: Duration,
 
+    /// Number of transactions the lifecycle event history is kept for, queryable via
+    /// `txpool_transactionHistory`. Disabled by default.
+    #[arg(long = "txpool.history-size", value_name = "COUNT", default_value_t = DefaultTxPoolValues::get_global().history_size)]
+    pub history_size: usize,
+
+    /// Number of shards the pool is split into by sender, each guarded by its own lock.
+    ///
+    /// More shards reduce lock contention under high transaction ingress. The sub-pool limits are
+    /// split evenly across the shards.
+    #[arg(long = "txpool.shards", value_name = "COUNT", default_value_t = DefaultTxPoolValues::get_global().shards, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub shards: usize,
+
     /// Path to store the local transaction backup at, to survive node restarts.
     #[arg(long = "txpool.transactions-backup", alias = "txpool.journal", value_name = "PATH", default_value = Resettable::from(DefaultTxPoolValues::get_global().transactions_backup_path.as_ref().map(|v| v.to_string_lossy().into())))]
     pub transactions_backup_path: Option<PathBuf>,
@@ -408,6 +473,17 @@ pub struct TxPoolArgs {
     )]
     pub disable_transactions_backup: bool,
 
+    /// Periodically persists all transactions of the pool, including blob transactions, to a
+    /// journal that is reloaded on startup.
+    ///
+    /// The sidecars of journaled blob transactions are kept in the blob store across restarts.
+    #[arg(long = "txpool.persist", default_value_t = DefaultTxPoolValues::get_global().persist)]
+    pub persist: bool,
+
+    /// Interval at which the transaction pool journal is written.
+    #[arg(long = "txpool.persist-interval", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION", default_value = format_duration_as_secs_or_ms(DefaultTxPoolValues::get_global().persist_interval))]
+    pub persist_interval: Duration,
+
     /// Max batch size for transaction pool insertions
     #[arg(long = "txpool.max-batch-size", default_value_t = DefaultTxPoolValues::get_global().max_batch_size)]
     pub max_batch_size: usize,
@@ -453,6 +529,7 @@ impl Default for TxPoolArgs {
             blob_transaction_price_bump,
             max_tx_input_bytes,
             max_cached_entries,
+            blob_sidecar_retention,
             no_locals,
             locals,
             no_local_transactions_propagation,
@@ -461,8 +538,12 @@ impl Default for TxPoolArgs {
             new_tx_listener_buffer_size,
             max_new_pending_txs_notifications,
             max_queued_lifetime,
+            history_size,
+            shards,
             transactions_backup_path,
             disable_transactions_backup,
+            persist,
+            persist_interval,
             max_batch_size,
         } = DefaultTxPoolValues::get_global().clone();
         Self {
@@ -485,6 +566,7 @@ impl Default for TxPoolArgs {
             blob_transaction_price_bump,
             max_tx_input_bytes,
             max_cached_entries,
+            blob_sidecar_retention,
             no_locals,
             locals,
             no_local_transactions_propagation,
@@ -493,8 +575,12 @@ impl Default for TxPoolArgs {
             new_tx_listener_buffer_size,
             max_new_pending_txs_notifications,
             max_queued_lifetime,
+            history_size,
+            shards,
             transactions_backup_path,
             disable_transactions_backup,
+            persist,
+            persist_interval,
             max_batch_size,
         }
     }
@@ -539,6 +625,8 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
+            max_history_transactions: self.history_size,
+            shards: self.shards,
             max_inflight_delegated_slot_limit: default_config.max_inflight_delegated_slot_limit,
         }
     }
@@ -611,6 +699,7 @@ mod tests {
             blob_transaction_price_bump: 25,
             max_tx_input_bytes: 131072,
             max_cached_entries: 200,
+            blob_sidecar_retention: 131072,
             no_locals: true,
             locals: vec![
                 address!("0x0000000000000000000000000000000000000001"),
@@ -622,8 +711,12 @@ mod tests {
             new_tx_listener_buffer_size: 256,
             max_new_pending_txs_notifications: 128,
             max_queued_lifetime: Duration::from_secs(7200),
+            history_size: 5000,
+            shards: 8,
             transactions_backup_path: Some(PathBuf::from("/tmp/txpool-backup")),
             disable_transactions_backup: false,
+            persist: true,
+            persist_interval: Duration::from_secs(30),
             max_batch_size: 10,
         };
 
@@ -665,6 +758,8 @@ mod tests {
             "131072",
             "--txpool.max-cached-entries",
             "200",
+            "--txpool.blob-sidecar-retention",
+            "131072",
             "--txpool.nolocals",
             "--txpool.locals",
             "0x0000000000000000000000000000000000000001",
@@ -681,8 +776,15 @@ mod tests {
             "128",
             "--txpool.lifetime",
             "7200",
+            "--txpool.history-size",
+            "5000",
+            "--txpool.shards",
+            "8",
             "--txpool.transactions-backup",
             "/tmp/txpool-backup",
+            "--txpool.persist",
+            "--txpool.persist-interval",
+            "30",
             "--txpool.max-batch-size",
             "10",
         ])
@@ -346,6 +346,14 @@ impl<D> ChainPath<D> {
         self.data_dir().join("blobstore")
     }
 
+    /// Returns the path to the directory where the blob sidecars of finalized transactions are
+    /// retained.
+    ///
+    /// `<DIR>/<CHAIN_ID>/blobstore-retained`
+    pub fn retained_blob_sidecars(&self) -> PathBuf {
+        self.data_dir().join("blobstore-retained")
+    }
+
     /// Returns the path to the local transactions backup file
     ///
     /// `<DIR>/<CHAIN_ID>/txpool-transactions-backup.rlp`
@@ -353,6 +361,34 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the execution cache snapshot
+    ///
+    /// `<DIR>/<CHAIN_ID>/execution-cache.snapshot`
+    pub fn execution_cache_snapshot(&self) -> PathBuf {
+        self.data_dir().join("execution-cache.snapshot")
+    }
+
+    /// Returns the path to the journal of all transactions in the pool
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-journal.json`
+    pub fn txpool_journal(&self) -> PathBuf {
+        self.data_dir().join("txpool-journal.json")
+    }
+
+    /// Returns the path to the Unix socket used to notify read-only replicas.
+    ///
+    /// `<DIR>/<CHAIN_ID>/replica.ipc`
+    pub fn replica_socket(&self) -> PathBuf {
+        self.data_dir().join("replica.ipc")
+    }
+
+    /// Returns the path to the Unix socket used to request backups from a running node.
+    ///
+    /// `<DIR>/<CHAIN_ID>/backup.ipc`
+    pub fn backup_socket(&self) -> PathBuf {
+        self.data_dir().join("backup.ipc")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -3,7 +3,8 @@
 use crate::{
     args::{
         DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, JitArgs, NetworkArgs,
-        PayloadBuilderArgs, PruningArgs, RpcServerArgs, StaticFilesArgs, StorageArgs, TxPoolArgs,
+        PayloadBuilderArgs, PruningArgs, ReplicaArgs, RpcServerArgs, ShadowForkArgs,
+        StaticFilesArgs, StorageArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -158,6 +159,12 @@ pub struct NodeConfig<ChainSpec> {
 
     /// All JIT related arguments with --jit prefix
     pub jit: JitArgs,
+
+    /// All replica notification related arguments with --replica prefix
+    pub replica: ReplicaArgs,
+
+    /// All shadow fork related arguments with --shadow-fork prefix
+    pub shadow_fork: ShadowForkArgs,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -191,6 +198,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             static_files: StaticFilesArgs::default(),
             storage: StorageArgs::default(),
             jit: JitArgs::default(),
+            replica: ReplicaArgs::default(),
+            shadow_fork: ShadowForkArgs::default(),
         }
     }
 
@@ -272,6 +281,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             static_files,
             storage,
             jit,
+            replica,
+            shadow_fork,
             ..
         } = self;
         NodeConfig {
@@ -293,6 +304,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             static_files,
             storage,
             jit,
+            replica,
+            shadow_fork,
         }
     }
 
@@ -375,6 +388,18 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the replica notification args for the node
+    pub fn with_replica(mut self, replica: ReplicaArgs) -> Self {
+        self.replica = replica;
+        self
+    }
+
+    /// Set the shadow fork args for the node
+    pub const fn with_shadow_fork(mut self, shadow_fork: ShadowForkArgs) -> Self {
+        self.shadow_fork = shadow_fork;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig>
     where
@@ -592,6 +617,8 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             static_files: self.static_files,
             storage: self.storage,
             jit: self.jit,
+            replica: self.replica,
+            shadow_fork: self.shadow_fork,
         }
     }
 
@@ -635,6 +662,8 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             static_files: self.static_files,
             storage: self.storage,
             jit: self.jit.clone(),
+            replica: self.replica.clone(),
+            shadow_fork: self.shadow_fork,
         }
     }
 }
@@ -0,0 +1,261 @@
+//! Liveness and readiness checks served next to the metrics.
+
+use serde::Serialize;
+use std::{
+    fmt,
+    sync::{Arc, OnceLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Thresholds used to decide whether the node is ready to serve traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HealthThresholds {
+    /// Maximum age of the canonical head.
+    pub max_head_lag: Duration,
+    /// Maximum number of canonical blocks that are not yet persisted to disk.
+    pub max_persistence_backlog: u64,
+    /// Minimum number of connected peers.
+    pub min_peers: usize,
+}
+
+impl HealthThresholds {
+    /// Checks the snapshot against the thresholds and returns every failed check.
+    pub fn check(&self, snapshot: &HealthSnapshot) -> Vec<ReadinessFailure> {
+        let mut failures = Vec::new();
+
+        if snapshot.pipeline_sync {
+            failures.push(ReadinessFailure::new(
+                "sync",
+                "node is syncing with the pipeline, live sync has not started yet",
+            ));
+        }
+
+        // the head is compared against the current time, so that it is also reported as lagging
+        // if the consensus layer stopped sending updates
+        if snapshot.last_cl_message.is_none() {
+            failures.push(ReadinessFailure::new(
+                "head_lag",
+                "no message received from the consensus layer",
+            ));
+        } else {
+            let lag = snapshot.timestamp.saturating_sub(snapshot.head_timestamp);
+            if lag > self.max_head_lag.as_secs() {
+                failures.push(ReadinessFailure::new(
+                    "head_lag",
+                    format!(
+                        "canonical head {} is {lag}s old, maximum is {}s",
+                        snapshot.head_number,
+                        self.max_head_lag.as_secs()
+                    ),
+                ));
+            }
+        }
+
+        let backlog = snapshot.persistence_backlog;
+        if backlog > self.max_persistence_backlog {
+            failures.push(ReadinessFailure::new(
+                "persistence",
+                format!(
+                    "{backlog} canonical blocks are not persisted, maximum is {}",
+                    self.max_persistence_backlog
+                ),
+            ));
+        }
+
+        if snapshot.peers < self.min_peers {
+            failures.push(ReadinessFailure::new(
+                "peers",
+                format!("{} connected peers, minimum is {}", snapshot.peers, self.min_peers),
+            ));
+        }
+
+        failures
+    }
+}
+
+/// The state of the node that readiness is evaluated against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HealthSnapshot {
+    /// Unix timestamp in seconds at which the snapshot was taken.
+    pub timestamp: u64,
+    /// Whether the node is syncing with the pipeline rather than following the chain live.
+    pub pipeline_sync: bool,
+    /// Unix timestamp in seconds of the last message received from the consensus layer.
+    pub last_cl_message: Option<u64>,
+    /// Number of the canonical head.
+    pub head_number: u64,
+    /// Timestamp of the canonical head.
+    pub head_timestamp: u64,
+    /// Number of canonical blocks that are not yet persisted to disk.
+    pub persistence_backlog: u64,
+    /// Number of connected peers.
+    pub peers: usize,
+}
+
+impl HealthSnapshot {
+    /// Returns the unix timestamp in seconds of the time `elapsed` ago.
+    pub fn unix_timestamp_since(elapsed: Duration) -> u64 {
+        SystemTime::now()
+            .checked_sub(elapsed)
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default()
+            .as_secs()
+    }
+}
+
+/// A source of [`HealthSnapshot`]s.
+pub trait HealthSource: Send + Sync + 'static {
+    /// Returns the current state of the node.
+    fn snapshot(&self) -> HealthSnapshot;
+}
+
+impl<F> HealthSource for F
+where
+    F: Fn() -> HealthSnapshot + Send + Sync + 'static,
+{
+    fn snapshot(&self) -> HealthSnapshot {
+        self()
+    }
+}
+
+/// A failed readiness check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReadinessFailure {
+    /// Name of the failed check.
+    pub check: &'static str,
+    /// Explanation of the failure.
+    pub reason: String,
+}
+
+impl ReadinessFailure {
+    fn new(check: &'static str, reason: impl Into<String>) -> Self {
+        Self { check, reason: reason.into() }
+    }
+}
+
+/// The outcome of the readiness checks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Readiness {
+    /// Whether all checks passed.
+    pub ready: bool,
+    /// The failed checks.
+    pub failures: Vec<ReadinessFailure>,
+}
+
+impl Readiness {
+    /// Creates a new [`Readiness`] from the failed checks.
+    pub const fn new(failures: Vec<ReadinessFailure>) -> Self {
+        Self { ready: failures.is_empty(), failures }
+    }
+}
+
+/// Shared handle to the health of the node.
+///
+/// The metrics server is started before the network and the engine are launched, so the
+/// [`HealthSource`] is installed later with [`NodeHealth::init`]. Until then the node is reported
+/// as not ready.
+#[derive(Clone, Default)]
+pub struct NodeHealth {
+    inner: Arc<OnceLock<(HealthThresholds, Box<dyn HealthSource>)>>,
+}
+
+impl NodeHealth {
+    /// Installs the thresholds and the source of the node state.
+    ///
+    /// Does nothing if the handle is already initialized.
+    pub fn init(&self, thresholds: HealthThresholds, source: impl HealthSource) {
+        if self.inner.set((thresholds, Box::new(source))).is_err() {
+            tracing::warn!(target: "reth::cli", "Node health is already initialized");
+        }
+    }
+
+    /// Evaluates the readiness checks.
+    pub fn readiness(&self) -> Readiness {
+        let failures = match self.inner.get() {
+            Some((thresholds, source)) => thresholds.check(&source.snapshot()),
+            None => vec![ReadinessFailure::new("startup", "node is starting")],
+        };
+        Readiness::new(failures)
+    }
+}
+
+impl fmt::Debug for NodeHealth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeHealth")
+            .field("thresholds", &self.inner.get().map(|(thresholds, _)| thresholds))
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLDS: HealthThresholds = HealthThresholds {
+        max_head_lag: Duration::from_secs(60),
+        max_persistence_backlog: 128,
+        min_peers: 1,
+    };
+
+    fn healthy() -> HealthSnapshot {
+        HealthSnapshot {
+            timestamp: 1_002,
+            pipeline_sync: false,
+            last_cl_message: Some(1_000),
+            head_number: 100,
+            head_timestamp: 996,
+            persistence_backlog: 2,
+            peers: 5,
+        }
+    }
+
+    #[test]
+    fn ready_when_healthy() {
+        assert!(THRESHOLDS.check(&healthy()).is_empty());
+    }
+
+    #[test]
+    fn reports_every_failure() {
+        let snapshot = HealthSnapshot {
+            pipeline_sync: true,
+            last_cl_message: Some(1_000),
+            head_timestamp: 100,
+            persistence_backlog: 100,
+            peers: 0,
+            ..healthy()
+        };
+        let checks = HealthThresholds { max_persistence_backlog: 10, ..THRESHOLDS }
+            .check(&snapshot)
+            .into_iter()
+            .map(|failure| failure.check)
+            .collect::<Vec<_>>();
+        assert_eq!(checks, ["sync", "head_lag", "persistence", "peers"]);
+    }
+
+    #[test]
+    fn not_ready_with_stale_cl_message() {
+        // the consensus layer stopped sending updates after the head was received
+        let snapshot = HealthSnapshot { timestamp: 2_000, ..healthy() };
+        let failures = THRESHOLDS.check(&snapshot);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].check, "head_lag");
+        assert_eq!(failures[0].reason, "canonical head 100 is 1004s old, maximum is 60s");
+    }
+
+    #[test]
+    fn not_ready_without_cl_message() {
+        let snapshot = HealthSnapshot { last_cl_message: None, ..healthy() };
+        let failures = THRESHOLDS.check(&snapshot);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].check, "head_lag");
+    }
+
+    #[test]
+    fn not_ready_before_init() {
+        let health = NodeHealth::default();
+        assert!(!health.readiness().ready);
+
+        health.init(THRESHOLDS, healthy);
+        assert_eq!(health.readiness(), Readiness { ready: true, failures: Vec::new() });
+    }
+}
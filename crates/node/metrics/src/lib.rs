@@ -8,8 +8,11 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod chain;
+pub mod health;
 /// The metrics hooks for prometheus.
 pub mod hooks;
+#[cfg(feature = "otlp")]
+pub mod otlp;
 pub mod process;
 pub mod recorder;
 /// The metric server serving the metrics.
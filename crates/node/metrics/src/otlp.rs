@@ -0,0 +1,325 @@
+//! Bridge from the [`metrics`] recorder to `OpenTelemetry` meters exported over OTLP.
+
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use opentelemetry::{
+    metrics::{Meter, MeterProvider},
+    KeyValue,
+};
+use opentelemetry_sdk::metrics::SdkMeterProvider;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+pub use reth_tracing_otlp::OtlpMetricsConfig;
+
+/// Name of the `OpenTelemetry` meter all metrics are recorded with.
+const METER_NAME: &str = "reth";
+
+/// A [`Recorder`] that records all metrics with `OpenTelemetry` instruments.
+///
+/// Counters, gauges and histograms are mapped to the `OpenTelemetry` instruments of the same
+/// kind, and metric labels are recorded as attributes. The exported resource attributes are
+/// configured on the [`SdkMeterProvider`].
+///
+/// The `metrics` macros register a metric on every use, so the instruments are kept per [`Key`]
+/// and every registration of a key returns the same handle.
+#[derive(Debug)]
+pub struct OtlpRecorder {
+    provider: SdkMeterProvider,
+    meter: Meter,
+    descriptions: RwLock<HashMap<KeyName, (Option<Unit>, SharedString)>>,
+    counters: Registry<OtlpCounter>,
+    gauges: Registry<OtlpGauge>,
+    histograms: Registry<OtlpHistogram>,
+}
+
+/// Instruments of one kind, keyed by the metric key they were registered with.
+type Registry<T> = RwLock<HashMap<Key, Arc<T>>>;
+
+/// Returns the instrument registered for the key, creating it if the key is new.
+fn get_or_register<T>(registry: &Registry<T>, key: &Key, create: impl FnOnce() -> T) -> Arc<T> {
+    if let Some(instrument) = registry.read().unwrap().get(key) {
+        return Arc::clone(instrument)
+    }
+    Arc::clone(registry.write().unwrap().entry(key.clone()).or_insert_with(|| Arc::new(create())))
+}
+
+impl OtlpRecorder {
+    /// Creates a new [`OtlpRecorder`] recording metrics with a meter of the given provider.
+    pub fn new(provider: SdkMeterProvider) -> Self {
+        let meter = provider.meter(METER_NAME);
+        Self {
+            provider,
+            meter,
+            descriptions: RwLock::default(),
+            counters: RwLock::default(),
+            gauges: RwLock::default(),
+            histograms: RwLock::default(),
+        }
+    }
+
+    /// Creates a new [`OtlpRecorder`] exporting metrics according to the given configuration.
+    ///
+    /// Note: gRPC export must be configured from within a Tokio runtime.
+    pub fn from_config(config: &OtlpMetricsConfig) -> eyre::Result<Self> {
+        Ok(Self::new(reth_tracing_otlp::meter_provider(config)?))
+    }
+
+    /// Returns the meter provider exporting the recorded metrics.
+    pub const fn provider(&self) -> &SdkMeterProvider {
+        &self.provider
+    }
+
+    fn describe(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.descriptions.write().unwrap().insert(key, (unit, description));
+    }
+
+    /// Returns the name, description and unit of the instrument for the given key.
+    fn instrument_info(&self, key: &Key) -> (String, Option<String>, Option<&'static str>) {
+        let descriptions = self.descriptions.read().unwrap();
+        let (unit, description) = descriptions
+            .get(key.name())
+            .map(|(unit, description)| (unit.map(otel_unit), Some(description.to_string())))
+            .unwrap_or_default();
+        (key.name().to_string(), description, unit)
+    }
+}
+
+impl Recorder for OtlpRecorder {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(key, unit, description)
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(key, unit, description)
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(key, unit, description)
+    }
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        Counter::from_arc(get_or_register(&self.counters, key, || {
+            let (name, description, unit) = self.instrument_info(key);
+            let mut builder = self.meter.u64_counter(name);
+            if let Some(description) = description {
+                builder = builder.with_description(description);
+            }
+            if let Some(unit) = unit {
+                builder = builder.with_unit(unit);
+            }
+            OtlpCounter {
+                counter: builder.build(),
+                attributes: attributes(key),
+                value: AtomicU64::new(0),
+            }
+        }))
+    }
+
+    fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        Gauge::from_arc(get_or_register(&self.gauges, key, || {
+            let (name, description, unit) = self.instrument_info(key);
+            let mut builder = self.meter.f64_gauge(name);
+            if let Some(description) = description {
+                builder = builder.with_description(description);
+            }
+            if let Some(unit) = unit {
+                builder = builder.with_unit(unit);
+            }
+            OtlpGauge {
+                gauge: builder.build(),
+                attributes: attributes(key),
+                value: AtomicU64::new(0f64.to_bits()),
+            }
+        }))
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        Histogram::from_arc(get_or_register(&self.histograms, key, || {
+            let (name, description, unit) = self.instrument_info(key);
+            let mut builder = self.meter.f64_histogram(name);
+            if let Some(description) = description {
+                builder = builder.with_description(description);
+            }
+            if let Some(unit) = unit {
+                builder = builder.with_unit(unit);
+            }
+            OtlpHistogram { histogram: builder.build(), attributes: attributes(key) }
+        }))
+    }
+}
+
+/// A counter that keeps track of its total to support absolute values.
+#[derive(Debug)]
+struct OtlpCounter {
+    counter: opentelemetry::metrics::Counter<u64>,
+    attributes: Vec<KeyValue>,
+    value: AtomicU64,
+}
+
+impl CounterFn for OtlpCounter {
+    fn increment(&self, value: u64) {
+        self.value.fetch_add(value, Ordering::Relaxed);
+        self.counter.add(value, &self.attributes);
+    }
+
+    fn absolute(&self, value: u64) {
+        let previous = self.value.fetch_max(value, Ordering::Relaxed);
+        if value > previous {
+            self.counter.add(value - previous, &self.attributes);
+        }
+    }
+}
+
+/// A gauge that keeps track of its value to support increments and decrements.
+#[derive(Debug)]
+struct OtlpGauge {
+    gauge: opentelemetry::metrics::Gauge<f64>,
+    attributes: Vec<KeyValue>,
+    value: AtomicU64,
+}
+
+impl OtlpGauge {
+    fn update(&self, f: impl Fn(f64) -> f64) {
+        let previous = self
+            .value
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                Some(f(f64::from_bits(bits)).to_bits())
+            })
+            .expect("closure always returns a value");
+        self.gauge.record(f(f64::from_bits(previous)), &self.attributes);
+    }
+}
+
+impl GaugeFn for OtlpGauge {
+    fn increment(&self, value: f64) {
+        self.update(|current| current + value)
+    }
+
+    fn decrement(&self, value: f64) {
+        self.update(|current| current - value)
+    }
+
+    fn set(&self, value: f64) {
+        self.value.store(value.to_bits(), Ordering::Relaxed);
+        self.gauge.record(value, &self.attributes);
+    }
+}
+
+#[derive(Debug)]
+struct OtlpHistogram {
+    histogram: opentelemetry::metrics::Histogram<f64>,
+    attributes: Vec<KeyValue>,
+}
+
+impl HistogramFn for OtlpHistogram {
+    fn record(&self, value: f64) {
+        self.histogram.record(value, &self.attributes);
+    }
+}
+
+/// Converts the labels of the key into `OpenTelemetry` attributes.
+fn attributes(key: &Key) -> Vec<KeyValue> {
+    key.labels()
+        .map(|label| KeyValue::new(label.key().to_string(), label.value().to_string()))
+        .collect()
+}
+
+/// Converts the unit into its UCUM representation used by `OpenTelemetry`.
+const fn otel_unit(unit: Unit) -> &'static str {
+    match unit {
+        Unit::Count => "1",
+        Unit::Percent => "%",
+        Unit::Seconds => "s",
+        Unit::Milliseconds => "ms",
+        Unit::Microseconds => "us",
+        Unit::Nanoseconds => "ns",
+        Unit::Tebibytes => "TiBy",
+        Unit::Gibibytes => "GiBy",
+        Unit::Mebibytes => "MiBy",
+        Unit::Kibibytes => "KiBy",
+        Unit::Bytes => "By",
+        Unit::TerabitsPerSecond => "Tbit/s",
+        Unit::GigabitsPerSecond => "Gbit/s",
+        Unit::MegabitsPerSecond => "Mbit/s",
+        Unit::KilobitsPerSecond => "kbit/s",
+        Unit::BitsPerSecond => "bit/s",
+        Unit::CountPerSecond => "1/s",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meter() -> Meter {
+        SdkMeterProvider::builder().build().meter("test")
+    }
+
+    #[test]
+    fn counter_tracks_absolute_values() {
+        let counter = OtlpCounter {
+            counter: meter().u64_counter("counter").build(),
+            attributes: Vec::new(),
+            value: AtomicU64::new(0),
+        };
+
+        counter.increment(2);
+        counter.absolute(5);
+        assert_eq!(counter.value.load(Ordering::Relaxed), 5);
+
+        // Counters are monotonic, lower absolute values are ignored
+        counter.absolute(3);
+        assert_eq!(counter.value.load(Ordering::Relaxed), 5);
+    }
+
+    #[test]
+    fn gauge_tracks_value() {
+        let gauge = OtlpGauge {
+            gauge: meter().f64_gauge("gauge").build(),
+            attributes: Vec::new(),
+            value: AtomicU64::new(0f64.to_bits()),
+        };
+
+        gauge.set(3.0);
+        gauge.increment(2.5);
+        gauge.decrement(1.0);
+        assert_eq!(f64::from_bits(gauge.value.load(Ordering::Relaxed)), 4.5);
+    }
+
+    #[test]
+    fn registrations_share_state() {
+        let recorder = OtlpRecorder::new(SdkMeterProvider::builder().build());
+        let key = Key::from_parts("requests", vec![metrics::Label::new("method", "get")]);
+        let metadata = Metadata::new("test", metrics::Level::INFO, None);
+
+        recorder.register_counter(&key, &metadata).absolute(10);
+        recorder.register_counter(&key, &metadata).absolute(10);
+        recorder.register_counter(&key, &metadata).increment(1);
+        assert_eq!(recorder.counters.read().unwrap()[&key].value.load(Ordering::Relaxed), 11);
+
+        recorder.register_gauge(&key, &metadata).increment(2.0);
+        recorder.register_gauge(&key, &metadata).increment(3.0);
+        recorder.register_gauge(&key, &metadata).decrement(1.0);
+        let gauge = Arc::clone(&recorder.gauges.read().unwrap()[&key]);
+        assert_eq!(f64::from_bits(gauge.value.load(Ordering::Relaxed)), 4.0);
+
+        // other labels are another metric
+        let other = Key::from_parts("requests", vec![metrics::Label::new("method", "post")]);
+        recorder.register_counter(&other, &metadata).increment(1);
+        assert_eq!(recorder.counters.read().unwrap()[&other].value.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn labels_to_attributes() {
+        let key = Key::from_parts("requests", vec![metrics::Label::new("method", "get")]);
+        assert_eq!(attributes(&key), vec![KeyValue::new("method", "get")]);
+    }
+}
@@ -1,9 +1,13 @@
 //! Prometheus recorder
 
 use eyre::WrapErr;
+use metrics::Recorder;
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 use metrics_util::layers::{PrefixLayer, Stack};
-use std::sync::{atomic::AtomicBool, OnceLock};
+use std::{
+    sync::{atomic::AtomicBool, OnceLock},
+    time::Duration,
+};
 
 /// Installs the Prometheus recorder as the global recorder.
 ///
@@ -49,6 +53,23 @@ pub fn try_install_prometheus_recorder_with_builder(
     Ok(PROMETHEUS_RECORDER_HANDLE.get().expect("recorder is set"))
 }
 
+/// Installs the Prometheus recorder as the global recorder, and additionally exports all metrics
+/// to an OTLP endpoint.
+///
+/// Returns an error if a recorder has already been installed.
+///
+/// Note: gRPC export must be configured from within a Tokio runtime.
+#[cfg(feature = "otlp")]
+pub fn try_install_prometheus_recorder_with_otlp(
+    config: &crate::otlp::OtlpMetricsConfig,
+) -> eyre::Result<&'static PrometheusRecorder> {
+    let recorder = PrometheusRecorder::install_with_otlp(PrometheusBuilder::new(), config)?;
+    PROMETHEUS_RECORDER_HANDLE
+        .set(recorder)
+        .map_err(|_| eyre::eyre!("Prometheus recorder already installed"))?;
+    Ok(PROMETHEUS_RECORDER_HANDLE.get().expect("recorder is set"))
+}
+
 /// A handle to the Prometheus recorder.
 ///
 /// This is intended to be used as the global recorder.
@@ -57,11 +78,22 @@ pub fn try_install_prometheus_recorder_with_builder(
 pub struct PrometheusRecorder {
     handle: PrometheusHandle,
     upkeep: AtomicBool,
+    /// The interval of the OTLP metrics export, if enabled.
+    otlp_interval: Option<Duration>,
+    /// Keeps the OTLP exporter alive.
+    #[cfg(feature = "otlp")]
+    _otlp_provider: Option<opentelemetry_sdk::metrics::SdkMeterProvider>,
 }
 
 impl PrometheusRecorder {
     const fn new(handle: PrometheusHandle) -> Self {
-        Self { handle, upkeep: AtomicBool::new(false) }
+        Self {
+            handle,
+            upkeep: AtomicBool::new(false),
+            otlp_interval: None,
+            #[cfg(feature = "otlp")]
+            _otlp_provider: None,
+        }
     }
 
     /// Returns a reference to the [`PrometheusHandle`].
@@ -69,6 +101,11 @@ impl PrometheusRecorder {
         &self.handle
     }
 
+    /// Returns the interval of the OTLP metrics export, if metrics are also exported over OTLP.
+    pub const fn otlp_interval(&self) -> Option<Duration> {
+        self.otlp_interval
+    }
+
     /// Spawns the upkeep task if there hasn't been one spawned already.
     ///
     /// ## Panics
@@ -115,14 +152,48 @@ impl PrometheusRecorder {
         let recorder = builder.build_recorder();
         let handle = recorder.handle();
 
-        // Build metrics stack
-        Stack::new(recorder)
-            .push(PrefixLayer::new("reth"))
-            .install()
-            .wrap_err("Couldn't set metrics recorder.")?;
+        install_stack(recorder)?;
 
         Ok(Self::new(handle))
     }
+
+    /// Installs Prometheus with a custom builder as the metrics recorder, and additionally
+    /// exports all metrics to an OTLP endpoint.
+    ///
+    /// Caution: This only configures the global recorder and does not spawn the exporter.
+    /// Callers must run [`Self::spawn_upkeep`] manually.
+    #[cfg(feature = "otlp")]
+    pub fn install_with_otlp(
+        builder: PrometheusBuilder,
+        config: &crate::otlp::OtlpMetricsConfig,
+    ) -> eyre::Result<Self> {
+        let recorder = builder.build_recorder();
+        let handle = recorder.handle();
+
+        let otlp = crate::otlp::OtlpRecorder::from_config(config)?;
+        let otlp_provider = otlp.provider().clone();
+
+        install_stack(
+            metrics_util::layers::FanoutBuilder::default()
+                .add_recorder(recorder)
+                .add_recorder(otlp)
+                .build(),
+        )?;
+
+        Ok(Self {
+            otlp_interval: Some(config.interval()),
+            _otlp_provider: Some(otlp_provider),
+            ..Self::new(handle)
+        })
+    }
+}
+
+/// Installs the recorder with the `reth` prefix as the global recorder.
+fn install_stack(recorder: impl Recorder + Sync + 'static) -> eyre::Result<()> {
+    Stack::new(recorder)
+        .push(PrefixLayer::new("reth"))
+        .install()
+        .wrap_err("Couldn't set metrics recorder.")
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 use crate::{
     chain::ChainSpecInfo,
+    health::{NodeHealth, Readiness},
     hooks::{Hook, Hooks},
     process::register_process_metrics,
     recorder::install_prometheus_recorder,
@@ -20,7 +21,7 @@ use std::{convert::Infallible, net::SocketAddr, path::PathBuf, sync::Arc, time::
 /// Configuration for the [`MetricServer`]
 #[derive(Debug)]
 pub struct MetricServerConfig {
-    listen_addr: SocketAddr,
+    listen_addr: Option<SocketAddr>,
     version_info: VersionInfo,
     chain_spec_info: ChainSpecInfo,
     storage_settings_info: Option<StorageSettingsInfo>,
@@ -29,12 +30,16 @@ pub struct MetricServerConfig {
     push_gateway_url: Option<String>,
     push_gateway_interval: Duration,
     pprof_dump_dir: PathBuf,
+    health: Option<NodeHealth>,
+    otlp_interval: Option<Duration>,
 }
 
 impl MetricServerConfig {
-    /// Create a new [`MetricServerConfig`] with the given configuration
+    /// Create a new [`MetricServerConfig`] with the given configuration.
+    ///
+    /// The Prometheus endpoint is only served if a listen address is given.
     pub const fn new(
-        listen_addr: SocketAddr,
+        listen_addr: Option<SocketAddr>,
         version_info: VersionInfo,
         chain_spec_info: ChainSpecInfo,
         task_executor: TaskExecutor,
@@ -51,6 +56,8 @@ impl MetricServerConfig {
             push_gateway_url: None,
             push_gateway_interval: Duration::from_secs(5),
             pprof_dump_dir,
+            health: None,
+            otlp_interval: None,
         }
     }
 
@@ -66,6 +73,21 @@ impl MetricServerConfig {
         self.push_gateway_interval = interval;
         self
     }
+
+    /// Set the interval of the OTLP metrics export.
+    ///
+    /// If set, the hooks are run periodically so that the metrics they collect are exported
+    /// without the Prometheus endpoint being scraped.
+    pub const fn with_otlp_interval(mut self, interval: Option<Duration>) -> Self {
+        self.otlp_interval = interval;
+        self
+    }
+
+    /// Set the node health handle backing the `/health/ready` endpoint.
+    pub fn with_health(mut self, health: NodeHealth) -> Self {
+        self.health = Some(health);
+        self
+    }
 }
 
 /// [`MetricServer`] responsible for serving the metrics endpoint
@@ -92,17 +114,27 @@ impl MetricServer {
             push_gateway_url,
             push_gateway_interval,
             pprof_dump_dir,
+            health,
+            otlp_interval,
         } = &self.config;
 
-        let hooks_for_endpoint = hooks.clone();
-        self.start_endpoint(
-            *listen_addr,
-            Arc::new(move || hooks_for_endpoint.iter().for_each(|hook| hook())),
-            task_executor.clone(),
-            pprof_dump_dir.clone(),
-        )
-        .await
-        .wrap_err_with(|| format!("Could not start Prometheus endpoint at {listen_addr}"))?;
+        if let Some(listen_addr) = listen_addr {
+            let hooks_for_endpoint = hooks.clone();
+            self.start_endpoint(
+                *listen_addr,
+                Arc::new(move || hooks_for_endpoint.iter().for_each(|hook| hook())),
+                task_executor.clone(),
+                pprof_dump_dir.clone(),
+                health.clone(),
+            )
+            .await
+            .wrap_err_with(|| format!("Could not start Prometheus endpoint at {listen_addr}"))?;
+        }
+
+        // Start OTLP hooks task if configured
+        if let Some(interval) = otlp_interval {
+            self.start_otlp_hooks_task(*interval, hooks.clone(), task_executor.clone());
+        }
 
         // Start push-gateway task if configured
         if let Some(url) = push_gateway_url {
@@ -138,6 +170,7 @@ impl MetricServer {
         hook: Arc<F>,
         task_executor: TaskExecutor,
         pprof_dump_dir: PathBuf,
+        health: Option<NodeHealth>,
     ) -> eyre::Result<()> {
         let listener = tokio::net::TcpListener::bind(listen_addr)
             .await
@@ -162,12 +195,20 @@ impl MetricServer {
             let handle = install_prometheus_recorder();
             let hook = hook.clone();
             let pprof_dump_dir = pprof_dump_dir.clone();
+            let health = health.clone();
             let service = tower::service_fn(move |req: Request<_>| {
                 let hook = hook.clone();
                 let pprof_dump_dir = pprof_dump_dir.clone();
+                let health = health.clone();
                 async move {
-                    let response =
-                        handle_request(req.uri().path(), &*hook, handle, &pprof_dump_dir).await;
+                    let response = handle_request(
+                        req.uri().path(),
+                        &*hook,
+                        handle,
+                        &pprof_dump_dir,
+                        health.as_ref(),
+                    )
+                    .await;
                     Ok::<_, Infallible>(response)
                 }
             });
@@ -183,6 +224,23 @@ impl MetricServer {
         Ok(())
     }
 
+    /// Starts a background task that runs the hooks for the OTLP metrics export.
+    ///
+    /// The exporter reads the recorded metrics on its own, but the metrics collected by the hooks,
+    /// like the process and database metrics, are otherwise only updated on a scrape.
+    fn start_otlp_hooks_task(&self, interval: Duration, hooks: Hooks, task_executor: TaskExecutor) {
+        task_executor.spawn_with_graceful_shutdown_signal(async move |mut signal| {
+            tracing::info!(interval = ?interval, "Starting task to collect metrics for OTLP export");
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = &mut signal => break,
+                    _ = interval.tick() => hooks.iter().for_each(|hook| hook()),
+                }
+            }
+        });
+    }
+
     /// Starts a background task to push metrics to a metrics gateway
     fn start_push_gateway_task(
         &self,
@@ -332,8 +390,11 @@ async fn handle_request(
     hook: impl Fn(),
     handle: &crate::recorder::PrometheusRecorder,
     pprof_dump_dir: &PathBuf,
+    health: Option<&NodeHealth>,
 ) -> Response<Full<Bytes>> {
     match path {
+        "/health/live" => json_response(StatusCode::OK, r#"{"status":"ok"}"#.to_string()),
+        "/health/ready" => handle_health_ready(health),
         "/debug/pprof/heap" => handle_pprof_heap(pprof_dump_dir),
         "/debug/tokio/dump" => handle_tokio_dump().await,
         _ => {
@@ -346,6 +407,23 @@ async fn handle_request(
     }
 }
 
+/// Responds with `200 OK` if the node is ready, and `503 Service Unavailable` otherwise.
+///
+/// Without a [`NodeHealth`] handle there is nothing to check and the node is always ready.
+fn handle_health_ready(health: Option<&NodeHealth>) -> Response<Full<Bytes>> {
+    let readiness = health.map(NodeHealth::readiness).unwrap_or_else(|| Readiness::new(Vec::new()));
+    let status = if readiness.ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    let body = serde_json::to_string(&readiness).expect("serializing Readiness should not fail");
+    json_response(status, body)
+}
+
+fn json_response(status: StatusCode, body: String) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
 #[cfg(all(feature = "jemalloc-prof", unix))]
 fn handle_pprof_heap(pprof_dump_dir: &PathBuf) -> Response<Full<Bytes>> {
     use http::header::CONTENT_ENCODING;
@@ -495,7 +573,7 @@ mod tests {
 
         let listen_addr = get_random_available_addr();
         let config = MetricServerConfig::new(
-            listen_addr,
+            Some(listen_addr),
             version_info,
             chain_spec_info,
             runtime.clone(),
@@ -524,4 +602,65 @@ mod tests {
         // Make sure the runtime is dropped after the test runs.
         drop(runtime);
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_health_endpoints() {
+        install_prometheus_recorder();
+
+        let runtime = Runtime::test();
+        let health = NodeHealth::default();
+
+        let listen_addr = get_random_available_addr();
+        let config = MetricServerConfig::new(
+            Some(listen_addr),
+            VersionInfo {
+                version: "test",
+                build_timestamp: "test",
+                cargo_features: "test",
+                git_sha: "test",
+                target_triple: "test",
+                build_profile: "test",
+            },
+            ChainSpecInfo { name: "test".to_string() },
+            runtime.clone(),
+            Hooks::builder().build(),
+            std::env::temp_dir(),
+        )
+        .with_health(health.clone());
+
+        MetricServer::new(config).serve().await.unwrap();
+
+        let client = Client::new();
+        let response =
+            client.get(format!("http://{listen_addr}/health/live")).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Not ready until the health source is installed
+        let ready_url = format!("http://{listen_addr}/health/ready");
+        let response = client.get(&ready_url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["failures"][0]["check"], "startup");
+
+        let thresholds = crate::health::HealthThresholds {
+            max_head_lag: Duration::from_secs(60),
+            max_persistence_backlog: 128,
+            min_peers: 1,
+        };
+        health.init(thresholds, || crate::health::HealthSnapshot {
+            timestamp: 12,
+            pipeline_sync: false,
+            last_cl_message: Some(12),
+            head_number: 1,
+            head_timestamp: 12,
+            persistence_backlog: 0,
+            peers: 1,
+        });
+        let response = client.get(&ready_url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        drop(runtime);
+    }
 }
@@ -0,0 +1,301 @@
+//! Replica side of the notification channel.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use reth_chain_state::{CanonicalInMemoryState, ExecutedBlock, NewCanonicalChain};
+use reth_primitives_traits::{NodePrimitives, SealedHeader};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockHashReader, BlockNumReader, HeaderProvider, ProviderFactory,
+    ProviderResult,
+};
+use std::{path::PathBuf, time::Duration};
+use tracing::{debug, info, trace, warn};
+
+/// Default interval at which the replica polls the shared database for newly persisted blocks.
+pub const DEFAULT_REPLICA_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Keeps the canonical in-memory state of a read-only node in sync with a primary node that shares
+/// its datadir.
+///
+/// The follower tails the shared database for persisted blocks and, if the primary exposes a
+/// notification socket, applies its in-memory blocks and forkchoice updates as they arrive. Without
+/// a socket, the replica only serves data that has already been persisted.
+#[derive(Debug)]
+pub struct ReplicaFollower<N: ProviderNodeTypes> {
+    /// Path of the primary's notification socket, if any.
+    socket: Option<PathBuf>,
+    /// Read-only provider factory over the shared datadir.
+    factory: ProviderFactory<N>,
+    /// The canonical in-memory state served by the replica.
+    state: CanonicalInMemoryState<N::Primitives>,
+    /// Interval for polling the database and reconnecting to the primary.
+    poll_interval: Duration,
+}
+
+impl<N: ProviderNodeTypes> ReplicaFollower<N> {
+    /// Creates a new follower for the given read-only factory and in-memory state.
+    pub const fn new(
+        factory: ProviderFactory<N>,
+        state: CanonicalInMemoryState<N::Primitives>,
+    ) -> Self {
+        Self { socket: None, factory, state, poll_interval: DEFAULT_REPLICA_POLL_INTERVAL }
+    }
+
+    /// Sets the path of the primary's notification socket.
+    pub fn with_socket(mut self, socket: PathBuf) -> Self {
+        self.socket = Some(socket);
+        self
+    }
+
+    /// Sets the database poll interval.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Runs the follower until the returned future is dropped.
+    pub async fn run(self) {
+        let Self { socket, factory, state, poll_interval } = self;
+        let tail = DatabaseTail { factory, state };
+
+        match socket {
+            #[cfg(unix)]
+            Some(socket) => {
+                tokio::join!(
+                    tail.clone().poll(poll_interval),
+                    follow_primary(socket, tail, poll_interval)
+                );
+            }
+            _ => {
+                #[cfg(not(unix))]
+                if socket.is_some() {
+                    warn!(target: "replica", "Replica notifications are only supported on Unix");
+                }
+                tail.poll(poll_interval).await
+            }
+        }
+    }
+}
+
+/// Connects to the primary's notification socket, reconnecting whenever the connection is lost.
+#[cfg(unix)]
+async fn follow_primary<N: ProviderNodeTypes>(
+    socket: PathBuf,
+    tail: DatabaseTail<N>,
+    retry_interval: Duration,
+) {
+    loop {
+        match tokio::net::UnixStream::connect(&socket).await {
+            Ok(mut stream) => {
+                info!(target: "replica", path = %socket.display(), "Connected to primary");
+                match follow(&mut stream, &tail).await {
+                    Ok(()) => info!(target: "replica", "Primary closed the connection"),
+                    Err(err) => warn!(target: "replica", %err, "Lost connection to primary"),
+                }
+            }
+            Err(err) => {
+                trace!(target: "replica", %err, path = %socket.display(), "Primary not reachable");
+            }
+        }
+        tokio::time::sleep(retry_interval).await;
+    }
+}
+
+/// Applies messages received from the primary until the connection is closed.
+#[cfg(unix)]
+async fn follow<N: ProviderNodeTypes>(
+    stream: &mut tokio::net::UnixStream,
+    tail: &DatabaseTail<N>,
+) -> std::io::Result<()> {
+    use crate::message::{into_executed_block, read_message, ReplicaMessage};
+
+    while let Some(message) = read_message::<N::Primitives, _>(stream).await? {
+        let result = match message {
+            ReplicaMessage::Block(chain) => {
+                let Some(block) = into_executed_block(chain) else {
+                    warn!(target: "replica", "Received empty block notification");
+                    continue
+                };
+                match tail.apply_block(block) {
+                    Ok(true) => Ok(()),
+                    // Reconnecting makes the primary send a fresh snapshot.
+                    Ok(false) => {
+                        return Err(std::io::Error::other(
+                            "received block doesn't extend the canonical chain",
+                        ))
+                    }
+                    Err(err) => Err(err),
+                }
+            }
+            ReplicaMessage::Forkchoice { safe, finalized } => {
+                tail.apply_forkchoice(safe, finalized)
+            }
+            ReplicaMessage::Persisted(num_hash) => {
+                trace!(target: "replica", ?num_hash, "Primary persisted blocks");
+                tail.sync()
+            }
+        };
+        result.map_err(std::io::Error::other)?;
+    }
+    Ok(())
+}
+
+/// Reconciles the in-memory state with the shared database.
+#[derive(Debug)]
+struct DatabaseTail<N: ProviderNodeTypes> {
+    factory: ProviderFactory<N>,
+    state: CanonicalInMemoryState<N::Primitives>,
+}
+
+impl<N: ProviderNodeTypes> Clone for DatabaseTail<N> {
+    fn clone(&self) -> Self {
+        Self { factory: self.factory.clone(), state: self.state.clone() }
+    }
+}
+
+impl<N: ProviderNodeTypes> DatabaseTail<N> {
+    /// Periodically syncs with the database.
+    async fn poll(self, poll_interval: Duration) {
+        let mut interval = tokio::time::interval(poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.sync() {
+                warn!(target: "replica", %err, "Failed to sync with the primary's database");
+            }
+        }
+    }
+
+    /// Catches up with the primary's database and drops in-memory blocks that were persisted.
+    fn sync(&self) -> ProviderResult<()> {
+        self.factory.sync_providers_if_needed()?;
+
+        let provider = self.factory.provider()?;
+        let number = provider.best_block_number()?;
+        let Some(hash) = provider.block_hash(number)? else { return Ok(()) };
+        drop(provider);
+
+        let persisted = BlockNumHash::new(number, hash);
+        if self.state.get_persisted_num_hash() == Some(persisted) {
+            return Ok(())
+        }
+        self.state.remove_persisted_blocks(persisted);
+
+        let head = self.state.get_canonical_head();
+        if head.number() < number || (head.number() == number && head.hash() != hash) {
+            // The primary persisted blocks we never received, so anything left in memory is stale.
+            let stale = self.state.canonical_chain().map(|block| block.block()).collect::<Vec<_>>();
+            if !stale.is_empty() {
+                self.state.update_chain(NewCanonicalChain::Reorg { new: Vec::new(), old: stale });
+            }
+            if let Some(header) = self.factory.sealed_header(number)? {
+                debug!(target: "replica", ?persisted, "Advanced head from database");
+                self.state.set_canonical_head(header);
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes the given block the new canonical head, replacing any in-memory blocks at the same or
+    /// a higher height.
+    ///
+    /// Returns `false` without applying the block if its parent is neither the current head nor a
+    /// canonical ancestor of it, in which case the replica missed blocks and has to resync.
+    fn apply_block(&self, block: ExecutedBlock<N::Primitives>) -> ProviderResult<bool> {
+        let number = block.block_number();
+        let persisted = self.state.get_persisted_num_hash();
+        if persisted.is_some_and(|persisted| persisted.number >= number) {
+            trace!(target: "replica", number, "Skipping already persisted block");
+            return Ok(true)
+        }
+
+        let parent_hash = block.recovered_block().parent_hash();
+        let extends_canonical = self.state.get_canonical_head().hash() == parent_hash ||
+            persisted.is_some_and(|persisted| persisted.hash == parent_hash) ||
+            self.state.canonical_chain().any(|state| state.hash() == parent_hash);
+        if !extends_canonical {
+            debug!(target: "replica", number, ?parent_hash, "Rejecting block with unknown parent");
+            return Ok(false)
+        }
+
+        let header = block.recovered_block().clone_sealed_header();
+        let old = self
+            .state
+            .canonical_chain()
+            .filter(|state| state.number() >= number)
+            .map(|state| state.block())
+            .collect::<Vec<_>>();
+        let new_chain = if old.is_empty() {
+            NewCanonicalChain::Commit { new: vec![block] }
+        } else {
+            NewCanonicalChain::Reorg { new: vec![block], old }
+        };
+
+        trace!(target: "replica", number, hash = ?header.hash(), "Applying in-memory block");
+        let notification = new_chain.to_chain_notification();
+        self.state.update_chain(new_chain);
+        self.state.set_canonical_head(header);
+        self.state.notify_canon_state(notification);
+        Ok(true)
+    }
+
+    /// Updates the safe and finalized blocks.
+    fn apply_forkchoice(
+        &self,
+        safe: Option<BlockNumHash>,
+        finalized: Option<BlockNumHash>,
+    ) -> ProviderResult<()> {
+        if let Some(header) = safe.map(|num_hash| self.header(num_hash)).transpose()?.flatten() {
+            self.state.set_safe(header);
+        }
+        if let Some(header) = finalized.map(|num_hash| self.header(num_hash)).transpose()?.flatten()
+        {
+            self.state.set_finalized(header);
+        }
+        Ok(())
+    }
+
+    /// Looks up a header in memory first and then in the database.
+    fn header(
+        &self,
+        num_hash: BlockNumHash,
+    ) -> ProviderResult<Option<SealedHeader<<N::Primitives as NodePrimitives>::BlockHeader>>> {
+        if let Some(state) = self.state.state_by_hash(num_hash.hash) {
+            return Ok(Some(state.block_ref().recovered_block().clone_sealed_header()))
+        }
+        Ok(self
+            .factory
+            .sealed_header(num_hash.number)?
+            .filter(|header| header.hash() == num_hash.hash))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chain_state::test_utils::TestBlockBuilder;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn rejects_block_with_unknown_parent() {
+        let tail = DatabaseTail {
+            factory: create_test_provider_factory(),
+            state: CanonicalInMemoryState::empty(),
+        };
+        let blocks = TestBlockBuilder::eth().get_executed_blocks(1..5).collect::<Vec<_>>();
+        let hash = |index: usize| blocks[index].recovered_block().hash();
+        tail.state.set_canonical_head(blocks[0].recovered_block().clone_sealed_header());
+
+        assert!(tail.apply_block(blocks[1].clone()).unwrap());
+        // the block at index 2 was missed
+        assert!(!tail.apply_block(blocks[3].clone()).unwrap());
+        assert_eq!(tail.state.get_canonical_head().hash(), hash(1));
+
+        assert!(tail.apply_block(blocks[2].clone()).unwrap());
+        assert!(tail.apply_block(blocks[3].clone()).unwrap());
+        assert_eq!(tail.state.get_canonical_head().hash(), hash(3));
+
+        // a block replacing the head extends its parent
+        assert!(tail.apply_block(blocks[3].clone()).unwrap());
+        assert_eq!(tail.state.canonical_chain().count(), 3);
+    }
+}
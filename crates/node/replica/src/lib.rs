@@ -0,0 +1,29 @@
+//! Notification channel that lets read-only nodes follow a running node sharing its datadir.
+//!
+//! The primary node runs a [`ReplicaNotificationServer`] on a Unix socket and streams its
+//! canonical in-memory blocks, forkchoice and persistence updates to connected replicas. A replica
+//! opens the same MDBX, `RocksDB` and static files in read-only mode and uses a
+//! [`ReplicaFollower`] to keep its canonical in-memory state in sync, so that it can serve RPC
+//! requests for blocks the primary has not persisted yet.
+//!
+//! The notification socket is only available on Unix platforms. Elsewhere, replicas only follow
+//! blocks once they are persisted.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+pub mod message;
+pub use message::ReplicaMessage;
+
+#[cfg(unix)]
+mod server;
+#[cfg(unix)]
+pub use server::ReplicaNotificationServer;
+
+mod follower;
+pub use follower::{ReplicaFollower, DEFAULT_REPLICA_POLL_INTERVAL};
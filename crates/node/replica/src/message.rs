@@ -0,0 +1,177 @@
+//! Messages sent from the primary node to replicas.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use reth_chain_state::{ComputedTrieData, ExecutedBlock};
+use reth_execution_types::{
+    serde_bincode_compat, BlockExecutionOutput, BlockExecutionResult, Chain, ExecutionOutcome,
+};
+use reth_primitives_traits::NodePrimitives;
+use reth_trie_common::LazyTrieData;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::{io, sync::Arc};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// Maximum size of a single encoded message.
+///
+/// In-memory blocks carry their full bundle state and trie updates, so this is generous.
+pub const MAX_MESSAGE_SIZE: usize = 512 * 1024 * 1024;
+
+/// A notification sent by the primary node to its replicas.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub enum ReplicaMessage<N: NodePrimitives> {
+    /// A canonical block that has not been persisted yet.
+    ///
+    /// The chain always contains exactly one block. If the replica already tracks in-memory blocks
+    /// at the same or a higher height, they are replaced by this block.
+    Block(#[serde_as(as = "serde_bincode_compat::Chain<'_, N>")] Chain<N>),
+    /// The safe or finalized block changed.
+    Forkchoice {
+        /// The current safe block, if any.
+        safe: Option<BlockNumHash>,
+        /// The current finalized block, if any.
+        finalized: Option<BlockNumHash>,
+    },
+    /// All blocks up to and including the given block were written to disk.
+    Persisted(BlockNumHash),
+}
+
+impl<N: NodePrimitives> ReplicaMessage<N> {
+    /// Creates a [`ReplicaMessage::Block`] from an executed in-memory block.
+    ///
+    /// This waits for the deferred trie data of the block if it hasn't been computed yet.
+    pub fn block(executed: &ExecutedBlock<N>) -> Self {
+        let trie_data = executed.trie_data();
+        Self::Block(Chain::from_block(
+            Arc::clone(&executed.recovered_block),
+            ExecutionOutcome::from((executed.execution_outcome().clone(), executed.block_number())),
+            LazyTrieData::ready(trie_data.hashed_state, trie_data.trie_updates),
+        ))
+    }
+}
+
+/// Converts a single-block chain received in a [`ReplicaMessage::Block`] back into an
+/// [`ExecutedBlock`].
+///
+/// Returns `None` if the chain is empty or doesn't carry trie data.
+pub fn into_executed_block<N: NodePrimitives>(chain: Chain<N>) -> Option<ExecutedBlock<N>> {
+    let (blocks, outcome, trie_data) = chain.into_inner();
+    let block = blocks.into_blocks().next()?;
+    let trie_data = trie_data.into_values().next()?.get().clone();
+
+    let ExecutionOutcome { bundle, receipts, requests, .. } = outcome;
+    let result = BlockExecutionResult {
+        receipts: receipts.into_iter().next().unwrap_or_default(),
+        requests: requests.into_iter().next().unwrap_or_default(),
+        gas_used: block.header().gas_used(),
+        blob_gas_used: block.header().blob_gas_used().unwrap_or_default(),
+    };
+
+    Some(ExecutedBlock::new(
+        block,
+        Arc::new(BlockExecutionOutput { result, state: bundle }),
+        ComputedTrieData {
+            hashed_state: trie_data.hashed_state,
+            trie_updates: trie_data.trie_updates,
+        },
+    ))
+}
+
+/// Encodes a message into a length-prefixed frame.
+pub fn encode_message<N: NodePrimitives>(message: &ReplicaMessage<N>) -> io::Result<Vec<u8>> {
+    let payload = rmp_serde::encode::to_vec(message).map_err(io::Error::other)?;
+    if payload.len() > MAX_MESSAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("replica message too large: {} bytes", payload.len()),
+        ))
+    }
+
+    let mut frame = Vec::with_capacity(4 + payload.len());
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&payload);
+    Ok(frame)
+}
+
+/// Writes an already encoded frame to the given writer.
+pub async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, frame: &[u8]) -> io::Result<()> {
+    writer.write_all(frame).await?;
+    writer.flush().await
+}
+
+/// Reads the next message from the given reader.
+///
+/// Returns `None` if the stream was closed cleanly before a new frame started.
+pub async fn read_message<N: NodePrimitives, R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> io::Result<Option<ReplicaMessage<N>>> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len).await {
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_MESSAGE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("replica message too large: {len} bytes"),
+        ))
+    }
+
+    let mut payload = vec![0u8; len];
+    reader.read_exact(&mut payload).await?;
+    rmp_serde::decode::from_slice(&payload)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chain_state::test_utils::TestBlockBuilder;
+    use reth_ethereum_primitives::EthPrimitives;
+
+    #[tokio::test]
+    async fn message_roundtrip() {
+        let mut builder = TestBlockBuilder::eth();
+        let executed = builder.get_executed_blocks(1..2).next().unwrap();
+
+        let messages = [
+            ReplicaMessage::<EthPrimitives>::block(&executed),
+            ReplicaMessage::Forkchoice {
+                safe: Some(BlockNumHash::new(1, executed.recovered_block().hash())),
+                finalized: None,
+            },
+            ReplicaMessage::Persisted(BlockNumHash::new(0, Default::default())),
+        ];
+
+        let mut buf = Vec::new();
+        for message in &messages {
+            buf.extend(encode_message(message).unwrap());
+        }
+
+        let mut reader = buf.as_slice();
+        let Some(ReplicaMessage::Block(chain)) = read_message(&mut reader).await.unwrap() else {
+            panic!("expected block message")
+        };
+        let decoded = into_executed_block(chain).unwrap();
+        assert_eq!(decoded.recovered_block(), executed.recovered_block());
+        assert_eq!(decoded.execution_outcome().receipts, executed.execution_outcome().receipts);
+        assert_eq!(decoded.execution_outcome().state, executed.execution_outcome().state);
+
+        assert!(matches!(
+            read_message::<EthPrimitives, _>(&mut reader).await.unwrap(),
+            Some(ReplicaMessage::Forkchoice { safe: Some(_), finalized: None })
+        ));
+        assert!(matches!(
+            read_message::<EthPrimitives, _>(&mut reader).await.unwrap(),
+            Some(ReplicaMessage::Persisted(_))
+        ));
+        assert!(read_message::<EthPrimitives, _>(&mut reader).await.unwrap().is_none());
+    }
+}
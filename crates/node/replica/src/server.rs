@@ -0,0 +1,197 @@
+//! Notification server run by the primary node.
+
+use crate::message::{encode_message, write_frame, ReplicaMessage};
+use reth_chain_state::{CanonicalInMemoryState, ExecutedBlock};
+use reth_primitives_traits::NodePrimitives;
+use reth_tasks::TaskExecutor;
+use std::{io, path::PathBuf};
+use tokio::{
+    net::{UnixListener, UnixStream},
+    sync::broadcast::error::RecvError,
+};
+use tracing::{debug, info, trace};
+
+/// Streams the canonical in-memory state of a node to read-only replicas over a Unix socket.
+///
+/// Every replica that connects first receives a snapshot of the current in-memory blocks, the
+/// forkchoice state and the last persisted block, followed by live updates. If a replica falls
+/// behind the canonical state notifications, its connection is dropped and it is expected to
+/// reconnect and receive a fresh snapshot.
+#[derive(Debug)]
+pub struct ReplicaNotificationServer<N: NodePrimitives> {
+    /// Path of the Unix socket.
+    path: PathBuf,
+    /// The canonical in-memory state of the node.
+    state: CanonicalInMemoryState<N>,
+    /// Executor the connections of replicas are served on.
+    executor: TaskExecutor,
+}
+
+impl<N: NodePrimitives> ReplicaNotificationServer<N> {
+    /// Creates a new server that will listen on the given socket path and serve replicas on the
+    /// given executor.
+    pub const fn new(
+        path: PathBuf,
+        state: CanonicalInMemoryState<N>,
+        executor: TaskExecutor,
+    ) -> Self {
+        Self { path, state, executor }
+    }
+
+    /// Binds the socket and serves replicas until the returned future is dropped.
+    ///
+    /// A stale socket file left behind by a previous run is removed before binding.
+    pub async fn run(self) -> io::Result<()> {
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        let listener = UnixListener::bind(&self.path)?;
+        info!(target: "replica", path = %self.path.display(), "Serving replica notifications");
+
+        loop {
+            let (stream, _) = listener.accept().await?;
+            debug!(target: "replica", "Replica connected");
+            let state = self.state.clone();
+            let executor = self.executor.clone();
+            self.executor.spawn_task(async move {
+                if let Err(err) = serve_replica(stream, state, executor).await {
+                    debug!(target: "replica", %err, "Replica disconnected");
+                }
+            });
+        }
+    }
+}
+
+/// Sends the initial snapshot and then forwards live updates to a single replica.
+async fn serve_replica<N: NodePrimitives>(
+    mut stream: UnixStream,
+    state: CanonicalInMemoryState<N>,
+    executor: TaskExecutor,
+) -> io::Result<()> {
+    // Subscribe before taking the snapshot so that no update is missed in between.
+    let mut canon_state = state.subscribe_canon_state();
+    let mut safe = state.subscribe_safe_block();
+    let mut finalized = state.subscribe_finalized_block();
+    let mut persisted = state.subscribe_persisted_block();
+
+    if let Some(num_hash) = state.get_persisted_num_hash() {
+        send(&mut stream, &ReplicaMessage::Persisted(num_hash)).await?;
+    }
+    let mut blocks = state.canonical_chain().map(|block| block.block()).collect::<Vec<_>>();
+    blocks.reverse();
+    for block in blocks {
+        send_block(&mut stream, &executor, block).await?;
+    }
+    send(&mut stream, &forkchoice(&state)).await?;
+
+    loop {
+        tokio::select! {
+            notification = canon_state.recv() => match notification {
+                Ok(notification) => {
+                    for block in notification.committed().blocks_iter() {
+                        // Blocks are looked up in the in-memory state because the notification
+                        // does not carry the full execution output.
+                        let Some(block_state) = state.state_by_hash(block.hash()) else {
+                            trace!(target: "replica", hash = ?block.hash(), "Committed block no longer in memory");
+                            continue
+                        };
+                        send_block(&mut stream, &executor, block_state.block()).await?;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    return Err(io::Error::other(format!(
+                        "replica lagged behind by {skipped} notifications"
+                    )))
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            },
+            Ok(()) = safe.changed() => {
+                safe.borrow_and_update();
+                send(&mut stream, &forkchoice(&state)).await?;
+            }
+            Ok(()) = finalized.changed() => {
+                finalized.borrow_and_update();
+                send(&mut stream, &forkchoice(&state)).await?;
+            }
+            Ok(()) = persisted.changed() => {
+                let num_hash = *persisted.borrow_and_update();
+                if let Some(num_hash) = num_hash {
+                    send(&mut stream, &ReplicaMessage::Persisted(num_hash)).await?;
+                }
+            }
+        }
+    }
+}
+
+/// Returns the current forkchoice message.
+fn forkchoice<N: NodePrimitives>(state: &CanonicalInMemoryState<N>) -> ReplicaMessage<N> {
+    ReplicaMessage::Forkchoice {
+        safe: state.get_safe_num_hash(),
+        finalized: state.get_finalized_num_hash(),
+    }
+}
+
+/// Encodes a block on a blocking thread, since this may wait for trie data and serializes the full
+/// bundle state, and sends it.
+async fn send_block<N: NodePrimitives>(
+    stream: &mut UnixStream,
+    executor: &TaskExecutor,
+    block: ExecutedBlock<N>,
+) -> io::Result<()> {
+    let frame = executor
+        .spawn_blocking(move || encode_message(&ReplicaMessage::block(&block)))
+        .await
+        .map_err(io::Error::other)??;
+    write_frame(stream, &frame).await
+}
+
+async fn send<N: NodePrimitives>(
+    stream: &mut UnixStream,
+    message: &ReplicaMessage<N>,
+) -> io::Result<()> {
+    write_frame(stream, &encode_message(message)?).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::{into_executed_block, read_message};
+    use reth_chain_state::{test_utils::TestBlockBuilder, NewCanonicalChain};
+    use reth_ethereum_primitives::EthPrimitives;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn sends_snapshot_on_connect() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("replica.ipc");
+
+        let state = CanonicalInMemoryState::<EthPrimitives>::empty();
+        let blocks = TestBlockBuilder::eth().get_executed_blocks(1..4).collect::<Vec<_>>();
+        state.update_chain(NewCanonicalChain::Commit { new: blocks.clone() });
+
+        let executor = TaskExecutor::test();
+        let server = ReplicaNotificationServer::new(path.clone(), state, executor.clone());
+        executor.spawn_task(async move { server.run().await.unwrap() });
+
+        let mut stream = loop {
+            if let Ok(stream) = UnixStream::connect(&path).await {
+                break stream
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        };
+
+        for expected in &blocks {
+            let Some(ReplicaMessage::Block(chain)) =
+                read_message::<EthPrimitives, _>(&mut stream).await.unwrap()
+            else {
+                panic!("expected block message")
+            };
+            let block = into_executed_block(chain).unwrap();
+            assert_eq!(block.recovered_block().hash(), expected.recovered_block().hash());
+        }
+        assert!(matches!(
+            read_message::<EthPrimitives, _>(&mut stream).await.unwrap(),
+            Some(ReplicaMessage::Forkchoice { safe: None, finalized: None })
+        ));
+    }
+}
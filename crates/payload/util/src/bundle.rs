@@ -0,0 +1,310 @@
+use alloy_consensus::transaction::{Recovered, TxHashRef};
+use alloy_primitives::{keccak256, map::HashMap, Address, B256};
+use parking_lot::RwLock;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Default maximum number of bundles kept in a [`BundlePool`].
+pub const DEFAULT_MAX_BUNDLES: usize = 1024;
+
+/// A transaction that is part of a [`PoolBundle`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleTransaction<T> {
+    /// The signed transaction with its recovered sender.
+    pub tx: Recovered<T>,
+    /// Whether the transaction is allowed to revert without invalidating the bundle.
+    pub can_revert: bool,
+    /// Percentage of the bundle's refundable value that is paid back for this transaction, if any.
+    pub refund_percent: Option<u64>,
+    /// How the refund of this transaction is split between recipients.
+    ///
+    /// If empty, the refund goes to the signer of the transaction.
+    pub refund_configs: Vec<BundleRefundConfig>,
+}
+
+/// Recipient of a share of a bundle refund.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundleRefundConfig {
+    /// Address that receives the refund.
+    pub address: Address,
+    /// Percentage of the refund paid to this address.
+    pub percent: u64,
+}
+
+/// A bundle of transactions that must be included atomically and in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolBundle<T> {
+    /// Hash of the bundle, derived from the hashes of its transactions.
+    pub hash: B256,
+    /// The transactions of the bundle in execution order.
+    pub transactions: Vec<BundleTransaction<T>>,
+    /// First block the bundle is valid for.
+    pub block_number: u64,
+    /// Last block the bundle is valid for.
+    pub max_block_number: u64,
+    /// Identifier that allows the sender to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+}
+
+impl<T: TxHashRef> PoolBundle<T> {
+    /// Creates a new bundle for the given block range and computes its hash.
+    pub fn new(
+        transactions: Vec<BundleTransaction<T>>,
+        block_number: u64,
+        max_block_number: u64,
+    ) -> Self {
+        let hash =
+            keccak256(transactions.iter().flat_map(|item| item.tx.tx_hash().0).collect::<Vec<_>>());
+        Self { hash, transactions, block_number, max_block_number, replacement_uuid: None }
+    }
+}
+
+impl<T> PoolBundle<T> {
+    /// Sets the replacement UUID of the bundle.
+    pub fn with_replacement_uuid(mut self, replacement_uuid: Option<String>) -> Self {
+        self.replacement_uuid = replacement_uuid;
+        self
+    }
+
+    /// Returns `true` if the bundle can be included in the given block.
+    pub const fn is_valid_for_block(&self, block_number: u64) -> bool {
+        self.block_number <= block_number && block_number <= self.max_block_number
+    }
+}
+
+/// Errors returned when inserting into a [`BundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// The bundle doesn't contain any transactions.
+    #[error("bundle is empty")]
+    EmptyBundle,
+    /// The block range of the bundle is invalid.
+    #[error("invalid bundle block range {block_number}..={max_block_number}")]
+    InvalidBlockRange {
+        /// First block of the range.
+        block_number: u64,
+        /// Last block of the range.
+        max_block_number: u64,
+    },
+    /// The pool has reached its maximum number of bundles.
+    #[error("bundle pool is full")]
+    PoolFull,
+}
+
+/// A shared pool of bundles waiting to be included by the payload builder.
+///
+/// Bundles are kept until their block range has passed or they are removed explicitly, and are
+/// handed out in insertion order. A bundle with the same replacement UUID as an existing one
+/// replaces it.
+#[derive(Debug, Clone)]
+pub struct BundlePool<T> {
+    inner: Arc<RwLock<BundlePoolInner<T>>>,
+}
+
+#[derive(Debug)]
+struct BundlePoolInner<T> {
+    /// Maximum number of bundles in the pool.
+    max_bundles: usize,
+    /// Next insertion id.
+    next_id: u64,
+    /// All bundles by insertion id.
+    bundles: BTreeMap<u64, Arc<PoolBundle<T>>>,
+    /// Insertion ids by bundle hash.
+    by_hash: HashMap<B256, u64>,
+    /// Insertion ids by replacement UUID.
+    by_uuid: HashMap<String, u64>,
+}
+
+impl<T> PartialEq for BundlePool<T> {
+    /// Two pools are equal if they are handles to the same shared pool.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl<T> Eq for BundlePool<T> {}
+
+impl<T> Default for BundlePool<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BUNDLES)
+    }
+}
+
+impl<T> BundlePool<T> {
+    /// Creates a new pool that holds at most `max_bundles` bundles.
+    pub fn new(max_bundles: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(BundlePoolInner {
+                max_bundles,
+                next_id: 0,
+                bundles: BTreeMap::new(),
+                by_hash: HashMap::default(),
+                by_uuid: HashMap::default(),
+            })),
+        }
+    }
+
+    /// Inserts a bundle and returns its hash.
+    ///
+    /// If a bundle with the same replacement UUID exists, it is replaced. Inserting a bundle that
+    /// is already in the pool is a no-op.
+    pub fn insert(&self, bundle: PoolBundle<T>) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+        if bundle.max_block_number < bundle.block_number {
+            return Err(BundlePoolError::InvalidBlockRange {
+                block_number: bundle.block_number,
+                max_block_number: bundle.max_block_number,
+            })
+        }
+
+        let hash = bundle.hash;
+        let mut inner = self.inner.write();
+        if inner.by_hash.contains_key(&hash) {
+            return Ok(hash)
+        }
+
+        if let Some(id) =
+            bundle.replacement_uuid.as_ref().and_then(|uuid| inner.by_uuid.get(uuid).copied())
+        {
+            inner.remove(id);
+        }
+        if inner.bundles.len() >= inner.max_bundles {
+            return Err(BundlePoolError::PoolFull)
+        }
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.by_hash.insert(hash, id);
+        if let Some(uuid) = &bundle.replacement_uuid {
+            inner.by_uuid.insert(uuid.clone(), id);
+        }
+        inner.bundles.insert(id, Arc::new(bundle));
+        Ok(hash)
+    }
+
+    /// Removes the bundle with the given hash.
+    pub fn remove(&self, hash: &B256) -> Option<Arc<PoolBundle<T>>> {
+        let mut inner = self.inner.write();
+        let id = *inner.by_hash.get(hash)?;
+        inner.remove(id)
+    }
+
+    /// Removes the bundle with the given replacement UUID.
+    pub fn remove_by_uuid(&self, uuid: &str) -> Option<Arc<PoolBundle<T>>> {
+        let mut inner = self.inner.write();
+        let id = *inner.by_uuid.get(uuid)?;
+        inner.remove(id)
+    }
+
+    /// Returns the bundle with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<Arc<PoolBundle<T>>> {
+        let inner = self.inner.read();
+        inner.by_hash.get(hash).and_then(|id| inner.bundles.get(id)).cloned()
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.len()
+    }
+
+    /// Returns `true` if the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().bundles.is_empty()
+    }
+
+    /// Returns all bundles that can be included in the given block, in insertion order.
+    ///
+    /// Bundles whose block range ends before the given block are removed from the pool.
+    pub fn bundles_for_block(&self, block_number: u64) -> Vec<Arc<PoolBundle<T>>> {
+        let mut inner = self.inner.write();
+        let expired = inner
+            .bundles
+            .iter()
+            .filter(|(_, bundle)| bundle.max_block_number < block_number)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for id in expired {
+            inner.remove(id);
+        }
+
+        inner
+            .bundles
+            .values()
+            .filter(|bundle| bundle.is_valid_for_block(block_number))
+            .cloned()
+            .collect()
+    }
+}
+
+impl<T> BundlePoolInner<T> {
+    fn remove(&mut self, id: u64) -> Option<Arc<PoolBundle<T>>> {
+        let bundle = self.bundles.remove(&id)?;
+        self.by_hash.remove(&bundle.hash);
+        if let Some(uuid) = &bundle.replacement_uuid {
+            self.by_uuid.remove(uuid);
+        }
+        Some(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Signed, TxEnvelope, TxLegacy};
+    use alloy_primitives::Signature;
+
+    fn tx(nonce: u64) -> BundleTransaction<TxEnvelope> {
+        let tx = Signed::new_unhashed(
+            TxLegacy { nonce, ..Default::default() },
+            Signature::test_signature(),
+        );
+        BundleTransaction {
+            tx: Recovered::new_unchecked(TxEnvelope::Legacy(tx), Address::ZERO),
+            can_revert: false,
+            refund_percent: None,
+            refund_configs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn replaces_by_uuid_and_expires() {
+        let pool = BundlePool::default();
+
+        let first = PoolBundle::new(vec![tx(0)], 1, 2).with_replacement_uuid(Some("a".into()));
+        let first = pool.insert(first).unwrap();
+        let second = PoolBundle::new(vec![tx(1)], 1, 3).with_replacement_uuid(Some("a".into()));
+        let second = pool.insert(second).unwrap();
+
+        assert!(pool.get(&first).is_none());
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.bundles_for_block(3)[0].hash, second);
+        assert!(pool.bundles_for_block(4).is_empty());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn cancels_by_uuid() {
+        let pool = BundlePool::default();
+
+        let bundle = PoolBundle::new(vec![tx(0)], 1, 1).with_replacement_uuid(Some("a".into()));
+        let hash = pool.insert(bundle).unwrap();
+
+        assert!(pool.remove_by_uuid("b").is_none());
+        assert_eq!(pool.remove_by_uuid("a").unwrap().hash, hash);
+        assert!(pool.get(&hash).is_none());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_bundles() {
+        let pool = BundlePool::<TxEnvelope>::new(1);
+        assert_eq!(pool.insert(PoolBundle::new(vec![], 1, 1)), Err(BundlePoolError::EmptyBundle));
+        assert!(matches!(
+            pool.insert(PoolBundle::new(vec![tx(0)], 2, 1)),
+            Err(BundlePoolError::InvalidBlockRange { .. })
+        ));
+        pool.insert(PoolBundle::new(vec![tx(0)], 1, 1)).unwrap();
+        assert_eq!(pool.insert(PoolBundle::new(vec![tx(1)], 1, 1)), Err(BundlePoolError::PoolFull));
+    }
+}
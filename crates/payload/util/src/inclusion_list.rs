@@ -0,0 +1,80 @@
+use alloy_primitives::{Bytes, B256};
+use parking_lot::RwLock;
+use std::{collections::VecDeque, sync::Arc};
+
+/// Default number of inclusion lists kept in an [`InclusionListStore`].
+pub const DEFAULT_MAX_INCLUSION_LISTS: usize = 64;
+
+/// Inclusion lists (EIP-7805) received from the consensus layer, keyed by the hash of the parent
+/// of the block they constrain.
+///
+/// The store is shared between the engine API, which receives the inclusion lists, and the payload
+/// builder, which appends their transactions to the blocks it builds on top of the parent. Only the
+/// most recently inserted lists are kept.
+#[derive(Debug, Clone)]
+pub struct InclusionListStore {
+    inner: Arc<RwLock<VecDeque<(B256, Vec<Bytes>)>>>,
+    max_lists: usize,
+}
+
+impl InclusionListStore {
+    /// Creates a new store that keeps at most `max_lists` inclusion lists.
+    pub fn new(max_lists: usize) -> Self {
+        Self { inner: Default::default(), max_lists }
+    }
+
+    /// Sets the inclusion list for blocks built on top of `parent_hash`, replacing any previous
+    /// list for the same parent.
+    pub fn insert(&self, parent_hash: B256, transactions: Vec<Bytes>) {
+        let mut inner = self.inner.write();
+        inner.retain(|(hash, _)| *hash != parent_hash);
+        inner.push_back((parent_hash, transactions));
+        while inner.len() > self.max_lists {
+            inner.pop_front();
+        }
+    }
+
+    /// Returns the inclusion list for blocks built on top of `parent_hash`.
+    pub fn get(&self, parent_hash: B256) -> Option<Vec<Bytes>> {
+        self.inner
+            .read()
+            .iter()
+            .find(|(hash, _)| *hash == parent_hash)
+            .map(|(_, transactions)| transactions.clone())
+    }
+}
+
+impl Default for InclusionListStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_INCLUSION_LISTS)
+    }
+}
+
+impl PartialEq for InclusionListStore {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for InclusionListStore {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_latest_lists() {
+        let store = InclusionListStore::new(2);
+        let (a, b, c) = (B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3));
+
+        store.insert(a, vec![Bytes::from_static(&[1])]);
+        store.insert(b, vec![]);
+        store.insert(a, vec![Bytes::from_static(&[2])]);
+        assert_eq!(store.get(a), Some(vec![Bytes::from_static(&[2])]));
+
+        store.insert(c, vec![]);
+        assert_eq!(store.get(b), None);
+        assert_eq!(store.get(a), Some(vec![Bytes::from_static(&[2])]));
+        assert_eq!(store.get(c), Some(vec![]));
+    }
+}
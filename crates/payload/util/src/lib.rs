@@ -8,8 +8,17 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod bundle;
+#[cfg(feature = "focil")]
+mod inclusion_list;
 mod traits;
 mod transaction;
 
+pub use bundle::{
+    BundlePool, BundlePoolError, BundleRefundConfig, BundleTransaction, PoolBundle,
+    DEFAULT_MAX_BUNDLES,
+};
+#[cfg(feature = "focil")]
+pub use inclusion_list::{InclusionListStore, DEFAULT_MAX_INCLUSION_LISTS};
 pub use traits::{BestPayloadTransactions, NoopPayloadTransactions, PayloadTransactions};
 pub use transaction::{PayloadTransactionsChain, PayloadTransactionsFixed};
@@ -1,4 +1,7 @@
-use crate::{segments::SegmentSet, Pruner};
+use crate::{
+    segments::{BlobSidecarStore, BlobSidecars, SegmentSet},
+    Pruner,
+};
 use reth_config::PruneConfig;
 use reth_db_api::{table::Value, transaction::DbTxMut};
 use reth_exex_types::FinishedExExHeight;
@@ -8,9 +11,11 @@ use reth_provider::{
     DatabaseProviderFactory, NodePrimitivesProvider, PruneCheckpointReader, PruneCheckpointWriter,
     RocksDBProviderFactory, StageCheckpointReader, StaticFileProviderFactory,
 };
-use reth_prune_types::PruneModes;
-use reth_storage_api::{ChangeSetReader, StorageChangeSetReader, StorageSettingsCache};
-use std::time::Duration;
+use reth_prune_types::{PruneMode, PruneModes};
+use reth_storage_api::{
+    ChangeSetReader, MetadataProvider, MetadataWriter, StorageChangeSetReader, StorageSettingsCache,
+};
+use std::{sync::Arc, time::Duration};
 use tokio::sync::watch;
 
 /// Contains the information required to build a pruner
@@ -28,6 +33,8 @@ pub struct PrunerBuilder {
     minimum_pruning_distance: Option<u64>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// The store of retained blob sidecars and how long they are retained.
+    blob_sidecars: Option<(PruneMode, Arc<dyn BlobSidecarStore>)>,
 }
 
 impl PrunerBuilder {
@@ -79,6 +86,13 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the store of the blob sidecars retained for finalized blocks, which are pruned
+    /// according to the given mode.
+    pub fn blob_sidecars(mut self, mode: PruneMode, store: Arc<dyn BlobSidecarStore>) -> Self {
+        self.blob_sidecars = Some((mode, store));
+        self
+    }
+
     /// Builds a [Pruner] from the current configuration with the given provider factory.
     pub fn build_with_provider_factory<PF>(self, provider_factory: PF) -> Pruner<PF::ProviderRW, PF>
     where
@@ -92,6 +106,8 @@ impl PrunerBuilder {
                                 + ChangeSetReader
                                 + StorageChangeSetReader
                                 + RocksDBProviderFactory
+                                + MetadataProvider
+                                + MetadataWriter
                                 + StaticFileProviderFactory<
                     Primitives: NodePrimitives<SignedTx: Value, Receipt: Value, BlockHeader: Value>,
                 >,
@@ -100,7 +116,10 @@ impl PrunerBuilder {
             >,
     {
         let segments =
-            SegmentSet::from_components(provider_factory.static_file_provider(), self.segments);
+            SegmentSet::from_components(provider_factory.static_file_provider(), self.segments)
+                .segment_opt(
+                    self.blob_sidecars.map(|(mode, store)| BlobSidecars::new(mode, store)),
+                );
 
         let mut pruner = Pruner::new_with_factory(
             provider_factory,
@@ -133,9 +152,12 @@ impl PrunerBuilder {
             + StageCheckpointReader
             + ChangeSetReader
             + StorageChangeSetReader
-            + RocksDBProviderFactory,
+            + RocksDBProviderFactory
+            + MetadataProvider
+            + MetadataWriter,
     {
-        let segments = SegmentSet::<Provider>::from_components(static_file_provider, self.segments);
+        let segments = SegmentSet::<Provider>::from_components(static_file_provider, self.segments)
+            .segment_opt(self.blob_sidecars.map(|(mode, store)| BlobSidecars::new(mode, store)));
 
         let mut pruner = Pruner::new(
             segments.into_vec(),
@@ -160,6 +182,7 @@ impl Default for PrunerBuilder {
             timeout: None,
             minimum_pruning_distance: None,
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
+            blob_sidecars: None,
         }
     }
 }
@@ -10,7 +10,9 @@ use reth_provider::{
     StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
-use reth_storage_api::{ChangeSetReader, StorageChangeSetReader, StorageSettingsCache};
+use reth_storage_api::{
+    ChangeSetReader, MetadataProvider, MetadataWriter, StorageChangeSetReader, StorageSettingsCache,
+};
 
 /// Collection of [`Segment`]. Thread-safe, allocated on the heap.
 #[derive(Debug)]
@@ -56,7 +58,9 @@ where
         + StorageSettingsCache
         + ChangeSetReader
         + StorageChangeSetReader
-        + RocksDBProviderFactory,
+        + RocksDBProviderFactory
+        + MetadataProvider
+        + MetadataWriter,
 {
     /// Creates a [`SegmentSet`] from an existing components, such as [`StaticFileProvider`] and
     /// [`PruneModes`].
@@ -72,6 +76,7 @@ where
             storage_history,
             bodies_history,
             receipts_log_filter,
+            history_retained_addresses,
         } = prune_modes;
 
         Self::default()
@@ -81,9 +86,14 @@ where
             // Bodies
             .segment_opt(bodies_history.map(|mode| Bodies::new(mode, transaction_lookup)))
             // Account history
-            .segment_opt(account_history.map(AccountHistory::new))
+            .segment_opt(account_history.map(|mode| {
+                AccountHistory::new(mode)
+                    .with_retained_addresses(history_retained_addresses.clone())
+            }))
             // Storage history
-            .segment_opt(storage_history.map(StorageHistory::new))
+            .segment_opt(storage_history.map(|mode| {
+                StorageHistory::new(mode).with_retained_addresses(history_retained_addresses)
+            }))
             // User receipts
             .segment_opt(receipts.map(UserReceipts::new))
             // Receipts by logs
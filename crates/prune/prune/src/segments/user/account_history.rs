@@ -1,12 +1,12 @@
 use crate::{
     db_ext::DbTxPruneExt,
     segments::{
-        user::history::{finalize_history_prune, HistoryPruneResult},
+        user::history::{finalize_history_prune, update_retained_history, HistoryPruneResult},
         PruneInput, Segment,
     },
     PrunerError,
 };
-use alloy_primitives::BlockNumber;
+use alloy_primitives::{Address, BlockNumber};
 use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
 use reth_provider::{
     changeset_walker::StaticFileAccountChangesetWalker, DBProvider, EitherWriter,
@@ -16,8 +16,9 @@ use reth_prune_types::{
     PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
 };
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{ChangeSetReader, StorageSettingsCache};
+use reth_storage_api::{ChangeSetReader, MetadataProvider, MetadataWriter, StorageSettingsCache};
 use rustc_hash::FxHashMap;
+use std::collections::BTreeSet;
 use tracing::{instrument, trace};
 
 /// Number of account history tables to prune in one step.
@@ -29,11 +30,23 @@ const ACCOUNT_HISTORY_TABLES_TO_PRUNE: usize = 2;
 #[derive(Debug)]
 pub struct AccountHistory {
     mode: PruneMode,
+    /// Addresses whose account history is never pruned.
+    retained_addresses: BTreeSet<Address>,
 }
 
 impl AccountHistory {
     pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+        Self { mode, retained_addresses: BTreeSet::new() }
+    }
+
+    /// Sets the addresses whose changesets and history indices are kept regardless of the prune
+    /// mode.
+    ///
+    /// Static file changesets can't be pruned per address, so they are kept entirely if any
+    /// address is retained.
+    pub fn with_retained_addresses(mut self, retained_addresses: BTreeSet<Address>) -> Self {
+        self.retained_addresses = retained_addresses;
+        self
     }
 }
 
@@ -43,7 +56,9 @@ where
         + StaticFileProviderFactory
         + StorageSettingsCache
         + ChangeSetReader
-        + RocksDBProviderFactory,
+        + RocksDBProviderFactory
+        + MetadataProvider
+        + MetadataWriter,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::AccountHistory
@@ -64,6 +79,13 @@ where
         ret(level = "trace")
     )]
     fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        update_retained_history(
+            provider,
+            PruneSegment::AccountHistory,
+            &self.retained_addresses,
+            &input,
+        )?;
+
         let range = match input.get_next_block_range() {
             Some(range) => range,
             None => {
@@ -134,14 +156,21 @@ impl AccountHistory {
                 break;
             }
             let (block_number, changeset) = result?;
-            highest_deleted_accounts.insert(changeset.address, block_number);
             last_changeset_pruned_block = Some(block_number);
+            if self.retained_addresses.contains(&changeset.address) {
+                continue
+            }
+            highest_deleted_accounts.insert(changeset.address, block_number);
             pruned_changesets += 1;
             limiter.increment_deleted_entries_count();
         }
 
-        // Delete static file jars only when fully processed
-        if done && let Some(last_block) = last_changeset_pruned_block {
+        // Delete static file jars only when fully processed. Jars also hold the changesets of
+        // retained addresses, so they are kept if any address is retained.
+        if done &&
+            self.retained_addresses.is_empty() &&
+            let Some(last_block) = last_changeset_pruned_block
+        {
             provider
                 .static_file_provider()
                 .delete_segment_below_block(StaticFileSegment::AccountChangeSets, last_block + 1)?;
@@ -202,7 +231,7 @@ impl AccountHistory {
             provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
                 range,
                 &mut limiter,
-                |_| false,
+                |(_, account)| self.retained_addresses.contains(&account.address),
                 |(block_number, account)| {
                     highest_deleted_accounts.insert(account.address, block_number);
                     last_changeset_pruned_block = Some(block_number);
@@ -268,8 +297,11 @@ impl AccountHistory {
                 break;
             }
             let (block_number, changeset) = result?;
-            highest_deleted_accounts.insert(changeset.address, block_number);
             last_changeset_pruned_block = Some(block_number);
+            if self.retained_addresses.contains(&changeset.address) {
+                continue
+            }
+            highest_deleted_accounts.insert(changeset.address, block_number);
             changesets_processed += 1;
             limiter.increment_deleted_entries_count();
         }
@@ -305,8 +337,9 @@ impl AccountHistory {
         // batch is committed before the MDBX checkpoint. If crash occurs after RocksDB commit
         // but before MDBX commit, on restart the pruner checkpoint indicates data needs
         // re-pruning, but the RocksDB shards are already pruned - this is safe because pruning
-        // is idempotent (re-pruning already-pruned shards is a no-op).
-        if done {
+        // is idempotent (re-pruning already-pruned shards is a no-op). Jars also hold the
+        // changesets of retained addresses, so they are kept if any address is retained.
+        if done && self.retained_addresses.is_empty() {
             provider.static_file_provider().delete_segment_below_block(
                 StaticFileSegment::AccountChangeSets,
                 last_changeset_pruned_block + 1,
@@ -338,7 +371,7 @@ mod tests {
         PruneCheckpoint, PruneInterruptReason, PruneMode, PruneProgress, PruneSegment,
     };
     use reth_stages::test_utils::{StorageKind, TestStageDB};
-    use reth_storage_api::StorageSettingsCache;
+    use reth_storage_api::{MetadataProvider, StorageSettingsCache};
     use reth_testing_utils::generators::{
         self, random_block_range, random_changeset_range, random_eoa_accounts, BlockRangeParams,
     };
@@ -606,6 +639,117 @@ mod tests {
         }
     }
 
+    #[test]
+    fn prune_retained_addresses() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(
+            &mut rng,
+            0..=100,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        let accounts = random_eoa_accounts(&mut rng, 2).into_iter().collect::<BTreeMap<_, _>>();
+        let retained = *accounts.keys().next().unwrap();
+        let retained_later = *accounts.keys().nth(1).unwrap();
+
+        let (changesets, _) = random_changeset_range(
+            &mut rng,
+            blocks.iter(),
+            accounts.into_iter().map(|(addr, acc)| (addr, (acc, Vec::new()))),
+            0..0,
+            0..0,
+        );
+        db.insert_changesets(changesets.clone(), None).expect("insert changesets");
+        db.insert_history(changesets.clone(), None).expect("insert history");
+
+        let retained_changesets = db
+            .table::<tables::AccountChangeSets>()
+            .unwrap()
+            .into_iter()
+            .filter(|(_, account)| account.address == retained)
+            .collect::<Vec<_>>();
+        let retained_shards = db
+            .table::<tables::AccountsHistory>()
+            .unwrap()
+            .into_iter()
+            .filter(|(key, _)| key.key == retained)
+            .collect::<Vec<_>>();
+
+        let to_block: BlockNumber = 50;
+        let input =
+            PruneInput { previous_checkpoint: None, to_block, limiter: PruneLimiter::default() };
+        let segment = AccountHistory::new(PruneMode::Before(to_block))
+            .with_retained_addresses([retained].into());
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        provider.set_storage_settings_cache(StorageSettings::v1());
+        let result = segment.prune(&provider, input).unwrap();
+        provider.commit().expect("commit");
+        assert!(result.progress.is_finished());
+
+        // Changesets and history of the retained address are untouched
+        let changesets = db.table::<tables::AccountChangeSets>().unwrap();
+        assert_eq!(
+            changesets
+                .iter()
+                .filter(|(_, account)| account.address == retained)
+                .cloned()
+                .collect::<Vec<_>>(),
+            retained_changesets
+        );
+        assert_eq!(
+            db.table::<tables::AccountsHistory>()
+                .unwrap()
+                .into_iter()
+                .filter(|(key, _)| key.key == retained)
+                .collect::<Vec<_>>(),
+            retained_shards
+        );
+
+        // Everything else is pruned up to the target block
+        assert!(
+            changesets
+                .iter()
+                .all(|(block_number, account)| account.address == retained ||
+                    *block_number > to_block)
+        );
+        assert!(db
+            .table::<tables::AccountsHistory>()
+            .unwrap()
+            .into_iter()
+            .filter(|(key, _)| key.key != retained)
+            .all(|(_, blocks)| blocks.iter().all(|block| block > to_block)));
+
+        // The history of the retained address is complete from genesis
+        let provider = db.factory.database_provider_rw().unwrap();
+        assert_eq!(
+            provider.retained_history().unwrap().account_history,
+            BTreeMap::from([(retained, 0)])
+        );
+
+        // An address retained after pruning only has its history from the next unpruned block
+        let input = PruneInput {
+            previous_checkpoint: Some(PruneCheckpoint {
+                block_number: Some(to_block),
+                tx_number: None,
+                prune_mode: PruneMode::Before(to_block),
+            }),
+            to_block: 80,
+            limiter: PruneLimiter::default(),
+        };
+        let segment = AccountHistory::new(PruneMode::Before(80))
+            .with_retained_addresses([retained, retained_later].into());
+        provider.set_storage_settings_cache(StorageSettings::v1());
+        segment.prune(&provider, input).unwrap();
+        assert_eq!(
+            provider.retained_history().unwrap().account_history,
+            BTreeMap::from([(retained, 0), (retained_later, to_block + 1)])
+        );
+    }
+
     /// Tests that when a limiter stops mid-block (with multiple changes for the same block),
     /// the checkpoint is set to `block_number - 1` to avoid dangling index entries.
     #[test]
@@ -0,0 +1,120 @@
+use crate::{
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use alloy_primitives::BlockNumber;
+use reth_provider::ProviderError;
+use reth_prune_types::{
+    PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use std::{fmt::Debug, sync::Arc};
+use tracing::{instrument, trace};
+
+/// A store of the blob sidecars of finalized blocks, kept outside of the database.
+pub trait BlobSidecarStore: Debug + Send + Sync {
+    /// Deletes the sidecars of all blocks up to and including `to_block`.
+    ///
+    /// Returns the number of blocks whose sidecars were deleted.
+    fn prune_blob_sidecars(&self, to_block: BlockNumber) -> Result<usize, ProviderError>;
+}
+
+/// Prunes the retained blob sidecars of a [`BlobSidecarStore`].
+#[derive(Debug)]
+pub struct BlobSidecars {
+    mode: PruneMode,
+    store: Arc<dyn BlobSidecarStore>,
+}
+
+impl BlobSidecars {
+    /// Creates a new segment that prunes the given store.
+    pub const fn new(mode: PruneMode, store: Arc<dyn BlobSidecarStore>) -> Self {
+        Self { mode, store }
+    }
+}
+
+impl<Provider> Segment<Provider> for BlobSidecars {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::BlobSidecars
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(
+        name = "BlobSidecars::prune",
+        target = "pruner",
+        skip(self, _provider),
+        ret(level = "trace")
+    )]
+    fn prune(&self, _provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        if input.get_next_block_range().is_none() {
+            trace!(target: "pruner", "No blob sidecars to prune");
+            return Ok(SegmentOutput::done())
+        }
+
+        // every block is stored in a single file, so the sidecars are pruned without a limit
+        let pruned = self.store.prune_blob_sidecars(input.to_block)?;
+        trace!(target: "pruner", %pruned, to_block = %input.to_block, "Pruned blob sidecars");
+
+        Ok(SegmentOutput {
+            progress: PruneProgress::Finished,
+            pruned,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(input.to_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PruneLimiter;
+    use reth_prune_types::PruneCheckpoint;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct MockStore {
+        blocks: Mutex<Vec<BlockNumber>>,
+    }
+
+    impl BlobSidecarStore for MockStore {
+        fn prune_blob_sidecars(&self, to_block: BlockNumber) -> Result<usize, ProviderError> {
+            let mut blocks = self.blocks.lock().unwrap();
+            let len = blocks.len();
+            blocks.retain(|block| *block > to_block);
+            Ok(len - blocks.len())
+        }
+    }
+
+    #[test]
+    fn prune() {
+        let store = Arc::new(MockStore { blocks: Mutex::new(vec![1, 2, 5, 10]) });
+        let segment = BlobSidecars::new(PruneMode::Distance(10), store.clone());
+
+        let input =
+            PruneInput { previous_checkpoint: None, to_block: 5, limiter: PruneLimiter::default() };
+        let output = Segment::<()>::prune(&segment, &(), input).unwrap();
+        assert_eq!(output.pruned, 3);
+        assert_eq!(output.checkpoint.unwrap().block_number, Some(5));
+        assert_eq!(*store.blocks.lock().unwrap(), vec![10]);
+
+        // nothing to prune below the checkpoint
+        let input = PruneInput {
+            previous_checkpoint: Some(PruneCheckpoint {
+                block_number: Some(5),
+                tx_number: None,
+                prune_mode: PruneMode::Distance(10),
+            }),
+            to_block: 5,
+            limiter: PruneLimiter::default(),
+        };
+        assert_eq!(Segment::<()>::prune(&segment, &(), input).unwrap().pruned, 0);
+    }
+}
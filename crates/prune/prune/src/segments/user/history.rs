@@ -1,5 +1,5 @@
-use crate::PruneLimiter;
-use alloy_primitives::BlockNumber;
+use crate::{segments::PruneInput, PruneLimiter, PrunerError};
+use alloy_primitives::{Address, BlockNumber};
 use itertools::Itertools;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
@@ -9,8 +9,10 @@ use reth_db_api::{
     BlockNumberList, DatabaseError, RawKey, RawTable, RawValue,
 };
 use reth_provider::DBProvider;
-use reth_prune_types::{SegmentOutput, SegmentOutputCheckpoint};
+use reth_prune_types::{PruneSegment, SegmentOutput, SegmentOutputCheckpoint};
+use reth_storage_api::{MetadataProvider, MetadataWriter};
 use rustc_hash::FxHashMap;
+use std::collections::BTreeSet;
 
 enum PruneShardOutcome {
     Deleted,
@@ -37,6 +39,27 @@ pub(crate) struct HistoryPruneResult<K> {
     pub(crate) done: bool,
 }
 
+/// Persists the addresses whose history is retained by the segment, see
+/// [`RetainedHistory`](reth_prune_types::RetainedHistory).
+///
+/// Newly retained addresses are recorded with the first block the segment hasn't pruned yet, so
+/// historical state queries for them below that block still fail.
+pub(crate) fn update_retained_history<Provider>(
+    provider: &Provider,
+    segment: PruneSegment,
+    retained_addresses: &BTreeSet<Address>,
+    input: &PruneInput,
+) -> Result<(), PrunerError>
+where
+    Provider: MetadataProvider + MetadataWriter,
+{
+    let mut retained_history = provider.retained_history()?;
+    if retained_history.update(segment, retained_addresses, input.get_start_next_block_range()) {
+        provider.write_retained_history(&retained_history)?;
+    }
+    Ok(())
+}
+
 /// Finalizes history pruning by sorting sharded keys, pruning history indices, and building output.
 ///
 /// This is shared between static file and database pruning for both account and storage history.
@@ -1,4 +1,5 @@
 mod account_history;
+mod blob_sidecars;
 mod bodies;
 mod history;
 mod receipts;
@@ -8,6 +9,7 @@ mod storage_history;
 mod transaction_lookup;
 
 pub use account_history::AccountHistory;
+pub use blob_sidecars::{BlobSidecarStore, BlobSidecars};
 pub use bodies::Bodies;
 pub use receipts::Receipts;
 pub use receipts_by_logs::ReceiptsByLogs;
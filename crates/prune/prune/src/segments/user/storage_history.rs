@@ -1,7 +1,7 @@
 use crate::{
     db_ext::DbTxPruneExt,
     segments::{
-        user::history::{finalize_history_prune, HistoryPruneResult},
+        user::history::{finalize_history_prune, update_retained_history, HistoryPruneResult},
         PruneInput, Segment,
     },
     PrunerError,
@@ -17,8 +17,11 @@ use reth_prune_types::{
     PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
 };
 use reth_static_file_types::StaticFileSegment;
-use reth_storage_api::{StorageChangeSetReader, StorageSettingsCache};
+use reth_storage_api::{
+    MetadataProvider, MetadataWriter, StorageChangeSetReader, StorageSettingsCache,
+};
 use rustc_hash::FxHashMap;
+use std::collections::BTreeSet;
 use tracing::{instrument, trace};
 
 /// Number of storage history tables to prune in one step.
@@ -30,11 +33,23 @@ const STORAGE_HISTORY_TABLES_TO_PRUNE: usize = 2;
 #[derive(Debug)]
 pub struct StorageHistory {
     mode: PruneMode,
+    /// Addresses whose storage history is never pruned.
+    retained_addresses: BTreeSet<Address>,
 }
 
 impl StorageHistory {
     pub const fn new(mode: PruneMode) -> Self {
-        Self { mode }
+        Self { mode, retained_addresses: BTreeSet::new() }
+    }
+
+    /// Sets the addresses whose changesets and history indices are kept regardless of the prune
+    /// mode.
+    ///
+    /// Static file changesets can't be pruned per address, so they are kept entirely if any
+    /// address is retained.
+    pub fn with_retained_addresses(mut self, retained_addresses: BTreeSet<Address>) -> Self {
+        self.retained_addresses = retained_addresses;
+        self
     }
 }
 
@@ -44,7 +59,9 @@ where
         + StaticFileProviderFactory
         + StorageChangeSetReader
         + StorageSettingsCache
-        + RocksDBProviderFactory,
+        + RocksDBProviderFactory
+        + MetadataProvider
+        + MetadataWriter,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::StorageHistory
@@ -65,6 +82,13 @@ where
         ret(level = "trace")
     )]
     fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        update_retained_history(
+            provider,
+            PruneSegment::StorageHistory,
+            &self.retained_addresses,
+            &input,
+        )?;
+
         let range = match input.get_next_block_range() {
             Some(range) => range,
             None => {
@@ -134,14 +158,21 @@ impl StorageHistory {
             let (block_address, entry) = result?;
             let block_number = block_address.block_number();
             let address = block_address.address();
-            highest_deleted_storages.insert((address, entry.key), block_number);
             last_changeset_pruned_block = Some(block_number);
+            if self.retained_addresses.contains(&address) {
+                continue
+            }
+            highest_deleted_storages.insert((address, entry.key), block_number);
             pruned_changesets += 1;
             limiter.increment_deleted_entries_count();
         }
 
-        // Delete static file jars only when fully processed
-        if done && let Some(last_block) = last_changeset_pruned_block {
+        // Delete static file jars only when fully processed. Jars also hold the changesets of
+        // retained addresses, so they are kept if any address is retained.
+        if done &&
+            self.retained_addresses.is_empty() &&
+            let Some(last_block) = last_changeset_pruned_block
+        {
             provider
                 .static_file_provider()
                 .delete_segment_below_block(StaticFileSegment::StorageChangeSets, last_block + 1)?;
@@ -204,7 +235,7 @@ impl StorageHistory {
             provider.tx_ref().prune_table_with_range::<tables::StorageChangeSets>(
                 BlockNumberAddress::range(range),
                 &mut limiter,
-                |_| false,
+                |(BlockNumberAddress((_, address)), _)| self.retained_addresses.contains(address),
                 |(BlockNumberAddress((block_number, address)), entry)| {
                     highest_deleted_storages.insert((address, entry.key), block_number);
                     last_changeset_pruned_block = Some(block_number);
@@ -271,8 +302,11 @@ impl StorageHistory {
             let (block_address, entry) = result?;
             let block_number = block_address.block_number();
             let address = block_address.address();
-            highest_deleted_storages.insert((address, entry.key), block_number);
             last_changeset_pruned_block = Some(block_number);
+            if self.retained_addresses.contains(&address) {
+                continue
+            }
+            highest_deleted_storages.insert((address, entry.key), block_number);
             changesets_processed += 1;
             limiter.increment_deleted_entries_count();
         }
@@ -312,8 +346,9 @@ impl StorageHistory {
         // batch is committed before the MDBX checkpoint. If crash occurs after RocksDB commit
         // but before MDBX commit, on restart the pruner checkpoint indicates data needs
         // re-pruning, but the RocksDB shards are already pruned - this is safe because pruning
-        // is idempotent (re-pruning already-pruned shards is a no-op).
-        if done {
+        // is idempotent (re-pruning already-pruned shards is a no-op). Jars also hold the
+        // changesets of retained addresses, so they are kept if any address is retained.
+        if done && self.retained_addresses.is_empty() {
             provider.static_file_provider().delete_segment_below_block(
                 StaticFileSegment::StorageChangeSets,
                 last_changeset_pruned_block + 1,
@@ -1,4 +1,5 @@
-use alloy_primitives::BlockNumber;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloy_primitives::{Address, BlockNumber};
 use derive_more::Display;
 use thiserror::Error;
 
@@ -87,6 +88,18 @@ pub struct PruneModes {
         serde(skip_serializing_if = "ReceiptsLogPruneConfig::is_empty")
     )]
     pub receipts_log_filter: ReceiptsLogPruneConfig,
+    /// Addresses whose account and storage history is retained in full, regardless of the
+    /// `account_history` and `storage_history` settings.
+    ///
+    /// Changesets and history indices of these addresses are never pruned, so historical state
+    /// queries for them keep working below the prune checkpoint. Changesets stored in static files
+    /// can't be pruned per address, so configuring any address here retains those files entirely
+    /// and only the history indices of other addresses are pruned.
+    ///
+    /// An address added after pruning started only has its history from the block recorded in
+    /// [`RetainedHistory`].
+    #[cfg_attr(any(test, feature = "serde"), serde(skip_serializing_if = "BTreeSet::is_empty"))]
+    pub history_retained_addresses: BTreeSet<Address>,
 }
 
 impl PruneModes {
@@ -100,9 +113,15 @@ impl PruneModes {
             storage_history: Some(PruneMode::Full),
             bodies_history: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
+            history_retained_addresses: Default::default(),
         }
     }
 
+    /// Returns `true` if the full account and storage history of the given address is retained.
+    pub fn is_history_retained(&self, address: &Address) -> bool {
+        self.history_retained_addresses.contains(address)
+    }
+
     /// Returns whether there is any kind of receipt pruning configuration.
     pub fn has_receipts_pruning(&self) -> bool {
         self.receipts.is_some() || !self.receipts_log_filter.is_empty()
@@ -170,6 +189,63 @@ impl PruneModes {
     }
 }
 
+/// Lowest blocks from which the history of the addresses in
+/// [`PruneModes::history_retained_addresses`] is complete, per history segment.
+///
+/// Persisted by the pruner. An address is recorded with the block after the highest pruned block
+/// of the segment at the first prune run after it was configured, because its history below that
+/// block may already be pruned.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "serde"), serde(default))]
+pub struct RetainedHistory {
+    /// Addresses whose account history is retained, with the lowest block it is complete from.
+    pub account_history: BTreeMap<Address, BlockNumber>,
+    /// Addresses whose storage history is retained, with the lowest block it is complete from.
+    pub storage_history: BTreeMap<Address, BlockNumber>,
+}
+
+impl RetainedHistory {
+    /// Returns the retained addresses of the given history segment, or `None` if the segment isn't
+    /// [`PruneSegment::AccountHistory`] or [`PruneSegment::StorageHistory`].
+    pub const fn segment(&self, segment: PruneSegment) -> Option<&BTreeMap<Address, BlockNumber>> {
+        match segment {
+            PruneSegment::AccountHistory => Some(&self.account_history),
+            PruneSegment::StorageHistory => Some(&self.storage_history),
+            _ => None,
+        }
+    }
+
+    /// Syncs the retained addresses of the given history segment with the configured ones.
+    ///
+    /// Newly configured addresses are recorded with `lowest_available`, the lowest block that
+    /// isn't pruned yet, and addresses that are no longer configured are removed. Returns `true`
+    /// if anything changed.
+    pub fn update(
+        &mut self,
+        segment: PruneSegment,
+        configured: &BTreeSet<Address>,
+        lowest_available: BlockNumber,
+    ) -> bool {
+        let retained = match segment {
+            PruneSegment::AccountHistory => &mut self.account_history,
+            PruneSegment::StorageHistory => &mut self.storage_history,
+            _ => return false,
+        };
+
+        let len = retained.len();
+        retained.retain(|address, _| configured.contains(address));
+        let mut changed = retained.len() != len;
+        for address in configured {
+            if !retained.contains_key(address) {
+                retained.insert(*address, lowest_available);
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
 /// Deserializes [`Option<PruneMode>`] and validates that the value is not less than the const
 /// generic parameter `MIN_BLOCKS`. This parameter represents the number of blocks that needs to be
 /// left in database after the pruning.
@@ -0,0 +1,24 @@
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, B256};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_trie_common::binary::BinaryTreeAccountProof;
+
+/// Reth API namespace methods of the experimental binary state tree.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethBinaryTreeApi {
+    /// Returns the root of the experimental binary state tree after the given block.
+    ///
+    /// Returns `None` if the root was not computed for the block.
+    #[method(name = "getBinaryTreeRoot")]
+    async fn reth_get_binary_tree_root(&self, block_id: BlockId) -> RpcResult<Option<B256>>;
+
+    /// Returns binary tree proofs of the account and the given storage slots against the root of
+    /// the latest block the binary tree was computed for.
+    #[method(name = "getBinaryTreeProof")]
+    async fn reth_get_binary_tree_proof(
+        &self,
+        address: Address,
+        storage_keys: Vec<B256>,
+    ) -> RpcResult<Option<BinaryTreeAccountProof>>;
+}
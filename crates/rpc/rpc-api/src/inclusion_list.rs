@@ -0,0 +1,48 @@
+//! Engine API extension for fork-choice enforced inclusion lists, see
+//! [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+
+use alloy_eips::eip7685::RequestsOrHash;
+use alloy_primitives::{BlockHash, Bytes, B256};
+use alloy_rpc_types_engine::{ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_engine_primitives::{EngineTypes, InclusionListPayloadStatus};
+
+/// Inclusion list endpoints of the `engine_` namespace.
+///
+/// These endpoints follow the devnet specification of EIP-7805 and are only available with the
+/// `focil` feature.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "engine"), server_bounds(Engine::PayloadAttributes: jsonrpsee::core::DeserializeOwned))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "engine", client_bounds(Engine::PayloadAttributes: jsonrpsee::core::Serialize + Clone), server_bounds(Engine::PayloadAttributes: jsonrpsee::core::DeserializeOwned)))]
+pub trait EngineInclusionListApi<Engine: EngineTypes> {
+    /// Returns an inclusion list for the block building on top of the given parent.
+    ///
+    /// The inclusion list consists of the EIP-2718 encoded transactions picked from the
+    /// transaction pool.
+    #[method(name = "getInclusionListV1")]
+    async fn get_inclusion_list_v1(&self, parent_hash: BlockHash) -> RpcResult<Vec<Bytes>>;
+
+    /// Post Prague payload handler that additionally checks the payload against an inclusion
+    /// list.
+    ///
+    /// Returns `INCLUSION_LIST_UNSATISFIED` if the payload is valid but omits a transaction of
+    /// the inclusion list that could have been included.
+    #[method(name = "newPayloadWithInclusionListV1")]
+    async fn new_payload_with_inclusion_list_v1(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        execution_requests: RequestsOrHash,
+        inclusion_list: Vec<Bytes>,
+    ) -> RpcResult<InclusionListPayloadStatus>;
+
+    /// Post Cancun forkchoice update handler that additionally provides the inclusion list the
+    /// payload built on top of the new head must satisfy.
+    #[method(name = "forkchoiceUpdatedWithInclusionListV1")]
+    async fn fork_choice_updated_with_inclusion_list_v1(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<Engine::PayloadAttributes>,
+        inclusion_list: Vec<Bytes>,
+    ) -> RpcResult<ForkchoiceUpdated>;
+}
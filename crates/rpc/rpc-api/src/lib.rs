@@ -4,7 +4,9 @@
 //!
 //! ## Feature Flags
 //!
+//! - `binary-tree`: Enables the methods of the experimental binary state tree.
 //! - `client`: Enables JSON-RPC client support.
+//! - `focil`: Enables the EIP-7805 inclusion list endpoints of the engine API.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -16,9 +18,13 @@
 
 mod admin;
 mod anvil;
+#[cfg(feature = "binary-tree")]
+mod binary_tree;
 mod debug;
 mod engine;
 mod hardhat;
+#[cfg(feature = "focil")]
+mod inclusion_list;
 mod mev;
 mod miner;
 mod net;
@@ -32,7 +38,7 @@ mod txpool;
 mod validation;
 mod web3;
 
-pub use reth::RethJitAction;
+pub use reth::{RethJitAction, TransactionBlobSidecar};
 pub use testing::{TestingBuildBlockRequestV1, TESTING_BUILD_BLOCK_V1, TESTING_COMMIT_BLOCK_V1};
 
 /// re-export of all server traits
@@ -40,6 +46,10 @@ pub use servers::*;
 
 /// Aggregates all server traits.
 pub mod servers {
+    #[cfg(feature = "binary-tree")]
+    pub use crate::binary_tree::RethBinaryTreeApiServer;
+    #[cfg(feature = "focil")]
+    pub use crate::inclusion_list::EngineInclusionListApiServer;
     pub use crate::{
         admin::AdminApiServer,
         anvil::AnvilApiServer,
@@ -72,6 +82,10 @@ pub use clients::*;
 /// Aggregates all client traits.
 #[cfg(feature = "client")]
 pub mod clients {
+    #[cfg(feature = "binary-tree")]
+    pub use crate::binary_tree::RethBinaryTreeApiClient;
+    #[cfg(feature = "focil")]
+    pub use crate::inclusion_list::EngineInclusionListApiClient;
     pub use crate::{
         admin::AdminApiClient,
         anvil::AnvilApiClient,
@@ -1,5 +1,5 @@
-use alloy_eips::BlockId;
-use alloy_primitives::{map::AddressMap, U256, U64};
+use alloy_eips::{eip7594::BlobTransactionSidecarVariant, BlockId};
+use alloy_primitives::{map::AddressMap, B256, U256, U64};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use serde::{Deserialize, Serialize};
 
@@ -29,6 +29,16 @@ pub trait RethApi {
         count: Option<U64>,
     ) -> RpcResult<Option<serde_json::Value>>;
 
+    /// Returns the blob sidecars of the blob transactions included in the given finalized block.
+    ///
+    /// Returns `None` if no sidecars are retained for the block. Sidecars are only retained if
+    /// the node is started with `--txpool.blob-sidecar-retention`.
+    #[method(name = "getBlobSidecars")]
+    async fn reth_get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<TransactionBlobSidecar>>>;
+
     /// Controls the revmc JIT backend.
     #[method(name = "jit")]
     async fn reth_jit(&self, action: RethJitAction) -> RpcResult<()>;
@@ -65,6 +75,18 @@ pub trait RethApi {
     ) -> jsonrpsee::core::SubscriptionResult;
 }
 
+/// The blob sidecar of a transaction, returned by `reth_getBlobSidecars`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBlobSidecar {
+    /// Hash of the blob transaction.
+    pub transaction_hash: B256,
+    /// The blob sidecar of the transaction.
+    ///
+    /// `None` if the sidecar was not in the blob pool when the block was finalized.
+    pub sidecar: Option<BlobTransactionSidecarVariant>,
+}
+
 /// Supported `reth_jit` control actions.
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
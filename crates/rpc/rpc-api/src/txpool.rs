@@ -1,7 +1,8 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_transaction_pool_primitives::TransactionHistory;
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +35,19 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Returns the recorded lifecycle events of the given transaction: when it was added, replaced,
+    /// moved between subpools, discarded and why, rejected, or mined.
+    ///
+    /// The history is kept for a bounded number of transactions, including transactions that are no
+    /// longer in the pool.
+    #[method(name = "transactionHistory")]
+    async fn txpool_transaction_history(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<TransactionHistory>>;
+
+    /// Returns the recorded lifecycle events of all transactions of this address.
+    #[method(name = "senderHistory")]
+    async fn txpool_sender_history(&self, from: Address) -> RpcResult<Vec<TransactionHistory>>;
 }
@@ -36,17 +36,18 @@ use reth_engine_primitives::{ConsensusEngineEvent, ConsensusEngineHandle};
 use reth_evm::ConfigureEvm;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_payload_primitives::PayloadTypes;
+use reth_payload_util::BundlePool;
 use reth_primitives_traits::{NodePrimitives, TxTy};
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
-    OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
+    AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, EthConditional,
+    EthMevBundle, MinerApi, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_engine_api::RethEngineApi;
 use reth_rpc_eth_api::{
     helpers::{
-        pending_block::PendingEnvBuilder, Call, EthApiSpec, EthTransactions, LoadPendingBlock,
-        TraceExt,
+        block::LoadBlock, pending_block::PendingEnvBuilder, Call, EthApiSpec, EthTransactions,
+        LoadPendingBlock, TraceExt,
     },
     node::RpcNodeCoreAdapter,
     EthApiServer, EthApiTypes, FullEthApiServer, FullEthApiTypes, RpcBlock, RpcConvert,
@@ -56,8 +57,8 @@ use reth_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig, EthSubscriptio
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 pub use reth_rpc_server_types::RethRpcModule;
 use reth_storage_api::{
-    AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, NodePrimitivesProvider,
-    StateProviderFactory,
+    AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, MaybeBinaryTreeReader,
+    NodePrimitivesProvider, ProviderTx, StateProviderFactory,
 };
 use reth_tasks::{pool::BlockingTaskGuard, Runtime};
 use reth_tokio_util::EventSender;
@@ -317,7 +318,8 @@ where
         + ForkChoiceSubscriptions<Header = N::BlockHeader>
         + PersistedBlockSubscriptions
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + MaybeBinaryTreeReader,
     Pool: TransactionPool + Clone + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
@@ -665,6 +667,7 @@ where
             Transaction = N::SignedTx,
         > + AccountReader
         + ChangeSetReader
+        + MaybeBinaryTreeReader
         + CanonStateSubscriptions<Primitives = N>
         + ForkChoiceSubscriptions<Header = N::BlockHeader>
         + PersistedBlockSubscriptions,
@@ -755,9 +758,12 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime.
-    pub fn register_reth(&mut self) -> &mut Self {
+    pub fn register_reth(&mut self) -> &mut Self
+    where
+        Pool: TransactionPool,
+    {
         let rethapi = self.reth_api();
-        self.modules.insert(RethRpcModule::Reth, rethapi.into_rpc().into());
+        self.modules.insert(RethRpcModule::Reth, reth_namespace_methods(rethapi));
         self
     }
 
@@ -782,7 +788,8 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + MaybeBinaryTreeReader,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
@@ -813,6 +820,23 @@ where
         EthBundle::new(eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates [`EthMevBundle`] Api that inserts the bundles received through
+    /// `mev_sendBundle` into the given pool.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn mev_bundle_api(
+        &self,
+        bundle_pool: BundlePool<ProviderTx<EthApi::Provider>>,
+    ) -> EthMevBundle<EthApi, ProviderTx<EthApi::Provider>>
+    where
+        EthApi: EthTransactions + LoadBlock + Call,
+    {
+        let eth_api = self.eth_api().clone();
+        EthMevBundle::new(eth_api, self.blocking_pool_guard.clone(), bundle_pool)
+    }
+
     /// Instantiates `DebugApi`
     ///
     /// # Panics
@@ -844,10 +868,14 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider, EvmConfig> {
+    pub fn reth_api(&self) -> RethApi<Provider, EvmConfig>
+    where
+        Pool: TransactionPool,
+    {
         RethApi::new(
             self.provider.clone(),
             self.evm_config.clone(),
+            self.pool.blob_store(),
             self.blocking_pool_guard.clone(),
             self.executor.clone(),
         )
@@ -863,7 +891,8 @@ where
         + ForkChoiceSubscriptions<Header = N::BlockHeader>
         + PersistedBlockSubscriptions
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + MaybeBinaryTreeReader,
     Pool: TransactionPool + Clone + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: FullEthApiServer,
@@ -988,6 +1017,9 @@ where
                                     .into_rpc(),
                                 )
                                 .expect("No conflicts");
+                            module
+                                .merge(EthConditional::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
 
                             module.into()
                         }
@@ -1017,14 +1049,13 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => RethApi::new(
+                        RethRpcModule::Reth => reth_namespace_methods(RethApi::new(
                             self.provider.clone(),
                             self.evm_config.clone(),
+                            self.pool.blob_store(),
                             self.blocking_pool_guard.clone(),
                             self.executor.clone(),
-                        )
-                        .into_rpc()
-                        .into(),
+                        )),
                         RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
                         RethRpcModule::Mev => {
                             EthSimBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
@@ -1071,6 +1102,29 @@ where
     }
 }
 
+/// Returns the methods of the `reth` namespace.
+#[cfg(not(feature = "binary-tree"))]
+fn reth_namespace_methods<Provider, EvmConfig>(api: RethApi<Provider, EvmConfig>) -> Methods
+where
+    RethApi<Provider, EvmConfig>: RethApiServer,
+{
+    api.into_rpc().into()
+}
+
+/// Returns the methods of the `reth` namespace, including the methods of the experimental binary
+/// state tree.
+#[cfg(feature = "binary-tree")]
+fn reth_namespace_methods<Provider, EvmConfig>(api: RethApi<Provider, EvmConfig>) -> Methods
+where
+    RethApi<Provider, EvmConfig>: RethApiServer + RethBinaryTreeApiServer,
+{
+    let mut methods: Methods = RethApiServer::into_rpc(api.clone()).into();
+    methods
+        .merge(RethBinaryTreeApiServer::into_rpc(api))
+        .expect("binary tree methods don't conflict with the reth namespace");
+    methods
+}
+
 /// A builder type for configuring and launching the servers that will handle RPC requests.
 ///
 /// Supported server transports are:
@@ -45,6 +45,15 @@ pub const CAPABILITIES: &[&str] = &[
     "engine_hasBlobs",
 ];
 
+/// Engine API capabilities of [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805) inclusion lists,
+/// supported with the `focil` feature.
+#[cfg(feature = "focil")]
+pub const INCLUSION_LIST_CAPABILITIES: &[&str] = &[
+    "engine_forkchoiceUpdatedWithInclusionListV1",
+    "engine_getInclusionListV1",
+    "engine_newPayloadWithInclusionListV1",
+];
+
 /// Engine API capabilities set.
 #[derive(Debug, Clone)]
 pub struct EngineCapabilities {
@@ -156,7 +165,10 @@ fn is_unstable_method(method: &str) -> bool {
 
 impl Default for EngineCapabilities {
     fn default() -> Self {
-        Self::new(CAPABILITIES.iter().copied())
+        let capabilities = CAPABILITIES.iter();
+        #[cfg(feature = "focil")]
+        let capabilities = capabilities.chain(INCLUSION_LIST_CAPABILITIES);
+        Self::new(capabilities.copied())
     }
 }
 
@@ -1,6 +1,8 @@
 use crate::{
     capabilities::EngineCapabilities, metrics::EngineApiMetrics, EngineApiError, EngineApiResult,
 };
+#[cfg(feature = "focil")]
+use alloy_eips::eip2718::Encodable2718;
 use alloy_eips::{
     eip1898::BlockHashOrNumber,
     eip4844::{BlobAndProofV1, BlobAndProofV2, BlobCellsAndProofsV1},
@@ -19,13 +21,19 @@ use async_trait::async_trait;
 use jsonrpsee_core::{server::RpcModule, RpcResult};
 use reth_chainspec::EthereumHardforks;
 use reth_engine_primitives::{ConsensusEngineHandle, EngineApiValidator, EngineTypes};
+#[cfg(feature = "focil")]
+use reth_engine_primitives::{InclusionListPayloadStatus, MAX_BYTES_PER_INCLUSION_LIST};
 use reth_network_api::{CellCustody, NetworkInfo};
 use reth_payload_builder::PayloadStore;
 use reth_payload_primitives::{
     validate_payload_timestamp, EngineApiMessageVersion, MessageValidationKind,
     PayloadOrAttributes, PayloadTypes,
 };
+#[cfg(feature = "focil")]
+use reth_payload_util::InclusionListStore;
 use reth_primitives_traits::{Block, BlockBody};
+#[cfg(feature = "focil")]
+use reth_rpc_api::EngineInclusionListApiServer;
 use reth_rpc_api::{EngineApiServer, IntoEngineApiRpcModule};
 use reth_storage_api::{BalProvider, BlockReader, HeaderProvider, StateProviderFactory};
 use reth_tasks::Runtime;
@@ -63,6 +71,9 @@ const MAX_BLOB_LIMIT: usize = 128;
 /// L1 implementation.
 pub struct EngineApi<Provider, PayloadT: PayloadTypes, Pool, Validator, ChainSpec> {
     inner: Arc<EngineApiInner<Provider, PayloadT, Pool, Validator, ChainSpec>>,
+    /// Inclusion lists received with forkchoice updates, shared with the payload builder.
+    #[cfg(feature = "focil")]
+    inclusion_lists: InclusionListStore,
 }
 
 impl<Provider, PayloadT: PayloadTypes, Pool, Validator, ChainSpec>
@@ -77,6 +88,15 @@ impl<Provider, PayloadT: PayloadTypes, Pool, Validator, ChainSpec>
     pub fn client_version(&self) -> &ClientVersionV1 {
         &self.inner.client
     }
+
+    /// Sets the store that inclusion lists received with forkchoice updates are inserted into.
+    ///
+    /// The payload builder reads the inclusion lists from this store.
+    #[cfg(feature = "focil")]
+    pub fn with_inclusion_lists(mut self, inclusion_lists: InclusionListStore) -> Self {
+        self.inclusion_lists = inclusion_lists;
+        self
+    }
 }
 
 impl<Provider, PayloadT, Pool, Validator, ChainSpec>
@@ -120,7 +140,11 @@ where
             cell_custody,
             is_syncing,
         });
-        Self { inner }
+        Self {
+            inner,
+            #[cfg(feature = "focil")]
+            inclusion_lists: InclusionListStore::default(),
+        }
     }
 
     /// Fetches the client version.
@@ -1539,6 +1563,142 @@ where
     }
 }
 
+/// Inclusion list endpoints, see [EIP-7805](https://eips.ethereum.org/EIPS/eip-7805).
+#[cfg(feature = "focil")]
+impl<Provider, EngineT, Pool, Validator, ChainSpec>
+    EngineApi<Provider, EngineT, Pool, Validator, ChainSpec>
+where
+    Provider: HeaderProvider + BlockReader + StateProviderFactory + BalProvider + 'static,
+    EngineT: EngineTypes,
+    Pool: TransactionPool + 'static,
+    Validator: EngineApiValidator<EngineT>,
+    ChainSpec: EthereumHardforks + Send + Sync + 'static,
+{
+    /// Returns an inclusion list for the block building on top of the given parent.
+    ///
+    /// The transactions are picked from the pool, so the inclusion list is built against the
+    /// pool's view of the chain, which is expected to be the given parent.
+    pub fn get_inclusion_list_v1(&self, parent_hash: BlockHash) -> EngineApiResult<Vec<Bytes>> {
+        let pool = &self.inner.tx_pool;
+        let block_info = pool.block_info();
+        if block_info.last_seen_block_hash != parent_hash {
+            debug!(target: "rpc::engine", %parent_hash, pool_tip = %block_info.last_seen_block_hash, "Building inclusion list for block that is not the pool's tip");
+        }
+
+        Ok(pool
+            .inclusion_list_transactions(block_info.pending_basefee, MAX_BYTES_PER_INCLUSION_LIST)
+            .into_iter()
+            .map(|tx| tx.transaction.clone_into_consensus().into_inner().encoded_2718().into())
+            .collect())
+    }
+
+    /// Validates a Prague payload and checks it against the given inclusion list.
+    pub async fn new_payload_with_inclusion_list_v1(
+        &self,
+        payload: EngineT::ExecutionData,
+        inclusion_list: Vec<Bytes>,
+    ) -> EngineApiResult<InclusionListPayloadStatus> {
+        let payload_or_attrs = PayloadOrAttributes::<
+            '_,
+            EngineT::ExecutionData,
+            EngineT::PayloadAttributes,
+        >::from_execution_payload(&payload);
+        self.inner
+            .validator
+            .validate_version_specific_fields(EngineApiMessageVersion::V4, payload_or_attrs)?;
+
+        Ok(self
+            .inner
+            .beacon_consensus
+            .new_payload_with_inclusion_list(payload, inclusion_list)
+            .await?)
+    }
+
+    /// Updates the fork choice like `engine_forkchoiceUpdatedV3` and records the inclusion list
+    /// that payloads built on top of the new head must satisfy.
+    ///
+    /// The inclusion list is only recorded if the new head is valid, so an invalid or unknown head
+    /// can't evict the inclusion lists of valid ones. The payload job started by the update picks
+    /// it up with its next build.
+    pub async fn fork_choice_updated_with_inclusion_list_v1(
+        &self,
+        state: ForkchoiceState,
+        payload_attrs: Option<EngineT::PayloadAttributes>,
+        inclusion_list: Vec<Bytes>,
+    ) -> EngineApiResult<ForkchoiceUpdated> {
+        let res = self
+            .validate_and_execute_forkchoice(EngineApiMessageVersion::V3, state, payload_attrs)
+            .await?;
+        if res.is_valid() {
+            self.inclusion_lists.insert(state.head_block_hash, inclusion_list);
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(feature = "focil")]
+#[async_trait]
+impl<Provider, EngineT, Pool, Validator, ChainSpec> EngineInclusionListApiServer<EngineT>
+    for EngineApi<Provider, EngineT, Pool, Validator, ChainSpec>
+where
+    Provider: HeaderProvider + BlockReader + StateProviderFactory + BalProvider + 'static,
+    EngineT: EngineTypes<ExecutionData = ExecutionData>,
+    Pool: TransactionPool + 'static,
+    Validator: EngineApiValidator<EngineT>,
+    ChainSpec: EthereumHardforks + Send + Sync + 'static,
+{
+    /// Handler for `engine_getInclusionListV1`
+    async fn get_inclusion_list_v1(&self, parent_hash: BlockHash) -> RpcResult<Vec<Bytes>> {
+        trace!(target: "rpc::engine", "Serving engine_getInclusionListV1");
+        Ok(Self::get_inclusion_list_v1(self, parent_hash)?)
+    }
+
+    /// Handler for `engine_newPayloadWithInclusionListV1`
+    async fn new_payload_with_inclusion_list_v1(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        requests: RequestsOrHash,
+        inclusion_list: Vec<Bytes>,
+    ) -> RpcResult<InclusionListPayloadStatus> {
+        trace!(target: "rpc::engine", "Serving engine_newPayloadWithInclusionListV1");
+
+        // Accept requests as a hash only if it is explicitly allowed
+        if requests.is_hash() && !self.inner.accept_execution_requests_hash {
+            return Err(EngineApiError::UnexpectedRequestsHash.into());
+        }
+
+        let payload = ExecutionData {
+            payload: payload.into(),
+            sidecar: ExecutionPayloadSidecar::v4(
+                CancunPayloadFields { versioned_hashes, parent_beacon_block_root },
+                PraguePayloadFields { requests },
+            ),
+        };
+
+        Ok(Self::new_payload_with_inclusion_list_v1(self, payload, inclusion_list).await?)
+    }
+
+    /// Handler for `engine_forkchoiceUpdatedWithInclusionListV1`
+    async fn fork_choice_updated_with_inclusion_list_v1(
+        &self,
+        fork_choice_state: ForkchoiceState,
+        payload_attributes: Option<EngineT::PayloadAttributes>,
+        inclusion_list: Vec<Bytes>,
+    ) -> RpcResult<ForkchoiceUpdated> {
+        trace!(target: "rpc::engine", "Serving engine_forkchoiceUpdatedWithInclusionListV1");
+        Ok(Self::fork_choice_updated_with_inclusion_list_v1(
+            self,
+            fork_choice_state,
+            payload_attributes,
+            inclusion_list,
+        )
+        .await?)
+    }
+}
+
+#[cfg(not(feature = "focil"))]
 impl<Provider, EngineT, Pool, Validator, ChainSpec> IntoEngineApiRpcModule
     for EngineApi<Provider, EngineT, Pool, Validator, ChainSpec>
 where
@@ -1550,6 +1710,22 @@ where
     }
 }
 
+#[cfg(feature = "focil")]
+impl<Provider, EngineT, Pool, Validator, ChainSpec> IntoEngineApiRpcModule
+    for EngineApi<Provider, EngineT, Pool, Validator, ChainSpec>
+where
+    EngineT: EngineTypes,
+    Self: EngineApiServer<EngineT> + EngineInclusionListApiServer<EngineT>,
+{
+    fn into_rpc_module(self) -> RpcModule<()> {
+        let mut module = EngineApiServer::<EngineT>::into_rpc(self.clone()).remove_context();
+        module
+            .merge(EngineInclusionListApiServer::<EngineT>::into_rpc(self).remove_context())
+            .expect("No conflicting methods");
+        module
+    }
+}
+
 impl<Provider, PayloadT, Pool, Validator, ChainSpec> std::fmt::Debug
     for EngineApi<Provider, PayloadT, Pool, Validator, ChainSpec>
 where
@@ -1566,7 +1742,11 @@ where
     PayloadT: PayloadTypes,
 {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            #[cfg(feature = "focil")]
+            inclusion_lists: self.inclusion_lists.clone(),
+        }
     }
 }
 
@@ -0,0 +1,125 @@
+//! `eth_sendRawTransactionConditional` implementation, see
+//! [ERC-7796](https://eips.ethereum.org/EIPS/eip-7796).
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockId;
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use jsonrpsee::core::RpcResult;
+use reth_rpc_eth_api::{
+    helpers::{EthTransactions, SpawnBlocking},
+    FromEthApiError, L2EthApiExtServer, RpcNodeCore,
+};
+use reth_rpc_eth_types::{error::ToRpcError, EthApiError};
+use reth_rpc_server_types::result::rpc_error_with_code;
+use reth_storage_api::{BlockReaderIdExt, StateProviderFactory};
+use reth_transaction_pool::{
+    conditional::{
+        is_expired_conditional, validate_known_accounts, TransactionConditionalError,
+        MAX_CONDITIONAL_COST,
+    },
+    PoolTransaction, PoolTx, TransactionOrigin, TransactionPool,
+};
+use tracing::trace;
+
+/// Error code returned if the conditions of the transaction are not satisfied.
+const CONDITIONAL_REJECTED_CODE: i32 = -32003;
+
+/// Error code returned if the cost of the conditions exceeds [`MAX_CONDITIONAL_COST`].
+const CONDITIONAL_COST_EXCEEDED_CODE: i32 = -32005;
+
+/// `eth` namespace extension that accepts conditional transactions.
+///
+/// The conditions are checked against the latest block before the transaction is submitted to the
+/// pool, which keeps them alongside the transaction. The pool evicts the transaction once its
+/// conditions can no longer be satisfied, and the payload builder only includes it in blocks that
+/// satisfy them.
+#[derive(Debug, Clone)]
+pub struct EthConditional<Eth> {
+    /// The `eth` API the transactions are submitted through.
+    eth_api: Eth,
+}
+
+impl<Eth> EthConditional<Eth> {
+    /// Create a new `EthConditional` instance.
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+}
+
+impl<Eth> EthConditional<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Checks the conditions against the latest block and state.
+    async fn validate_conditional(
+        &self,
+        condition: TransactionConditional,
+    ) -> Result<(), Eth::Error> {
+        self.eth_api
+            .spawn_blocking_io(move |eth_api| {
+                let provider = eth_api.provider();
+                let header =
+                    provider.latest_header().map_err(Eth::Error::from_eth_err)?.ok_or_else(
+                        || Eth::Error::from_eth_err(EthApiError::HeaderNotFound(BlockId::latest())),
+                    )?;
+                if is_expired_conditional(&condition, header.number(), header.timestamp()) {
+                    return Ok(Err(TransactionConditionalError::BlockNumber(header.number())))
+                }
+                let state = provider.latest().map_err(Eth::Error::from_eth_err)?;
+                Ok(validate_known_accounts(&condition, state.as_ref()))
+            })
+            .await?
+            .map_err(|err| match err {
+                TransactionConditionalError::Provider(err) => Eth::Error::from_eth_err(err),
+                err => Eth::Error::from_eth_err(EthApiError::other(ConditionalRejected(err))),
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> L2EthApiExtServer for EthConditional<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        condition: TransactionConditional,
+    ) -> RpcResult<B256> {
+        trace!(target: "rpc::eth", ?condition, "Serving eth_sendRawTransactionConditional");
+
+        let cost = condition.cost();
+        if cost > MAX_CONDITIONAL_COST {
+            return Err(rpc_error_with_code(
+                CONDITIONAL_COST_EXCEEDED_CODE,
+                TransactionConditionalError::CostExceeded(cost).to_string(),
+            ))
+        }
+
+        let transaction = <PoolTx<Eth::Pool> as PoolTransaction>::recover_raw_transaction(&bytes)
+            .map_err(EthApiError::from)?;
+        self.validate_conditional(condition.clone()).await.map_err(Into::into)?;
+
+        let outcome = self
+            .eth_api
+            .pool()
+            .add_conditional_transaction(TransactionOrigin::Local, transaction, condition)
+            .await
+            .map_err(EthApiError::from)?;
+
+        Ok(outcome.hash)
+    }
+}
+
+/// Wraps a [`TransactionConditionalError`] so it's returned with the
+/// [`CONDITIONAL_REJECTED_CODE`].
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+struct ConditionalRejected(TransactionConditionalError);
+
+impl ToRpcError for ConditionalRejected {
+    fn to_rpc_error(&self) -> jsonrpsee_types::ErrorObject<'static> {
+        rpc_error_with_code(CONDITIONAL_REJECTED_CODE, self.0.to_string())
+    }
+}
@@ -0,0 +1,254 @@
+//! `mev_sendBundle` and `eth_sendBundle` implementation backed by a bundle pool.
+
+use crate::eth::{
+    bundle::EthBundle,
+    sim_bundle::{EthSimBundle, EthSimBundleError},
+};
+use alloy_consensus::transaction::TxHashRef;
+use alloy_eips::Typed2718;
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_mev::{
+    EthBundleHash, EthCallBundle, EthCallBundleResponse, EthCancelBundle,
+    EthCancelPrivateTransaction, EthSendBundle, EthSendPrivateTransaction, MevSendBundle,
+    SimBundleOverrides, SimBundleResponse,
+};
+use jsonrpsee::core::RpcResult;
+use reth_payload_util::{BundlePool, BundleRefundConfig, BundleTransaction, PoolBundle};
+use reth_rpc_api::{EthBundleApiServer, EthCallBundleApiServer, MevFullApiServer, MevSimApiServer};
+use reth_rpc_eth_api::{
+    helpers::{block::LoadBlock, Call, EthTransactions, LoadPendingBlock},
+    RpcNodeCore,
+};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_storage_api::{BlockNumReader, ProviderTx};
+use reth_tasks::pool::BlockingTaskGuard;
+use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
+use tracing::trace;
+
+/// `mev` namespace implementation that accepts bundles into a [`BundlePool`].
+///
+/// Bundles submitted through `mev_sendBundle` or `eth_sendBundle` are validated, flattened and
+/// stored in the pool, from where the payload builder includes them at the top of the blocks it
+/// builds. Bundles sent with a replacement UUID through `eth_sendBundle` replace the previous
+/// bundle with the same UUID and can be cancelled with `eth_cancelBundle`. `mev_simBundle` and
+/// `eth_callBundle` are served by [`EthSimBundle`] and [`EthBundle`].
+///
+/// This is registered in place of the default `mev` namespace if the bundle pool is enabled with
+/// `--builder.bundles`.
+pub struct EthMevBundle<Eth, T> {
+    /// Bundle simulation and validation.
+    sim: EthSimBundle<Eth>,
+    /// `eth_callBundle` implementation.
+    call: EthBundle<Eth>,
+    /// Pool the accepted bundles are inserted into.
+    bundle_pool: BundlePool<T>,
+}
+
+impl<Eth: Clone, T> EthMevBundle<Eth, T> {
+    /// Create a new `EthMevBundle` instance.
+    pub fn new(
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        bundle_pool: BundlePool<T>,
+    ) -> Self {
+        Self {
+            sim: EthSimBundle::new(eth_api.clone(), blocking_task_guard.clone()),
+            call: EthBundle::new(eth_api, blocking_task_guard),
+            bundle_pool,
+        }
+    }
+}
+
+impl<Eth, T> EthMevBundle<Eth, T> {
+    /// Returns the bundle pool.
+    pub const fn bundle_pool(&self) -> &BundlePool<T> {
+        &self.bundle_pool
+    }
+}
+
+impl<Eth> EthMevBundle<Eth, ProviderTx<Eth::Provider>>
+where
+    Eth: EthTransactions + LoadBlock + Call + 'static,
+{
+    /// Validates the bundle and inserts it into the pool.
+    fn send_bundle_inner(&self, request: &MevSendBundle) -> Result<EthBundleHash, EthApiError> {
+        let items = self.sim.parse_and_flatten_bundle(request)?;
+
+        // nested bundles may narrow the block range of the parent bundle
+        let block_number =
+            items.iter().map(|item| item.inclusion.block_number()).max().unwrap_or_default();
+        let max_block_number = items
+            .iter()
+            .map(|item| item.inclusion.max_block_number().unwrap_or(item.inclusion.block_number()))
+            .min()
+            .unwrap_or_default();
+
+        let transactions = items
+            .into_iter()
+            .map(|item| BundleTransaction {
+                tx: item.tx,
+                can_revert: item.can_revert,
+                refund_percent: item.refund_percent,
+                refund_configs: item
+                    .refund_configs
+                    .into_iter()
+                    .flatten()
+                    .map(|config| BundleRefundConfig {
+                        address: config.address,
+                        percent: config.percent,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        self.insert_bundle(PoolBundle::new(transactions, block_number, max_block_number))
+    }
+
+    /// Validates the `eth_sendBundle` request and inserts it into the pool.
+    fn send_eth_bundle_inner(&self, request: EthSendBundle) -> Result<EthBundleHash, EthApiError> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+            dropping_tx_hashes,
+            refund_percent,
+            refund_recipient,
+            refund_tx_hashes,
+            ..
+        } = request;
+
+        if min_timestamp.is_some_and(|timestamp| timestamp != 0) ||
+            max_timestamp.is_some_and(|timestamp| timestamp != 0)
+        {
+            return Err(EthApiError::Unsupported("bundle timestamp ranges are not supported"))
+        }
+        if !dropping_tx_hashes.is_empty() ||
+            refund_percent.is_some() ||
+            refund_recipient.is_some() ||
+            !refund_tx_hashes.is_empty()
+        {
+            return Err(EthApiError::Unsupported(
+                "dropping and refunding transactions is only supported by mev_sendBundle",
+            ))
+        }
+
+        let transactions = txs
+            .iter()
+            .map(|tx| {
+                let tx = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(tx)?
+                    .map(<Eth::Pool as TransactionPool>::Transaction::pooled_into_consensus);
+                let can_revert = reverting_tx_hashes.contains(tx.tx_hash());
+                Ok(BundleTransaction {
+                    tx,
+                    can_revert,
+                    refund_percent: None,
+                    refund_configs: Vec::new(),
+                })
+            })
+            .collect::<Result<Vec<_>, EthApiError>>()?;
+
+        let bundle = PoolBundle::new(transactions, block_number, block_number)
+            .with_replacement_uuid(replacement_uuid);
+        self.insert_bundle(bundle)
+    }
+
+    /// Checks that the bundle can still be included and inserts it into the pool.
+    fn insert_bundle(
+        &self,
+        bundle: PoolBundle<ProviderTx<Eth::Provider>>,
+    ) -> Result<EthBundleHash, EthApiError> {
+        // blob sidecars can't be carried through the bundle pool
+        if bundle.transactions.iter().any(|item| item.tx.is_eip4844()) {
+            return Err(EthApiError::InvalidParams(EthSimBundleError::InvalidBundle.to_string()))
+        }
+
+        let head = self.sim.eth_api().provider().best_block_number()?;
+        if bundle.max_block_number < bundle.block_number || bundle.max_block_number <= head {
+            return Err(EthApiError::InvalidParams(EthSimBundleError::InvalidInclusion.to_string()))
+        }
+
+        let bundle_hash = self
+            .bundle_pool
+            .insert(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        Ok(EthBundleHash { bundle_hash })
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> MevFullApiServer for EthMevBundle<Eth, ProviderTx<Eth::Provider>>
+where
+    Eth: EthTransactions + LoadBlock + Call + 'static,
+{
+    async fn send_bundle(&self, request: MevSendBundle) -> RpcResult<EthBundleHash> {
+        trace!(target: "rpc::mev", ?request, "Serving mev_sendBundle");
+        Ok(self.send_bundle_inner(&request)?)
+    }
+
+    async fn sim_bundle(
+        &self,
+        bundle: MevSendBundle,
+        sim_overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        MevSimApiServer::sim_bundle(&self.sim, bundle, sim_overrides).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthMevBundle<Eth, ProviderTx<Eth::Provider>>
+where
+    Eth: EthTransactions + LoadBlock + LoadPendingBlock + Call + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        trace!(target: "rpc::eth", ?bundle, "Serving eth_sendBundle");
+        Ok(self.send_eth_bundle_inner(bundle)?)
+    }
+
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        EthCallBundleApiServer::call_bundle(&self.call, request).await
+    }
+
+    async fn cancel_bundle(&self, request: EthCancelBundle) -> RpcResult<()> {
+        trace!(target: "rpc::eth", ?request, "Serving eth_cancelBundle");
+        self.bundle_pool.remove_by_uuid(&request.replacement_uuid);
+        Ok(())
+    }
+
+    async fn send_private_transaction(
+        &self,
+        _request: EthSendPrivateTransaction,
+    ) -> RpcResult<B256> {
+        Err(EthApiError::Unsupported("eth_sendPrivateTransaction is not supported").into())
+    }
+
+    async fn send_private_raw_transaction(&self, _bytes: Bytes) -> RpcResult<B256> {
+        Err(EthApiError::Unsupported("eth_sendPrivateRawTransaction is not supported").into())
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        _request: EthCancelPrivateTransaction,
+    ) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("eth_cancelPrivateTransaction is not supported").into())
+    }
+}
+
+impl<Eth, T> std::fmt::Debug for EthMevBundle<Eth, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthMevBundle").finish_non_exhaustive()
+    }
+}
+
+impl<Eth, T> Clone for EthMevBundle<Eth, T> {
+    fn clone(&self) -> Self {
+        Self {
+            sim: self.sim.clone(),
+            call: self.call.clone(),
+            bundle_pool: self.bundle_pool.clone(),
+        }
+    }
+}
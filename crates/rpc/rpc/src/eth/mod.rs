@@ -2,17 +2,21 @@
 
 pub mod builder;
 pub mod bundle;
+pub mod conditional;
 pub mod core;
 pub mod filter;
 pub mod helpers;
+pub mod mev_bundle;
 pub mod pubsub;
 pub mod sim_bundle;
 
 /// Implementation of `eth` namespace API.
 pub use builder::EthApiBuilder;
 pub use bundle::EthBundle;
+pub use conditional::EthConditional;
 pub use core::{EthApi, EthApiFor};
 pub use filter::EthFilter;
+pub use mev_bundle::EthMevBundle;
 pub use pubsub::EthPubSub;
 
 pub use helpers::{signer::DevSigner, sync_listener::SyncListener};
@@ -45,7 +45,10 @@ pub use admin::AdminApi;
 pub use aliases::*;
 pub use debug::DebugApi;
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
+pub use eth::{
+    helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthConditional, EthFilter,
+    EthMevBundle, EthPubSub,
+};
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
@@ -2,6 +2,8 @@ use std::{future::Future, sync::Arc};
 
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockId;
+#[cfg(feature = "binary-tree")]
+use alloy_primitives::{keccak256, Address, B256};
 use alloy_primitives::{map::AddressMap, U256, U64};
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
@@ -14,12 +16,19 @@ use reth_errors::{RethError, RethResult};
 use reth_evm::{execute::Executor, ConfigureEvm};
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives_traits::{NodePrimitives, SealedHeader};
-use reth_rpc_api::{RethApiServer, RethJitAction};
+#[cfg(feature = "binary-tree")]
+use reth_rpc_api::RethBinaryTreeApiServer;
+use reth_rpc_api::{RethApiServer, RethJitAction, TransactionBlobSidecar};
 use reth_rpc_eth_types::{EthApiError, EthResult};
+#[cfg(feature = "binary-tree")]
+use reth_storage_api::BinaryTreeReader;
 use reth_storage_api::{
     BlockReader, BlockReaderIdExt, ChangeSetReader, StateProviderFactory, TransactionVariant,
 };
 use reth_tasks::{pool::BlockingTaskGuard, Runtime};
+use reth_transaction_pool::BlobStore;
+#[cfg(feature = "binary-tree")]
+use reth_trie_common::binary::BinaryTreeAccountProof;
 use serde::Serialize;
 use tokio::sync::oneshot;
 
@@ -47,11 +56,17 @@ impl<Provider, EvmConfig> RethApi<Provider, EvmConfig> {
     pub fn new(
         provider: Provider,
         evm_config: EvmConfig,
+        blob_store: Box<dyn BlobStore>,
         blocking_task_guard: BlockingTaskGuard,
         task_spawner: Runtime,
     ) -> Self {
-        let inner =
-            Arc::new(RethApiInner { provider, evm_config, blocking_task_guard, task_spawner });
+        let inner = Arc::new(RethApiInner {
+            provider,
+            evm_config,
+            blob_store,
+            blocking_task_guard,
+            task_spawner,
+        });
         Self { inner }
     }
 }
@@ -103,6 +118,66 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the blob sidecars retained for the given finalized block.
+    pub async fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<TransactionBlobSidecar>>> {
+        self.on_blocking_task(async move |this| {
+            let Some(block_number) = this.provider().block_number_for_id(block_id)? else {
+                return Err(EthApiError::HeaderNotFound(block_id))
+            };
+            let sidecars = this
+                .inner
+                .blob_store
+                .get_finalized_block_sidecars(block_number)
+                .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+            Ok(sidecars.map(|sidecars| {
+                sidecars
+                    .into_iter()
+                    .map(|(transaction_hash, sidecar)| TransactionBlobSidecar {
+                        transaction_hash,
+                        sidecar,
+                    })
+                    .collect()
+            }))
+        })
+        .await
+    }
+}
+
+#[cfg(feature = "binary-tree")]
+impl<Provider, EvmConfig> RethApi<Provider, EvmConfig>
+where
+    Provider:
+        BlockReaderIdExt + ChangeSetReader + BinaryTreeReader + StateProviderFactory + 'static,
+    EvmConfig: Send + Sync + 'static,
+{
+    /// Returns the root of the binary state tree after the given block.
+    pub async fn binary_tree_root(&self, block_id: BlockId) -> EthResult<Option<B256>> {
+        self.on_blocking_task(async move |this| {
+            let Some(block_number) = this.provider().block_number_for_id(block_id)? else {
+                return Err(EthApiError::HeaderNotFound(block_id))
+            };
+            Ok(this.provider().binary_tree_root(block_number)?)
+        })
+        .await
+    }
+
+    /// Returns binary tree proofs of the account and the given storage slots against the latest
+    /// binary tree root.
+    pub async fn binary_tree_proof(
+        &self,
+        address: Address,
+        storage_keys: Vec<B256>,
+    ) -> EthResult<Option<BinaryTreeAccountProof>> {
+        self.on_blocking_task(async move |this| {
+            let hashed_slots = storage_keys.iter().map(keccak256).collect::<Vec<_>>();
+            Ok(this.provider().binary_tree_account_proof(keccak256(address), &hashed_slots)?)
+        })
+        .await
+    }
 }
 
 impl<N, Provider, EvmConfig> RethApi<Provider, EvmConfig>
@@ -224,6 +299,14 @@ where
         }
     }
 
+    /// Handler for `reth_getBlobSidecars`
+    async fn reth_get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<TransactionBlobSidecar>>> {
+        Ok(Self::blob_sidecars(self, block_id).await?)
+    }
+
     /// Handler for `reth_jit`
     async fn reth_jit(&self, action: RethJitAction) -> RpcResult<()> {
         let Some(jit_backend) = self.evm_config().jit_backend() else {
@@ -287,6 +370,29 @@ where
     }
 }
 
+#[cfg(feature = "binary-tree")]
+#[async_trait]
+impl<Provider, EvmConfig> RethBinaryTreeApiServer for RethApi<Provider, EvmConfig>
+where
+    Provider:
+        BlockReaderIdExt + ChangeSetReader + BinaryTreeReader + StateProviderFactory + 'static,
+    EvmConfig: Send + Sync + 'static,
+{
+    /// Handler for `reth_getBinaryTreeRoot`
+    async fn reth_get_binary_tree_root(&self, block_id: BlockId) -> RpcResult<Option<B256>> {
+        Ok(Self::binary_tree_root(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getBinaryTreeProof`
+    async fn reth_get_binary_tree_proof(
+        &self,
+        address: Address,
+        storage_keys: Vec<B256>,
+    ) -> RpcResult<Option<BinaryTreeAccountProof>> {
+        Ok(Self::binary_tree_proof(self, address, storage_keys).await?)
+    }
+}
+
 /// Pipes all stream items to the subscription sink.
 async fn pipe_from_stream<S, T>(sink: SubscriptionSink, mut stream: S)
 where
@@ -399,6 +505,8 @@ struct RethApiInner<Provider, EvmConfig> {
     provider: Provider,
     /// The EVM configuration used to create block executors.
     evm_config: EvmConfig,
+    /// The blob store that retains the blob sidecars of finalized blocks.
+    blob_store: Box<dyn BlobStore>,
     /// Guard to restrict the number of concurrent block re-execution requests.
     blocking_task_guard: BlockingTaskGuard,
     /// The type that can spawn tasks which would otherwise block.
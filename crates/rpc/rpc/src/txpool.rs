@@ -2,7 +2,7 @@ use core::fmt;
 use std::collections::BTreeMap;
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
@@ -13,7 +13,8 @@ use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_convert::{RpcConvert, RpcTypes};
 use reth_rpc_eth_api::RpcTransaction;
 use reth_transaction_pool::{
-    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
+    pool::history::TransactionHistory, AllPoolTransactions, PoolConsensusTx, PoolTransaction,
+    TransactionPool,
 };
 use tracing::trace;
 
@@ -147,6 +148,21 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content().map_err(Into::into)?)
     }
+
+    /// Handler for `txpool_transactionHistory`
+    async fn txpool_transaction_history(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Option<TransactionHistory>> {
+        trace!(target: "rpc::eth", ?hash, "Serving txpool_transactionHistory");
+        Ok(self.pool.transaction_history(hash))
+    }
+
+    /// Handler for `txpool_senderHistory`
+    async fn txpool_sender_history(&self, from: Address) -> RpcResult<Vec<TransactionHistory>> {
+        trace!(target: "rpc::eth", ?from, "Serving txpool_senderHistory");
+        Ok(self.pool.sender_transaction_history(from))
+    }
 }
 
 impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
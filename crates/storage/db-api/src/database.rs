@@ -3,7 +3,11 @@ use crate::{
     transaction::{DbTx, DbTxMut},
     DatabaseError,
 };
-use std::{fmt::Debug, path::PathBuf, sync::Arc};
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 /// Main Database trait that can open read-only and read-write transactions.
 ///
@@ -37,6 +41,20 @@ pub trait Database: Send + Sync + Debug {
     /// Returns the ID of the most recently committed transaction, if available.
     fn last_txnid(&self) -> Option<u64>;
 
+    /// Writes a copy of the snapshot seen by the read-only transaction `tx` to the file at `dest`,
+    /// reclaiming free space if `compact` is set.
+    ///
+    /// Returns [`DatabaseError::Other`] if the backend does not support copying.
+    fn copy_snapshot(
+        &self,
+        tx: &Self::TX,
+        dest: &Path,
+        compact: bool,
+    ) -> Result<(), DatabaseError> {
+        let _ = (tx, dest, compact);
+        Err(DatabaseError::Other("database does not support copying a snapshot".to_string()))
+    }
+
     /// Takes a function and passes a read-only transaction into it, making sure it's closed in the
     /// end of the execution.
     fn view<T, F>(&self, f: F) -> Result<T, DatabaseError>
@@ -89,6 +107,15 @@ impl<DB: Database> Database for Arc<DB> {
     fn last_txnid(&self) -> Option<u64> {
         <DB as Database>::last_txnid(self)
     }
+
+    fn copy_snapshot(
+        &self,
+        tx: &Self::TX,
+        dest: &Path,
+        compact: bool,
+    ) -> Result<(), DatabaseError> {
+        <DB as Database>::copy_snapshot(self, tx, dest, compact)
+    }
 }
 
 impl<DB: Database> Database for &DB {
@@ -114,6 +141,15 @@ impl<DB: Database> Database for &DB {
     fn last_txnid(&self) -> Option<u64> {
         <DB as Database>::last_txnid(self)
     }
+
+    fn copy_snapshot(
+        &self,
+        tx: &Self::TX,
+        dest: &Path,
+        compact: bool,
+    ) -> Result<(), DatabaseError> {
+        <DB as Database>::copy_snapshot(self, tx, dest, compact)
+    }
 }
 
 /// Object-safe adapter for reader-txn tracking during unwind.
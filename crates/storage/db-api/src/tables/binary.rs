@@ -0,0 +1,91 @@
+//! Tables of the experimental binary state tree.
+//!
+//! The tables are not part of [`Tables`](super::Tables), they are created in addition to it if the
+//! `binary-tree` feature is enabled, see [`BinaryTreeTables`].
+
+use super::TableSet;
+use crate::table::{Table, TableInfo};
+use alloy_primitives::{BlockNumber, B256};
+
+/// Stores the nodes of the experimental binary state tree, keyed by their encoded path.
+#[derive(Debug)]
+pub struct BinaryTreeNodes {
+    _private: (),
+}
+
+impl Table for BinaryTreeNodes {
+    const NAME: &'static str = "BinaryTreeNodes";
+    const DUPSORT: bool = false;
+
+    type Key = Vec<u8>;
+    type Value = Vec<u8>;
+}
+
+/// Stores the encoded values of each stem of the experimental binary state tree.
+#[derive(Debug)]
+pub struct BinaryTreeStems {
+    _private: (),
+}
+
+impl Table for BinaryTreeStems {
+    const NAME: &'static str = "BinaryTreeStems";
+    const DUPSORT: bool = false;
+
+    type Key = Vec<u8>;
+    type Value = Vec<u8>;
+}
+
+/// Stores the root of the experimental binary state tree after each persisted block.
+#[derive(Debug)]
+pub struct BinaryTreeRoots {
+    _private: (),
+}
+
+impl Table for BinaryTreeRoots {
+    const NAME: &'static str = "BinaryTreeRoots";
+    const DUPSORT: bool = false;
+
+    type Key = BlockNumber;
+    type Value = B256;
+}
+
+/// The tables of the experimental binary state tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryTreeTables {
+    /// The [`BinaryTreeNodes`] table.
+    Nodes,
+    /// The [`BinaryTreeStems`] table.
+    Stems,
+    /// The [`BinaryTreeRoots`] table.
+    Roots,
+}
+
+impl BinaryTreeTables {
+    /// All the tables of the binary tree.
+    pub const ALL: &'static [Self] = &[Self::Nodes, Self::Stems, Self::Roots];
+
+    /// Returns the name of the table.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Nodes => BinaryTreeNodes::NAME,
+            Self::Stems => BinaryTreeStems::NAME,
+            Self::Roots => BinaryTreeRoots::NAME,
+        }
+    }
+}
+
+impl TableInfo for BinaryTreeTables {
+    fn name(&self) -> &'static str {
+        self.name()
+    }
+
+    fn is_dupsort(&self) -> bool {
+        false
+    }
+}
+
+impl TableSet for BinaryTreeTables {
+    fn tables() -> Box<dyn Iterator<Item = Box<dyn TableInfo>>> {
+        Box::new(Self::ALL.iter().map(|table| Box::new(*table) as Box<dyn TableInfo>))
+    }
+}
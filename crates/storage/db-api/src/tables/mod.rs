@@ -16,6 +16,11 @@ pub mod codecs;
 mod raw;
 pub use raw::{RawDupSort, RawKey, RawTable, RawValue, TableRawRow};
 
+#[cfg(feature = "binary-tree")]
+mod binary;
+#[cfg(feature = "binary-tree")]
+pub use binary::{BinaryTreeNodes, BinaryTreeRoots, BinaryTreeStems, BinaryTreeTables};
+
 use crate::{
     models::{
         accounts::BlockNumberAddress,
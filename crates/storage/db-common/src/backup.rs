@@ -0,0 +1,354 @@
+//! Consistent backups of the MDBX database, `RocksDB` and static files.
+//!
+//! A backup is taken by [`create_backup`] from the process that owns the storage, i.e. either a
+//! running node that pauses its persistence service for the duration of the backup, or `reth db
+//! backup` itself if no node is running. A running node accepts [`BackupRequest`]s on a Unix
+//! socket, see [`request_backup`].
+
+use alloy_primitives::{BlockNumber, B256};
+use reth_chainspec::EthChainSpec;
+use reth_db::version::{create_db_version_file, DB_VERSION};
+use reth_db_api::database::Database;
+use reth_nippy_jar::{NippyJar, CHANGESET_OFFSETS_FILE_EXTENSION, CONFIG_FILE_EXTENSION};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockNumReader, ChainSpecProvider, DBProvider, ProviderFactory,
+    RocksDBProviderFactory, StaticFileProviderFactory,
+};
+use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive, StaticFileSegment};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+/// Name of the manifest file written to the root of a backup directory.
+pub const MANIFEST_FILE_NAME: &str = "backup.json";
+
+/// Current version of the backup manifest format.
+pub const MANIFEST_VERSION: u64 = 1;
+
+/// Extensions of the files making up a static file, the data file has none.
+const STATIC_FILE_EXTENSIONS: [&str; 5] =
+    ["", OFFSETS_FILE_EXTENSION, CONFIG_FILE_EXTENSION, "idx", CHANGESET_OFFSETS_FILE_EXTENSION];
+
+/// Extension of static file offsets files.
+const OFFSETS_FILE_EXTENSION: &str = "off";
+
+/// Size of one record of a changeset offsets file.
+const CHANGESET_OFFSET_RECORD_SIZE: u64 = 16;
+
+/// Number of attempts to copy a static file that is pruned while copying it.
+const STATIC_FILE_COPY_ATTEMPTS: usize = 3;
+
+/// Describes the contents of a backup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupManifest {
+    /// Version of the manifest format.
+    pub manifest_version: u64,
+    /// Version of the backed up MDBX database.
+    pub db_version: u64,
+    /// Genesis hash of the chain the backup belongs to.
+    pub genesis_hash: B256,
+    /// Block number of the `Finish` stage checkpoint in the MDBX snapshot.
+    pub block_number: BlockNumber,
+    /// Highest block of each static file segment that was present when the backup was taken.
+    pub static_files: BTreeMap<StaticFileSegment, BlockNumber>,
+}
+
+impl BackupManifest {
+    /// Reads the manifest of the backup in `dir`, failing if its format is not supported.
+    pub fn load(dir: &Path) -> eyre::Result<Self> {
+        let manifest: Self = reth_fs_util::read_json_file(&dir.join(MANIFEST_FILE_NAME))?;
+        eyre::ensure!(
+            manifest.manifest_version == MANIFEST_VERSION,
+            "Unsupported backup manifest version {}, expected {MANIFEST_VERSION}",
+            manifest.manifest_version
+        );
+        Ok(manifest)
+    }
+}
+
+/// Request to create a backup, sent to a running node by `reth db backup`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupRequest {
+    /// Destination directory for the backup, must not exist yet.
+    pub dest: PathBuf,
+    /// Whether to compact the MDBX database while copying it.
+    pub compact: bool,
+    /// Whether to hardlink static files that are no longer appended to instead of copying them.
+    pub hardlink: bool,
+}
+
+/// Response of a running node to a [`BackupRequest`], either the manifest of the backup or the
+/// error that occurred while creating it.
+pub type BackupResponse = Result<BackupManifest, String>;
+
+/// Creates a backup of the storage of `provider_factory` as described by `request`.
+///
+/// The storage must not be written to while the backup is taken: MDBX is copied from a single
+/// read transaction, `RocksDB` is checkpointed and static files are copied up to the highest block
+/// found in their headers on disk, so data written in between would leave the parts of the backup
+/// at different blocks.
+pub fn create_backup<N: ProviderNodeTypes>(
+    provider_factory: &ProviderFactory<N>,
+    request: &BackupRequest,
+) -> eyre::Result<BackupManifest> {
+    let BackupRequest { dest, compact, hardlink } = request;
+    eyre::ensure!(!dest.exists(), "Backup destination already exists: {dest:?}");
+
+    let db_dest = dest.join("db");
+    reth_fs_util::create_dir_all(&db_dest)?;
+
+    let provider = provider_factory.provider()?.disable_long_read_transaction_safety();
+    let block_number = provider.best_block_number()?;
+
+    info!(target: "reth::cli", block_number, dest = ?db_dest, "Copying MDBX database");
+    provider_factory.db_ref().copy_snapshot(
+        provider.tx_ref(),
+        &db_dest.join("mdbx.dat"),
+        *compact,
+    )?;
+    drop(provider);
+    create_db_version_file(&db_dest)?;
+
+    let rocksdb_dest = dest.join("rocksdb");
+    info!(target: "reth::cli", dest = ?rocksdb_dest, "Creating RocksDB checkpoint");
+    provider_factory.rocksdb_provider().create_checkpoint(&rocksdb_dest)?;
+
+    let static_file_provider = provider_factory.static_file_provider();
+    let static_files = highest_static_file_blocks(static_file_provider.directory())?;
+
+    let static_files_dest = dest.join("static_files");
+    info!(target: "reth::cli", dest = ?static_files_dest, "Copying static files");
+    backup_static_files(
+        static_file_provider.directory(),
+        &static_files_dest,
+        &static_files,
+        *hardlink,
+    )?;
+
+    let manifest = BackupManifest {
+        manifest_version: MANIFEST_VERSION,
+        db_version: DB_VERSION,
+        genesis_hash: provider_factory.chain_spec().genesis_hash(),
+        block_number,
+        static_files,
+    };
+    reth_fs_util::write_json_file(&dest.join(MANIFEST_FILE_NAME), &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Asks the node listening on `socket` to create a backup.
+///
+/// Returns `None` if no node is listening on the socket.
+#[cfg(unix)]
+pub fn request_backup(
+    socket: &Path,
+    request: &BackupRequest,
+) -> eyre::Result<Option<BackupManifest>> {
+    use std::{
+        io::{BufRead, BufReader, ErrorKind, Write},
+        os::unix::net::UnixStream,
+    };
+
+    let mut stream = match UnixStream::connect(socket) {
+        Ok(stream) => stream,
+        Err(err) if matches!(err.kind(), ErrorKind::NotFound | ErrorKind::ConnectionRefused) => {
+            return Ok(None)
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response)?;
+    eyre::ensure!(!response.is_empty(), "Node closed the backup connection without a response");
+
+    let response: BackupResponse = serde_json::from_str(&response)?;
+    response.map(Some).map_err(|err| eyre::eyre!("Node failed to create the backup: {err}"))
+}
+
+/// Handles a serialized [`BackupRequest`] received by a running node by passing it to `backup`,
+/// which is expected to create the backup with [`create_backup`].
+///
+/// Returns the serialized [`BackupResponse`], terminated by a newline.
+pub fn handle_backup_request(
+    request: &str,
+    backup: impl FnOnce(&BackupRequest) -> eyre::Result<BackupManifest>,
+) -> String {
+    let response: BackupResponse = serde_json::from_str(request)
+        .map_err(eyre::Report::from)
+        .and_then(|request| backup(&request))
+        .map_err(|err| err.to_string());
+
+    let mut line = serde_json::to_string(&response).expect("response is serializable");
+    line.push('\n');
+    line
+}
+
+/// Returns the data files in the static files directory `dir`, together with their segment and
+/// block range.
+fn static_files(
+    dir: &Path,
+) -> eyre::Result<Vec<(PathBuf, StaticFileSegment, SegmentRangeInclusive)>> {
+    let mut files = Vec::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        let path = entry?.path();
+        // satellite files are handled together with their data file, which has no extension
+        if !path.is_file() || path.extension().is_some() {
+            continue
+        }
+
+        if let Some((segment, range)) = path
+            .file_name()
+            .and_then(|name| StaticFileSegment::parse_filename(&name.to_string_lossy()))
+        {
+            files.push((path, segment, range));
+        }
+    }
+    Ok(files)
+}
+
+/// Returns the highest block of each static file segment, read from the headers of the static
+/// files on disk.
+fn highest_static_file_blocks(
+    dir: &Path,
+) -> eyre::Result<BTreeMap<StaticFileSegment, BlockNumber>> {
+    let mut highest = BTreeMap::<_, BlockNumber>::new();
+    for (path, segment, _) in static_files(dir)? {
+        let jar = NippyJar::<SegmentHeader>::load(&path)?;
+        if let Some(block_end) = jar.user_header().block_end() {
+            let entry = highest.entry(segment).or_default();
+            *entry = (*entry).max(block_end);
+        }
+    }
+    Ok(highest)
+}
+
+/// Copies all static files into `dest`, skipping those that start past the highest block of
+/// their segment.
+///
+/// Static files that are no longer appended to are hardlinked, or copied if hardlinks are disabled.
+/// Of the static files that can still be appended to only the committed part is copied, see
+/// [`copy_committed_static_file`].
+fn backup_static_files(
+    src: &Path,
+    dest: &Path,
+    highest: &BTreeMap<StaticFileSegment, BlockNumber>,
+    hardlink: bool,
+) -> eyre::Result<()> {
+    reth_fs_util::create_dir_all(dest)?;
+
+    for (path, segment, range) in static_files(src)? {
+        let Some(&highest) = highest.get(&segment) else { continue };
+        if range.start() > highest {
+            continue
+        }
+
+        let target = dest.join(path.file_name().expect("is a file"));
+        if range.end() < highest {
+            copy_sealed_static_file(&path, &target, hardlink)?;
+        } else {
+            copy_committed_static_file(&path, &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies a static file that is no longer appended to, together with its satellite files.
+fn copy_sealed_static_file(path: &Path, target: &Path, hardlink: bool) -> eyre::Result<()> {
+    for extension in STATIC_FILE_EXTENSIONS {
+        let (path, target) = (path.with_extension(extension), target.with_extension(extension));
+        if !path.exists() {
+            continue
+        }
+
+        if hardlink {
+            match std::fs::hard_link(&path, &target) {
+                Ok(()) => continue,
+                Err(err) => {
+                    warn!(target: "reth::cli", ?path, %err, "Failed to hardlink static file, copying instead");
+                }
+            }
+        }
+        std::fs::copy(&path, &target)?;
+    }
+    Ok(())
+}
+
+/// Copies the committed part of a static file that can still be appended to.
+///
+/// The configuration is the commit boundary of a static file: it is written after the offsets and
+/// changeset offsets it covers, which in turn are written after the data they point to. The
+/// configuration is read first and only the prefixes of the other files it covers are copied, so
+/// rows appended while copying are left out. If the file is pruned while copying, the copied
+/// prefixes may not match the configuration anymore and the copy is retried.
+fn copy_committed_static_file(path: &Path, target: &Path) -> eyre::Result<()> {
+    for _ in 0..STATIC_FILE_COPY_ATTEMPTS {
+        let config = reth_fs_util::read(path.with_extension(CONFIG_FILE_EXTENSION))?;
+        let jar = NippyJar::<SegmentHeader>::load_from_reader(config.as_slice())?;
+
+        // The offsets file starts with the size of one offset, followed by one offset per column
+        // of every row and the length of the data file.
+        let offsets_path = path.with_extension(OFFSETS_FILE_EXTENSION);
+        let mut offset_size = [0u8; 1];
+        File::open(&offsets_path)?.read_exact(&mut offset_size)?;
+        let offset_size = offset_size[0] as u64;
+        let offsets_len = 1 + offset_size * (jar.rows() * jar.columns()) as u64 + offset_size;
+        let offsets = read_prefix(&offsets_path, offsets_len)?;
+
+        let mut data_len = [0u8; 8];
+        data_len[..offset_size as usize]
+            .copy_from_slice(&offsets[offsets.len() - offset_size as usize..]);
+        let data_len = u64::from_le_bytes(data_len);
+
+        let changeset_offsets_path = path.with_extension(CHANGESET_OFFSETS_FILE_EXTENSION);
+        let changeset_offsets_len =
+            jar.user_header().changeset_offsets_len() * CHANGESET_OFFSET_RECORD_SIZE;
+
+        let copied = copy_prefix(path, target, data_len)? &&
+            (changeset_offsets_len == 0 ||
+                copy_prefix(
+                    &changeset_offsets_path,
+                    &target.with_extension(CHANGESET_OFFSETS_FILE_EXTENSION),
+                    changeset_offsets_len,
+                )?);
+
+        // a pruned file has fewer rows or different offsets than the ones that were copied
+        let current = NippyJar::<SegmentHeader>::load(path)?;
+        if copied &&
+            current.rows() >= jar.rows() &&
+            read_prefix(&offsets_path, offsets_len)? == offsets
+        {
+            reth_fs_util::write(target.with_extension(OFFSETS_FILE_EXTENSION), offsets)?;
+            reth_fs_util::write(target.with_extension(CONFIG_FILE_EXTENSION), config)?;
+            return Ok(())
+        }
+
+        warn!(target: "reth::cli", ?path, "Static file was pruned while copying it, retrying");
+    }
+
+    eyre::bail!("Static file {path:?} kept changing while copying it")
+}
+
+/// Reads the first `len` bytes of the file at `path`.
+fn read_prefix(path: &Path, len: u64) -> eyre::Result<Vec<u8>> {
+    let mut buf = vec![0; len as usize];
+    File::open(path)?.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Copies the first `len` bytes of `src` to `dest`. Returns `false` if `src` is shorter.
+fn copy_prefix(src: &Path, dest: &Path, len: u64) -> eyre::Result<bool> {
+    let mut src = File::open(src)?.take(len);
+    let copied = std::io::copy(&mut src, &mut File::create(dest)?)?;
+    Ok(copied == len)
+}
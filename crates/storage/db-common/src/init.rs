@@ -296,6 +296,13 @@ where
     // compute state root to populate trie tables
     compute_state_root(&provider_rw, None)?;
 
+    // build the binary tree from the genesis state, persisted blocks update it incrementally
+    #[cfg(feature = "binary-tree")]
+    {
+        let root = reth_trie_db::rebuild_binary_tree(provider_rw.tx_ref())?;
+        provider_rw.tx_ref().put::<tables::BinaryTreeRoots>(genesis_block_number, root)?;
+    }
+
     // set stage checkpoint to genesis block number for all stages
     let checkpoint = StageCheckpoint::new(genesis_block_number);
     for stage in StageId::ALL {
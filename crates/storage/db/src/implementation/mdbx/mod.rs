@@ -303,6 +303,37 @@ impl Database for DatabaseEnv {
             Some(txnid as u64)
         }
     }
+
+    fn copy_snapshot(
+        &self,
+        tx: &Self::TX,
+        dest: &Path,
+        compact: bool,
+    ) -> Result<(), DatabaseError> {
+        let mut flags: ffi::MDBX_copy_flags_t = ffi::MDBX_CP_DEFAULTS;
+        if compact {
+            flags |= ffi::MDBX_CP_COMPACT;
+        }
+
+        let dest = std::ffi::CString::new(dest.as_os_str().as_encoded_bytes())
+            .map_err(|err| DatabaseError::Other(err.to_string()))?;
+
+        let rc = tx
+            .inner()
+            .txn_execute(|txn_ptr| unsafe {
+                ffi::mdbx_txn_copy2pathname(txn_ptr, dest.as_ptr(), flags)
+            })
+            .map_err(|e| DatabaseError::Other(e.to_string()))?;
+
+        if rc != 0 {
+            return Err(DatabaseError::Other(format!(
+                "failed to copy database: {}",
+                reth_libmdbx::Error::from_err_code(rc)
+            )))
+        }
+
+        Ok(())
+    }
 }
 
 impl DatabaseMetrics for DatabaseEnv {
@@ -560,9 +591,14 @@ impl DatabaseEnv {
 
     /// Creates all the tables defined in [`Tables`], if necessary.
     ///
+    /// With the `binary-tree` feature, the tables of the binary state tree are created as well.
+    ///
     /// This keeps tracks of the created table handles and stores them for better efficiency.
     pub fn create_tables(&mut self) -> Result<(), DatabaseError> {
-        self.create_and_track_tables_for::<Tables>()
+        self.create_and_track_tables_for::<Tables>()?;
+        #[cfg(feature = "binary-tree")]
+        self.create_and_track_tables_for::<reth_db_api::tables::BinaryTreeTables>()?;
+        Ok(())
     }
 
     /// Creates all the tables defined in the given [`TableSet`], if necessary.
@@ -152,6 +152,15 @@ pub mod test_utils {
         fn last_txnid(&self) -> Option<u64> {
             self.db().last_txnid()
         }
+
+        fn copy_snapshot(
+            &self,
+            tx: &Self::TX,
+            dest: &Path,
+            compact: bool,
+        ) -> Result<(), DatabaseError> {
+            self.db().copy_snapshot(tx, dest, compact)
+        }
     }
 
     impl<DB: DatabaseMetrics> DatabaseMetrics for TempDatabase<DB> {
@@ -104,6 +104,8 @@ pub fn init_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Result
 
 /// Opens up an existing database or creates a new one at the specified path. Creates tables defined
 /// in the given [`TableSet`] if necessary. Read/Write mode.
+///
+/// With the `binary-tree` feature, the tables of the binary state tree are created as well.
 pub fn init_db_for<P: AsRef<Path>, TS: TableSet>(
     path: P,
     args: DatabaseArguments,
@@ -111,6 +113,8 @@ pub fn init_db_for<P: AsRef<Path>, TS: TableSet>(
     let client_version = args.client_version().clone();
     let mut db = create_db(path, args)?;
     db.create_and_track_tables_for::<TS>()?;
+    #[cfg(feature = "binary-tree")]
+    db.create_and_track_tables_for::<reth_db_api::tables::BinaryTreeTables>()?;
     db.record_client_version(client_version)?;
     drop_orphan_tables(&db);
     Ok(db)
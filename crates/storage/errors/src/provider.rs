@@ -105,6 +105,15 @@ pub enum ProviderError {
     /// State is not available for the given block number because it is pruned.
     #[error("state at block #{_0} is pruned")]
     StateAtBlockPruned(BlockNumber),
+    /// History of the address is not available for the given block number because it is pruned
+    /// and the address is not in the set of addresses with retained history.
+    #[error("history of {address} at block #{block_number} is pruned and not retained")]
+    AddressHistoryPruned {
+        /// The address whose history was requested.
+        address: Address,
+        /// The block number that was requested.
+        block_number: BlockNumber,
+    },
     /// State is not available because the block has not been executed yet.
     #[error("state at block #{requested} is not available, block has not been executed yet (latest executed: #{executed})")]
     BlockNotExecuted {
@@ -152,6 +161,9 @@ pub enum ProviderError {
     /// Trying to insert data from an unexpected block number.
     #[error("trying to append row to {_0} at index #{_1} but expected index #{_2}")]
     UnexpectedStaticFileTxNumber(StaticFileSegment, TxNumber, TxNumber),
+    /// Block cannot be restored into a static file below the lowest one of the segment.
+    #[error("unable to restore block #{_1} into {_0} static files")]
+    UnrestorableStaticFileBlock(StaticFileSegment, BlockNumber),
     /// Changeset static file is corrupted, and does not have offsets for changesets in each block
     #[error("changeset static file is corrupted, missing offsets for changesets in each block")]
     CorruptedChangeSetStaticFile,
@@ -29,8 +29,12 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
+#[cfg(feature = "binary-tree")]
+use reth_storage_api::BinaryTreeReader;
 use reth_storage_api::{BlockBodyIndicesProvider, NodePrimitivesProvider, StorageChangeSetReader};
 use reth_storage_errors::provider::ProviderResult;
+#[cfg(feature = "binary-tree")]
+use reth_trie::binary::BinaryTreeAccountProof;
 use reth_trie::{HashedPostState, KeccakKeyHasher};
 use revm_database::BundleState;
 use std::{
@@ -767,6 +771,21 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+#[cfg(feature = "binary-tree")]
+impl<N: ProviderNodeTypes> BinaryTreeReader for BlockchainProvider<N> {
+    fn binary_tree_root(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.database.binary_tree_root(block_number)
+    }
+
+    fn binary_tree_account_proof(
+        &self,
+        hashed_address: B256,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<Option<BinaryTreeAccountProof>> {
+        self.database.binary_tree_account_proof(hashed_address, hashed_slots)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
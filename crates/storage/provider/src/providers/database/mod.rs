@@ -28,11 +28,15 @@ use reth_primitives_traits::{RecoveredBlock, SealedHeader};
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment, MINIMUM_UNWIND_SAFE_DISTANCE};
 use reth_stages_types::{PipelineTarget, StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
+#[cfg(feature = "binary-tree")]
+use reth_storage_api::BinaryTreeReader;
 use reth_storage_api::{
     BlockBodyIndicesProvider, ChainStateBlockReader, ChainStateBlockWriter, DBProvider,
     NodePrimitivesProvider, StorageSettings, StorageSettingsCache, TryIntoHistoricalStateProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
+#[cfg(feature = "binary-tree")]
+use reth_trie::binary::BinaryTreeAccountProof;
 use reth_trie::HashedPostState;
 use reth_trie_db::ChangesetCache;
 use revm_database::BundleState;
@@ -953,6 +957,21 @@ impl<N: ProviderNodeTypes> PruneCheckpointReader for ProviderFactory<N> {
     }
 }
 
+#[cfg(feature = "binary-tree")]
+impl<N: ProviderNodeTypes> BinaryTreeReader for ProviderFactory<N> {
+    fn binary_tree_root(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.provider()?.binary_tree_root(block_number)
+    }
+
+    fn binary_tree_account_proof(
+        &self,
+        hashed_address: B256,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<Option<BinaryTreeAccountProof>> {
+        self.provider()?.binary_tree_account_proof(hashed_address, hashed_slots)
+    }
+}
+
 impl<N: ProviderNodeTypes> HashedPostStateProvider for ProviderFactory<N> {
     fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
         HashedPostState::from_bundle_state::<KeccakKeyHasher>(bundle_state.state())
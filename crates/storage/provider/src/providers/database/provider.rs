@@ -55,20 +55,30 @@ use reth_primitives_traits::{
     SealedHeader, StorageEntry,
 };
 use reth_prune_types::{
-    PruneCheckpoint, PruneMode, PruneModes, PruneSegment, MINIMUM_UNWIND_SAFE_DISTANCE,
+    PruneCheckpoint, PruneMode, PruneModes, PruneSegment, RetainedHistory,
+    MINIMUM_UNWIND_SAFE_DISTANCE,
 };
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_static_file_types::StaticFileSegment;
+#[cfg(feature = "binary-tree")]
+use reth_storage_api::BinaryTreeReader;
 use reth_storage_api::{
     BlockBodyIndicesProvider, BlockBodyReader, MetadataProvider, MetadataWriter,
     NodePrimitivesProvider, StateProvider, StateReader, StateWriteConfig, StorageChangeSetReader,
     StoragePath, StorageSettingsCache, TryIntoHistoricalStateProvider, WriteStateInput,
 };
 use reth_storage_errors::provider::{ProviderResult, StaticFileWriterError};
+#[cfg(feature = "binary-tree")]
+use reth_trie::{
+    binary::{BinaryTree, BinaryTreeAccountProof, BinaryTreeKey},
+    HashedStorageSorted,
+};
 use reth_trie::{
     updates::{StorageTrieUpdatesSorted, TrieUpdatesSorted},
     HashedPostStateSorted,
 };
+#[cfg(feature = "binary-tree")]
+use reth_trie_db::DatabaseBinaryTreeStore;
 use reth_trie_db::{ChangesetCache, DatabaseStorageTrieCursor, TrieTableAdapter};
 use revm_database::states::{
     PlainStateReverts, PlainStorageChangeset, PlainStorageRevert, StateChangeset,
@@ -82,7 +92,7 @@ use std::{
     path::PathBuf,
     sync::Arc,
 };
-use tracing::{debug, instrument, trace};
+use tracing::{debug, instrument, trace, warn};
 
 /// Determines the commit order for database operations.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -306,6 +316,23 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         self.history_by_block_number(block_number)
     }
 
+    /// Returns the persisted [`RetainedHistory`] of the addresses in
+    /// [`PruneModes::history_retained_addresses`], or `None` if no address is configured.
+    ///
+    /// Configured addresses that the pruner hasn't recorded yet are left out, so their history is
+    /// only available above the prune checkpoints.
+    fn configured_retained_history(&self) -> ProviderResult<Option<RetainedHistory>> {
+        let configured = &self.prune_modes.history_retained_addresses;
+        if configured.is_empty() {
+            return Ok(None)
+        }
+
+        let mut retained_history = self.retained_history()?;
+        retained_history.account_history.retain(|address, _| configured.contains(address));
+        retained_history.storage_history.retain(|address, _| configured.contains(address));
+        Ok(Some(retained_history))
+    }
+
     /// Storage provider for state at that given block number
     pub fn history_by_block_number<'a>(
         &'a self,
@@ -343,6 +370,9 @@ impl<TX: DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
                 prune_checkpoint_block_number + 1,
             );
         }
+        if let Some(retained_history) = self.configured_retained_history()? {
+            state_provider = state_provider.with_retained_history(Arc::new(retained_history));
+        }
 
         Ok(Box::new(state_provider))
     }
@@ -724,6 +754,11 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
             // Write all hashed state and trie updates in single batches.
             // This reduces cursor open/close overhead from N calls to 1.
             if save_mode.with_state() {
+                // The binary tree looks up wiped storage in the hashed state tables, so it's
+                // updated before they're written.
+                #[cfg(feature = "binary-tree")]
+                self.update_binary_tree(&blocks)?;
+
                 // Blocks are oldest-to-newest, merge_batch expects newest-to-oldest.
                 let start = Instant::now();
                 let merged_hashed_state = HashedPostStateSorted::merge_batch(
@@ -779,6 +814,105 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         Ok(())
     }
 
+    /// Applies the hashed state of the given blocks to the binary tree and records its root after
+    /// each block.
+    ///
+    /// Must be called before the hashed state of the blocks is written, so that the slots of wiped
+    /// storage can be looked up in the hashed state tables. The tree is only updated if it ends at
+    /// the parent of the first block. It's never rebuilt here, see
+    /// [`reth_trie_db::rebuild_binary_tree`].
+    #[cfg(feature = "binary-tree")]
+    #[instrument(level = "debug", target = "providers::db", skip_all)]
+    fn update_binary_tree(&self, blocks: &[ExecutedBlock<N::Primitives>]) -> ProviderResult<()> {
+        let Some(first) = blocks.first() else { return Ok(()) };
+        let first_number = first.recovered_block().number();
+
+        let Some((head, _)) = self.tx.cursor_read::<tables::BinaryTreeRoots>()?.last()? else {
+            return Ok(())
+        };
+        if head + 1 != first_number {
+            warn!(target: "providers::db", head, first_number, "Binary tree is not at the parent of the first block, skipping update");
+            if head >= first_number {
+                // the tree is ahead of the hashed state, make sure it's never resumed
+                self.remove::<tables::BinaryTreeRoots>(first_number.saturating_sub(1)..)?;
+            }
+            return Ok(())
+        }
+
+        let mut tree = BinaryTree::new(DatabaseBinaryTreeStore::new(&self.tx));
+        // Slots set by earlier blocks of the batch, which aren't in the hashed state tables yet.
+        let mut batch_slots = B256Map::<BTreeSet<B256>>::default();
+        for block in blocks {
+            let hashed_state = block.trie_data().hashed_state;
+            let root = tree.update(&hashed_state, |hashed_address| {
+                let mut slots = reth_trie_db::hashed_storage_slots(&self.tx, hashed_address)?;
+                slots.extend(batch_slots.remove(&hashed_address).unwrap_or_default());
+                Ok(slots)
+            })?;
+            for (hashed_address, storage) in hashed_state.account_storages() {
+                batch_slots
+                    .entry(*hashed_address)
+                    .or_default()
+                    .extend(storage.storage_slots_ref().iter().map(|(slot, _)| *slot));
+            }
+            self.tx.put::<tables::BinaryTreeRoots>(block.recovered_block().number(), root)?;
+        }
+        Ok(())
+    }
+
+    /// Reverts the binary tree to the state before block `from` and removes the roots of the
+    /// unwound blocks.
+    ///
+    /// Must be called after the hashed state tables were unwound, with the accounts and storage
+    /// slots that were reverted. If the tree isn't at the database tip it can't be reverted, and
+    /// its roots from the parent of `from` onwards are removed so it's never resumed.
+    #[cfg(feature = "binary-tree")]
+    fn unwind_binary_tree(
+        &self,
+        from: BlockNumber,
+        db_tip: BlockNumber,
+        accounts: &BTreeMap<B256, Option<Account>>,
+        storages: &B256Map<BTreeSet<B256>>,
+    ) -> ProviderResult<()> {
+        let Some((head, _)) = self.tx.cursor_read::<tables::BinaryTreeRoots>()?.last()? else {
+            return Ok(())
+        };
+        if head < from {
+            return Ok(())
+        }
+        if head != db_tip {
+            warn!(target: "providers::db", head, db_tip, from, "Binary tree is not at the database tip, discarding it");
+            self.remove::<tables::BinaryTreeRoots>(from.saturating_sub(1)..)?;
+            return Ok(())
+        }
+
+        let mut hashed_storages = self.tx.cursor_dup_read::<tables::HashedStorages>()?;
+        let mut reverted_storages = B256Map::default();
+        for (hashed_address, slots) in storages {
+            let mut storage_slots = Vec::with_capacity(slots.len());
+            for slot in slots {
+                let value = hashed_storages
+                    .seek_by_key_subkey(*hashed_address, *slot)?
+                    .filter(|entry| entry.key == *slot)
+                    .map(|entry| entry.value)
+                    .unwrap_or_default();
+                storage_slots.push((*slot, value));
+            }
+            reverted_storages
+                .insert(*hashed_address, HashedStorageSorted { storage_slots, wiped: false });
+        }
+        let reverted = HashedPostStateSorted::new(
+            accounts.iter().map(|(hashed_address, account)| (*hashed_address, *account)).collect(),
+            reverted_storages,
+        );
+
+        // wiped storage is restored slot by slot from the changesets
+        BinaryTree::new(DatabaseBinaryTreeStore::new(&self.tx))
+            .update(&reverted, |_| Ok(Vec::new()))?;
+        self.remove::<tables::BinaryTreeRoots>(from..)?;
+        Ok(())
+    }
+
     /// Writes MDBX-only data for a block (indices, lookups, and senders if configured for MDBX).
     ///
     /// SF data (headers, transactions, senders if SF, receipts if SF) must be written separately.
@@ -852,7 +986,8 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         let changed_accounts = self.account_changesets_range(from..)?;
 
         // Unwind account hashes.
-        self.unwind_account_hashing(changed_accounts.iter())?;
+        #[cfg_attr(not(feature = "binary-tree"), expect(unused_variables))]
+        let reverted_accounts = self.unwind_account_hashing(changed_accounts.iter())?;
 
         // Unwind account history indices.
         self.unwind_account_history_indices(changed_accounts.iter())?;
@@ -860,7 +995,8 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         let changed_storages = self.storage_changesets_range(from..)?;
 
         // Unwind storage hashes.
-        self.unwind_storage_hashing(changed_storages.iter().copied())?;
+        #[cfg_attr(not(feature = "binary-tree"), expect(unused_variables))]
+        let reverted_storages = self.unwind_storage_hashing(changed_storages.iter().copied())?;
 
         // Unwind storage history indices.
         self.unwind_storage_history_indices(changed_storages.iter().copied())?;
@@ -879,6 +1015,9 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         let trie_revert = self.changeset_cache.get_or_compute_range(self, from..=db_tip_block)?;
         self.write_trie_updates_sorted(&trie_revert)?;
 
+        #[cfg(feature = "binary-tree")]
+        self.unwind_binary_tree(from, db_tip_block, &reverted_accounts, &reverted_storages)?;
+
         Ok(())
     }
 
@@ -967,6 +1106,9 @@ impl<TX: DbTx + 'static, N: NodeTypes> TryIntoHistoricalStateProvider for Databa
                 prune_checkpoint_block_number + 1,
             );
         }
+        if let Some(retained_history) = self.configured_retained_history()? {
+            state_provider = state_provider.with_retained_history(Arc::new(retained_history));
+        }
 
         Ok(Box::new(state_provider))
     }
@@ -1606,6 +1748,38 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
     }
 }
 
+#[cfg(feature = "binary-tree")]
+impl<TX: DbTx, N: NodeTypes> BinaryTreeReader for DatabaseProvider<TX, N> {
+    fn binary_tree_root(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        Ok(self.tx.get::<tables::BinaryTreeRoots>(block_number)?)
+    }
+
+    fn binary_tree_account_proof(
+        &self,
+        hashed_address: B256,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<Option<BinaryTreeAccountProof>> {
+        let Some((block_number, root)) =
+            self.tx.cursor_read::<tables::BinaryTreeRoots>()?.last()?
+        else {
+            return Ok(None)
+        };
+
+        let tree = BinaryTree::new(DatabaseBinaryTreeStore::new(&self.tx));
+        let storage = hashed_slots
+            .iter()
+            .map(|slot| tree.proof(BinaryTreeKey::storage_slot(hashed_address, *slot)))
+            .collect::<Result<_, _>>()?;
+        Ok(Some(BinaryTreeAccountProof {
+            block_number,
+            root,
+            basic_data: tree.proof(BinaryTreeKey::basic_data(hashed_address))?,
+            code_hash: tree.proof(BinaryTreeKey::code_hash(hashed_address))?,
+            storage,
+        }))
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
     fn account_block_changeset(
         &self,
@@ -8,9 +8,13 @@ mod database;
 pub use database::*;
 
 mod static_file;
+#[cfg(feature = "s3-cold-storage")]
+pub use static_file::S3ColdStorage;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderBuilder,
-    StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWriteCtx, StaticFileWriter,
+    ColdStorage, ColdStorageConfig, LocalColdStorage, StaticFileAccess, StaticFileJarProvider,
+    StaticFileProvider, StaticFileProviderBuilder, StaticFileProviderRW,
+    StaticFileProviderRWRefMut, StaticFileWriteCtx, StaticFileWriter, DEFAULT_COLD_CACHE_MAX_BYTES,
+    DEFAULT_COLD_KEEP_RECENT_BLOCKS,
 };
 
 mod state;
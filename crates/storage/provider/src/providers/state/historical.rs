@@ -13,6 +13,7 @@ use reth_db_api::{
     BlockNumberList,
 };
 use reth_primitives_traits::{Account, Bytecode, NodePrimitives};
+use reth_prune_types::{PruneSegment, RetainedHistory};
 use reth_storage_api::{
     BlockNumReader, BytecodeReader, DBProvider, NodePrimitivesProvider, PruneCheckpointReader,
     StageCheckpointReader, StateProofProvider, StorageChangeSetReader, StorageRootProvider,
@@ -136,6 +137,9 @@ pub struct HistoricalStateProviderRef<
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Addresses whose history is available below [`LowestAvailableBlocks`], down to the block
+    /// the pruner started retaining it at.
+    retained_history: Option<Arc<RetainedHistory>>,
     /// Marker for the provider's node primitives.
     _primitives: PhantomData<N>,
 }
@@ -160,6 +164,7 @@ where
             changeset_cache,
             block_number,
             lowest_available_blocks: Default::default(),
+            retained_history: None,
             _primitives: PhantomData,
         }
     }
@@ -177,6 +182,7 @@ where
             changeset_cache,
             block_number,
             lowest_available_blocks,
+            retained_history: None,
             _primitives: PhantomData,
         }
     }
@@ -186,20 +192,23 @@ where
     where
         Provider: StorageSettingsCache + RocksDBProviderFactory + NodePrimitivesProvider,
     {
-        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        let lowest_available =
+            match self.retained_history_since(PruneSegment::AccountHistory, &address) {
+                Some(since) => (since > 0).then_some(since),
+                None if self
+                    .lowest_available_blocks
+                    .is_account_history_available(self.block_number) =>
+                {
+                    self.lowest_available_blocks.account_history_block_number
+                }
+                None => return Err(self.history_pruned_error(address)),
+            };
 
         let visible_tip = self.provider.best_block_number()?;
 
         self.provider.with_rocksdb_snapshot(|rocksdb_ref| {
             let mut reader = EitherReader::new_accounts_history(self.provider, rocksdb_ref)?;
-            reader.account_history_info(
-                address,
-                self.block_number,
-                self.lowest_available_blocks.account_history_block_number,
-                visible_tip,
-            )
+            reader.account_history_info(address, self.block_number, lowest_available, visible_tip)
         })
     }
 
@@ -214,9 +223,17 @@ where
     where
         Provider: StorageSettingsCache + RocksDBProviderFactory + NodePrimitivesProvider,
     {
-        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
-            return Err(ProviderError::StateAtBlockPruned(self.block_number))
-        }
+        let lowest_available =
+            match self.retained_history_since(PruneSegment::StorageHistory, &address) {
+                Some(since) => (since > 0).then_some(since),
+                None if self
+                    .lowest_available_blocks
+                    .is_storage_history_available(self.block_number) =>
+                {
+                    self.lowest_available_blocks.storage_history_block_number
+                }
+                None => return Err(self.history_pruned_error(address)),
+            };
 
         let visible_tip = self.provider.best_block_number()?;
 
@@ -226,12 +243,34 @@ where
                 address,
                 lookup_key,
                 self.block_number,
-                self.lowest_available_blocks.storage_history_block_number,
+                lowest_available,
                 visible_tip,
             )
         })
     }
 
+    /// Returns the block from which the pruner retains the history of the address in the given
+    /// segment, if the history at the provider's block number is retained.
+    fn retained_history_since(
+        &self,
+        segment: PruneSegment,
+        address: &Address,
+    ) -> Option<BlockNumber> {
+        self.retained_history
+            .as_ref()
+            .and_then(|history| history.segment(segment)?.get(address).copied())
+            .filter(|since| self.block_number >= *since)
+    }
+
+    /// Returns the error for a history lookup of the address below the lowest available block.
+    fn history_pruned_error(&self, address: Address) -> ProviderError {
+        if self.retained_history.is_some() {
+            ProviderError::AddressHistoryPruned { address, block_number: self.block_number }
+        } else {
+            ProviderError::StateAtBlockPruned(self.block_number)
+        }
+    }
+
     /// Resolves a storage value by looking up the given key in history, changesets, or
     /// plain state.
     ///
@@ -334,6 +373,13 @@ where
         self.lowest_available_blocks.storage_history_block_number = Some(block_number);
         self
     }
+
+    /// Set the addresses whose history is retained by the pruner, so their history is available
+    /// below the lowest available blocks, down to the block it's retained from.
+    pub fn with_retained_history(mut self, retained_history: Arc<RetainedHistory>) -> Self {
+        self.retained_history = Some(retained_history);
+        self
+    }
 }
 
 impl<Provider, N> HistoricalStateProviderRef<'_, Provider, N>
@@ -695,6 +741,9 @@ pub struct HistoricalStateProvider<Provider> {
     block_number: BlockNumber,
     /// Lowest blocks at which different parts of the state are available.
     lowest_available_blocks: LowestAvailableBlocks,
+    /// Addresses whose history is available below [`LowestAvailableBlocks`], down to the block
+    /// the pruner started retaining it at.
+    retained_history: Option<Arc<RetainedHistory>>,
 }
 
 impl<Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + BlockNumReader>
@@ -711,6 +760,7 @@ impl<Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + BlockNumR
             changeset_cache,
             block_number,
             lowest_available_blocks: Default::default(),
+            retained_history: None,
         }
     }
 
@@ -731,6 +781,13 @@ impl<Provider: DBProvider + ChangeSetReader + StorageChangeSetReader + BlockNumR
         self.lowest_available_blocks.storage_history_block_number = Some(block_number);
         self
     }
+
+    /// Set the addresses whose history is retained by the pruner, so their history is available
+    /// below the lowest available blocks, down to the block it's retained from.
+    pub fn with_retained_history(mut self, retained_history: Arc<RetainedHistory>) -> Self {
+        self.retained_history = Some(retained_history);
+        self
+    }
 }
 
 impl<
@@ -744,12 +801,16 @@ impl<
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     fn as_ref(&self) -> HistoricalStateProviderRef<'_, Provider> {
-        HistoricalStateProviderRef::new_with_lowest_available_blocks(
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
             &self.provider,
             self.block_number,
             self.lowest_available_blocks,
             self.changeset_cache.clone(),
-        )
+        );
+        match &self.retained_history {
+            Some(retained_history) => provider.with_retained_history(retained_history.clone()),
+            None => provider,
+        }
     }
 }
 
@@ -885,6 +946,7 @@ mod tests {
         BlockNumberList,
     };
     use reth_primitives_traits::{Account, StorageEntry};
+    use reth_prune_types::RetainedHistory;
     use reth_storage_api::{
         BlockHashReader, BlockNumReader, ChangeSetReader, DBProvider, DatabaseProviderFactory,
         NodePrimitivesProvider, PruneCheckpointReader, StageCheckpointReader,
@@ -892,6 +954,7 @@ mod tests {
     };
     use reth_storage_errors::provider::ProviderError;
     use reth_trie_db::ChangesetCache;
+    use std::sync::Arc;
 
     const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0x0000000000000000000000000000000000000005");
@@ -1193,6 +1256,99 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn history_provider_retained_addresses() {
+        let factory = create_test_provider_factory();
+        let db = factory.database_provider_rw().unwrap();
+
+        // provider block_number < lowest available block number, but the history of `ADDRESS`
+        // is retained
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            2,
+            LowestAvailableBlocks {
+                account_history_block_number: Some(3),
+                storage_history_block_number: Some(3),
+            },
+            ChangesetCache::new(),
+        )
+        .with_retained_history(Arc::new(RetainedHistory {
+            account_history: [(ADDRESS, 0)].into(),
+            storage_history: [(ADDRESS, 0)].into(),
+        }));
+        assert!(matches!(provider.account_history_lookup(ADDRESS), Ok(HistoryInfo::NotYetWritten)));
+        assert!(matches!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Ok(HistoryInfo::NotYetWritten)
+        ));
+        assert!(matches!(
+            provider.account_history_lookup(HIGHER_ADDRESS),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == HIGHER_ADDRESS && block_number == provider.block_number
+        ));
+        assert!(matches!(
+            provider.storage_history_lookup(HIGHER_ADDRESS, STORAGE),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == HIGHER_ADDRESS && block_number == provider.block_number
+        ));
+    }
+
+    #[test]
+    fn history_provider_address_retained_after_pruning() {
+        let factory = create_test_provider_factory();
+        let db = factory.database_provider_rw().unwrap();
+
+        // history up to block 4 was pruned before `ADDRESS` was retained
+        let retained_history = Arc::new(RetainedHistory {
+            account_history: [(ADDRESS, 5)].into(),
+            storage_history: [(ADDRESS, 5)].into(),
+        });
+        let lowest_available_blocks = LowestAvailableBlocks {
+            account_history_block_number: Some(8),
+            storage_history_block_number: Some(8),
+        };
+
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            2,
+            lowest_available_blocks,
+            ChangesetCache::new(),
+        )
+        .with_retained_history(retained_history.clone());
+        assert!(matches!(
+            provider.account_history_lookup(ADDRESS),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == ADDRESS && block_number == 2
+        ));
+        assert!(matches!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == ADDRESS && block_number == 2
+        ));
+
+        // the history of `ADDRESS` is available from block 5, but it may have been written before
+        let provider = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+            &db,
+            6,
+            lowest_available_blocks,
+            ChangesetCache::new(),
+        )
+        .with_retained_history(retained_history);
+        assert!(matches!(
+            provider.account_history_lookup(ADDRESS),
+            Ok(HistoryInfo::MaybeInPlainState)
+        ));
+        assert!(matches!(
+            provider.storage_history_lookup(ADDRESS, STORAGE),
+            Ok(HistoryInfo::MaybeInPlainState)
+        ));
+        assert!(matches!(
+            provider.account_history_lookup(HIGHER_ADDRESS),
+            Err(ProviderError::AddressHistoryPruned { address, block_number })
+                if address == HIGHER_ADDRESS && block_number == 6
+        ));
+    }
+
     #[test]
     fn test_history_info_from_lookup() {
         // Before first write, no pruning → not yet written
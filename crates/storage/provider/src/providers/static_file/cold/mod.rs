@@ -0,0 +1,489 @@
+//! Cold storage tier for sealed static files.
+//!
+//! Sealed jars of the configured segments that are older than
+//! [`ColdStorageConfig::keep_recent_blocks`] can be moved to a [`ColdStorage`] backend. Only the
+//! configuration file of an offloaded jar stays in the static files directory, so the provider
+//! index is unaffected. When an offloaded jar is read, its files are fetched into a size-bounded
+//! local read cache and served through the regular
+//! [`StaticFileJarProvider`](super::StaticFileJarProvider) API.
+
+use parking_lot::Mutex;
+use reth_nippy_jar::NippyJar;
+use reth_static_file_types::{
+    SegmentHeader, SegmentRangeInclusive, StaticFileMap, StaticFileSegment,
+};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+use tracing::{debug, trace};
+
+#[cfg(feature = "s3-cold-storage")]
+mod s3;
+#[cfg(feature = "s3-cold-storage")]
+pub use s3::S3ColdStorage;
+
+/// Extension of the jar configuration file, which always stays local.
+const CONFIG_EXTENSION: &str = "conf";
+
+/// Extensions of the files that make up the data of a jar and are moved to cold storage.
+///
+/// `None` is the data file itself. The changeset offsets file only exists for change-based
+/// segments.
+const DATA_EXTENSIONS: [Option<&str>; 3] = [None, Some("off"), Some("csoff")];
+
+/// Default number of most recent blocks whose static files are always kept local.
+pub const DEFAULT_COLD_KEEP_RECENT_BLOCKS: u64 = 1_000_000;
+
+/// Default maximum size of the local read cache, 16 GiB.
+pub const DEFAULT_COLD_CACHE_MAX_BYTES: u64 = 16 * 1024 * 1024 * 1024;
+
+/// Object storage for sealed static files.
+///
+/// Objects are addressed by the static file name, e.g. `static_file_transactions_0_499999.off`.
+pub trait ColdStorage: Debug + Send + Sync + 'static {
+    /// Uploads the local file at `src` as `name`, replacing any existing object.
+    fn upload(&self, name: &str, src: &Path) -> io::Result<()>;
+
+    /// Downloads the object `name` to `dst`.
+    ///
+    /// Returns an [`io::ErrorKind::NotFound`] error if the object does not exist.
+    fn download(&self, name: &str, dst: &Path) -> io::Result<()>;
+
+    /// Deletes the object `name`. Deleting a missing object is not an error.
+    fn delete(&self, name: &str) -> io::Result<()>;
+}
+
+/// [`ColdStorage`] that keeps files in a local directory, usually on a slower or larger disk.
+#[derive(Debug, Clone)]
+pub struct LocalColdStorage {
+    dir: PathBuf,
+}
+
+impl LocalColdStorage {
+    /// Creates a new [`LocalColdStorage`] in the given directory, creating it if necessary.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Returns the directory that stores the cold files.
+    pub fn directory(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl ColdStorage for LocalColdStorage {
+    fn upload(&self, name: &str, src: &Path) -> io::Result<()> {
+        let dst = self.dir.join(name);
+        let tmp = partial_path(&dst);
+        fs::copy(src, &tmp)?;
+        File::open(&tmp)?.sync_all()?;
+        fs::rename(tmp, dst)
+    }
+
+    fn download(&self, name: &str, dst: &Path) -> io::Result<()> {
+        fs::copy(self.dir.join(name), dst).map(|_| ())
+    }
+
+    fn delete(&self, name: &str) -> io::Result<()> {
+        match fs::remove_file(self.dir.join(name)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Configuration of the static files cold storage tier.
+#[derive(Debug, Clone)]
+pub struct ColdStorageConfig {
+    /// Backend that stores the cold files.
+    pub storage: Arc<dyn ColdStorage>,
+    /// Segments whose sealed files are moved to cold storage. Headers are always kept local.
+    pub segments: Vec<StaticFileSegment>,
+    /// Number of most recent blocks whose static files are always kept local.
+    pub keep_recent_blocks: u64,
+    /// Directory of the local read cache.
+    pub cache_dir: PathBuf,
+    /// Maximum size of the local read cache in bytes.
+    pub cache_max_bytes: u64,
+}
+
+impl ColdStorageConfig {
+    /// Creates a new configuration that moves transactions and receipts to `storage`, caching
+    /// fetched files in `cache_dir`.
+    pub fn new(storage: Arc<dyn ColdStorage>, cache_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            storage,
+            segments: vec![StaticFileSegment::Transactions, StaticFileSegment::Receipts],
+            keep_recent_blocks: DEFAULT_COLD_KEEP_RECENT_BLOCKS,
+            cache_dir: cache_dir.into(),
+            cache_max_bytes: DEFAULT_COLD_CACHE_MAX_BYTES,
+        }
+    }
+
+    /// Sets the segments whose sealed files are moved to cold storage.
+    ///
+    /// The headers segment is ignored.
+    pub fn with_segments(mut self, segments: impl IntoIterator<Item = StaticFileSegment>) -> Self {
+        self.segments = segments.into_iter().filter(|segment| !segment.is_headers()).collect();
+        self
+    }
+
+    /// Sets the number of most recent blocks whose static files are always kept local.
+    pub const fn with_keep_recent_blocks(mut self, keep_recent_blocks: u64) -> Self {
+        self.keep_recent_blocks = keep_recent_blocks;
+        self
+    }
+
+    /// Sets the maximum size of the local read cache in bytes.
+    pub const fn with_cache_max_bytes(mut self, cache_max_bytes: u64) -> Self {
+        self.cache_max_bytes = cache_max_bytes;
+        self
+    }
+
+    /// Returns `true` if sealed files of the segment are moved to cold storage.
+    pub fn is_cold_segment(&self, segment: StaticFileSegment) -> bool {
+        !segment.is_headers() && self.segments.contains(&segment)
+    }
+}
+
+/// Cold storage tier of a [`StaticFileProvider`](super::StaticFileProvider).
+#[derive(Debug)]
+pub(crate) struct ColdTier {
+    config: ColdStorageConfig,
+    /// Locks of the jars that are being transferred, by jar name.
+    ///
+    /// Transfers of the same jar wait for each other, while different jars are transferred
+    /// concurrently. Read cache evictions hold the map lock, so no transfer starts meanwhile.
+    transfers: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+}
+
+impl ColdTier {
+    /// Creates a new [`ColdTier`], creating the cache directory if necessary.
+    pub(crate) fn new(config: ColdStorageConfig) -> io::Result<Self> {
+        fs::create_dir_all(&config.cache_dir)?;
+        Ok(Self { config, transfers: Mutex::default() })
+    }
+
+    /// Returns the cold storage configuration.
+    pub(crate) const fn config(&self) -> &ColdStorageConfig {
+        &self.config
+    }
+
+    /// Returns `true` if the jar with the given local data path has been moved to cold storage.
+    pub(crate) fn is_offloaded(data_path: &Path) -> bool {
+        !data_path.exists() && data_path.with_extension(CONFIG_EXTENSION).exists()
+    }
+
+    /// Adds the jars that have been moved to cold storage to `static_files`, which only lists
+    /// jars with a local data file.
+    pub(crate) fn extend_with_offloaded(
+        &self,
+        static_files_dir: &Path,
+        static_files: &mut StaticFileMap<Vec<(SegmentRangeInclusive, SegmentHeader)>>,
+    ) -> io::Result<()> {
+        for entry in fs::read_dir(static_files_dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|extension| extension != CONFIG_EXTENSION) {
+                continue
+            }
+
+            let data_path = path.with_extension("");
+            let Some((segment, _)) = data_path
+                .file_name()
+                .and_then(|name| StaticFileSegment::parse_filename(&name.to_string_lossy()))
+            else {
+                continue
+            };
+            if data_path.exists() {
+                continue
+            }
+
+            let jar = NippyJar::<SegmentHeader>::load(&data_path).map_err(io::Error::other)?;
+            if let Some(block_range) = jar.user_header().block_range() {
+                trace!(target: "providers::static_file::cold", ?segment, ?block_range, "Found offloaded static file");
+                static_files
+                    .entry(segment)
+                    .and_modify(|headers| headers.push((block_range, jar.user_header().clone())))
+                    .or_insert_with(|| vec![(block_range, jar.user_header().clone())]);
+            }
+        }
+
+        for range_list in static_files.values_mut() {
+            range_list.sort_unstable_by_key(|(block_range, _)| block_range.end());
+        }
+
+        Ok(())
+    }
+
+    /// Uploads the files of the jar at `data_path` to cold storage.
+    ///
+    /// The configuration file is uploaded as well, so a lost static files directory can be
+    /// recovered from cold storage. The local files are kept until [`Self::remove_local`] is
+    /// called.
+    pub(crate) fn upload(&self, data_path: &Path) -> io::Result<()> {
+        let name = file_name(data_path)?;
+        self.with_jar_lock(&name, || {
+            for extension in DATA_EXTENSIONS.into_iter().chain([Some(CONFIG_EXTENSION)]) {
+                let path = with_extension(data_path, extension);
+                if path.exists() {
+                    debug!(target: "providers::static_file::cold", ?path, "Uploading static file to cold storage");
+                    self.config.storage.upload(&object_name(&name, extension), &path)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes the local data files of the jar at `data_path` once it has been uploaded, keeping
+    /// its configuration file.
+    pub(crate) fn remove_local(data_path: &Path) -> io::Result<()> {
+        for extension in DATA_EXTENSIONS {
+            match fs::remove_file(with_extension(data_path, extension)) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes the uploaded objects of the jar at `data_path`, which is still local.
+    pub(crate) fn discard_upload(&self, data_path: &Path) -> io::Result<()> {
+        let name = file_name(data_path)?;
+        self.with_jar_lock(&name, || self.delete_objects(&name))
+    }
+
+    /// Returns the path of the offloaded jar with the given local data path in the read cache,
+    /// fetching it from cold storage if necessary.
+    ///
+    /// Only fetches of the same jar wait for each other.
+    pub(crate) fn fetch(
+        &self,
+        data_path: &Path,
+        segment: StaticFileSegment,
+    ) -> io::Result<PathBuf> {
+        let name = file_name(data_path)?;
+        let cached = self.config.cache_dir.join(&name);
+
+        let fetched = self.with_jar_lock(&name, || {
+            if cached.exists() && cached.with_extension(CONFIG_EXTENSION).exists() {
+                trace!(target: "providers::static_file::cold", ?cached, "Static file found in read cache");
+                // Mark as recently used for eviction.
+                File::options().write(true).open(&cached)?.set_modified(SystemTime::now())?;
+                return Ok(false)
+            }
+
+            debug!(target: "providers::static_file::cold", ?data_path, "Fetching static file from cold storage");
+            self.download_data(&name, &cached, segment)?;
+            // The configuration is written last, since it marks the cached jar as complete.
+            fs::copy(
+                data_path.with_extension(CONFIG_EXTENSION),
+                cached.with_extension(CONFIG_EXTENSION),
+            )?;
+            Ok(true)
+        })?;
+
+        if fetched {
+            self.evict(&name)?;
+        }
+        Ok(cached)
+    }
+
+    /// Moves the offloaded jar with the given local data path back to the static files directory
+    /// and removes it from cold storage and the read cache.
+    pub(crate) fn restore(&self, data_path: &Path, segment: StaticFileSegment) -> io::Result<()> {
+        let name = file_name(data_path)?;
+        debug!(target: "providers::static_file::cold", ?data_path, "Restoring static file from cold storage");
+
+        self.with_jar_lock(&name, || {
+            self.download_data(&name, data_path, segment)?;
+            self.delete_objects(&name)?;
+            remove_cached(&self.config.cache_dir.join(&name))
+        })
+    }
+
+    /// Removes the jar with the given local data path from cold storage and the read cache.
+    ///
+    /// If the jar has been offloaded, its local configuration file is removed as well.
+    pub(crate) fn delete(&self, data_path: &Path) -> io::Result<()> {
+        let name = file_name(data_path)?;
+
+        self.with_jar_lock(&name, || {
+            if Self::is_offloaded(data_path) {
+                fs::remove_file(data_path.with_extension(CONFIG_EXTENSION))?;
+            }
+            self.delete_objects(&name)?;
+            remove_cached(&self.config.cache_dir.join(&name))
+        })
+    }
+
+    /// Runs `f` while holding the transfer lock of the jar `name`.
+    fn with_jar_lock<T>(&self, name: &str, f: impl FnOnce() -> T) -> T {
+        let lock = Arc::clone(self.transfers.lock().entry(name.to_string()).or_default());
+        let result = {
+            let _guard = lock.lock();
+            f()
+        };
+
+        let mut transfers = self.transfers.lock();
+        // Only the map and this call hold the lock if nobody else is waiting for it.
+        if Arc::strong_count(&lock) == 2 {
+            transfers.remove(name);
+        }
+        result
+    }
+
+    /// Downloads the data files of the jar `name` next to `dst`, which is the data file path.
+    ///
+    /// Each file is downloaded to a temporary path first, so an interrupted download never
+    /// leaves a partial file behind.
+    fn download_data(&self, name: &str, dst: &Path, segment: StaticFileSegment) -> io::Result<()> {
+        for extension in DATA_EXTENSIONS {
+            let path = with_extension(dst, extension);
+            let tmp = partial_path(&path);
+            match self.config.storage.download(&object_name(name, extension), &tmp) {
+                Ok(()) => fs::rename(&tmp, &path)?,
+                // Empty changeset offsets are never written.
+                Err(err)
+                    if err.kind() == io::ErrorKind::NotFound &&
+                        extension == Some("csoff") &&
+                        segment.is_change_based() => {}
+                Err(err) => {
+                    let _ = fs::remove_file(&tmp);
+                    return Err(err)
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes all objects of the jar `name` from cold storage.
+    fn delete_objects(&self, name: &str) -> io::Result<()> {
+        for extension in DATA_EXTENSIONS.into_iter().chain([Some(CONFIG_EXTENSION)]) {
+            self.config.storage.delete(&object_name(name, extension))?;
+        }
+        Ok(())
+    }
+
+    /// Evicts the least recently used jars from the read cache until it fits into
+    /// [`ColdStorageConfig::cache_max_bytes`]. The jar `keep` and jars that are being transferred
+    /// are never evicted.
+    ///
+    /// Evicted jars may still be memory mapped by the provider, which remains valid until they
+    /// are unmapped.
+    fn evict(&self, keep: &str) -> io::Result<()> {
+        let transfers = self.transfers.lock();
+
+        // Jar name -> (size of all files, last use)
+        let mut jars: HashMap<String, (u64, SystemTime)> = HashMap::new();
+        for entry in fs::read_dir(&self.config.cache_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            // Partial files belong to downloads in progress.
+            if path.extension().is_some_and(|extension| extension == "partial") {
+                continue
+            }
+            let metadata = entry.metadata()?;
+            let Some(stem) = path.file_stem().map(|stem| stem.to_string_lossy().into_owned())
+            else {
+                continue
+            };
+
+            let jar = jars.entry(stem).or_insert((0, SystemTime::UNIX_EPOCH));
+            jar.0 += metadata.len();
+            if path.extension().is_none() {
+                jar.1 = metadata.modified()?;
+            }
+        }
+
+        let mut total = jars.values().map(|(size, _)| size).sum::<u64>();
+        let mut jars = jars
+            .into_iter()
+            .filter(|(name, _)| name != keep && !transfers.contains_key(name))
+            .collect::<Vec<_>>();
+        jars.sort_unstable_by_key(|(_, (_, last_use))| *last_use);
+
+        for (name, (size, _)) in jars {
+            if total <= self.config.cache_max_bytes {
+                break
+            }
+            debug!(target: "providers::static_file::cold", %name, size, "Evicting static file from read cache");
+            remove_cached(&self.config.cache_dir.join(name))?;
+            total -= size;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the file name of `path`.
+fn file_name(path: &Path) -> io::Result<String> {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, format!("{} has no file name", path.display()))
+    })
+}
+
+/// Returns the object name of the jar file with the given optional extension.
+fn object_name(name: &str, extension: Option<&str>) -> String {
+    match extension {
+        Some(extension) => format!("{name}.{extension}"),
+        None => name.to_string(),
+    }
+}
+
+/// Returns the path of the jar file with the given optional extension.
+fn with_extension(data_path: &Path, extension: Option<&str>) -> PathBuf {
+    match extension {
+        Some(extension) => data_path.with_extension(extension),
+        None => data_path.to_path_buf(),
+    }
+}
+
+/// Returns the temporary path used while writing `path`.
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".partial");
+    PathBuf::from(name)
+}
+
+/// Removes all cached files of the jar with the given data path in the read cache.
+fn remove_cached(data_path: &Path) -> io::Result<()> {
+    for extension in DATA_EXTENSIONS.into_iter().chain([Some(CONFIG_EXTENSION)]) {
+        match fs::remove_file(with_extension(data_path, extension)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_cold_storage() {
+        let cold = tempfile::tempdir().unwrap();
+        let local = tempfile::tempdir().unwrap();
+        let storage = LocalColdStorage::new(cold.path()).unwrap();
+
+        let src = local.path().join("file");
+        fs::write(&src, b"data").unwrap();
+        storage.upload("object", &src).unwrap();
+
+        let dst = local.path().join("downloaded");
+        storage.download("object", &dst).unwrap();
+        assert_eq!(fs::read(&dst).unwrap(), b"data");
+
+        storage.delete("object").unwrap();
+        storage.delete("object").unwrap();
+        assert_eq!(storage.download("object", &dst).unwrap_err().kind(), io::ErrorKind::NotFound);
+    }
+}
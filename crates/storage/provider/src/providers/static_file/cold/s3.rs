@@ -0,0 +1,268 @@
+//! [`ColdStorage`] backed by an S3-compatible object store.
+
+use super::ColdStorage;
+use alloy_primitives::hex;
+use hmac::{Hmac, Mac};
+use reqwest::{
+    header::{AUTHORIZATION, CONTENT_LENGTH},
+    Client, Method, RequestBuilder, Response, StatusCode, Url,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    fmt::Debug,
+    future::Future,
+    io,
+    path::Path,
+    sync::{mpsc, Arc},
+};
+use tokio::{io::AsyncWriteExt, runtime::Runtime};
+
+/// Headers covered by the request signature of [`S3ColdStorage`].
+const S3_SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+/// Payload hash sent with every request, file contents are not hashed.
+const S3_UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Number of threads of the runtime that drives the requests of [`S3ColdStorage`].
+const S3_RUNTIME_THREADS: usize = 2;
+
+/// [`ColdStorage`] backed by an S3-compatible object store.
+///
+/// Requests use path-style addressing (`<endpoint>/<bucket>/<prefix><name>`) and are signed with
+/// AWS Signature Version 4, so any S3-compatible server can be used. Each object is streamed with
+/// a single request, so the backend must accept objects as large as a static file.
+///
+/// All requests share one HTTP client and are driven by a small runtime owned by the storage,
+/// since cold storage is accessed from blocking code.
+#[derive(Clone)]
+pub struct S3ColdStorage {
+    endpoint: Url,
+    bucket: String,
+    region: String,
+    prefix: String,
+    access_key_id: String,
+    secret_access_key: String,
+    client: Client,
+    runtime: Arc<S3Runtime>,
+}
+
+impl S3ColdStorage {
+    /// Creates a new [`S3ColdStorage`] for the given endpoint, bucket and credentials.
+    pub fn new(
+        endpoint: Url,
+        bucket: impl Into<String>,
+        region: impl Into<String>,
+        access_key_id: impl Into<String>,
+        secret_access_key: impl Into<String>,
+    ) -> io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(S3_RUNTIME_THREADS)
+            .thread_name("s3-cold-storage")
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            endpoint,
+            bucket: bucket.into(),
+            region: region.into(),
+            prefix: String::new(),
+            access_key_id: access_key_id.into(),
+            secret_access_key: secret_access_key.into(),
+            client: Client::builder().build().map_err(io::Error::other)?,
+            runtime: Arc::new(S3Runtime(Some(runtime))),
+        })
+    }
+
+    /// Sets the prefix prepended to every object key.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Returns the URL of the object `name`.
+    fn object_url(&self, name: &str) -> Url {
+        let mut url = self.endpoint.clone();
+        url.set_path(&format!("/{}/{}{name}", self.bucket, self.prefix));
+        url
+    }
+
+    /// Creates a request for the object `name` signed with AWS Signature Version 4.
+    fn signed_request(&self, method: Method, name: &str) -> RequestBuilder {
+        let url = self.object_url(name);
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+
+        let host = match url.port() {
+            Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+        let canonical_request = format!(
+            "{method}\n{}\n\nhost:{host}\nx-amz-content-sha256:{S3_UNSIGNED_PAYLOAD}\nx-amz-date:{amz_date}\n\n{S3_SIGNED_HEADERS}\n{S3_UNSIGNED_PAYLOAD}",
+            url.path()
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = [self.region.as_bytes(), b"s3".as_slice(), b"aws4_request".as_slice()]
+            .into_iter()
+            .fold(
+                hmac_sha256(format!("AWS4{}", self.secret_access_key).as_bytes(), date.as_bytes()),
+                |key, data| hmac_sha256(&key, data),
+            );
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        self.client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", S3_UNSIGNED_PAYLOAD)
+            .header(
+                AUTHORIZATION,
+                format!(
+                    "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={S3_SIGNED_HEADERS}, Signature={signature}",
+                    self.access_key_id
+                ),
+            )
+    }
+
+    /// Runs the request on the runtime of the storage and waits for its result.
+    ///
+    /// The result is received through a channel instead of `block_on`, because static file reads
+    /// may happen on threads of another runtime.
+    fn run<T: Send + 'static>(
+        &self,
+        request: impl Future<Output = io::Result<T>> + Send + 'static,
+    ) -> io::Result<T> {
+        let (tx, rx) = mpsc::sync_channel(1);
+        self.runtime.handle().spawn(async move {
+            let _ = tx.send(request.await);
+        });
+        rx.recv().map_err(|_| io::Error::other("S3 request was cancelled"))?
+    }
+}
+
+impl Debug for S3ColdStorage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("S3ColdStorage")
+            .field("endpoint", &self.endpoint)
+            .field("bucket", &self.bucket)
+            .field("region", &self.region)
+            .field("prefix", &self.prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ColdStorage for S3ColdStorage {
+    fn upload(&self, name: &str, src: &Path) -> io::Result<()> {
+        let request = self.signed_request(Method::PUT, name);
+        let (name, src) = (name.to_string(), src.to_path_buf());
+        self.run(async move {
+            let file = tokio::fs::File::open(&src).await?;
+            let len = file.metadata().await?.len();
+            let response = request
+                .header(CONTENT_LENGTH, len)
+                .body(file)
+                .send()
+                .await
+                .map_err(io::Error::other)?;
+            s3_status(&name, &response)
+        })
+    }
+
+    fn download(&self, name: &str, dst: &Path) -> io::Result<()> {
+        let request = self.signed_request(Method::GET, name);
+        let (name, dst) = (name.to_string(), dst.to_path_buf());
+        self.run(async move {
+            let mut response = request.send().await.map_err(io::Error::other)?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Err(io::Error::new(io::ErrorKind::NotFound, format!("{name} not found")))
+            }
+            s3_status(&name, &response)?;
+            stream_to_file(&mut response, &dst).await
+        })
+    }
+
+    fn delete(&self, name: &str) -> io::Result<()> {
+        let request = self.signed_request(Method::DELETE, name);
+        let name = name.to_string();
+        self.run(async move {
+            let response = request.send().await.map_err(io::Error::other)?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(())
+            }
+            s3_status(&name, &response)
+        })
+    }
+}
+
+/// Runtime that drives the requests of [`S3ColdStorage`].
+///
+/// It's shut down in the background when dropped, since the storage may be dropped in an async
+/// context.
+#[derive(Debug)]
+struct S3Runtime(Option<Runtime>);
+
+impl S3Runtime {
+    fn handle(&self) -> &tokio::runtime::Handle {
+        self.0.as_ref().expect("runtime is only taken on drop").handle()
+    }
+}
+
+impl Drop for S3Runtime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.0.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// Writes the body of the response to `dst` chunk by chunk.
+async fn stream_to_file(response: &mut Response, dst: &Path) -> io::Result<()> {
+    let mut file = tokio::fs::File::create(dst).await?;
+    while let Some(chunk) = response.chunk().await.map_err(io::Error::other)? {
+        file.write_all(&chunk).await?;
+    }
+    file.sync_all().await
+}
+
+/// Returns an error if the S3 request for `name` was not successful.
+fn s3_status(name: &str, response: &Response) -> io::Result<()> {
+    let status = response.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("S3 request for {name} failed with status {status}")))
+    }
+}
+
+/// Computes `HMAC-SHA256(key, data)`.
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn s3_object_url() {
+        let storage = S3ColdStorage::new(
+            "http://localhost:9000".parse().unwrap(),
+            "reth",
+            "us-east-1",
+            "access",
+            "secret",
+        )
+        .unwrap()
+        .with_prefix("mainnet/");
+
+        assert_eq!(
+            storage.object_url("static_file_transactions_0_499999.off").as_str(),
+            "http://localhost:9000/reth/mainnet/static_file_transactions_0_499999.off"
+        );
+    }
+}
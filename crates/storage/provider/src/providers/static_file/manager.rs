@@ -1,6 +1,8 @@
 use super::{
-    metrics::StaticFileProviderMetrics, writer::StaticFileWriters, LoadedJar,
-    StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    cold::{ColdStorageConfig, ColdTier},
+    metrics::StaticFileProviderMetrics,
+    writer::StaticFileWriters,
+    LoadedJar, StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
 };
 use crate::{
     changeset_walker::{StaticFileAccountChangesetWalker, StaticFileStorageChangesetWalker},
@@ -131,6 +133,7 @@ pub struct StaticFileProviderBuilder<P> {
     blocks_per_file: StaticFileMap<u64>,
     path: P,
     genesis_block_number: u64,
+    cold_storage: Option<ColdStorageConfig>,
 }
 
 impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
@@ -142,6 +145,7 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             blocks_per_file: Default::default(),
             use_metrics: false,
             genesis_block_number: 0,
+            cold_storage: None,
         }
     }
 
@@ -153,6 +157,7 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             blocks_per_file: Default::default(),
             use_metrics: false,
             genesis_block_number: 0,
+            cold_storage: None,
         }
     }
 
@@ -218,6 +223,16 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
         self
     }
 
+    /// Enables the cold storage tier.
+    ///
+    /// Sealed files of the configured segments can then be moved to cold storage with
+    /// [`StaticFileProvider::offload_cold_static_files`], and are fetched into the local read
+    /// cache when accessed.
+    pub fn with_cold_storage(mut self, config: ColdStorageConfig) -> Self {
+        self.cold_storage = Some(config);
+        self
+    }
+
     /// Builds the final [`StaticFileProvider`] and initializes the index.
     pub fn build<N: NodePrimitives>(self) -> ProviderResult<StaticFileProvider<N>> {
         let mut provider = StaticFileProviderInner::new(self.path, self.access)?;
@@ -229,6 +244,8 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             provider.blocks_per_file.insert(segment, blocks_per_file);
         }
         provider.genesis_block_number = self.genesis_block_number;
+        provider.cold =
+            self.cold_storage.map(ColdTier::new).transpose().map_err(ProviderError::other)?;
 
         let provider = StaticFileProvider(Arc::new(provider));
         provider.initialize_index()?;
@@ -301,6 +318,8 @@ pub struct StaticFileProviderInner<N> {
     _lock_file: Option<StorageLock>,
     /// Genesis block number, default is 0;
     genesis_block_number: u64,
+    /// Cold storage tier for sealed static files, if enabled.
+    cold: Option<ColdTier>,
 }
 
 impl<N: NodePrimitives> StaticFileProviderInner<N> {
@@ -328,6 +347,7 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             blocks_per_file,
             _lock_file,
             genesis_block_number: 0,
+            cold: None,
         };
 
         Ok(provider)
@@ -879,6 +899,65 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         }
     }
 
+    /// Returns a writer that backfills the fixed block range directly below the lowest static file
+    /// of `segment`, e.g. to restore data that was previously removed by
+    /// [`Self::delete_segment_below_block`].
+    ///
+    /// Ranges are restored top-down so the segment never has gaps. The writer has to be filled
+    /// from the start of the range up to its end before committing, and is not cached by the
+    /// provider. A leftover file from an interrupted restore of the same range is discarded.
+    pub fn restore_writer(
+        &self,
+        segment: StaticFileSegment,
+    ) -> ProviderResult<StaticFileProviderRW<N>> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess);
+        }
+
+        let lowest_range = self
+            .get_lowest_range(segment)
+            .ok_or(ProviderError::MissingHighestStaticFileBlock(segment))?;
+
+        // Only whole static files are deleted when pruning, so anything else can't be restored.
+        let lowest_start = lowest_range.start();
+        if lowest_start <= self.genesis_block_number() ||
+            self.find_fixed_range(segment, lowest_start).start() != lowest_start
+        {
+            return Err(ProviderError::UnrestorableStaticFileBlock(
+                segment,
+                lowest_start.saturating_sub(1),
+            ));
+        }
+
+        let block_range = self.find_fixed_range(segment, lowest_start - 1);
+        let file = self.path.join(segment.filename(&block_range));
+        if file.exists() {
+            debug!(
+                target: "providers::static_file",
+                ?file,
+                ?block_range,
+                "Deleting leftover static file of an interrupted restore"
+            );
+            if segment.is_change_based() {
+                let csoff_path = file.with_extension("csoff");
+                if csoff_path.exists() {
+                    std::fs::remove_file(&csoff_path).map_err(ProviderError::other)?;
+                }
+            }
+            NippyJar::<SegmentHeader>::load(&file)
+                .map_err(ProviderError::other)?
+                .delete()
+                .map_err(ProviderError::other)?;
+        }
+
+        StaticFileProviderRW::new_restore(
+            segment,
+            block_range,
+            Arc::downgrade(&self.0),
+            self.metrics.clone(),
+        )
+    }
+
     /// Given a segment and block, it deletes the jar and all files from the respective block range.
     ///
     /// CAUTION: destructive. Deletes files on disk.
@@ -917,6 +996,14 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             }
         }
 
+        // Offloaded jars are loaded from the read cache, so their local configuration, cold
+        // objects and cached copies are removed separately.
+        if let Some(cold) = &self.cold &&
+            ColdTier::is_offloaded(&file)
+        {
+            cold.delete(&file).map_err(ProviderError::other)?;
+        }
+
         jar.delete().map_err(ProviderError::other)?;
 
         // SAFETY: this is currently necessary to ensure that certain indexes like
@@ -956,6 +1043,101 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(deleted_headers)
     }
 
+    /// Moves sealed static files of the segments configured in [`ColdStorageConfig`] to cold
+    /// storage.
+    ///
+    /// Files are only moved if they end at least [`ColdStorageConfig::keep_recent_blocks`] blocks
+    /// below the highest block of their segment. The highest file of a segment is never moved.
+    ///
+    /// Files are uploaded while the segment can still be written to, and the local files are
+    /// removed under the writer lock of the segment, so this can run concurrently with the writer.
+    ///
+    /// Returns the moved segments and their block ranges. Does nothing if the cold storage tier
+    /// is not enabled.
+    ///
+    /// CAUTION: cached providers of the moved files and the writer of the segment should be
+    /// dropped before calling this or IT WILL deadlock.
+    pub fn offload_cold_static_files(
+        &self,
+    ) -> ProviderResult<Vec<(StaticFileSegment, SegmentRangeInclusive)>> {
+        let Some(cold) = &self.cold else { return Ok(Vec::new()) };
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        let mut offloaded = Vec::new();
+        for &segment in &cold.config().segments {
+            if !cold.config().is_cold_segment(segment) {
+                continue
+            }
+            let Some(highest_block) = self.get_highest_static_file_block(segment) else { continue };
+            let offload_below = highest_block.saturating_sub(cold.config().keep_recent_blocks);
+
+            let ranges = self
+                .indexes
+                .read()
+                .get(segment)
+                .map(|index| {
+                    index
+                        .expected_block_ranges_by_max_block
+                        .values()
+                        .filter(|range| range.end() < offload_below && range.end() < highest_block)
+                        .copied()
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+
+            for range in ranges {
+                let path = self.path.join(segment.filename(&range));
+                let Some(version) = file_version(&path)? else { continue };
+
+                // Sealed files are uploaded without blocking writers of the segment.
+                debug!(target: "providers::static_file", ?segment, ?range, "Moving static file to cold storage");
+                cold.upload(&path).map_err(ProviderError::other)?;
+
+                // The local file is only removed under the writer lock, unless it was unwound or
+                // deleted during the upload.
+                let _writer = self.writers.lock(segment);
+                if file_version(&path)? != Some(version) {
+                    debug!(target: "providers::static_file", ?segment, ?range, "Static file changed while moving it to cold storage");
+                    cold.discard_upload(&path).map_err(ProviderError::other)?;
+                    continue
+                }
+                self.map.remove(&(range.end(), segment));
+                ColdTier::remove_local(&path).map_err(ProviderError::other)?;
+                offloaded.push((segment, range));
+            }
+        }
+
+        if !offloaded.is_empty() {
+            info!(target: "providers::static_file", files = offloaded.len(), "Moved static files to cold storage");
+        }
+
+        Ok(offloaded)
+    }
+
+    /// Moves the static file of the given segment and block range back from cold storage, if it
+    /// has been offloaded.
+    ///
+    /// This is required before the file can be opened for writing, e.g. when unwinding.
+    ///
+    /// CAUTION: cached provider should be dropped before calling this or IT WILL deadlock.
+    pub fn ensure_local_static_file(
+        &self,
+        segment: StaticFileSegment,
+        fixed_block_range: &SegmentRangeInclusive,
+    ) -> ProviderResult<()> {
+        let Some(cold) = &self.cold else { return Ok(()) };
+
+        let path = self.path.join(segment.filename(fixed_block_range));
+        if ColdTier::is_offloaded(&path) {
+            self.map.remove(&(fixed_block_range.end(), segment));
+            cold.restore(&path, segment).map_err(ProviderError::other)?;
+        }
+
+        Ok(())
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -973,7 +1155,12 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             jar.into()
         } else {
             trace!(target: "providers::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
-            let path = self.path.join(segment.filename(fixed_block_range));
+            let mut path = self.path.join(segment.filename(fixed_block_range));
+            if let Some(cold) = &self.cold &&
+                ColdTier::is_offloaded(&path)
+            {
+                path = cold.fetch(&path, segment).map_err(ProviderError::other)?;
+            }
             let jar = NippyJar::load(&path).map_err(ProviderError::other)?;
             self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
         };
@@ -1181,7 +1368,13 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         let mut indexes = self.indexes.write();
         indexes.clear();
 
-        for (segment, headers) in &*iter_static_files(&self.path).map_err(ProviderError::other)? {
+        let mut static_files = iter_static_files(&self.path).map_err(ProviderError::other)?;
+        if let Some(cold) = &self.cold {
+            cold.extend_with_offloaded(&self.path, &mut static_files)
+                .map_err(ProviderError::other)?;
+        }
+
+        for (segment, headers) in &*static_files {
             // Update first and last block for each segment
             //
             // It's safe to call `expect` here, because every segment has at least one header
@@ -3013,6 +3206,18 @@ where
     Ok((*tx.tx_hash(), tx_id))
 }
 
+/// Returns the size and modification time of the data file at `path`, or `None` if it doesn't
+/// exist.
+fn file_version(path: &Path) -> ProviderResult<Option<(u64, std::time::SystemTime)>> {
+    match std::fs::metadata(path) {
+        Ok(metadata) => {
+            Ok(Some((metadata.len(), metadata.modified().map_err(ProviderError::other)?)))
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(ProviderError::other(err)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -12,6 +12,14 @@ pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
 
+mod cold;
+#[cfg(feature = "s3-cold-storage")]
+pub use cold::S3ColdStorage;
+pub use cold::{
+    ColdStorage, ColdStorageConfig, LocalColdStorage, DEFAULT_COLD_CACHE_MAX_BYTES,
+    DEFAULT_COLD_KEEP_RECENT_BLOCKS,
+};
+
 #[cfg(test)]
 mod writer_tests;
 
@@ -612,6 +620,145 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_restore_pruned_static_files() {
+        let segment = StaticFileSegment::TransactionSenders;
+        let (static_dir, _) = create_test_static_files_dir();
+        let sf_rw: StaticFileProvider<EthPrimitives> =
+            StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(10)
+                .build()
+                .expect("Failed to build static file provider");
+        let sender = |tx_num: u64| Address::from(U160::from(tx_num));
+
+        // One transaction per block, so transaction and block numbers match.
+        {
+            let mut writer = sf_rw.latest_writer(segment).unwrap();
+            for block in 0..30 {
+                writer.increment_block(block).unwrap();
+                writer.append_transaction_sender(block, &sender(block)).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        // Prune the static files for blocks `0..=19`.
+        assert_eq!(sf_rw.delete_segment_below_block(segment, 20).unwrap().len(), 2);
+        assert_eq!(sf_rw.get_lowest_range_start(segment), Some(20));
+
+        // Ranges are restored top-down, starting with `10..=19`.
+        for range_start in [10, 0] {
+            let mut writer = sf_rw.restore_writer(segment).unwrap();
+            assert_eq!(writer.user_header().expected_block_start(), range_start);
+
+            for block in range_start..range_start + 5 {
+                writer.increment_block(block).unwrap();
+                writer.append_transaction_sender(block, &sender(block)).unwrap();
+            }
+            // Committing only part of the range would leave a gap.
+            assert!(matches!(
+                writer.commit(),
+                Err(ProviderError::UnrestorableStaticFileBlock(_, block)) if block == range_start + 5
+            ));
+
+            for block in range_start + 5..range_start + 10 {
+                writer.increment_block(block).unwrap();
+                writer.append_transaction_sender(block, &sender(block)).unwrap();
+            }
+            // The restored static file never advances into the existing one above it.
+            assert!(matches!(
+                writer.increment_block(range_start + 10),
+                Err(ProviderError::UnrestorableStaticFileBlock(_, _))
+            ));
+            writer.commit().unwrap();
+
+            assert_eq!(sf_rw.get_lowest_range_start(segment), Some(range_start));
+            assert_eq!(sf_rw.get_highest_static_file_block(segment), Some(29));
+        }
+
+        for tx_num in 0..30 {
+            assert_eq!(sf_rw.transaction_sender(tx_num).unwrap(), Some(sender(tx_num)));
+        }
+
+        // Nothing is left to restore below the first static file.
+        assert!(matches!(
+            sf_rw.restore_writer(segment),
+            Err(ProviderError::UnrestorableStaticFileBlock(_, 0))
+        ));
+    }
+
+    #[test]
+    fn test_cold_static_files() {
+        let segment = StaticFileSegment::Receipts;
+        let blocks_per_file = 10;
+        let (static_dir, _) = create_test_static_files_dir();
+        let cold_dir = tempfile::tempdir().unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+
+        let build = || {
+            let storage = LocalColdStorage::new(cold_dir.path()).unwrap();
+            StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(blocks_per_file)
+                .with_cold_storage(
+                    ColdStorageConfig::new(Arc::new(storage), cache_dir.path())
+                        .with_segments([segment])
+                        .with_keep_recent_blocks(5),
+                )
+                .build::<EthPrimitives>()
+                .expect("Failed to build static file provider")
+        };
+
+        let sf_rw = build();
+        setup_tx_based_scenario(&sf_rw, segment, blocks_per_file);
+
+        // Files ending more than 5 blocks below the highest block are moved, the highest is kept.
+        let first_range = SegmentRangeInclusive::new(0, 9);
+        let second_range = SegmentRangeInclusive::new(10, 19);
+        assert_eq!(
+            sf_rw.offload_cold_static_files().unwrap(),
+            vec![(segment, first_range), (segment, second_range)]
+        );
+        assert!(sf_rw.offload_cold_static_files().unwrap().is_empty());
+
+        // Only the configuration stays local.
+        let first_name = segment.filename(&first_range);
+        let first_path = static_dir.path().join(&first_name);
+        assert!(!first_path.exists());
+        assert!(!first_path.with_extension("off").exists());
+        assert!(first_path.with_extension("conf").exists());
+        assert!(cold_dir.path().join(&first_name).exists());
+
+        // Offloaded files are fetched into the read cache.
+        assert_eq!(sf_rw.receipt(0).unwrap().map(|r| r.cumulative_gas_used), Some(0));
+        assert!(cache_dir.path().join(&first_name).exists());
+
+        // The index includes offloaded files after a restart.
+        drop(sf_rw);
+        let sf_rw = build();
+        assert_eq!(sf_rw.get_highest_static_file_tx(segment), Some(9));
+        assert_eq!(sf_rw.receipt(8).unwrap().map(|r| r.cumulative_gas_used), Some(8));
+        assert_eq!(
+            sf_rw.tx_index(segment),
+            Some(BTreeMap::from([(8, first_range), (9, SegmentRangeInclusive::new(20, 29))]))
+        );
+
+        // Unwinding into an offloaded file moves it back from cold storage.
+        let mut writer = sf_rw.latest_writer(segment).unwrap();
+        writer.prune_receipts(1, blocks_per_file * 2 - 1).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+
+        let second_name = segment.filename(&second_range);
+        assert_eq!(sf_rw.get_highest_static_file_block(segment), Some(19));
+        assert!(static_dir.path().join(&second_name).exists());
+        assert!(!cold_dir.path().join(&second_name).exists());
+
+        // Deleting an offloaded file removes it from cold storage and the read cache.
+        sf_rw.delete_jar(segment, 0).unwrap();
+        assert!(!first_path.with_extension("conf").exists());
+        assert!(!cold_dir.path().join(&first_name).exists());
+        assert!(!cache_dir.path().join(&first_name).exists());
+    }
+
     /// Returns the number of files in the provided path, excluding ".lock" files.
     fn count_files_without_lockfile(path: impl AsRef<Path>) -> eyre::Result<usize> {
         let is_lockfile = |entry: &fs::DirEntry| {
@@ -129,6 +129,22 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
         *write_guard = None;
     }
 
+    /// Locks the writer slot of a segment without creating a writer, so nothing is written to the
+    /// segment until the guard is dropped.
+    pub(crate) fn lock(
+        &self,
+        segment: StaticFileSegment,
+    ) -> RwLockWriteGuard<'_, RawRwLock, Option<StaticFileProviderRW<N>>> {
+        match segment {
+            StaticFileSegment::Headers => self.headers.write(),
+            StaticFileSegment::Transactions => self.transactions.write(),
+            StaticFileSegment::Receipts => self.receipts.write(),
+            StaticFileSegment::TransactionSenders => self.transaction_senders.write(),
+            StaticFileSegment::AccountChangeSets => self.account_change_sets.write(),
+            StaticFileSegment::StorageChangeSets => self.storage_change_sets.write(),
+        }
+    }
+
     #[instrument(
         name = "StaticFileWriters::commit",
         level = "debug",
@@ -253,6 +269,9 @@ pub struct StaticFileProviderRW<N> {
     changeset_offsets: Option<ChangesetOffsetWriter>,
     /// Current block's changeset offset being written.
     current_changeset_offset: Option<ChangesetOffset>,
+    /// Whether this writer backfills a range below the lowest static file of the segment. See
+    /// [`StaticFileProvider::restore_writer`].
+    restore: bool,
 }
 
 impl<N: NodePrimitives> StaticFileProviderRW<N> {
@@ -279,6 +298,7 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
             synced: false,
             changeset_offsets: None,
             current_changeset_offset: None,
+            restore: false,
         };
 
         // Run NippyJar healing BEFORE setting up changeset sidecar
@@ -293,6 +313,22 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         Ok(writer)
     }
 
+    /// Creates a new [`StaticFileProviderRW`] that backfills the static file for `block_range`,
+    /// which has to be the fixed range directly below the lowest static file of `segment`.
+    ///
+    /// Unlike regular writers, it never advances into the next static file, and on commit it
+    /// requires the whole range to be written and re-initializes the reader index.
+    pub(crate) fn new_restore(
+        segment: StaticFileSegment,
+        block_range: SegmentRangeInclusive,
+        reader: Weak<StaticFileProviderInner<N>>,
+        metrics: Option<Arc<StaticFileProviderMetrics>>,
+    ) -> ProviderResult<Self> {
+        let mut writer = Self::new(segment, block_range.start(), reader, metrics)?;
+        writer.restore = true;
+        Ok(writer)
+    }
+
     fn open(
         segment: StaticFileSegment,
         block: u64,
@@ -304,6 +340,8 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         let static_file_provider = Self::upgrade_provider_to_strong_reference(&reader);
 
         let block_range = static_file_provider.find_fixed_range(segment, block);
+        // Sealed files may have been moved to cold storage, but writers need them local.
+        static_file_provider.ensure_local_static_file(segment, &block_range)?;
         let (jar, path) = match static_file_provider.get_segment_provider_for_block(
             segment,
             block_range.start(),
@@ -603,6 +641,17 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
     pub fn commit(&mut self) -> ProviderResult<()> {
         let start = Instant::now();
 
+        // A partially restored static file would leave a gap between it and the next one.
+        if self.restore &&
+            self.writer.user_header().block_end() !=
+                Some(self.writer.user_header().expected_block_end())
+        {
+            return Err(ProviderError::UnrestorableStaticFileBlock(
+                self.writer.user_header().segment(),
+                self.next_block_number(),
+            ))
+        }
+
         // Truncates the data file if instructed to.
         if let Some(strategy) = self.prune_on_commit.take() {
             debug!(
@@ -710,6 +759,12 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
 
     /// Updates the `self.reader` internal index.
     fn update_index(&self) -> ProviderResult<()> {
+        // The index update below assumes the writer is at the tip of the segment, which a
+        // restored static file never is.
+        if self.restore {
+            return self.reader().initialize_index()
+        }
+
         let segment = self.writer.user_header().segment();
 
         // We find the maximum block of the segment by checking this writer's last block.
@@ -782,6 +837,14 @@ impl<N: NodePrimitives> StaticFileProviderRW<N> {
         if let Some(last_block) = self.writer.user_header().block_end() {
             // We have finished the previous static file and must freeze it
             if last_block == self.writer.user_header().expected_block_end() {
+                // The next static file already exists when restoring.
+                if self.restore {
+                    return Err(ProviderError::UnrestorableStaticFileBlock(
+                        segment,
+                        expected_block_number,
+                    ))
+                }
+
                 // Commits offsets and new user_header to disk
                 self.commit()?;
 
@@ -32,12 +32,16 @@ use reth_primitives_traits::{
 };
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
+#[cfg(feature = "binary-tree")]
+use reth_storage_api::BinaryTreeReader;
 use reth_storage_api::{
     BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
     HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader, StateProofProvider,
     StorageChangeSetReader, StorageRootProvider, StorageSettingsCache,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
+#[cfg(feature = "binary-tree")]
+use reth_trie::binary::BinaryTreeAccountProof;
 use reth_trie::{
     updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
     MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
@@ -1003,6 +1007,21 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> BlockBodyIndicesProvider
     }
 }
 
+#[cfg(feature = "binary-tree")]
+impl<T: NodePrimitives, ChainSpec: Send + Sync> BinaryTreeReader for MockEthProvider<T, ChainSpec> {
+    fn binary_tree_root(&self, _block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        Ok(None)
+    }
+
+    fn binary_tree_account_proof(
+        &self,
+        _hashed_address: B256,
+        _hashed_slots: &[B256],
+    ) -> ProviderResult<Option<BinaryTreeAccountProof>> {
+        Ok(None)
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvider<T, ChainSpec> {
     fn account_block_changeset(
         &self,
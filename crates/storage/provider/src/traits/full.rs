@@ -2,7 +2,7 @@
 
 use crate::{
     AccountReader, BalProvider, BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, HashedPostStateProvider, PruneCheckpointReader,
+    DatabaseProviderFactory, HashedPostStateProvider, MaybeBinaryTreeReader, PruneCheckpointReader,
     RocksDBProviderFactory, StageCheckpointReader, StateProviderFactory, StateReader,
     StaticFileProviderFactory,
 };
@@ -39,6 +39,7 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
     + StorageChangeSetReader
+    + MaybeBinaryTreeReader
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + PersistedBlockSubscriptions
@@ -76,6 +77,7 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
         + StorageChangeSetReader
+        + MaybeBinaryTreeReader
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + PersistedBlockSubscriptions
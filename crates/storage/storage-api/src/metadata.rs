@@ -1,13 +1,20 @@
 //! Metadata provider trait for reading and writing node metadata.
 
 use alloc::vec::Vec;
+use alloy_primitives::BlockNumber;
 use reth_db_api::models::StorageSettings;
+use reth_prune_types::RetainedHistory;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 
 /// Metadata keys.
 pub mod keys {
     /// Storage configuration settings for this node.
     pub const STORAGE_SETTINGS: &str = "storage_settings";
+    /// Addresses whose history is retained by the pruner, with the blocks their history is
+    /// complete from.
+    pub const RETAINED_HISTORY: &str = "retained_history";
+    /// Block the datadir was branched off at to run a shadow fork.
+    pub const SHADOW_FORK_BLOCK: &str = "shadow_fork_block";
 }
 
 /// Client trait for reading node metadata from the database.
@@ -26,6 +33,23 @@ pub trait MetadataProvider: Send {
             .get_metadata(keys::STORAGE_SETTINGS)?
             .and_then(|bytes| serde_json::from_slice(&bytes).ok()))
     }
+
+    /// Get the addresses whose history is retained by the pruner.
+    ///
+    /// Returns an empty [`RetainedHistory`] if nothing was persisted yet.
+    fn retained_history(&self) -> ProviderResult<RetainedHistory> {
+        self.get_metadata(keys::RETAINED_HISTORY)?
+            .map(|bytes| serde_json::from_slice(&bytes).map_err(ProviderError::other))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Get the block the datadir was branched off at to run a shadow fork, if any.
+    fn shadow_fork_block(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.get_metadata(keys::SHADOW_FORK_BLOCK)?
+            .map(|bytes| serde_json::from_slice(&bytes).map_err(ProviderError::other))
+            .transpose()
+    }
 }
 
 /// Client trait for writing node metadata to the database.
@@ -43,6 +67,22 @@ pub trait MetadataWriter: Send {
             serde_json::to_vec(&settings).map_err(ProviderError::other)?,
         )
     }
+
+    /// Write the addresses whose history is retained by the pruner.
+    fn write_retained_history(&self, retained_history: &RetainedHistory) -> ProviderResult<()> {
+        self.write_metadata(
+            keys::RETAINED_HISTORY,
+            serde_json::to_vec(retained_history).map_err(ProviderError::other)?,
+        )
+    }
+
+    /// Write the block the datadir was branched off at to run a shadow fork.
+    fn write_shadow_fork_block(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        self.write_metadata(
+            keys::SHADOW_FORK_BLOCK,
+            serde_json::to_vec(&block_number).map_err(ProviderError::other)?,
+        )
+    }
 }
 
 /// Trait for caching storage settings on a provider factory.
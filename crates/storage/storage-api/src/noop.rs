@@ -392,6 +392,21 @@ impl<C: Send + Sync, N: NodePrimitives> AccountReader for NoopProvider<C, N> {
     }
 }
 
+#[cfg(feature = "binary-tree")]
+impl<C: Send + Sync, N: NodePrimitives> crate::BinaryTreeReader for NoopProvider<C, N> {
+    fn binary_tree_root(&self, _block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        Ok(None)
+    }
+
+    fn binary_tree_account_proof(
+        &self,
+        _hashed_address: B256,
+        _hashed_slots: &[B256],
+    ) -> ProviderResult<Option<reth_trie_common::binary::BinaryTreeAccountProof>> {
+        Ok(None)
+    }
+}
+
 impl<C: Send + Sync, N: NodePrimitives> ChangeSetReader for NoopProvider<C, N> {
     fn account_block_changeset(
         &self,
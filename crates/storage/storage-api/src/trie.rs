@@ -1,6 +1,10 @@
 use alloc::vec::Vec;
+#[cfg(feature = "binary-tree")]
+use alloy_primitives::BlockNumber;
 use alloy_primitives::{Address, Bytes, B256};
 use reth_storage_errors::provider::ProviderResult;
+#[cfg(feature = "binary-tree")]
+use reth_trie_common::binary::BinaryTreeAccountProof;
 use reth_trie_common::{
     updates::{StorageTrieUpdatesSorted, TrieUpdates, TrieUpdatesSorted},
     AccountProof, ExecutionWitnessMode, HashedPostState, HashedStorage, MultiProof,
@@ -123,3 +127,35 @@ pub trait StorageTrieWriter: Send {
         storage_tries: impl Iterator<Item = (&'a B256, &'a StorageTrieUpdatesSorted)>,
     ) -> ProviderResult<usize>;
 }
+
+/// A type that can read the experimental binary state tree.
+///
+/// The tree tracks the latest persisted state.
+#[cfg(feature = "binary-tree")]
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait BinaryTreeReader: Send {
+    /// Returns the root of the binary tree after the given block, if it was computed.
+    fn binary_tree_root(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>>;
+
+    /// Returns proofs of the account and the given storage slots against the latest binary tree
+    /// root, or `None` if the tree is not maintained.
+    fn binary_tree_account_proof(
+        &self,
+        hashed_address: B256,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<Option<BinaryTreeAccountProof>>;
+}
+
+/// Helper trait that requires [`BinaryTreeReader`] if the `binary-tree` feature is enabled.
+#[cfg(feature = "binary-tree")]
+pub trait MaybeBinaryTreeReader: BinaryTreeReader {}
+
+#[cfg(feature = "binary-tree")]
+impl<T: BinaryTreeReader> MaybeBinaryTreeReader for T {}
+
+/// Helper trait that requires [`BinaryTreeReader`] if the `binary-tree` feature is enabled.
+#[cfg(not(feature = "binary-tree"))]
+pub trait MaybeBinaryTreeReader {}
+
+#[cfg(not(feature = "binary-tree"))]
+impl<T> MaybeBinaryTreeReader for T {}
@@ -1,7 +1,7 @@
 #![cfg(feature = "otlp")]
 
-//! Provides tracing layers for `OpenTelemetry` that export spans, logs, and metrics to an OTLP
-//! endpoint.
+//! Provides tracing layers for `OpenTelemetry` that export spans and logs, and a meter provider
+//! that exports metrics, to an OTLP endpoint.
 //!
 //! This module simplifies the integration of `OpenTelemetry` with OTLP export in Rust
 //! applications. It allows for easily capturing and exporting distributed traces, logs,
@@ -29,8 +29,11 @@ use base64::{prelude::BASE64_STANDARD, Engine};
 // See also <https://opentelemetry.io/docs/languages/sdk-configuration/otlp-exporter/#otel_exporter_otlp_traces_endpoint>.
 const HTTP_TRACE_ENDPOINT: &str = "/v1/traces";
 const HTTP_LOGS_ENDPOINT: &str = "/v1/logs";
+const HTTP_METRICS_ENDPOINT: &str = "/v1/metrics";
 const OTEL_EXPORTER_OTLP_TRACES_HEADERS: &str = "OTEL_EXPORTER_OTLP_TRACES_HEADERS";
 const OTEL_EXPORTER_OTLP_LOGS_HEADERS: &str = "OTEL_EXPORTER_OTLP_LOGS_HEADERS";
+#[cfg(feature = "otlp-metrics")]
+const OTEL_EXPORTER_OTLP_METRICS_HEADERS: &str = "OTEL_EXPORTER_OTLP_METRICS_HEADERS";
 
 /// Creates a tracing [`OpenTelemetryLayer`] that exports spans to an OTLP endpoint.
 ///
@@ -112,6 +115,37 @@ pub fn log_layer(
     Ok(opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge::new(&logger_provider))
 }
 
+/// Creates a meter provider that periodically exports metrics to an OTLP endpoint.
+///
+/// The provider is not installed globally, callers are expected to bridge their metrics to the
+/// meters it creates.
+#[cfg(feature = "otlp-metrics")]
+pub fn meter_provider(
+    otlp_config: &OtlpMetricsConfig,
+) -> eyre::Result<opentelemetry_sdk::metrics::SdkMeterProvider> {
+    use opentelemetry_otlp::MetricExporter;
+    use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+
+    let resource =
+        build_resource(otlp_config.service_name.clone(), otlp_config.service_version.as_deref());
+
+    let metric_builder = MetricExporter::builder();
+
+    let metric_exporter = match otlp_config.protocol {
+        OtlpProtocol::Http => {
+            metric_builder.with_http().with_endpoint(otlp_config.endpoint.as_str()).build()?
+        }
+        OtlpProtocol::Grpc => {
+            metric_builder.with_tonic().with_endpoint(otlp_config.endpoint.as_str()).build()?
+        }
+    };
+
+    let reader =
+        PeriodicReader::builder(metric_exporter).with_interval(otlp_config.interval).build();
+
+    Ok(SdkMeterProvider::builder().with_resource(resource).with_reader(reader).build())
+}
+
 /// Configuration for OTLP trace export.
 #[derive(Debug, Clone)]
 pub struct OtlpConfig {
@@ -231,6 +265,70 @@ impl OtlpLogsConfig {
     }
 }
 
+/// Configuration for OTLP metrics export.
+#[cfg(feature = "otlp-metrics")]
+#[derive(Debug, Clone)]
+pub struct OtlpMetricsConfig {
+    /// Service name for metrics identification
+    service_name: String,
+    /// Optional service version override. Falls back to `CARGO_PKG_VERSION` if `None`.
+    service_version: Option<String>,
+    /// Otlp endpoint URL
+    endpoint: Url,
+    /// Transport protocol, HTTP or gRPC
+    protocol: OtlpProtocol,
+    /// Interval between two exports
+    interval: std::time::Duration,
+}
+
+#[cfg(feature = "otlp-metrics")]
+impl OtlpMetricsConfig {
+    /// Creates a new OTLP metrics configuration.
+    pub fn new(
+        service_name: impl Into<String>,
+        endpoint: Url,
+        protocol: OtlpProtocol,
+        interval: std::time::Duration,
+    ) -> eyre::Result<Self> {
+        ensure!(!interval.is_zero(), "OTLP metrics export interval must not be zero");
+
+        set_otlp_auth_header_from_endpoint(&endpoint, OTEL_EXPORTER_OTLP_METRICS_HEADERS)?;
+        Ok(Self {
+            service_name: service_name.into(),
+            service_version: None,
+            endpoint: endpoint_without_credentials(endpoint),
+            protocol,
+            interval,
+        })
+    }
+
+    /// Sets the service version for OTLP resource identification.
+    pub fn with_service_version(mut self, version: impl Into<String>) -> Self {
+        self.service_version = Some(version.into());
+        self
+    }
+
+    /// Returns the service name.
+    pub fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
+    /// Returns the OTLP endpoint URL.
+    pub const fn endpoint(&self) -> &Url {
+        &self.endpoint
+    }
+
+    /// Returns the transport protocol.
+    pub const fn protocol(&self) -> OtlpProtocol {
+        self.protocol
+    }
+
+    /// Returns the interval between two exports.
+    pub const fn interval(&self) -> std::time::Duration {
+        self.interval
+    }
+}
+
 // Builds OTLP resource with service information.
 fn build_resource(service_name: impl Into<Value>, service_version: Option<&str>) -> Resource {
     let version = service_version.unwrap_or(env!("CARGO_PKG_VERSION"));
@@ -327,6 +425,14 @@ impl OtlpProtocol {
         self.validate_endpoint_with_path(url, HTTP_LOGS_ENDPOINT)
     }
 
+    /// Validate and correct the URL to match protocol requirements for metrics.
+    ///
+    /// For HTTP: Ensures the path ends with `/v1/metrics`, appending it if necessary.
+    /// For gRPC: Ensures the path does NOT include `/v1/metrics`.
+    pub fn validate_metrics_endpoint(&self, url: &mut Url) -> eyre::Result<()> {
+        self.validate_endpoint_with_path(url, HTTP_METRICS_ENDPOINT)
+    }
+
     fn validate_endpoint_with_path(&self, url: &mut Url, http_path: &str) -> eyre::Result<()> {
         match self {
             Self::Http => {
@@ -393,6 +499,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn appends_metrics_path_to_http_endpoint() {
+        let mut url = "http://localhost:4318".parse().unwrap();
+        OtlpProtocol::Http.validate_metrics_endpoint(&mut url).unwrap();
+        assert_eq!(url.as_str(), "http://localhost:4318/v1/metrics");
+
+        let mut url = "http://localhost:4317/v1/metrics".parse().unwrap();
+        assert!(OtlpProtocol::Grpc.validate_metrics_endpoint(&mut url).is_err());
+    }
+
     #[test]
     fn leaves_endpoint_without_credentials_unchanged() {
         let config = OtlpConfig::new(
@@ -0,0 +1,182 @@
+#![allow(missing_docs)]
+
+//! Benchmarks for transaction pool throughput under concurrent ingress: several threads insert
+//! batches of transactions from distinct senders while the payload builder side keeps reading the
+//! best transactions, mimicking a node receiving 10k+ transactions per second.
+//!
+//! Every writer count is measured with a single pool lock and with the pool sharded by sender.
+
+use alloy_primitives::{Address, U256};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use reth_primitives_traits::SealedBlock;
+use reth_transaction_pool::{
+    blobstore::InMemoryBlobStore,
+    noop::MockTransactionValidator,
+    pool::PoolInner,
+    test_utils::{MockOrdering, MockTransaction},
+    validate::ValidTransaction,
+    CanonicalStateUpdate, PoolConfig, PoolUpdateKind, SubPoolLimit, TransactionOrigin,
+    TransactionValidationOutcome,
+};
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
+
+type BenchPool =
+    PoolInner<MockTransactionValidator<MockTransaction>, MockOrdering, InMemoryBlobStore>;
+
+type Batch = Vec<(TransactionOrigin, TransactionValidationOutcome<MockTransaction>)>;
+
+/// Base fee the pool is initialized with.
+const BASE_FEE: u64 = 100;
+
+/// Total number of transactions inserted per iteration.
+const TOTAL_TXS: usize = 16_384;
+
+/// Number of transactions per sender.
+const TXS_PER_SENDER: u64 = 4;
+
+/// Number of transactions per inserted batch.
+const BATCH_SIZE: usize = 128;
+
+/// Number of shards of the sharded pool.
+const SHARDS: usize = 8;
+
+/// Deterministic unique sender address derived from a counter.
+fn sender_address(counter: u64) -> Address {
+    let mut bytes = [0u8; 20];
+    bytes[12..].copy_from_slice(&counter.to_be_bytes());
+    Address::from(bytes)
+}
+
+/// Creates the batches inserted by the given number of writers, every writer gets its own
+/// senders.
+fn batches(writers: usize) -> Vec<Vec<Batch>> {
+    let senders_per_writer = TOTAL_TXS / writers / TXS_PER_SENDER as usize;
+    (0..writers)
+        .map(|writer| {
+            let mut txs = Vec::with_capacity(TOTAL_TXS / writers);
+            for s in 0..senders_per_writer {
+                let sender = sender_address((writer * senders_per_writer + s) as u64 + 1);
+                let fee = BASE_FEE as u128 + (s % 200) as u128;
+                for nonce in 0..TXS_PER_SENDER {
+                    let mut tx = MockTransaction::eip1559();
+                    tx.set_sender(sender)
+                        .set_nonce(nonce)
+                        .set_max_fee(fee)
+                        .set_priority_fee(1 + (s % 50) as u128)
+                        .set_gas_limit(100_000);
+                    txs.push((
+                        TransactionOrigin::External,
+                        TransactionValidationOutcome::Valid {
+                            balance: U256::MAX,
+                            state_nonce: 0,
+                            bytecode_hash: None,
+                            transaction: ValidTransaction::Valid(tx),
+                            propagate: true,
+                            authorities: None,
+                        },
+                    ));
+                }
+            }
+            let mut batches = Vec::new();
+            while !txs.is_empty() {
+                let rest = txs.split_off(txs.len().min(BATCH_SIZE));
+                batches.push(std::mem::replace(&mut txs, rest));
+            }
+            batches
+        })
+        .collect()
+}
+
+/// Creates an empty pool with the given number of shards that is large enough to hold all
+/// inserted transactions.
+fn empty_pool(shards: usize) -> BenchPool {
+    let limit = SubPoolLimit { max_txs: TOTAL_TXS * 2, max_size: usize::MAX };
+    let config = PoolConfig {
+        pending_limit: limit,
+        basefee_limit: limit,
+        queued_limit: limit,
+        shards,
+        ..Default::default()
+    };
+    let pool = PoolInner::new(
+        MockTransactionValidator::default(),
+        MockOrdering::default(),
+        InMemoryBlobStore::default(),
+        config,
+    );
+
+    let mut block = reth_ethereum_primitives::Block::default();
+    block.header.gas_limit = 30_000_000;
+    block.header.base_fee_per_gas = Some(BASE_FEE);
+    let block = SealedBlock::seal_slow(block);
+    pool.on_canonical_state_change(CanonicalStateUpdate {
+        new_tip: &block,
+        pending_block_base_fee: BASE_FEE,
+        pending_block_blob_fee: None,
+        changed_accounts: Vec::new(),
+        mined_transactions: Vec::new(),
+        update_kind: PoolUpdateKind::Commit,
+    });
+    pool
+}
+
+/// Inserts all batches with one thread per writer, while another thread keeps iterating the best
+/// transactions.
+fn ingest(pool: &BenchPool, writers: Vec<Vec<Batch>>) {
+    let done = AtomicBool::new(false);
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                let best = pool.best_transactions().take(1_000).count();
+                std::hint::black_box(best);
+            }
+        });
+
+        let handles = writers
+            .into_iter()
+            .map(|batches| {
+                scope.spawn(move || {
+                    for batch in batches {
+                        std::hint::black_box(pool.add_transactions_with_origins(batch));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+    assert_eq!(pool.len(), TOTAL_TXS);
+}
+
+/// Benchmarks the throughput of concurrent insertions with a concurrent best transactions
+/// reader.
+fn bench_concurrent_ingress(c: &mut Criterion) {
+    let mut group = c.benchmark_group("concurrent_ingress");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(TOTAL_TXS as u64));
+
+    for shards in [1, SHARDS] {
+        for writers in [1, 4, 8] {
+            group.bench_function(
+                format!("add_{TOTAL_TXS}_txs_{writers}_writers_{shards}_shards"),
+                |b| {
+                    b.iter_batched(
+                        || (empty_pool(shards), batches(writers)),
+                        |(pool, batches)| ingest(&pool, batches),
+                        BatchSize::PerIteration,
+                    )
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_concurrent_ingress);
+criterion_main!(benches);
@@ -0,0 +1,96 @@
+//! Lifecycle events of transactions recorded by the pool.
+
+use crate::SubPool;
+use alloy_primitives::{Address, TxHash, B256};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of events that are kept per transaction, older events are dropped first.
+pub const MAX_HISTORY_EVENTS_PER_TRANSACTION: usize = 32;
+
+/// An event in the lifecycle of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum TransactionHistoryEvent {
+    /// Transaction was added to the given subpool.
+    Added {
+        /// The subpool the transaction was added to.
+        subpool: SubPool,
+    },
+    /// Transaction was replaced by the transaction with the given hash.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// The transaction that replaced this transaction.
+        replaced_by: TxHash,
+    },
+    /// Transaction was promoted or demoted to the given subpool.
+    Moved {
+        /// The subpool the transaction was moved to.
+        subpool: SubPool,
+    },
+    /// Transaction was removed from the pool.
+    Discarded {
+        /// Why the transaction was removed.
+        reason: DiscardReason,
+    },
+    /// Transaction was not accepted into the pool.
+    Rejected {
+        /// The validation or insertion error.
+        reason: String,
+    },
+    /// Transaction was included in the block with the given hash.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// The hash of the block that contains the transaction.
+        block_hash: B256,
+    },
+}
+
+/// The reason a transaction was removed from the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiscardReason {
+    /// Evicted to enforce the pool's size limits.
+    PoolLimit,
+    /// No longer valid on top of the current state, e.g. because its nonce was used.
+    Invalidated,
+    /// Explicitly removed, e.g. because it expired or was stale.
+    Removed,
+}
+
+/// A recorded [`TransactionHistoryEvent`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionHistoryRecord {
+    /// Unix timestamp in milliseconds when the event was recorded.
+    pub timestamp: u64,
+    /// The recorded event.
+    #[serde(flatten)]
+    pub event: TransactionHistoryEvent,
+}
+
+/// The recorded events of a transaction, ordered from oldest to newest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionHistory {
+    /// Hash of the transaction.
+    pub hash: TxHash,
+    /// Sender of the transaction, if it was known when an event was recorded.
+    pub sender: Option<Address>,
+    /// The recorded events, at most [`MAX_HISTORY_EVENTS_PER_TRANSACTION`].
+    pub events: Vec<TransactionHistoryRecord>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_history_event() {
+        let record = TransactionHistoryRecord {
+            timestamp: 1,
+            event: TransactionHistoryEvent::Discarded { reason: DiscardReason::PoolLimit },
+        };
+        assert_eq!(
+            serde_json::to_string(&record).unwrap(),
+            r#"{"timestamp":1,"event":"discarded","reason":"poolLimit"}"#
+        );
+    }
+}
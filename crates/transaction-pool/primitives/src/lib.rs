@@ -0,0 +1,18 @@
+//! Transaction pool types that are shared with consumers of the pool, like the RPC.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod history;
+pub use history::{
+    DiscardReason, TransactionHistory, TransactionHistoryEvent, TransactionHistoryRecord,
+    MAX_HISTORY_EVENTS_PER_TRANSACTION,
+};
+
+mod subpool;
+pub use subpool::SubPool;
@@ -1,17 +1,23 @@
 //! A simple diskstore for blobs
 
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize};
+use crate::blobstore::{
+    BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize, RetainedBlobSidecars,
+    RetainedBlockSidecars,
+};
 use alloy_eips::{
     eip4844::{BlobAndProofV1, BlobAndProofV2, BlobCellsAndProofsV1},
     eip7594::{BlobCellMask, BlobTransactionSidecarVariant, Cell},
     eip7840::BlobParams,
     merge::EPOCH_SLOTS,
 };
-use alloy_primitives::{map::B256Set, TxHash, B128, B256};
+use alloy_primitives::{
+    map::{B256Map, B256Set},
+    BlockNumber, TxHash, B128, B256,
+};
 use parking_lot::{Mutex, RwLock};
 use schnellru::{ByLength, LruMap};
 use std::{fmt, fs, io, path::PathBuf, sync::Arc};
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
 /// How many [`BlobTransactionSidecarVariant`] to cache in memory.
 pub const DEFAULT_MAX_CACHED_BLOBS: u32 = 100;
@@ -28,9 +34,13 @@ const VERSIONED_HASH_TO_TX_HASH_CACHE_SIZE: u64 =
 /// The type uses deferred deletion, meaning that blobs are not immediately deleted from disk, but
 /// it's expected that the maintenance task will call [`BlobStore::cleanup`] to remove the deleted
 /// blobs from disk.
+///
+/// If configured with [`RetainedBlobSidecars`], the sidecars of finalized transactions are moved
+/// there instead of being deleted, and blobs that are no longer in the store are looked up there.
 #[derive(Clone, Debug)]
 pub struct DiskFileBlobStore {
     inner: Arc<DiskFileBlobStoreInner>,
+    retained: Option<RetainedBlobSidecars>,
 }
 
 impl DiskFileBlobStore {
@@ -40,14 +50,41 @@ impl DiskFileBlobStore {
         opts: DiskFileBlobStoreConfig,
     ) -> Result<Self, DiskFileBlobStoreError> {
         let blob_dir = blob_dir.into();
-        let DiskFileBlobStoreConfig { max_cached_entries, .. } = opts;
+        let DiskFileBlobStoreConfig { max_cached_entries, open, retain, retained_sidecars } = opts;
         let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries);
 
         // initialize the blob store
-        inner.delete_all()?;
-        inner.create_blob_dir()?;
+        match open {
+            OpenDiskFileBlobStore::Clear => {
+                inner.delete_all()?;
+                inner.create_blob_dir()?;
+            }
+            OpenDiskFileBlobStore::ReIndex => {
+                inner.create_blob_dir()?;
+                inner.reindex(retain.as_ref())?;
+            }
+        }
+
+        Ok(Self { inner: Arc::new(inner), retained: retained_sidecars })
+    }
 
-        Ok(Self { inner: Arc::new(inner) })
+    /// Returns the retained sidecars that contain any of the requested versioned hashes that are
+    /// still missing in the result.
+    fn retained_sidecars_for_missing<R>(
+        &self,
+        versioned_hashes: &[B256],
+        result: &[Option<R>],
+    ) -> Result<Vec<BlobTransactionSidecarVariant>, BlobStoreError> {
+        let Some(retained) = &self.retained else { return Ok(Vec::new()) };
+        let missing = versioned_hashes
+            .iter()
+            .zip(result)
+            .filter_map(|(hash, blob)| blob.is_none().then_some(*hash))
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return Ok(Vec::new())
+        }
+        retained.get_by_versioned_hashes(&missing)
     }
 
     #[cfg(test)]
@@ -131,6 +168,19 @@ impl DiskFileBlobStore {
             }
         }
 
+        // finally fall back to the sidecars of finalized transactions
+        for blob_sidecar in self.retained_sidecars_for_missing(versioned_hashes, &result)? {
+            if let Some(blob_sidecar) = blob_sidecar.as_eip7594() {
+                for (hash_idx, match_result) in
+                    blob_sidecar.match_versioned_hashes(versioned_hashes)
+                {
+                    if result[hash_idx].is_none() {
+                        result[hash_idx] = Some(match_result);
+                    }
+                }
+            }
+        }
+
         Ok(result)
     }
 
@@ -347,6 +397,19 @@ impl BlobStore for DiskFileBlobStore {
             }
         }
 
+        // finally fall back to the sidecars of finalized transactions
+        for blob_sidecar in self.retained_sidecars_for_missing(versioned_hashes, &result)? {
+            if let Some(blob_sidecar) = blob_sidecar.as_eip4844() {
+                for (hash_idx, match_result) in
+                    blob_sidecar.match_versioned_hashes(versioned_hashes)
+                {
+                    if result[hash_idx].is_none() {
+                        result[hash_idx] = Some(match_result);
+                    }
+                }
+            }
+        }
+
         Ok(result)
     }
 
@@ -413,6 +476,14 @@ impl BlobStore for DiskFileBlobStore {
             }
         }
 
+        if let Some(retained) = &self.retained {
+            for (idx, requested_hash) in versioned_hashes.iter().enumerate() {
+                if !result[idx] && retained.contains_versioned_hash(requested_hash) {
+                    result[idx] = true;
+                }
+            }
+        }
+
         Ok(result)
     }
 
@@ -442,6 +513,46 @@ impl BlobStore for DiskFileBlobStore {
     fn blobs_len(&self) -> usize {
         self.inner.size_tracker.blobs_len()
     }
+
+    fn on_finalized_blocks(
+        &self,
+        blocks: Vec<(BlockNumber, Vec<B256>)>,
+    ) -> Result<(), BlobStoreError> {
+        let txs = blocks.iter().flat_map(|(_, txs)| txs.iter().copied()).collect();
+        if let Some(retained) = &self.retained {
+            for (block, txs) in blocks {
+                if txs.is_empty() {
+                    continue
+                }
+
+                // sidecars can be missing if they were never received or already deleted, these
+                // are recorded as missing instead of retaining an incomplete block
+                let mut found =
+                    self.inner.get_all(txs.clone())?.into_iter().collect::<B256Map<_>>();
+                let sidecars =
+                    txs.into_iter().map(|tx| (tx, found.remove(&tx))).collect::<Vec<_>>();
+                let missing = sidecars.iter().filter(|(_, sidecar)| sidecar.is_none()).count();
+                if missing > 0 {
+                    warn!(target:"txpool::blob", block, missing, "Missing blob sidecars of finalized transactions");
+                }
+                retained.insert_block(block, sidecars)?;
+            }
+        }
+        self.delete_all(txs)
+    }
+
+    fn get_finalized_block_sidecars(
+        &self,
+        block: BlockNumber,
+    ) -> Result<Option<RetainedBlockSidecars>, BlobStoreError> {
+        let Some(retained) = &self.retained else { return Ok(None) };
+        retained.get_block(block)
+    }
+
+    fn prune_finalized_sidecars(&self, to_block: BlockNumber) -> Result<usize, BlobStoreError> {
+        let Some(retained) = &self.retained else { return Ok(0) };
+        retained.prune(to_block)
+    }
 }
 
 struct DiskFileBlobStoreInner {
@@ -491,6 +602,56 @@ impl DiskFileBlobStoreInner {
         Ok(())
     }
 
+    /// Indexes the blob files that already exist on disk.
+    ///
+    /// Files that can't be decoded, or whose transaction is not in the given set to retain, are
+    /// removed.
+    fn reindex(&self, retain: Option<&B256Set>) -> Result<(), DiskFileBlobStoreError> {
+        let entries = fs::read_dir(&self.blob_dir)
+            .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+
+        let mut size = 0;
+        let mut num = 0;
+        let mut removed = 0;
+        for entry in entries {
+            let path =
+                entry.map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?.path();
+            let Some(tx) = path.file_name().and_then(|name| name.to_str()?.parse::<B256>().ok())
+            else {
+                continue
+            };
+
+            let decoded = if retain.is_none_or(|retain| retain.contains(&tx)) {
+                fs::read(&path).ok().and_then(|data| {
+                    BlobTransactionSidecarVariant::rlp_decode_fields(&mut data.as_slice())
+                        .ok()
+                        .map(|sidecar| (sidecar, data.len()))
+                })
+            } else {
+                None
+            };
+            let Some((sidecar, len)) = decoded else {
+                if let Err(err) = fs::remove_file(&path) {
+                    debug!(target:"txpool::blob", %err, ?path, "Failed to remove blob file");
+                }
+                removed += 1;
+                continue
+            };
+
+            let mut map = self.versioned_hashes_to_txhash.lock();
+            sidecar.versioned_hashes().for_each(|hash| {
+                map.insert(hash, tx);
+            });
+            size += len;
+            num += 1;
+        }
+
+        self.size_tracker.add_size(size);
+        self.size_tracker.inc_len(num);
+        debug!(target:"txpool::blob", blob_dir = ?self.blob_dir, num, removed, "Reindexed blob store");
+        Ok(())
+    }
+
     /// Ensures blob is in the blob cache and written to the disk.
     fn insert_one(
         &self,
@@ -512,8 +673,11 @@ impl DiskFileBlobStoreInner {
 
         let size = self.write_one_encoded(tx, &buf)?;
 
-        self.size_tracker.add_size(size);
-        self.size_tracker.inc_len(1);
+        // the blob file may already exist, for example if it was kept on open
+        if size > 0 {
+            self.size_tracker.add_size(size);
+            self.size_tracker.inc_len(1);
+        }
         Ok(())
     }
 
@@ -781,6 +945,15 @@ pub enum DiskFileBlobStoreError {
     #[error("[{0}] failed to delete blob file at {1}: {2}")]
     /// Indicates a failure while deleting a blob file.
     DeleteFile(TxHash, PathBuf, io::Error),
+    /// Failure while reading the retained sidecars of a block.
+    #[error("[{0}] failed to read retained blob sidecars at {1}: {2}")]
+    ReadBlockFile(BlockNumber, PathBuf, io::Error),
+    /// Failure while writing the retained sidecars of a block.
+    #[error("[{0}] failed to write retained blob sidecars at {1}: {2}")]
+    WriteBlockFile(BlockNumber, PathBuf, io::Error),
+    /// Failure while deleting the retained sidecars of a block.
+    #[error("[{0}] failed to delete retained blob sidecars at {1}: {2}")]
+    DeleteBlockFile(BlockNumber, PathBuf, io::Error),
 }
 
 impl From<DiskFileBlobStoreError> for BlobStoreError {
@@ -796,11 +969,25 @@ pub struct DiskFileBlobStoreConfig {
     pub max_cached_entries: u32,
     /// How to open the blob store.
     pub open: OpenDiskFileBlobStore,
+    /// The transactions whose blobs are kept when the store is opened with
+    /// [`OpenDiskFileBlobStore::ReIndex`].
+    ///
+    /// If `None`, all existing blobs are kept.
+    pub retain: Option<B256Set>,
+    /// Where the sidecars of finalized transactions are moved to.
+    ///
+    /// If `None`, they are deleted.
+    pub retained_sidecars: Option<RetainedBlobSidecars>,
 }
 
 impl Default for DiskFileBlobStoreConfig {
     fn default() -> Self {
-        Self { max_cached_entries: DEFAULT_MAX_CACHED_BLOBS, open: Default::default() }
+        Self {
+            max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
+            open: Default::default(),
+            retain: None,
+            retained_sidecars: None,
+        }
     }
 }
 
@@ -810,6 +997,26 @@ impl DiskFileBlobStoreConfig {
         self.max_cached_entries = max_cached_entries;
         self
     }
+
+    /// Set how to open the blob store.
+    pub const fn with_open(mut self, open: OpenDiskFileBlobStore) -> Self {
+        self.open = open;
+        self
+    }
+
+    /// Set the transactions whose blobs are kept when the store is reindexed, all other blobs are
+    /// removed.
+    pub fn with_retain(mut self, retain: B256Set) -> Self {
+        self.retain = Some(retain);
+        self
+    }
+
+    /// Set the store the sidecars of finalized transactions are moved to instead of being
+    /// deleted.
+    pub fn with_retained_sidecars(mut self, retained_sidecars: RetainedBlobSidecars) -> Self {
+        self.retained_sidecars = Some(retained_sidecars);
+        self
+    }
 }
 
 /// How to open a disk file blob store.
@@ -819,6 +1026,9 @@ pub enum OpenDiskFileBlobStore {
     #[default]
     Clear,
     /// Keep the existing blob store and index
+    ///
+    /// This allows blob transactions to be reinserted into the pool after a restart without
+    /// fetching their sidecars again, see [`crate::journal`].
     ReIndex,
 }
 
@@ -870,6 +1080,41 @@ mod tests {
         (BlobTransactionSidecarVariant::Eip7594(sidecar), versioned_hash, expected)
     }
 
+    #[test]
+    fn disk_retains_finalized_sidecars() {
+        let dir = tempfile::tempdir().unwrap();
+        let retained = RetainedBlobSidecars::open(dir.path().join("retained")).unwrap();
+        let store = DiskFileBlobStore::open(
+            dir.path().join("blobs"),
+            DiskFileBlobStoreConfig::default().with_retained_sidecars(retained),
+        )
+        .unwrap();
+
+        let (sidecar, versioned_hash, expected) = eip7594_single_blob_sidecar();
+        let tx = B256::random();
+        store.insert(tx, sidecar.clone()).unwrap();
+        // the sidecar of a finalized transaction was never received
+        let missing = B256::random();
+        store.on_finalized_blocks(vec![(7, vec![tx, missing])]).unwrap();
+        store.cleanup();
+        store.clear_cache();
+
+        assert!(!store.contains(tx).unwrap());
+        assert_eq!(
+            store.get_finalized_block_sidecars(7).unwrap(),
+            Some(vec![(tx, Some(sidecar)), (missing, None)])
+        );
+        assert_eq!(
+            store.get_by_versioned_hashes_v2(&[versioned_hash]).unwrap(),
+            Some(vec![expected])
+        );
+        assert_eq!(store.has_versioned_hashes(&[versioned_hash]).unwrap(), vec![true]);
+
+        assert_eq!(store.prune_finalized_sidecars(7).unwrap(), 1);
+        assert!(store.get_finalized_block_sidecars(7).unwrap().is_none());
+        assert_eq!(store.get_by_versioned_hashes_v2(&[versioned_hash]).unwrap(), None);
+    }
+
     #[test]
     fn disk_insert_all_get_all() {
         let (store, _dir) = tmp_store();
@@ -920,6 +1165,42 @@ mod tests {
         assert_eq!(retrieved_blob, blob);
     }
 
+    #[test]
+    fn disk_reindex_on_open() {
+        let (store, dir) = tmp_store();
+
+        let blobs = rng_blobs(3);
+        store.insert_all(blobs.clone()).unwrap();
+        std::fs::write(dir.path().join(format!("{:x}", B256::random())), [0xff]).unwrap();
+        drop(store);
+
+        let retain = blobs[..2].iter().map(|(tx, _)| *tx).collect();
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig::default()
+                .with_open(OpenDiskFileBlobStore::ReIndex)
+                .with_retain(retain),
+        )
+        .unwrap();
+
+        assert_eq!(store.blobs_len(), 2);
+        for (tx, blob) in &blobs[..2] {
+            assert!(!store.is_cached(tx));
+            assert_eq!(store.get(*tx).unwrap().map(Arc::unwrap_or_clone), Some(blob.clone()));
+        }
+        assert!(store.get(blobs[2].0).unwrap().is_none());
+
+        // reinserting a kept blob doesn't count it twice
+        store.insert(blobs[0].0, blobs[0].1.clone()).unwrap();
+        assert_eq!(store.blobs_len(), 2);
+
+        // opening the store by default clears it
+        drop(store);
+        let store = DiskFileBlobStore::open(dir.path(), Default::default()).unwrap();
+        assert_eq!(store.blobs_len(), 0);
+        assert!(store.get(blobs[0].0).unwrap().is_none());
+    }
+
     #[test]
     fn disk_delete_blob() {
         let (store, _dir) = tmp_store();
@@ -4,11 +4,12 @@ use alloy_eips::{
     eip4844::{BlobAndProofV1, BlobAndProofV2, BlobCellsAndProofsV1},
     eip7594::{BlobTransactionSidecarVariant, Cell},
 };
-use alloy_primitives::{TxHash, B128, B256};
+use alloy_primitives::{BlockNumber, TxHash, B128, B256};
 pub use converter::BlobSidecarConverter;
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
+pub use retained::{RetainedBlobSidecars, RetainedBlockSidecars};
 use std::{
     fmt,
     sync::{
@@ -22,6 +23,7 @@ mod converter;
 pub mod disk;
 mod mem;
 mod noop;
+mod retained;
 mod tracker;
 
 /// A blob store that can be used to store blob data of EIP4844 transactions.
@@ -156,6 +158,36 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
 
     /// How many blobs are in the blob store.
     fn blobs_len(&self) -> usize;
+
+    /// Invoked with the blob transactions of blocks that are now finalized.
+    ///
+    /// By default, their sidecars are deleted. Stores that retain the sidecars of finalized
+    /// transactions keep them by block instead, see [`BlobStore::get_finalized_block_sidecars`].
+    fn on_finalized_blocks(
+        &self,
+        blocks: Vec<(BlockNumber, Vec<B256>)>,
+    ) -> Result<(), BlobStoreError> {
+        self.delete_all(blocks.into_iter().flat_map(|(_, txs)| txs).collect())
+    }
+
+    /// Returns the retained sidecars of the blob transactions included in the given finalized
+    /// block.
+    ///
+    /// Returns `None` if the store doesn't retain any sidecars for the block. Transactions whose
+    /// sidecar was missing when the block was finalized are returned without a sidecar.
+    fn get_finalized_block_sidecars(
+        &self,
+        _block: BlockNumber,
+    ) -> Result<Option<RetainedBlockSidecars>, BlobStoreError> {
+        Ok(None)
+    }
+
+    /// Deletes the retained sidecars of all finalized blocks up to and including `to_block`.
+    ///
+    /// Returns the number of blocks whose sidecars were deleted.
+    fn prune_finalized_sidecars(&self, _to_block: BlockNumber) -> Result<usize, BlobStoreError> {
+        Ok(0)
+    }
 }
 
 /// Error variants that can occur when interacting with a blob store.
@@ -0,0 +1,341 @@
+//! A disk store for the blob sidecars of finalized transactions, keyed by block.
+
+use crate::blobstore::{disk::DiskFileBlobStoreError, BlobStoreError};
+use alloy_eips::eip7594::BlobTransactionSidecarVariant;
+use alloy_primitives::{map::B256Map, BlockNumber, TxHash, B256};
+use parking_lot::RwLock;
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::debug;
+
+/// The retained sidecars of the blob transactions of a block, `None` if a sidecar is missing.
+pub type RetainedBlockSidecars = Vec<(TxHash, Option<BlobTransactionSidecarVariant>)>;
+
+/// Stores the blob sidecars of included blob transactions once they are finalized, so they can
+/// still be served after they were removed from the pool's blob store.
+///
+/// Every block is stored in a single file named after the block number. The file starts with the
+/// number of stored transactions, followed by every transaction's hash, the versioned hashes of
+/// its blobs and the length prefixed RLP encoded sidecar fields:
+///
+/// ```text
+/// count: u32 | (tx_hash: [u8; 32] | n: u32 | versioned_hash: [u8; 32] * n | len: u32 | fields)*
+/// ```
+///
+/// Transactions whose sidecar was missing when the block was finalized are stored without
+/// versioned hashes and fields, so the block's sidecars are never silently incomplete.
+///
+/// The versioned hashes are indexed in memory when the store is opened, without reading the
+/// sidecars.
+///
+/// Retained sidecars are only removed by [`RetainedBlobSidecars::prune`], which is driven by the
+/// pruner.
+#[derive(Clone)]
+pub struct RetainedBlobSidecars {
+    inner: Arc<RetainedBlobSidecarsInner>,
+}
+
+impl RetainedBlobSidecars {
+    /// Opens the store at the given directory and indexes the existing blocks.
+    pub fn open(dir: impl Into<PathBuf>) -> Result<Self, DiskFileBlobStoreError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(|e| DiskFileBlobStoreError::Open(dir.clone(), e))?;
+
+        let mut index = RetainedIndex::default();
+        let entries =
+            fs::read_dir(&dir).map_err(|e| DiskFileBlobStoreError::Open(dir.clone(), e))?;
+        for entry in entries {
+            let path = entry.map_err(|e| DiskFileBlobStoreError::Open(dir.clone(), e))?.path();
+            let Some(block) =
+                path.file_name().and_then(|name| name.to_str()?.parse::<BlockNumber>().ok())
+            else {
+                continue
+            };
+            match read_versioned_hashes(&path) {
+                Ok(hashes) => index.insert(block, hashes),
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, ?path, "Removing unreadable retained sidecars");
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+        debug!(target:"txpool::blob", ?dir, blocks = index.blocks.len(), "Opened retained blob sidecars");
+
+        Ok(Self { inner: Arc::new(RetainedBlobSidecarsInner { dir, index: RwLock::new(index) }) })
+    }
+
+    /// Stores the sidecars of the blob transactions of the given block.
+    ///
+    /// Transactions without a sidecar are recorded as missing. Replaces any sidecars previously
+    /// stored for the block.
+    pub fn insert_block(
+        &self,
+        block: BlockNumber,
+        sidecars: Vec<(TxHash, Option<Arc<BlobTransactionSidecarVariant>>)>,
+    ) -> Result<(), BlobStoreError> {
+        if sidecars.is_empty() {
+            return Ok(())
+        }
+
+        let mut buf = Vec::new();
+        let mut hashes = Vec::new();
+        buf.extend_from_slice(&(sidecars.len() as u32).to_be_bytes());
+        for (tx, sidecar) in &sidecars {
+            buf.extend_from_slice(tx.as_slice());
+            let Some(sidecar) = sidecar else {
+                // no versioned hashes and no fields
+                buf.extend_from_slice(&[0; 8]);
+                continue
+            };
+            let versioned_hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+            buf.extend_from_slice(&(versioned_hashes.len() as u32).to_be_bytes());
+            for hash in &versioned_hashes {
+                buf.extend_from_slice(hash.as_slice());
+            }
+            buf.extend_from_slice(&(sidecar.rlp_encoded_fields_length() as u32).to_be_bytes());
+            sidecar.rlp_encode_fields(&mut buf);
+            hashes.extend(versioned_hashes);
+        }
+
+        let path = self.inner.block_file(block);
+        let mut index = self.inner.index.write();
+        fs::write(&path, &buf)
+            .map_err(|e| DiskFileBlobStoreError::WriteBlockFile(block, path, e))?;
+        index.insert(block, hashes);
+        Ok(())
+    }
+
+    /// Returns the retained sidecars of the given block, if any.
+    ///
+    /// The sidecar is `None` for transactions whose sidecar was missing when the block was
+    /// finalized.
+    pub fn get_block(
+        &self,
+        block: BlockNumber,
+    ) -> Result<Option<RetainedBlockSidecars>, BlobStoreError> {
+        let _lock = self.inner.index.read();
+        self.inner.read_block(block)
+    }
+
+    /// Returns the retained sidecars that contain any of the given versioned hashes.
+    pub fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<BlobTransactionSidecarVariant>, BlobStoreError> {
+        let index = self.inner.index.read();
+        let mut blocks = versioned_hashes
+            .iter()
+            .filter_map(|hash| index.versioned_hashes.get(hash).copied())
+            .collect::<Vec<_>>();
+        blocks.sort_unstable();
+        blocks.dedup();
+
+        let mut sidecars = Vec::new();
+        for block in blocks {
+            let Some(block_sidecars) = self.inner.read_block(block)? else { continue };
+            sidecars.extend(block_sidecars.into_iter().filter_map(|(_, sidecar)| sidecar).filter(
+                |sidecar| sidecar.versioned_hashes().any(|hash| versioned_hashes.contains(&hash)),
+            ));
+        }
+        Ok(sidecars)
+    }
+
+    /// Returns `true` if a retained sidecar contains the given versioned hash.
+    pub fn contains_versioned_hash(&self, versioned_hash: &B256) -> bool {
+        self.inner.index.read().versioned_hashes.contains_key(versioned_hash)
+    }
+
+    /// Returns the lowest and highest block with retained sidecars.
+    pub fn block_range(&self) -> Option<(BlockNumber, BlockNumber)> {
+        let index = self.inner.index.read();
+        Some((*index.blocks.first_key_value()?.0, *index.blocks.last_key_value()?.0))
+    }
+
+    /// Deletes the sidecars of all blocks up to and including `to_block`.
+    ///
+    /// Returns the number of blocks whose sidecars were deleted.
+    pub fn prune(&self, to_block: BlockNumber) -> Result<usize, BlobStoreError> {
+        let mut index = self.inner.index.write();
+        let mut pruned = 0;
+        while let Some(entry) = index.blocks.first_entry() {
+            let block = *entry.key();
+            if block > to_block {
+                break
+            }
+            let path = self.inner.block_file(block);
+            match fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(DiskFileBlobStoreError::DeleteBlockFile(block, path, e).into())
+                }
+            }
+            index.remove(block);
+            pruned += 1;
+        }
+        Ok(pruned)
+    }
+}
+
+impl fmt::Debug for RetainedBlobSidecars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetainedBlobSidecars")
+            .field("dir", &self.inner.dir)
+            .field("blocks", &self.inner.index.try_read().map(|index| index.blocks.len()))
+            .finish()
+    }
+}
+
+struct RetainedBlobSidecarsInner {
+    dir: PathBuf,
+    /// Index of the retained blocks.
+    ///
+    /// Also guards the block files: writers hold the write lock, readers the read lock.
+    index: RwLock<RetainedIndex>,
+}
+
+impl RetainedBlobSidecarsInner {
+    fn block_file(&self, block: BlockNumber) -> PathBuf {
+        self.dir.join(block.to_string())
+    }
+
+    /// Reads and decodes the sidecars of the given block.
+    ///
+    /// The caller must hold the index lock.
+    fn read_block(
+        &self,
+        block: BlockNumber,
+    ) -> Result<Option<RetainedBlockSidecars>, BlobStoreError> {
+        let path = self.block_file(block);
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(DiskFileBlobStoreError::ReadBlockFile(block, path, e).into()),
+        };
+        decode_block(&data).map(Some)
+    }
+}
+
+/// In memory index of the retained blocks.
+#[derive(Debug, Default)]
+struct RetainedIndex {
+    /// The versioned hashes of the blobs retained for every block.
+    blocks: BTreeMap<BlockNumber, Vec<B256>>,
+    /// The block a versioned hash was retained for.
+    versioned_hashes: B256Map<BlockNumber>,
+}
+
+impl RetainedIndex {
+    fn insert(&mut self, block: BlockNumber, hashes: Vec<B256>) {
+        self.remove(block);
+        for hash in &hashes {
+            self.versioned_hashes.insert(*hash, block);
+        }
+        self.blocks.insert(block, hashes);
+    }
+
+    fn remove(&mut self, block: BlockNumber) {
+        for hash in self.blocks.remove(&block).unwrap_or_default() {
+            if self.versioned_hashes.get(&hash) == Some(&block) {
+                self.versioned_hashes.remove(&hash);
+            }
+        }
+    }
+}
+
+/// Reads only the versioned hashes of a block file, skipping the sidecars.
+fn read_versioned_hashes(path: &Path) -> io::Result<Vec<B256>> {
+    let mut file = io::BufReader::new(fs::File::open(path)?);
+    let mut hashes = Vec::new();
+    for _ in 0..read_u32(&mut file)? {
+        file.seek_relative(32)?;
+        for _ in 0..read_u32(&mut file)? {
+            let mut hash = B256::ZERO;
+            file.read_exact(hash.as_mut_slice())?;
+            hashes.push(hash);
+        }
+        let len = read_u32(&mut file)?;
+        file.seek_relative(len as i64)?;
+    }
+    Ok(hashes)
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_be_bytes(buf))
+}
+
+/// Decodes all sidecars of a block file, missing sidecars are stored without fields.
+fn decode_block(mut data: &[u8]) -> Result<RetainedBlockSidecars, BlobStoreError> {
+    let count = read_u32(&mut data).map_err(|_| alloy_rlp::Error::InputTooShort)?;
+    let mut sidecars = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let mut tx = B256::ZERO;
+        data.read_exact(tx.as_mut_slice()).map_err(|_| alloy_rlp::Error::InputTooShort)?;
+        let hashes = read_u32(&mut data).map_err(|_| alloy_rlp::Error::InputTooShort)? as usize;
+        data = data.get(hashes * 32..).ok_or(alloy_rlp::Error::InputTooShort)?;
+        let len = read_u32(&mut data).map_err(|_| alloy_rlp::Error::InputTooShort)? as usize;
+        let (mut fields, rest) =
+            data.split_at_checked(len).ok_or(alloy_rlp::Error::InputTooShort)?;
+        let sidecar = if fields.is_empty() {
+            None
+        } else {
+            Some(BlobTransactionSidecarVariant::rlp_decode_fields(&mut fields)?)
+        };
+        sidecars.push((tx, sidecar));
+        data = rest;
+    }
+    Ok(sidecars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::BlobTransactionSidecar;
+    use alloy_eips::eip4844::{Blob, Bytes48};
+
+    fn sidecar(seed: u8) -> Arc<BlobTransactionSidecarVariant> {
+        Arc::new(BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar::new(
+            vec![Blob::repeat_byte(seed)],
+            vec![Bytes48::repeat_byte(seed)],
+            vec![Bytes48::repeat_byte(seed)],
+        )))
+    }
+
+    #[test]
+    fn retain_and_prune_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = RetainedBlobSidecars::open(dir.path()).unwrap();
+        let (a, b) = (sidecar(1), sidecar(2));
+        let versioned_hash = a.versioned_hashes().next().unwrap();
+
+        store
+            .insert_block(
+                10,
+                vec![(B256::with_last_byte(1), Some(a.clone())), (B256::with_last_byte(3), None)],
+            )
+            .unwrap();
+        store.insert_block(11, vec![(B256::with_last_byte(2), Some(b.clone()))]).unwrap();
+        assert_eq!(store.block_range(), Some((10, 11)));
+        assert_eq!(
+            store.get_block(10).unwrap().unwrap(),
+            vec![(B256::with_last_byte(1), Some((*a).clone())), (B256::with_last_byte(3), None)]
+        );
+        assert_eq!(store.get_by_versioned_hashes(&[versioned_hash]).unwrap(), vec![(*a).clone()]);
+
+        // the index is rebuilt from disk
+        let store = RetainedBlobSidecars::open(dir.path()).unwrap();
+        assert!(store.contains_versioned_hash(&versioned_hash));
+
+        assert_eq!(store.prune(10).unwrap(), 1);
+        assert!(store.get_block(10).unwrap().is_none());
+        assert!(!store.contains_versioned_hash(&versioned_hash));
+        assert_eq!(store.block_range(), Some((11, 11)));
+    }
+}
@@ -59,20 +59,39 @@ impl BlobStoreCanonTracker {
     ///
     /// This returns all blob transactions that were included in blocks that are now finalized.
     pub fn on_finalized_block(&mut self, finalized_block: BlockNumber) -> BlobStoreUpdates {
+        let finalized = self
+            .take_finalized_blocks(finalized_block)
+            .into_iter()
+            .flat_map(|(_, txs)| txs)
+            .collect::<Vec<_>>();
+
+        if finalized.is_empty() {
+            BlobStoreUpdates::None
+        } else {
+            BlobStoreUpdates::Finalized(finalized)
+        }
+    }
+
+    /// Removes and returns the blob transactions of all blocks up to and including the finalized
+    /// block, grouped by block.
+    ///
+    /// Blocks without blob transactions are skipped.
+    pub fn take_finalized_blocks(
+        &mut self,
+        finalized_block: BlockNumber,
+    ) -> Vec<(BlockNumber, Vec<B256>)> {
         let mut finalized = Vec::new();
         while let Some(entry) = self.blob_txs_in_blocks.first_entry() {
             if *entry.key() <= finalized_block {
-                finalized.extend(entry.remove_entry().1);
+                let (block, txs) = entry.remove_entry();
+                if !txs.is_empty() {
+                    finalized.push((block, txs));
+                }
             } else {
                 break
             }
         }
-
-        if finalized.is_empty() {
-            BlobStoreUpdates::None
-        } else {
-            BlobStoreUpdates::Finalized(finalized)
-        }
+        finalized
     }
 }
 
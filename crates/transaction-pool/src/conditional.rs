@@ -0,0 +1,196 @@
+//! Support for conditional transactions, see [ERC-7796](https://eips.ethereum.org/EIPS/eip-7796).
+//!
+//! A conditional transaction must only be included in a block within the given block number and
+//! timestamp bounds, and only if the storage of the given accounts matches the expected state.
+//! The pool keeps the conditions on the [`ValidPoolTransaction`](crate::ValidPoolTransaction) and
+//! evicts the transaction once they can no longer be satisfied.
+
+use crate::error::PoolTransactionError;
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types_eth::erc4337::AccountStorage;
+pub use alloy_rpc_types_eth::erc4337::TransactionConditional;
+use reth_storage_api::{errors::provider::ProviderError, StateProvider, StorageRootProvider};
+use std::any::Any;
+
+/// The maximum accepted [`TransactionConditional::cost`] of a transaction's conditions.
+pub const MAX_CONDITIONAL_COST: u64 = 1000;
+
+/// Errors returned when the conditions of a transaction are not satisfied.
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionConditionalError {
+    /// The cost of checking the conditions exceeds [`MAX_CONDITIONAL_COST`].
+    #[error("conditional cost {0} exceeds the maximum of {MAX_CONDITIONAL_COST}")]
+    CostExceeded(u64),
+    /// The block number is outside of the conditional bounds.
+    #[error("block number {0} is out of the conditional bounds")]
+    BlockNumber(u64),
+    /// The block timestamp is outside of the conditional bounds.
+    #[error("timestamp {0} is out of the conditional bounds")]
+    Timestamp(u64),
+    /// The storage root of a known account doesn't match.
+    #[error("storage root of account {0} does not match")]
+    StorageRoot(Address),
+    /// A storage slot of a known account doesn't match.
+    #[error("storage slot {1} of account {0} does not match")]
+    StorageSlot(Address, B256),
+    /// Failed to read the state.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    /// The pool doesn't support conditional transactions.
+    #[error("conditional transactions are not supported")]
+    Unsupported,
+}
+
+impl PoolTransactionError for TransactionConditionalError {
+    fn is_bad_transaction(&self) -> bool {
+        // conditions depend on the state of the chain
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Returns `true` if the conditions can't be satisfied by any block after the block with the given
+/// number and timestamp.
+pub const fn is_expired_conditional(
+    conditional: &TransactionConditional,
+    number: u64,
+    timestamp: u64,
+) -> bool {
+    matches!(conditional.block_number_max, Some(max) if max <= number) ||
+        matches!(conditional.timestamp_max, Some(max) if max <= timestamp)
+}
+
+/// Checks that a block with the given number and timestamp is within the bounds of the
+/// conditions.
+pub fn validate_block_conditional(
+    conditional: &TransactionConditional,
+    number: u64,
+    timestamp: u64,
+) -> Result<(), TransactionConditionalError> {
+    if conditional.block_number_min.is_some_and(|min| number < min) ||
+        conditional.block_number_max.is_some_and(|max| number > max)
+    {
+        return Err(TransactionConditionalError::BlockNumber(number))
+    }
+    if conditional.timestamp_min.is_some_and(|min| timestamp < min) ||
+        conditional.timestamp_max.is_some_and(|max| timestamp > max)
+    {
+        return Err(TransactionConditionalError::Timestamp(timestamp))
+    }
+    Ok(())
+}
+
+/// Read access to the storage of the known accounts of [`TransactionConditional`]s.
+///
+/// This is implemented for references to any [`StateProvider`]. Block builders implement it to
+/// check the known accounts against the state of the block that is being built.
+pub trait KnownAccountsReader {
+    /// Returns the storage root of the account.
+    fn storage_root(&mut self, address: Address) -> Result<B256, ProviderError>;
+
+    /// Returns the value of the storage slot of the account.
+    fn storage(&mut self, address: Address, slot: B256) -> Result<U256, ProviderError>;
+}
+
+impl<S: StateProvider + ?Sized> KnownAccountsReader for &S {
+    fn storage_root(&mut self, address: Address) -> Result<B256, ProviderError> {
+        StorageRootProvider::storage_root(*self, address, Default::default())
+    }
+
+    fn storage(&mut self, address: Address, slot: B256) -> Result<U256, ProviderError> {
+        Ok(StateProvider::storage(*self, address, slot)?.unwrap_or_default())
+    }
+}
+
+/// Checks that the storage of the known accounts of the conditions matches the given state.
+pub fn validate_known_accounts(
+    conditional: &TransactionConditional,
+    mut state: impl KnownAccountsReader,
+) -> Result<(), TransactionConditionalError> {
+    for (address, storage) in &conditional.known_accounts {
+        match storage {
+            AccountStorage::RootHash(root) => {
+                if state.storage_root(*address)? != *root {
+                    return Err(TransactionConditionalError::StorageRoot(*address))
+                }
+            }
+            AccountStorage::Slots(slots) => {
+                for (slot, value) in slots {
+                    let slot = B256::from(*slot);
+                    let current = state.storage(*address, slot)?;
+                    if current != U256::from_be_bytes(value.0) {
+                        return Err(TransactionConditionalError::StorageSlot(*address, slot))
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn block_bounds() {
+        let conditional = TransactionConditional {
+            block_number_min: Some(10),
+            block_number_max: Some(20),
+            timestamp_max: Some(1000),
+            ..Default::default()
+        };
+
+        assert!(validate_block_conditional(&conditional, 10, 1000).is_ok());
+        assert!(matches!(
+            validate_block_conditional(&conditional, 9, 1000),
+            Err(TransactionConditionalError::BlockNumber(9))
+        ));
+        assert!(matches!(
+            validate_block_conditional(&conditional, 21, 1000),
+            Err(TransactionConditionalError::BlockNumber(21))
+        ));
+        assert!(matches!(
+            validate_block_conditional(&conditional, 15, 1001),
+            Err(TransactionConditionalError::Timestamp(1001))
+        ));
+
+        // the next block can still satisfy the conditions
+        assert!(!is_expired_conditional(&conditional, 19, 999));
+        assert!(is_expired_conditional(&conditional, 20, 999));
+        assert!(is_expired_conditional(&conditional, 19, 1000));
+    }
+
+    #[test]
+    fn known_account_slots() {
+        let address = Address::random();
+        let state = MockEthProvider::default();
+        state.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(B256::with_last_byte(1), U256::from(7))]),
+        );
+
+        let conditional = |value: u64| TransactionConditional {
+            known_accounts: [(
+                address,
+                AccountStorage::Slots(
+                    [(U256::from(1), B256::from(U256::from(value)))].into_iter().collect(),
+                ),
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        assert!(validate_known_accounts(&conditional(7), &state).is_ok());
+        assert!(matches!(
+            validate_known_accounts(&conditional(8), &state),
+            Err(TransactionConditionalError::StorageSlot(account, _)) if account == address
+        ));
+    }
+}
@@ -34,6 +34,9 @@ pub const MAX_NEW_PENDING_TXS_NOTIFICATIONS: usize = 200;
 /// Default maximum allowed in flight delegated transactions per account.
 pub const DEFAULT_MAX_INFLIGHT_DELEGATED_SLOTS: usize = 1;
 
+/// Default number of shards the pool is split into.
+pub const DEFAULT_TXPOOL_SHARDS: usize = 1;
+
 /// Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -72,6 +75,16 @@ pub struct PoolConfig {
     ///
     /// This restricts how many executable transaction a delegated sender can stack.
     pub max_inflight_delegated_slot_limit: usize,
+    /// Number of transactions the lifecycle event history is kept for.
+    ///
+    /// Defaults to `0`, which disables the history.
+    pub max_history_transactions: usize,
+    /// Number of shards the pool is split into by sender.
+    ///
+    /// Each shard holds the transactions of a subset of the senders and is guarded by its own
+    /// lock, so insertions of transactions from different senders don't contend on the same lock.
+    /// The sub-pool limits are split evenly across the shards and are enforced per shard.
+    pub shards: usize,
 }
 
 impl PoolConfig {
@@ -100,6 +113,26 @@ impl PoolConfig {
         self
     }
 
+    /// Configures the number of shards the pool is split into by sender.
+    pub const fn with_shards(mut self, shards: usize) -> Self {
+        self.shards = shards;
+        self
+    }
+
+    /// Returns the configuration of a single shard of the pool.
+    ///
+    /// The sub-pool limits are divided by the number of shards, rounding up.
+    pub(crate) fn shard_config(&self) -> Self {
+        let shards = self.shards.max(1);
+        Self {
+            pending_limit: self.pending_limit.per_shard(shards),
+            basefee_limit: self.basefee_limit.per_shard(shards),
+            queued_limit: self.queued_limit.per_shard(shards),
+            blob_limit: self.blob_limit.per_shard(shards),
+            ..self.clone()
+        }
+    }
+
     /// Returns whether the size and amount constraints in any sub-pools are exceeded.
     #[inline]
     pub const fn is_exceeded(&self, pool_size: PoolSize) -> bool {
@@ -129,6 +162,8 @@ impl Default for PoolConfig {
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
             max_inflight_delegated_slot_limit: DEFAULT_MAX_INFLIGHT_DELEGATED_SLOTS,
+            max_history_transactions: 0,
+            shards: DEFAULT_TXPOOL_SHARDS,
         }
     }
 }
@@ -159,6 +194,11 @@ impl SubPoolLimit {
         self.max_txs < txs || self.max_size < size
     }
 
+    /// Returns the limit of one of the given number of shards, rounding up.
+    const fn per_shard(self, shards: usize) -> Self {
+        Self::new(self.max_txs.div_ceil(shards), self.max_size.div_ceil(shards))
+    }
+
     /// Returns how many transactions exceed the configured limit.
     pub const fn tx_excess(&self, txs: usize) -> Option<usize> {
         txs.checked_sub(self.max_txs)
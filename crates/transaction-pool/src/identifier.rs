@@ -76,6 +76,13 @@ impl SenderId {
     pub const fn into_transaction_id(self, nonce: u64) -> TransactionId {
         TransactionId::new(self, nonce)
     }
+
+    /// Returns the index of the shard this sender belongs to, given the number of shards.
+    ///
+    /// Ids are assigned sequentially, so senders are spread evenly across the shards.
+    pub(crate) const fn shard(self, shards: usize) -> usize {
+        (self.0 % shards as u64) as usize
+    }
 }
 
 impl From<u64> for SenderId {
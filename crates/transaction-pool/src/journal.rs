@@ -0,0 +1,298 @@
+//! Periodic journal of all transactions in the pool.
+//!
+//! Unlike the local transactions backup of [`crate::maintain`], the journal covers the transactions
+//! of all subpools regardless of their origin. It is written periodically and on shutdown, so that
+//! the pool can be restored after a restart, including after an unclean one.
+//!
+//! Blob transactions are journaled without their sidecars: the sidecars remain in the blob store,
+//! which must keep them across restarts, see
+//! [`OpenDiskFileBlobStore::ReIndex`](crate::blobstore::OpenDiskFileBlobStore::ReIndex).
+//!
+//! On startup, all journaled transactions are revalidated against the current head and reinserted
+//! into the pool in bulk.
+
+use crate::{
+    maintain::TransactionsBackupError, traits::EthPoolTransaction, PoolTransaction,
+    TransactionOrigin, TransactionPool,
+};
+use alloy_consensus::Typed2718;
+use alloy_eips::{Decodable2718, Encodable2718};
+use alloy_primitives::{map::B256Set, BlockHash, Bytes, TxHash};
+use reth_primitives_traits::transaction::signed::SignedTransaction;
+use reth_tasks::shutdown::GracefulShutdown;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    pin::pin,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::time::{interval_at, Instant};
+use tracing::{debug, error, info, warn};
+
+/// The default interval at which the transaction pool journal is written.
+pub const DEFAULT_JOURNAL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Configuration of the transaction pool journal.
+#[derive(Debug, Clone)]
+pub struct TransactionPoolJournalConfig {
+    /// Path of the journal file.
+    pub path: PathBuf,
+    /// Interval at which the journal is written.
+    pub interval: Duration,
+}
+
+impl TransactionPoolJournalConfig {
+    /// Creates a new config that writes the journal to the given path every
+    /// [`DEFAULT_JOURNAL_INTERVAL`].
+    pub const fn new(path: PathBuf) -> Self {
+        Self { path, interval: DEFAULT_JOURNAL_INTERVAL }
+    }
+
+    /// Sets the interval at which the journal is written.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// A snapshot of all transactions in the pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionPoolJournal {
+    /// Hash of the block the pool was tracking when the journal was written.
+    pub block_hash: BlockHash,
+    /// The journaled transactions, ordered by sender and nonce.
+    pub transactions: Vec<JournalTransaction>,
+}
+
+impl TransactionPoolJournal {
+    /// Takes a snapshot of all transactions in the given pool.
+    pub fn from_pool<P>(pool: &P) -> Self
+    where
+        P: TransactionPool,
+    {
+        let block_hash = pool.block_info().last_seen_block_hash;
+        let mut transactions = pool.get_all(pool.all_transaction_hashes());
+        transactions.sort_unstable_by_key(|tx| (tx.sender(), tx.nonce()));
+
+        let transactions = transactions
+            .into_iter()
+            .map(|tx| JournalTransaction {
+                hash: *tx.hash(),
+                origin: tx.origin,
+                blob: tx.transaction.is_eip4844(),
+                rlp: tx.transaction.clone_into_consensus().into_inner().encoded_2718().into(),
+            })
+            .collect();
+
+        Self { block_hash, transactions }
+    }
+
+    /// Reads the journal from the given file, returns `None` if it doesn't exist.
+    pub fn read(path: &Path) -> Result<Option<Self>, TransactionsBackupError> {
+        if !path.exists() {
+            return Ok(None)
+        }
+        let data = reth_fs_util::read(path)?;
+        Ok(Some(serde_json::from_slice(&data)?))
+    }
+
+    /// Atomically writes the journal to the given file.
+    pub fn write(&self, path: &Path) -> Result<(), TransactionsBackupError> {
+        if let Some(parent) = path.parent() {
+            reth_fs_util::create_dir_all(parent)?;
+        }
+        reth_fs_util::atomic_write_file(path, |file| serde_json::to_writer(file, self))?;
+        Ok(())
+    }
+
+    /// Returns the hashes of the journaled blob transactions.
+    ///
+    /// The blob store must keep the sidecars of these transactions when it is opened.
+    pub fn blob_transactions(&self) -> B256Set {
+        self.transactions.iter().filter(|tx| tx.blob).map(|tx| tx.hash).collect()
+    }
+}
+
+/// A transaction in the [`TransactionPoolJournal`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalTransaction {
+    /// Hash of the transaction.
+    pub hash: TxHash,
+    /// The origin of the transaction.
+    pub origin: TransactionOrigin,
+    /// Whether this is a blob transaction, whose sidecar is kept in the blob store.
+    pub blob: bool,
+    /// EIP-2718 encoded consensus transaction, without the blob sidecar.
+    pub rlp: Bytes,
+}
+
+/// Reinserts the transactions of the journal at the given path into the pool.
+///
+/// All transactions are revalidated against the pool's current head. Blob transactions are
+/// reassembled with their sidecars from the blob store, and the sidecars of blob transactions that
+/// are no longer valid are deleted.
+pub async fn reload_journal<P>(pool: &P, path: &Path) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool<Transaction: PoolTransaction<Consensus: SignedTransaction>>,
+{
+    let Some(journal) = TransactionPoolJournal::read(path)? else { return Ok(()) };
+
+    if journal.block_hash != pool.block_info().last_seen_block_hash {
+        debug!(target: "txpool", journal_block = ?journal.block_hash, "Transaction pool journal was written at a different head");
+    }
+
+    let num_journaled = journal.transactions.len();
+    let mut blob_transactions = B256Set::default();
+    let transactions = journal
+        .transactions
+        .into_iter()
+        .filter_map(|journaled| {
+            let recovered = <P::Transaction as PoolTransaction>::Consensus::decode_2718_exact(
+                journaled.rlp.as_ref(),
+            )
+            .ok()?
+            .try_into_recovered()
+            .ok()?;
+
+            let tx = if recovered.is_eip4844() {
+                let sidecar = pool.get_blob(journaled.hash).ok().flatten()?;
+                blob_transactions.insert(journaled.hash);
+                P::Transaction::try_from_eip4844(recovered, Arc::unwrap_or_clone(sidecar))?
+            } else {
+                P::Transaction::try_from_consensus(recovered).ok()?
+            };
+            Some((journaled.origin, tx))
+        })
+        .collect::<Vec<_>>();
+
+    let hashes = transactions.iter().map(|(_, tx)| *tx.hash()).collect::<Vec<_>>();
+    let results = pool.add_transactions_with_origins(transactions).await;
+
+    let mut num_inserted = 0;
+    let mut invalid_blobs = Vec::new();
+    for (hash, result) in hashes.into_iter().zip(results) {
+        if result.is_ok() {
+            num_inserted += 1;
+        } else if blob_transactions.contains(&hash) {
+            invalid_blobs.push(hash);
+        }
+    }
+    if !invalid_blobs.is_empty() {
+        pool.delete_blobs(invalid_blobs);
+    }
+
+    info!(target: "txpool", journal =?path, num_journaled, num_inserted, "Reinserted transactions from the transaction pool journal");
+    Ok(())
+}
+
+/// Writes a snapshot of the pool to the journal file, logging any error.
+fn write_journal<P>(pool: &P, path: &Path)
+where
+    P: TransactionPool,
+{
+    let journal = TransactionPoolJournal::from_pool(pool);
+    match journal.write(path) {
+        Ok(()) => {
+            debug!(target: "txpool", journal =?path, num_txs = journal.transactions.len(), "Wrote transaction pool journal");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, journal =?path, "Failed to write transaction pool journal");
+        }
+    }
+}
+
+/// Task which reinserts the journaled transactions into the pool on startup, and then periodically
+/// writes the transactions of the pool to the journal until shutdown, when it is written a final
+/// time.
+pub async fn journal_transactions_task<P>(
+    shutdown: GracefulShutdown,
+    pool: P,
+    config: TransactionPoolJournalConfig,
+) where
+    P: TransactionPool<Transaction: PoolTransaction<Consensus: SignedTransaction>>,
+{
+    let TransactionPoolJournalConfig { path, interval } = config;
+
+    if let Err(err) = reload_journal(&pool, &path).await {
+        error!(target: "txpool", %err, journal =?path, "Failed to reload transaction pool journal")
+    }
+
+    let mut shutdown = pin!(shutdown);
+    let mut interval = interval_at(Instant::now() + interval, interval);
+    loop {
+        tokio::select! {
+            graceful_guard = &mut shutdown => {
+                write_journal(&pool, &path);
+                drop(graceful_guard);
+                break
+            }
+            _ = interval.tick() => write_journal(&pool, &path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        CoinbaseTipOrdering, EthPooledTransaction, Pool,
+    };
+    use alloy_primitives::{hex, Address, U256};
+    use reth_ethereum_primitives::PooledTransactionVariant;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_tasks::Runtime;
+
+    const SENDER: Address = Address::new(hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326"));
+
+    fn transaction() -> EthPooledTransaction {
+        let tx_bytes = hex!(
+            "02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507"
+        );
+        let tx = PooledTransactionVariant::decode_2718(&mut &tx_bytes[..]).unwrap();
+        EthPooledTransaction::from_pooled(tx.try_into_recovered().unwrap())
+    }
+
+    fn pool(nonce: u64) -> impl TransactionPool<Transaction = EthPooledTransaction> + Clone {
+        let provider = MockEthProvider::default().with_genesis_block();
+        provider.add_account(SENDER, ExtendedAccount::new(nonce, U256::MAX));
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(provider, EthEvmConfig::mainnet())
+            .build(blob_store.clone());
+        Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn journal_and_reload_transactions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("txpool-journal.json");
+        let transaction = transaction();
+
+        let txpool = pool(42);
+        txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+
+        let rt = Runtime::test();
+        let config = TransactionPoolJournalConfig::new(path.clone());
+        rt.spawn_critical_with_graceful_shutdown_signal("test task", |shutdown| {
+            journal_transactions_task(shutdown, txpool.clone(), config)
+        });
+        rt.graceful_shutdown();
+
+        let journal = TransactionPoolJournal::read(&path).unwrap().unwrap();
+        assert_eq!(journal.transactions.len(), 1);
+        assert_eq!(journal.transactions[0].hash, *transaction.hash());
+        assert_eq!(journal.transactions[0].origin, TransactionOrigin::External);
+        assert!(!journal.transactions[0].blob);
+
+        // the journal is revalidated when it's reloaded
+        let txpool = pool(42);
+        reload_journal(&txpool, &path).await.unwrap();
+        assert!(txpool.contains(transaction.hash()));
+
+        let txpool = pool(43);
+        reload_journal(&txpool, &path).await.unwrap();
+        assert!(!txpool.contains(transaction.hash()));
+    }
+}
@@ -284,9 +284,10 @@ pub use crate::{
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
         DEFAULT_MAX_INFLIGHT_DELEGATED_SLOTS, DEFAULT_PRICE_BUMP,
-        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, DEFAULT_TXPOOL_SHARDS,
+        MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::{PoolResult, RawPoolTransactionError},
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
@@ -302,12 +303,15 @@ pub use crate::{
         ValidPoolTransaction,
     },
 };
-use crate::{identifier::TransactionId, pool::PoolInner};
+use crate::{
+    identifier::TransactionId,
+    pool::{history::TransactionHistory, PoolInner},
+};
 use alloy_eips::{
     eip4844::{BlobAndProofV1, BlobAndProofV2, BlobCellsAndProofsV1},
     eip7594::BlobTransactionSidecarVariant,
 };
-use alloy_primitives::{map::AddressSet, Address, TxHash, B128, B256, U256};
+use alloy_primitives::{map::AddressSet, Address, BlockNumber, TxHash, B128, B256, U256};
 use aquamarine as _;
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_eth_wire_types::HandleMempoolData;
@@ -320,7 +324,9 @@ use std::sync::Arc;
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
+pub mod conditional;
 pub mod error;
+pub mod journal;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
@@ -502,6 +508,20 @@ where
         results.pop().expect("result length is the same as the input")
     }
 
+    async fn add_conditional_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        conditional: conditional::TransactionConditional,
+    ) -> PoolResult<AddedTransactionOutcome> {
+        let tx = self.validate(origin, transaction).await;
+        self.pool.add_conditional_transaction(origin, tx, conditional)
+    }
+
+    fn conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.conditional_transactions()
+    }
+
     async fn add_transactions(
         &self,
         origin: TransactionOrigin,
@@ -538,6 +558,14 @@ where
         self.pool.add_all_transactions_event_listener()
     }
 
+    fn transaction_history(&self, tx_hash: TxHash) -> Option<TransactionHistory> {
+        self.pool.transaction_history(&tx_hash)
+    }
+
+    fn sender_transaction_history(&self, sender: Address) -> Vec<TransactionHistory> {
+        self.pool.sender_transaction_history(sender)
+    }
+
     fn pending_transactions_listener_for(&self, kind: TransactionListenerKind) -> Receiver<TxHash> {
         self.pool.add_pending_listener(kind)
     }
@@ -600,7 +628,7 @@ where
     fn best_transactions(
         &self,
     ) -> Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<Self::Transaction>>>> {
-        Box::new(self.pool.best_transactions())
+        self.pool.best_transactions()
     }
 
     fn best_transactions_with_attributes(
@@ -634,10 +662,7 @@ where
     }
 
     fn pending_and_queued_txn_count(&self) -> (usize, usize) {
-        let data = self.pool.get_pool_data();
-        let pending = data.pending_transactions_count();
-        let queued = data.queued_transactions_count();
-        (pending, queued)
+        self.pool.pending_and_queued_transactions_count()
     }
 
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction> {
@@ -753,7 +778,7 @@ where
         let sender_id = self.pool.sender_id(&sender)?;
         let transaction_id = TransactionId::new(sender_id, nonce);
 
-        self.inner().get_pool_data().all().get(&transaction_id).map(|tx| tx.transaction.clone())
+        self.inner().get_transaction_by_transaction_id(&transaction_id)
     }
 
     fn get_transactions_by_origin(
@@ -868,6 +893,10 @@ where
         self.pool.delete_blobs(txs)
     }
 
+    fn finalize_blobs(&self, blocks: Vec<(BlockNumber, Vec<TxHash>)>) {
+        self.pool.finalize_blobs(blocks)
+    }
+
     fn cleanup_blobs(&self) {
         self.pool.cleanup_blobs()
     }
@@ -1,7 +1,8 @@
 //! Support for maintaining the state of the transaction pool
 
 use crate::{
-    blobstore::{BlobSidecarConverter, BlobStoreCanonTracker, BlobStoreUpdates},
+    blobstore::{BlobSidecarConverter, BlobStoreCanonTracker},
+    conditional::{is_expired_conditional, validate_known_accounts, TransactionConditionalError},
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
@@ -233,15 +234,15 @@ pub async fn maintain_transaction_pool<N, Client, P, St>(
         // check if we have a new finalized block
         if let Some(finalized) =
             last_finalized_block.update(client.finalized_block_number().ok().flatten()) &&
-            let BlobStoreUpdates::Finalized(blobs) =
-                blob_store_tracker.on_finalized_block(finalized)
+            let blocks = blob_store_tracker.take_finalized_blocks(finalized) &&
+            !blocks.is_empty()
         {
-            metrics.inc_deleted_tracked_blobs(blobs.len());
-            // remove all finalized blobs from the blob store
-            pool.delete_blobs(blobs);
-            // and also do periodic cleanup
+            metrics.inc_deleted_tracked_blobs(blocks.iter().map(|(_, txs)| txs.len()).sum());
             let pool = pool.clone();
             task_spawner.spawn_blocking_task(async move {
+                // remove all finalized blobs from the blob store, or retain them if configured
+                pool.finalize_blobs(blocks);
+                // and also do periodic cleanup
                 debug!(target: "txpool", finalized_block = %finalized, "cleaning up blob store");
                 pool.cleanup_blobs();
             });
@@ -435,6 +436,18 @@ pub async fn maintain_transaction_pool<N, Client, P, St>(
 
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
+
+                // evict conditional transactions that can no longer be included
+                {
+                    let (pool, client) = (pool.clone(), client.clone());
+                    let (hash, number, timestamp) =
+                        (new_tip.hash(), new_tip.number(), new_tip.timestamp());
+                    task_spawner.spawn_blocking_task(async move {
+                        remove_unsatisfiable_conditional_transactions(
+                            &pool, &client, hash, number, timestamp,
+                        );
+                    });
+                }
             }
             CanonStateNotification::Commit { new } => {
                 let (blocks, state) = new.inner();
@@ -510,6 +523,17 @@ pub async fn maintain_transaction_pool<N, Client, P, St>(
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
 
+                // evict conditional transactions that can no longer be included
+                {
+                    let (pool, client) = (pool.clone(), client.clone());
+                    let (hash, number, timestamp) = (tip.hash(), tip.number(), tip.timestamp());
+                    task_spawner.spawn_blocking_task(async move {
+                        remove_unsatisfiable_conditional_transactions(
+                            &pool, &client, hash, number, timestamp,
+                        );
+                    });
+                }
+
                 // If Osaka activates in 2 slots we need to convert blobs to new format.
                 if !chain_spec.is_osaka_active_at_timestamp(tip.timestamp()) &&
                     !chain_spec.is_osaka_active_at_timestamp(tip.timestamp().saturating_add(12)) &&
@@ -661,6 +685,59 @@ struct LoadedAccounts {
     failed_to_load: Vec<Address>,
 }
 
+/// Removes all transactions whose [ERC-7796](https://eips.ethereum.org/EIPS/eip-7796) conditions
+/// can no longer be satisfied by a block on top of the given block.
+fn remove_unsatisfiable_conditional_transactions<P, Client>(
+    pool: &P,
+    client: &Client,
+    block_hash: BlockHash,
+    block_number: BlockNumber,
+    timestamp: u64,
+) where
+    P: TransactionPool,
+    Client: StateProviderFactory,
+{
+    let conditional_txs = pool.conditional_transactions();
+    if conditional_txs.is_empty() {
+        return
+    }
+
+    let state = match client.state_by_block_hash(block_hash) {
+        Ok(state) => state,
+        Err(err) => {
+            debug!(target: "txpool", %err, "failed to load state to check conditional transactions");
+            return
+        }
+    };
+
+    let mut to_remove = Vec::new();
+    let mut blobs = Vec::new();
+    for tx in conditional_txs {
+        let Some(conditional) = tx.conditional.as_deref() else { continue };
+        let satisfiable = !is_expired_conditional(conditional, block_number, timestamp) &&
+            match validate_known_accounts(conditional, state.as_ref()) {
+                Ok(()) => true,
+                Err(TransactionConditionalError::Provider(err)) => {
+                    debug!(target: "txpool", %err, tx=?tx.hash(), "failed to check known accounts of conditional transaction");
+                    true
+                }
+                Err(_) => false,
+            };
+        if !satisfiable {
+            if tx.is_eip4844() {
+                blobs.push(*tx.hash());
+            }
+            to_remove.push(*tx.hash());
+        }
+    }
+
+    if !to_remove.is_empty() {
+        debug!(target: "txpool", count=%to_remove.len(), "removing unsatisfiable conditional transactions");
+        pool.remove_transactions(to_remove);
+        pool.delete_blobs(blobs);
+    }
+}
+
 /// Loads all accounts at the given state
 ///
 /// Returns an error with all given addresses if the state is not available.
@@ -1,5 +1,6 @@
 //! Transaction pool metrics.
 
+use crate::traits::PoolSize;
 use reth_metrics::{
     metrics::{Counter, Gauge, Histogram},
     Metrics,
@@ -64,6 +65,21 @@ pub struct TxPoolMetrics {
     pub queued_transactions_evicted: Counter,
 }
 
+impl TxPoolMetrics {
+    /// Sets the sub-pool size gauges.
+    pub(crate) fn record_pool_size(&self, stats: &PoolSize) {
+        self.pending_pool_transactions.set(stats.pending as f64);
+        self.pending_pool_size_bytes.set(stats.pending_size as f64);
+        self.basefee_pool_transactions.set(stats.basefee as f64);
+        self.basefee_pool_size_bytes.set(stats.basefee_size as f64);
+        self.queued_pool_transactions.set(stats.queued as f64);
+        self.queued_pool_size_bytes.set(stats.queued_size as f64);
+        self.blob_pool_transactions.set(stats.blob as f64);
+        self.blob_pool_size_bytes.set(stats.blob_size as f64);
+        self.total_transactions.set(stats.total as f64);
+    }
+}
+
 /// Transaction pool blobstore metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
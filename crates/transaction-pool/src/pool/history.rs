@@ -0,0 +1,184 @@
+//! Bounded history of the lifecycle events of transactions.
+//!
+//! The pool records what happened to a transaction (added, replaced, moved between subpools,
+//! discarded, rejected, mined) so that it's still possible to tell why a transaction is no longer
+//! in the pool after it was removed.
+//!
+//! Recording is disabled by default, see
+//! [`PoolConfig::max_history_transactions`](crate::PoolConfig::max_history_transactions).
+
+use crate::{PoolTransaction, TransactionOrigin, ValidPoolTransaction};
+use alloy_primitives::{Address, TxHash};
+use schnellru::{ByLength, LruMap};
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub use reth_transaction_pool_primitives::{
+    DiscardReason, TransactionHistory, TransactionHistoryEvent, TransactionHistoryRecord,
+    MAX_HISTORY_EVENTS_PER_TRANSACTION,
+};
+
+/// Keeps the [`TransactionHistory`] of the most recently updated transactions.
+pub(crate) struct TransactionHistoryLog {
+    /// Histories by transaction hash.
+    histories: LruMap<TxHash, TransactionHistory, ByLength>,
+}
+
+impl TransactionHistoryLog {
+    /// Creates a new log that keeps the history of at most `max_transactions` transactions.
+    pub(crate) fn new(max_transactions: usize) -> Self {
+        Self { histories: LruMap::new(ByLength::new(max_transactions as u32)) }
+    }
+
+    /// Returns the history of the given transaction.
+    pub(crate) fn get(&self, hash: &TxHash) -> Option<TransactionHistory> {
+        self.histories.peek(hash).cloned()
+    }
+
+    /// Returns the histories of all transactions of the given sender, ordered by their first
+    /// event.
+    pub(crate) fn by_sender(&self, sender: Address) -> Vec<TransactionHistory> {
+        let mut histories = self
+            .histories
+            .iter()
+            .filter(|(_, history)| history.sender == Some(sender))
+            .map(|(_, history)| history.clone())
+            .collect::<Vec<_>>();
+        histories.sort_unstable_by_key(|history| history.events.first().map(|e| e.timestamp));
+        histories
+    }
+
+    /// Records an event for the given transaction.
+    pub(crate) fn record(
+        &mut self,
+        hash: TxHash,
+        sender: Option<Address>,
+        event: TransactionHistoryEvent,
+        timestamp: u64,
+    ) {
+        let Some(history) = self.histories.get_or_insert(hash, || TransactionHistory {
+            hash,
+            sender,
+            events: Vec::new(),
+        }) else {
+            return
+        };
+        if history.sender.is_none() {
+            history.sender = sender;
+        }
+        if history.events.len() == MAX_HISTORY_EVENTS_PER_TRANSACTION {
+            history.events.remove(0);
+        }
+        history.events.push(TransactionHistoryRecord { timestamp, event });
+    }
+
+    /// Records an event for the given pool transaction.
+    pub(crate) fn record_tx<T: PoolTransaction>(
+        &mut self,
+        tx: &ValidPoolTransaction<T>,
+        event: TransactionHistoryEvent,
+        timestamp: u64,
+    ) {
+        self.record(*tx.hash(), Some(tx.sender()), event, timestamp)
+    }
+
+    /// Records that the given transaction was not accepted into the pool.
+    ///
+    /// Rejections of transactions received from peers are only recorded if the transaction's
+    /// history is already tracked, so that invalid gossip can't evict the histories of
+    /// transactions that were in the pool.
+    pub(crate) fn record_rejected(
+        &mut self,
+        hash: TxHash,
+        sender: Option<Address>,
+        origin: TransactionOrigin,
+        reason: String,
+        timestamp: u64,
+    ) {
+        let event = TransactionHistoryEvent::Rejected { reason };
+        if origin.is_external() {
+            self.record_known(hash, event, timestamp)
+        } else {
+            self.record(hash, sender, event, timestamp)
+        }
+    }
+
+    /// Records an event for the given transaction only if its history is already tracked.
+    ///
+    /// This is used for events that also concern transactions that never were in the pool, like
+    /// mined transactions, which would otherwise evict the tracked histories.
+    pub(crate) fn record_known(
+        &mut self,
+        hash: TxHash,
+        event: TransactionHistoryEvent,
+        timestamp: u64,
+    ) {
+        if self.histories.peek(&hash).is_some() {
+            self.record(hash, None, event, timestamp)
+        }
+    }
+}
+
+impl fmt::Debug for TransactionHistoryLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionHistoryLog").field("len", &self.histories.len()).finish()
+    }
+}
+
+/// Returns the current unix timestamp in milliseconds.
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SubPool;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn bounded_history() {
+        let mut log = TransactionHistoryLog::new(2);
+        let sender = Address::random();
+        let (a, b, c) = (B256::random(), B256::random(), B256::random());
+
+        log.record(a, Some(sender), TransactionHistoryEvent::Added { subpool: SubPool::Queued }, 1);
+        for i in 0..MAX_HISTORY_EVENTS_PER_TRANSACTION as u64 {
+            log.record(
+                a,
+                None,
+                TransactionHistoryEvent::Moved { subpool: SubPool::Pending },
+                i + 2,
+            );
+        }
+        let history = log.get(&a).unwrap();
+        assert_eq!(history.sender, Some(sender));
+        assert_eq!(history.events.len(), MAX_HISTORY_EVENTS_PER_TRANSACTION);
+        assert_eq!(history.events[0].timestamp, 2);
+
+        // unknown transactions are not tracked
+        log.record_known(b, TransactionHistoryEvent::Mined { block_hash: B256::ZERO }, 100);
+        assert!(log.get(&b).is_none());
+
+        log.record(
+            b,
+            Some(sender),
+            TransactionHistoryEvent::Added { subpool: SubPool::Pending },
+            3,
+        );
+        assert_eq!(log.by_sender(sender).iter().map(|h| h.hash).collect::<Vec<_>>(), vec![a, b]);
+
+        // rejected gossip is only recorded for tracked transactions
+        log.record_rejected(c, None, TransactionOrigin::External, "invalid".to_string(), 4);
+        assert!(log.get(&c).is_none());
+        log.record_rejected(b, None, TransactionOrigin::External, "invalid".to_string(), 4);
+        assert_eq!(log.get(&b).unwrap().events.len(), 2);
+
+        // the least recently updated history is evicted
+        log.record(c, None, TransactionHistoryEvent::Added { subpool: SubPool::Pending }, 4);
+        assert!(log.get(&a).is_none());
+        assert!(log.get(&c).is_some());
+    }
+}
@@ -64,17 +64,38 @@
 //!  - _Queued_: queued transactions are transactions that fall under category (3.). Those
 //!    transactions are _currently_ waiting for state changes that eventually move them into
 //!    category (2.) and become pending.
+//!
+//! ## Locking
+//!
+//! The pool is split into shards by sender, see [`PoolConfig::shards`]. Each shard holds all
+//! sub-pools for its senders behind its own lock, because promotions and demotions move a sender's
+//! transactions between the sub-pools atomically. Transactions from senders in different shards
+//! can therefore be inserted concurrently. Operations that affect the entire pool, like canonical
+//! state changes, lock the shards one after another and never hold more than one write lock at a
+//! time. The sub-pool limits are split evenly across the shards and are enforced per shard.
+//!
+//! To further reduce contention under high ingress, everything that doesn't touch the sub-pools is
+//! done outside of the write locks: validation, resolving sender ids, notifying listeners and
+//! storing blob sidecars. A shard's write lock is only held for the actual insertion and the
+//! enforcement of the shard's size limits. See the `concurrent_ingress` benchmark for the
+//! throughput under concurrent insertions with different numbers of shards.
 
 use crate::{
     blobstore::BlobStore,
+    conditional::TransactionConditional,
     error::{PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     metrics::BlobStoreMetrics,
     pool::{
+        history::{
+            now_millis, DiscardReason, TransactionHistory, TransactionHistoryEvent,
+            TransactionHistoryLog,
+        },
         listener::{
             BlobTransactionSidecarListener, PendingTransactionHashListener, PoolEventBroadcast,
             TransactionListener,
         },
+        sharded::ShardedPool,
         state::SubPool,
         txpool::{SenderInfo, TxPool},
         update::UpdateOutcome,
@@ -90,9 +111,9 @@ use crate::{
 
 use alloy_primitives::{
     map::{AddressSet, HashSet},
-    Address, TxHash, B256,
+    Address, BlockNumber, TxHash, B256, U256,
 };
-use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use parking_lot::{Mutex, RwLock};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
 
@@ -110,6 +131,7 @@ use std::{
 use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 mod events;
+pub mod history;
 pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
 pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
@@ -124,6 +146,7 @@ mod blob;
 pub mod listener;
 mod parked;
 pub mod pending;
+mod sharded;
 pub mod size;
 pub(crate) mod state;
 pub mod txpool;
@@ -147,14 +170,19 @@ where
     validator: V,
     /// Storage for blob transactions
     blob_store: S,
-    /// The internal pool that manages all transactions.
-    pool: RwLock<TxPool<T>>,
+    /// The internal pool that manages all transactions, sharded by sender.
+    pool: ShardedPool<T>,
     /// Pool settings.
     config: PoolConfig,
     /// Manages listeners for transaction state change events.
     event_listener: RwLock<PoolEventBroadcast<T::Transaction>>,
     /// Tracks whether any event listeners have ever been installed.
     has_event_listeners: AtomicBool,
+    /// Recorded lifecycle events of transactions, if enabled.
+    history: Option<Mutex<TransactionHistoryLog>>,
+    /// Hashes of the transactions that were added with conditions, see
+    /// [`Self::conditional_transactions`].
+    conditional_transactions: Mutex<HashSet<TxHash>>,
     /// Listeners for new _full_ pending transactions.
     pending_transaction_listener: RwLock<Vec<PendingTransactionHashListener>>,
     /// Listeners for new transactions added to the pool.
@@ -180,7 +208,10 @@ where
             validator,
             event_listener: Default::default(),
             has_event_listeners: AtomicBool::new(false),
-            pool: RwLock::new(TxPool::new(ordering, config.clone())),
+            history: (config.max_history_transactions > 0)
+                .then(|| Mutex::new(TransactionHistoryLog::new(config.max_history_transactions))),
+            conditional_transactions: Default::default(),
+            pool: ShardedPool::new(ordering, config.clone()),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
@@ -197,19 +228,19 @@ where
 
     /// Returns stats about the size of the pool.
     pub fn size(&self) -> PoolSize {
-        self.get_pool_data().size()
+        self.pool.size()
     }
 
     /// Returns the currently tracked block
     pub fn block_info(&self) -> BlockInfo {
-        self.get_pool_data().block_info()
+        self.pool.block_info()
     }
     /// Sets the currently tracked block.
     ///
     /// This will also notify subscribers about any transactions that were promoted to the pending
     /// pool due to fee changes.
     pub fn set_block_info(&self, info: BlockInfo) {
-        let outcome = self.pool.write().set_block_info(info);
+        let outcome = self.pool.set_block_info(info);
 
         // Notify subscribers about promoted transactions due to fee changes
         self.notify_on_transaction_updates(outcome.promoted, outcome.discarded);
@@ -240,7 +271,7 @@ where
 
     /// Returns all senders in the pool
     pub fn unique_senders(&self) -> AddressSet {
-        self.get_pool_data().unique_senders()
+        self.pool.unique_senders()
     }
 
     /// Converts the changed accounts to a map of sender ids to sender info (internal identifier
@@ -310,7 +341,7 @@ where
     /// If the pool contains the transaction, this adds a new listener that gets notified about
     /// transaction events.
     pub fn add_transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
-        if !self.get_pool_data().contains(&tx_hash) {
+        if !self.pool.contains(&tx_hash) {
             return None
         }
         let mut listener = self.event_listener.write();
@@ -359,9 +390,25 @@ where
         self.update_event_listener_state(&listener);
     }
 
-    /// Returns a read lock to the pool's data.
-    pub fn get_pool_data(&self) -> RwLockReadGuard<'_, TxPool<T>> {
-        self.pool.read()
+    /// Records events in the transaction history, if it's enabled.
+    #[inline]
+    fn with_history<F>(&self, record: F)
+    where
+        F: FnOnce(&mut TransactionHistoryLog, u64),
+    {
+        if let Some(history) = &self.history {
+            record(&mut history.lock(), now_millis());
+        }
+    }
+
+    /// Returns the recorded lifecycle events of the given transaction.
+    pub fn transaction_history(&self, tx_hash: &TxHash) -> Option<TransactionHistory> {
+        self.history.as_ref()?.lock().get(tx_hash)
+    }
+
+    /// Returns the recorded lifecycle events of all transactions of the given sender.
+    pub fn sender_transaction_history(&self, sender: Address) -> Vec<TransactionHistory> {
+        self.history.as_ref().map(|history| history.lock().by_sender(sender)).unwrap_or_default()
     }
 
     /// Returns transactions in the pool that can be propagated
@@ -387,9 +434,8 @@ where
             return Vec::new()
         }
 
-        let pool = self.get_pool_data();
-        let mut out = Vec::with_capacity(max.min(pool.all().len()));
-        out.extend(pool.all().transactions_iter().filter(|tx| tx.propagate).take(max).cloned());
+        let mut out = Vec::new();
+        self.append_pooled_transactions_max(max, &mut out);
         out
     }
 
@@ -398,9 +444,9 @@ where
         &self,
         out: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
     ) {
-        out.extend(
-            self.get_pool_data().all().transactions_iter().filter(|tx| tx.propagate).cloned(),
-        );
+        for shard in self.pool.shards() {
+            out.extend(shard.read().all().transactions_iter().filter(|tx| tx.propagate).cloned());
+        }
     }
 
     /// Extends the given vector with pooled transactions for the given hashes that are allowed to
@@ -433,13 +479,16 @@ where
     /// Extends the given vector with the hashes of all transactions in the pool that can be
     /// propagated.
     pub fn append_pooled_transactions_hashes(&self, out: &mut Vec<TxHash>) {
-        out.extend(
-            self.get_pool_data()
-                .all()
-                .transactions_iter()
-                .filter(|tx| tx.propagate)
-                .map(|tx| *tx.hash()),
-        );
+        for shard in self.pool.shards() {
+            out.extend(
+                shard
+                    .read()
+                    .all()
+                    .transactions_iter()
+                    .filter(|tx| tx.propagate)
+                    .map(|tx| *tx.hash()),
+            );
+        }
     }
 
     /// Extends the given vector with only the first `max` transactions in the pool that can be
@@ -449,14 +498,23 @@ where
         max: usize,
         out: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
     ) {
-        out.extend(
-            self.get_pool_data()
-                .all()
-                .transactions_iter()
-                .filter(|tx| tx.propagate)
-                .take(max)
-                .cloned(),
-        );
+        let mut remaining = max;
+        for shard in self.pool.shards() {
+            if remaining == 0 {
+                break
+            }
+            let len = out.len();
+            out.extend(
+                shard
+                    .read()
+                    .all()
+                    .transactions_iter()
+                    .filter(|tx| tx.propagate)
+                    .take(remaining)
+                    .cloned(),
+            );
+            remaining -= out.len() - len;
+        }
     }
 
     /// Returns only the first `max` hashes of transactions in the pool that can be propagated.
@@ -465,11 +523,22 @@ where
             return Vec::new();
         }
 
-        let pool = self.get_pool_data();
-        let mut out = Vec::with_capacity(max.min(pool.all().len()));
-        out.extend(
-            pool.all().transactions_iter().filter(|tx| tx.propagate).take(max).map(|tx| *tx.hash()),
-        );
+        let mut out = Vec::with_capacity(max.min(self.len()));
+        for shard in self.pool.shards() {
+            let remaining = max - out.len();
+            if remaining == 0 {
+                break
+            }
+            out.extend(
+                shard
+                    .read()
+                    .all()
+                    .transactions_iter()
+                    .filter(|tx| tx.propagate)
+                    .take(remaining)
+                    .map(|tx| *tx.hash()),
+            );
+        }
         out
     }
 
@@ -541,7 +610,7 @@ where
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
         // update the pool
-        let outcome = self.pool.write().on_canonical_state_change(
+        let outcome = self.pool.on_canonical_state_change(
             block_info,
             mined_transactions,
             changed_senders,
@@ -562,25 +631,24 @@ where
     /// This should be invoked when the pool drifted and accounts are updated manually
     pub fn update_accounts(&self, accounts: Vec<ChangedAccount>) {
         let changed_senders = self.changed_senders(accounts.into_iter());
-        let UpdateOutcome { promoted, discarded } =
-            self.pool.write().update_accounts(changed_senders);
+        let UpdateOutcome { promoted, discarded, demoted } =
+            self.pool.update_accounts(changed_senders);
 
+        self.record_demoted(&demoted);
         self.notify_on_transaction_updates(promoted, discarded);
     }
 
-    /// Add a single validated transaction into the pool.
-    ///
-    /// Returns the outcome and optionally metadata to be processed after the pool lock is
-    /// released.
+    /// Prepares a validated transaction for insertion into the pool.
     ///
-    /// Note: this is only used internally by [`Self::add_transactions()`], all new transaction(s)
-    /// come in through that function, either as a batch or `std::iter::once`.
-    fn add_transaction(
+    /// This resolves the sender ids and notifies listeners about invalid transactions. It doesn't
+    /// require the pool lock, so it's performed before the lock is acquired to keep the critical
+    /// section of [`Self::add_transactions()`] as short as possible.
+    fn prepare_transaction(
         &self,
-        pool: &mut RwLockWriteGuard<'_, TxPool<T>>,
         origin: TransactionOrigin,
         tx: TransactionValidationOutcome<T::Transaction>,
-    ) -> (PoolResult<AddedTransactionOutcome>, Option<AddedTransactionMeta<T::Transaction>>) {
+        conditional: Option<Box<TransactionConditional>>,
+    ) -> PoolResult<PreparedTransaction<T::Transaction>> {
         match tx {
             TransactionValidationOutcome::Valid {
                 balance,
@@ -605,37 +673,67 @@ where
                     }
                 };
 
-                let tx = ValidPoolTransaction {
+                let transaction = ValidPoolTransaction {
                     transaction,
                     transaction_id,
                     propagate,
                     timestamp: Instant::now(),
                     origin,
                     authority_ids: authorities.map(|auths| self.get_sender_ids(auths)),
+                    conditional,
                 };
 
-                let added = match pool.add_transaction(tx, balance, state_nonce, bytecode_hash) {
-                    Ok(added) => added,
-                    Err(err) => return (Err(err), None),
-                };
-                let hash = *added.hash();
-                let state = added.transaction_state();
-
-                let meta = AddedTransactionMeta { added, blob_sidecar };
-
-                (Ok(AddedTransactionOutcome { hash, state }), Some(meta))
+                Ok(PreparedTransaction {
+                    transaction,
+                    balance,
+                    state_nonce,
+                    bytecode_hash,
+                    blob_sidecar,
+                })
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 self.with_event_listener(|listener| listener.invalid(tx.hash()));
-                (Err(PoolError::new(*tx.hash(), err)), None)
+                self.with_history(|history, timestamp| {
+                    history.record_rejected(
+                        *tx.hash(),
+                        Some(tx.sender()),
+                        origin,
+                        err.to_string(),
+                        timestamp,
+                    )
+                });
+                Err(PoolError::new(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 self.with_event_listener(|listener| listener.discarded(&tx_hash));
-                (Err(PoolError::other(tx_hash, err)), None)
+                self.with_history(|history, timestamp| {
+                    history.record_rejected(tx_hash, None, origin, err.to_string(), timestamp)
+                });
+                Err(PoolError::other(tx_hash, err))
             }
         }
     }
 
+    /// Inserts a prepared transaction into the pool.
+    ///
+    /// Returns the outcome and metadata to be processed after the pool lock is released.
+    ///
+    /// Note: this is only used internally by [`Self::add_transactions()`], all new transaction(s)
+    /// come in through that function, either as a batch or `std::iter::once`.
+    fn insert_prepared_transaction(
+        pool: &mut TxPool<T>,
+        prepared: PreparedTransaction<T::Transaction>,
+    ) -> PoolResult<(AddedTransactionOutcome, AddedTransactionMeta<T::Transaction>)> {
+        let PreparedTransaction { transaction, balance, state_nonce, bytecode_hash, blob_sidecar } =
+            prepared;
+
+        let added = pool.add_transaction(transaction, balance, state_nonce, bytecode_hash)?;
+        let hash = *added.hash();
+        let state = added.transaction_state();
+
+        Ok((AddedTransactionOutcome { hash, state }, AddedTransactionMeta { added, blob_sidecar }))
+    }
+
     /// Adds a transaction and returns the event stream.
     pub fn add_transaction_and_subscribe(
         &self,
@@ -673,38 +771,126 @@ where
             Item = (TransactionOrigin, TransactionValidationOutcome<T::Transaction>),
         >,
     ) -> Vec<PoolResult<AddedTransactionOutcome>> {
-        // Collect results and metadata while holding the pool write lock
-        let (mut results, added_metas, discarded) = {
-            let mut pool = self.pool.write();
-            let mut added_metas = Vec::new();
-
-            let results = transactions
-                .into_iter()
-                .map(|(origin, tx)| {
-                    let (result, meta) = self.add_transaction(&mut pool, origin, tx);
-
-                    // Only collect metadata for successful insertions
-                    if result.is_ok() &&
-                        let Some(meta) = meta
-                    {
+        self.add_transactions_with_conditionals(
+            transactions.into_iter().map(|(origin, tx)| (origin, tx, None)),
+        )
+    }
+
+    /// Adds a transaction with the given [ERC-7796](https://eips.ethereum.org/EIPS/eip-7796)
+    /// conditions to the pool.
+    pub fn add_conditional_transaction(
+        &self,
+        origin: TransactionOrigin,
+        tx: TransactionValidationOutcome<T::Transaction>,
+        conditional: TransactionConditional,
+    ) -> PoolResult<AddedTransactionOutcome> {
+        let mut results = self.add_transactions_with_conditionals(std::iter::once((
+            origin,
+            tx,
+            Some(Box::new(conditional)),
+        )));
+        let result = results.pop().expect("result length is the same as the input");
+        if let Ok(AddedTransactionOutcome { hash, .. }) = &result {
+            self.conditional_transactions.lock().insert(*hash);
+        }
+        result
+    }
+
+    /// Returns all transactions in the pool that were added with
+    /// [ERC-7796](https://eips.ethereum.org/EIPS/eip-7796) conditions.
+    ///
+    /// The hashes of these transactions are tracked in their own index, so this doesn't scan the
+    /// entire pool. Hashes of transactions that are no longer in the pool are dropped from the
+    /// index.
+    pub fn conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let mut hashes = self.conditional_transactions.lock();
+        let mut transactions = Vec::with_capacity(hashes.len());
+        hashes.retain(|hash| {
+            let Some(tx) = self.pool.get(hash).filter(|tx| tx.conditional.is_some()) else {
+                return false
+            };
+            transactions.push(tx);
+            true
+        });
+        transactions
+    }
+
+    /// Adds all transactions in the iterator to the pool, each with its own
+    /// [`TransactionOrigin`] and optional conditions, returning a list of results.
+    fn add_transactions_with_conditionals(
+        &self,
+        transactions: impl IntoIterator<
+            Item = (
+                TransactionOrigin,
+                TransactionValidationOutcome<T::Transaction>,
+                Option<Box<TransactionConditional>>,
+            ),
+        >,
+    ) -> Vec<PoolResult<AddedTransactionOutcome>> {
+        // Prepare all transactions before acquiring any shard's write lock and group them by the
+        // shard of their sender, keeping track of their position in the batch
+        let mut results = Vec::new();
+        let mut by_shard = (0..self.pool.num_shards()).map(|_| Vec::new()).collect::<Vec<_>>();
+        for (index, (origin, tx, conditional)) in transactions.into_iter().enumerate() {
+            match self.prepare_transaction(origin, tx, conditional) {
+                Ok(prepared) => {
+                    let shard = self.pool.shard_index(prepared.transaction.sender_id());
+                    by_shard[shard].push((index, prepared));
+                    results.push(None);
+                }
+                Err(err) => results.push(Some(Err(err))),
+            }
+        }
+
+        // Insert the transactions of each shard while holding only that shard's write lock
+        let mut added_metas = Vec::new();
+        let mut rejected = Vec::new();
+        let mut discarded = Vec::new();
+        for (shard, prepared) in by_shard.into_iter().enumerate() {
+            if prepared.is_empty() {
+                continue
+            }
+
+            let mut pool = self.pool.shard(shard).write();
+            let mut any_added = false;
+            for (index, prepared) in prepared {
+                let (sender, origin) = (prepared.transaction.sender(), prepared.transaction.origin);
+                let result = match Self::insert_prepared_transaction(&mut pool, prepared) {
+                    Ok((outcome, meta)) => {
                         added_metas.push(meta);
+                        any_added = true;
+                        Ok(outcome)
                     }
+                    Err(err) => {
+                        if !matches!(err.kind, PoolErrorKind::AlreadyImported) {
+                            rejected.push((err.hash, sender, origin, err.to_string()));
+                        }
+                        Err(err)
+                    }
+                };
+                results[index] = Some(result);
+            }
 
-                    result
-                })
-                .collect::<Vec<_>>();
-
-            // Enforce the pool size limits if at least one transaction was added successfully
-            let discarded = if results.iter().any(Result::is_ok) {
-                let discarded = pool.discard_worst();
+            // Enforce the shard's size limits if at least one transaction was added successfully
+            if any_added {
+                discarded.extend(pool.discard_worst());
                 pool.update_size_metrics();
-                discarded
-            } else {
-                Default::default()
-            };
+            }
+        }
+        self.pool.update_metrics();
 
-            (results, added_metas, discarded)
-        };
+        let mut results = results
+            .into_iter()
+            .map(|result| result.expect("every transaction has a result"))
+            .collect::<Vec<_>>();
+
+        if !rejected.is_empty() {
+            self.with_history(|history, timestamp| {
+                for (hash, sender, origin, reason) in rejected {
+                    history.record_rejected(hash, Some(sender), origin, reason, timestamp);
+                }
+            });
+        }
 
         for meta in added_metas {
             self.on_added_transaction(meta);
@@ -714,6 +900,7 @@ where
             // Delete any blobs associated with discarded blob transactions
             self.delete_discarded_blobs(discarded.iter());
             self.with_event_listener(|listener| listener.discarded_many(&discarded));
+            self.record_discarded(&discarded, DiscardReason::PoolLimit);
 
             let discarded_hashes =
                 discarded.into_iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
@@ -762,6 +949,7 @@ where
 
         // Notify event listeners
         self.notify_event_listeners(&meta.added);
+        self.record_added(&meta.added);
 
         // Notify new transaction listeners
         self.on_new_transaction(meta.added.into_new_transaction_event());
@@ -888,7 +1076,17 @@ where
             self.transaction_listener.write().retain(|l| !l.sender.is_closed());
         }
 
-        let OnNewCanonicalStateOutcome { mined, promoted, discarded, block_hash } = outcome;
+        let OnNewCanonicalStateOutcome { mined, promoted, discarded, demoted, block_hash } =
+            outcome;
+
+        self.with_history(|history, timestamp| {
+            for tx in &mined {
+                history.record_known(*tx, TransactionHistoryEvent::Mined { block_hash }, timestamp);
+            }
+        });
+        self.record_promoted(&promoted);
+        self.record_demoted(&demoted);
+        self.record_discarded(&discarded, DiscardReason::Invalidated);
 
         // broadcast specific transaction events
         self.with_event_listener(|listener| {
@@ -969,6 +1167,8 @@ where
                 listener.discarded(tx.hash());
             }
         });
+        self.record_promoted(&promoted);
+        self.record_discarded(&discarded, DiscardReason::Invalidated);
 
         if !discarded.is_empty() {
             // This deletes outdated blob txs from the blob store, based on the account's nonce.
@@ -1007,9 +1207,98 @@ where
         });
     }
 
+    /// Records the events of a newly added transaction in the transaction history.
+    fn record_added(&self, added: &AddedTransaction<T::Transaction>) {
+        self.with_history(|history, timestamp| {
+            let (transaction, replaced, subpool) = match added {
+                AddedTransaction::Pending(pending) => {
+                    for tx in &pending.promoted {
+                        history.record_tx(
+                            tx,
+                            TransactionHistoryEvent::Moved { subpool: SubPool::Pending },
+                            timestamp,
+                        );
+                    }
+                    for tx in &pending.discarded {
+                        history.record_tx(
+                            tx,
+                            TransactionHistoryEvent::Discarded {
+                                reason: DiscardReason::Invalidated,
+                            },
+                            timestamp,
+                        );
+                    }
+                    (&pending.transaction, &pending.replaced, SubPool::Pending)
+                }
+                AddedTransaction::Parked { transaction, replaced, subpool, .. } => {
+                    (transaction, replaced, *subpool)
+                }
+            };
+            history.record_tx(transaction, TransactionHistoryEvent::Added { subpool }, timestamp);
+            if let Some(replaced) = replaced {
+                history.record_tx(
+                    replaced,
+                    TransactionHistoryEvent::Replaced { replaced_by: *transaction.hash() },
+                    timestamp,
+                );
+            }
+        });
+    }
+
+    /// Records the transactions promoted to the pending pool in the transaction history.
+    fn record_promoted(&self, promoted: &[Arc<ValidPoolTransaction<T::Transaction>>]) {
+        if promoted.is_empty() {
+            return
+        }
+        self.with_history(|history, timestamp| {
+            for tx in promoted {
+                history.record_tx(
+                    tx,
+                    TransactionHistoryEvent::Moved { subpool: SubPool::Pending },
+                    timestamp,
+                );
+            }
+        });
+    }
+
+    /// Records the transactions demoted from the pending pool in the transaction history.
+    fn record_demoted(&self, demoted: &[(Arc<ValidPoolTransaction<T::Transaction>>, SubPool)]) {
+        if demoted.is_empty() {
+            return
+        }
+        self.with_history(|history, timestamp| {
+            for (tx, subpool) in demoted {
+                history.record_tx(
+                    tx,
+                    TransactionHistoryEvent::Moved { subpool: *subpool },
+                    timestamp,
+                );
+            }
+        });
+    }
+
+    /// Records the discarded transactions in the transaction history.
+    fn record_discarded(
+        &self,
+        discarded: &[Arc<ValidPoolTransaction<T::Transaction>>],
+        reason: DiscardReason,
+    ) {
+        if discarded.is_empty() {
+            return
+        }
+        self.with_history(|history, timestamp| {
+            for tx in discarded {
+                history.record_tx(tx, TransactionHistoryEvent::Discarded { reason }, timestamp);
+            }
+        });
+    }
+
     /// Returns an iterator that yields transactions that are ready to be included in the block.
-    pub fn best_transactions(&self) -> BestTransactions<T> {
-        self.get_pool_data().best_transactions()
+    pub fn best_transactions(
+        &self,
+    ) -> Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T::Transaction>>>>
+    {
+        self.pool.best_transactions()
     }
 
     /// Returns an iterator that yields transactions that are ready to be included in the block with
@@ -1019,7 +1308,7 @@ where
         best_transactions_attributes: BestTransactionsAttributes,
     ) -> Box<dyn crate::traits::BestTransactions<Item = Arc<ValidPoolTransaction<T::Transaction>>>>
     {
-        self.get_pool_data().best_transactions_with_attributes(best_transactions_attributes)
+        self.pool.best_transactions_with_attributes(best_transactions_attributes)
     }
 
     /// Returns only the first `max` transactions in the pending pool.
@@ -1027,31 +1316,49 @@ where
         &self,
         max: usize,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().pending_transactions_iter().take(max).collect()
+        let mut out = Vec::new();
+        for shard in self.pool.shards() {
+            let remaining = max - out.len();
+            if remaining == 0 {
+                break
+            }
+            out.extend(shard.read().pending_transactions_iter().take(remaining));
+        }
+        out
     }
 
     /// Returns all transactions from the pending sub-pool
     pub fn pending_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().pending_transactions()
+        self.pool.pending_transactions()
     }
 
     /// Returns all transactions from parked pools
     pub fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().queued_transactions()
+        self.pool.queued_transactions()
+    }
+
+    /// Returns the number of transactions in the pending and the parked sub-pools.
+    pub fn pending_and_queued_transactions_count(&self) -> (usize, usize) {
+        self.pool.pending_and_queued_transactions_count()
     }
 
     /// Returns all transactions in the pool
     pub fn all_transactions(&self) -> AllPoolTransactions<T::Transaction> {
-        let pool = self.get_pool_data();
-        AllPoolTransactions {
-            pending: pool.pending_transactions(),
-            queued: pool.queued_transactions(),
+        let mut all = AllPoolTransactions::default();
+        for shard in self.pool.read_all() {
+            all.pending.extend(shard.pending_transactions());
+            all.queued.extend(shard.queued_transactions());
         }
+        all
     }
 
     /// Returns _all_ transactions in the pool
     pub fn all_transaction_hashes(&self) -> Vec<TxHash> {
-        self.get_pool_data().all().transactions_iter().map(|tx| *tx.hash()).collect()
+        let mut hashes = Vec::new();
+        for shard in self.pool.shards() {
+            hashes.extend(shard.read().all().transactions_iter().map(|tx| *tx.hash()));
+        }
+        hashes
     }
 
     /// Removes and returns all matching transactions from the pool.
@@ -1065,9 +1372,10 @@ where
         if hashes.is_empty() {
             return Vec::new()
         }
-        let removed = self.pool.write().remove_transactions(hashes);
+        let removed = self.pool.remove_transactions(hashes);
 
         self.with_event_listener(|listener| listener.discarded_many(&removed));
+        self.record_discarded(&removed, DiscardReason::Removed);
 
         removed
     }
@@ -1081,13 +1389,14 @@ where
         if hashes.is_empty() {
             return Vec::new()
         }
-        let removed = self.pool.write().remove_transactions_and_descendants(hashes);
+        let removed = self.pool.remove_transactions_and_descendants(hashes);
 
         self.with_event_listener(|listener| {
             for tx in &removed {
                 listener.discarded(tx.hash());
             }
         });
+        self.record_discarded(&removed, DiscardReason::Removed);
 
         removed
     }
@@ -1098,9 +1407,10 @@ where
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
-        let removed = self.pool.write().remove_transactions_by_sender(sender_id);
+        let removed = self.pool.remove_transactions_by_sender(sender_id);
 
         self.with_event_listener(|listener| listener.discarded_many(&removed));
+        self.record_discarded(&removed, DiscardReason::Removed);
 
         removed
     }
@@ -1117,7 +1427,7 @@ where
             return Vec::new()
         }
 
-        self.pool.write().prune_transactions(hashes)
+        self.pool.prune_transactions(hashes)
     }
 
     /// Retains only transactions that are not present in the pool.
@@ -1128,8 +1438,8 @@ where
         if announcement.is_empty() {
             return
         }
-        let pool = self.get_pool_data();
-        announcement.retain_by_hash(|tx| !pool.contains(tx))
+        let shards = self.pool.read_all();
+        announcement.retain_by_hash(|tx| !shards.iter().any(|pool| pool.contains(tx)))
     }
 
     /// Retains only transactions that are present in the pool.
@@ -1140,13 +1450,13 @@ where
         if announcement.is_empty() {
             return
         }
-        let pool = self.get_pool_data();
-        announcement.retain_by_hash(|tx| pool.contains(tx))
+        let shards = self.pool.read_all();
+        announcement.retain_by_hash(|tx| shards.iter().any(|pool| pool.contains(tx)))
     }
 
     /// Returns the transaction by hash.
     pub fn get(&self, tx_hash: &TxHash) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().get(tx_hash)
+        self.pool.get(tx_hash)
     }
 
     /// Returns all transactions of the address
@@ -1155,7 +1465,7 @@ where
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
-        self.get_pool_data().get_transactions_by_sender(sender_id)
+        self.pool.sender_shard(sender_id).read().get_transactions_by_sender(sender_id)
     }
 
     /// Returns a pending transaction sent by the given sender with the given nonce.
@@ -1165,7 +1475,10 @@ where
         nonce: u64,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
         let sender_id = self.sender_id(&sender)?;
-        self.get_pool_data().get_pending_transaction_by_sender_and_nonce(sender_id, nonce)
+        self.pool
+            .sender_shard(sender_id)
+            .read()
+            .get_pending_transaction_by_sender_and_nonce(sender_id, nonce)
     }
 
     /// Returns all queued transactions of the address by sender
@@ -1174,7 +1487,7 @@ where
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
-        self.get_pool_data().queued_txs_by_sender(sender_id)
+        self.pool.sender_shard(sender_id).read().queued_txs_by_sender(sender_id)
     }
 
     /// Returns all pending transactions filtered by predicate
@@ -1182,7 +1495,7 @@ where
         &self,
         predicate: impl FnMut(&ValidPoolTransaction<T::Transaction>) -> bool,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().pending_transactions_with_predicate(predicate)
+        self.pool.pending_transactions_with_predicate(predicate)
     }
 
     /// Returns all pending transactions of the address by sender
@@ -1191,7 +1504,7 @@ where
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
-        self.get_pool_data().pending_txs_by_sender(sender_id)
+        self.pool.sender_shard(sender_id).read().pending_txs_by_sender(sender_id)
     }
 
     /// Returns the highest transaction of the address
@@ -1200,7 +1513,7 @@ where
         sender: Address,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
         let sender_id = self.sender_id(&sender)?;
-        self.get_pool_data().get_highest_transaction_by_sender(sender_id)
+        self.pool.sender_shard(sender_id).read().get_highest_transaction_by_sender(sender_id)
     }
 
     /// Returns the transaction with the highest nonce that is executable given the on chain nonce.
@@ -1210,7 +1523,7 @@ where
         on_chain_nonce: u64,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
         let sender_id = self.sender_id(&sender)?;
-        self.get_pool_data().get_highest_consecutive_transaction_by_sender(
+        self.pool.sender_shard(sender_id).read().get_highest_consecutive_transaction_by_sender(
             sender_id.into_transaction_id(on_chain_nonce),
         )
     }
@@ -1220,7 +1533,12 @@ where
         &self,
         transaction_id: &TransactionId,
     ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().all().get(transaction_id).map(|tx| tx.transaction.clone())
+        self.pool
+            .sender_shard(transaction_id.sender)
+            .read()
+            .all()
+            .get(transaction_id)
+            .map(|tx| tx.transaction.clone())
     }
 
     /// Returns all transactions that where submitted with the given [`TransactionOrigin`]
@@ -1228,12 +1546,13 @@ where
         &self,
         origin: TransactionOrigin,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data()
-            .all()
-            .transactions_iter()
-            .filter(|tx| tx.origin == origin)
-            .cloned()
-            .collect()
+        let mut txs = Vec::new();
+        for shard in self.pool.shards() {
+            txs.extend(
+                shard.read().all().transactions_iter().filter(|tx| tx.origin == origin).cloned(),
+            );
+        }
+        txs
     }
 
     /// Returns all pending transactions filtered by [`TransactionOrigin`]
@@ -1241,7 +1560,7 @@ where
         &self,
         origin: TransactionOrigin,
     ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
-        self.get_pool_data().pending_transactions_iter().filter(|tx| tx.origin == origin).collect()
+        self.pool.pending_transactions_with_predicate(|tx| tx.origin == origin)
     }
 
     /// Returns all the transactions belonging to the hashes.
@@ -1251,7 +1570,8 @@ where
         if txs.is_empty() {
             return Vec::new()
         }
-        self.get_pool_data().get_all(txs).collect()
+        let shards = self.pool.read_all();
+        txs.into_iter().filter_map(|tx| shards.iter().find_map(|pool| pool.get(&tx))).collect()
     }
 
     /// Returns all the transactions belonging to the hashes that are propagatable.
@@ -1264,8 +1584,11 @@ where
         if txs.is_empty() {
             return Vec::new()
         }
-        let pool = self.get_pool_data();
-        txs.iter().filter_map(|tx| pool.get(tx).filter(|tx| tx.propagate)).collect()
+        let shards = self.pool.read_all();
+        txs.iter()
+            .filter_map(|tx| shards.iter().find_map(|pool| pool.get(tx)))
+            .filter(|tx| tx.propagate)
+            .collect()
     }
 
     /// Notify about propagated transactions.
@@ -1280,17 +1603,17 @@ where
 
     /// Number of transactions in the entire pool
     pub fn len(&self) -> usize {
-        self.get_pool_data().len()
+        self.pool.len()
     }
 
     /// Whether the pool is empty
     pub fn is_empty(&self) -> bool {
-        self.get_pool_data().is_empty()
+        self.pool.is_empty()
     }
 
     /// Returns whether or not the pool is over its configured size and transaction count limits.
     pub fn is_exceeded(&self) -> bool {
-        self.pool.read().is_exceeded()
+        self.pool.is_exceeded()
     }
 
     /// Inserts a blob transaction into the blob store
@@ -1313,6 +1636,13 @@ where
         let _ = self.blob_store.delete_all(txs);
     }
 
+    /// Hands the blob transactions of finalized blocks to the blob store.
+    pub fn finalize_blobs(&self, blocks: Vec<(BlockNumber, Vec<TxHash>)>) {
+        if let Err(err) = self.blob_store.on_finalized_blocks(blocks) {
+            warn!(target: "txpool", %err, "failed to finalize blobs");
+        }
+    }
+
     /// Cleans up the blob store
     pub fn cleanup_blobs(&self) {
         let stat = self.blob_store.cleanup();
@@ -1347,6 +1677,21 @@ impl<V, T: TransactionOrdering, S> fmt::Debug for PoolInner<V, T, S> {
     }
 }
 
+/// A validated transaction that is ready to be inserted into the pool.
+#[derive(Debug)]
+struct PreparedTransaction<T: PoolTransaction> {
+    /// The transaction to insert.
+    transaction: ValidPoolTransaction<T>,
+    /// The sender's on-chain balance.
+    balance: U256,
+    /// The sender's on-chain nonce.
+    state_nonce: u64,
+    /// The sender's on-chain code hash.
+    bytecode_hash: Option<B256>,
+    /// The blob sidecar, if this is a blob transaction.
+    blob_sidecar: Option<BlobTransactionSidecarVariant>,
+}
+
 /// Metadata for a transaction that was added to the pool.
 ///
 /// This holds all the data needed to complete post-insertion operations (notifications,
@@ -1627,6 +1972,8 @@ pub(crate) struct OnNewCanonicalStateOutcome<T: PoolTransaction> {
     pub(crate) promoted: Vec<Arc<ValidPoolTransaction<T>>>,
     /// transaction that were discarded during the update
     pub(crate) discarded: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// Transactions demoted from the pending pool to the given subpool.
+    pub(crate) demoted: Vec<(Arc<ValidPoolTransaction<T>>, SubPool)>,
 }
 
 impl<T: PoolTransaction> OnNewCanonicalStateOutcome<T> {
@@ -26,7 +26,9 @@ use tokio::sync::broadcast;
 #[derive(Debug, Clone)]
 pub struct PendingPool<T: TransactionOrdering> {
     /// How to order transactions.
-    ordering: T,
+    ///
+    /// Shared with the other shards of a `ShardedPool`.
+    ordering: Arc<T>,
     /// Keeps track of transactions inserted in the pool.
     ///
     /// This way we can determine when transactions were submitted to the pool.
@@ -58,6 +60,11 @@ impl<T: TransactionOrdering> PendingPool<T> {
 
     /// Create a new pool instance with the given buffer capacity.
     pub fn with_buffer(ordering: T, buffer_capacity: usize) -> Self {
+        Self::with_shared_ordering(Arc::new(ordering), buffer_capacity)
+    }
+
+    /// Create a new pool instance that uses the given shared ordering.
+    pub(crate) fn with_shared_ordering(ordering: Arc<T>, buffer_capacity: usize) -> Self {
         let (new_transaction_notifier, _) = broadcast::channel(buffer_capacity);
         Self {
             ordering,
@@ -0,0 +1,592 @@
+//! A transaction pool that is split into shards by sender.
+
+use crate::{
+    error::InvalidPoolTransactionError,
+    identifier::SenderId,
+    metrics::{AllTransactionsMetrics, TxPoolMetrics},
+    pool::{
+        txpool::{SenderInfo, SharedSenderState, TxPool, TxTypeCounts},
+        update::UpdateOutcome,
+        OnNewCanonicalStateOutcome,
+    },
+    traits::{BestTransactions, BestTransactionsAttributes, BlockInfo, PoolSize},
+    PoolConfig, PoolUpdateKind, Priority, TransactionOrdering, ValidPoolTransaction,
+};
+use alloy_primitives::{map::AddressSet, TxHash};
+use parking_lot::{RwLock, RwLockReadGuard};
+use rustc_hash::FxHashMap;
+use std::{fmt, sync::Arc};
+
+/// Boxed [`BestTransactions`] iterator of a shard.
+type BoxedBestTransactions<T> = Box<
+    dyn BestTransactions<Item = Arc<ValidPoolTransaction<<T as TransactionOrdering>::Transaction>>>,
+>;
+
+/// A pool that is split into [`TxPool`] shards by sender.
+///
+/// All transactions of a sender are held by the same shard, so everything that concerns a single
+/// sender, such as inserting a transaction, replacing it or moving it between sub-pools, only
+/// requires the lock of that sender's shard. Operations that concern the entire pool, such as
+/// canonical state changes, are applied to every shard, one at a time.
+///
+/// The sub-pool limits are split evenly across the shards, see [`PoolConfig::shards`].
+///
+/// With a single shard, this behaves exactly like a [`TxPool`] behind a single lock.
+pub(crate) struct ShardedPool<T: TransactionOrdering> {
+    /// The shards of the pool, indexed by [`SenderId::shard`].
+    shards: Box<[RwLock<TxPool<T>>]>,
+    /// How to order transactions, shared by all shards.
+    ordering: Arc<T>,
+    /// Metrics for all shards combined.
+    ///
+    /// The size and transaction type gauges are only reported here if there is more than one
+    /// shard, otherwise the shard reports them itself.
+    metrics: TxPoolMetrics,
+    /// All transactions metrics for all shards combined, only used if there is more than one
+    /// shard.
+    all_transactions_metrics: AllTransactionsMetrics,
+}
+
+// === impl ShardedPool ===
+
+impl<T: TransactionOrdering> ShardedPool<T> {
+    /// Creates a new pool with [`PoolConfig::shards`] shards.
+    pub(crate) fn new(ordering: T, config: PoolConfig) -> Self {
+        let num_shards = config.shards.max(1);
+        let ordering = Arc::new(ordering);
+        let shared = SharedSenderState::default();
+        let shard_config = config.shard_config();
+        let shards = (0..num_shards)
+            .map(|_| {
+                RwLock::new(TxPool::new_shard(
+                    ordering.clone(),
+                    shared.clone(),
+                    shard_config.clone(),
+                    num_shards == 1,
+                ))
+            })
+            .collect();
+
+        Self {
+            shards,
+            ordering,
+            metrics: Default::default(),
+            all_transactions_metrics: Default::default(),
+        }
+    }
+
+    /// Returns the number of shards.
+    pub(crate) fn num_shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the index of the shard that holds the transactions of the sender.
+    pub(crate) fn shard_index(&self, sender: SenderId) -> usize {
+        sender.shard(self.shards.len())
+    }
+
+    /// Returns the shard at the given index.
+    pub(crate) fn shard(&self, index: usize) -> &RwLock<TxPool<T>> {
+        &self.shards[index]
+    }
+
+    /// Returns the shard that holds the transactions of the sender.
+    pub(crate) fn sender_shard(&self, sender: SenderId) -> &RwLock<TxPool<T>> {
+        self.shard(self.shard_index(sender))
+    }
+
+    /// Returns an iterator over all shards.
+    pub(crate) fn shards(&self) -> impl Iterator<Item = &RwLock<TxPool<T>>> + '_ {
+        self.shards.iter()
+    }
+
+    /// Acquires the read lock of all shards, in order.
+    ///
+    /// This provides a consistent view of the entire pool, but blocks writers of all shards for
+    /// as long as the guards are held.
+    pub(crate) fn read_all(&self) -> Vec<RwLockReadGuard<'_, TxPool<T>>> {
+        self.shards.iter().map(|shard| shard.read()).collect()
+    }
+
+    /// Returns stats about the size of the pool.
+    pub(crate) fn size(&self) -> PoolSize {
+        let mut size = PoolSize::default();
+        for shard in self.shards() {
+            size += shard.read().size();
+        }
+        size
+    }
+
+    /// Returns the currently tracked block.
+    ///
+    /// All shards track the same block.
+    pub(crate) fn block_info(&self) -> BlockInfo {
+        self.shards[0].read().block_info()
+    }
+
+    /// Sets the currently tracked block of all shards.
+    pub(crate) fn set_block_info(&self, info: BlockInfo) -> UpdateOutcome<T::Transaction> {
+        let mut outcome = UpdateOutcome::default();
+        for shard in self.shards() {
+            outcome.extend(shard.write().set_block_info(info));
+        }
+        self.update_metrics();
+        outcome
+    }
+
+    /// Updates all shards after a new block was mined.
+    pub(crate) fn on_canonical_state_change(
+        &self,
+        block_info: BlockInfo,
+        mined_transactions: Vec<TxHash>,
+        changed_senders: FxHashMap<SenderId, SenderInfo>,
+        update_kind: PoolUpdateKind,
+    ) -> OnNewCanonicalStateOutcome<T::Transaction> {
+        let mut changed_senders = self.partition_senders(changed_senders);
+        let mut outcome = OnNewCanonicalStateOutcome {
+            block_hash: block_info.last_seen_block_hash,
+            mined: Vec::new(),
+            promoted: Vec::new(),
+            discarded: Vec::new(),
+            demoted: Vec::new(),
+        };
+
+        let last = self.shards.len() - 1;
+        let mut mined_transactions = Some(mined_transactions);
+        for (index, shard) in self.shards().enumerate() {
+            // mined transactions can belong to any shard
+            let mined = if index == last {
+                mined_transactions.take().unwrap_or_default()
+            } else {
+                mined_transactions.clone().unwrap_or_default()
+            };
+            let shard_outcome = shard.write().on_canonical_state_change(
+                block_info,
+                mined,
+                std::mem::take(&mut changed_senders[index]),
+                update_kind,
+            );
+            outcome.mined = shard_outcome.mined;
+            outcome.promoted.extend(shard_outcome.promoted);
+            outcome.discarded.extend(shard_outcome.discarded);
+            outcome.demoted.extend(shard_outcome.demoted);
+        }
+
+        self.update_metrics();
+        self.metrics.performed_state_updates.increment(1);
+        outcome
+    }
+
+    /// Updates the transactions of the changed senders.
+    pub(crate) fn update_accounts(
+        &self,
+        changed_senders: FxHashMap<SenderId, SenderInfo>,
+    ) -> UpdateOutcome<T::Transaction> {
+        let mut outcome = UpdateOutcome::default();
+        for (index, changed_senders) in
+            self.partition_senders(changed_senders).into_iter().enumerate()
+        {
+            if !changed_senders.is_empty() {
+                outcome.extend(self.shards[index].write().update_accounts(changed_senders));
+            }
+        }
+        self.update_metrics();
+        outcome
+    }
+
+    /// Splits the changed senders by shard.
+    fn partition_senders(
+        &self,
+        changed_senders: FxHashMap<SenderId, SenderInfo>,
+    ) -> Vec<FxHashMap<SenderId, SenderInfo>> {
+        let mut partitioned = vec![FxHashMap::default(); self.shards.len()];
+        for (sender, info) in changed_senders {
+            partitioned[self.shard_index(sender)].insert(sender, info);
+        }
+        partitioned
+    }
+
+    /// Applies the given removal to every shard and returns all removed transactions.
+    ///
+    /// The transactions can belong to any shard, so every shard is given all hashes.
+    fn remove_from_all(
+        &self,
+        hashes: Vec<TxHash>,
+        mut remove: impl FnMut(
+            &mut TxPool<T>,
+            Vec<TxHash>,
+        ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let mut removed = Vec::new();
+        let last = self.shards.len() - 1;
+        let mut hashes = Some(hashes);
+        for (index, shard) in self.shards().enumerate() {
+            let hashes =
+                if index == last { hashes.take() } else { hashes.clone() }.unwrap_or_default();
+            removed.extend(remove(&mut *shard.write(), hashes));
+        }
+        self.update_metrics();
+        removed
+    }
+
+    /// Removes and returns all matching transactions from the pool.
+    pub(crate) fn remove_transactions(
+        &self,
+        hashes: Vec<TxHash>,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.remove_from_all(hashes, TxPool::remove_transactions)
+    }
+
+    /// Removes and returns all matching transactions and their descendants from the pool.
+    pub(crate) fn remove_transactions_and_descendants(
+        &self,
+        hashes: Vec<TxHash>,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.remove_from_all(hashes, TxPool::remove_transactions_and_descendants)
+    }
+
+    /// Prunes and returns all matching transactions from the pool.
+    pub(crate) fn prune_transactions(
+        &self,
+        hashes: Vec<TxHash>,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.remove_from_all(hashes, TxPool::prune_transactions)
+    }
+
+    /// Removes all transactions from the given sender.
+    pub(crate) fn remove_transactions_by_sender(
+        &self,
+        sender: SenderId,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let removed = self.sender_shard(sender).write().remove_transactions_by_sender(sender);
+        self.update_metrics();
+        removed
+    }
+
+    /// Returns all senders in the pool.
+    pub(crate) fn unique_senders(&self) -> AddressSet {
+        let mut senders = AddressSet::default();
+        for shard in self.shards() {
+            senders.extend(shard.read().unique_senders());
+        }
+        senders
+    }
+
+    /// Returns `true` if the transaction with the given hash is in the pool.
+    pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
+        self.shards().any(|shard| shard.read().contains(tx_hash))
+    }
+
+    /// Returns the transaction for the given hash.
+    pub(crate) fn get(
+        &self,
+        tx_hash: &TxHash,
+    ) -> Option<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.shards().find_map(|shard| shard.read().get(tx_hash))
+    }
+
+    /// Returns an iterator that yields transactions that are ready to be included in the block
+    /// with the tracked fees.
+    pub(crate) fn best_transactions(&self) -> BoxedBestTransactions<T> {
+        if let [shard] = &*self.shards {
+            return Box::new(shard.read().best_transactions())
+        }
+        let base_fee = self.block_info().pending_basefee;
+        self.merge_best(base_fee, |pool| Box::new(pool.best_transactions()))
+    }
+
+    /// Returns an iterator that yields transactions that are ready to be included in the block
+    /// with the given base fee and optional blob fee.
+    pub(crate) fn best_transactions_with_attributes(
+        &self,
+        best_transactions_attributes: BestTransactionsAttributes,
+    ) -> BoxedBestTransactions<T> {
+        if let [shard] = &*self.shards {
+            return shard.read().best_transactions_with_attributes(best_transactions_attributes)
+        }
+        self.merge_best(best_transactions_attributes.basefee, |pool| {
+            pool.best_transactions_with_attributes(best_transactions_attributes)
+        })
+    }
+
+    /// Merges the best transactions of all shards into a single iterator.
+    fn merge_best(
+        &self,
+        base_fee: u64,
+        mut best: impl FnMut(&TxPool<T>) -> BoxedBestTransactions<T>,
+    ) -> BoxedBestTransactions<T> {
+        let shards = self.shards().map(|shard| best(&shard.read())).collect::<Vec<_>>();
+        Box::new(ShardedBestTransactions {
+            ordering: self.ordering.clone(),
+            base_fee,
+            heads: (0..shards.len()).map(|_| None).collect(),
+            shards,
+        })
+    }
+
+    /// Returns all transactions from the pending sub-pools.
+    pub(crate) fn pending_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.shards().flat_map(|shard| shard.read().pending_transactions()).collect()
+    }
+
+    /// Returns all transactions from the parked sub-pools.
+    pub(crate) fn queued_transactions(&self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.shards().flat_map(|shard| shard.read().queued_transactions()).collect()
+    }
+
+    /// Returns the number of transactions in the pending and the parked sub-pools.
+    pub(crate) fn pending_and_queued_transactions_count(&self) -> (usize, usize) {
+        self.shards().fold((0, 0), |(pending, queued), shard| {
+            let shard = shard.read();
+            (
+                pending + shard.pending_transactions_count(),
+                queued + shard.queued_transactions_count(),
+            )
+        })
+    }
+
+    /// Returns all pending transactions filtered by predicate.
+    pub(crate) fn pending_transactions_with_predicate(
+        &self,
+        mut predicate: impl FnMut(&ValidPoolTransaction<T::Transaction>) -> bool,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.shards()
+            .flat_map(|shard| shard.read().pending_transactions_with_predicate(&mut predicate))
+            .collect()
+    }
+
+    /// Number of transactions in the entire pool.
+    pub(crate) fn len(&self) -> usize {
+        self.shards().map(|shard| shard.read().len()).sum()
+    }
+
+    /// Whether the pool is empty.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.shards().all(|shard| shard.read().is_empty())
+    }
+
+    /// Returns whether any shard is over its configured limits.
+    pub(crate) fn is_exceeded(&self) -> bool {
+        self.shards().any(|shard| shard.read().is_exceeded())
+    }
+
+    /// Updates the size and transaction type gauges of all shards combined.
+    ///
+    /// This is a noop for a single shard, which reports the gauges itself.
+    pub(crate) fn update_metrics(&self) {
+        if self.shards.len() == 1 {
+            return
+        }
+
+        let mut size = PoolSize::default();
+        let mut type_counts = TxTypeCounts::default();
+        for shard in self.shards() {
+            let shard = shard.read();
+            size += shard.size();
+            type_counts.merge(&shard.tx_type_counts());
+        }
+
+        self.metrics.record_pool_size(&size);
+        type_counts.record(&self.metrics);
+        self.all_transactions_metrics.all_transactions_by_hash.set(size.total as f64);
+        self.all_transactions_metrics.all_transactions_by_id.set(size.total as f64);
+    }
+
+    /// Asserts the invariants of all shards.
+    #[cfg(test)]
+    pub(crate) fn assert_invariants(&self) {
+        for shard in self.shards() {
+            shard.read().assert_invariants();
+        }
+    }
+}
+
+impl<T: TransactionOrdering> fmt::Debug for ShardedPool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedPool").field("shards", &self.shards.len()).finish_non_exhaustive()
+    }
+}
+
+/// The next transaction of a shard, with its priority.
+type Head<T> = (
+    Priority<<T as TransactionOrdering>::PriorityValue>,
+    Arc<ValidPoolTransaction<<T as TransactionOrdering>::Transaction>>,
+);
+
+/// Merges the best transactions of all shards of a [`ShardedPool`].
+///
+/// This keeps the next transaction of every shard and always yields the one with the highest
+/// priority, ties are broken in favor of the transaction that was submitted first.
+struct ShardedBestTransactions<T: TransactionOrdering> {
+    /// How to order transactions.
+    ordering: Arc<T>,
+    /// The base fee the priorities are computed with.
+    base_fee: u64,
+    /// The best transactions of every shard, indexed by [`SenderId::shard`].
+    shards: Vec<BoxedBestTransactions<T>>,
+    /// The next transaction of every shard, if already taken from the shard's iterator.
+    heads: Vec<Option<Head<T>>>,
+}
+
+impl<T: TransactionOrdering> ShardedBestTransactions<T> {
+    /// Fills the heads of all shards that don't have one.
+    fn fill_heads(&mut self) {
+        for (head, best) in self.heads.iter_mut().zip(&mut self.shards) {
+            if head.is_none() {
+                *head = best
+                    .next()
+                    .map(|tx| (self.ordering.priority(&tx.transaction, self.base_fee), tx));
+            }
+        }
+    }
+}
+
+impl<T: TransactionOrdering> Iterator for ShardedBestTransactions<T> {
+    type Item = Arc<ValidPoolTransaction<T::Transaction>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.fill_heads();
+        let (index, _) = self
+            .heads
+            .iter()
+            .enumerate()
+            .filter_map(|(index, head)| head.as_ref().map(|head| (index, head)))
+            .max_by(|(_, (a_priority, a)), (_, (b_priority, b))| {
+                a_priority.cmp(b_priority).then_with(|| b.timestamp.cmp(&a.timestamp))
+            })?;
+        self.heads[index].take().map(|(_, tx)| tx)
+    }
+}
+
+impl<T: TransactionOrdering> BestTransactions for ShardedBestTransactions<T> {
+    fn mark_invalid(&mut self, tx: &Self::Item, kind: InvalidPoolTransactionError) {
+        let index = tx.sender_id().shard(self.shards.len());
+        // the head of the sender's shard may be a descendant of the transaction
+        if self.heads[index].as_ref().is_some_and(|(_, head)| head.sender_id() == tx.sender_id()) {
+            self.heads[index] = None;
+        }
+        self.shards[index].mark_invalid(tx, kind)
+    }
+
+    fn no_updates(&mut self) {
+        self.shards.iter_mut().for_each(|best| best.no_updates());
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        if skip_blobs {
+            for head in &mut self.heads {
+                if head.as_ref().is_some_and(|(_, tx)| tx.is_eip4844()) {
+                    *head = None;
+                }
+            }
+        }
+        self.shards.iter_mut().for_each(|best| best.set_skip_blobs(skip_blobs));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockOrdering, MockTransaction, MockTransactionFactory};
+    use alloy_primitives::U256;
+
+    fn sharded_pool(shards: usize) -> ShardedPool<MockOrdering> {
+        ShardedPool::new(MockOrdering::default(), PoolConfig::default().with_shards(shards))
+    }
+
+    #[test]
+    fn senders_are_spread_across_shards() {
+        let pool = sharded_pool(4);
+        let mut f = MockTransactionFactory::default();
+
+        for _ in 0..8 {
+            let tx = f.validated(MockTransaction::eip1559());
+            let sender = tx.sender_id();
+            pool.sender_shard(sender).write().add_transaction(tx, U256::MAX, 0, None).unwrap();
+        }
+
+        assert_eq!(pool.len(), 8);
+        assert_eq!(pool.size().pending, 8);
+        for shard in pool.shards() {
+            assert_eq!(shard.read().len(), 2);
+        }
+        pool.assert_invariants();
+    }
+
+    #[test]
+    fn best_transactions_are_merged_by_priority() {
+        let pool = sharded_pool(3);
+        let mut f = MockTransactionFactory::default();
+
+        for priority in [5u128, 50, 20, 40, 10, 30] {
+            let tx = f.validated(
+                MockTransaction::eip1559().with_max_fee(1_000).with_priority_fee(priority),
+            );
+            let sender = tx.sender_id();
+            pool.sender_shard(sender).write().add_transaction(tx, U256::MAX, 0, None).unwrap();
+        }
+
+        let priorities = pool
+            .best_transactions()
+            .map(|tx| tx.transaction.get_priority_fee().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(priorities, vec![50, 40, 30, 20, 10, 5]);
+    }
+
+    #[test]
+    fn mark_invalid_skips_descendants_across_merge() {
+        let pool = sharded_pool(2);
+        let mut f = MockTransactionFactory::default();
+
+        let tx = MockTransaction::eip1559().with_max_fee(1_000).with_priority_fee(100);
+        let first = f.validated(tx.clone());
+        let second = f.validated(tx.next());
+        let other =
+            f.validated(MockTransaction::eip1559().with_max_fee(1_000).with_priority_fee(1));
+        for tx in [first, second, other] {
+            let sender = tx.sender_id();
+            pool.sender_shard(sender).write().add_transaction(tx, U256::MAX, 0, None).unwrap();
+        }
+
+        let mut best = pool.best_transactions();
+        let next = best.next().unwrap();
+        assert_eq!(next.nonce(), 0);
+        best.mark_invalid(&next, InvalidPoolTransactionError::Underpriced);
+
+        let remaining = best.collect::<Vec<_>>();
+        assert_eq!(remaining.len(), 1);
+        assert_ne!(remaining[0].sender(), next.sender());
+    }
+
+    #[test]
+    fn authorities_are_shared_across_shards() {
+        let pool = sharded_pool(2);
+        let mut f = MockTransactionFactory::default();
+
+        // the authority and the delegating sender land in different shards
+        let authority_tx = MockTransaction::eip1559();
+        let authority = f.validated(authority_tx.clone());
+        let authority_id = authority.sender_id();
+        let mut delegating = f.validated(MockTransaction::eip7702());
+        let delegating_id = delegating.sender_id();
+        assert_ne!(pool.shard_index(authority_id), pool.shard_index(delegating_id));
+
+        pool.sender_shard(authority_id)
+            .write()
+            .add_transaction(authority, U256::MAX, 0, None)
+            .unwrap();
+
+        delegating.authority_ids = Some(vec![authority_id]);
+        pool.sender_shard(delegating_id)
+            .write()
+            .add_transaction(delegating, U256::MAX, 0, None)
+            .unwrap();
+
+        // the authorization pooled in the other shard limits the in-flight transactions of the
+        // authority
+        let next = f.validated(authority_tx.next());
+        let res = pool.sender_shard(authority_id).write().add_transaction(next, U256::MAX, 0, None);
+        assert!(res.is_err());
+        pool.assert_invariants();
+    }
+}
@@ -35,13 +35,14 @@ use alloy_primitives::{
     map::{AddressSet, B256Map, B256Set},
     TxHash, B256,
 };
-use rustc_hash::FxHashMap;
+use dashmap::DashMap;
+use rustc_hash::{FxBuildHasher, FxHashMap};
 use smallvec::SmallVec;
 #[cfg(test)]
 use std::collections::{HashMap, HashSet};
 use std::{
     cmp::Ordering,
-    collections::{btree_map::Entry, hash_map, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap},
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
@@ -120,6 +121,11 @@ pub struct TxPool<T: TransactionOrdering> {
     all_transactions: AllTransactions<T::Transaction>,
     /// Transaction pool metrics
     metrics: TxPoolMetrics,
+    /// Whether this pool reports the size and transaction type gauges.
+    ///
+    /// This is disabled for the shards of a `ShardedPool` with more than one shard, which reports
+    /// the gauges for all shards combined.
+    report_gauges: bool,
 }
 
 // === impl TxPool ===
@@ -127,17 +133,31 @@ pub struct TxPool<T: TransactionOrdering> {
 impl<T: TransactionOrdering> TxPool<T> {
     /// Create a new graph pool instance.
     pub fn new(ordering: T, config: PoolConfig) -> Self {
+        Self::new_shard(Arc::new(ordering), Default::default(), config, true)
+    }
+
+    /// Create a new pool instance that is one shard of a `ShardedPool`.
+    ///
+    /// All shards share the same ordering and [`SharedSenderState`]. If `report_gauges` is false,
+    /// the size and transaction type gauges are left to the `ShardedPool`.
+    pub(crate) fn new_shard(
+        ordering: Arc<T>,
+        shared: SharedSenderState,
+        config: PoolConfig,
+        report_gauges: bool,
+    ) -> Self {
         Self {
-            pending_pool: PendingPool::with_buffer(
+            pending_pool: PendingPool::with_shared_ordering(
                 ordering,
                 config.max_new_pending_txs_notifications,
             ),
             queued_pool: Default::default(),
             basefee_pool: Default::default(),
             blob_pool: Default::default(),
-            all_transactions: AllTransactions::new(&config),
+            all_transactions: AllTransactions::new(&config, shared),
             config,
             metrics: Default::default(),
+            report_gauges,
         }
     }
 
@@ -555,14 +575,6 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.by_hash.get(tx_hash).cloned()
     }
 
-    /// Returns transactions for the multiple given hashes, if they exist.
-    pub(crate) fn get_all(
-        &self,
-        txs: Vec<TxHash>,
-    ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T::Transaction>>> + '_ {
-        txs.into_iter().filter_map(|tx| self.get(&tx))
-    }
-
     /// Returns all transactions sent from the given sender.
     pub(crate) fn get_transactions_by_sender(
         &self,
@@ -697,40 +709,36 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.set_block_info(block_info);
 
         self.update_transaction_type_metrics();
-        self.metrics.performed_state_updates.increment(1);
 
         OnNewCanonicalStateOutcome {
             block_hash,
             mined: mined_transactions,
             promoted: outcome.promoted,
             discarded: outcome.discarded,
+            demoted: outcome.demoted,
         }
     }
 
     /// Update sub-pools size metrics.
     pub(crate) fn update_size_metrics(&self) {
+        if !self.report_gauges {
+            return
+        }
         self.all_transactions.update_size_metrics();
-        let stats = self.size();
-        self.metrics.pending_pool_transactions.set(stats.pending as f64);
-        self.metrics.pending_pool_size_bytes.set(stats.pending_size as f64);
-        self.metrics.basefee_pool_transactions.set(stats.basefee as f64);
-        self.metrics.basefee_pool_size_bytes.set(stats.basefee_size as f64);
-        self.metrics.queued_pool_transactions.set(stats.queued as f64);
-        self.metrics.queued_pool_size_bytes.set(stats.queued_size as f64);
-        self.metrics.blob_pool_transactions.set(stats.blob as f64);
-        self.metrics.blob_pool_size_bytes.set(stats.blob_size as f64);
-        self.metrics.total_transactions.set(stats.total as f64);
+        self.metrics.record_pool_size(&self.size());
     }
 
     /// Updates transaction type metrics for the entire pool.
     pub(crate) fn update_transaction_type_metrics(&self) {
-        let counts = &self.all_transactions.tx_type_counts;
-        self.metrics.total_legacy_transactions.set(counts.legacy as f64);
-        self.metrics.total_eip2930_transactions.set(counts.eip2930 as f64);
-        self.metrics.total_eip1559_transactions.set(counts.eip1559 as f64);
-        self.metrics.total_eip4844_transactions.set(counts.eip4844 as f64);
-        self.metrics.total_eip7702_transactions.set(counts.eip7702 as f64);
-        self.metrics.total_other_transactions.set(counts.other as f64);
+        if !self.report_gauges {
+            return
+        }
+        self.all_transactions.tx_type_counts.record(&self.metrics);
+    }
+
+    /// Returns the number of transactions in the pool by transaction type.
+    pub(crate) const fn tx_type_counts(&self) -> TxTypeCounts {
+        self.all_transactions.tx_type_counts
     }
 
     pub(crate) fn add_transaction(
@@ -767,14 +775,16 @@ impl<T: TransactionOrdering> TxPool<T> {
                 let (promoted, discarded) = match split {
                     // All updates are lower-nonce — promote them first, then add new tx
                     None => {
-                        let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+                        let UpdateOutcome { promoted, discarded, .. } =
+                            self.process_updates(updates);
                         self.add_new_transaction(transaction.clone(), replaced_tx.clone(), move_to);
                         (promoted, discarded)
                     }
                     // All updates are higher-nonce — add new tx first, then promote
                     Some(0) => {
                         self.add_new_transaction(transaction.clone(), replaced_tx.clone(), move_to);
-                        let UpdateOutcome { promoted, discarded } = self.process_updates(updates);
+                        let UpdateOutcome { promoted, discarded, .. } =
+                            self.process_updates(updates);
                         (promoted, discarded)
                     }
                     // Mixed — split and interleave
@@ -882,7 +892,7 @@ impl<T: TransactionOrdering> TxPool<T> {
     ) -> Result<(), PoolError> {
         // Short circuit if the sender has neither delegation nor pending delegation.
         if (on_chain_code_hash.is_none() || on_chain_code_hash == Some(KECCAK_EMPTY)) &&
-            !self.all_transactions.auths.contains_key(&transaction.sender_id())
+            !self.all_transactions.shared.has_auths(transaction.sender_id())
         {
             return Ok(())
         }
@@ -950,11 +960,11 @@ impl<T: TransactionOrdering> TxPool<T> {
         if let Some(authority_list) = &transaction.authority_ids {
             for sender_id in authority_list {
                 // Ensure authority does not exceed the configured inflight delegation slot limit.
-                if self
-                    .all_transactions
-                    .txs_iter(*sender_id)
-                    .nth(self.config.max_inflight_delegated_slot_limit)
-                    .is_some()
+                //
+                // The authority may belong to another shard, so this is looked up in the shared
+                // sender state.
+                if self.all_transactions.shared.tx_count(*sender_id) >
+                    self.config.max_inflight_delegated_slot_limit
                 {
                     return Err(PoolError::new(
                         *transaction.hash(),
@@ -987,11 +997,13 @@ impl<T: TransactionOrdering> TxPool<T> {
                 Destination::Pool(move_to) => {
                     debug_assert_ne!(&move_to, &current, "destination must be different");
                     let moved = self.move_transaction(current, move_to, &id);
-                    if matches!(move_to, SubPool::Pending) &&
-                        let Some(tx) = moved
-                    {
-                        trace!(target: "txpool", hash=%tx.transaction.hash(), "Promoted transaction to pending");
-                        outcome.promoted.push(tx);
+                    if let Some(tx) = moved {
+                        if move_to.is_pending() {
+                            trace!(target: "txpool", hash=%tx.transaction.hash(), "Promoted transaction to pending");
+                            outcome.promoted.push(tx);
+                        } else if current.is_pending() {
+                            outcome.demoted.push((tx, move_to));
+                        }
                     }
                 }
             }
@@ -1382,8 +1394,8 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Contains the currently known information about the senders.
     sender_info: FxHashMap<SenderId, SenderInfo>,
-    /// Tracks the number of transactions by sender that are currently in the pool.
-    tx_counter: FxHashMap<SenderId, usize>,
+    /// Per sender transaction counts and pooled authorizations, shared by all shards.
+    shared: SharedSenderState,
     /// The current block number the pool keeps track of.
     last_seen_block_number: u64,
     /// The current block hash the pool keeps track of.
@@ -1394,8 +1406,6 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     price_bumps: PriceBumpConfig,
     /// How to handle [`TransactionOrigin::Local`](crate::TransactionOrigin) transactions.
     local_transactions_config: LocalTransactionConfig,
-    /// All accounts with a pooled authorization
-    auths: FxHashMap<SenderId, B256Set>,
     /// Number of transactions in the pool by transaction type, tracked incrementally so metrics
     /// updates don't require iterating the entire pool.
     tx_type_counts: TxTypeCounts,
@@ -1405,8 +1415,9 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
 
 impl<T: PoolTransaction> AllTransactions<T> {
     /// Create a new instance
-    fn new(config: &PoolConfig) -> Self {
+    fn new(config: &PoolConfig, shared: SharedSenderState) -> Self {
         Self {
+            shared,
             max_account_slots: config.max_account_slots,
             price_bumps: config.price_bumps,
             local_transactions_config: config.local_transactions_config.clone(),
@@ -1441,24 +1452,17 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
     /// Increments the transaction counter for the sender
     pub(crate) fn tx_inc(&mut self, sender: SenderId) {
-        let count = self.tx_counter.entry(sender).or_default();
-        *count += 1;
+        self.shared.tx_inc(sender);
         self.metrics.all_transactions_by_all_senders.increment(1.0);
     }
 
     /// Decrements the transaction counter for the sender
     pub(crate) fn tx_decr(&mut self, sender: SenderId) {
-        if let hash_map::Entry::Occupied(mut entry) = self.tx_counter.entry(sender) {
-            let count = entry.get_mut();
-            if *count == 1 {
-                entry.remove();
-                self.sender_info.remove(&sender);
-                self.metrics.all_transactions_by_all_senders.decrement(1.0);
-                return
-            }
-            *count -= 1;
-            self.metrics.all_transactions_by_all_senders.decrement(1.0);
+        let Some(remaining) = self.shared.tx_decr(sender) else { return };
+        if remaining == 0 {
+            self.sender_info.remove(&sender);
         }
+        self.metrics.all_transactions_by_all_senders.decrement(1.0);
     }
 
     /// Updates the block specific info
@@ -1818,12 +1822,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
 
         let tx_hash = tx.transaction.hash();
         for auth in auths {
-            if let Some(list) = self.auths.get_mut(auth) {
-                list.remove(tx_hash);
-                if list.is_empty() {
-                    self.auths.remove(auth);
-                }
-            }
+            self.shared.remove_auth(*auth, tx_hash);
         }
     }
 
@@ -1853,8 +1852,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         on_chain_nonce: u64,
     ) -> Result<ValidPoolTransaction<T>, InsertErr<T>> {
         if !self.local_transactions_config.is_local(transaction.origin, transaction.sender_ref()) {
-            let current_txs =
-                self.tx_counter.get(&transaction.sender_id()).copied().unwrap_or_default();
+            let current_txs = self.shared.tx_count(transaction.sender_id());
 
             // Reject transactions if sender's capacity is exceeded.
             // If transaction's nonce matches on-chain nonce always let it through
@@ -2079,7 +2077,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         if let Some(auths) = &transaction.authority_ids {
             let tx_hash = transaction.hash();
             for auth in auths {
-                self.auths.entry(*auth).or_default().insert(*tx_hash);
+                self.shared.insert_auth(*auth, *tx_hash);
             }
         }
 
@@ -2172,7 +2170,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     #[cfg(any(test, feature = "test-utils"))]
     pub(crate) fn assert_invariants(&self) {
         assert_eq!(self.by_hash.len(), self.txs.len(), "by_hash.len() != txs.len()");
-        assert!(self.auths.len() <= self.txs.len(), "auths.len() > txs.len()");
+        assert!(self.shared.auths_len() <= self.shared.total_tx_count(), "auths.len() > txs.len()");
     }
 }
 
@@ -2182,7 +2180,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     ///
     /// If there are no transactions for the given sender, it returns zero by default.
     pub(crate) fn tx_count(&self, sender: SenderId) -> usize {
-        self.tx_counter.get(&sender).copied().unwrap_or_default()
+        self.shared.tx_count(sender)
     }
 }
 
@@ -2195,19 +2193,95 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
             by_hash: Default::default(),
             txs: Default::default(),
             sender_info: Default::default(),
-            tx_counter: Default::default(),
+            shared: Default::default(),
             last_seen_block_number: Default::default(),
             last_seen_block_hash: Default::default(),
             pending_fees: Default::default(),
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
-            auths: Default::default(),
             tx_type_counts: Default::default(),
             metrics: Default::default(),
         }
     }
 }
 
+/// Sender state that is shared by all shards of a
+/// [`ShardedPool`](crate::pool::sharded::ShardedPool).
+///
+/// Transactions are assigned to shards by sender, so most sender state is local to a shard.
+/// EIP-7702 authorizations however reference accounts that may be tracked by any shard, which is
+/// why the transaction counts by sender and the pooled authorizations are kept here.
+///
+/// Updates of a sender's entries only happen while the sender's shard is locked for writing, but
+/// reads from other shards are not synchronized with them: a check against an authority tracked by
+/// a different shard observes a consistent value for that authority, not a snapshot of the entire
+/// pool.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SharedSenderState {
+    /// Tracks the number of transactions by sender that are currently in the pool.
+    tx_counts: Arc<DashMap<SenderId, usize, FxBuildHasher>>,
+    /// All accounts with a pooled authorization
+    auths: Arc<DashMap<SenderId, B256Set, FxBuildHasher>>,
+}
+
+impl SharedSenderState {
+    /// Returns the number of transactions of the sender in the pool.
+    pub(crate) fn tx_count(&self, sender: SenderId) -> usize {
+        self.tx_counts.get(&sender).map(|count| *count).unwrap_or_default()
+    }
+
+    /// Increments the transaction counter for the sender.
+    fn tx_inc(&self, sender: SenderId) {
+        *self.tx_counts.entry(sender).or_default() += 1;
+    }
+
+    /// Decrements the transaction counter for the sender.
+    ///
+    /// Returns the remaining number of transactions of the sender, or `None` if the sender is not
+    /// tracked.
+    fn tx_decr(&self, sender: SenderId) -> Option<usize> {
+        let remaining = {
+            let mut count = self.tx_counts.get_mut(&sender)?;
+            *count -= 1;
+            *count
+        };
+        if remaining == 0 {
+            self.tx_counts.remove_if(&sender, |_, count| *count == 0);
+        }
+        Some(remaining)
+    }
+
+    /// Returns `true` if there's a pooled authorization for the account.
+    fn has_auths(&self, sender: SenderId) -> bool {
+        self.auths.contains_key(&sender)
+    }
+
+    /// Tracks the authorization of the account by the given transaction.
+    fn insert_auth(&self, auth: SenderId, tx_hash: TxHash) {
+        self.auths.entry(auth).or_default().insert(tx_hash);
+    }
+
+    /// Removes the authorization of the account by the given transaction.
+    fn remove_auth(&self, auth: SenderId, tx_hash: &TxHash) {
+        self.auths.remove_if_mut(&auth, |_, hashes| {
+            hashes.remove(tx_hash);
+            hashes.is_empty()
+        });
+    }
+
+    /// Returns the number of accounts with a pooled authorization.
+    #[cfg(any(test, feature = "test-utils"))]
+    fn auths_len(&self) -> usize {
+        self.auths.len()
+    }
+
+    /// Returns the number of transactions of all senders.
+    #[cfg(any(test, feature = "test-utils"))]
+    fn total_tx_count(&self) -> usize {
+        self.tx_counts.iter().map(|count| *count).sum()
+    }
+}
+
 /// Number of transactions in the pool grouped by transaction type.
 ///
 /// Maintained incrementally on insert/remove so that metrics updates don't require iterating
@@ -2244,6 +2318,26 @@ impl TxTypeCounts {
     const fn dec(&mut self, tx_type: u8) {
         *self.counter_mut(tx_type) -= 1;
     }
+
+    /// Adds the counts of another pool to this one.
+    pub(crate) const fn merge(&mut self, other: &Self) {
+        self.legacy += other.legacy;
+        self.eip2930 += other.eip2930;
+        self.eip1559 += other.eip1559;
+        self.eip4844 += other.eip4844;
+        self.eip7702 += other.eip7702;
+        self.other += other.other;
+    }
+
+    /// Sets the transaction type gauges.
+    pub(crate) fn record(&self, metrics: &TxPoolMetrics) {
+        metrics.total_legacy_transactions.set(self.legacy as f64);
+        metrics.total_eip2930_transactions.set(self.eip2930 as f64);
+        metrics.total_eip1559_transactions.set(self.eip1559 as f64);
+        metrics.total_eip4844_transactions.set(self.eip4844 as f64);
+        metrics.total_eip7702_transactions.set(self.eip7702 as f64);
+        metrics.total_other_transactions.set(self.other as f64);
+    }
 }
 
 /// Represents updated fees for the pending block.
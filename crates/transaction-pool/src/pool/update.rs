@@ -40,10 +40,21 @@ pub struct UpdateOutcome<T: PoolTransaction> {
     pub promoted: Vec<Arc<ValidPoolTransaction<T>>>,
     /// transaction that failed and were discarded
     pub discarded: Vec<Arc<ValidPoolTransaction<T>>>,
+    /// transactions demoted from the pending pool to the given subpool
+    pub demoted: Vec<(Arc<ValidPoolTransaction<T>>, SubPool)>,
 }
 
 impl<T: PoolTransaction> Default for UpdateOutcome<T> {
     fn default() -> Self {
-        Self { promoted: vec![], discarded: vec![] }
+        Self { promoted: vec![], discarded: vec![], demoted: vec![] }
+    }
+}
+
+impl<T: PoolTransaction> UpdateOutcome<T> {
+    /// Appends the changes of another outcome to this one.
+    pub(crate) fn extend(&mut self, other: Self) {
+        self.promoted.extend(other.promoted);
+        self.discarded.extend(other.discarded);
+        self.demoted.extend(other.demoted);
     }
 }
@@ -1392,6 +1392,7 @@ impl MockTransactionFactory {
             timestamp: Instant::now(),
             origin,
             authority_ids: None,
+            conditional: None,
         }
     }
 
@@ -52,10 +52,11 @@
 
 use crate::{
     blobstore::{BlobStore, BlobStoreError},
+    conditional::{TransactionConditional, TransactionConditionalError},
     error::{InvalidPoolTransactionError, PoolError, PoolResult, RawPoolTransactionError},
     pool::{
-        state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
-        TransactionListenerKind,
+        history::TransactionHistory, state::SubPool, BestTransactionFilter, NewTransactionEvent,
+        TransactionEvents, TransactionListenerKind,
     },
     validate::{TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction},
     AddedTransactionOutcome, AllTransactionsEvents,
@@ -73,7 +74,7 @@ use alloy_eips::{
 };
 use alloy_primitives::{
     map::{AddressSet, B256Map},
-    Address, Bytes, TxHash, TxKind, B128, B256, U256,
+    Address, BlockNumber, Bytes, TxHash, TxKind, B128, B256, U256,
 };
 use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
@@ -192,6 +193,38 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         transactions: Vec<(TransactionOrigin, Self::Transaction)>,
     ) -> impl Future<Output = Vec<PoolResult<AddedTransactionOutcome>>> + Send;
 
+    /// Adds an _unvalidated_ transaction into the pool that must only be included in blocks that
+    /// satisfy the given [ERC-7796](https://eips.ethereum.org/EIPS/eip-7796) conditions.
+    ///
+    /// The conditions are stored on the [`ValidPoolTransaction`] and the transaction is evicted
+    /// once they can no longer be satisfied.
+    ///
+    /// By default, conditional transactions are not supported and rejected.
+    ///
+    /// Consumer: RPC
+    fn add_conditional_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+        conditional: TransactionConditional,
+    ) -> impl Future<Output = PoolResult<AddedTransactionOutcome>> + Send {
+        let _ = (origin, conditional);
+        let hash = *transaction.hash();
+        futures_util::future::ready(Err(PoolError::other(
+            hash,
+            TransactionConditionalError::Unsupported,
+        )))
+    }
+
+    /// Returns all transactions that were added with
+    /// [ERC-7796](https://eips.ethereum.org/EIPS/eip-7796) conditions, see
+    /// [`Self::add_conditional_transaction`].
+    ///
+    /// Consumer: Pool maintenance
+    fn conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        Vec::new()
+    }
+
     /// Submit a consensus transaction directly to the pool
     fn add_consensus_transaction(
         &self,
@@ -236,6 +269,26 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Returns a new transaction change event stream for _all_ transactions in the pool.
     fn all_transactions_event_listener(&self) -> AllTransactionsEvents<Self::Transaction>;
 
+    /// Returns the recorded lifecycle events of the given transaction.
+    ///
+    /// Unlike [`Self::transaction_event_listener`], the history is kept after the transaction was
+    /// removed from the pool, for a bounded number of transactions, see
+    /// [`PoolConfig::max_history_transactions`](crate::PoolConfig::max_history_transactions).
+    ///
+    /// Returns `None` by default, for pools that don't record a history.
+    fn transaction_history(&self, tx_hash: TxHash) -> Option<TransactionHistory> {
+        let _ = tx_hash;
+        None
+    }
+
+    /// Returns the recorded lifecycle events of all transactions of the given sender.
+    ///
+    /// See also [`Self::transaction_history`].
+    fn sender_transaction_history(&self, sender: Address) -> Vec<TransactionHistory> {
+        let _ = sender;
+        Vec::new()
+    }
+
     /// Returns a new Stream that yields transactions hashes for new __pending__ transactions
     /// inserted into the pool that are allowed to be propagated.
     ///
@@ -430,6 +483,39 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         self.best_transactions().find(|tx| tx.sender() == sender && tx.nonce() == nonce)
     }
 
+    /// Returns transactions for an EIP-7805 inclusion list whose encoded size doesn't exceed
+    /// `max_bytes`.
+    ///
+    /// Only the next executable transaction of every sender is considered, since the inclusion
+    /// list is enforced against the state after the block. Transactions that have been waiting in
+    /// the pool the longest are picked first. Blob transactions are never included.
+    ///
+    /// Consumer: Engine API
+    #[cfg(feature = "focil")]
+    fn inclusion_list_transactions(
+        &self,
+        base_fee: u64,
+        max_bytes: usize,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        let mut senders = AddressSet::default();
+        let mut candidates = self
+            .best_transactions_with_attributes(BestTransactionsAttributes::base_fee(base_fee))
+            .filter(|tx| !tx.is_eip4844() && senders.insert(tx.sender()))
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|tx| tx.timestamp);
+
+        let mut size = 0;
+        candidates.retain(|tx| {
+            let len = tx.encoded_length();
+            if size + len > max_bytes {
+                return false
+            }
+            size += len;
+            true
+        });
+        candidates
+    }
+
     /// Returns first `max` transactions that can be included in the next block.
     /// See <https://github.com/paradigmxyz/reth/issues/12767#issuecomment-2493223579>
     ///
@@ -795,6 +881,10 @@ pub trait TransactionPoolExt: TransactionPool {
     /// Deletes multiple blob sidecars from the blob store
     fn delete_blobs(&self, txs: Vec<B256>);
 
+    /// Hands the blob transactions of finalized blocks to the blob store, which deletes their
+    /// sidecars or retains them by block, see [`BlobStore::on_finalized_blocks`].
+    fn finalize_blobs(&self, blocks: Vec<(BlockNumber, Vec<B256>)>);
+
     /// Maintenance function to cleanup blobs that are no longer needed.
     fn cleanup_blobs(&self);
 }
@@ -1822,6 +1912,20 @@ impl PoolSize {
     }
 }
 
+impl std::ops::AddAssign for PoolSize {
+    fn add_assign(&mut self, other: Self) {
+        self.pending += other.pending;
+        self.pending_size += other.pending_size;
+        self.blob += other.blob;
+        self.blob_size += other.blob_size;
+        self.basefee += other.basefee;
+        self.basefee_size += other.basefee_size;
+        self.queued += other.queued;
+        self.queued_size += other.queued_size;
+        self.total += other.total;
+    }
+}
+
 /// Represents the current status of the pool.
 #[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
 pub struct BlockInfo {
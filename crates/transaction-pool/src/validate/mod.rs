@@ -1,6 +1,7 @@
 //! Transaction validation abstractions.
 
 use crate::{
+    conditional::TransactionConditional,
     error::InvalidPoolTransactionError,
     identifier::{SenderId, TransactionId},
     traits::{PoolTransaction, TransactionOrigin},
@@ -296,6 +297,9 @@ pub struct ValidPoolTransaction<T: PoolTransaction> {
     pub origin: TransactionOrigin,
     /// The sender ids of the 7702 transaction authorities.
     pub authority_ids: Option<Vec<SenderId>>,
+    /// The [ERC-7796](https://eips.ethereum.org/EIPS/eip-7796) conditions of the transaction, if
+    /// it was submitted with any.
+    pub conditional: Option<Box<TransactionConditional>>,
 }
 
 // === impl ValidPoolTransaction ===
@@ -502,6 +506,7 @@ impl<T: PoolTransaction> Clone for ValidPoolTransaction<T> {
             timestamp: self.timestamp,
             origin: self.origin,
             authority_ids: self.authority_ids.clone(),
+            conditional: self.conditional.clone(),
         }
     }
 }
@@ -514,6 +519,7 @@ impl<T: PoolTransaction> fmt::Debug for ValidPoolTransaction<T> {
             .field("origin", &self.origin)
             .field("hash", self.transaction.hash())
             .field("tx", &self.transaction)
+            .field("conditional", &self.conditional)
             .finish()
     }
 }
@@ -0,0 +1,31 @@
+use reth_transaction_pool::{
+    conditional::TransactionConditional,
+    test_utils::{MockTransaction, TestPoolBuilder},
+    TransactionOrigin, TransactionPool,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tracks_conditional_transactions() {
+    let txpool = TestPoolBuilder::default();
+
+    let conditional = MockTransaction::eip1559();
+    let regular = MockTransaction::eip1559();
+    txpool
+        .add_conditional_transaction(
+            TransactionOrigin::External,
+            conditional.clone(),
+            TransactionConditional { block_number_max: Some(100), ..Default::default() },
+        )
+        .await
+        .unwrap();
+    txpool.add_transaction(TransactionOrigin::External, regular).await.unwrap();
+
+    let tracked = txpool.conditional_transactions();
+    assert_eq!(tracked.len(), 1);
+    assert_eq!(tracked[0].hash(), conditional.get_hash());
+    assert_eq!(tracked[0].conditional.as_ref().unwrap().block_number_max, Some(100));
+
+    // removed transactions are no longer tracked
+    txpool.remove_transactions(vec![*conditional.get_hash()]);
+    assert!(txpool.conditional_transactions().is_empty());
+}
@@ -0,0 +1,24 @@
+use reth_transaction_pool::{
+    test_utils::{MockTransaction, TestPoolBuilder},
+    TransactionOrigin, TransactionPool,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn inclusion_list_picks_next_transaction_of_each_sender() {
+    let txpool = TestPoolBuilder::default();
+
+    let first = MockTransaction::eip1559();
+    let first_next = first.next();
+    let second = MockTransaction::eip1559();
+    let blob = MockTransaction::eip4844();
+    for tx in [&first, &first_next, &second, &blob] {
+        txpool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+    }
+
+    let base_fee = txpool.block_info().pending_basefee;
+    let inclusion_list = txpool.inclusion_list_transactions(base_fee, usize::MAX);
+    let hashes = inclusion_list.iter().map(|tx| *tx.hash()).collect::<Vec<_>>();
+
+    // oldest transactions first, one per sender and no blob transactions
+    assert_eq!(hashes, vec![*first.get_hash(), *second.get_hash()]);
+}
@@ -1,9 +1,10 @@
 use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
+    pool::history::{DiscardReason, TransactionHistoryEvent},
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, PoolTransaction, TransactionEvent, TransactionListenerKind,
-    TransactionOrigin, TransactionPool,
+    FullTransactionEvent, PoolConfig, PoolTransaction, SubPool, TransactionEvent,
+    TransactionListenerKind, TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -238,3 +239,45 @@ async fn txpool_listener_blob_sidecar() {
     let inserted = listener_blob.recv().await.unwrap();
     assert_eq!(*inserted.tx_hash, expected);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_transaction_history() {
+    let txpool = TestPoolBuilder::default()
+        .with_config(PoolConfig { max_history_transactions: 100, ..Default::default() });
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let mut gapped = mock_tx_factory.create_eip1559();
+    let mut first = mock_tx_factory.create_eip1559();
+    let sender = *first.transaction.get_sender();
+    gapped.transaction.set_sender(sender);
+    gapped.transaction.set_nonce(1);
+    first.transaction.set_nonce(0);
+
+    txpool.add_transaction(TransactionOrigin::External, gapped.transaction.clone()).await.unwrap();
+    txpool.add_transaction(TransactionOrigin::External, first.transaction.clone()).await.unwrap();
+    txpool.remove_transactions(vec![*gapped.transaction.hash()]);
+
+    // the history is kept after the transaction was removed
+    let history = txpool.transaction_history(*gapped.transaction.hash()).unwrap();
+    assert_eq!(history.sender, Some(sender));
+    assert_eq!(
+        history.events.into_iter().map(|record| record.event).collect::<Vec<_>>(),
+        vec![
+            TransactionHistoryEvent::Added { subpool: SubPool::Queued },
+            TransactionHistoryEvent::Moved { subpool: SubPool::Pending },
+            TransactionHistoryEvent::Discarded { reason: DiscardReason::Removed },
+        ]
+    );
+
+    let sender_history = txpool.sender_transaction_history(sender);
+    assert_eq!(sender_history.len(), 2);
+
+    // rejected gossip doesn't create new histories
+    let mut invalid = mock_tx_factory.create_eip1559();
+    invalid.transaction.set_sender(sender);
+    invalid.transaction.set_nonce(0);
+    assert!(txpool
+        .add_transaction(TransactionOrigin::External, invalid.transaction.clone())
+        .await
+        .is_err());
+    assert!(txpool.transaction_history(*invalid.transaction.hash()).is_none());
+}
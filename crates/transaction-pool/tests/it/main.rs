@@ -3,7 +3,11 @@
 #[cfg(feature = "test-utils")]
 mod blobs;
 #[cfg(feature = "test-utils")]
+mod conditional;
+#[cfg(feature = "test-utils")]
 mod evict;
+#[cfg(all(feature = "test-utils", feature = "focil"))]
+mod inclusion_list;
 #[cfg(feature = "test-utils")]
 mod listeners;
 #[cfg(feature = "test-utils")]
@@ -0,0 +1,487 @@
+//! Types for the experimental [EIP-7864](https://eips.ethereum.org/EIPS/eip-7864) binary state
+//! tree.
+//!
+//! The binary tree is a research prototype that is computed alongside the Merkle Patricia Trie,
+//! which stays canonical. It is derived from
+//! [`HashedPostStateSorted`](crate::HashedPostStateSorted) and therefore keyed by hashed addresses
+//! and hashed storage slots, since the hashed state does not carry their preimages. Code chunks are
+//! not part of the tree and the code size is always zero in the basic data leaf. The resulting
+//! roots are comparable between reth nodes, but not with the roots of other EIP-7864
+//! implementations.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use alloy_consensus::constants::KECCAK_EMPTY;
+use alloy_primitives::{BlockNumber, FixedBytes, B256, U256};
+use reth_primitives_traits::Account;
+use sha2::{Digest, Sha256};
+
+/// The stem of a binary tree key, shared by up to [`STEM_SUBTREE_WIDTH`] values.
+pub type Stem = FixedBytes<31>;
+
+/// Number of values stored under a single stem.
+pub const STEM_SUBTREE_WIDTH: usize = 256;
+
+/// Number of bits in a [`Stem`], the maximum depth of an internal node.
+pub const STEM_BITS: u8 = 248;
+
+/// Sub-index of the basic data leaf in the account stem.
+pub const BASIC_DATA_LEAF_KEY: u8 = 0;
+
+/// Sub-index of the code hash leaf in the account stem.
+pub const CODE_HASH_LEAF_KEY: u8 = 1;
+
+/// Offset of the storage slots that are stored in the account stem.
+pub const HEADER_STORAGE_OFFSET: u8 = 64;
+
+/// Offset of the code chunks in the account stem.
+pub const CODE_OFFSET: u8 = 128;
+
+/// Key of a value in the binary tree.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "serde"), serde(rename_all = "camelCase"))]
+pub struct BinaryTreeKey {
+    /// The stem the value is stored under.
+    pub stem: Stem,
+    /// The index of the value within the stem.
+    pub sub_index: u8,
+}
+
+impl BinaryTreeKey {
+    /// Derives the key of the value at `sub_index` of the tree with index `tree_index` belonging to
+    /// `address`.
+    pub fn new(address: B256, tree_index: U256, sub_index: u8) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(address);
+        hasher.update(tree_index.to_le_bytes::<32>());
+        let digest = hasher.finalize();
+        Self { stem: Stem::from_slice(&digest[..31]), sub_index }
+    }
+
+    /// Returns the key of the basic data leaf of the account.
+    pub fn basic_data(address: B256) -> Self {
+        Self::new(address, U256::ZERO, BASIC_DATA_LEAF_KEY)
+    }
+
+    /// Returns the key of the code hash leaf of the account.
+    pub fn code_hash(address: B256) -> Self {
+        Self::new(address, U256::ZERO, CODE_HASH_LEAF_KEY)
+    }
+
+    /// Returns the key of the storage slot of the account.
+    pub fn storage_slot(address: B256, slot: B256) -> Self {
+        let slot = U256::from_be_bytes(slot.0);
+        let header_slots = U256::from(CODE_OFFSET - HEADER_STORAGE_OFFSET);
+        if slot < header_slots {
+            return Self::new(address, U256::ZERO, HEADER_STORAGE_OFFSET + slot.to::<u8>())
+        }
+
+        // `MAIN_STORAGE_OFFSET` is `256^31`, a multiple of the stem width.
+        let tree_index = (U256::from(1) << 240) + (slot >> 8);
+        Self::new(address, tree_index, slot.byte(0))
+    }
+}
+
+/// Encodes the basic data leaf of an account.
+///
+/// The layout is `version (1) | reserved (4) | code size (3) | nonce (8) | balance (16)`.
+pub fn basic_data_leaf(account: &Account) -> B256 {
+    let mut leaf = B256::ZERO;
+    leaf[8..16].copy_from_slice(&account.nonce.to_be_bytes());
+    leaf[16..].copy_from_slice(&account.balance.to_be_bytes::<32>()[16..]);
+    leaf
+}
+
+/// Returns the code hash leaf of an account.
+pub fn code_hash_leaf(account: &Account) -> B256 {
+    account.bytecode_hash.unwrap_or(KECCAK_EMPTY)
+}
+
+/// Hashes the concatenation of two child hashes. Two empty children hash to zero.
+pub fn hash_pair(left: B256, right: B256) -> B256 {
+    if left.is_zero() && right.is_zero() {
+        return B256::ZERO
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// Hashes a value stored in the tree.
+pub fn hash_value(value: B256) -> B256 {
+    B256::from_slice(&Sha256::digest(value))
+}
+
+/// Hashes a stem node given the root of its values subtree.
+pub fn hash_stem(stem: &Stem, values_root: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(stem);
+    hasher.update([0]);
+    hasher.update(values_root);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// The values stored under a single stem.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct StemValues(BTreeMap<u8, B256>);
+
+impl StemValues {
+    /// Returns the value at the given sub-index.
+    pub fn get(&self, sub_index: u8) -> Option<B256> {
+        self.0.get(&sub_index).copied()
+    }
+
+    /// Sets or, if `value` is `None`, removes the value at the given sub-index.
+    pub fn set(&mut self, sub_index: u8, value: Option<B256>) {
+        match value {
+            Some(value) => self.0.insert(sub_index, value),
+            None => self.0.remove(&sub_index),
+        };
+    }
+
+    /// Returns `true` if no values are stored.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the leaf hashes of the values subtree.
+    fn leaves(&self) -> Vec<B256> {
+        let mut leaves = alloc::vec![B256::ZERO; STEM_SUBTREE_WIDTH];
+        for (&index, &value) in &self.0 {
+            leaves[index as usize] = hash_value(value);
+        }
+        leaves
+    }
+
+    /// Computes the root of the values subtree.
+    pub fn root(&self) -> B256 {
+        let mut level = self.leaves();
+        while level.len() > 1 {
+            level = level.chunks_exact(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+        }
+        level[0]
+    }
+
+    /// Returns the siblings of the value at `sub_index` in the values subtree, bottom-up.
+    pub fn siblings(&self, sub_index: u8) -> Vec<B256> {
+        let mut siblings = Vec::with_capacity(8);
+        let mut level = self.leaves();
+        let mut index = sub_index as usize;
+        while level.len() > 1 {
+            siblings.push(level[index ^ 1]);
+            level = level.chunks_exact(2).map(|pair| hash_pair(pair[0], pair[1])).collect();
+            index /= 2;
+        }
+        siblings
+    }
+
+    /// Encodes the values as a sequence of `sub_index (1) | value (32)` entries.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.0.len() * 33);
+        for (&index, value) in &self.0 {
+            buf.push(index);
+            buf.extend_from_slice(value.as_slice());
+        }
+        buf
+    }
+
+    /// Decodes values encoded with [`Self::encode`].
+    pub fn decode(buf: &[u8]) -> Option<Self> {
+        if !buf.len().is_multiple_of(33) {
+            return None
+        }
+        Some(Self(
+            buf.chunks_exact(33).map(|entry| (entry[0], B256::from_slice(&entry[1..]))).collect(),
+        ))
+    }
+}
+
+/// The path of a node in the binary tree, a prefix of the stems in its subtree.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BinaryNodePath {
+    /// The bits of the path, bits past `len` are zero.
+    bits: Stem,
+    /// The number of bits in the path.
+    len: u8,
+}
+
+impl BinaryNodePath {
+    /// Returns the path of the root node.
+    pub const fn root() -> Self {
+        Self { bits: Stem::ZERO, len: 0 }
+    }
+
+    /// Returns the number of bits in the path.
+    pub const fn len(&self) -> u8 {
+        self.len
+    }
+
+    /// Returns `true` if this is the path of the root node.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the path of the left (`false`) or right (`true`) child.
+    pub fn child(&self, right: bool) -> Self {
+        debug_assert!(self.len < STEM_BITS, "path exceeds stem length");
+        let mut bits = self.bits;
+        if right {
+            bits[self.len as usize / 8] |= 0x80 >> (self.len % 8);
+        }
+        Self { bits, len: self.len + 1 }
+    }
+
+    /// Returns the bit of the stem at the given depth, `true` for a right branch.
+    pub fn stem_bit(stem: &Stem, depth: u8) -> bool {
+        stem[depth as usize / 8] & (0x80 >> (depth % 8)) != 0
+    }
+
+    /// Encodes the path as `len (1) | bits (ceil(len / 8))`.
+    pub fn encode(&self) -> Vec<u8> {
+        let bytes = (self.len as usize).div_ceil(8);
+        let mut buf = Vec::with_capacity(1 + bytes);
+        buf.push(self.len);
+        buf.extend_from_slice(&self.bits[..bytes]);
+        buf
+    }
+
+    /// Decodes a path encoded with [`Self::encode`].
+    pub fn decode(buf: &[u8]) -> Option<Self> {
+        let (&len, rest) = buf.split_first()?;
+        if len > STEM_BITS || rest.len() != (len as usize).div_ceil(8) {
+            return None
+        }
+        let mut bits = Stem::ZERO;
+        bits[..rest.len()].copy_from_slice(rest);
+        Some(Self { bits, len })
+    }
+}
+
+/// A node of the binary tree.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BinaryNode {
+    /// An internal node with the hashes of its children, zero for an empty child.
+    Internal {
+        /// Hash of the left child.
+        left: B256,
+        /// Hash of the right child.
+        right: B256,
+    },
+    /// A stem node, placed at the shortest path that is unique to its stem.
+    Stem {
+        /// The stem of the node.
+        stem: Stem,
+        /// The root of the values subtree.
+        values_root: B256,
+    },
+}
+
+impl BinaryNode {
+    /// Returns the hash of the node.
+    pub fn hash(&self) -> B256 {
+        match self {
+            Self::Internal { left, right } => hash_pair(*left, *right),
+            Self::Stem { stem, values_root } => hash_stem(stem, *values_root),
+        }
+    }
+
+    /// Encodes the node as a tag byte followed by its fields.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(65);
+        match self {
+            Self::Internal { left, right } => {
+                buf.push(0);
+                buf.extend_from_slice(left.as_slice());
+                buf.extend_from_slice(right.as_slice());
+            }
+            Self::Stem { stem, values_root } => {
+                buf.push(1);
+                buf.extend_from_slice(stem.as_slice());
+                buf.extend_from_slice(values_root.as_slice());
+            }
+        }
+        buf
+    }
+
+    /// Decodes a node encoded with [`Self::encode`].
+    pub fn decode(buf: &[u8]) -> Option<Self> {
+        match buf {
+            [0, rest @ ..] if rest.len() == 64 => Some(Self::Internal {
+                left: B256::from_slice(&rest[..32]),
+                right: B256::from_slice(&rest[32..]),
+            }),
+            [1, rest @ ..] if rest.len() == 63 => Some(Self::Stem {
+                stem: Stem::from_slice(&rest[..31]),
+                values_root: B256::from_slice(&rest[31..]),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// The node a [`BinaryTreeProof`] terminates in.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "serde"), serde(rename_all = "camelCase", tag = "type"))]
+pub enum BinaryProofLeaf {
+    /// The path of the key ends in an empty subtree, the key is not in the tree.
+    Empty,
+    /// The path of the key ends in the stem node of the key.
+    Stem {
+        /// The value of the key, `None` if it is not set.
+        value: Option<B256>,
+        /// The siblings of the value in the values subtree, bottom-up.
+        value_siblings: Vec<B256>,
+    },
+    /// The path of the key ends in the stem node of a different stem, the key is not in the
+    /// tree.
+    OtherStem {
+        /// The stem of the node.
+        stem: Stem,
+        /// The root of the values subtree of the node.
+        values_root: B256,
+    },
+}
+
+/// An inclusion or exclusion proof for a single key of the binary tree.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "serde"), serde(rename_all = "camelCase"))]
+pub struct BinaryTreeProof {
+    /// The proven key.
+    pub key: BinaryTreeKey,
+    /// The siblings of the internal nodes along the path of the key, top-down.
+    pub siblings: Vec<B256>,
+    /// The node the path of the key terminates in.
+    pub leaf: BinaryProofLeaf,
+}
+
+impl BinaryTreeProof {
+    /// Returns the proven value of the key.
+    pub const fn value(&self) -> Option<B256> {
+        match &self.leaf {
+            BinaryProofLeaf::Stem { value, .. } => *value,
+            _ => None,
+        }
+    }
+
+    /// Verifies the proof against the given root.
+    pub fn verify(&self, root: B256) -> bool {
+        if self.siblings.len() > STEM_BITS as usize {
+            return false
+        }
+
+        let mut hash = match &self.leaf {
+            BinaryProofLeaf::Empty => B256::ZERO,
+            BinaryProofLeaf::Stem { value, value_siblings } => {
+                if value_siblings.len() != 8 {
+                    return false
+                }
+                let mut hash = value.map(hash_value).unwrap_or_default();
+                let mut index = self.key.sub_index;
+                for sibling in value_siblings {
+                    hash = if index & 1 == 0 {
+                        hash_pair(hash, *sibling)
+                    } else {
+                        hash_pair(*sibling, hash)
+                    };
+                    index >>= 1;
+                }
+                hash_stem(&self.key.stem, hash)
+            }
+            BinaryProofLeaf::OtherStem { stem, values_root } => {
+                // The other stem has to share the path of the key.
+                let depth = self.siblings.len() as u8;
+                if *stem == self.key.stem ||
+                    (0..depth).any(|bit| {
+                        BinaryNodePath::stem_bit(stem, bit) !=
+                            BinaryNodePath::stem_bit(&self.key.stem, bit)
+                    })
+                {
+                    return false
+                }
+                hash_stem(stem, *values_root)
+            }
+        };
+
+        for (depth, sibling) in self.siblings.iter().enumerate().rev() {
+            hash = if BinaryNodePath::stem_bit(&self.key.stem, depth as u8) {
+                hash_pair(*sibling, hash)
+            } else {
+                hash_pair(hash, *sibling)
+            };
+        }
+
+        hash == root
+    }
+}
+
+/// Binary tree proofs of an account and some of its storage slots.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "serde"), serde(rename_all = "camelCase"))]
+pub struct BinaryTreeAccountProof {
+    /// The block the proofs were generated for.
+    pub block_number: BlockNumber,
+    /// The root of the binary tree after the block.
+    pub root: B256,
+    /// Proof of the basic data leaf of the account.
+    pub basic_data: BinaryTreeProof,
+    /// Proof of the code hash leaf of the account.
+    pub code_hash: BinaryTreeProof,
+    /// Proofs of the requested storage slots.
+    pub storage: Vec<BinaryTreeProof>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn storage_slot_keys() {
+        let address = B256::repeat_byte(0x11);
+
+        // Header slots share the stem of the account.
+        let header = BinaryTreeKey::storage_slot(address, B256::with_last_byte(5));
+        assert_eq!(header.stem, BinaryTreeKey::basic_data(address).stem);
+        assert_eq!(header.sub_index, HEADER_STORAGE_OFFSET + 5);
+
+        // Consecutive main storage slots share a stem.
+        let first = BinaryTreeKey::storage_slot(address, B256::with_last_byte(0x80));
+        let second = BinaryTreeKey::storage_slot(address, B256::with_last_byte(0x81));
+        assert_eq!(first.stem, second.stem);
+        assert_ne!(first.stem, header.stem);
+        assert_eq!((first.sub_index, second.sub_index), (0x80, 0x81));
+    }
+
+    #[test]
+    fn basic_data_layout() {
+        let account = Account { nonce: 2, balance: U256::from(3), bytecode_hash: None };
+        assert_eq!(
+            basic_data_leaf(&account),
+            b256!("0x0000000000000000000000000000000200000000000000000000000000000003")
+        );
+        assert_eq!(code_hash_leaf(&account), KECCAK_EMPTY);
+    }
+
+    #[test]
+    fn encoding_roundtrip() {
+        let path = BinaryNodePath::root().child(true).child(false).child(true);
+        assert_eq!(BinaryNodePath::decode(&path.encode()), Some(path));
+
+        let mut values = StemValues::default();
+        values.set(0, Some(B256::repeat_byte(1)));
+        values.set(255, Some(B256::repeat_byte(2)));
+        assert_eq!(StemValues::decode(&values.encode()), Some(values.clone()));
+
+        let node = BinaryNode::Stem { stem: Stem::repeat_byte(3), values_root: values.root() };
+        assert_eq!(BinaryNode::decode(&node.encode()), Some(node));
+    }
+
+    #[test]
+    fn empty_values_root() {
+        assert_eq!(StemValues::default().root(), B256::ZERO);
+    }
+}
@@ -78,6 +78,10 @@ pub mod ordered_root;
 /// Buffer for trie updates.
 pub mod updates;
 
+/// Types for the experimental binary state tree.
+#[cfg(feature = "binary-tree")]
+pub mod binary;
+
 pub mod added_removed_keys;
 
 /// Utilities used by other modules in this crate.
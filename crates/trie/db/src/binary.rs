@@ -0,0 +1,115 @@
+use alloy_primitives::B256;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
+use reth_trie::binary::{BinaryTree, BinaryTreeStore, BinaryTreeStoreMut};
+use reth_trie_common::binary::{
+    basic_data_leaf, code_hash_leaf, BinaryNode, BinaryNodePath, BinaryTreeKey, Stem, StemValues,
+};
+use tracing::info;
+
+/// Number of values applied to the binary tree at once while rebuilding it.
+const REBUILD_BATCH_SIZE: usize = 100_000;
+
+/// [`BinaryTreeStore`] backed by the [`tables::BinaryTreeNodes`] and [`tables::BinaryTreeStems`]
+/// tables.
+#[derive(Debug)]
+pub struct DatabaseBinaryTreeStore<'a, TX> {
+    tx: &'a TX,
+}
+
+impl<'a, TX> DatabaseBinaryTreeStore<'a, TX> {
+    /// Creates a new store over the given transaction.
+    pub const fn new(tx: &'a TX) -> Self {
+        Self { tx }
+    }
+}
+
+impl<TX: DbTx> BinaryTreeStore for DatabaseBinaryTreeStore<'_, TX> {
+    fn node(&self, path: &BinaryNodePath) -> Result<Option<BinaryNode>, DatabaseError> {
+        self.tx
+            .get::<tables::BinaryTreeNodes>(path.encode())?
+            .map(|node| BinaryNode::decode(&node).ok_or(DatabaseError::Decode))
+            .transpose()
+    }
+
+    fn stem_values(&self, stem: &Stem) -> Result<StemValues, DatabaseError> {
+        self.tx
+            .get::<tables::BinaryTreeStems>(stem.to_vec())?
+            .map(|values| StemValues::decode(&values).ok_or(DatabaseError::Decode))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> BinaryTreeStoreMut for DatabaseBinaryTreeStore<'_, TX> {
+    fn set_node(
+        &mut self,
+        path: &BinaryNodePath,
+        node: Option<BinaryNode>,
+    ) -> Result<(), DatabaseError> {
+        match node {
+            Some(node) => self.tx.put::<tables::BinaryTreeNodes>(path.encode(), node.encode()),
+            None => self.tx.delete::<tables::BinaryTreeNodes>(path.encode(), None).map(|_| ()),
+        }
+    }
+
+    fn set_stem_values(&mut self, stem: &Stem, values: &StemValues) -> Result<(), DatabaseError> {
+        if values.is_empty() {
+            self.tx.delete::<tables::BinaryTreeStems>(stem.to_vec(), None).map(|_| ())
+        } else {
+            self.tx.put::<tables::BinaryTreeStems>(stem.to_vec(), values.encode())
+        }
+    }
+}
+
+/// Returns the slots of the account that have a value in the [`tables::HashedStorages`] table.
+pub fn hashed_storage_slots<TX: DbTx>(
+    tx: &TX,
+    hashed_address: B256,
+) -> Result<Vec<B256>, DatabaseError> {
+    tx.cursor_dup_read::<tables::HashedStorages>()?
+        .walk_dup(Some(hashed_address), None)?
+        .map(|entry| entry.map(|(_, entry)| entry.key))
+        .collect()
+}
+
+/// Clears the binary tree tables and rebuilds the tree from the hashed state tables.
+///
+/// This walks the entire hashed state and is only meant for small states, like the genesis
+/// state. Returns the root of the rebuilt tree.
+pub fn rebuild_binary_tree<TX: DbTxMut + DbTx>(tx: &TX) -> Result<B256, DatabaseError> {
+    tx.clear::<tables::BinaryTreeNodes>()?;
+    tx.clear::<tables::BinaryTreeStems>()?;
+
+    let mut tree = BinaryTree::new(DatabaseBinaryTreeStore::new(tx));
+    let mut batch = Vec::with_capacity(REBUILD_BATCH_SIZE);
+
+    info!(target: "trie::binary", "Rebuilding binary tree from hashed accounts");
+    for entry in tx.cursor_read::<tables::HashedAccounts>()?.walk(None)? {
+        let (address, account) = entry?;
+        batch.push((BinaryTreeKey::basic_data(address), Some(basic_data_leaf(&account))));
+        batch.push((BinaryTreeKey::code_hash(address), Some(code_hash_leaf(&account))));
+        if batch.len() >= REBUILD_BATCH_SIZE {
+            tree.insert_values(batch.drain(..))?;
+        }
+    }
+
+    info!(target: "trie::binary", "Rebuilding binary tree from hashed storages");
+    for entry in tx.cursor_dup_read::<tables::HashedStorages>()?.walk(None)? {
+        let (address, entry) = entry?;
+        if entry.value.is_zero() {
+            continue
+        }
+        batch
+            .push((BinaryTreeKey::storage_slot(address, entry.key), Some(B256::from(entry.value))));
+        if batch.len() >= REBUILD_BATCH_SIZE {
+            tree.insert_values(batch.drain(..))?;
+        }
+    }
+
+    tree.insert_values(batch)
+}
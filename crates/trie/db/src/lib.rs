@@ -2,6 +2,10 @@
 
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+#[cfg(feature = "binary-tree")]
+mod binary;
+#[cfg(feature = "binary-tree")]
+pub use binary::{hashed_storage_slots, rebuild_binary_tree, DatabaseBinaryTreeStore};
 mod changesets;
 pub use changesets::*;
 mod hashed_cursor;
@@ -19,7 +19,7 @@ struct LfuEntryMeta {
 ///
 /// Generic over the key type `K`.
 #[derive(Debug)]
-pub(crate) struct BucketedLfu<K> {
+pub struct BucketedLfu<K> {
     capacity: usize,
     /// Maps each key to its frequency and bucket position.
     entries: HashMap<K, LfuEntryMeta>,
@@ -37,7 +37,8 @@ impl<K> Default for BucketedLfu<K> {
 }
 
 impl<K> BucketedLfu<K> {
-    pub(crate) fn new(capacity: usize) -> Self {
+    /// Creates an empty LFU that retains at most `capacity` keys.
+    pub fn new(capacity: usize) -> Self {
         Self {
             capacity,
             entries: HashMap::default(),
@@ -52,7 +53,7 @@ impl<K: fmt::Debug + Copy + Eq + hash::Hash> BucketedLfu<K> {
     ///
     /// Entries accumulate via [`Self::touch`] over time. This method trims the cache
     /// so that only the `capacity` hottest entries are retained.
-    pub(crate) fn decay_and_evict(&mut self, capacity: usize) {
+    pub fn decay_and_evict(&mut self, capacity: usize) {
         self.capacity = capacity;
 
         if self.capacity == 0 {
@@ -104,7 +105,7 @@ impl<K: fmt::Debug + Copy + Eq + hash::Hash> BucketedLfu<K> {
     }
 
     /// Records a key touch. O(1) amortized.
-    pub(crate) fn touch(&mut self, key: K) {
+    pub fn touch(&mut self, key: K) {
         if self.capacity == 0 {
             return;
         }
@@ -151,7 +152,7 @@ impl<K: fmt::Debug + Copy + Eq + hash::Hash> BucketedLfu<K> {
 
     /// Returns an iterator over all retained keys.
     #[cfg(any(test, feature = "std"))]
-    pub(crate) fn keys(&self) -> impl Iterator<Item = &K> {
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
         self.entries.keys()
     }
 }
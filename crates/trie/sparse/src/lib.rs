@@ -12,6 +12,8 @@ pub use state::*;
 
 #[cfg(feature = "std")]
 mod lfu;
+#[cfg(feature = "std")]
+pub use lfu::BucketedLfu;
 
 #[cfg(feature = "std")]
 mod trie;
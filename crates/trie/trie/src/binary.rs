@@ -0,0 +1,482 @@
+use alloy_primitives::{map::HashMap, B256};
+use reth_storage_errors::db::DatabaseError;
+use reth_trie_common::HashedPostStateSorted;
+use std::collections::BTreeMap;
+use tracing::debug;
+
+pub use reth_trie_common::binary::*;
+
+/// Read access to the nodes and stem values of a [`BinaryTree`].
+#[auto_impl::auto_impl(&, &mut, Box)]
+pub trait BinaryTreeStore {
+    /// Returns the node at the given path.
+    fn node(&self, path: &BinaryNodePath) -> Result<Option<BinaryNode>, DatabaseError>;
+
+    /// Returns the values stored under the given stem.
+    fn stem_values(&self, stem: &Stem) -> Result<StemValues, DatabaseError>;
+}
+
+/// Write access to the nodes and stem values of a [`BinaryTree`].
+#[auto_impl::auto_impl(&mut, Box)]
+pub trait BinaryTreeStoreMut: BinaryTreeStore {
+    /// Sets or, if `node` is `None`, removes the node at the given path.
+    fn set_node(
+        &mut self,
+        path: &BinaryNodePath,
+        node: Option<BinaryNode>,
+    ) -> Result<(), DatabaseError>;
+
+    /// Sets the values stored under the given stem, removing the stem if `values` is empty.
+    fn set_stem_values(&mut self, stem: &Stem, values: &StemValues) -> Result<(), DatabaseError>;
+}
+
+/// In-memory [`BinaryTreeStore`] and [`BinaryTreeStoreMut`].
+#[derive(Clone, Default, Debug)]
+pub struct MemoryBinaryTreeStore {
+    nodes: HashMap<BinaryNodePath, BinaryNode>,
+    stems: HashMap<Stem, StemValues>,
+}
+
+impl MemoryBinaryTreeStore {
+    /// Returns `true` if the store contains no nodes and no values.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.stems.is_empty()
+    }
+}
+
+impl BinaryTreeStore for MemoryBinaryTreeStore {
+    fn node(&self, path: &BinaryNodePath) -> Result<Option<BinaryNode>, DatabaseError> {
+        Ok(self.nodes.get(path).copied())
+    }
+
+    fn stem_values(&self, stem: &Stem) -> Result<StemValues, DatabaseError> {
+        Ok(self.stems.get(stem).cloned().unwrap_or_default())
+    }
+}
+
+impl BinaryTreeStoreMut for MemoryBinaryTreeStore {
+    fn set_node(
+        &mut self,
+        path: &BinaryNodePath,
+        node: Option<BinaryNode>,
+    ) -> Result<(), DatabaseError> {
+        match node {
+            Some(node) => self.nodes.insert(*path, node),
+            None => self.nodes.remove(path),
+        };
+        Ok(())
+    }
+
+    fn set_stem_values(&mut self, stem: &Stem, values: &StemValues) -> Result<(), DatabaseError> {
+        if values.is_empty() {
+            self.stems.remove(stem);
+        } else {
+            self.stems.insert(*stem, values.clone());
+        }
+        Ok(())
+    }
+}
+
+/// An experimental [EIP-7864](https://eips.ethereum.org/EIPS/eip-7864) binary state tree.
+///
+/// The tree is updated incrementally from [`HashedPostStateSorted`], see
+/// [`reth_trie_common::binary`] for how it deviates from the EIP.
+#[derive(Debug)]
+pub struct BinaryTree<S> {
+    store: S,
+}
+
+impl<S> BinaryTree<S> {
+    /// Creates a new binary tree backed by the given store.
+    pub const fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    /// Consumes the tree and returns its store.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+impl<S: BinaryTreeStore> BinaryTree<S> {
+    /// Returns the root of the tree.
+    pub fn root(&self) -> Result<B256, DatabaseError> {
+        Ok(self.store.node(&BinaryNodePath::root())?.map(|node| node.hash()).unwrap_or_default())
+    }
+
+    /// Returns a proof for the given key.
+    pub fn proof(&self, key: BinaryTreeKey) -> Result<BinaryTreeProof, DatabaseError> {
+        let mut siblings = Vec::new();
+        let mut path = BinaryNodePath::root();
+        let leaf = loop {
+            match self.store.node(&path)? {
+                None => break BinaryProofLeaf::Empty,
+                Some(BinaryNode::Stem { stem, values_root }) => {
+                    if stem != key.stem {
+                        break BinaryProofLeaf::OtherStem { stem, values_root }
+                    }
+                    let values = self.store.stem_values(&stem)?;
+                    break BinaryProofLeaf::Stem {
+                        value: values.get(key.sub_index),
+                        value_siblings: values.siblings(key.sub_index),
+                    }
+                }
+                Some(BinaryNode::Internal { left, right }) => {
+                    let bit = BinaryNodePath::stem_bit(&key.stem, path.len());
+                    siblings.push(if bit { left } else { right });
+                    path = path.child(bit);
+                }
+            }
+        };
+        Ok(BinaryTreeProof { key, siblings, leaf })
+    }
+}
+
+impl<S: BinaryTreeStoreMut> BinaryTree<S> {
+    /// Applies the hashed state to the tree and returns the new root.
+    ///
+    /// The tree can't enumerate the storage slots of an account, so `storage_slots` must return the
+    /// slots of the given account that have a value before the update. If the storage of an account
+    /// is wiped, these slots are removed together with all storage in the account's stem.
+    pub fn update<F>(
+        &mut self,
+        state: &HashedPostStateSorted,
+        mut storage_slots: F,
+    ) -> Result<B256, DatabaseError>
+    where
+        F: FnMut(B256) -> Result<Vec<B256>, DatabaseError>,
+    {
+        let mut values = Vec::new();
+        for (address, account) in state.accounts() {
+            values
+                .push((BinaryTreeKey::basic_data(*address), account.as_ref().map(basic_data_leaf)));
+            values.push((BinaryTreeKey::code_hash(*address), account.as_ref().map(code_hash_leaf)));
+        }
+        for (address, storage) in state.account_storages() {
+            if storage.is_wiped() {
+                let stem = BinaryTreeKey::basic_data(*address).stem;
+                values.extend(
+                    (HEADER_STORAGE_OFFSET..CODE_OFFSET)
+                        .map(|sub_index| (BinaryTreeKey { stem, sub_index }, None)),
+                );
+                for slot in storage_slots(*address)? {
+                    values.push((BinaryTreeKey::storage_slot(*address, slot), None));
+                }
+            }
+            for (slot, value) in storage.storage_slots_ref() {
+                let value = (!value.is_zero()).then(|| B256::from(*value));
+                values.push((BinaryTreeKey::storage_slot(*address, *slot), value));
+            }
+        }
+        self.insert_values(values)
+    }
+
+    /// Sets or, for `None` values, removes the given keys and returns the new root.
+    pub fn insert_values(
+        &mut self,
+        values: impl IntoIterator<Item = (BinaryTreeKey, Option<B256>)>,
+    ) -> Result<B256, DatabaseError> {
+        let mut by_stem = BTreeMap::<Stem, Vec<(u8, Option<B256>)>>::new();
+        for (key, value) in values {
+            by_stem.entry(key.stem).or_default().push((key.sub_index, value));
+        }
+
+        let mut changes = Vec::with_capacity(by_stem.len());
+        for (stem, updates) in by_stem {
+            let mut stem_values = self.store.stem_values(&stem)?;
+            for (sub_index, value) in updates {
+                stem_values.set(sub_index, value);
+            }
+            self.store.set_stem_values(&stem, &stem_values)?;
+            changes.push((stem, (!stem_values.is_empty()).then(|| stem_values.root())));
+        }
+        debug!(target: "trie::binary", stems = changes.len(), "Updating binary tree");
+
+        let root = self.update_subtree(BinaryNodePath::root(), &changes)?;
+        Ok(root.map(|node| node.hash()).unwrap_or_default())
+    }
+
+    /// Applies the changed stems, sorted by stem, to the subtree at `path` and returns its new
+    /// root node. A `None` values root removes the stem.
+    fn update_subtree(
+        &mut self,
+        path: BinaryNodePath,
+        changes: &[(Stem, Option<B256>)],
+    ) -> Result<Option<BinaryNode>, DatabaseError> {
+        let node = match self.store.node(&path)? {
+            None => {
+                let stems = changes
+                    .iter()
+                    .filter_map(|(stem, root)| root.map(|root| (*stem, root)))
+                    .collect::<Vec<_>>();
+                self.build_subtree(path, &stems)?
+            }
+            Some(BinaryNode::Stem { stem, values_root }) => {
+                let mut stems = changes
+                    .iter()
+                    .filter_map(|(stem, root)| root.map(|root| (*stem, root)))
+                    .collect::<Vec<_>>();
+                if changes.binary_search_by_key(&stem, |(stem, _)| *stem).is_err() {
+                    let index = stems.partition_point(|(other, _)| *other < stem);
+                    stems.insert(index, (stem, values_root));
+                }
+                self.build_subtree(path, &stems)?
+            }
+            Some(BinaryNode::Internal { .. }) => {
+                let depth = path.len();
+                let split =
+                    changes.partition_point(|(stem, _)| !BinaryNodePath::stem_bit(stem, depth));
+                let (left_changes, right_changes) = changes.split_at(split);
+
+                let left_path = path.child(false);
+                let right_path = path.child(true);
+                let left = if left_changes.is_empty() {
+                    self.store.node(&left_path)?
+                } else {
+                    self.update_subtree(left_path, left_changes)?
+                };
+                let right = if right_changes.is_empty() {
+                    self.store.node(&right_path)?
+                } else {
+                    self.update_subtree(right_path, right_changes)?
+                };
+
+                // A stem that is the only one left in the subtree moves up to this path.
+                match (left, right) {
+                    (None, None) => None,
+                    (Some(node @ BinaryNode::Stem { .. }), None) => {
+                        self.store.set_node(&left_path, None)?;
+                        Some(node)
+                    }
+                    (None, Some(node @ BinaryNode::Stem { .. })) => {
+                        self.store.set_node(&right_path, None)?;
+                        Some(node)
+                    }
+                    (left, right) => Some(BinaryNode::Internal {
+                        left: left.map(|node| node.hash()).unwrap_or_default(),
+                        right: right.map(|node| node.hash()).unwrap_or_default(),
+                    }),
+                }
+            }
+        };
+
+        self.store.set_node(&path, node)?;
+        Ok(node)
+    }
+
+    /// Builds the subtree at `path`, which must be empty, from stems sorted by stem and returns
+    /// its root node.
+    fn build_subtree(
+        &mut self,
+        path: BinaryNodePath,
+        stems: &[(Stem, B256)],
+    ) -> Result<Option<BinaryNode>, DatabaseError> {
+        let node = match stems {
+            [] => None,
+            [(stem, values_root)] => {
+                Some(BinaryNode::Stem { stem: *stem, values_root: *values_root })
+            }
+            stems => {
+                let depth = path.len();
+                let split =
+                    stems.partition_point(|(stem, _)| !BinaryNodePath::stem_bit(stem, depth));
+                let (left, right) = stems.split_at(split);
+                let left = self.build_subtree(path.child(false), left)?;
+                let right = self.build_subtree(path.child(true), right)?;
+                Some(BinaryNode::Internal {
+                    left: left.map(|node| node.hash()).unwrap_or_default(),
+                    right: right.map(|node| node.hash()).unwrap_or_default(),
+                })
+            }
+        };
+
+        self.store.set_node(&path, node)?;
+        Ok(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use rand::{rng, Rng};
+    use reth_primitives_traits::Account;
+    use reth_trie_common::HashedStorageSorted;
+
+    /// Computes the root of the given stems from scratch.
+    fn reference_root(stems: &[(Stem, B256)], depth: u8) -> B256 {
+        match stems {
+            [] => B256::ZERO,
+            [(stem, values_root)] => hash_stem(stem, *values_root),
+            stems => {
+                let split =
+                    stems.partition_point(|(stem, _)| !BinaryNodePath::stem_bit(stem, depth));
+                let (left, right) = stems.split_at(split);
+                hash_pair(reference_root(left, depth + 1), reference_root(right, depth + 1))
+            }
+        }
+    }
+
+    fn random_values(count: usize) -> Vec<(BinaryTreeKey, Option<B256>)> {
+        let mut rng = rng();
+        (0..count)
+            .map(|_| {
+                let key = BinaryTreeKey {
+                    stem: Stem::from(rng.random::<[u8; 31]>()),
+                    sub_index: rng.random(),
+                };
+                (key, Some(B256::from(rng.random::<[u8; 32]>())))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn incremental_root_matches_reference() {
+        let values = random_values(200);
+
+        let mut tree = BinaryTree::new(MemoryBinaryTreeStore::default());
+        for chunk in values.chunks(17) {
+            tree.insert_values(chunk.iter().copied()).unwrap();
+        }
+
+        let mut by_stem = BTreeMap::<Stem, StemValues>::new();
+        for (key, value) in &values {
+            by_stem.entry(key.stem).or_default().set(key.sub_index, *value);
+        }
+        let stems =
+            by_stem.into_iter().map(|(stem, values)| (stem, values.root())).collect::<Vec<_>>();
+
+        assert_eq!(tree.root().unwrap(), reference_root(&stems, 0));
+    }
+
+    #[test]
+    fn removal_restores_root() {
+        let values = random_values(50);
+        let (first, second) = values.split_at(25);
+
+        let mut tree = BinaryTree::new(MemoryBinaryTreeStore::default());
+        let root = tree.insert_values(first.iter().copied()).unwrap();
+        assert_ne!(tree.insert_values(second.iter().copied()).unwrap(), root);
+
+        let removed = second.iter().map(|(key, _)| (*key, None));
+        assert_eq!(tree.insert_values(removed).unwrap(), root);
+
+        let removed = first.iter().map(|(key, _)| (*key, None));
+        assert_eq!(tree.insert_values(removed).unwrap(), B256::ZERO);
+        assert!(tree.into_store().is_empty());
+    }
+
+    #[test]
+    fn proofs() {
+        let values = random_values(100);
+        let mut tree = BinaryTree::new(MemoryBinaryTreeStore::default());
+        let root = tree.insert_values(values.iter().copied()).unwrap();
+
+        for (key, value) in values.iter().take(10) {
+            let proof = tree.proof(*key).unwrap();
+            assert_eq!(proof.value(), *value);
+            assert!(proof.verify(root));
+            assert!(!proof.verify(B256::repeat_byte(0xff)));
+        }
+
+        // Absent key under an existing stem.
+        let (key, _) = values[0];
+        let absent = BinaryTreeKey { sub_index: key.sub_index.wrapping_add(1), ..key };
+        let proof = tree.proof(absent).unwrap();
+        if values.iter().all(|(key, _)| *key != absent) {
+            assert_eq!(proof.value(), None);
+        }
+        assert!(proof.verify(root));
+
+        // Absent stem.
+        let stem = Stem::from(rng().random::<[u8; 31]>());
+        let proof = tree.proof(BinaryTreeKey { stem, sub_index: 0 }).unwrap();
+        assert_eq!(proof.value(), None);
+        assert!(matches!(proof.leaf, BinaryProofLeaf::Empty | BinaryProofLeaf::OtherStem { .. }));
+        assert!(proof.verify(root));
+    }
+
+    #[test]
+    fn update_from_hashed_state() {
+        let address = B256::repeat_byte(0x11);
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let slot = B256::repeat_byte(0x22);
+        let state = HashedPostStateSorted::new(
+            vec![(address, Some(account))],
+            [(
+                address,
+                HashedStorageSorted { storage_slots: vec![(slot, U256::from(5))], wiped: false },
+            )]
+            .into_iter()
+            .collect(),
+        );
+
+        let mut tree = BinaryTree::new(MemoryBinaryTreeStore::default());
+        let root = tree.update(&state, |_| Ok(Vec::new())).unwrap();
+
+        let proof = tree.proof(BinaryTreeKey::basic_data(address)).unwrap();
+        assert_eq!(proof.value(), Some(basic_data_leaf(&account)));
+        assert!(proof.verify(root));
+
+        let proof = tree.proof(BinaryTreeKey::storage_slot(address, slot)).unwrap();
+        assert_eq!(proof.value(), Some(B256::from(U256::from(5))));
+        assert!(proof.verify(root));
+
+        let destroyed = HashedPostStateSorted::new(
+            vec![(address, None)],
+            [(
+                address,
+                HashedStorageSorted { storage_slots: vec![(slot, U256::ZERO)], wiped: false },
+            )]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(tree.update(&destroyed, |_| Ok(Vec::new())).unwrap(), B256::ZERO);
+    }
+
+    #[test]
+    fn wiped_storage_is_cleared() {
+        let address = B256::repeat_byte(0x11);
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let header_slot = B256::with_last_byte(1);
+        let main_slot = B256::repeat_byte(0x22);
+        let storage = |wiped, storage_slots| {
+            HashedPostStateSorted::new(
+                vec![(address, Some(account))],
+                [(address, HashedStorageSorted { storage_slots, wiped })].into_iter().collect(),
+            )
+        };
+
+        let mut tree = BinaryTree::new(MemoryBinaryTreeStore::default());
+        let account_only = tree.update(&storage(false, Vec::new()), |_| Ok(Vec::new())).unwrap();
+        tree.update(
+            &storage(false, vec![(header_slot, U256::from(1)), (main_slot, U256::from(2))]),
+            |_| Ok(Vec::new()),
+        )
+        .unwrap();
+
+        // The slots of the wiped storage are removed, including those in the account stem.
+        let root = tree
+            .update(&storage(true, Vec::new()), |wiped| {
+                assert_eq!(wiped, address);
+                Ok(vec![main_slot])
+            })
+            .unwrap();
+        assert_eq!(root, account_only);
+        assert_eq!(
+            tree.proof(BinaryTreeKey::storage_slot(address, header_slot)).unwrap().value(),
+            None
+        );
+        assert_eq!(
+            tree.proof(BinaryTreeKey::storage_slot(address, main_slot)).unwrap().value(),
+            None
+        );
+
+        // Slots written together with the wipe are kept.
+        tree.update(&storage(true, vec![(main_slot, U256::from(3))]), |_| Ok(Vec::new())).unwrap();
+        assert_eq!(
+            tree.proof(BinaryTreeKey::storage_slot(address, main_slot)).unwrap().value(),
+            Some(B256::from(U256::from(3)))
+        );
+    }
+}
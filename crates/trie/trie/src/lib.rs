@@ -55,6 +55,10 @@ pub use progress::{
 /// Trie calculation stats.
 pub mod stats;
 
+/// Experimental binary state tree, computed alongside the Merkle Patricia Trie.
+#[cfg(feature = "binary-tree")]
+pub mod binary;
+
 // re-export for convenience
 pub use reth_trie_common::*;
 
@@ -69,6 +69,14 @@ export const rethCliSidebar: SidebarItem = {
                     text: "reth db copy",
                     link: "/cli/reth/db/copy"
                 },
+                {
+                    text: "reth db backup",
+                    link: "/cli/reth/db/backup"
+                },
+                {
+                    text: "reth db restore",
+                    link: "/cli/reth/db/restore"
+                },
                 {
                     text: "reth db diff",
                     link: "/cli/reth/db/diff"
@@ -305,11 +313,26 @@ export const rethCliSidebar: SidebarItem = {
         },
         {
             text: "reth prune",
-            link: "/cli/reth/prune"
+            link: "/cli/reth/prune",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth prune restore",
+                    link: "/cli/reth/prune/restore"
+                }
+            ]
         },
         {
             text: "reth re-execute",
             link: "/cli/reth/re-execute"
+        },
+        {
+            text: "reth rpc-node",
+            link: "/cli/reth/rpc-node"
+        },
+        {
+            text: "reth t8n",
+            link: "/cli/reth/t8n"
         }
     ]
 };
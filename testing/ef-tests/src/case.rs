@@ -1,6 +1,9 @@
 //! Test case definitions
 
-use crate::result::{CaseResult, Error};
+use crate::{
+    models::ForkSpec,
+    result::{CaseResult, Error},
+};
 use rayon::prelude::*;
 use std::{
     fmt::Debug,
@@ -21,6 +24,11 @@ pub trait Case: Debug + Send + Sync + Sized + 'static {
     /// The file can be assumed to be a valid EF test case as described on <https://ethereum-tests.readthedocs.io/>.
     fn load(path: &Path) -> Result<Self, Error>;
 
+    /// Retain only the tests that target one of the given forks.
+    ///
+    /// This is a no-op for test cases that do not target specific forks.
+    fn retain_forks(&mut self, _forks: &[ForkSpec]) {}
+
     /// Run the test.
     fn run(self) -> Result<(), Error>;
 }
@@ -1,7 +1,7 @@
 //! Test runners for `BlockchainTests` in <https://github.com/ethereum/tests>
 
 use crate::{
-    models::{BlockchainTest, ForkSpec},
+    models::{BlockchainTest, ForkSpec, Header, State},
     Case, Error, Suite,
 };
 use alloy_rlp::Decodable;
@@ -15,9 +15,10 @@ use reth_evm::{execute::Executor, ConfigureEvm};
 use reth_evm_ethereum::EthEvmConfig;
 use reth_primitives_traits::{ParallelBridgeBuffered, RecoveredBlock, SealedBlock};
 use reth_provider::{
-    test_utils::create_test_provider_factory_with_chain_spec, BlockWriter, DatabaseProviderFactory,
-    ExecutionOutcome, HistoryWriter, OriginalValuesKnown, StateWriteConfig, StateWriter,
-    StaticFileProviderFactory, StaticFileSegment, StaticFileWriter, StorageSettingsCache,
+    test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
+    BlockWriter, DatabaseProviderFactory, ExecutionOutcome, HistoryWriter, OriginalValuesKnown,
+    ProviderFactory, StateWriteConfig, StateWriter, StaticFileProviderFactory, StaticFileSegment,
+    StaticFileWriter, StorageSettingsCache,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_trie::{HashedPostState, KeccakKeyHasher, StateRoot};
@@ -33,12 +34,19 @@ use std::{
 #[derive(Debug)]
 pub struct BlockchainTests {
     suite_path: PathBuf,
+    forks: Vec<ForkSpec>,
 }
 
 impl BlockchainTests {
     /// Create a new suite for tests with blockchain tests format.
     pub const fn new(suite_path: PathBuf) -> Self {
-        Self { suite_path }
+        Self { suite_path, forks: Vec::new() }
+    }
+
+    /// Only run the tests for the given forks, or for all forks if empty.
+    pub fn with_forks(mut self, forks: Vec<ForkSpec>) -> Self {
+        self.forks = forks;
+        self
     }
 }
 
@@ -48,6 +56,10 @@ impl Suite for BlockchainTests {
     fn suite_path(&self) -> &Path {
         &self.suite_path
     }
+
+    fn forks(&self) -> &[ForkSpec] {
+        &self.forks
+    }
 }
 
 /// An Ethereum blockchain test.
@@ -61,7 +73,7 @@ pub struct BlockchainTestCase {
 
 impl BlockchainTestCase {
     /// Returns `true` if the fork is not supported.
-    const fn excluded_fork(network: ForkSpec) -> bool {
+    pub(crate) const fn excluded_fork(network: ForkSpec) -> bool {
         matches!(
             network,
             ForkSpec::ByzantiumToConstantinopleAt5 |
@@ -158,6 +170,10 @@ impl Case for BlockchainTestCase {
         })
     }
 
+    fn retain_forks(&mut self, forks: &[ForkSpec]) {
+        self.tests.retain(|_, case| forks.contains(&case.network));
+    }
+
     /// Runs the test cases for the Ethereum Forks test suite.
     ///
     /// # Errors
@@ -197,29 +213,7 @@ fn run_case(case: &BlockchainTest) -> Result<(), Error> {
     let provider = factory.database_provider_rw().unwrap();
 
     // Insert initial test state into the provider.
-    let genesis_block = SealedBlock::<Block>::from_sealed_parts(
-        case.genesis_block_header.clone().into(),
-        Default::default(),
-    )
-    .try_recover()
-    .unwrap();
-
-    provider.insert_block(&genesis_block).map_err(|err| Error::block_failed(0, err))?;
-
-    // Increment block number for receipts static file
-    provider
-        .static_file_provider()
-        .latest_writer(StaticFileSegment::Receipts)
-        .and_then(|mut writer| writer.increment_block(0))
-        .map_err(|err| Error::block_failed(0, err))?;
-
-    let genesis_state = case.pre.clone().into_genesis_state();
-    insert_genesis_state(&provider, genesis_state.iter())
-        .map_err(|err| Error::block_failed(0, err))?;
-    insert_genesis_hashes(&provider, genesis_state.iter())
-        .map_err(|err| Error::block_failed(0, err))?;
-    insert_genesis_history(&provider, genesis_state.iter())
-        .map_err(|err| Error::block_failed(0, err))?;
+    let genesis_block = insert_genesis(&provider, &case.genesis_block_header, &case.pre)?;
 
     // Decode blocks
     let blocks = decode_blocks(&case.blocks)?;
@@ -317,6 +311,40 @@ fn run_case(case: &BlockchainTest) -> Result<(), Error> {
     Ok(())
 }
 
+/// Inserts the genesis block and the pre-state of a test into the database, returning the
+/// genesis block.
+pub(crate) fn insert_genesis(
+    provider: &<ProviderFactory<MockNodeTypesWithDB> as DatabaseProviderFactory>::ProviderRW,
+    genesis_block_header: &Header,
+    pre: &State,
+) -> Result<RecoveredBlock<Block>, Error> {
+    let genesis_block = SealedBlock::<Block>::from_sealed_parts(
+        genesis_block_header.clone().into(),
+        Default::default(),
+    )
+    .try_recover()
+    .unwrap();
+
+    provider.insert_block(&genesis_block).map_err(|err| Error::block_failed(0, err))?;
+
+    // Increment block number for receipts static file
+    provider
+        .static_file_provider()
+        .latest_writer(StaticFileSegment::Receipts)
+        .and_then(|mut writer| writer.increment_block(0))
+        .map_err(|err| Error::block_failed(0, err))?;
+
+    let genesis_state = pre.clone().into_genesis_state();
+    insert_genesis_state(provider, genesis_state.iter())
+        .map_err(|err| Error::block_failed(0, err))?;
+    insert_genesis_hashes(provider, genesis_state.iter())
+        .map_err(|err| Error::block_failed(0, err))?;
+    insert_genesis_history(provider, genesis_state.iter())
+        .map_err(|err| Error::block_failed(0, err))?;
+
+    Ok(genesis_block)
+}
+
 fn decode_blocks(
     test_case_blocks: &[crate::models::Block],
 ) -> Result<Vec<RecoveredBlock<Block>>, Error> {
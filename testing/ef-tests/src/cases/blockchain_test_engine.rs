@@ -0,0 +1,384 @@
+//! Test runners for blockchain tests in the engine format of
+//! <https://github.com/ethereum/execution-spec-tests>
+
+use crate::{
+    cases::blockchain_test::{insert_genesis, should_skip, BlockchainTestCase},
+    models::{BlockchainEngineTest, EngineNewPayload, ForkSpec},
+    Case, Error, Suite,
+};
+use alloy_primitives::B256;
+use alloy_rpc_types_engine::{
+    CancunPayloadFields, ExecutionData, ExecutionPayload, ExecutionPayloadInputV2,
+    ExecutionPayloadSidecar, ExecutionPayloadV1, ExecutionPayloadV3, ExecutionPayloadV4,
+    ForkchoiceState, PayloadStatus, PayloadStatusEnum, PraguePayloadFields,
+};
+use crossbeam_channel::Sender;
+use rayon::prelude::*;
+use reth_engine_primitives::{
+    BeaconEngineMessage, EngineApiValidator, ForkchoiceStatus, NoopInvalidBlockHook,
+};
+use reth_engine_tree::{
+    engine::{EngineApiKind, EngineApiRequest, FromEngine},
+    persistence::PersistenceHandle,
+    tree::{BasicEngineValidator, EngineApiTreeHandler, TreeConfig},
+};
+use reth_ethereum_consensus::EthBeaconConsensus;
+use reth_ethereum_primitives::{Block, EthPrimitives};
+use reth_evm_ethereum::EthEvmConfig;
+use reth_node_ethereum::{EthEngineTypes, EthereumEngineValidator};
+use reth_payload_builder::PayloadBuilderHandle;
+use reth_payload_primitives::{
+    EngineApiMessageVersion, EngineObjectValidationError, PayloadOrAttributes,
+};
+use reth_provider::{
+    providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+    DBProvider, DatabaseProviderFactory, StateProviderFactory,
+};
+use reth_prune::PrunerBuilder;
+use reth_tasks::Runtime;
+use reth_trie_db::ChangesetCache;
+use serde::de::DeserializeOwned;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::sync::{mpsc::unbounded_channel, oneshot};
+
+/// A handler for the blockchain test suite in the engine format.
+#[derive(Debug)]
+pub struct BlockchainEngineTests {
+    suite_path: PathBuf,
+    forks: Vec<ForkSpec>,
+}
+
+impl BlockchainEngineTests {
+    /// Create a new suite for tests with blockchain engine tests format.
+    pub const fn new(suite_path: PathBuf) -> Self {
+        Self { suite_path, forks: Vec::new() }
+    }
+
+    /// Only run the tests for the given forks, or for all forks if empty.
+    pub fn with_forks(mut self, forks: Vec<ForkSpec>) -> Self {
+        self.forks = forks;
+        self
+    }
+}
+
+impl Suite for BlockchainEngineTests {
+    type Case = BlockchainEngineTestCase;
+
+    fn suite_path(&self) -> &Path {
+        &self.suite_path
+    }
+
+    fn forks(&self) -> &[ForkSpec] {
+        &self.forks
+    }
+}
+
+/// An Ethereum blockchain test in the engine format.
+///
+/// The blocks are imported through `engine_newPayload` and `engine_forkchoiceUpdated` messages
+/// sent to an engine tree, the same way a consensus client drives the node.
+#[derive(Debug, PartialEq, Eq)]
+pub struct BlockchainEngineTestCase {
+    /// The tests within this test case.
+    pub tests: BTreeMap<String, BlockchainEngineTest>,
+    /// Whether to skip this test case.
+    pub skip: bool,
+}
+
+impl BlockchainEngineTestCase {
+    /// Execute a single `BlockchainEngineTest`, validating the outcome against the expectations
+    /// encoded in the JSON file.
+    pub fn run_single_case(name: &str, case: &BlockchainEngineTest) -> Result<(), Error> {
+        run_case(case).map_err(|err| Error::Assertion(format!("Test case: {name}\n{err}")))
+    }
+}
+
+impl Case for BlockchainEngineTestCase {
+    fn load(path: &Path) -> Result<Self, Error> {
+        Ok(Self {
+            tests: {
+                let s = fs::read_to_string(path)
+                    .map_err(|error| Error::Io { path: path.into(), error })?;
+                serde_json::from_str(&s)
+                    .map_err(|error| Error::CouldNotDeserialize { path: path.into(), error })?
+            },
+            skip: should_skip(path),
+        })
+    }
+
+    fn retain_forks(&mut self, forks: &[ForkSpec]) {
+        self.tests.retain(|_, case| forks.contains(&case.network));
+    }
+
+    fn run(self) -> Result<(), Error> {
+        if self.skip {
+            return Err(Error::Skipped);
+        }
+
+        self.tests
+            .into_par_iter()
+            .filter(|(_, case)| !BlockchainTestCase::excluded_fork(case.network))
+            .try_for_each(|(name, case)| Self::run_single_case(&name, &case))
+    }
+}
+
+/// Executes a single `BlockchainEngineTest`.
+///
+/// The genesis block and the pre-state are written to a fresh database, on top of which an
+/// engine tree is spawned. Every payload is then sent to the tree with `engine_newPayload`, and
+/// every valid payload is made canonical with `engine_forkchoiceUpdated`. Finally, the canonical
+/// head and the post-state are checked.
+fn run_case(case: &BlockchainEngineTest) -> Result<(), Error> {
+    let chain_spec = case.network.to_chain_spec();
+    let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+
+    let provider = factory.database_provider_rw()?;
+    insert_genesis(&provider, &case.genesis_block_header, &case.pre)?;
+    provider.commit()?;
+
+    let blockchain_db = BlockchainProvider::new(factory.clone())?;
+    let consensus = Arc::new(EthBeaconConsensus::new(chain_spec.clone()));
+    let evm_config = EthEvmConfig::ethereum(chain_spec.clone());
+    let engine_validator = EthereumEngineValidator::new(chain_spec);
+    let tree_config = TreeConfig::default();
+    let changeset_cache = ChangesetCache::new();
+    let runtime = Runtime::test();
+
+    let payload_validator = BasicEngineValidator::new(
+        blockchain_db.clone(),
+        consensus.clone(),
+        evm_config.clone(),
+        engine_validator.clone(),
+        tree_config.clone(),
+        Box::new(NoopInvalidBlockHook::default()),
+        changeset_cache.clone(),
+        runtime.clone(),
+    );
+    let (sync_metrics_tx, _sync_metrics_rx) = unbounded_channel();
+    let persistence = PersistenceHandle::<EthPrimitives>::spawn_service(
+        factory.clone(),
+        PrunerBuilder::default().build_with_provider_factory(factory),
+        sync_metrics_tx,
+    );
+    let (to_payload_service, _payload_service_rx) = unbounded_channel();
+    let (to_tree, _from_tree) = EngineApiTreeHandler::spawn_new(
+        blockchain_db.clone(),
+        consensus,
+        payload_validator,
+        persistence,
+        PayloadBuilderHandle::<EthEngineTypes>::new(to_payload_service),
+        blockchain_db.canonical_in_memory_state(),
+        tree_config,
+        EngineApiKind::Ethereum,
+        evm_config,
+        changeset_cache,
+        runtime,
+    );
+
+    for (payload_index, new_payload) in case.engine_new_payloads.iter().enumerate() {
+        // The payloads do not include the genesis block which is why we have the plus one.
+        let block_number = (payload_index + 1) as u64;
+
+        let response = parse_new_payload(new_payload, &engine_validator)
+            .map_err(|err| Error::block_failed(block_number, err))
+            .and_then(|payload| {
+                let block_hash = payload.payload.block_hash();
+                send_new_payload(&to_tree, block_number, payload).map(|status| (block_hash, status))
+            });
+        let (block_hash, status) = match (response, new_payload.error_code) {
+            (Ok(response), None) => response,
+            // The engine API responded with the expected JSON-RPC error.
+            (Err(_), Some(_)) => continue,
+            (Ok(_), Some(code)) => {
+                return Err(Error::Assertion(format!(
+                    "Expected error code {code} for payload {block_number}, but the payload was accepted"
+                )))
+            }
+            (Err(err), None) => return Err(err),
+        };
+
+        match (status.status, &new_payload.validation_error) {
+            (PayloadStatusEnum::Invalid { .. }, Some(_)) => {}
+            (PayloadStatusEnum::Valid, None) => {
+                let status = send_forkchoice_updated(&to_tree, block_number, block_hash)?;
+                if !status.is_valid() {
+                    return Err(Error::block_failed(
+                        block_number,
+                        Error::Assertion(format!("forkchoice update failed: {status:?}")),
+                    ))
+                }
+            }
+            (PayloadStatusEnum::Invalid { validation_error }, None) => {
+                return Err(Error::block_failed(block_number, Error::Assertion(validation_error)))
+            }
+            (status, Some(expected)) => {
+                return Err(Error::Assertion(format!(
+                    "Expected payload {block_number} to be invalid ({expected}), got {status:?}"
+                )))
+            }
+            (status, None) => {
+                return Err(Error::Assertion(format!(
+                    "Expected payload {block_number} to be valid, got {status:?}"
+                )))
+            }
+        }
+    }
+
+    let head = blockchain_db.canonical_in_memory_state().get_canonical_head().hash();
+    if head != case.lastblockhash {
+        return Err(Error::Assertion(format!(
+            "Expected canonical head {}, got {head}",
+            case.lastblockhash
+        )))
+    }
+
+    if let Some(expected_post_state) = &case.post_state {
+        let state = blockchain_db.latest()?;
+        for (address, account) in expected_post_state {
+            account.assert_state(*address, &*state)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Decodes the parameters of an `engine_newPayload` call, and runs the checks that the engine
+/// API does before handing the payload over to the engine tree.
+///
+/// Failures of these checks are responded to with a JSON-RPC error instead of a payload status.
+fn parse_new_payload(
+    new_payload: &EngineNewPayload,
+    validator: &EthereumEngineValidator,
+) -> Result<ExecutionData, EngineObjectValidationError> {
+    let params = &new_payload.params;
+    let (version, payload) = match new_payload.new_payload_version.as_str() {
+        "1" => (
+            EngineApiMessageVersion::V1,
+            ExecutionData {
+                payload: ExecutionPayload::V1(param::<ExecutionPayloadV1>(params, 0)?),
+                sidecar: ExecutionPayloadSidecar::none(),
+            },
+        ),
+        "2" => (
+            EngineApiMessageVersion::V2,
+            ExecutionData {
+                payload: param::<ExecutionPayloadInputV2>(params, 0)?.into_payload(),
+                sidecar: ExecutionPayloadSidecar::none(),
+            },
+        ),
+        "3" => (
+            EngineApiMessageVersion::V3,
+            ExecutionData {
+                payload: param::<ExecutionPayloadV3>(params, 0)?.into(),
+                sidecar: ExecutionPayloadSidecar::v3(CancunPayloadFields {
+                    versioned_hashes: param(params, 1)?,
+                    parent_beacon_block_root: param(params, 2)?,
+                }),
+            },
+        ),
+        "4" => (
+            EngineApiMessageVersion::V4,
+            ExecutionData {
+                payload: param::<ExecutionPayloadV3>(params, 0)?.into(),
+                sidecar: ExecutionPayloadSidecar::v4(
+                    CancunPayloadFields {
+                        versioned_hashes: param(params, 1)?,
+                        parent_beacon_block_root: param(params, 2)?,
+                    },
+                    PraguePayloadFields { requests: param(params, 3)? },
+                ),
+            },
+        ),
+        "5" => (
+            EngineApiMessageVersion::V5,
+            ExecutionData {
+                payload: param::<ExecutionPayloadV4>(params, 0)?.into(),
+                sidecar: ExecutionPayloadSidecar::v4(
+                    CancunPayloadFields {
+                        versioned_hashes: param(params, 1)?,
+                        parent_beacon_block_root: param(params, 2)?,
+                    },
+                    PraguePayloadFields { requests: param(params, 3)? },
+                ),
+            },
+        ),
+        version => {
+            return Err(EngineObjectValidationError::InvalidParams(
+                format!("unsupported engine_newPayload version {version}").into(),
+            ))
+        }
+    };
+
+    EngineApiValidator::<EthEngineTypes>::validate_version_specific_fields(
+        validator,
+        version,
+        PayloadOrAttributes::from_execution_payload(&payload),
+    )?;
+
+    Ok(payload)
+}
+
+/// Deserializes the parameter at the given index of an `engine_newPayload` call.
+fn param<T: DeserializeOwned>(
+    params: &[serde_json::Value],
+    index: usize,
+) -> Result<T, EngineObjectValidationError> {
+    let param = params.get(index).cloned().ok_or_else(|| {
+        EngineObjectValidationError::InvalidParams(format!("missing parameter {index}").into())
+    })?;
+    serde_json::from_value(param)
+        .map_err(|err| EngineObjectValidationError::InvalidParams(err.into()))
+}
+
+/// Sends the payload to the engine tree, returning its payload status.
+fn send_new_payload(
+    to_tree: &Sender<FromEngine<EngineApiRequest<EthEngineTypes, EthPrimitives>, Block>>,
+    block_number: u64,
+    payload: ExecutionData,
+) -> Result<PayloadStatus, Error> {
+    let (tx, rx) = oneshot::channel();
+    to_tree
+        .send(FromEngine::Request(EngineApiRequest::Beacon(BeaconEngineMessage::NewPayload {
+            payload,
+            tx,
+        })))
+        .map_err(|_| Error::Assertion("engine tree stopped".to_string()))?;
+
+    rx.blocking_recv()
+        .map_err(|err| Error::block_failed(block_number, err))?
+        .map_err(|err| Error::block_failed(block_number, err))
+}
+
+/// Makes the block with the given hash the canonical head.
+fn send_forkchoice_updated(
+    to_tree: &Sender<FromEngine<EngineApiRequest<EthEngineTypes, EthPrimitives>, Block>>,
+    block_number: u64,
+    head_block_hash: B256,
+) -> Result<ForkchoiceStatus, Error> {
+    let (tx, rx) = oneshot::channel();
+    to_tree
+        .send(FromEngine::Request(EngineApiRequest::Beacon(
+            BeaconEngineMessage::ForkchoiceUpdated {
+                state: ForkchoiceState {
+                    head_block_hash,
+                    safe_block_hash: B256::ZERO,
+                    finalized_block_hash: B256::ZERO,
+                },
+                payload_attrs: None,
+                tx,
+            },
+        )))
+        .map_err(|_| Error::Assertion("engine tree stopped".to_string()))?;
+
+    let outcome = rx
+        .blocking_recv()
+        .map_err(|err| Error::block_failed(block_number, err))?
+        .map_err(|err| Error::block_failed(block_number, err))?;
+
+    Ok(outcome.forkchoice_status())
+}
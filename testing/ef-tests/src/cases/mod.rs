@@ -1,3 +1,6 @@
 //! Specific test case handler implementations.
 
 pub mod blockchain_test;
+pub mod blockchain_test_engine;
+pub mod state_test;
+pub mod transaction_test;
@@ -0,0 +1,249 @@
+//! Test runners for `StateTests` in <https://github.com/ethereum/tests>
+
+use crate::{
+    cases::blockchain_test::{should_skip, BlockchainTestCase},
+    models::{ForkSpec, State as TestState, StateTest, StateTestPost},
+    Case, Error, Suite,
+};
+use alloy_consensus::Header;
+use alloy_eips::eip2718::Decodable2718;
+use alloy_primitives::{keccak256, Address, B256, U256};
+use rayon::prelude::*;
+use reth_ethereum_primitives::TransactionSigned;
+use reth_evm::{ConfigureEvm, Evm};
+use reth_evm_ethereum::{revm_spec_by_timestamp_and_block_number, EthEvmConfig};
+use reth_primitives_traits::{Account, Recovered, SignerRecoverable};
+use reth_revm::db::{states::bundle_state::BundleRetention, BundleState, CacheDB, EmptyDB, State};
+use reth_trie::root::{state_root_unhashed, storage_root_unhashed};
+use revm::{bytecode::Bytecode, primitives::hardfork::SpecId, state::AccountInfo, DatabaseCommit};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A handler for the state test suite.
+#[derive(Debug)]
+pub struct StateTests {
+    suite_path: PathBuf,
+    forks: Vec<ForkSpec>,
+}
+
+impl StateTests {
+    /// Create a new suite for tests with state tests format.
+    pub const fn new(suite_path: PathBuf) -> Self {
+        Self { suite_path, forks: Vec::new() }
+    }
+
+    /// Only run the tests for the given forks, or for all forks if empty.
+    pub fn with_forks(mut self, forks: Vec<ForkSpec>) -> Self {
+        self.forks = forks;
+        self
+    }
+}
+
+impl Suite for StateTests {
+    type Case = StateTestCase;
+
+    fn suite_path(&self) -> &Path {
+        &self.suite_path
+    }
+
+    fn forks(&self) -> &[ForkSpec] {
+        &self.forks
+    }
+}
+
+/// An Ethereum state test.
+///
+/// Every state test executes a single transaction on top of a pre-state, and lists the expected
+/// post-state root and logs hash for every fork and variation of the transaction.
+#[derive(Debug, PartialEq, Eq)]
+pub struct StateTestCase {
+    /// The tests within this test case.
+    pub tests: BTreeMap<String, StateTest>,
+    /// Whether to skip this test case.
+    pub skip: bool,
+}
+
+impl StateTestCase {
+    /// Execute the transaction of a single post entry of a `StateTest`, validating the outcome
+    /// against the expectations encoded in the JSON file.
+    pub fn run_single_case(
+        name: &str,
+        case: &StateTest,
+        fork: ForkSpec,
+        post: &StateTestPost,
+    ) -> Result<(), Error> {
+        match (run_case(case, fork, post), &post.expect_exception) {
+            (Ok(()), None) | (Err(Error::TransactionFailed(_)), Some(_)) => Ok(()),
+            (Ok(()), Some(exception)) => Err(Error::Assertion(format!(
+                "Test case: {name}\nFork: {fork:?}\nIndexes: {:?}\nExpected exception {exception}, but the transaction succeeded",
+                post.indexes
+            ))),
+            (Err(err), _) => Err(Error::Assertion(format!(
+                "Test case: {name}\nFork: {fork:?}\nIndexes: {:?}\n{err}",
+                post.indexes
+            ))),
+        }
+    }
+}
+
+impl Case for StateTestCase {
+    fn load(path: &Path) -> Result<Self, Error> {
+        Ok(Self {
+            tests: {
+                let s = fs::read_to_string(path)
+                    .map_err(|error| Error::Io { path: path.into(), error })?;
+                serde_json::from_str(&s)
+                    .map_err(|error| Error::CouldNotDeserialize { path: path.into(), error })?
+            },
+            skip: should_skip(path),
+        })
+    }
+
+    fn retain_forks(&mut self, forks: &[ForkSpec]) {
+        for case in self.tests.values_mut() {
+            case.post.retain(|fork, _| forks.contains(fork));
+        }
+    }
+
+    fn run(self) -> Result<(), Error> {
+        if self.skip {
+            return Err(Error::Skipped);
+        }
+
+        self.tests.into_par_iter().try_for_each(|(name, case)| {
+            case.post
+                .iter()
+                .filter(|(fork, _)| !BlockchainTestCase::excluded_fork(**fork))
+                .flat_map(|(fork, posts)| posts.iter().map(move |post| (*fork, post)))
+                .try_for_each(|(fork, post)| Self::run_single_case(&name, &case, fork, post))
+        })
+    }
+}
+
+/// Executes the transaction of a single post entry of a `StateTest` on top of its pre-state.
+///
+/// Returns:
+/// - `Ok(())` if the transaction executed and both the post-state root and the logs hash match.
+/// - `Err(Error::TransactionFailed)` if the transaction could not be decoded or was rejected.
+/// - `Err(Error)` for any other mismatch.
+fn run_case(case: &StateTest, fork: ForkSpec, post: &StateTestPost) -> Result<(), Error> {
+    let chain_spec = fork.to_chain_spec();
+    let header = Header::from(&case.env);
+    let spec =
+        revm_spec_by_timestamp_and_block_number(&*chain_spec, header.timestamp, header.number);
+    let tx = decode_transaction(&post.txbytes, spec)?;
+
+    let evm_config = EthEvmConfig::ethereum(chain_spec);
+    let evm_env = evm_config.evm_env(&header).expect("EVM environment is infallible");
+    let mut db =
+        State::builder().with_database(pre_state_db(&case.pre)).with_bundle_update().build();
+
+    let result = evm_config
+        .evm_with_env(&mut db, evm_env)
+        .transact(evm_config.tx_env(&tx))
+        .map_err(|err| Error::TransactionFailed(err.to_string()))?;
+
+    let logs_hash = keccak256(alloy_rlp::encode(result.result.logs()));
+    if logs_hash != post.logs {
+        return Err(Error::Assertion(format!(
+            "logs hash mismatch: expected {}, got {logs_hash}",
+            post.logs
+        )))
+    }
+
+    db.commit(result.state);
+    db.merge_transitions(BundleRetention::Reverts);
+    let state_root = post_state_root(&case.pre, db.take_bundle());
+    if state_root != post.hash {
+        return Err(Error::Assertion(format!(
+            "state root mismatch: expected {}, got {state_root}",
+            post.hash
+        )))
+    }
+
+    Ok(())
+}
+
+/// Decodes the transaction and recovers its signer with the signature rules of the given spec.
+pub(crate) fn decode_transaction(
+    txbytes: &[u8],
+    spec: SpecId,
+) -> Result<Recovered<TransactionSigned>, Error> {
+    let tx = TransactionSigned::decode_2718_exact(txbytes)
+        .map_err(|err| Error::TransactionFailed(err.to_string()))?;
+
+    // Signatures with a high `s` value are only invalid since EIP-2.
+    let signer = if spec.is_enabled_in(SpecId::HOMESTEAD) {
+        tx.recover_signer()
+    } else {
+        tx.recover_signer_unchecked()
+    }
+    .map_err(|err| Error::TransactionFailed(err.to_string()))?;
+
+    Ok(Recovered::new_unchecked(tx, signer))
+}
+
+/// Returns an in-memory database holding the pre-state of a test.
+fn pre_state_db(pre: &TestState) -> CacheDB<EmptyDB> {
+    let mut db = CacheDB::new(EmptyDB::default());
+    for (address, account) in pre.iter() {
+        let code = Bytecode::new_raw(account.code.clone());
+        db.insert_account_info(
+            *address,
+            AccountInfo::new(account.balance, account.nonce.to(), code.hash_slow(), code),
+        );
+        for (slot, value) in &account.storage {
+            db.insert_account_storage(*address, *slot, *value)
+                .expect("empty database is infallible");
+        }
+    }
+    db
+}
+
+/// Computes the state root of the pre-state with the changes of the bundle applied.
+fn post_state_root(pre: &TestState, bundle: BundleState) -> B256 {
+    let mut accounts = pre
+        .iter()
+        .map(|(address, account)| {
+            let info = Account {
+                nonce: account.nonce.to(),
+                balance: account.balance,
+                bytecode_hash: (!account.code.is_empty()).then(|| keccak256(&account.code)),
+            };
+            let storage = account
+                .storage
+                .iter()
+                .map(|(slot, value)| (B256::from(*slot), *value))
+                .collect::<BTreeMap<_, _>>();
+            (*address, (info, storage))
+        })
+        .collect::<BTreeMap<Address, (Account, BTreeMap<B256, U256>)>>();
+
+    for (address, account) in bundle.state {
+        let Some(info) = &account.info else {
+            accounts.remove(&address);
+            continue
+        };
+
+        let (current, storage) = accounts.entry(address).or_default();
+        *current = Account::from(info);
+        if account.was_destroyed() {
+            storage.clear();
+        }
+        storage.extend(
+            account.storage.iter().map(|(slot, value)| (B256::from(*slot), value.present_value)),
+        );
+    }
+
+    state_root_unhashed(accounts.into_iter().map(|(address, (account, storage))| {
+        (
+            address,
+            account.into_trie_account(storage_root_unhashed(
+                storage.into_iter().filter(|(_, value)| !value.is_zero()),
+            )),
+        )
+    }))
+}
@@ -0,0 +1,232 @@
+//! Test runners for `TransactionTests` in <https://github.com/ethereum/tests>
+
+use crate::{
+    cases::{
+        blockchain_test::{should_skip, BlockchainTestCase},
+        state_test::decode_transaction,
+    },
+    models::{ForkSpec, TransactionTest, TransactionTestResult},
+    Case, Error, Suite,
+};
+use alloy_consensus::{transaction::TxHashRef, Transaction};
+use alloy_eips::eip4844::VERSIONED_HASH_VERSION_KZG;
+use rayon::prelude::*;
+use reth_chainspec::EthChainSpec;
+use reth_ethereum_primitives::{TransactionSigned, TxType};
+use reth_evm_ethereum::revm_spec_by_timestamp_and_block_number;
+use reth_primitives_traits::Recovered;
+use revm::{
+    interpreter::gas::calculate_initial_tx_gas,
+    primitives::{eip3860::MAX_INITCODE_SIZE, eip7825::TX_GAS_LIMIT_CAP, hardfork::SpecId},
+};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A handler for the transaction test suite.
+#[derive(Debug)]
+pub struct TransactionTests {
+    suite_path: PathBuf,
+    forks: Vec<ForkSpec>,
+}
+
+impl TransactionTests {
+    /// Create a new suite for tests with transaction tests format.
+    pub const fn new(suite_path: PathBuf) -> Self {
+        Self { suite_path, forks: Vec::new() }
+    }
+
+    /// Only run the tests for the given forks, or for all forks if empty.
+    pub fn with_forks(mut self, forks: Vec<ForkSpec>) -> Self {
+        self.forks = forks;
+        self
+    }
+}
+
+impl Suite for TransactionTests {
+    type Case = TransactionTestCase;
+
+    fn suite_path(&self) -> &Path {
+        &self.suite_path
+    }
+
+    fn forks(&self) -> &[ForkSpec] {
+        &self.forks
+    }
+}
+
+/// An Ethereum transaction test.
+///
+/// Every transaction test checks whether an encoded transaction is valid under each fork, and if
+/// it is, that its hash and sender match.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TransactionTestCase {
+    /// The tests within this test case.
+    pub tests: BTreeMap<String, TransactionTest>,
+    /// Whether to skip this test case.
+    pub skip: bool,
+}
+
+impl TransactionTestCase {
+    /// Validate the transaction of a `TransactionTest` under a single fork against the
+    /// expectations encoded in the JSON file.
+    pub fn run_single_case(
+        name: &str,
+        case: &TransactionTest,
+        fork: ForkSpec,
+        expected: &TransactionTestResult,
+    ) -> Result<(), Error> {
+        match (validate_transaction(&case.txbytes, fork), &expected.exception) {
+            (Ok(tx), None) => {
+                if expected.hash.is_some_and(|hash| hash != *tx.tx_hash()) {
+                    return Err(Error::Assertion(format!(
+                        "Test case: {name}\nFork: {fork:?}\nExpected hash {:?}, got {}",
+                        expected.hash,
+                        tx.tx_hash()
+                    )))
+                }
+                if expected.sender.is_some_and(|sender| sender != tx.signer()) {
+                    return Err(Error::Assertion(format!(
+                        "Test case: {name}\nFork: {fork:?}\nExpected sender {:?}, got {}",
+                        expected.sender,
+                        tx.signer()
+                    )))
+                }
+                Ok(())
+            }
+            (Err(Error::TransactionFailed(_)), Some(_)) => Ok(()),
+            (Ok(_), Some(exception)) => Err(Error::Assertion(format!(
+                "Test case: {name}\nFork: {fork:?}\nExpected exception {exception}, but the transaction is valid",
+            ))),
+            (Err(err), _) => {
+                Err(Error::Assertion(format!("Test case: {name}\nFork: {fork:?}\n{err}")))
+            }
+        }
+    }
+}
+
+impl Case for TransactionTestCase {
+    fn load(path: &Path) -> Result<Self, Error> {
+        Ok(Self {
+            tests: {
+                let s = fs::read_to_string(path)
+                    .map_err(|error| Error::Io { path: path.into(), error })?;
+                serde_json::from_str(&s)
+                    .map_err(|error| Error::CouldNotDeserialize { path: path.into(), error })?
+            },
+            skip: should_skip(path),
+        })
+    }
+
+    fn retain_forks(&mut self, forks: &[ForkSpec]) {
+        for case in self.tests.values_mut() {
+            case.result.retain(|fork, _| forks.contains(fork));
+        }
+    }
+
+    fn run(self) -> Result<(), Error> {
+        if self.skip {
+            return Err(Error::Skipped);
+        }
+
+        self.tests.into_par_iter().try_for_each(|(name, case)| {
+            case.result
+                .iter()
+                .filter(|(fork, _)| !BlockchainTestCase::excluded_fork(**fork))
+                .try_for_each(|(fork, expected)| {
+                    Self::run_single_case(&name, &case, *fork, expected)
+                })
+        })
+    }
+}
+
+/// Decodes the transaction and checks that it is valid under the rules of the given fork,
+/// without any state.
+///
+/// Returns `Err(Error::TransactionFailed)` if the transaction is invalid.
+fn validate_transaction(
+    txbytes: &[u8],
+    fork: ForkSpec,
+) -> Result<Recovered<TransactionSigned>, Error> {
+    let chain_spec = fork.to_chain_spec();
+    let spec = revm_spec_by_timestamp_and_block_number(&*chain_spec, 0, 0);
+    let tx = decode_transaction(txbytes, spec)?;
+    let invalid = |reason: &str| Err(Error::TransactionFailed(reason.to_string()));
+
+    let activation = match tx.tx_type() {
+        TxType::Legacy => SpecId::FRONTIER,
+        TxType::Eip2930 => SpecId::BERLIN,
+        TxType::Eip1559 => SpecId::LONDON,
+        TxType::Eip4844 => SpecId::CANCUN,
+        TxType::Eip7702 => SpecId::PRAGUE,
+    };
+    if !spec.is_enabled_in(activation) {
+        return invalid("transaction type not supported")
+    }
+
+    if let Some(chain_id) = tx.chain_id() {
+        if tx.tx_type() == TxType::Legacy && !spec.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
+            return invalid("replay protected transaction before EIP-155")
+        }
+        if chain_id != chain_spec.chain().id() {
+            return invalid("invalid chain id")
+        }
+    }
+
+    // EIP-2681
+    if tx.nonce() == u64::MAX {
+        return invalid("nonce overflow")
+    }
+
+    if tx.max_priority_fee_per_gas().is_some_and(|tip| tip > tx.max_fee_per_gas()) {
+        return invalid("priority fee greater than max fee")
+    }
+
+    if spec.is_enabled_in(SpecId::SHANGHAI) &&
+        tx.is_create() &&
+        tx.input().len() > MAX_INITCODE_SIZE
+    {
+        return invalid("initcode size limit exceeded")
+    }
+
+    if spec.is_enabled_in(SpecId::OSAKA) && tx.gas_limit() > TX_GAS_LIMIT_CAP {
+        return invalid("gas limit above cap")
+    }
+
+    if let Some(blob_hashes) = tx.blob_versioned_hashes() {
+        if blob_hashes.is_empty() {
+            return invalid("blob transaction without blobs")
+        }
+        if blob_hashes.iter().any(|hash| hash[0] != VERSIONED_HASH_VERSION_KZG) {
+            return invalid("invalid blob versioned hash")
+        }
+        if chain_spec
+            .blob_params_at_timestamp(0)
+            .is_some_and(|params| blob_hashes.len() as u64 > params.max_blobs_per_tx)
+        {
+            return invalid("too many blobs")
+        }
+    }
+
+    if tx.authorization_list().is_some_and(|authorizations| authorizations.is_empty()) {
+        return invalid("empty authorization list")
+    }
+
+    let gas = calculate_initial_tx_gas(
+        spec,
+        tx.input(),
+        tx.is_create(),
+        tx.access_list().map(|list| list.len()).unwrap_or_default() as u64,
+        tx.access_list()
+            .map(|list| list.iter().map(|item| item.storage_keys.len()).sum::<usize>())
+            .unwrap_or_default() as u64,
+        tx.authorization_list().map(|list| list.len()).unwrap_or_default() as u64,
+    );
+    if tx.gas_limit() < gas.initial_total_gas() || tx.gas_limit() < gas.floor_gas {
+        return invalid("intrinsic gas too low")
+    }
+
+    Ok(tx)
+}
@@ -8,11 +8,13 @@ use alloy_primitives::{keccak256, Address, Bloom, Bytes, B256, B64, U256};
 use reth_chainspec::{ChainSpec, ChainSpecBuilder, EthereumHardfork, ForkCondition};
 use reth_db_api::{cursor::DbDupCursorRO, tables, transaction::DbTx};
 use reth_primitives_traits::SealedHeader;
+use reth_provider::{AccountReader, StateProvider};
 use revm::primitives::HashMap;
-use serde::Deserialize;
+use serde::{de::IntoDeserializer, Deserialize};
 use std::{
     collections::BTreeMap,
     ops::Deref,
+    str::FromStr,
     sync::{Arc, OnceLock, RwLock},
 };
 
@@ -40,6 +42,149 @@ pub struct BlockchainTest {
     pub seal_engine: SealEngine,
 }
 
+/// The definition of a blockchain test in the engine format.
+///
+/// Instead of RLP encoded blocks, the blocks are given as the parameters of the
+/// `engine_newPayload` calls that import them.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockchainEngineTest {
+    /// Genesis block header.
+    pub genesis_block_header: Header,
+    /// The `engine_newPayload` calls.
+    pub engine_new_payloads: Vec<EngineNewPayload>,
+    /// The expected post state.
+    pub post_state: Option<BTreeMap<Address, Account>>,
+    /// The test pre-state.
+    pub pre: State,
+    /// Hash of the best block.
+    pub lastblockhash: B256,
+    /// Network spec.
+    pub network: ForkSpec,
+}
+
+/// An `engine_newPayload` call in an Ethereum blockchain engine test.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineNewPayload {
+    /// The parameters of the call, as they would be sent over JSON-RPC.
+    pub params: Vec<serde_json::Value>,
+    /// The version of the `engine_newPayload` method, e.g. `"3"`.
+    pub new_payload_version: String,
+    /// The version of the `engine_forkchoiceUpdated` method.
+    pub forkchoice_updated_version: String,
+    /// If the payload should be rejected as invalid, `validation_error` is `Some`.
+    /// Its contents detail the reason for the failure.
+    pub validation_error: Option<String>,
+    /// If the call should fail with a JSON-RPC error, the expected error code.
+    pub error_code: Option<i64>,
+}
+
+/// The definition of a state test.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateTest {
+    /// The block environment the transaction is executed in.
+    pub env: StateTestEnv,
+    /// The test pre-state.
+    pub pre: State,
+    /// The expected outcome of the transaction, per fork.
+    pub post: BTreeMap<ForkSpec, Vec<StateTestPost>>,
+}
+
+/// The block environment of a state test.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateTestEnv {
+    /// Coinbase.
+    pub current_coinbase: Address,
+    /// Difficulty.
+    pub current_difficulty: U256,
+    /// Gas limit.
+    pub current_gas_limit: U256,
+    /// Block number.
+    pub current_number: U256,
+    /// Timestamp.
+    pub current_timestamp: U256,
+    /// Base fee per gas.
+    pub current_base_fee: Option<U256>,
+    /// Randomness value, replacing the difficulty after the merge.
+    pub current_random: Option<B256>,
+    /// Excess blob gas.
+    pub current_excess_blob_gas: Option<U256>,
+}
+
+impl From<&StateTestEnv> for RethHeader {
+    fn from(env: &StateTestEnv) -> Self {
+        Self {
+            beneficiary: env.current_coinbase,
+            difficulty: env.current_difficulty,
+            gas_limit: env.current_gas_limit.to(),
+            number: env.current_number.to(),
+            timestamp: env.current_timestamp.to(),
+            base_fee_per_gas: env.current_base_fee.map(|fee| fee.to()),
+            mix_hash: env.current_random.unwrap_or_default(),
+            excess_blob_gas: env.current_excess_blob_gas.map(|gas| gas.to()),
+            ..Default::default()
+        }
+    }
+}
+
+/// The expected outcome of a state test transaction for a single fork.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateTestPost {
+    /// The expected post state root.
+    pub hash: B256,
+    /// The expected hash of the RLP encoded logs.
+    pub logs: B256,
+    /// The indexes of the data, gas and value of the transaction.
+    pub indexes: StateTestIndexes,
+    /// The EIP-2718 encoded transaction.
+    pub txbytes: Bytes,
+    /// If the transaction should be rejected, `expect_exception` is `Some`.
+    /// Its contents detail the reason for the failure.
+    pub expect_exception: Option<String>,
+    /// The expected post state.
+    pub state: Option<BTreeMap<Address, Account>>,
+}
+
+/// The indexes of a state test transaction into the transaction template.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+pub struct StateTestIndexes {
+    /// Index of the data.
+    pub data: usize,
+    /// Index of the gas limit.
+    pub gas: usize,
+    /// Index of the value.
+    pub value: usize,
+}
+
+/// The definition of a transaction test.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTest {
+    /// The EIP-2718 encoded transaction.
+    pub txbytes: Bytes,
+    /// The expected outcome, per fork.
+    pub result: BTreeMap<ForkSpec, TransactionTestResult>,
+}
+
+/// The expected outcome of a transaction test for a single fork.
+#[derive(Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionTestResult {
+    /// The transaction hash, if the transaction is valid.
+    pub hash: Option<B256>,
+    /// The transaction sender, if the transaction is valid.
+    pub sender: Option<Address>,
+    /// The intrinsic gas of the transaction.
+    pub intrinsic_gas: Option<U256>,
+    /// If the transaction is invalid, `exception` is `Some`.
+    /// Its contents detail the reason.
+    pub exception: Option<String>,
+}
+
 /// A block header in an Ethereum blockchain test.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
@@ -261,6 +406,37 @@ impl Account {
 
         Ok(())
     }
+
+    /// Check that the account matches the given state.
+    ///
+    /// In case of a mismatch, `Err(Error::Assertion)` is returned.
+    pub fn assert_state(&self, address: Address, state: &dyn StateProvider) -> Result<(), Error> {
+        let account = state.basic_account(&address)?.ok_or_else(|| {
+            Error::Assertion(format!(
+                "Expected account ({address}) is missing from state: {self:?}"
+            ))
+        })?;
+
+        assert_equal(self.balance, account.balance, "Balance does not match")?;
+        assert_equal(self.nonce.to(), account.nonce, "Nonce does not match")?;
+
+        if let Some(bytecode_hash) = account.bytecode_hash {
+            assert_equal(keccak256(&self.code), bytecode_hash, "Bytecode does not match")?;
+        } else {
+            assert_equal(
+                self.code.is_empty(),
+                true,
+                "Expected empty bytecode, got bytecode in state.",
+            )?;
+        }
+
+        for (slot, value) in &self.storage {
+            let stored = state.storage(address, B256::new(slot.to_be_bytes()))?.unwrap_or_default();
+            assert_equal(*value, stored, &format!("Storage for slot {slot:?} does not match"))?;
+        }
+
+        Ok(())
+    }
 }
 
 /// Fork specification.
@@ -399,6 +575,14 @@ impl ForkSpec {
     }
 }
 
+impl FromStr for ForkSpec {
+    type Err = serde::de::value::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::deserialize(s.into_deserializer())
+    }
+}
+
 /// Possible seal engines.
 #[derive(Debug, PartialEq, Eq, Default, Deserialize)]
 pub enum SealEngine {
@@ -509,4 +693,11 @@ mod tests {
         let res = serde_json::from_str::<Vec<Transaction>>(test);
         assert!(res.is_ok(), "Failed to deserialize transaction with error: {res:?}");
     }
+
+    #[test]
+    fn fork_spec_from_str() {
+        assert_eq!("Cancun".parse::<ForkSpec>().unwrap(), ForkSpec::Cancun);
+        assert_eq!("Paris".parse::<ForkSpec>().unwrap(), ForkSpec::Merge);
+        assert!("Byzantine".parse::<ForkSpec>().is_err());
+    }
 }
@@ -27,6 +27,9 @@ pub enum Error {
         #[source]
         err: Box<dyn std::error::Error + Send + Sync>,
     },
+    /// A transaction could not be decoded or was rejected before execution.
+    #[error("transaction failed: {0}")]
+    TransactionFailed(String),
     /// An IO error occurred
     #[error("an error occurred interacting with the file system at {path}: {error}")]
     Io {
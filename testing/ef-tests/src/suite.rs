@@ -2,6 +2,7 @@
 
 use crate::{
     case::{Case, Cases},
+    models::ForkSpec,
     result::assert_tests_pass,
 };
 use std::path::{Path, PathBuf};
@@ -15,6 +16,11 @@ pub trait Suite {
     /// The path to the test suite directory.
     fn suite_path(&self) -> &Path;
 
+    /// The forks to run tests for, or an empty slice to run tests for all forks.
+    fn forks(&self) -> &[ForkSpec] {
+        &[]
+    }
+
     /// Run all test cases in the suite.
     fn run(&self) {
         let suite_path = self.suite_path();
@@ -42,7 +48,10 @@ pub trait Suite {
         let test_cases = find_all_files_with_extension(&suite_path, ".json")
             .into_iter()
             .map(|test_case_path| {
-                let case = Self::Case::load(&test_case_path).expect("test case should load");
+                let mut case = Self::Case::load(&test_case_path).expect("test case should load");
+                if !self.forks().is_empty() {
+                    case.retain_forks(self.forks());
+                }
                 (test_case_path, case)
             })
             .collect();
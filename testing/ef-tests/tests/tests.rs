@@ -1,7 +1,13 @@
 #![allow(missing_docs)]
 #![cfg(feature = "ef-tests")]
 
-use ef_tests::{cases::blockchain_test::BlockchainTests, suite::Suite};
+use ef_tests::{
+    cases::{
+        blockchain_test::BlockchainTests, blockchain_test_engine::BlockchainEngineTests,
+        state_test::StateTests, transaction_test::TransactionTests,
+    },
+    suite::Suite,
+};
 use std::path::PathBuf;
 
 macro_rules! general_state_test {
@@ -114,3 +120,44 @@ fn eest_fixtures() {
 
     BlockchainTests::new(suite_path).run();
 }
+
+#[test]
+fn eest_engine_fixtures() {
+    reth_tracing::init_test_tracing();
+    let suite_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("execution-spec-tests")
+        .join("blockchain_tests_engine");
+
+    if !suite_path.exists() {
+        return;
+    }
+
+    BlockchainEngineTests::new(suite_path).run();
+}
+
+#[test]
+fn eest_state_fixtures() {
+    reth_tracing::init_test_tracing();
+    let suite_path =
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("execution-spec-tests").join("state_tests");
+
+    if !suite_path.exists() {
+        return;
+    }
+
+    StateTests::new(suite_path).run();
+}
+
+#[test]
+fn eest_transaction_fixtures() {
+    reth_tracing::init_test_tracing();
+    let suite_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("execution-spec-tests")
+        .join("transaction_tests");
+
+    if !suite_path.exists() {
+        return;
+    }
+
+    TransactionTests::new(suite_path).run();
+}
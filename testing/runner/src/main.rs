@@ -2,16 +2,47 @@
 use std::path::PathBuf;
 
 use clap::Parser;
-use ef_tests::{cases::blockchain_test::BlockchainTests, Suite};
+use ef_tests::{
+    cases::{
+        blockchain_test::BlockchainTests, blockchain_test_engine::BlockchainEngineTests,
+        state_test::StateTests, transaction_test::TransactionTests,
+    },
+    models::ForkSpec,
+    Suite,
+};
 
 /// Command-line arguments for the test runner.
 #[derive(Debug, Parser)]
 pub struct TestRunnerCommand {
     /// Path to the test suite
     suite_path: PathBuf,
+
+    /// Only run the tests for the given forks, e.g. `--fork Prague --fork Osaka`.
+    ///
+    /// Runs the tests for all forks if not set.
+    #[arg(long = "fork", value_name = "FORK")]
+    forks: Vec<ForkSpec>,
 }
 
 fn main() {
     let cmd = TestRunnerCommand::parse();
-    BlockchainTests::new(cmd.suite_path.join("blockchain_tests")).run();
+    BlockchainTests::new(cmd.suite_path.join("blockchain_tests"))
+        .with_forks(cmd.forks.clone())
+        .run();
+
+    // These formats are not part of every fixture release.
+    let engine_tests_path = cmd.suite_path.join("blockchain_tests_engine");
+    if engine_tests_path.exists() {
+        BlockchainEngineTests::new(engine_tests_path).with_forks(cmd.forks.clone()).run();
+    }
+
+    let state_tests_path = cmd.suite_path.join("state_tests");
+    if state_tests_path.exists() {
+        StateTests::new(state_tests_path).with_forks(cmd.forks.clone()).run();
+    }
+
+    let transaction_tests_path = cmd.suite_path.join("transaction_tests");
+    if transaction_tests_path.exists() {
+        TransactionTests::new(transaction_tests_path).with_forks(cmd.forks).run();
+    }
 }